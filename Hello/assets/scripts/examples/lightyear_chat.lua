@@ -315,7 +315,7 @@ if IS_CLIENT_MODE then
         local mouse_events = world:query_events("bevy_input::mouse::MouseButtonInput")
         if mouse_events and input_entity_ref then
             for _, event in ipairs(mouse_events) do
-                if event.state and event.state.Pressed and event.button and event.button.Left then
+                if event.state == "Pressed" and event.button == "Left" then
                     -- Since Node bounds may not be accessible, use hardcoded bounds from spawn
                     local left = 20
                     local top = 30 + CHAT_HEIGHT
@@ -340,21 +340,23 @@ if IS_CLIENT_MODE then
         if input_focused then
             local key_events = world:query_events("bevy_input::keyboard::KeyboardInput")
             for _, event in ipairs(key_events) do
-                if event.state and event.state.Pressed then
+                if event.state == "Pressed" then
                     local key = event.key_code
-                    if key.Enter or key.Return then
+                    if key == "Enter" or key == "NumpadEnter" then
                         send_current_message(world)
-                    elseif key.Backspace or key.Back then
+                    elseif key == "Backspace" then
                         if #current_input > 0 then
                             current_input = current_input:sub(1, -2)
                             update_input_display(world)
                         end
-                    elseif key.Space then
+                    elseif key == "Space" then
                         current_input = current_input .. " "
                         is_typing = true
                         update_input_display(world)
-                    elseif event.text and #event.text.Some == 1 then
-                        current_input = current_input .. event.text.Some[1]:sub(2,2)
+                    elseif type(event.text) == "string" and #event.text:gsub('^"(.*)"$', '%1') == 1 then
+                        -- Option<SmolStr> reads as the (debug-quoted) text or nil
+                        local text = event.text:gsub('^"(.*)"$', '%1')
+                        current_input = current_input .. text
                         is_typing = true
                         update_input_display(world)
                     end
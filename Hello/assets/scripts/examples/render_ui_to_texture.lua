@@ -279,13 +279,13 @@ function drive_diegetic_pointer(world)
     -- Handle mouse button events for press/release
     local button_events = world:read_events("MouseButtonInput")
     for _, event in ipairs(button_events) do
-        if event.button and event.button.Left then
+        if event.button == "Left" then
             local action = nil
-            if event.state.Pressed then
+            if event.state == "Pressed" then
                 -- PointerButton::Primary is a unit variant, so just use the string directly
                 -- The reflection code should handle this as a tuple variant containing a unit enum
                 action = { Press = "Primary" }
-            elseif event.state.Released then
+            elseif event.state == "Released" then
                 action = { Release = "Primary" }
             end
             
@@ -869,11 +869,11 @@ function FileBrowser:update(world)
     for _, e in ipairs(keyboard_events) do
         -- Check for Ctrl key (ControlLeft or ControlRight)
         local key = e.key_code
-        if key and (key.ControlLeft or key.ControlRight) then
+        if key == "ControlLeft" or key == "ControlRight" then
             local state = e.state
-            if state and state.Pressed then
+            if state == "Pressed" then
                 self.ctrl_held = true
-            elseif state and state.Released then
+            elseif state == "Released" then
                 self.ctrl_held = false
             end
         end
@@ -975,8 +975,8 @@ function FileBrowser:process_pending_external_drops(world)
     for _, row_entity in ipairs(rows) do
         local interaction = row_entity:get("Interaction")
         local row_data = row_entity:get("LuaRowData")
-        -- Check for Hovered state (table format: {Hovered = true} or {None = true})
-        if interaction and interaction.Hovered then
+        -- Unit enum variants read as strings ("Hovered", "Pressed", "None")
+        if interaction == "Hovered" then
             if row_data then
                 if row_data.is_directory then
                     target_folder = row_data.path or ""
@@ -35,11 +35,13 @@ fn main() {
         // Initialize Renet event resources for Lua messaging
         app.init_resource::<renet_lua::LuaEventQueue>();
         app.init_resource::<renet_lua::ReceivedLuaEvents>();
+        app.init_resource::<hello::subscription_registry::SubscriptionRegistry>();
         
         // Add Renet messaging systems (for Lua events)
         // IMPORTANT: Must run before run_lua_systems so events are available in Lua
         app.add_systems(Update, (
             renet_lua::sync_lua_events_to_queue,
+            renet_lua::cleanup_event_subscriptions,
             renet_lua::send_lua_events_renet,
             renet_lua::receive_lua_events_renet,
             renet_lua::sync_received_events_to_lua,
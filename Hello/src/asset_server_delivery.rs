@@ -960,13 +960,19 @@ pub fn receive_asset_responses_global(
     pending_upload_responses: ResMut<PendingUploadResponses>,
     mut server_hashes: ResMut<crate::server_hash_tracker::ServerFileHashes>,
     client: Option<ResMut<RenetClient>>,
+    loader: Option<Res<bevy_lua_ecs::NetworkAssetLoader>>,
+    download_progress: Option<Res<bevy_lua_ecs::DownloadProgress>>,
 ) {
     let Some(mut client) = client else { return };
+    let progress = DownloadProgressReporter {
+        loader: loader.as_deref(),
+        progress: download_progress.as_deref(),
+    };
     
     while let Some(message_bytes) = client.receive_message(ASSET_CHANNEL) {
         match bincode::deserialize::<crate::network_asset_client::ServerToClientMessage>(&message_bytes) {
             Ok(crate::network_asset_client::ServerToClientMessage::Response(response)) => {
-                process_asset_response(&pending_requests, &mut server_hashes, response);
+                process_asset_response(&pending_requests, &mut server_hashes, &progress, response);
             }
             Ok(crate::network_asset_client::ServerToClientMessage::Update(notification)) => {
                 // Store hash from update notification
@@ -1006,11 +1012,31 @@ pub fn receive_asset_responses_global(
 }
 
 
+/// Where received chunks are reported: the `NetworkAssetLoader` when one is installed (it also
+/// notifies its requestor), otherwise straight into the `DownloadProgress` resource
+#[cfg(feature = "networking")]
+struct DownloadProgressReporter<'a> {
+    loader: Option<&'a bevy_lua_ecs::NetworkAssetLoader>,
+    progress: Option<&'a bevy_lua_ecs::DownloadProgress>,
+}
+
+#[cfg(feature = "networking")]
+impl DownloadProgressReporter<'_> {
+    fn report(&self, request_id: u64, path: &str, downloaded: u64, total: u64) {
+        if let Some(loader) = self.loader {
+            loader.report_progress(request_id, path, downloaded, Some(total));
+        } else if let Some(progress) = self.progress {
+            progress.update(request_id, path, downloaded, Some(total));
+        }
+    }
+}
+
 /// Process an asset response message
 #[cfg(feature = "networking")]
 fn process_asset_response(
     pending_requests: &crate::network_asset_client::PendingAssetRequests,
     server_hashes: &mut crate::server_hash_tracker::ServerFileHashes,
+    progress: &DownloadProgressReporter,
     response: AssetResponseMessage,
 ) {
     // Store server hash if provided (for LocalNewer detection)
@@ -1069,6 +1095,9 @@ fn process_asset_response(
     ).is_some() {
         // Check if request is complete
         if let Some(request) = pending_requests.get_request_by_id(response.request_id) {
+            let received: usize = request.chunks.iter().map(|c| c.len()).sum();
+            progress.report(response.request_id, &request.path, received as u64, response.total_size as u64);
+
             if matches!(request.status, crate::network_asset_client::AssetRequestStatus::Complete) {
                 // Reassemble data
                 if let Some(data) = request.reassemble_data() {
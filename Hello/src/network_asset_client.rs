@@ -492,13 +492,35 @@ impl PendingAssetRequests {
     
     /// Queue a new asset request, returns the request ID
     /// If a request for this path is already pending, returns existing request ID
+    /// A failed request is re-sent under a new request ID, keeping its callbacks and waiting coroutines
     /// context_path is the script making the request (for relative path resolution on server)
     pub fn queue_request(&self, path: String, asset_type: AssetType, context_path: Option<String>) -> u64 {
         let mut requests = self.requests.lock().unwrap();
         
         // Check if already pending
-        if let Some(existing) = requests.get(&path) {
-            return existing.request_id;
+        if let Some(existing) = requests.get_mut(&path) {
+            if !matches!(existing.status, AssetRequestStatus::Error(_)) {
+                return existing.request_id;
+            }
+            
+            // Retry: fresh ID so late chunks of the failed attempt are dropped
+            let request_id = self.next_request_id.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let mut id_to_path = self.request_id_to_path.lock().unwrap();
+            id_to_path.remove(&existing.request_id);
+            id_to_path.insert(request_id, path.clone());
+            
+            let retry = AssetRequest::new(
+                request_id,
+                path,
+                existing.asset_type.clone(),
+                existing.context_path.clone().or(context_path),
+            );
+            *existing = AssetRequest {
+                callbacks: std::mem::take(&mut existing.callbacks),
+                waiting_coroutines: std::mem::take(&mut existing.waiting_coroutines),
+                ..retry
+            };
+            return request_id;
         }
         
         // Generate new request ID
@@ -961,4 +983,22 @@ mod tests {
             0.5
         );
     }
+    
+    #[test]
+    fn test_failed_request_is_requeued() {
+        let requests = PendingAssetRequests::new();
+        let first = requests.queue_request("scripts/a.lua".to_string(), AssetType::Script, Some("scripts/main.lua".to_string()));
+        assert_eq!(requests.queue_request("scripts/a.lua".to_string(), AssetType::Script, None), first);
+        assert_eq!(requests.drain_pending_requests().len(), 1);
+        
+        requests.mark_failed("scripts/a.lua", "Request timed out".to_string());
+        let retry = requests.queue_request("scripts/a.lua".to_string(), AssetType::Script, None);
+        assert_ne!(retry, first);
+        assert!(requests.get_request_by_id(first).is_none());
+        
+        let sent = requests.drain_pending_requests();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].request_id, retry);
+        assert_eq!(sent[0].context_path.as_deref(), Some("scripts/main.lua"));
+    }
 }
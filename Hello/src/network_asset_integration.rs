@@ -44,7 +44,12 @@ impl Plugin for NetworkAssetPlugin {
         app.add_plugins(AssetEventsPlugin);
         
         // Initialize client resources
-        app.init_resource::<PendingAssetRequests>();
+        // The loader shares the request queue so failed downloads are retried with backoff
+        let pending_requests = PendingAssetRequests::new();
+        app.insert_resource(bevy_lua_ecs::NetworkAssetLoader::new(PendingAssetRequestor {
+            requests: pending_requests.clone(),
+        }));
+        app.insert_resource(pending_requests);
         app.init_resource::<crate::network_asset_client::PendingAssetUpdates>();
         app.init_resource::<PendingCoroutines>();
         
@@ -109,6 +114,61 @@ impl Plugin for NetworkAssetPlugin {
     }
 }
 
+/// `NetworkAssetRequestor` backed by the client's `PendingAssetRequests` queue
+/// Re-queuing a failed path re-sends it (see `PendingAssetRequests::queue_request`)
+#[cfg(feature = "networking")]
+struct PendingAssetRequestor {
+    requests: PendingAssetRequests,
+}
+
+#[cfg(feature = "networking")]
+impl bevy_lua_ecs::NetworkAssetRequestor for PendingAssetRequestor {
+    fn is_available_locally(&self, path: &str) -> bool {
+        std::path::Path::new("assets").join(path).exists()
+    }
+
+    fn queue_download(&self, path: &str) -> u64 {
+        self.requests.queue_request(path.to_string(), AssetType::Script, None)
+    }
+
+    fn is_download_complete(&self, path: &str) -> bool {
+        self.requests.is_completed(path)
+    }
+
+    fn take_downloaded_data(&self, path: &str) -> Option<Vec<u8>> {
+        self.requests.take_completed(path)
+    }
+
+    fn get_status(&self, path: &str) -> bevy_lua_ecs::AssetDownloadStatus {
+        use crate::network_asset_client::AssetRequestStatus;
+        use bevy_lua_ecs::AssetDownloadStatus;
+
+        if self.requests.is_completed(path) {
+            return AssetDownloadStatus::Complete;
+        }
+        match self.requests.get_request(path).map(|r| r.status) {
+            Some(AssetRequestStatus::Pending | AssetRequestStatus::Requested) => AssetDownloadStatus::Pending,
+            Some(status @ AssetRequestStatus::Downloading { .. }) => AssetDownloadStatus::Downloading(status.progress()),
+            Some(AssetRequestStatus::Error(reason)) => AssetDownloadStatus::Failed(reason),
+            Some(AssetRequestStatus::Complete) => AssetDownloadStatus::Complete,
+            Some(AssetRequestStatus::UpToDate | AssetRequestStatus::LocalNewer { .. }) => AssetDownloadStatus::LocalAvailable,
+            // Finished requests are dropped from the queue once their data is taken
+            None if self.is_available_locally(path) => AssetDownloadStatus::LocalAvailable,
+            None => AssetDownloadStatus::Failed("no download queued".to_string()),
+        }
+    }
+
+    fn register_pending_coroutine(&self, path: &str, coroutine_key: Arc<mlua::RegistryKey>, _instance_id: u64) {
+        self.requests.add_waiting_coroutine(path, coroutine_key);
+    }
+
+    fn has_pending_coroutines(&self, path: &str) -> bool {
+        self.requests
+            .get_request(path)
+            .is_some_and(|r| !r.waiting_coroutines.is_empty())
+    }
+}
+
 /// Resource to debounce rapid reload events
 /// Prevents the same script from reloading multiple times in quick succession
 #[cfg(feature = "networking")]
@@ -306,6 +366,7 @@ pub fn enable_network_downloads(
 #[cfg(feature = "networking")]
 pub fn process_download_requests(
     pending_requests: Res<PendingAssetRequests>,
+    loader: Option<Res<bevy_lua_ecs::NetworkAssetLoader>>,
     lua_ctx: Option<Res<LuaScriptContext>>,
 ) {
     let Some(lua_ctx) = lua_ctx else { return };
@@ -324,6 +385,12 @@ pub fn process_download_requests(
             continue;
         }
         
+        // Failed requests are re-queued by the loader's retry backoff, not every frame
+        if pending_requests.get_request(&path).is_some() {
+            debug!("⏭️ [PROCESS] Skipping '{}' - request already tracked (failed ones wait for retry)", path);
+            continue;
+        }
+        
         // Check if already completed
         if pending_requests.is_completed(&path) {
             debug!("⏭️ [PROCESS] Skipping '{}' - already completed (in completed_assets)", path);
@@ -341,6 +408,10 @@ pub fn process_download_requests(
         
         // Queue a new download request with context for server-side path resolution
         let request_id = pending_requests.queue_request(path.clone(), AssetType::Script, context_path.clone());
+        if let Some(loader) = &loader {
+            // Already queued above with its context; this starts retry tracking
+            loader.queue_download(&path);
+        }
         debug!("📤 [PROCESS] Queued download request for '{}' (request_id: {}, context: {:?})", path, request_id, context_path);
     }
 }
@@ -477,11 +548,13 @@ fn resume_coroutines(
     debug!("📋 Resuming {} coroutines for '{}'", waiting_coroutines.len(), path);
     
     for (coroutine_key, instance_id) in waiting_coroutines {
+        // Sandboxed instances keep their coroutines in their own Lua state
+        let lua = lua_ctx.instance_lua(instance_id);
         debug!("Resuming coroutine for '{}' (instance {})", path, instance_id);
         
         // First check if this is a nil placeholder (used for background server checks)
         // Background checks register with nil - they're not actual coroutines to resume
-        let registry_value: mlua::Value = match lua_ctx.lua.registry_value(&*coroutine_key) {
+        let registry_value: mlua::Value = match lua.registry_value(&*coroutine_key) {
             Ok(v) => v,
             Err(e) => {
                 error!("❌ [RESUME] Failed to get registry value: {}", e);
@@ -513,6 +586,9 @@ fn resume_coroutines(
                 match coroutine.status() {
                     mlua::ThreadStatus::Finished => {
                         debug!("✅ [RESUME] Coroutine completed for '{}'", path);
+                        if let Err(e) = lua_ctx.finish_resumed_coroutine(&lua, instance_id, &coroutine, yield_value) {
+                            error!("❌ [RESUME] Failed to store module for '{}': {}", path, e);
+                        }
                     }
                     mlua::ThreadStatus::Resumable => {
                         // Coroutine yielded again - needs another download
@@ -523,7 +599,7 @@ fn resume_coroutines(
                                 debug!("📥 [RESUME] Coroutine for '{}' yielded for new download: {}", path, new_path);
                                 
                                 // Re-register the coroutine for the new path
-                                let coroutine_key = match lua_ctx.lua.create_registry_value(coroutine) {
+                                let coroutine_key = match lua.create_registry_value(coroutine) {
                                     Ok(key) => std::sync::Arc::new(key),
                                     Err(e) => {
                                         error!("❌ [RESUME] Failed to store coroutine in registry: {}", e);
@@ -570,12 +646,14 @@ fn resume_coroutines_with_source(
     let waiting_coroutines = lua_ctx.script_cache.take_pending_download_coroutines(&path);
     
     for (coroutine_key, instance_id) in waiting_coroutines {
+        // Sandboxed instances keep their coroutines in their own Lua state
+        let lua = lua_ctx.instance_lua(instance_id);
         debug!("Resuming coroutine for '{}' (instance {})", path, instance_id);
         
         // Resume the coroutine with the downloaded source
-        if let Ok(coroutine) = lua_ctx.lua.registry_value::<mlua::Thread>(&*coroutine_key) {
+        if let Ok(coroutine) = lua.registry_value::<mlua::Thread>(&*coroutine_key) {
             // Set instance ID before resuming
-            if let Err(e) = lua_ctx.lua.globals().set("__INSTANCE_ID__", instance_id) {
+            if let Err(e) = lua.globals().set("__INSTANCE_ID__", instance_id) {
                 error!("Failed to set __INSTANCE_ID__: {}", e);
             }
             
@@ -588,6 +666,9 @@ fn resume_coroutines_with_source(
                     match coroutine.status() {
                         mlua::ThreadStatus::Finished => {
                             debug!("✓ Coroutine completed for '{}'", path);
+                            if let Err(e) = lua_ctx.finish_resumed_coroutine(&lua, instance_id, &coroutine, yield_value) {
+                                error!("❌ [RESUME] Failed to store module for '{}': {}", path, e);
+                            }
                         }
                         mlua::ThreadStatus::Resumable => {
                             // Coroutine yielded again - needs another download
@@ -597,7 +678,7 @@ fn resume_coroutines_with_source(
                                     debug!("📥 [RESUME] Coroutine yielded again for new download: {}", new_path);
                                     
                                     // Re-register the coroutine for the new path
-                                    let coroutine_key = match lua_ctx.lua.create_registry_value(coroutine) {
+                                    let coroutine_key = match lua.create_registry_value(coroutine) {
                                         Ok(key) => std::sync::Arc::new(key),
                                         Err(e) => {
                                             error!("❌ [RESUME] Failed to store coroutine in registry: {}", e);
@@ -832,7 +913,7 @@ pub fn receive_asset_updates(
                         
                         debug!("🔄 [CLIENT] Triggering reload for script '{}' (depends on '{}')", script_path, notification.path);
                         file_events.write(bevy_lua_ecs::lua_file_watcher::LuaFileChangeEvent {
-                            path: std::path::PathBuf::from(format!("assets/{}", script_path)),
+                            path: std::path::PathBuf::from(&script_path),
                         });
                     }
                 } else {
@@ -933,12 +1014,7 @@ pub fn detect_local_newer_files(
 ) {
     for event in file_events.read() {
         // Convert file path to asset-relative path
-        let path_str = event.path.to_string_lossy().replace('\\', "/");
-        let asset_path = if path_str.starts_with("assets/") {
-            path_str.strip_prefix("assets/").unwrap_or(&path_str).to_string()
-        } else {
-            path_str.to_string()
-        };
+        let asset_path = bevy_lua_ecs::to_asset_server_path(&event.path);
         
         // Check if we have a server hash for this file
         if let Some(server_hash) = server_hashes.get_hash(&asset_path) {
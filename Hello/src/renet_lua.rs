@@ -155,6 +155,7 @@ pub fn send_lua_events_renet(
     mut event_queue: ResMut<LuaEventQueue>,
     mut server: Option<ResMut<RenetServer>>,
     mut client: Option<ResMut<RenetClient>>,
+    subscriptions: Option<Res<hello::subscription_registry::SubscriptionRegistry>>,
 ) {
     if event_queue.events.is_empty() {
         return;
@@ -169,9 +170,18 @@ pub fn send_lua_events_renet(
         let msg_len = message.len();
         
         if let Some(ref mut server) = server {
-            // Server: broadcast to all clients
-            server.broadcast_message(LUA_EVENTS_CHANNEL, message);
-            debug!("  → Server broadcast {} bytes (JSON)", msg_len);
+            if let Some(ref subscriptions) = subscriptions {
+                // Server: only forward to clients whose subscription filters accept the event
+                let recipients = subscriptions.recipients(server.clients_id(), &event_data);
+                for client_id in &recipients {
+                    server.send_message(*client_id, LUA_EVENTS_CHANNEL, message.clone());
+                }
+                debug!("  → Server sent {} bytes (JSON) to {} filtered clients", msg_len, recipients.len());
+            } else {
+                // Server: broadcast to all clients
+                server.broadcast_message(LUA_EVENTS_CHANNEL, message);
+                debug!("  → Server broadcast {} bytes (JSON)", msg_len);
+            }
         } else if let Some(ref mut client) = client {
             // Client: send to server
             client.send_message(LUA_EVENTS_CHANNEL, message);
@@ -180,12 +190,30 @@ pub fn send_lua_events_renet(
     }
 }
 
+/// System dropping the event subscriptions (exact and glob) of clients that disconnected
+#[cfg(feature = "networking")]
+pub fn cleanup_event_subscriptions(
+    server: Option<Res<RenetServer>>,
+    subscriptions: Option<Res<hello::subscription_registry::SubscriptionRegistry>>,
+    mut connected: Local<std::collections::HashSet<u64>>,
+) {
+    let (Some(server), Some(subscriptions)) = (server, subscriptions) else { return };
+
+    let current: std::collections::HashSet<u64> = server.clients_id().into_iter().collect();
+    for client_id in connected.difference(&current) {
+        debug!("🧹 [SERVER] Dropping event subscriptions of disconnected client {}", client_id);
+        subscriptions.unsubscribe_client(*client_id);
+    }
+    *connected = current;
+}
+
 /// System to receive Lua events via Renet
 #[cfg(feature = "networking")]
 pub fn receive_lua_events_renet(
     mut server: Option<ResMut<RenetServer>>,
     mut client: Option<ResMut<RenetClient>>,
     mut received: ResMut<ReceivedLuaEvents>,
+    subscriptions: Option<Res<hello::subscription_registry::SubscriptionRegistry>>,
 ) {
     if let Some(ref mut server) = server {
         // Server: receive from clients
@@ -198,9 +226,15 @@ pub fn receive_lua_events_renet(
                         debug!("  ✓ Deserialized: {:?}", event_data);
                         received.events.push(event_data.clone());
                         debug!("  ✓ Added to queue (total: {})", received.events.len());
-                        // Echo back to all clients
+                        // Echo back to all (subscribed) clients
                         let response = json_str.as_bytes().to_vec();
-                        server.broadcast_message(LUA_EVENTS_CHANNEL, response);
+                        if let Some(ref subscriptions) = subscriptions {
+                            for recipient in subscriptions.recipients(server.clients_id(), &event_data) {
+                                server.send_message(recipient, LUA_EVENTS_CHANNEL, response.clone());
+                            }
+                        } else {
+                            server.broadcast_message(LUA_EVENTS_CHANNEL, response);
+                        }
                     } else {
                         error!("  ✗ JSON PARSE FAILED: {}", json_str);
                     }
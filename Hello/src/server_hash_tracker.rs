@@ -75,3 +75,23 @@ impl ServerFileHashes {
         self.hashes.clear();
     }
 }
+
+/// Read-only queries for Lua, e.g. `world:call_resource_method("ServerFileHashes", "server_hash", path)`
+/// Kept out of the main impl so scripts can't change what the client believes the server has.
+#[bevy_lua_ecs::lua_export]
+impl ServerFileHashes {
+    /// Last-known server hash of `path`, or nil if the file was never synced
+    pub fn server_hash(&self, path: &str) -> Option<String> {
+        self.get_hash(path).map(str::to_string)
+    }
+
+    /// Whether `path` has been downloaded from (or updated by) the server
+    pub fn is_synced(&self, path: &str) -> bool {
+        self.has_hash(path)
+    }
+
+    /// Number of files with a known server hash
+    pub fn synced_count(&self) -> usize {
+        self.hashes.len()
+    }
+}
@@ -325,3 +325,192 @@ impl AssetSubscriptionRegistry {
         std::mem::take(&mut *self.pending_notifications.lock().unwrap())
     }
 }
+
+/// Predicate deciding whether an event payload should be forwarded to a client
+pub type EventFilter = Box<dyn Fn(&serde_json::Value) -> bool + Send + Sync>;
+
+/// Tracks per-client interest in network events
+/// Clients that never subscribed to an event type keep receiving every broadcast;
+/// once a client subscribes, only payloads passing one of its filters are forwarded
+/// Glob subscriptions ("physics.*") apply to event types without an exact subscription
+#[derive(Resource, Clone, Default)]
+pub struct SubscriptionRegistry {
+    /// Filters: event_type -> (client_id -> filters)
+    filters: Arc<Mutex<HashMap<String, HashMap<u64, Vec<EventFilter>>>>>,
+    /// Glob filters: client_id -> (pattern, filter), in subscription order
+    glob_filters: Arc<Mutex<HashMap<u64, Vec<(glob::Pattern, EventFilter)>>>>,
+}
+
+impl SubscriptionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Event type of a network event payload: its "type" field, falling back to "action"
+    pub fn event_type_of(data: &serde_json::Value) -> &str {
+        data.get("type")
+            .or_else(|| data.get("action"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+    }
+
+    /// Subscribe a client to every event of a type
+    pub fn subscribe(&self, client_id: u64, event_type: impl Into<String>) {
+        self.subscribe_filtered(client_id, event_type, |_| true);
+    }
+
+    /// Subscribe a client to events of a type that pass `filter`
+    /// (e.g. only entities within a certain radius of the client's player)
+    pub fn subscribe_filtered(
+        &self,
+        client_id: u64,
+        event_type: impl Into<String>,
+        filter: impl Fn(&serde_json::Value) -> bool + Send + Sync + 'static,
+    ) {
+        let event_type = event_type.into();
+        debug!("📡 [SERVER] Client {} subscribed to '{}' events", client_id, event_type);
+        self.filters.lock().unwrap()
+            .entry(event_type)
+            .or_default()
+            .entry(client_id)
+            .or_default()
+            .push(Box::new(filter));
+    }
+
+    /// Drop a client's filters for one event type (back to unfiltered broadcast)
+    pub fn unsubscribe(&self, client_id: u64, event_type: &str) {
+        let mut filters = self.filters.lock().unwrap();
+        if let Some(clients) = filters.get_mut(event_type) {
+            clients.remove(&client_id);
+            if clients.is_empty() {
+                filters.remove(event_type);
+            }
+        }
+    }
+
+    /// Subscribe a client to every event type matching a glob pattern (e.g. "ui.button.*")
+    /// that passes `filter`
+    pub fn subscribe_glob(
+        &self,
+        client_id: u64,
+        pattern: &str,
+        filter: impl Fn(&serde_json::Value) -> bool + Send + Sync + 'static,
+    ) -> Result<(), glob::PatternError> {
+        let pattern = glob::Pattern::new(pattern)?;
+        debug!("📡 [SERVER] Client {} subscribed to '{}' events", client_id, pattern);
+        self.glob_filters.lock().unwrap()
+            .entry(client_id)
+            .or_default()
+            .push((pattern, Box::new(filter)));
+        Ok(())
+    }
+
+    /// Drop a client's filters for one glob pattern
+    pub fn unsubscribe_glob(&self, client_id: u64, pattern: &str) {
+        let mut glob_filters = self.glob_filters.lock().unwrap();
+        if let Some(client_globs) = glob_filters.get_mut(&client_id) {
+            client_globs.retain(|(client_pattern, _)| client_pattern.as_str() != pattern);
+            if client_globs.is_empty() {
+                glob_filters.remove(&client_id);
+            }
+        }
+    }
+
+    /// Drop all filters for a client, exact and glob (when client disconnects)
+    pub fn unsubscribe_client(&self, client_id: u64) {
+        let mut filters = self.filters.lock().unwrap();
+        filters.retain(|_, clients| {
+            clients.remove(&client_id);
+            !clients.is_empty()
+        });
+        self.glob_filters.lock().unwrap().remove(&client_id);
+    }
+
+    /// Whether an event payload should be sent to a client
+    pub fn should_forward(&self, client_id: u64, data: &serde_json::Value) -> bool {
+        let event_type = Self::event_type_of(data);
+
+        // Fast path: an exact subscription decides on its own
+        {
+            let filters = self.filters.lock().unwrap();
+            if let Some(client_filters) = filters
+                .get(event_type)
+                .and_then(|clients| clients.get(&client_id))
+            {
+                return client_filters.iter().any(|filter| filter(data));
+            }
+        }
+
+        let glob_filters = self.glob_filters.lock().unwrap();
+        let Some(client_globs) = glob_filters.get(&client_id) else {
+            return true;
+        };
+        let mut matching = client_globs
+            .iter()
+            .filter(|(pattern, _)| pattern.matches(event_type))
+            .peekable();
+        if matching.peek().is_none() {
+            return true;
+        }
+        matching.any(|(_, filter)| filter(data))
+    }
+
+    /// Clients (out of `client_ids`) that should receive an event payload
+    pub fn recipients(&self, client_ids: impl IntoIterator<Item = u64>, data: &serde_json::Value) -> Vec<u64> {
+        client_ids
+            .into_iter()
+            .filter(|client_id| self.should_forward(*client_id, data))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_filtered_subscription_limits_recipients() {
+        let registry = SubscriptionRegistry::new();
+        registry.subscribe_filtered(1, "moved", |data| {
+            data["x"].as_f64().is_some_and(|x| x.abs() <= 10.0)
+        });
+
+        let near = json!({ "type": "moved", "x": 3.0 });
+        let far = json!({ "type": "moved", "x": 50.0 });
+        let other = json!({ "type": "chat", "text": "hi" });
+
+        assert_eq!(registry.recipients([1, 2], &near), vec![1, 2]);
+        assert_eq!(registry.recipients([1, 2], &far), vec![2]);
+        assert_eq!(registry.recipients([1, 2], &other), vec![1, 2]);
+
+        registry.unsubscribe_client(1);
+        assert!(registry.should_forward(1, &far));
+    }
+
+    #[test]
+    fn test_glob_subscription_falls_back_after_exact() {
+        let registry = SubscriptionRegistry::new();
+        registry.subscribe_glob(1, "physics.*", |data| data["mass"].as_f64().unwrap_or(0.0) > 1.0).unwrap();
+        registry.subscribe(1, "physics.sleep");
+
+        let heavy = json!({ "type": "physics.collision", "mass": 5.0 });
+        let light = json!({ "type": "physics.collision", "mass": 0.5 });
+        // The exact subscription wins over the glob for its event type
+        let sleep = json!({ "type": "physics.sleep", "mass": 0.5 });
+        let chat = json!({ "type": "chat" });
+
+        assert_eq!(registry.recipients([1, 2], &heavy), vec![1, 2]);
+        assert_eq!(registry.recipients([1, 2], &light), vec![2]);
+        assert!(registry.should_forward(1, &sleep));
+        assert!(registry.should_forward(1, &chat));
+        assert!(registry.subscribe_glob(1, "[", |_| true).is_err());
+
+        registry.unsubscribe_glob(1, "physics.*");
+        assert!(registry.should_forward(1, &light));
+
+        registry.subscribe_glob(1, "physics.*", |_| false).unwrap();
+        registry.unsubscribe_client(1);
+        assert!(registry.should_forward(1, &light));
+    }
+}
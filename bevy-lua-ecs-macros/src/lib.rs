@@ -0,0 +1,285 @@
+//! Procedural macros for bevy-lua-ecs
+//!
+//! `#[lua_export]` on an inherent `impl` block of a Bevy `Resource` exposes its public
+//! `&self`/`&mut self` methods to Lua through `LuaResourceRegistry`:
+//!
+//! ```ignore
+//! #[derive(Resource)]
+//! struct Score { value: u32 }
+//!
+//! #[lua_export]
+//! impl Score {
+//!     pub fn add(&mut self, amount: u32) -> u32 {
+//!         self.value += amount;
+//!         self.value
+//!     }
+//! }
+//! ```
+//!
+//! The macro emits an `inventory::submit!` entry, and `LuaSpawnPlugin` registers every
+//! submitted type at startup - no manual `register_resource` call is needed.
+//! Use `#[lua_export(name = "LuaName")]` to override the Lua-visible type name.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::parse::Parser;
+use syn::spanned::Spanned;
+use syn::{FnArg, ImplItem, ItemImpl, Pat, ReturnType, Type};
+
+#[proc_macro_attribute]
+pub fn lua_export(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item_impl = syn::parse_macro_input!(item as ItemImpl);
+    match expand_lua_export(attr.into(), &item_impl) {
+        Ok(registration) => quote! {
+            #item_impl
+            #registration
+        }
+        .into(),
+        Err(e) => {
+            let error = e.to_compile_error();
+            quote! {
+                #item_impl
+                #error
+            }
+            .into()
+        }
+    }
+}
+
+fn expand_lua_export(attr: TokenStream2, item_impl: &ItemImpl) -> syn::Result<TokenStream2> {
+    if let Some((_, path, _)) = &item_impl.trait_ {
+        return Err(syn::Error::new(
+            path.span(),
+            "#[lua_export] must be placed on an inherent impl block, not a trait impl",
+        ));
+    }
+    if !item_impl.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            item_impl.generics.span(),
+            "#[lua_export] does not support generic impl blocks",
+        ));
+    }
+
+    let self_ty = &item_impl.self_ty;
+    let type_name = match parse_name_override(attr)? {
+        Some(name) => name,
+        None => type_short_name(self_ty)?,
+    };
+
+    let mut method_registrations = Vec::new();
+    for item in &item_impl.items {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
+        // Only public methods taking &self / &mut self are exported
+        if !matches!(method.vis, syn::Visibility::Public(_)) {
+            continue;
+        }
+        let Some(FnArg::Receiver(receiver)) = method.sig.inputs.first() else {
+            continue;
+        };
+        if receiver.reference.is_none() {
+            return Err(syn::Error::new(
+                receiver.span(),
+                "#[lua_export] methods must take &self or &mut self",
+            ));
+        }
+        if !method.sig.generics.params.is_empty() {
+            return Err(syn::Error::new(
+                method.sig.generics.span(),
+                "#[lua_export] methods cannot be generic",
+            ));
+        }
+        if method.sig.asyncness.is_some() {
+            return Err(syn::Error::new(
+                method.sig.asyncness.span(),
+                "#[lua_export] methods cannot be async",
+            ));
+        }
+
+        method_registrations.push(expand_method(method)?);
+    }
+
+    let register_fn = format_ident!("__lua_export_register_{}", sanitize_ident(&type_name));
+
+    Ok(quote! {
+        const _: () = {
+            fn #register_fn(registry: &::bevy_lua_ecs::LuaResourceRegistry) {
+                registry.register_resource::<#self_ty, _>(#type_name, |methods| {
+                    #(#method_registrations)*
+                });
+            }
+
+            ::bevy_lua_ecs::inventory::submit! {
+                ::bevy_lua_ecs::resource_lua_trait::LuaExportedResource {
+                    type_name: #type_name,
+                    register: #register_fn,
+                }
+            }
+        };
+    })
+}
+
+/// Generate the `methods.add(...)` call for one exported method
+fn expand_method(method: &syn::ImplItemFn) -> syn::Result<TokenStream2> {
+    let method_ident = &method.sig.ident;
+    let method_name = method_ident.to_string();
+
+    let mut arg_idents = Vec::new();
+    let mut arg_types = Vec::new();
+    let mut call_args = Vec::new();
+
+    for (index, input) in method.sig.inputs.iter().skip(1).enumerate() {
+        let FnArg::Typed(pat_type) = input else {
+            continue;
+        };
+        let ident = match &*pat_type.pat {
+            Pat::Ident(pat_ident) => pat_ident.ident.clone(),
+            _ => format_ident!("arg{}", index),
+        };
+
+        let (lua_ty, call_arg) = lua_argument(&pat_type.ty, &ident)?;
+        arg_idents.push(ident);
+        arg_types.push(lua_ty);
+        call_args.push(call_arg);
+    }
+
+    let call = quote! { resource.#method_ident(#(#call_args),*) };
+    let body = lua_return(&method.sig.output, call)?;
+
+    Ok(quote! {
+        methods.add(
+            #method_name,
+            |resource, _lua, (#(#arg_idents,)*): (#(#arg_types,)*)| {
+                #body
+            },
+        );
+    })
+}
+
+/// Map a Rust argument type to the type parsed from Lua and the expression passed to the method
+fn lua_argument(ty: &Type, ident: &syn::Ident) -> syn::Result<(TokenStream2, TokenStream2)> {
+    match ty {
+        Type::Reference(reference) => {
+            if reference.mutability.is_none() && is_str(&reference.elem) {
+                // &str arguments are received as an owned String
+                Ok((quote! { ::std::string::String }, quote! { &#ident }))
+            } else {
+                Err(syn::Error::new(
+                    ty.span(),
+                    "#[lua_export] unsupported argument type: only &str references can be passed from Lua",
+                ))
+            }
+        }
+        Type::ImplTrait(_) => Err(syn::Error::new(
+            ty.span(),
+            "#[lua_export] unsupported argument type: impl Trait arguments cannot be converted from Lua",
+        )),
+        Type::BareFn(_) | Type::TraitObject(_) | Type::Ptr(_) | Type::Never(_) => {
+            Err(syn::Error::new(
+                ty.span(),
+                "#[lua_export] unsupported argument type: expected a type implementing FromLua",
+            ))
+        }
+        // Everything else is converted with FromLua (checked by the compiler)
+        _ => Ok((quote! { #ty }, quote! { #ident })),
+    }
+}
+
+/// Build the handler body converting the method's return value for Lua
+fn lua_return(output: &ReturnType, call: TokenStream2) -> syn::Result<TokenStream2> {
+    let ty = match output {
+        ReturnType::Default => {
+            return Ok(quote! {
+                #call;
+                Ok(())
+            })
+        }
+        ReturnType::Type(_, ty) => ty,
+    };
+
+    match &**ty {
+        // impl IntoLua (or any other impl Trait the compiler accepts) is passed through
+        Type::ImplTrait(_) => Ok(quote! { Ok(#call) }),
+        Type::Reference(reference) => {
+            if is_str(&reference.elem) {
+                Ok(quote! { Ok(#call.to_string()) })
+            } else {
+                Err(syn::Error::new(
+                    ty.span(),
+                    "#[lua_export] unsupported return type: return an owned value instead of a reference",
+                ))
+            }
+        }
+        Type::Path(type_path) if is_lua_result(type_path) => Ok(call),
+        Type::Path(type_path) if is_result(type_path) => Ok(quote! {
+            #call.map_err(::bevy_lua_ecs::mlua::Error::external)
+        }),
+        Type::BareFn(_) | Type::TraitObject(_) | Type::Ptr(_) => Err(syn::Error::new(
+            ty.span(),
+            "#[lua_export] unsupported return type: expected a type implementing IntoLua",
+        )),
+        _ => Ok(quote! { Ok(#call) }),
+    }
+}
+
+/// Parse `name = "..."` from the attribute arguments
+fn parse_name_override(attr: TokenStream2) -> syn::Result<Option<String>> {
+    if attr.is_empty() {
+        return Ok(None);
+    }
+    let mut name = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            let value: syn::LitStr = meta.value()?.parse()?;
+            name = Some(value.value());
+            Ok(())
+        } else {
+            Err(meta.error("unsupported #[lua_export] argument, expected `name = \"...\"`"))
+        }
+    });
+    parser.parse2(attr)?;
+    Ok(name)
+}
+
+fn type_short_name(ty: &Type) -> syn::Result<String> {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .ok_or_else(|| syn::Error::new(ty.span(), "#[lua_export] could not determine type name")),
+        _ => Err(syn::Error::new(
+            ty.span(),
+            "#[lua_export] must be placed on an impl block for a named type",
+        )),
+    }
+}
+
+fn sanitize_ident(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn is_str(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.path.is_ident("str"))
+}
+
+fn is_result(type_path: &syn::TypePath) -> bool {
+    type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Result")
+}
+
+fn is_lua_result(type_path: &syn::TypePath) -> bool {
+    type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "LuaResult")
+}
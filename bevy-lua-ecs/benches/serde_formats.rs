@@ -0,0 +1,260 @@
+//! JSON vs bincode round trip for a 100-field component
+//!
+//! Run with `cargo bench --bench serde_formats`. This is the serde step that
+//! `SerdeComponentRegistry::register` (JSON) and `register_binary` (bincode) add to every
+//! network payload; the payload size is printed alongside the time.
+
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+const ROUNDS: usize = 10_000;
+
+/// Wide component mixing the field types common in replicated game state
+#[derive(Serialize, Deserialize)]
+struct WideComponent {
+    f0: f32,
+    f1: u32,
+    f2: bool,
+    f3: i64,
+    f4: f32,
+    f5: u32,
+    f6: bool,
+    f7: i64,
+    f8: f32,
+    f9: u32,
+    f10: bool,
+    f11: i64,
+    f12: f32,
+    f13: u32,
+    f14: bool,
+    f15: i64,
+    f16: f32,
+    f17: u32,
+    f18: bool,
+    f19: i64,
+    f20: f32,
+    f21: u32,
+    f22: bool,
+    f23: i64,
+    f24: f32,
+    f25: u32,
+    f26: bool,
+    f27: i64,
+    f28: f32,
+    f29: u32,
+    f30: bool,
+    f31: i64,
+    f32: f32,
+    f33: u32,
+    f34: bool,
+    f35: i64,
+    f36: f32,
+    f37: u32,
+    f38: bool,
+    f39: i64,
+    f40: f32,
+    f41: u32,
+    f42: bool,
+    f43: i64,
+    f44: f32,
+    f45: u32,
+    f46: bool,
+    f47: i64,
+    f48: f32,
+    f49: u32,
+    f50: bool,
+    f51: i64,
+    f52: f32,
+    f53: u32,
+    f54: bool,
+    f55: i64,
+    f56: f32,
+    f57: u32,
+    f58: bool,
+    f59: i64,
+    f60: f32,
+    f61: u32,
+    f62: bool,
+    f63: i64,
+    f64: f32,
+    f65: u32,
+    f66: bool,
+    f67: i64,
+    f68: f32,
+    f69: u32,
+    f70: bool,
+    f71: i64,
+    f72: f32,
+    f73: u32,
+    f74: bool,
+    f75: i64,
+    f76: f32,
+    f77: u32,
+    f78: bool,
+    f79: i64,
+    f80: f32,
+    f81: u32,
+    f82: bool,
+    f83: i64,
+    f84: f32,
+    f85: u32,
+    f86: bool,
+    f87: i64,
+    f88: f32,
+    f89: u32,
+    f90: bool,
+    f91: i64,
+    f92: f32,
+    f93: u32,
+    f94: bool,
+    f95: i64,
+    f96: f32,
+    f97: u32,
+    f98: bool,
+    f99: i64,
+}
+
+impl WideComponent {
+    fn sample(i: usize) -> Self {
+        Self {
+            f0: i as f32 * 0.5 + 0.25,
+            f1: i as u32 + 1,
+            f2: (i + 2) % 2 == 0,
+            f3: i as i64 - 3,
+            f4: i as f32 * 0.5 + 4.25,
+            f5: i as u32 + 5,
+            f6: (i + 6) % 2 == 0,
+            f7: i as i64 - 7,
+            f8: i as f32 * 0.5 + 8.25,
+            f9: i as u32 + 9,
+            f10: (i + 10) % 2 == 0,
+            f11: i as i64 - 11,
+            f12: i as f32 * 0.5 + 12.25,
+            f13: i as u32 + 13,
+            f14: (i + 14) % 2 == 0,
+            f15: i as i64 - 15,
+            f16: i as f32 * 0.5 + 16.25,
+            f17: i as u32 + 17,
+            f18: (i + 18) % 2 == 0,
+            f19: i as i64 - 19,
+            f20: i as f32 * 0.5 + 20.25,
+            f21: i as u32 + 21,
+            f22: (i + 22) % 2 == 0,
+            f23: i as i64 - 23,
+            f24: i as f32 * 0.5 + 24.25,
+            f25: i as u32 + 25,
+            f26: (i + 26) % 2 == 0,
+            f27: i as i64 - 27,
+            f28: i as f32 * 0.5 + 28.25,
+            f29: i as u32 + 29,
+            f30: (i + 30) % 2 == 0,
+            f31: i as i64 - 31,
+            f32: i as f32 * 0.5 + 32.25,
+            f33: i as u32 + 33,
+            f34: (i + 34) % 2 == 0,
+            f35: i as i64 - 35,
+            f36: i as f32 * 0.5 + 36.25,
+            f37: i as u32 + 37,
+            f38: (i + 38) % 2 == 0,
+            f39: i as i64 - 39,
+            f40: i as f32 * 0.5 + 40.25,
+            f41: i as u32 + 41,
+            f42: (i + 42) % 2 == 0,
+            f43: i as i64 - 43,
+            f44: i as f32 * 0.5 + 44.25,
+            f45: i as u32 + 45,
+            f46: (i + 46) % 2 == 0,
+            f47: i as i64 - 47,
+            f48: i as f32 * 0.5 + 48.25,
+            f49: i as u32 + 49,
+            f50: (i + 50) % 2 == 0,
+            f51: i as i64 - 51,
+            f52: i as f32 * 0.5 + 52.25,
+            f53: i as u32 + 53,
+            f54: (i + 54) % 2 == 0,
+            f55: i as i64 - 55,
+            f56: i as f32 * 0.5 + 56.25,
+            f57: i as u32 + 57,
+            f58: (i + 58) % 2 == 0,
+            f59: i as i64 - 59,
+            f60: i as f32 * 0.5 + 60.25,
+            f61: i as u32 + 61,
+            f62: (i + 62) % 2 == 0,
+            f63: i as i64 - 63,
+            f64: i as f32 * 0.5 + 64.25,
+            f65: i as u32 + 65,
+            f66: (i + 66) % 2 == 0,
+            f67: i as i64 - 67,
+            f68: i as f32 * 0.5 + 68.25,
+            f69: i as u32 + 69,
+            f70: (i + 70) % 2 == 0,
+            f71: i as i64 - 71,
+            f72: i as f32 * 0.5 + 72.25,
+            f73: i as u32 + 73,
+            f74: (i + 74) % 2 == 0,
+            f75: i as i64 - 75,
+            f76: i as f32 * 0.5 + 76.25,
+            f77: i as u32 + 77,
+            f78: (i + 78) % 2 == 0,
+            f79: i as i64 - 79,
+            f80: i as f32 * 0.5 + 80.25,
+            f81: i as u32 + 81,
+            f82: (i + 82) % 2 == 0,
+            f83: i as i64 - 83,
+            f84: i as f32 * 0.5 + 84.25,
+            f85: i as u32 + 85,
+            f86: (i + 86) % 2 == 0,
+            f87: i as i64 - 87,
+            f88: i as f32 * 0.5 + 88.25,
+            f89: i as u32 + 89,
+            f90: (i + 90) % 2 == 0,
+            f91: i as i64 - 91,
+            f92: i as f32 * 0.5 + 92.25,
+            f93: i as u32 + 93,
+            f94: (i + 94) % 2 == 0,
+            f95: i as i64 - 95,
+            f96: i as f32 * 0.5 + 96.25,
+            f97: i as u32 + 97,
+            f98: (i + 98) % 2 == 0,
+            f99: i as i64 - 99,
+        }
+    }
+}
+
+fn main() {
+    let components: Vec<WideComponent> = (0..64).map(WideComponent::sample).collect();
+
+    let json_size = serde_json::to_vec(&components[0]).unwrap().len();
+    let bincode_size = bincode::serialize(&components[0]).unwrap().len();
+    let json_ns = measure(|i| {
+        let bytes = serde_json::to_vec(&components[i % components.len()]).unwrap();
+        let decoded: WideComponent = serde_json::from_slice(&bytes).unwrap();
+        std::hint::black_box(decoded);
+    });
+    let bincode_ns = measure(|i| {
+        let bytes = bincode::serialize(&components[i % components.len()]).unwrap();
+        let decoded: WideComponent = bincode::deserialize(&bytes).unwrap();
+        std::hint::black_box(decoded);
+    });
+
+    println!("100-field component round trip, {} rounds", ROUNDS);
+    println!("  json:    {:>8.1} ns  {:>5} bytes", json_ns, json_size);
+    println!("  bincode: {:>8.1} ns  {:>5} bytes", bincode_ns, bincode_size);
+    println!(
+        "  bincode is {:.2}x faster and {:.2}x smaller",
+        json_ns / bincode_ns,
+        json_size as f64 / bincode_size as f64
+    );
+}
+
+/// Average ns per round trip over ROUNDS runs, after one warm-up pass
+fn measure(mut run: impl FnMut(usize)) -> f64 {
+    for i in 0..ROUNDS / 10 {
+        run(i);
+    }
+    let start = Instant::now();
+    for i in 0..ROUNDS {
+        run(i);
+    }
+    start.elapsed().as_nanos() as f64 / ROUNDS as f64
+}
@@ -0,0 +1,67 @@
+//! Cost of converting `Transform` components to Lua, per entity
+//!
+//! Run with `cargo bench --bench transform_query`. The "reflected" case goes through the
+//! Vec3/Quat fast paths; the "dynamic" case converts a `DynamicStruct` copy of the same
+//! component, which has to walk every field through reflection.
+
+use bevy::ecs::reflect::ReflectComponent;
+use bevy::prelude::*;
+use bevy::reflect::PartialReflect;
+use bevy_lua_ecs::lua_world_api::reflection_to_lua;
+use mlua::prelude::*;
+use std::time::Instant;
+
+const ENTITIES: usize = 10_000;
+const ROUNDS: usize = 10;
+
+fn main() {
+    let mut world = World::new();
+    world.init_resource::<AppTypeRegistry>();
+    world.resource::<AppTypeRegistry>().write().register::<Transform>();
+    for i in 0..ENTITIES {
+        world.spawn(Transform::from_xyz(i as f32, 1.0, 2.0).with_rotation(Quat::from_rotation_z(i as f32)));
+    }
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let reflect_component = registry
+        .get(std::any::TypeId::of::<Transform>())
+        .and_then(|registration| registration.data::<ReflectComponent>())
+        .expect("Transform is registered")
+        .clone();
+    let entities: Vec<Entity> = world.query::<Entity>().iter(&world).collect();
+    let dynamic: Vec<Box<dyn PartialReflect>> = entities
+        .iter()
+        .map(|entity| world.get::<Transform>(*entity).unwrap().to_dynamic())
+        .collect();
+    let lua = Lua::new();
+
+    let reflected_ns = measure(|| {
+        for entity in &entities {
+            let component = reflect_component.reflect(world.entity(*entity)).unwrap();
+            let value = reflection_to_lua(&lua, component.as_partial_reflect()).unwrap();
+            std::hint::black_box(value);
+        }
+    });
+    let dynamic_ns = measure(|| {
+        for component in &dynamic {
+            let value = reflection_to_lua(&lua, component.as_ref()).unwrap();
+            std::hint::black_box(value);
+        }
+    });
+
+    println!("Transform -> Lua, {} entities x {} rounds", ENTITIES, ROUNDS);
+    println!("  reflected (fast paths): {:>8.1} ns/entity", reflected_ns);
+    println!("  dynamic (full walk):    {:>8.1} ns/entity", dynamic_ns);
+    println!("  speedup:                {:>8.2}x", dynamic_ns / reflected_ns);
+}
+
+/// Average ns per entity over ROUNDS runs, after one warm-up run
+fn measure(mut run: impl FnMut()) -> f64 {
+    run();
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        run();
+    }
+    start.elapsed().as_nanos() as f64 / (ROUNDS * ENTITIES) as f64
+}
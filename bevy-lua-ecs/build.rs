@@ -8,6 +8,25 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use syn::{File, FnArg, ImplItem, Item, ItemImpl, ReturnType, Visibility};
 
+#[path = "build_support/signatures.rs"]
+mod signatures;
+use signatures::{
+    builder_call_args, builder_return_kind, propagate_result_error, returns_self_or_type,
+    unwrap_constructor_call, BuilderReturn, SelfReturn,
+};
+#[path = "build_support/file_cache.rs"]
+mod file_cache;
+use file_cache::{hash_map_context, scan_file_cached, FileScanEntry, FileScanStats};
+#[path = "build_support/derives.rs"]
+mod derives;
+use derives::{
+    collect_derive_names, generate_enum_setter_arm, has_reflect_component_attr,
+    parse_component_enum_derives, parse_reflect_component_types_from_source,
+};
+#[path = "build_support/type_paths.rs"]
+mod type_paths;
+use type_paths::{apply_path_override, is_primitive_type, parse_path_overrides, resolve_short_type_to_full_path};
+
 fn main() {
     // IMPORTANT: We intentionally do NOT use cargo:rerun-if-changed for most files.
     // This allows the build script to run every time and detect feature changes.
@@ -31,6 +50,9 @@ fn main() {
             let needs_regen = if !out_bindings.exists() {
                 println!("cargo:warning=Build script: OUT_DIR/auto_bindings.rs missing, forcing regeneration");
                 true
+            } else if !build_dir.join(DISCOVER_REPORT_FILE).exists() {
+                println!("cargo:warning=Build script: OUT_DIR/{} missing, forcing regeneration", DISCOVER_REPORT_FILE);
+                true
             } else if let Some(parent_manifest) = find_parent_manifest(&build_dir) {
                 // Also check parent's auto_resource_bindings.rs
                 let parent_src_dir = parent_manifest.parent().unwrap().join("src");
@@ -75,11 +97,13 @@ fn main() {
                     }
                 }
 
+                write_empty_discover_report(&event_types, None);
                 write_empty_bindings_with_events(event_types);
                 return;
             }
         }
     }
+    write_empty_discover_report(&[], None);
     write_empty_bindings_with_events(Vec::new());
 }
 
@@ -192,6 +216,7 @@ fn generate_bindings_for_manifest(manifest_path: &Path) {
         Ok(content) => content,
         Err(e) => {
             eprintln!("Failed to read manifest: {}", e);
+            write_empty_discover_report(&[], None);
             write_empty_bindings_with_events(Vec::new());
             return;
         }
@@ -201,24 +226,79 @@ fn generate_bindings_for_manifest(manifest_path: &Path) {
         Ok(m) => m,
         Err(e) => {
             eprintln!("Failed to parse manifest: {}", e);
+            write_empty_discover_report(&[], None);
             write_empty_bindings_with_events(Vec::new());
             return;
         }
     };
 
+    // Extract the package name from the manifest
+    let parent_crate_name = manifest
+        .get("package")
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+        .unwrap_or("unknown_crate")
+        .to_string();
+
     // Get types from metadata
     let types_to_expose = get_types_from_metadata(&manifest);
 
-    if types_to_expose.is_empty() {
+    // Auto-discover #[derive(Resource)] structs in workspace members so new resources
+    // don't need to be added to the manual list (opt out via `exclude`)
+    let excluded_resources = get_excluded_resources_from_metadata(&manifest);
+    let ignored_impl_returns = get_ignored_impl_returns_from_metadata(&manifest);
+    let discovered_resources: Vec<(TypeSpec, PathBuf)> =
+        discover_workspace_resource_types(&parent_crate_name, &excluded_resources)
+            .into_iter()
+            .filter(|(spec, _)| {
+                !types_to_expose
+                    .iter()
+                    .any(|manual| manual.type_name == spec.type_name)
+            })
+            .collect();
+    for (spec, _) in &discovered_resources {
+        println!(
+            "cargo:warning=  🔍 Auto-discovered resource {}",
+            spec.full_path
+        );
+    }
+
+    if types_to_expose.is_empty() && discovered_resources.is_empty() {
         println!("cargo:warning=No types specified in [package.metadata.lua_resources]");
+        write_empty_discover_report(&[], Some((&manifest, manifest_path)));
         write_empty_bindings_with_events(Vec::new());
         return;
     }
 
-    // Generate bindings for each type
+    let mut report = DiscoverReport::default();
+    report.resources = types_to_expose
+        .iter()
+        .map(|spec| (spec.full_path.clone(), "metadata"))
+        .chain(
+            discovered_resources
+                .iter()
+                .map(|(spec, _)| (spec.full_path.clone(), "auto-discovered")),
+        )
+        .collect();
+
+    // Generate bindings for each type (manual list first, then auto-discovered resources)
     let mut all_bindings = Vec::new();
-    for type_spec in types_to_expose {
-        match generate_bindings_for_type(&type_spec) {
+    let specs_with_sources = types_to_expose
+        .into_iter()
+        .map(|spec| (spec, None))
+        .chain(
+            discovered_resources
+                .into_iter()
+                .map(|(spec, source)| (spec, Some(source))),
+        );
+    for (type_spec, source_path) in specs_with_sources {
+        let result = match &source_path {
+            Some(source_path) => {
+                generate_bindings_for_type_in_file(&type_spec, source_path, &ignored_impl_returns)
+            }
+            None => generate_bindings_for_type(&type_spec, &ignored_impl_returns),
+        };
+        match result {
             Ok(bindings) => {
                 println!(
                     "cargo:warning=✓ Generated bindings for {}",
@@ -238,6 +318,10 @@ fn generate_bindings_for_manifest(manifest_path: &Path) {
     // Process constructors
     let constructors_to_expose = get_constructors_from_metadata(&manifest);
     let mut all_constructor_bindings = Vec::new();
+    report.constructors = constructors_to_expose
+        .iter()
+        .map(|spec| spec.full_path.clone())
+        .collect();
 
     for constructor_spec in constructors_to_expose {
         match generate_bindings_for_constructor(&constructor_spec) {
@@ -262,7 +346,7 @@ fn generate_bindings_for_manifest(manifest_path: &Path) {
     //
     // RUNTIME-BASED: We collect TYPE NAMES only (not compile-time paths)
     // The runtime will look up each name in TypeRegistry and register if found
-    let discovered_assets = discover_asset_types();
+    let (discovered_assets, discovered_constructors) = get_discovered_assets_and_constructors();
 
     // Collect just the type names for runtime lookup (no compile-time paths)
     let asset_type_names: Vec<String> = discovered_assets
@@ -275,10 +359,6 @@ fn generate_bindings_for_manifest(manifest_path: &Path) {
         asset_type_names.len()
     );
 
-    // Auto-discover constructors for asset types (new_*, from_*, etc.)
-    // These are used for opaque types that can't be created via reflection
-    let discovered_constructors = discover_asset_constructors(&discovered_assets);
-
     let all_discovered_bitflags: Vec<DiscoveredBitflags> = Vec::new();
 
     // Get parent crate's src directory
@@ -369,13 +449,20 @@ fn generate_bindings_for_manifest(manifest_path: &Path) {
         }
     }
 
-    // Extract the package name from the manifest
-    let parent_crate_name = manifest
-        .get("package")
-        .and_then(|p| p.get("name"))
-        .and_then(|n| n.as_str())
-        .unwrap_or("unknown_crate")
-        .to_string();
+    // Workspace #[derive(Event)] types are opt-in since scanning every member is slow
+    let workspace_events = if get_include_workspace_events_from_metadata(&manifest) {
+        discover_workspace_events(&parent_crate_name, &manifest)
+    } else {
+        Vec::new()
+    };
+
+    report.asset_types = discovered_assets.clone();
+    report.asset_constructors = discovered_constructors.clone();
+    report.entity_wrappers = discovered_entity_wrappers.clone();
+    report.systemparams = discovered_systemparams.clone();
+    report.systemparam_methods = discovered_systemparam_methods.clone();
+    report.events = event_types.clone();
+    write_discover_report(&report, Some((&manifest, manifest_path)));
 
     // Write generated code to parent crate's src directory
     // Now with simplified signature - asset_type_names for runtime registration
@@ -393,6 +480,7 @@ fn generate_bindings_for_manifest(manifest_path: &Path) {
         discovered_component_methods,
         discovered_static_methods,
         &lua_methods_config,
+        workspace_events,
         &parent_src_dir,
         &parent_crate_name,
     );
@@ -402,6 +490,209 @@ fn generate_bindings_for_manifest(manifest_path: &Path) {
     write_empty_bindings_with_events(event_types);
 }
 
+/// File name of the discovery summary written to OUT_DIR (and optionally the project root)
+const DISCOVER_REPORT_FILE: &str = "DISCOVER_REPORT.md";
+
+/// Everything the build script generated bindings for, collected for DISCOVER_REPORT.md
+#[derive(Default)]
+struct DiscoverReport {
+    /// (full path, "metadata" or "auto-discovered")
+    resources: Vec<(String, &'static str)>,
+    constructors: Vec<String>,
+    asset_types: Vec<DiscoveredAssetType>,
+    asset_constructors: Vec<DiscoveredAssetConstructor>,
+    entity_wrappers: Vec<DiscoveredEntityWrapper>,
+    systemparams: Vec<DiscoveredSystemParam>,
+    systemparam_methods: Vec<DiscoveredSystemParamMethod>,
+    events: Vec<String>,
+}
+
+/// Whether [package.metadata.lua_build] write_report = true is set in the manifest
+fn get_write_report_from_metadata(manifest: &toml::Value) -> bool {
+    manifest
+        .get("package")
+        .and_then(|p| p.get("metadata"))
+        .and_then(|m| m.get("lua_build"))
+        .and_then(|lb| lb.get("write_report"))
+        .and_then(|w| w.as_bool())
+        .unwrap_or(false)
+}
+
+/// Whether [package.metadata.lua_build] include_workspace_events = true is set in the manifest
+/// Off by default: scanning every workspace member's sources slows down the build
+fn get_include_workspace_events_from_metadata(manifest: &toml::Value) -> bool {
+    manifest
+        .get("package")
+        .and_then(|p| p.get("metadata"))
+        .and_then(|m| m.get("lua_build"))
+        .and_then(|lb| lb.get("include_workspace_events"))
+        .and_then(|w| w.as_bool())
+        .unwrap_or(false)
+}
+
+/// Escape a value for use inside a Markdown table cell
+fn report_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Append a Markdown table section; rows are sorted so the file diffs cleanly between builds
+fn push_report_table(out: &mut String, title: &str, headers: &[&str], mut rows: Vec<Vec<String>>) {
+    rows.sort();
+    rows.dedup();
+    out.push_str(&format!("## {} ({})\n\n", title, rows.len()));
+    if rows.is_empty() {
+        out.push_str("_None_\n\n");
+        return;
+    }
+    out.push_str(&format!("| {} |\n", headers.join(" | ")));
+    out.push_str(&format!("|{}\n", " --- |".repeat(headers.len())));
+    for row in rows {
+        let cells: Vec<String> = row.iter().map(|c| report_cell(c)).collect();
+        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    out.push('\n');
+}
+
+/// Render the report as Markdown
+/// Contains no timestamps or absolute paths so the output only changes when the bindings do
+fn render_discover_report(report: &DiscoverReport) -> String {
+    let yes_no = |b: bool| if b { "yes" } else { "no" }.to_string();
+    let mut out = String::from(
+        "# Lua Binding Discovery Report\n\n\
+         Generated by the bevy-lua-ecs build script. Do not edit by hand.\n\n",
+    );
+
+    push_report_table(
+        &mut out,
+        "Resources",
+        &["Type", "Source"],
+        report
+            .resources
+            .iter()
+            .map(|(path, source)| vec![path.clone(), source.to_string()])
+            .collect(),
+    );
+    push_report_table(
+        &mut out,
+        "Constructors",
+        &["Function"],
+        report.constructors.iter().map(|c| vec![c.clone()]).collect(),
+    );
+    push_report_table(
+        &mut out,
+        "Asset Types",
+        &["Type", "Path", "Clone", "Generic"],
+        report
+            .asset_types
+            .iter()
+            .map(|a| {
+                vec![
+                    a.type_name.clone(),
+                    a.full_path.clone(),
+                    yes_no(a.has_clone),
+                    yes_no(a.is_generic),
+                ]
+            })
+            .collect(),
+    );
+    push_report_table(
+        &mut out,
+        "Asset Constructors",
+        &["Type", "Method", "Parameters"],
+        report
+            .asset_constructors
+            .iter()
+            .map(|c| {
+                let params: Vec<String> = c
+                    .params
+                    .iter()
+                    .map(|p| format!("{}: {}", p.name, p.type_str))
+                    .collect();
+                vec![c.type_name.clone(), c.method_name.clone(), params.join(", ")]
+            })
+            .collect(),
+    );
+    push_report_table(
+        &mut out,
+        "Entity Wrappers",
+        &["Type", "Path", "Reflect Component"],
+        report
+            .entity_wrappers
+            .iter()
+            .map(|w| {
+                vec![
+                    w.type_name.clone(),
+                    w.full_path.clone(),
+                    yes_no(w.has_reflect_component),
+                ]
+            })
+            .collect(),
+    );
+    push_report_table(
+        &mut out,
+        "SystemParams",
+        &["Type", "Path", "Methods"],
+        report
+            .systemparams
+            .iter()
+            .map(|sp| {
+                let mut methods: Vec<&str> = report
+                    .systemparam_methods
+                    .iter()
+                    .filter(|m| m.param_type == sp.type_name)
+                    .map(|m| m.method_name.as_str())
+                    .collect();
+                methods.sort();
+                vec![sp.type_name.clone(), sp.full_path.clone(), methods.join(", ")]
+            })
+            .collect(),
+    );
+    push_report_table(
+        &mut out,
+        "Events",
+        &["Type"],
+        report.events.iter().map(|e| vec![e.clone()]).collect(),
+    );
+    out
+}
+
+/// Write DISCOVER_REPORT.md to OUT_DIR, and next to the parent manifest when
+/// [package.metadata.lua_build] write_report = true
+/// Files are only rewritten when their contents change
+fn write_discover_report(report: &DiscoverReport, manifest: Option<(&toml::Value, &Path)>) {
+    let content = render_discover_report(report);
+    let mut targets = Vec::new();
+    if let Ok(out_dir) = env::var("OUT_DIR") {
+        targets.push(PathBuf::from(out_dir).join(DISCOVER_REPORT_FILE));
+    }
+    if let Some((manifest, manifest_path)) = manifest {
+        if get_write_report_from_metadata(manifest) {
+            if let Some(project_root) = manifest_path.parent() {
+                targets.push(project_root.join(DISCOVER_REPORT_FILE));
+            }
+        }
+    }
+
+    for target in targets {
+        if fs::read_to_string(&target).ok().as_deref() == Some(content.as_str()) {
+            continue;
+        }
+        if let Err(e) = fs::write(&target, &content) {
+            println!("cargo:warning=⚠ Failed to write {:?}: {}", target, e);
+        }
+    }
+}
+
+/// Report for builds that generate no bindings (no parent manifest, unreadable manifest, no types)
+/// Written on these paths too, otherwise a missing report would force regeneration on every build
+fn write_empty_discover_report(event_types: &[String], manifest: Option<(&toml::Value, &Path)>) {
+    let report = DiscoverReport {
+        events: event_types.to_vec(),
+        ..Default::default()
+    };
+    write_discover_report(&report, manifest);
+}
+
 fn generate_event_registrations(manifest: &toml::Value) -> Vec<String> {
     let events_array = manifest
         .get("package")
@@ -668,6 +959,175 @@ fn parse_constructor_spec(full_path: &str) -> Option<ConstructorSpec> {
     })
 }
 
+/// Get resource types to skip during auto-discovery from
+/// [package.metadata.lua_resources] exclude = [...] (type names or full paths)
+fn get_excluded_resources_from_metadata(manifest: &toml::Value) -> Vec<String> {
+    manifest
+        .get("package")
+        .and_then(|p| p.get("metadata"))
+        .and_then(|m| m.get("lua_resources"))
+        .and_then(|lr| lr.get("exclude"))
+        .and_then(|e| e.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Get resource methods returning `impl Trait` that should get no Lua binding at all from
+/// [package.metadata.lua_resources] ignore_impl_returns = [...] (method names)
+fn get_ignored_impl_returns_from_metadata(manifest: &toml::Value) -> Vec<String> {
+    manifest
+        .get("package")
+        .and_then(|p| p.get("metadata"))
+        .and_then(|m| m.get("lua_resources"))
+        .and_then(|lr| lr.get("ignore_impl_returns"))
+        .and_then(|e| e.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A workspace member crate found through the workspace root's Cargo.toml
+struct WorkspaceMember {
+    /// Package name from the member's Cargo.toml with '-' replaced by '_' (e.g., "hello")
+    crate_name: String,
+    /// Prefix for generated Rust paths: "crate" for the parent crate, otherwise `crate_name`
+    path_root: String,
+    /// Member directory containing its Cargo.toml
+    dir: PathBuf,
+}
+
+/// List the members of the workspace this crate lives in
+fn workspace_members(parent_crate_name: &str) -> Vec<WorkspaceMember> {
+    let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") else {
+        return Vec::new();
+    };
+    let Some(workspace_root) = PathBuf::from(&manifest_dir).parent().map(Path::to_path_buf) else {
+        return Vec::new();
+    };
+    let Ok(content) = fs::read_to_string(workspace_root.join("Cargo.toml")) else {
+        return Vec::new();
+    };
+    let Ok(workspace_manifest) = toml::from_str::<toml::Value>(&content) else {
+        return Vec::new();
+    };
+    let Some(members) = workspace_manifest
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+    else {
+        return Vec::new();
+    };
+
+    members
+        .iter()
+        .filter_map(|m| m.as_str())
+        .map(|member_name| (member_name, workspace_root.join(member_name)))
+        .filter(|(_, dir)| dir.exists())
+        .map(|(member_name, dir)| {
+            // Directory names needn't match package names (e.g. "Hello" -> "hello")
+            let package_name = fs::read_to_string(dir.join("Cargo.toml"))
+                .ok()
+                .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+                .and_then(|manifest| {
+                    manifest
+                        .get("package")
+                        .and_then(|p| p.get("name"))
+                        .and_then(|n| n.as_str())
+                        .map(str::to_string)
+                })
+                .unwrap_or_else(|| member_name.to_string());
+            let crate_name = package_name.replace('-', "_");
+            let path_root = if crate_name == parent_crate_name.replace('-', "_") {
+                "crate".to_string()
+            } else {
+                crate_name.clone()
+            };
+            WorkspaceMember { crate_name, path_root, dir }
+        })
+        .collect()
+}
+
+/// Package names (with '-' replaced by '_') of the crates `manifest` depends on, mapped to the
+/// name the crate is used under (differs for `foo = { package = "bar" }` renames)
+fn dependency_crate_names(manifest: &toml::Value) -> std::collections::HashMap<String, String> {
+    // Only regular dependencies: the generated code is part of the parent's normal build
+    let Some(dependencies) = manifest.get("dependencies").and_then(|d| d.as_table()) else {
+        return std::collections::HashMap::new();
+    };
+    dependencies
+        .iter()
+        .map(|(dep_name, spec)| {
+            let package = spec.get("package").and_then(|p| p.as_str()).unwrap_or(dep_name);
+            (package.replace('-', "_"), dep_name.replace('-', "_"))
+        })
+        .collect()
+}
+
+/// Auto-discover `#[derive(Resource)]` structs in workspace members
+/// Returns each resource's TypeSpec with the source file it was found in.
+/// Types in the parent crate itself are addressed via `crate::` since the
+/// generated bindings are compiled into that crate.
+fn discover_workspace_resource_types(
+    parent_crate_name: &str,
+    exclude: &[String],
+) -> Vec<(TypeSpec, PathBuf)> {
+    let mut results: Vec<(TypeSpec, PathBuf)> = Vec::new();
+
+    for member in workspace_members(parent_crate_name) {
+        let WorkspaceMember { crate_name, path_root, dir: member_dir } = member;
+        // This crate's own resources already have dedicated world APIs
+        if env::var("CARGO_PKG_NAME").is_ok_and(|own| own.replace('-', "_") == crate_name) {
+            continue;
+        }
+
+        let api_scan = scan_public_api(&member_dir, &crate_name);
+        for module in &api_scan.modules {
+            let Ok(source) = fs::read_to_string(&module.file_path) else {
+                continue;
+            };
+            let Ok(syntax_tree) = syn::parse_file(&source) else {
+                continue;
+            };
+
+            for item in &syntax_tree.items {
+                let Item::Struct(item_struct) = item else {
+                    continue;
+                };
+                // Generic resources can't be named without type parameters
+                if !matches!(item_struct.vis, Visibility::Public(_))
+                    || !item_struct.generics.params.is_empty()
+                    || !collect_derive_names(&item_struct.attrs).iter().any(|d| d == "Resource")
+                {
+                    continue;
+                }
+
+                let type_name = item_struct.ident.to_string();
+                let full_path = build_type_path(&path_root, &module.module_path, &type_name);
+                if exclude.iter().any(|e| *e == type_name || *e == full_path)
+                    || results.iter().any(|(spec, _)| spec.type_name == type_name)
+                {
+                    continue;
+                }
+
+                if let Some(spec) = parse_type_spec(&full_path) {
+                    results.push((spec, module.file_path.clone()));
+                }
+            }
+        }
+    }
+
+    results
+}
+
 /// Get entity_components from TOML metadata - these are newtypes wrapping Entity
 /// Example: entity_components = ["bevy_ui::ui_node::UiTargetCamera"]
 fn get_entity_components_from_metadata(manifest: &toml::Value) -> Vec<String> {
@@ -1016,7 +1476,7 @@ fn scan_public_api(crate_root: &Path, crate_name: &str) -> PublicApiScan {
     // Parse lib.rs for pub use re-exports and pub mod declarations
     if let Ok(content) = fs::read_to_string(&lib_path) {
         // Collect re-exports from lib.rs (these are the shortest paths)
-        scan_pub_use_exports(&content, crate_name, "", &mut re_exports);
+        scan_pub_use_exports_cached(&lib_path, crate_name, "", &mut re_exports);
         
         // Recursively scan public modules WITH FEATURE AWARENESS
         scan_public_mods_recursive_with_features(
@@ -1029,9 +1489,7 @@ fn scan_public_api(crate_root: &Path, crate_name: &str) -> PublicApiScan {
         
         // Also collect re-exports from public modules (but don't overwrite shorter paths)
         for module in &modules {
-            if let Ok(mod_content) = fs::read_to_string(&module.file_path) {
-                scan_pub_use_exports(&mod_content, crate_name, &module.module_path, &mut re_exports);
-            }
+            scan_pub_use_exports_cached(&module.file_path, crate_name, &module.module_path, &mut re_exports);
         }
         
         // PRELUDE FALLBACK: If crate has a prelude module, assume all types can be 
@@ -1608,13 +2066,16 @@ fn discover_components_from_public_api(
         if let Ok(content) = fs::read_to_string(&module.file_path) {
             // Parse for types with the specified derive
             let types = parse_source_for_derive_types(&content, derive_name);
+            let reflect_components = parse_reflect_component_types_from_source(&content);
             
             for (type_name, has_debug) in types {
                 let full_path = build_type_path(crate_name, &module.module_path, &type_name);
                 results.push(DiscoveredEntityWrapper {
+                    has_reflect_component: reflect_components.contains(&type_name),
                     type_name: type_name.clone(),
                     full_path,
                     has_debug,
+                    string_enum: false,
                 });
             }
 
@@ -1712,7 +2173,7 @@ fn parse_source_for_derive_types(source: &str, derive_name: &str) -> Vec<(String
 // =============================================================================
 
 /// Discovered entity wrapper component (newtype around Entity)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct DiscoveredEntityWrapper {
     /// Full type path (e.g., "bevy_ui::ui_node::UiTargetCamera")
     full_path: String,
@@ -1720,10 +2181,16 @@ struct DiscoveredEntityWrapper {
     type_name: String,
     /// Whether this type derives Debug (for serialization)
     has_debug: bool,
+    /// Enum deriving Reflect + Default + Clone - settable from Lua as a variant name string
+    /// via `parse_enum_from_string`
+    string_enum: bool,
+    /// Has `#[reflect(Component)]` - only these are registered by name through the TypeRegistry
+    /// at runtime; the rest go through the non-reflected component paths
+    has_reflect_component: bool,
 }
 
 /// Discovered asset type (implements Asset trait)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct DiscoveredAssetType {
     /// Full type path (e.g., "bevy_mesh::mesh::Mesh")
     full_path: String,
@@ -1740,7 +2207,7 @@ struct DiscoveredAssetType {
 }
 
 /// A constructor parameter with name and type
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct ConstructorParam {
     /// Parameter name (e.g., "width")
     name: String,
@@ -1749,7 +2216,7 @@ struct ConstructorParam {
 }
 
 /// Discovered asset constructor method
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct DiscoveredAssetConstructor {
     /// Full type path (e.g., "bevy_image::image::Image")
     type_path: String,
@@ -1759,6 +2226,9 @@ struct DiscoveredAssetConstructor {
     method_name: String,
     /// Parameters with names and types
     params: Vec<ConstructorParam>,
+    /// Whether the constructor returns Self directly, Result<Self, E> or Option<Self>
+    #[serde(default)]
+    returns: SelfReturn,
 }
 
 /// Discovered Handle<T> newtype wrapper (e.g., ImageRenderTarget wraps Handle<Image>)
@@ -1854,6 +2324,18 @@ struct SystemParamCache {
     methods: Vec<DiscoveredSystemParamMethod>,
 }
 
+/// Cache for discovered asset types and their constructors
+/// Uses the same Cargo.lock hash invalidation as SystemParamCache
+#[derive(Debug, Serialize, Deserialize)]
+struct AssetDiscoveryCache {
+    /// Hash of Cargo.lock to detect dependency changes
+    cargo_lock_hash: String,
+    /// Discovered asset types
+    asset_types: Vec<DiscoveredAssetType>,
+    /// Discovered constructors for those asset types
+    constructors: Vec<DiscoveredAssetConstructor>,
+}
+
 // =============================================================================
 // COMPONENT METHOD DISCOVERY
 // Auto-discover methods on Component types (e.g., Transform::looking_at)
@@ -2890,55 +3372,180 @@ fn get_discovered_systemparams_and_methods(
     (systemparams, methods)
 }
 
-/// Resolve a short type name to its full Bevy path
-/// For compile-time code generation, we need fully qualified paths
-fn resolve_short_type_to_full_path(short_name: &str) -> Option<String> {
-    // Map of short names to full Bevy paths
-    // These are the most common types found in SystemParam method signatures
-    let mappings: &[(&str, &str)] = &[
-        // Math types
-        ("Ray3d", "bevy::math::Ray3d"),
-        ("Dir3", "bevy::math::Dir3"),
-        ("Dir3A", "bevy::math::Dir3A"),
-        ("Vec2", "bevy::math::Vec2"),
-        ("Vec3", "bevy::math::Vec3"),
-        ("Vec3A", "bevy::math::Vec3A"),
-        ("Vec4", "bevy::math::Vec4"),
-        ("Quat", "bevy::math::Quat"),
-        ("Mat4", "bevy::math::Mat4"),
-        // Picking types
-        (
-            "MeshRayCastSettings",
-            "bevy::picking::mesh_picking::ray_cast::MeshRayCastSettings",
-        ),
-        (
-            "RayCastSettings",
-            "bevy::picking::mesh_picking::ray_cast::RayCastSettings",
-        ),
-        // ECS types
-        ("Entity", "bevy::ecs::entity::Entity"),
-        // Common types - these are primitives and don't need full paths
-        // Just return them as-is if they parse ok
-    ];
+/// Get cache file path for asset discovery
+fn get_asset_discovery_cache_path() -> Option<PathBuf> {
+    let out_dir = env::var("OUT_DIR").ok()?;
+    Some(PathBuf::from(out_dir).join("asset_discovery_cache.json"))
+}
+
+/// Try to load asset discovery cache from disk
+fn load_asset_discovery_cache() -> Option<AssetDiscoveryCache> {
+    let cache_path = get_asset_discovery_cache_path()?;
+    let content = fs::read_to_string(&cache_path).ok()?;
+    let cache: AssetDiscoveryCache = serde_json::from_str(&content).ok()?;
+
+    // Check if cache is still valid (Cargo.lock hasn't changed)
+    if cache.cargo_lock_hash == compute_cargo_lock_hash() {
+        println!(
+            "cargo:warning=[CACHE] Loaded {} asset types and {} constructors from cache",
+            cache.asset_types.len(),
+            cache.constructors.len()
+        );
+        Some(cache)
+    } else {
+        println!("cargo:warning=[CACHE] Asset discovery cache invalidated (Cargo.lock changed)");
+        None
+    }
+}
+
+/// Save asset discovery cache to disk
+fn save_asset_discovery_cache(
+    asset_types: &[DiscoveredAssetType],
+    constructors: &[DiscoveredAssetConstructor],
+) {
+    let Some(cache_path) = get_asset_discovery_cache_path() else {
+        return;
+    };
+
+    let cache = AssetDiscoveryCache {
+        cargo_lock_hash: compute_cargo_lock_hash(),
+        asset_types: asset_types.to_vec(),
+        constructors: constructors.to_vec(),
+    };
 
-    for (short, full) in mappings {
-        if short_name == *short {
-            return Some(full.to_string());
+    if let Ok(json) = serde_json::to_string_pretty(&cache) {
+        if fs::write(&cache_path, json).is_ok() {
+            println!(
+                "cargo:warning=[CACHE] Saved {} asset types and {} constructors to cache",
+                asset_types.len(),
+                constructors.len()
+            );
         }
     }
+}
 
-    // If it already contains ::, assume it's a full path
-    if short_name.contains("::") {
-        return Some(short_name.to_string());
+/// Get discovered asset types and constructors (from cache or by discovery)
+fn get_discovered_assets_and_constructors(
+) -> (Vec<DiscoveredAssetType>, Vec<DiscoveredAssetConstructor>) {
+    // Try cache first
+    if let Some(cache) = load_asset_discovery_cache() {
+        return (cache.asset_types, cache.constructors);
     }
 
-    // Primitive types don't need resolution
-    if is_primitive_type(short_name) {
-        return Some(short_name.to_string());
+    // Cache miss - run discovery
+    println!("cargo:warning=[CACHE] Cache miss - running asset discovery...");
+    let asset_types = discover_asset_types();
+
+    // Auto-discover constructors for asset types (new_*, from_*, etc.)
+    // These are used for opaque types that can't be created via reflection
+    let constructors = discover_asset_constructors(&asset_types);
+
+    // Save to cache for next time
+    save_asset_discovery_cache(&asset_types, &constructors);
+
+    (asset_types, constructors)
+}
+
+/// Per-file results of the asset type, entity wrapper and `pub use` scans
+/// Files whose size and modification time match their entry are not re-parsed. Invalidated as
+/// a whole when Cargo.lock changes, like SystemParamCache.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FileTimestampCache {
+    /// Hash of Cargo.lock to detect dependency changes
+    cargo_lock_hash: String,
+    /// Asset types found in each scanned file, keyed by file path
+    #[serde(default)]
+    asset_types: std::collections::HashMap<String, FileScanEntry<DiscoveredAssetType>>,
+    /// Entity wrappers found in each scanned public module file, keyed by file path
+    #[serde(default)]
+    entity_wrappers: std::collections::HashMap<String, FileScanEntry<DiscoveredEntityWrapper>>,
+    /// `pub use` re-exports (type name, path) found in each public module file, keyed by file path
+    #[serde(default)]
+    pub_use_exports: std::collections::HashMap<String, FileScanEntry<(String, String)>>,
+}
+
+/// Get cache file path for per-file scan results
+fn get_file_timestamp_cache_path() -> Option<PathBuf> {
+    let out_dir = env::var("OUT_DIR").ok()?;
+    Some(PathBuf::from(out_dir).join("file_timestamp_cache.json"))
+}
+
+/// Load the per-file scan cache, or an empty one if it is missing or Cargo.lock changed
+fn load_file_timestamp_cache() -> FileTimestampCache {
+    let cargo_lock_hash = compute_cargo_lock_hash();
+    let cached = get_file_timestamp_cache_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<FileTimestampCache>(&content).ok());
+    match cached {
+        Some(cache) if cache.cargo_lock_hash == cargo_lock_hash => cache,
+        Some(_) => {
+            println!("cargo:warning=[CACHE] File timestamp cache invalidated (Cargo.lock changed)");
+            FileTimestampCache { cargo_lock_hash, ..Default::default() }
+        }
+        None => FileTimestampCache { cargo_lock_hash, ..Default::default() },
     }
+}
 
-    // Unknown type - can't resolve
-    None
+/// Save the per-file scan cache to disk, along with the `pub use` results gathered so far
+/// by `scan_public_api` in this build
+fn save_file_timestamp_cache(cache: &mut FileTimestampCache) {
+    let Some(cache_path) = get_file_timestamp_cache_path() else {
+        return;
+    };
+    cache.pub_use_exports = get_pub_use_export_cache().lock().unwrap().clone();
+    cache.pub_use_exports.retain(|path, _| Path::new(path).exists());
+    if let Ok(json) = serde_json::to_string(cache) {
+        let _ = fs::write(&cache_path, json);
+    }
+}
+
+/// `pub use` scan results shared by every `scan_public_api` call in this build
+/// Several discovery passes scan the same crates, so this outlives any one of them.
+fn get_pub_use_export_cache(
+) -> &'static std::sync::Mutex<std::collections::HashMap<String, FileScanEntry<(String, String)>>> {
+    use std::sync::{Mutex, OnceLock};
+    static CACHE: OnceLock<Mutex<std::collections::HashMap<String, FileScanEntry<(String, String)>>>> =
+        OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(load_file_timestamp_cache().pub_use_exports))
+}
+
+/// `scan_pub_use_exports` for one file, reusing the cached results when it is unchanged
+fn scan_pub_use_exports_cached(
+    path: &Path,
+    crate_name: &str,
+    module_path: &str,
+    re_exports: &mut std::collections::HashMap<String, String>,
+) {
+    // Export paths depend on where the module sits, not just the file
+    let context = {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        (crate_name, module_path).hash(&mut hasher);
+        hasher.finish()
+    };
+    let exports = scan_file_cached(
+        &mut get_pub_use_export_cache().lock().unwrap(),
+        &mut std::collections::HashSet::new(),
+        &mut FileScanStats::default(),
+        path,
+        context,
+        |source, found| {
+            let mut file_exports = std::collections::HashMap::new();
+            scan_pub_use_exports(source, crate_name, module_path, &mut file_exports);
+            found.extend(file_exports);
+        },
+    );
+
+    // Only insert if this path is shorter than existing
+    for (type_name, export_path) in exports {
+        re_exports.entry(type_name)
+            .and_modify(|existing| {
+                if export_path.len() < existing.len() {
+                    *existing = export_path.clone();
+                }
+            })
+            .or_insert(export_path);
+    }
 }
 
 /// Auto-discover entity wrapper components from bevy crates and workspace members
@@ -2972,6 +3579,11 @@ fn discover_entity_wrapper_components() -> Vec<DiscoveredEntityWrapper> {
         dependencies.len()
     );
 
+    // Files unchanged since the last build reuse their cached results
+    let mut cache = load_file_timestamp_cache();
+    let mut seen = std::collections::HashSet::new();
+    let mut stats = FileScanStats::default();
+
     // Scan bevy_* dependency crates using PUBLIC API SCANNING
     for index_entry in fs::read_dir(&registry_src).into_iter().flatten().flatten() {
         let index_dir = index_entry.path();
@@ -2993,32 +3605,14 @@ fn discover_entity_wrapper_components() -> Vec<DiscoveredEntityWrapper> {
 
             // Use PUBLIC API SCANNING - scan modules AND collect re-exports
             let api_scan = scan_public_api(&crate_dir, base_crate);
-            for module in &api_scan.modules {
-                if let Ok(content) = fs::read_to_string(&module.file_path) {
-                    // Parse all Component types from this public module
-                    parse_entity_wrappers_from_source_with_path(
-                        &content,
-                        base_crate,
-                        &module.module_path,
-                        &api_scan.re_exports,
-                        &mut wrappers,
-                    );
-                    parse_component_enums_from_source_with_path(
-                        &content,
-                        base_crate,
-                        &module.module_path,
-                        &api_scan.re_exports,
-                        &mut wrappers,
-                    );
-                    parse_component_structs_from_source_with_path(
-                        &content,
-                        base_crate,
-                        &module.module_path,
-                        &api_scan.re_exports,
-                        &mut wrappers,
-                    );
-                }
-            }
+            scan_public_modules_for_entity_wrappers(
+                &api_scan,
+                base_crate,
+                &mut cache,
+                &mut seen,
+                &mut stats,
+                &mut wrappers,
+            );
         }
     }
 
@@ -3040,31 +3634,14 @@ fn discover_entity_wrapper_components() -> Vec<DiscoveredEntityWrapper> {
                                 if member_dir.exists() {
                                     // Use PUBLIC API SCANNING for workspace members too
                                     let api_scan = scan_public_api(&member_dir, member_name);
-                                    for module in &api_scan.modules {
-                                        if let Ok(source) = fs::read_to_string(&module.file_path) {
-                                            parse_entity_wrappers_from_source_with_path(
-                                                &source,
-                                                member_name,
-                                                &module.module_path,
-                                                &api_scan.re_exports,
-                                                &mut wrappers,
-                                            );
-                                            parse_component_enums_from_source_with_path(
-                                                &source,
-                                                member_name,
-                                                &module.module_path,
-                                                &api_scan.re_exports,
-                                                &mut wrappers,
-                                            );
-                                            parse_component_structs_from_source_with_path(
-                                                &source,
-                                                member_name,
-                                                &module.module_path,
-                                                &api_scan.re_exports,
-                                                &mut wrappers,
-                                            );
-                                        }
-                                    }
+                                    scan_public_modules_for_entity_wrappers(
+                                        &api_scan,
+                                        member_name,
+                                        &mut cache,
+                                        &mut seen,
+                                        &mut stats,
+                                        &mut wrappers,
+                                    );
                                 }
                             }
                         }
@@ -3074,9 +3651,30 @@ fn discover_entity_wrapper_components() -> Vec<DiscoveredEntityWrapper> {
         }
     }
 
+    cache.entity_wrappers.retain(|path, _| seen.contains(path));
+    save_file_timestamp_cache(&mut cache);
+    println!(
+        "cargo:warning=[CACHE] Entity wrapper scan: {} files reused, {} parsed",
+        stats.reused, stats.parsed
+    );
+
     // Deduplicate by type_name - we only care about the short name for runtime lookup
-    let mut seen = std::collections::HashSet::new();
-    wrappers.retain(|w| seen.insert(w.type_name.clone()));
+    // The derive-line scan finds enums too, so keep the string_enum flag from the syn-based enum scan
+    let mut seen: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    let mut deduped: Vec<DiscoveredEntityWrapper> = Vec::new();
+    for wrapper in wrappers {
+        match seen.get(&wrapper.type_name) {
+            Some(&index) => {
+                deduped[index].string_enum |= wrapper.string_enum;
+                deduped[index].has_reflect_component |= wrapper.has_reflect_component;
+            }
+            None => {
+                seen.insert(wrapper.type_name.clone(), deduped.len());
+                deduped.push(wrapper);
+            }
+        }
+    }
+    let wrappers = deduped;
 
     println!(
         "cargo:warning=  ✓ Auto-discovered {} entity wrapper type names (via PUBLIC API scanning)",
@@ -3092,6 +3690,58 @@ fn discover_entity_wrapper_components() -> Vec<DiscoveredEntityWrapper> {
     wrappers
 }
 
+/// Parse all Component types from a crate's public modules, skipping files unchanged since
+/// the last build
+fn scan_public_modules_for_entity_wrappers(
+    api_scan: &PublicApiScan,
+    crate_name: &str,
+    cache: &mut FileTimestampCache,
+    seen: &mut std::collections::HashSet<String>,
+    stats: &mut FileScanStats,
+    results: &mut Vec<DiscoveredEntityWrapper>,
+) {
+    let re_exports_hash = hash_map_context(&api_scan.re_exports);
+    for module in &api_scan.modules {
+        // Type paths depend on the module path and the crate's re-exports, not just the file
+        let context = {
+            use std::hash::{Hash, Hasher};
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            (crate_name, &module.module_path, re_exports_hash).hash(&mut hasher);
+            hasher.finish()
+        };
+        results.extend(scan_file_cached(
+            &mut cache.entity_wrappers,
+            seen,
+            stats,
+            &module.file_path,
+            context,
+            |source, found| {
+                parse_entity_wrappers_from_source_with_path(
+                    source,
+                    crate_name,
+                    &module.module_path,
+                    &api_scan.re_exports,
+                    found,
+                );
+                parse_component_enums_from_source_with_path(
+                    source,
+                    crate_name,
+                    &module.module_path,
+                    &api_scan.re_exports,
+                    found,
+                );
+                parse_component_structs_from_source_with_path(
+                    source,
+                    crate_name,
+                    &module.module_path,
+                    &api_scan.re_exports,
+                    found,
+                );
+            },
+        ));
+    }
+}
+
 /// Parse entity wrappers using a known module path instead of file path
 fn parse_entity_wrappers_from_source_with_path(
     source: &str,
@@ -3102,14 +3752,18 @@ fn parse_entity_wrappers_from_source_with_path(
 ) {
     // Look for #[derive(Component)] followed by pub struct Foo(pub Entity)
     let derive_types = parse_source_for_derive_types(source, "Component");
+    // Second pass: which of those also carry #[reflect(Component)]
+    let reflect_components = parse_reflect_component_types_from_source(source);
     for (type_name, has_debug) in derive_types {
         // Check if this looks like an entity wrapper (tuple struct with Entity)
         // Use re-exports to find shortest path when available
         let full_path = build_type_path_with_reexports(crate_name, module_path, &type_name, re_exports);
         results.push(DiscoveredEntityWrapper {
+            has_reflect_component: reflect_components.contains(&type_name),
             type_name,
             full_path,
             has_debug,
+            string_enum: false,
         });
     }
 
@@ -3136,26 +3790,10 @@ fn parse_component_enums_from_source_with_path(
                 continue;
             }
 
-            // Check for Component and Debug derives
-            let mut has_component = false;
-            let mut has_debug = false;
-            for attr in &item_enum.attrs {
-                if attr.path().is_ident("derive") {
-                    if let Ok(meta) = attr.parse_args::<proc_macro2::TokenStream>() {
-                        let meta_str = meta.to_string();
-                        if meta_str.contains("Component") {
-                            has_component = true;
-                        }
-                        if meta_str.contains("Debug") {
-                            has_debug = true;
-                        }
-                    }
-                }
-            }
-
-            if !has_component {
+            let Some(derives) = parse_component_enum_derives(&item_enum) else {
                 continue;
-            }
+            };
+            let has_debug = derives.has_debug;
 
             let type_name = item_enum.ident.to_string();
 
@@ -3180,6 +3818,8 @@ fn parse_component_enums_from_source_with_path(
                 full_path: final_path,
                 type_name,
                 has_debug,
+                string_enum: derives.string_enum,
+                has_reflect_component: has_reflect_component_attr(&item_enum.attrs),
             });
         }
     }
@@ -3478,6 +4118,8 @@ fn parse_entity_wrappers_from_source(
                     full_path: final_path,
                     type_name,
                     has_debug,
+                    string_enum: false,
+                    has_reflect_component: has_reflect_component_attr(&item_struct.attrs),
                 });
 
             }
@@ -3505,26 +4147,10 @@ fn parse_component_enums_from_source(
                 continue;
             }
 
-            // Check for Component and Debug derives
-            let mut has_component = false;
-            let mut has_debug = false;
-            for attr in &item_enum.attrs {
-                if attr.path().is_ident("derive") {
-                    if let Ok(meta) = attr.parse_args::<proc_macro2::TokenStream>() {
-                        let meta_str = meta.to_string();
-                        if meta_str.contains("Component") {
-                            has_component = true;
-                        }
-                        if meta_str.contains("Debug") {
-                            has_debug = true;
-                        }
-                    }
-                }
-            }
-
-            if !has_component {
+            let Some(derives) = parse_component_enum_derives(&item_enum) else {
                 continue;
-            }
+            };
+            let has_debug = derives.has_debug;
 
             let type_name = item_enum.ident.to_string();
 
@@ -3547,6 +4173,8 @@ fn parse_component_enums_from_source(
                 full_path: final_path,
                 type_name,
                 has_debug,
+                string_enum: derives.string_enum,
+                has_reflect_component: has_reflect_component_attr(&item_enum.attrs),
             });
 
         }
@@ -3627,6 +4255,8 @@ fn parse_component_structs_from_source(
                 full_path: final_path,
                 type_name,
                 has_debug,
+                string_enum: false,
+                has_reflect_component: has_reflect_component_attr(&item_struct.attrs),
             });
         }
     }
@@ -3875,6 +4505,11 @@ fn discover_asset_types() -> Vec<DiscoveredAssetType> {
         dependencies.len()
     );
 
+    // Files unchanged since the last build reuse their cached results
+    let mut cache = load_file_timestamp_cache();
+    let mut seen = std::collections::HashSet::new();
+    let mut stats = FileScanStats::default();
+
     // Scan ALL bevy_* crates in cargo registry
     if let Ok(home) =
         env::var("CARGO_HOME").or_else(|_| env::var("USERPROFILE").map(|p| format!("{}/.cargo", p)))
@@ -3911,7 +4546,14 @@ fn discover_asset_types() -> Vec<DiscoveredAssetType> {
 
                             let crate_src = crate_entry.path().join("src");
                             if crate_src.exists() {
-                                scan_directory_for_asset_types(&crate_src, base_crate, &mut assets);
+                                scan_directory_for_asset_types(
+                                    &crate_src,
+                                    base_crate,
+                                    &mut cache,
+                                    &mut seen,
+                                    &mut stats,
+                                    &mut assets,
+                                );
                             }
                         }
                     }
@@ -3938,6 +4580,9 @@ fn discover_asset_types() -> Vec<DiscoveredAssetType> {
                                     scan_directory_for_asset_types(
                                         &member_src,
                                         member_name,
+                                        &mut cache,
+                                        &mut seen,
+                                        &mut stats,
                                         &mut assets,
                                     );
                                 }
@@ -3949,6 +4594,13 @@ fn discover_asset_types() -> Vec<DiscoveredAssetType> {
         }
     }
 
+    cache.asset_types.retain(|path, _| seen.contains(path));
+    save_file_timestamp_cache(&mut cache);
+    println!(
+        "cargo:warning=[CACHE] Asset type scan: {} files reused, {} parsed",
+        stats.reused, stats.parsed
+    );
+
     // Only filter GENERIC types (contain angle brackets) - these are syntactically invalid
     assets.retain(|asset| {
         let type_name = &asset.type_name;
@@ -3979,21 +4631,29 @@ fn discover_asset_types() -> Vec<DiscoveredAssetType> {
     assets
 }
 
-/// Scan a directory recursively for asset types
+/// Scan a directory recursively for asset types, skipping files unchanged since the last build
 fn scan_directory_for_asset_types(
     dir: &Path,
     crate_name: &str,
+    cache: &mut FileTimestampCache,
+    seen: &mut std::collections::HashSet<String>,
+    stats: &mut FileScanStats,
     results: &mut Vec<DiscoveredAssetType>,
 ) {
     for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
         let path = entry.path();
 
         if path.is_dir() {
-            scan_directory_for_asset_types(&path, crate_name, results);
+            scan_directory_for_asset_types(&path, crate_name, cache, seen, stats, results);
         } else if path.extension().map_or(false, |ext| ext == "rs") {
-            if let Ok(source) = fs::read_to_string(&path) {
-                parse_asset_types_from_source(&source, crate_name, &path, results);
-            }
+            results.extend(scan_file_cached(
+                &mut cache.asset_types,
+                seen,
+                stats,
+                &path,
+                0,
+                |source, found| parse_asset_types_from_source(source, crate_name, &path, found),
+            ));
         }
     }
 }
@@ -4302,10 +4962,10 @@ fn parse_constructors_from_source(
                         continue;
                     }
 
-                    // Check if it returns Self (or the type name)
-                    if !returns_self_or_type(&method.sig.output, &type_name) {
+                    // Check if it returns Self (or the type name), possibly wrapped in Result/Option
+                    let Some(returns) = returns_self_or_type(&method.sig.output, &type_name) else {
                         continue;
-                    }
+                    };
 
                     // Parse parameters (skip &self, &mut self)
                     let params = parse_method_params(&method.sig);
@@ -4320,6 +4980,7 @@ fn parse_constructors_from_source(
                         type_name: type_name.clone(),
                         method_name,
                         params,
+                        returns,
                     });
                 }
             }
@@ -4332,17 +4993,6 @@ fn is_constructor_name(name: &str) -> bool {
     name.starts_with("new") || name.starts_with("from_") || name == "default"
 }
 
-/// Check if return type is Self or the type name
-fn returns_self_or_type(output: &syn::ReturnType, type_name: &str) -> bool {
-    match output {
-        syn::ReturnType::Default => false,
-        syn::ReturnType::Type(_, ty) => {
-            let ty_str = quote::quote!(#ty).to_string();
-            ty_str == "Self" || ty_str.contains(type_name)
-        }
-    }
-}
-
 /// Parse method parameters, skipping self parameters
 fn parse_method_params(sig: &syn::Signature) -> Vec<ConstructorParam> {
     let mut params = Vec::new();
@@ -5415,6 +6065,9 @@ struct BevyEventSpec {
     full_path: String,
     /// Crate name (e.g., "bevy_window")
     crate_name: String,
+    /// Rust path used in generated code for workspace events (e.g., "crate::combat::DamageDealt")
+    /// None for Bevy events, which go through their bevy:: re-export
+    rust_path: Option<String>,
 }
 
 /// Specification for a Bevy Message type (for Lua write_message via MessageWriter)
@@ -5490,6 +6143,7 @@ fn discover_bevy_events() -> Vec<BevyEventSpec> {
             type_name: d.type_name,
             full_path: d.full_path,
             crate_name: d.crate_name,
+            rust_path: None,
         }
     }).collect();
 
@@ -5507,6 +6161,77 @@ fn discover_bevy_events() -> Vec<BevyEventSpec> {
     events
 }
 
+/// Discover public `#[derive(Event)]` types in workspace member crates
+/// Only the parent crate and the members it depends on are scanned, since the generated
+/// code is compiled into the parent and can't name types from crates it doesn't use.
+/// Opt-in via [package.metadata.lua_build] include_workspace_events = true
+fn discover_workspace_events(parent_crate_name: &str, parent_manifest: &toml::Value) -> Vec<BevyEventSpec> {
+    let mut events: Vec<BevyEventSpec> = Vec::new();
+    let dependencies = dependency_crate_names(parent_manifest);
+
+    for mut member in workspace_members(parent_crate_name) {
+        if member.path_root != "crate" {
+            let Some(used_as) = dependencies.get(&member.crate_name) else {
+                continue;
+            };
+            member.path_root = used_as.clone();
+        }
+        let api_scan = scan_public_api(&member.dir, &member.crate_name);
+        for module in &api_scan.modules {
+            let Ok(source) = fs::read_to_string(&module.file_path) else {
+                continue;
+            };
+            // Quick contains check before parsing
+            if !source.contains("Event") {
+                continue;
+            }
+            let Ok(syntax_tree) = syn::parse_file(&source) else {
+                continue;
+            };
+
+            for item in &syntax_tree.items {
+                let (ident, vis, generics, attrs) = match item {
+                    Item::Struct(s) => (&s.ident, &s.vis, &s.generics, &s.attrs),
+                    Item::Enum(e) => (&e.ident, &e.vis, &e.generics, &e.attrs),
+                    _ => continue,
+                };
+                // Generic events can't be named without type parameters
+                if !matches!(vis, Visibility::Public(_))
+                    || !generics.params.is_empty()
+                    || !collect_derive_names(attrs).iter().any(|d| d == "Event")
+                {
+                    continue;
+                }
+
+                let type_name = ident.to_string();
+                if events.iter().any(|e| e.type_name == type_name) {
+                    println!(
+                        "cargo:warning=    ⚠ Skipping duplicate workspace event name: {} in {}",
+                        type_name, member.crate_name
+                    );
+                    continue;
+                }
+                events.push(BevyEventSpec {
+                    full_path: build_type_path(&member.crate_name, &module.module_path, &type_name),
+                    rust_path: Some(build_type_path(&member.path_root, &module.module_path, &type_name)),
+                    crate_name: member.crate_name.clone(),
+                    type_name,
+                });
+            }
+        }
+    }
+
+    println!(
+        "cargo:warning=  ✓ Discovered {} workspace Event types for Lua read_events()",
+        events.len()
+    );
+    for event in &events {
+        println!("cargo:warning=    - {} ({})", event.type_name, event.full_path);
+    }
+
+    events
+}
+
 /// Spec for a discovered Bevy resource type that should be registered for Lua access
 struct BevyResourceSpec {
     /// Full path like "bevy::input::ButtonInput<bevy::input::keyboard::KeyCode>"
@@ -5928,10 +6653,22 @@ fn generate_entity_component_binding(full_path: &str) -> Result<proc_macro2::Tok
 }
 
 #[allow(dead_code)]
-fn generate_bindings_for_type(spec: &TypeSpec) -> Result<proc_macro2::TokenStream, String> {
+fn generate_bindings_for_type(
+    spec: &TypeSpec,
+    ignored_impl_returns: &[String],
+) -> Result<proc_macro2::TokenStream, String> {
     // Find source file
     let source_path = find_source_file(spec)?;
+    generate_bindings_for_type_in_file(spec, &source_path, ignored_impl_returns)
+}
 
+/// Generate bindings for a type whose defining source file is already known
+/// (auto-discovered workspace resources live outside the cargo registry)
+fn generate_bindings_for_type_in_file(
+    spec: &TypeSpec,
+    source_path: &Path,
+    ignored_impl_returns: &[String],
+) -> Result<proc_macro2::TokenStream, String> {
     // Parse source
     let source_code =
         fs::read_to_string(&source_path).map_err(|e| format!("Failed to read source: {}", e))?;
@@ -5947,7 +6684,7 @@ fn generate_bindings_for_type(spec: &TypeSpec) -> Result<proc_macro2::TokenStrea
     }
 
     // Generate binding code
-    generate_registration_code(spec, &methods)
+    generate_registration_code(spec, &methods, ignored_impl_returns)
 }
 
 fn find_source_file(spec: &TypeSpec) -> Result<PathBuf, String> {
@@ -6049,6 +6786,8 @@ struct MethodInfo {
     name: String,
     #[allow(dead_code)]
     is_mut: bool,
+    /// Receiver is `self`/`mut self` (consuming builder methods)
+    self_by_value: bool,
     args: Vec<(String, String)>,
     #[allow(dead_code)]
     return_type: Option<String>,
@@ -6103,6 +6842,7 @@ fn extract_associated_function(
                     return Ok(MethodInfo {
                         name: function_name.to_string(),
                         is_mut: false, // Constructors don't have &mut self
+                        self_by_value: false,
                         args,
                         return_type,
                     });
@@ -6137,15 +6877,19 @@ fn extract_methods_for_type(
 
                     let mut has_self = false;
                     let mut is_mut = false;
+                    let mut self_by_value = false;
 
                     for arg in &method.sig.inputs {
                         if let FnArg::Receiver(receiver) = arg {
                             has_self = true;
                             is_mut = receiver.mutability.is_some();
+                            self_by_value = receiver.reference.is_none();
                             break;
                         }
                     }
 
+                    // Associated functions (constructors included) would have to replace the
+                    // live resource, so only methods with a receiver are bound
                     if !has_self {
                         continue;
                     }
@@ -6158,7 +6902,8 @@ fn extract_methods_for_type(
                             if let FnArg::Typed(pat_type) = arg {
                                 if let syn::Pat::Ident(pat_ident) = &*pat_type.pat {
                                     let name = pat_ident.ident.to_string();
-                                    let ty = quote!(#pat_type.ty).to_string();
+                                    let ty = &pat_type.ty;
+                                    let ty = quote!(#ty).to_string();
                                     return Some((name, ty));
                                 }
                             }
@@ -6174,6 +6919,7 @@ fn extract_methods_for_type(
                     methods.push(MethodInfo {
                         name: method.sig.ident.to_string(),
                         is_mut,
+                        self_by_value,
                         args,
                         return_type,
                     });
@@ -6266,6 +7012,7 @@ fn generate_bindings_for_constructor(
 fn generate_registration_code(
     spec: &TypeSpec,
     methods: &[MethodInfo],
+    ignored_impl_returns: &[String],
 ) -> Result<proc_macro2::TokenStream, String> {
     let type_path = syn::parse_str::<syn::Path>(&spec.full_path)
         .map_err(|e| format!("Invalid type path: {}", e))?;
@@ -6273,17 +7020,90 @@ fn generate_registration_code(
 
     let method_registrations: Vec<_> = methods
         .iter()
-        .map(|method| {
+        .filter(|method| {
+            !(returns_impl_trait(method.return_type.as_deref())
+                && ignored_impl_returns.contains(&method.name))
+        })
+        .filter_map(|method| {
             let method_name = &method.name;
             let method_ident = syn::Ident::new(method_name, proc_macro2::Span::call_site());
 
+            // Err(e) from methods returning Result<T, E> is raised as a Lua error
+            let propagate_error = propagate_result_error(method.return_type.as_deref(), type_name_str, method_name);
+
+            // Methods returning Self / &mut Self become chainable builder methods in Lua
+            // Each argument is converted from Lua to its declared type
+            if let Some(kind) = builder_return_kind(method.return_type.as_deref(), type_name_str) {
+                let Some((args_pattern, call_args)) = builder_call_args(&method.args) else {
+                    println!(
+                        "cargo:warning=  ⚠ Skipping builder {}::{}: an argument type can't be converted from Lua",
+                        type_name_str, method_name
+                    );
+                    return None;
+                };
+                return Some(match (kind, method.self_by_value) {
+                    (BuilderReturn::MutRef, _) => quote! {
+                        methods.add_builder(#method_name, |resource, _lua, #args_pattern| {
+                            resource.#method_ident(#call_args);
+                            Ok(())
+                        });
+                    },
+                    // `self` builders take the resource by value and hand back its replacement
+                    (BuilderReturn::Owned, true) => quote! {
+                        methods.add_consuming_builder(#method_name, |resource, #args_pattern| {
+                            resource.#method_ident(#call_args)
+                        });
+                    },
+                    (BuilderReturn::Owned, false) => quote! {
+                        methods.add_builder(#method_name, |resource, _lua, #args_pattern| {
+                            let updated = resource.#method_ident(#call_args);
+                            *resource = updated;
+                            Ok(())
+                        });
+                    },
+                });
+            }
+
+            // `impl Trait` can't be named, so it can't be converted to Lua directly:
+            // fall back to its Debug output when the bounds include Debug, otherwise nil
+            if returns_impl_trait(method.return_type.as_deref()) {
+                let return_type = method.return_type.as_deref().unwrap_or_default();
+                println!(
+                    "cargo:warning=  ⚠ {}::{} returns `{}`; binding it via {} (opt out with ignore_impl_returns)",
+                    type_name_str,
+                    method_name,
+                    return_type,
+                    if impl_bounds_include_debug(return_type) { "Debug" } else { "nil" }
+                );
+                let (args_pattern, call_args) = match method.args.len() {
+                    0 => (quote! { _args: () }, quote! {}),
+                    1 => (quote! { arg: mlua::Value }, quote! { arg }),
+                    // Same limitation as the multi-arg case below
+                    _ => (quote! { _args: mlua::MultiValue }, quote! {}),
+                };
+                let convert = if impl_bounds_include_debug(return_type) {
+                    quote! { Ok(format!("{:?}", result)) }
+                } else {
+                    quote! {
+                        let _ = result;
+                        Ok(mlua::Value::Nil)
+                    }
+                };
+                return Some(quote! {
+                    methods.add(#method_name, |resource, _lua, #args_pattern| {
+                        let result = resource.#method_ident(#call_args);
+                        #convert
+                    });
+                });
+            }
+
             // Generate based on argument count - using mlua's automatic type conversion
-            match method.args.len() {
+            Some(match method.args.len() {
                 0 => {
                     // No arguments - simple call
                     quote! {
                         methods.add(#method_name, |resource, _lua, _args: ()| {
-                            let result = resource.#method_ident();
+                            let result = resource.#method_ident()#propagate_error;
                             Ok(result)
                         });
                     }
@@ -6292,7 +7112,7 @@ fn generate_registration_code(
                     // Single argument - mlua will handle conversion
                     quote! {
                         methods.add(#method_name, |resource, _lua, arg: mlua::Value| {
-                            let result = resource.#method_ident(arg);
+                            let result = resource.#method_ident(arg)#propagate_error;
                             Ok(result)
                         });
                     }
@@ -6303,12 +7123,12 @@ fn generate_registration_code(
                         methods.add(#method_name, |resource, _lua, args: mlua::MultiValue| {
                             // For now, just call with first arg
                             // TODO: Proper multi-arg handling
-                            let result = resource.#method_ident();
+                            let result = resource.#method_ident()#propagate_error;
                             Ok(result)
                         });
                     }
                 }
-            }
+            })
         })
         .collect();
 
@@ -6334,6 +7154,7 @@ fn write_bindings_to_parent_crate(
     discovered_component_methods: Vec<DiscoveredComponentMethod>, // Methods on Component types (e.g., Transform::looking_at)
     discovered_static_methods: Vec<DiscoveredStaticMethod>, // Static methods on math types
     lua_methods_config: &LuaMethodsConfig, // Configuration for component/static method types
+    workspace_events: Vec<BevyEventSpec>, // #[derive(Event)] types from workspace members (opt-in)
     parent_src_dir: &Path,
     parent_crate_name: &str,
 ) {
@@ -6467,6 +7288,38 @@ fn write_bindings_to_parent_crate(
         asset_type_paths.len()
     );
 
+    // Generate world:create_asset match arms - one per asset type path, keyed by short name
+    // Each arm adds the asset through SystemState<ResMut<Assets<T>>> and returns its handle
+    let mut create_asset_names = std::collections::HashSet::new();
+    let create_asset_arms: Vec<_> = asset_type_paths
+        .iter()
+        .filter_map(|type_path| {
+            let short_name = type_path.segments.last()?.ident.to_string();
+            if !create_asset_names.insert(short_name.clone()) {
+                return None;
+            }
+            Some(quote::quote! {
+                #short_name => {
+                    if !world.contains_resource::<bevy::prelude::Assets<#type_path>>() {
+                        return Err(mlua::Error::RuntimeError(format!(
+                            "create_asset: Assets<{}> resource not found", #short_name
+                        )));
+                    }
+                    let asset = bevy_lua_ecs::asset_from_lua_table::<#type_path>(lua, world, props)
+                        .map_err(|e| mlua::Error::RuntimeError(format!("create_asset: {}", e)))?;
+                    let mut system_state = bevy::ecs::system::SystemState::<
+                        bevy::prelude::ResMut<bevy::prelude::Assets<#type_path>>,
+                    >::new(world);
+                    let handle = system_state.get_mut(world).add(asset);
+                    let asset_registry = world.resource::<bevy_lua_ecs::AssetRegistry>().clone();
+                    asset_registry
+                        .handle_to_lua(lua, handle.untyped(), #short_name)
+                        .map(mlua::Value::Table)
+                }
+            })
+        })
+        .collect();
+
     // Generate asset constructor registrations for discovered constructors
     // These allow opaque types like Image to be created from Lua using their actual constructors
     let constructor_registrations: Vec<_> = discovered_constructors.iter().filter_map(|ctor| {
@@ -6571,13 +7424,20 @@ fn write_bindings_to_parent_crate(
         let method_ident = syn::Ident::new(method_name, proc_macro2::Span::call_site());
         
         println!("cargo:warning=    - Registering constructor {}::{}({} params)", ctor.type_name, method_name, param_names.len());
+
+        let construct = unwrap_constructor_call(
+            ctor.returns,
+            quote::quote! { #type_path::#method_ident(#(#param_names),*) },
+            type_path_str,
+            method_name,
+        );
         
         Some(quote::quote! {
             asset_registry.register_asset_constructor(#type_path_str, |table| {
                 #(#param_extractions)*
                 
                 bevy::log::debug!("[AUTO_CONSTRUCTOR] Calling {}::{}", stringify!(#type_path), stringify!(#method_ident));
-                Ok(Box::new(#type_path::#method_ident(#(#param_names),*)) as Box<dyn bevy::reflect::Reflect>)
+                Ok(Box::new(#construct) as Box<dyn bevy::reflect::Reflect>)
             });
         })
     }).collect();
@@ -6626,8 +7486,13 @@ fn write_bindings_to_parent_crate(
     let observer_handlers = generate_observer_handlers(&observable_events);
     let observer_match_arms = generate_observer_match_arms(&observable_events);
 
-    // Discover Bevy Event types for Lua read_events()
-    let bevy_events = discover_bevy_events();
+    // Discover Bevy Event types for Lua read_events(), plus workspace events when opted in
+    let mut bevy_events = discover_bevy_events();
+    let workspace_events: Vec<_> = workspace_events
+        .into_iter()
+        .filter(|event| !bevy_events.iter().any(|b| b.type_name == event.type_name))
+        .collect();
+    bevy_events.extend(workspace_events);
 
     // Discover Bevy Message types for Lua write_message() (uses MessageWriter<T>)
     // Also scan parent crate for #[derive(Message)] types using passed crate name from Cargo.toml
@@ -6848,11 +7713,13 @@ fn write_bindings_to_parent_crate(
         } else {
             event.full_path.clone()
         };
+        // Workspace events are named through `crate::` when they live in the parent crate
+        let rust_path = event.rust_path.as_deref().unwrap_or(&bevy_path);
         
-        let type_path: syn::Path = match syn::parse_str(&bevy_path) {
+        let type_path: syn::Path = match syn::parse_str(rust_path) {
             Ok(p) => p,
             Err(_) => {
-                println!("cargo:warning=  ⚠ Could not parse event path: {}", bevy_path);
+                println!("cargo:warning=  ⚠ Could not parse event path: {}", rust_path);
                 return None;
             }
         };
@@ -6975,8 +7842,10 @@ fn write_bindings_to_parent_crate(
     );
 
     // Convert entity wrapper names to quote literals for const array
+    // Only types with #[reflect(Component)] are registered through the TypeRegistry at runtime
     let entity_wrapper_name_literals: Vec<_> = discovered_entity_wrappers
         .iter()
+        .filter(|w| w.has_reflect_component)
         .map(|w| {
             let name = &w.type_name;
             quote::quote! { #name }
@@ -7066,6 +7935,21 @@ fn write_bindings_to_parent_crate(
         non_reflected_serialize_arms.len()
     );
 
+    // Generate setter arms for Component enums so Lua can write them as variant name strings
+    let non_reflected_enum_setter_arms: Vec<_> = discovered_entity_wrappers
+        .iter()
+        .filter(|w| w.string_enum)
+        .filter_map(|w| {
+            let use_path = normalize_bevy_path_for_entity_wrapper(&w.full_path)?;
+            generate_enum_setter_arm(&w.type_name, &use_path)
+        })
+        .collect();
+
+    println!(
+        "cargo:warning=  ✓ Generated {} non-reflected enum setter arms",
+        non_reflected_enum_setter_arms.len()
+    );
+
 
 
     // Generate SystemParam type name literals for const array
@@ -7931,6 +8815,25 @@ fn write_bindings_to_parent_crate(
             component_registry.set_non_reflected_serializer(
                 std::sync::Arc::new(serialize_non_reflected_component)
             );
+
+            // Register the setter callback so Lua can write enum components as variant strings
+            component_registry.set_non_reflected_setter(
+                std::sync::Arc::new(set_non_reflected_enum_component)
+            );
+        }
+
+        /// Set a non-reflected enum component from a variant name string
+        /// e.g. entity:set({ HandBone = "Palm" })
+        #[allow(unused_variables)]
+        pub fn set_non_reflected_enum_component(
+            entity_mut: &mut bevy::ecs::world::EntityWorldMut,
+            component_name: &str,
+            variant: &str,
+        ) -> Result<(), String> {
+            match component_name {
+                #(#non_reflected_enum_setter_arms)*
+                _ => Err(format!("Component {} cannot be set from a string", component_name))
+            }
         }
 
 
@@ -7995,6 +8898,39 @@ fn write_bindings_to_parent_crate(
             );
         }
 
+        /// Create an asset from a Lua table for world:create_asset
+        /// The type name (short or full path) is resolved through the TypeRegistry and must be one of
+        /// DISCOVERED_ASSET_TYPES; the matching arm adds it via SystemState<ResMut<Assets<T>>>
+        /// Returns the handle as { id = <asset index bits>, type = "Image" }
+        pub fn dispatch_create_asset(
+            lua: &mlua::Lua,
+            world: &mut bevy::prelude::World,
+            type_name: &str,
+            props: mlua::Table,
+        ) -> mlua::Result<mlua::Value> {
+            let short_name = {
+                let type_registry = world.resource::<bevy::ecs::reflect::AppTypeRegistry>().read();
+                type_registry
+                    .get_with_short_type_path(type_name)
+                    .or_else(|| type_registry.get_with_type_path(type_name))
+                    .filter(|registration| registration.data::<bevy::asset::ReflectAsset>().is_some())
+                    .map(|registration| registration.type_info().type_path_table().short_path().to_string())
+                    .filter(|short_name| DISCOVERED_ASSET_TYPES.contains(&short_name.as_str()))
+                    .ok_or_else(|| {
+                        mlua::Error::RuntimeError(format!(
+                            "create_asset: '{}' is not a registered asset type", type_name
+                        ))
+                    })?
+            };
+
+            match short_name.as_str() {
+                #(#create_asset_arms)*
+                _ => Err(mlua::Error::RuntimeError(format!(
+                    "create_asset: {} can't be created from Lua", short_name
+                )))
+            }
+        }
+
         /// Auto-discovered Handle<T> newtype wrappers
         /// Format: (newtype_name, inner_asset_name) - runtime will resolve via TypeRegistry
         /// Examples: ("ImageRenderTarget", "Image"), ("Mesh3d", "Mesh")
@@ -8153,9 +9089,9 @@ fn write_bindings_to_parent_crate(
             let callbacks = observer_registry.callbacks().lock().unwrap();
 
             if let Some(observers) = callbacks.get(&entity) {
-                for (ev_type, callback_key) in observers {
+                for (ev_type, observer) in observers {
                     if ev_type == event_type {
-                        if let Ok(callback) = lua_ctx.lua.registry_value::<mlua::Function>(callback_key) {
+                        if let Ok(callback) = lua_ctx.lua.registry_value::<mlua::Function>(&observer.key) {
                             let entity_snapshot = bevy_lua_ecs::LuaEntitySnapshot {
                                 entity,
                                 component_data: std::collections::HashMap::new(),
@@ -8180,7 +9116,13 @@ fn write_bindings_to_parent_crate(
                                 }
                             };
 
-                            if let Err(e) = callback.call::<()>((entity_snapshot, event_table)) {
+                            if let Err(e) = bevy_lua_ecs::LuaObserverCallback::call(
+                                &callback,
+                                observer.receives_event_type,
+                                entity_snapshot,
+                                event_type,
+                                event_table,
+                            ) {
                                 bevy::log::error!("[LUA_OBSERVER] Error calling {} callback: {}", event_type, e);
                             }
                         }
@@ -8201,6 +9143,10 @@ fn write_bindings_to_parent_crate(
         ) {
             match event_type {
                 #observer_match_arms
+                // Component lifecycle events (OnAdd<T>, OnInsert<T>, OnRemove<T>) use reflection
+                lifecycle if bevy_lua_ecs::ComponentLifecycle::parse(lifecycle).is_some() => {
+                    bevy_lua_ecs::attach_component_lifecycle_observer(commands, entity, lifecycle);
+                }
                 _ => bevy::log::warn!("[LUA_OBSERVER] Unknown observer type: {}", event_type),
             }
         }
@@ -8247,6 +9193,10 @@ fn write_bindings_to_parent_crate(
                 // dispatch_static_method function to the library's call_static_method
                 bevy_lua_ecs::set_static_method_dispatcher(dispatch_static_method);
 
+                // Register the asset creation dispatcher - this connects the generated
+                // dispatch_create_asset function to the library's world:create_asset
+                bevy_lua_ecs::set_asset_create_dispatcher(dispatch_create_asset);
+
                 // Register Bevy Event types for Lua read_events()
                 // This registers Events<T> for auto-discovered event types
                 register_bevy_events(app);
@@ -8553,19 +9503,41 @@ fn write_empty_bindings_with_events(event_types: Vec<String>) {
         }
 
         /// Auto-generated event dispatch system
-        /// This system drains PendingLuaEvents and logs them.
-        /// TODO: Implement reflection-based event construction and dispatch.
+        /// This system drains PendingLuaEvents (e.g. from world:send_events) and writes the
+        /// whole batch through the event write dispatcher in one pass.
         pub fn dispatch_lua_events(
-            pending: bevy::prelude::Res<crate::event_sender::PendingLuaEvents>,
+            world: &mut bevy::prelude::World,
         ) {
+            let pending = world.resource::<crate::event_sender::PendingLuaEvents>().clone();
+            // Drained in priority order: high, normal, low
             let events = pending.drain_events();
+
+            if events.is_empty() {
+                return;
+            }
+
+            let lua_ctx = world.resource::<crate::LuaScriptContext>().clone();
+
             for (type_name, data) in events {
-                bevy::log::debug!("[LUA_EVENT] Received event '{}': {:?}", type_name, data);
+                bevy::log::debug!("[LUA_EVENT] Processing event '{}': {:?}", type_name, data);
+
+                match json_to_lua_table(&lua_ctx.lua, &data) {
+                    Ok(lua_table) => {
+                        if let Err(e) = crate::call_write_events_global(&lua_ctx.lua, world, &type_name, &lua_table) {
+                            bevy::log::warn!("[LUA_EVENT] Failed to dispatch '{}': {}", type_name, e);
+                        }
+                    }
+                    Err(e) => {
+                        bevy::log::warn!("[LUA_EVENT] Failed to convert JSON to Lua table: {}", e);
+                    }
+                }
             }
         }
 
         /// Auto-generated message dispatch system
-        /// This system drains PendingLuaMessages and dispatches them via MessageWriter.
+        /// This system drains PendingLuaMessages and dispatches them via MessageWriter, in send order.
+        /// Messages from Lua are read straight from the Lua registry;
+        /// JSON messages go through a JSON -> Lua table conversion.
         /// Uses the same reflection pattern as event dispatch.
         pub fn dispatch_lua_messages(
             world: &mut bevy::prelude::World,
@@ -8582,18 +9554,29 @@ fn write_empty_bindings_with_events(event_types: Vec<String>) {
             let lua_ctx = world.resource::<crate::LuaScriptContext>().clone();
 
             for (type_name, data) in messages {
-                bevy::log::debug!("[LUA_MESSAGE] Processing message '{}': {:?}", type_name, data);
-
-                // Convert JSON to Lua table for reflection
-                match json_to_lua_table(&lua_ctx.lua, &data) {
+                let (lua, lua_table) = match data {
+                    crate::event_sender::LuaMessageData::Lua(key) => {
+                        let lua = lua_ctx.state_for_key(&key);
+                        let table = lua.registry_value::<mlua::Table>(&key);
+                        let _ = lua.remove_registry_value(key);
+                        (lua, table)
+                    }
+                    crate::event_sender::LuaMessageData::Json(data) => {
+                        bevy::log::debug!("[LUA_MESSAGE] Processing message '{}': {:?}", type_name, data);
+                        // Convert JSON to Lua table for reflection
+                        let table = json_to_lua_table(&lua_ctx.lua, &data);
+                        (lua_ctx.lua.clone(), table)
+                    }
+                };
+                match lua_table {
                     Ok(lua_table) => {
                         // Use the global message dispatch function (set by parent crate's generated code)
-                        if let Err(e) = crate::call_write_messages_global(&lua_ctx.lua, world, &type_name, &lua_table) {
+                        if let Err(e) = crate::call_write_messages_global(&lua, world, &type_name, &lua_table) {
                             bevy::log::warn!("[LUA_MESSAGE] Failed to dispatch '{}': {}", type_name, e);
                         }
                     }
                     Err(e) => {
-                        bevy::log::warn!("[LUA_MESSAGE] Failed to convert JSON to Lua table: {}", e);
+                        bevy::log::warn!("[LUA_MESSAGE] Failed to read message table for '{}': {}", type_name, e);
                     }
                 }
             }
@@ -8899,13 +9882,38 @@ fn get_known_type_definition(type_name: &str) -> Option<TypeDefinition> {
     }
 }
 
+/// Path overrides from the parent manifest (cached)
+fn get_path_overrides() -> &'static Vec<(String, String)> {
+    use std::sync::OnceLock;
+    static CACHED: OnceLock<Vec<(String, String)>> = OnceLock::new();
+
+    CACHED.get_or_init(|| {
+        let Some(parent_manifest) = env::var("OUT_DIR")
+            .ok()
+            .and_then(|out_dir| find_parent_manifest(&PathBuf::from(out_dir)))
+        else {
+            return Vec::new();
+        };
+        fs::read_to_string(&parent_manifest)
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .map(|manifest| parse_path_overrides(&manifest))
+            .unwrap_or_default()
+    })
+}
+
 /// Check if a crate path is valid for code generation
 /// 
 /// Now simplified since we use public API scanning:
+/// - Overrides from `[package.metadata.lua_path_overrides]`: checked first (workspace members, re-exports)
 /// - Direct dependencies (in Cargo.toml): Use original path unchanged (public API scanning already filtered)
 /// - Transitive bevy_* dependencies (simple names): Transform to bevy::module::TypeName
 /// - Other transitive dependencies: Reject (not directly accessible)
 fn normalize_bevy_path(path: &str) -> Option<String> {
+    if let Some(overridden) = apply_path_override(path, get_path_overrides()) {
+        return Some(overridden);
+    }
+
     // Extract crate name from path (first segment before ::)
     let crate_name = path.split("::").next()?;
     
@@ -8926,8 +9934,8 @@ fn normalize_bevy_path(path: &str) -> Option<String> {
             let type_name = path.rsplit("::").next()?;
             return Some(format!("{}::prelude::{}", crate_name, type_name));
         }
-        // No prelude - paths from public API scanning are trusted
-        return Some(path.to_string());
+        // No prelude - prefer the shortest public re-export of the scanned path
+        return Some(apply_reexports(path).unwrap_or_else(|| path.to_string()));
     }
     
     // Transitive bevy_* dependencies that are OFFICIAL Bevy internal crates
@@ -8951,6 +9959,49 @@ fn normalize_bevy_path(path: &str) -> Option<String> {
 
 }
 
+/// Shorten `path` to the shortest public re-export of its type, as collected by
+/// `scan_public_api` for the crate that defines it
+fn apply_reexports(path: &str) -> Option<String> {
+    let (crate_name, rest) = path.split_once("::")?;
+    let (module_path, type_name) = rest.rsplit_once("::").unwrap_or(("", rest));
+    let re_exports = get_dependency_reexports().get(crate_name)?;
+    let shortened = build_type_path_with_reexports(crate_name, module_path, type_name, re_exports);
+    (shortened.len() < path.len()).then_some(shortened)
+}
+
+/// Re-export maps of every direct dependency, from `scan_public_api`
+fn get_dependency_reexports(
+) -> &'static std::collections::HashMap<String, std::collections::HashMap<String, String>> {
+    use std::sync::OnceLock;
+
+    static CACHE: OnceLock<std::collections::HashMap<String, std::collections::HashMap<String, String>>> =
+        OnceLock::new();
+
+    CACHE.get_or_init(|| {
+        let mut by_crate = std::collections::HashMap::new();
+        let Some(cargo_home) = get_cargo_home() else {
+            return by_crate;
+        };
+        let registry_src = cargo_home.join("registry").join("src");
+        let direct_deps = get_direct_dependencies();
+
+        for index_entry in fs::read_dir(&registry_src).into_iter().flatten().flatten() {
+            for crate_entry in fs::read_dir(index_entry.path()).into_iter().flatten().flatten() {
+                let crate_dir = crate_entry.path();
+                let dir_name = crate_dir.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                // Extract base crate name (e.g., "bevy_ui" from "bevy_ui-0.17.2")
+                let base_crate = dir_name.split('-').next().unwrap_or(dir_name);
+                if !direct_deps.contains(base_crate) || by_crate.contains_key(base_crate) {
+                    continue;
+                }
+                let api_scan = scan_public_api(&crate_dir, base_crate);
+                by_crate.insert(base_crate.to_string(), api_scan.re_exports);
+            }
+        }
+        by_crate
+    })
+}
+
 /// Normalize path for entity wrapper component discovery
 /// Unlike normalize_bevy_path, this SKIPS transitive bevy internal crates
 /// because they have #[reflect(Component)] and are handled via TypeRegistry at runtime
@@ -9466,30 +10517,6 @@ fn discover_type_recursive(
     Ok(type_def)
 }
 
-/// Check if a type is a primitive
-fn is_primitive_type(type_str: &str) -> bool {
-    matches!(
-        type_str,
-        "u8" | "u16"
-            | "u32"
-            | "u64"
-            | "u128"
-            | "usize"
-            | "i8"
-            | "i16"
-            | "i32"
-            | "i64"
-            | "i128"
-            | "isize"
-            | "f32"
-            | "f64"
-            | "bool"
-            | "String"
-            | "str"
-            | "()"
-    )
-}
-
 /// Find type definition in a syntax tree
 #[allow(dead_code)]
 fn find_type_definition(
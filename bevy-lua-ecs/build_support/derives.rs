@@ -0,0 +1,104 @@
+//! Derive and reflect attribute helpers shared by build.rs and the crate's tests
+//! (build scripts can't run their own `#[cfg(test)]` tests, so tests/build_support_tests.rs
+//! includes this file directly)
+
+/// Derive information for a `#[derive(Component)]` enum
+pub struct ComponentEnumDerives {
+    pub has_debug: bool,
+    /// Reflect + Default + Clone are the bounds `parse_enum_from_string` needs
+    pub string_enum: bool,
+}
+
+/// Inspect an enum's derives; returns None unless it derives Component
+pub fn parse_component_enum_derives(item_enum: &syn::ItemEnum) -> Option<ComponentEnumDerives> {
+    let derive_names = collect_derive_names(&item_enum.attrs);
+    let has = |name: &str| derive_names.iter().any(|derive| derive == name);
+    if !has("Component") {
+        return None;
+    }
+
+    Some(ComponentEnumDerives {
+        has_debug: has("Debug"),
+        string_enum: has("Reflect") && has("Default") && has("Clone"),
+    })
+}
+
+/// Collect the last path segment of every `#[derive(...)]` entry (e.g. `bevy::prelude::Resource` -> `Resource`)
+pub fn collect_derive_names(attrs: &[syn::Attribute]) -> Vec<String> {
+    let mut derive_names = Vec::new();
+    for attr in attrs {
+        if attr.path().is_ident("derive") {
+            if let Ok(paths) = attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+            ) {
+                for path in paths {
+                    if let Some(segment) = path.segments.last() {
+                        derive_names.push(segment.ident.to_string());
+                    }
+                }
+            }
+        }
+    }
+    derive_names
+}
+
+/// Collect the last path segment of every `#[reflect(...)]` argument (e.g. `#[reflect(Component, Default)]`
+/// -> `["Component", "Default"]`)
+pub fn collect_reflect_args(attrs: &[syn::Attribute]) -> Vec<String> {
+    let mut reflect_args = Vec::new();
+    for attr in attrs {
+        if attr.path().is_ident("reflect") {
+            if let Ok(paths) = attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+            ) {
+                for path in paths {
+                    if let Some(segment) = path.segments.last() {
+                        reflect_args.push(segment.ident.to_string());
+                    }
+                }
+            }
+        }
+    }
+    reflect_args
+}
+
+/// Whether the attributes include `#[reflect(Component)]`
+/// Types deriving Component without it have no `ReflectComponent` data and must not be
+/// registered through the TypeRegistry
+pub fn has_reflect_component_attr(attrs: &[syn::Attribute]) -> bool {
+    collect_reflect_args(attrs).iter().any(|arg| arg == "Component")
+}
+
+/// Names of structs/enums in `source` that carry `#[reflect(Component)]`
+/// Second pass for the line-based derive scan, which doesn't look at reflect attributes
+pub fn parse_reflect_component_types_from_source(source: &str) -> std::collections::HashSet<String> {
+    let mut results = std::collections::HashSet::new();
+    let Ok(file) = syn::parse_file(source) else {
+        return results;
+    };
+    for item in file.items {
+        let (ident, attrs) = match &item {
+            syn::Item::Struct(item_struct) => (&item_struct.ident, &item_struct.attrs),
+            syn::Item::Enum(item_enum) => (&item_enum.ident, &item_enum.attrs),
+            _ => continue,
+        };
+        if has_reflect_component_attr(attrs) {
+            results.insert(ident.to_string());
+        }
+    }
+    results
+}
+
+/// Generate the `set_non_reflected_enum_component` match arm for one Component enum
+/// The variant name string from Lua is parsed with `parse_enum_from_string`
+pub fn generate_enum_setter_arm(type_name: &str, use_path: &str) -> Option<proc_macro2::TokenStream> {
+    let type_path: syn::Path = syn::parse_str(use_path).ok()?;
+    Some(quote::quote! {
+        #type_name => {
+            let value = bevy_lua_ecs::parse_enum_from_string::<#type_path>(variant)
+                .ok_or_else(|| format!("Unknown variant '{}' for enum {}", variant, #type_name))?;
+            entity_mut.insert(value);
+            Ok(())
+        }
+    })
+}
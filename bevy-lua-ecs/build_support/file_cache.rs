@@ -0,0 +1,97 @@
+//! Per-file scan caching shared by build.rs and the crate's tests
+//! (build scripts can't run their own `#[cfg(test)]` tests, so tests/build_support_tests.rs
+//! includes this file directly)
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Identity of a file's contents as far as the scans care: size plus full-precision mtime
+/// Whole seconds alone would miss edits made within the same second as the last scan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    pub len: u64,
+    /// Modification time since the Unix epoch
+    pub secs: u64,
+    pub nanos: u32,
+}
+
+impl FileStamp {
+    /// Stamp of the file at `path`, or None if it (or its mtime) can't be read
+    pub fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            len: metadata.len(),
+            secs: modified.as_secs(),
+            nanos: modified.subsec_nanos(),
+        })
+    }
+}
+
+/// What one file contained when it was last parsed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileScanEntry<T> {
+    pub stamp: FileStamp,
+    /// Hash of any other input the results depend on (e.g. the crate's re-exports)
+    pub context: u64,
+    pub items: Vec<T>,
+}
+
+/// Counts for the "[CACHE]" summary line of an incremental scan
+#[derive(Debug, Default)]
+pub struct FileScanStats {
+    pub reused: usize,
+    pub parsed: usize,
+}
+
+/// Scan one file, reusing the cached results when its stamp and context are unchanged
+/// Files that can't be read yield no results and are not cached.
+pub fn scan_file_cached<T: Clone>(
+    entries: &mut HashMap<String, FileScanEntry<T>>,
+    seen: &mut HashSet<String>,
+    stats: &mut FileScanStats,
+    path: &Path,
+    context: u64,
+    scan: impl FnOnce(&str, &mut Vec<T>),
+) -> Vec<T> {
+    let key = path.to_string_lossy().to_string();
+    seen.insert(key.clone());
+    let stamp = FileStamp::of(path);
+
+    if let (Some(stamp), Some(entry)) = (stamp, entries.get(&key)) {
+        if entry.stamp == stamp && entry.context == context {
+            stats.reused += 1;
+            return entry.items.clone();
+        }
+    }
+
+    let Ok(source) = fs::read_to_string(path) else {
+        entries.remove(&key);
+        return Vec::new();
+    };
+    stats.parsed += 1;
+    let mut items = Vec::new();
+    scan(&source, &mut items);
+    match stamp {
+        Some(stamp) => {
+            entries.insert(key, FileScanEntry { stamp, context, items: items.clone() });
+        }
+        None => {
+            entries.remove(&key);
+        }
+    }
+    items
+}
+
+/// Hash a map in a stable order, for use as a `scan_file_cached` context
+pub fn hash_map_context(map: &HashMap<String, String>) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort();
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    entries.hash(&mut hasher);
+    hasher.finish()
+}
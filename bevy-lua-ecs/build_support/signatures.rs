@@ -0,0 +1,203 @@
+//! Signature helpers shared by build.rs and the crate's tests
+//! (build scripts can't run their own `#[cfg(test)]` tests, so tests/build_support_tests.rs
+//! includes this file directly)
+
+use serde::{Deserialize, Serialize};
+
+/// How a constructor hands back the constructed value
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SelfReturn {
+    /// `-> Self`
+    #[default]
+    Plain,
+    /// `-> Result<Self, E>` - the error is raised in Lua (E must implement Display)
+    Result,
+    /// `-> Option<Self>` - None is raised in Lua as well
+    Option,
+}
+
+/// Last path segment of a type, e.g. `Result` for `std::result::Result<Self, E>`
+fn last_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+    match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => type_path.path.segments.last(),
+        _ => None,
+    }
+}
+
+/// Whether `ty` is exactly `Self` or `type_name` (any path prefix, no generic arguments)
+fn is_self_type(ty: &syn::Type, type_name: &str) -> bool {
+    last_segment(ty).is_some_and(|segment| {
+        (segment.ident == "Self" || segment.ident == type_name) && segment.arguments.is_none()
+    })
+}
+
+/// Check if return type is Self or the type name, possibly wrapped in `Result` or `Option`
+/// Only the last path segment counts, so `Vec<Self>` or `Option<FooBuilder>` aren't
+/// constructors of `Foo`
+pub fn returns_self_or_type(output: &syn::ReturnType, type_name: &str) -> Option<SelfReturn> {
+    let syn::ReturnType::Type(_, ty) = output else {
+        return None;
+    };
+    if is_self_type(ty, type_name) {
+        return Some(SelfReturn::Plain);
+    }
+    let segment = last_segment(ty)?;
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    let Some(syn::GenericArgument::Type(first)) = args.args.first() else {
+        return None;
+    };
+    if !is_self_type(first, type_name) {
+        return None;
+    }
+    match segment.ident.to_string().as_str() {
+        "Result" => Some(SelfReturn::Result),
+        "Option" => Some(SelfReturn::Option),
+        _ => None,
+    }
+}
+
+/// Check if a return type (as rendered by quote!) is a `Result`
+/// e.g. "Result < u32 , String >" or "std :: result :: Result < () , io :: Error >"
+pub fn returns_result(return_type: Option<&str>) -> bool {
+    return_type
+        .and_then(|ty| ty.split(" < ").next())
+        .is_some_and(|wrapper| wrapper.rsplit(' ').next() == Some("Result"))
+}
+
+/// Suffix for a generated method call that raises `Err(e)` as a Lua error when the method
+/// returns `Result<T, E>` (E must implement Display); empty otherwise
+pub fn propagate_result_error(
+    return_type: Option<&str>,
+    type_name: &str,
+    method_name: &str,
+) -> proc_macro2::TokenStream {
+    if !returns_result(return_type) {
+        return proc_macro2::TokenStream::new();
+    }
+    quote::quote! {
+        .map_err(|e| mlua::Error::RuntimeError(format!("{}::{}: {}", #type_name, #method_name, e)))?
+    }
+}
+
+/// Unwrap a constructor call according to its `SelfReturn`
+/// An asset has to be produced, so `Err` and `None` both become Lua errors
+pub fn unwrap_constructor_call(
+    returns: SelfReturn,
+    call: proc_macro2::TokenStream,
+    type_path: &str,
+    method_name: &str,
+) -> proc_macro2::TokenStream {
+    match returns {
+        SelfReturn::Plain => call,
+        SelfReturn::Result => quote::quote! {
+            #call.map_err(|e| {
+                mlua::Error::RuntimeError(format!("{}::{}: {}", #type_path, #method_name, e))
+            })?
+        },
+        SelfReturn::Option => quote::quote! {
+            #call.ok_or_else(|| {
+                mlua::Error::RuntimeError(format!("{}::{} returned None", #type_path, #method_name))
+            })?
+        },
+    }
+}
+
+/// How a resource method hands back the resource for Lua method chaining
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuilderReturn {
+    /// `-> &mut Self` - the resource was modified in place
+    MutRef,
+    /// `-> Self` - the returned value replaces the resource
+    Owned,
+}
+
+/// Detect builder-style return types (`Self`, `&mut Self`, or the type's own name)
+pub fn builder_return_kind(return_type: Option<&str>, type_name: &str) -> Option<BuilderReturn> {
+    // quote! renders `&'a mut Self` as "& 'a mut Self"
+    let tokens: Vec<&str> = return_type?.split_whitespace().collect();
+    let is_self = |ty: &[&str]| ty == ["Self"] || ty == [type_name];
+
+    match tokens.as_slice() {
+        ["&", rest @ ..] => {
+            let rest = match rest {
+                [lifetime, rest @ ..] if lifetime.starts_with('\'') => rest,
+                rest => rest,
+            };
+            match rest {
+                ["mut", ty @ ..] if is_self(ty) => Some(BuilderReturn::MutRef),
+                _ => None,
+            }
+        }
+        ty if is_self(ty) => Some(BuilderReturn::Owned),
+        _ => None,
+    }
+}
+
+/// Type Lua converts a declared argument into with FromLua, and how it is passed on
+/// `&T` / `&mut T` are converted to an owned `T` (`String` for `&str`, `Vec<T>` for `&[T]`)
+/// and borrowed for the call. `impl Trait` can't be named, so it yields None.
+fn lua_arg_type(declared: &syn::Type) -> Option<(syn::Type, ArgPassing)> {
+    match declared {
+        syn::Type::Reference(reference) => {
+            let passing = if reference.mutability.is_some() { ArgPassing::RefMut } else { ArgPassing::Ref };
+            let owned = match &*reference.elem {
+                syn::Type::Path(path) if path.qself.is_none() && path.path.is_ident("str") => {
+                    syn::parse_quote!(String)
+                }
+                syn::Type::Slice(slice) => {
+                    let elem = &slice.elem;
+                    syn::parse_quote!(Vec<#elem>)
+                }
+                syn::Type::ImplTrait(_) => return None,
+                elem => elem.clone(),
+            };
+            Some((owned, passing))
+        }
+        syn::Type::ImplTrait(_) | syn::Type::Infer(_) => None,
+        syn::Type::Paren(paren) => lua_arg_type(&paren.elem),
+        ty => Some((ty.clone(), ArgPassing::Value)),
+    }
+}
+
+/// How a converted argument is handed to the bound method
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArgPassing {
+    Value,
+    Ref,
+    RefMut,
+}
+
+/// Closure parameter and call arguments for a generated builder binding
+/// `args` are the method's (name, declared type) pairs as rendered by quote!. Each argument is
+/// converted with FromLua to its declared type, e.g. `(f32, & str)` becomes the parameter
+/// `(arg0, arg1): (f32, String)` and the call `(arg0, &arg1)`. None if any argument type can't
+/// be converted from Lua.
+pub fn builder_call_args(
+    args: &[(String, String)],
+) -> Option<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    let mut patterns = Vec::new();
+    let mut types = Vec::new();
+    let mut call_args = Vec::new();
+    for (index, (_, declared)) in args.iter().enumerate() {
+        let declared = syn::parse_str::<syn::Type>(declared).ok()?;
+        let (ty, passing) = lua_arg_type(&declared)?;
+        let ident = quote::format_ident!("arg{}", index);
+        let (pattern, call) = match passing {
+            ArgPassing::Value => (quote::quote!(#ident), quote::quote!(#ident)),
+            ArgPassing::Ref => (quote::quote!(#ident), quote::quote!(&#ident)),
+            ArgPassing::RefMut => (quote::quote!(mut #ident), quote::quote!(&mut #ident)),
+        };
+        patterns.push(pattern);
+        types.push(ty);
+        call_args.push(call);
+    }
+
+    let parameter = match (patterns.as_slice(), types.as_slice()) {
+        ([], _) => quote::quote!(_args: ()),
+        ([pattern], [ty]) => quote::quote!(#pattern: #ty),
+        _ => quote::quote!((#(#patterns),*): (#(#types),*)),
+    };
+    Some((parameter, quote::quote!(#(#call_args),*)))
+}
@@ -0,0 +1,121 @@
+//! Type path resolution helpers shared by build.rs and the crate's tests
+//! (build scripts can't run their own `#[cfg(test)]` tests, so tests/build_support_tests.rs
+//! includes this file directly)
+
+/// Resolve a short type name to its full Bevy path
+/// For compile-time code generation, we need fully qualified paths
+pub fn resolve_short_type_to_full_path(short_name: &str) -> Option<String> {
+    // Map of short names to full Bevy paths
+    // These are the most common types found in SystemParam method signatures
+    let mappings: &[(&str, &str)] = &[
+        // Math types
+        ("Ray3d", "bevy::math::Ray3d"),
+        ("Dir3", "bevy::math::Dir3"),
+        ("Dir3A", "bevy::math::Dir3A"),
+        ("Vec2", "bevy::math::Vec2"),
+        ("Vec3", "bevy::math::Vec3"),
+        ("Vec3A", "bevy::math::Vec3A"),
+        ("Vec4", "bevy::math::Vec4"),
+        ("Quat", "bevy::math::Quat"),
+        ("Mat4", "bevy::math::Mat4"),
+        // Picking types
+        (
+            "MeshRayCastSettings",
+            "bevy::picking::mesh_picking::ray_cast::MeshRayCastSettings",
+        ),
+        (
+            "RayCastSettings",
+            "bevy::picking::mesh_picking::ray_cast::RayCastSettings",
+        ),
+        // ECS types
+        ("Entity", "bevy::ecs::entity::Entity"),
+        // Common types - these are primitives and don't need full paths
+        // Just return them as-is if they parse ok
+    ];
+
+    for (short, full) in mappings {
+        if short_name == *short {
+            return Some(full.to_string());
+        }
+    }
+
+    // Option<T> resolves through its inner type (the runtime converter maps nil <-> None)
+    let compact: String = short_name.chars().filter(|c| !c.is_whitespace()).collect();
+    if let Some(inner) = compact
+        .strip_prefix("Option<")
+        .and_then(|rest| rest.strip_suffix('>'))
+    {
+        return resolve_short_type_to_full_path(inner)
+            .map(|full| format!("core::option::Option<{}>", full));
+    }
+
+    // If it already contains ::, assume it's a full path
+    if short_name.contains("::") {
+        return Some(short_name.to_string());
+    }
+
+    // Primitive types don't need resolution
+    if is_primitive_type(short_name) {
+        return Some(short_name.to_string());
+    }
+
+    // Unknown type - can't resolve
+    None
+}
+
+/// Read `[package.metadata.lua_path_overrides]` from a manifest
+/// e.g. `"hello_game::components" = "hello::components"`
+/// Sorted longest prefix first so the most specific override wins
+pub fn parse_path_overrides(manifest: &toml::Value) -> Vec<(String, String)> {
+    let mut overrides: Vec<(String, String)> = manifest
+        .get("package")
+        .and_then(|p| p.get("metadata"))
+        .and_then(|m| m.get("lua_path_overrides"))
+        .and_then(|o| o.as_table())
+        .map(|table| {
+            table
+                .iter()
+                .filter_map(|(from, to)| Some((from.clone(), to.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+    overrides.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+    overrides
+}
+
+/// Rewrite the module prefix of `path` using the first matching override
+/// Prefixes match whole path segments (`a::b` matches `a::b::T`, not `a::bc::T`)
+pub fn apply_path_override(path: &str, overrides: &[(String, String)]) -> Option<String> {
+    overrides.iter().find_map(|(from, to)| {
+        if path == from {
+            return Some(to.clone());
+        }
+        path.strip_prefix(from.as_str())
+            .and_then(|rest| rest.strip_prefix("::"))
+            .map(|rest| format!("{}::{}", to, rest))
+    })
+}
+
+/// Check if a type is a primitive
+pub fn is_primitive_type(type_str: &str) -> bool {
+    matches!(
+        type_str,
+        "u8" | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "usize"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "isize"
+            | "f32"
+            | "f64"
+            | "bool"
+            | "String"
+            | "str"
+            | "()"
+    )
+}
@@ -19,7 +19,7 @@ fn main() {
 
     // Register serde-based components (for types that don't implement Reflect)
     // This uses the SerdeComponentRegistry that was auto-initialized by LuaSpawnPlugin
-    app.insert_resource(bevy_lua_ecs::serde_components![Collider,]);
+    bevy_lua_ecs::register_serde_components!(app, Collider);
 
     app.add_systems(Startup, setup)
         .add_systems(PostStartup, load_and_run_script)
@@ -166,6 +166,180 @@ pub fn register_cloner_if_clone<T: Asset + Clone + 'static>(
     cloners.insert(std::any::TypeId::of::<T>(), cloner);
 }
 
+/// Asset source serving byte buffers handed over by `world:load_asset_from_bytes`
+pub const LUA_BYTES_SOURCE: &str = "lua_bytes";
+
+/// In-memory files behind the `lua_bytes://` asset source
+/// Each buffer is dropped once its load finishes; the loaded asset stays alive through the
+/// handle kept in AssetRegistry.
+#[derive(Resource, Clone, Default)]
+pub struct LuaBytesAssets {
+    dir: bevy::asset::io::memory::Dir,
+    next_id: Arc<AtomicU32>,
+    loads: Arc<Mutex<Vec<PendingBytesLoad>>>,
+}
+
+/// A `load_untyped_async` started by `world:load_asset_from_bytes`
+struct PendingBytesLoad {
+    path: String,
+    short_name: String,
+    asset_type: std::any::TypeId,
+    /// The handle table returned to Lua, filled in with `id` when the load finishes
+    handle_table: Arc<RegistryKey>,
+    task: bevy::tasks::Task<Result<UntypedHandle, bevy::asset::AssetLoadError>>,
+}
+
+impl LuaBytesAssets {
+    /// Store `bytes` as a new file and return its asset path (`lua_bytes://<n>.<extension>`)
+    /// The extension selects the `AssetLoader`, as it does for files on disk
+    pub fn insert(&self, bytes: Vec<u8>, extension: &str) -> String {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let file_name = format!("{}.{}", id, extension.trim_start_matches('.'));
+        self.dir.insert_asset(std::path::Path::new(&file_name), bytes);
+        format!("{}://{}", LUA_BYTES_SOURCE, file_name)
+    }
+
+    /// Whether the buffer behind `path` is still held (it is dropped once its load finishes)
+    pub fn contains(&self, path: &str) -> bool {
+        self.file_name(path)
+            .is_some_and(|file_name| self.dir.get_asset(std::path::Path::new(file_name)).is_some())
+    }
+
+    /// Start loading `path` with `AssetServer::load_untyped_async` on the IO task pool
+    /// `handle_table` gets its `id` once the asset is loaded (see `finish_lua_bytes_loads`)
+    pub fn load(
+        &self,
+        asset_server: &AssetServer,
+        path: String,
+        short_name: String,
+        asset_type: std::any::TypeId,
+        handle_table: RegistryKey,
+    ) {
+        let asset_server = asset_server.clone();
+        let load_path = path.clone();
+        let task = bevy::tasks::IoTaskPool::get()
+            .spawn(async move { asset_server.load_untyped_async(load_path).await });
+        self.loads.lock().unwrap().push(PendingBytesLoad {
+            path,
+            short_name,
+            asset_type,
+            handle_table: Arc::new(handle_table),
+            task,
+        });
+    }
+
+    fn file_name<'a>(&self, path: &'a str) -> Option<&'a str> {
+        path.strip_prefix(LUA_BYTES_SOURCE)?.strip_prefix("://")
+    }
+
+    /// Drop the buffer behind `path`
+    fn remove(&self, path: &str) {
+        if let Some(file_name) = self.file_name(path) {
+            self.dir.remove_asset(std::path::Path::new(file_name));
+        }
+    }
+}
+
+/// Finish `world:load_asset_from_bytes` loads: keep the loaded handle alive in AssetRegistry,
+/// write its id into the Lua handle table (or `error` if the load failed) and drop the buffer
+pub fn finish_lua_bytes_loads(
+    bytes_assets: Res<LuaBytesAssets>,
+    asset_registry: Option<Res<AssetRegistry>>,
+    lua_ctx: Option<Res<crate::lua_integration::LuaScriptContext>>,
+) {
+    use bevy::tasks::{block_on, futures_lite::future};
+
+    let (Some(asset_registry), Some(lua_ctx)) = (asset_registry, lua_ctx) else {
+        return;
+    };
+
+    let finished: Vec<_> = {
+        let mut loads = bytes_assets.loads.lock().unwrap();
+        let mut finished = Vec::new();
+        let mut i = 0;
+        while i < loads.len() {
+            if let Some(result) = block_on(future::poll_once(&mut loads[i].task)) {
+                finished.push((loads.swap_remove(i), result));
+            } else {
+                i += 1;
+            }
+        }
+        finished
+    };
+
+    for (load, result) in finished {
+        bytes_assets.remove(&load.path);
+
+        let lua = lua_ctx.state_for_key(&load.handle_table);
+        let Ok(handle_table) = lua.registry_value::<LuaTable>(&load.handle_table) else {
+            continue;
+        };
+        let outcome = match result {
+            Ok(handle) if handle.type_id() == load.asset_type => {
+                asset_registry.register_created_handle(handle, &load.short_name)
+            }
+            Ok(handle) => Err(LuaError::RuntimeError(format!(
+                "'{}' did not load as {} ({:?})",
+                load.path,
+                load.short_name,
+                handle.id()
+            ))),
+            Err(e) => Err(LuaError::RuntimeError(format!("failed to load '{}': {}", load.path, e))),
+        };
+        let written = match outcome {
+            Ok(bits) => handle_table.set("id", bits as i64),
+            Err(e) => {
+                warn!("[LOAD_BYTES] {}", e);
+                handle_table.set("error", e.to_string())
+            }
+        };
+        if let Err(e) = written.and_then(|_| handle_table.set("loading", LuaValue::Nil)) {
+            warn!("[LOAD_BYTES] Failed to update handle for '{}': {}", load.path, e);
+        }
+    }
+}
+
+/// Registers the `lua_bytes://` asset source used by `world:load_asset_from_bytes`
+/// Asset sources must exist before the `AssetPlugin` is built, so add this before `DefaultPlugins`
+pub struct LuaBytesAssetSourcePlugin;
+
+impl Plugin for LuaBytesAssetSourcePlugin {
+    fn build(&self, app: &mut App) {
+        use bevy::asset::io::{memory::MemoryAssetReader, AssetSource};
+        use bevy::asset::AssetApp;
+
+        let assets = LuaBytesAssets::default();
+        let root = assets.dir.clone();
+        app.register_asset_source(
+            LUA_BYTES_SOURCE,
+            AssetSource::build().with_reader(move || Box::new(MemoryAssetReader { root: root.clone() })),
+        );
+        app.insert_resource(assets);
+        app.add_systems(PreUpdate, finish_lua_bytes_loads);
+    }
+}
+
+/// Read raw bytes from Lua: a (binary) string or an array of integers 0-255
+pub fn bytes_from_lua(value: &LuaValue) -> LuaResult<Vec<u8>> {
+    match value {
+        LuaValue::String(s) => Ok(s.as_bytes().to_vec()),
+        LuaValue::Table(table) => table
+            .sequence_values::<i64>()
+            .enumerate()
+            .map(|(i, byte)| {
+                let byte = byte?;
+                u8::try_from(byte).map_err(|_| {
+                    LuaError::RuntimeError(format!("byte {} is out of range (0-255): {}", i + 1, byte))
+                })
+            })
+            .collect(),
+        other => Err(LuaError::RuntimeError(format!(
+            "expected a byte string or an array of bytes, got {}",
+            other.type_name()
+        ))),
+    }
+}
+
 /// Pending asset to be created via reflection
 #[derive(Clone)]
 pub struct PendingAsset {
@@ -189,6 +363,10 @@ pub struct AssetRegistry {
     /// Maps asset IDs to (type_name, UntypedAssetId) for created assets
     asset_handles: Arc<Mutex<HashMap<u32, (String, UntypedAssetId)>>>,
 
+    /// Strong handles returned to Lua by world:create_asset, keyed by (short type name, asset index bits)
+    /// Holding them here keeps the assets alive while Lua refers to them by id
+    created_handles: Arc<Mutex<HashMap<(String, u64), UntypedHandle>>>,
+
     /// Pending assets to be created
     pending_assets: Arc<Mutex<HashMap<u32, PendingAsset>>>,
 
@@ -261,6 +439,7 @@ impl Default for AssetRegistry {
             asset_paths: Default::default(),
             typed_handles: Default::default(),
             asset_handles: Default::default(),
+            created_handles: Default::default(),
             pending_assets: Default::default(),
             next_id: Default::default(),
             handle_setters: Default::default(),
@@ -567,6 +746,7 @@ impl AssetRegistry {
             asset_paths: Default::default(),
             typed_handles: Default::default(),
             asset_handles: Default::default(),
+            created_handles: Default::default(),
             pending_assets: Default::default(),
             next_id: Default::default(),
             handle_setters: Arc::new(Mutex::new(HashMap::new())),
@@ -658,6 +838,7 @@ impl AssetRegistry {
             asset_paths: Default::default(),
             typed_handles: Default::default(),
             asset_handles: Default::default(),
+            created_handles: Default::default(),
             pending_assets: Default::default(),
             next_id: Default::default(),
             handle_setters: Arc::new(Mutex::new(handle_setters)),
@@ -1327,6 +1508,42 @@ impl AssetRegistry {
         None
     }
 
+    /// Keep a handle created for Lua alive and return the table Lua refers to it by:
+    /// `{ id = <asset index bits>, type = "Image" }`
+    pub fn handle_to_lua(&self, lua: &Lua, handle: UntypedHandle, short_name: &str) -> LuaResult<LuaTable> {
+        let bits = self.register_created_handle(handle, short_name)?;
+        let table = lua.create_table()?;
+        table.set("id", bits as i64)?;
+        table.set("type", short_name)?;
+        Ok(table)
+    }
+
+    /// Keep a handle alive for Lua and return the id it is known by (its asset index bits)
+    pub fn register_created_handle(&self, handle: UntypedHandle, short_name: &str) -> LuaResult<u64> {
+        let UntypedAssetId::Index { index, .. } = handle.id() else {
+            return Err(LuaError::RuntimeError(format!(
+                "{} handle {:?} has no asset index",
+                short_name,
+                handle.id()
+            )));
+        };
+        let bits = index.to_bits();
+        self.created_handles
+            .lock()
+            .unwrap()
+            .insert((short_name.to_string(), bits), handle);
+        Ok(bits)
+    }
+
+    /// Look up a handle returned by `handle_to_lua` from its `type` and `id` fields
+    pub fn get_created_handle(&self, short_name: &str, bits: u64) -> Option<UntypedHandle> {
+        self.created_handles
+            .lock()
+            .unwrap()
+            .get(&(short_name.to_string(), bits))
+            .cloned()
+    }
+
     /// Register an asset path for deferred typed loading
     /// The actual typed loading happens at spawn time when component type is known
     pub fn register_path(&self, path: String) -> u32 {
@@ -1539,16 +1756,10 @@ pub fn process_pending_assets(world: &mut World) {
     // Get resources we need
     let type_registry = world.resource::<AppTypeRegistry>().clone();
     let asset_registry = world.resource::<AssetRegistry>().clone();
-    let asset_server = world.resource::<AssetServer>().clone();
     let lua_ctx = world
         .resource::<crate::lua_integration::LuaScriptContext>()
         .clone();
 
-    // Get BitflagsRegistry if available (optional - for generic bitflags handling)
-    let bitflags_registry = world
-        .get_resource::<crate::bitflags_registry::BitflagsRegistry>()
-        .cloned();
-
     let mut pending = asset_registry.drain_pending_assets();
 
     if pending.is_empty() {
@@ -1562,158 +1773,14 @@ pub fn process_pending_assets(world: &mut World) {
 
     for (id, pending_asset) in pending {
         let registry = type_registry.read();
+        // Asset data lives in the registry of the state that called create_asset
+        let lua = lua_ctx.state_for_key(&pending_asset.data);
 
         // Find the type registration
         if let Some(registration) = registry.get_with_type_path(&pending_asset.type_name) {
             // Check if it has ReflectAsset
             if let Some(reflect_asset) = registration.data::<ReflectAsset>() {
-                // Special handling for Mesh - convert primitives to mesh
-                let asset_result = if pending_asset.type_name == "bevy_mesh::mesh::Mesh" {
-                    debug!("[ASSET_PROCESS] Processing Mesh asset, ID {}", id);
-                    match create_mesh_from_primitive(
-                        &pending_asset.data,
-                        &lua_ctx.lua,
-                        &type_registry,
-                    ) {
-                        Ok(mesh) => {
-                            if let Some(mesh_ref) = mesh.as_reflect().downcast_ref::<Mesh>() {
-                                debug!(
-                                    "[ASSET_PROCESS] Mesh created successfully, vertices: {}",
-                                    mesh_ref.count_vertices()
-                                );
-                            } else {
-                                debug!(
-                                    "[ASSET_PROCESS] Mesh created, but downcast to Mesh failed!"
-                                );
-                            }
-                            Some(mesh)
-                        }
-                        Err(e) => {
-                            error!("Failed to create mesh from primitive: {}", e);
-                            None
-                        }
-                    }
-                } else {
-                    // Try registered asset constructor first (for generated bindings like Image)
-                    let constructor_result: Option<LuaResult<Box<dyn Reflect>>> = {
-                        if let Ok(lua_value) =
-                            lua_ctx.lua.registry_value::<LuaValue>(&pending_asset.data)
-                        {
-                            if let LuaValue::Table(table) = lua_value {
-                                asset_registry.try_construct_asset(&pending_asset.type_name, &table)
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        }
-                    };
-
-                    if let Some(result) = constructor_result {
-                        debug!(
-                            "[ASSET_PROCESS] Using registered constructor for {}",
-                            pending_asset.type_name
-                        );
-                        match result {
-                            Ok(asset) => Some(asset),
-                            Err(e) => {
-                                error!(
-                                    "Asset constructor failed for {}: {}",
-                                    pending_asset.type_name, e
-                                );
-                                None
-                            }
-                        }
-                    } else if let Some(reflect_default) = registration.data::<ReflectDefault>() {
-                        // Use Default if available
-                        let mut asset = reflect_default.default();
-                        debug!(
-                            "[ASSET_PROCESS] Created default for {}, now populating from Lua",
-                            pending_asset.type_name
-                        );
-
-                        // Populate fields from Lua data (with handle resolution!)
-                        if let Err(e) = populate_asset_from_lua(
-                            asset.as_partial_reflect_mut(),
-                            &pending_asset.data,
-                            &lua_ctx.lua,
-                            &asset_registry,
-                            &asset_server,
-                        ) {
-                            error!(
-                                "Failed to populate asset {}: {}",
-                                pending_asset.type_name, e
-                            );
-                            continue;
-                        }
-
-                        Some(asset)
-                    } else {
-                        // Try to construct from Lua data directly using reflection
-                        match construct_asset_from_lua(
-                            registration,
-                            &pending_asset.data,
-                            &lua_ctx.lua,
-                            &type_registry,
-                        ) {
-                            Ok(asset) => Some(asset),
-                            Err(e) => {
-                                error!(
-                                    "Failed to construct asset {}: {}",
-                                    pending_asset.type_name, e
-                                );
-                                None
-                            }
-                        }
-                    }
-                };
-
-                if let Some(mut asset) = asset_result {
-                    // Generic post-construction: apply any bitflags fields from Lua table
-                    // Uses BitflagsRegistry for generic type detection and value mapping
-                    if let Ok(lua_value) =
-                        lua_ctx.lua.registry_value::<LuaValue>(&pending_asset.data)
-                    {
-                        if let LuaValue::Table(ref t) = lua_value {
-                            apply_post_construction_bitflags(
-                                asset.as_mut(),
-                                t,
-                                &pending_asset.type_name,
-                                bitflags_registry.as_ref(),
-                            );
-
-                            // SPECIAL CASE: Image texture_usages - wgpu types don't support Bevy reflection
-                            // so we need to manually set texture_descriptor.usage
-                            if pending_asset.type_name == "bevy_image::image::Image" {
-                                if let Some(image) = asset.as_any_mut().downcast_mut::<Image>() {
-                                    if let Ok(usages_str) = t.get::<String>("texture_usages") {
-                                        let flags: Vec<&str> =
-                                            usages_str.split('|').map(|s| s.trim()).collect();
-                                        let mut usage_bits = 0u32;
-                                        for flag in flags {
-                                            match flag {
-                                                "COPY_SRC" => usage_bits |= 1,
-                                                "COPY_DST" => usage_bits |= 2,
-                                                "TEXTURE_BINDING" => usage_bits |= 4,
-                                                "STORAGE_BINDING" => usage_bits |= 8,
-                                                "RENDER_ATTACHMENT" => usage_bits |= 16,
-                                                _ => {
-                                                    warn!("[IMAGE] Unknown texture usage: {}", flag)
-                                                }
-                                            }
-                                        }
-                                        image.texture_descriptor.usage =
-                                            TextureUsages::from_bits_truncate(usage_bits);
-                                        debug!(
-                                            "[IMAGE] ✓ Set texture_usages to {:?}",
-                                            image.texture_descriptor.usage
-                                        );
-                                    }
-                                }
-                            }
-                        }
-                    }
-
+                if let Some(asset) = build_asset_from_lua(world, registration, &pending_asset.data, &lua) {
                     // Priority order for asset addition:
                     // 1. Try ReflectDirectAssetAdd from TypeRegistry (uses as_any() downcasting - preserves ALL data)
                     // 2. Try registered cloners from AssetRegistry (same mechanism but manually registered)
@@ -1818,6 +1885,208 @@ pub fn process_pending_assets(world: &mut World) {
     }
 }
 
+/// Build a `T` from a Lua table for the generated `world:create_asset` dispatcher, which adds
+/// it to `Assets<T>` through SystemState; values built through reflection go via ReflectFromReflect
+pub fn asset_from_lua_table<T: Asset>(lua: &Lua, world: &World, props: LuaTable) -> LuaResult<T> {
+    use bevy::reflect::{ReflectFromReflect, TypePath};
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = type_registry.read();
+    let registration = registry.get(std::any::TypeId::of::<T>()).ok_or_else(|| {
+        LuaError::RuntimeError(format!("{} is not registered for reflection", T::short_type_path()))
+    })?;
+
+    let data = lua.create_registry_value(props)?;
+    let construct_error = || {
+        LuaError::RuntimeError(format!(
+            "failed to construct {} from the given properties",
+            T::short_type_path()
+        ))
+    };
+    let asset = build_asset_from_lua(world, registration, &data, lua).ok_or_else(construct_error)?;
+    let asset = if asset.as_any().is::<T>() {
+        asset
+    } else {
+        registration
+            .data::<ReflectFromReflect>()
+            .and_then(|from_reflect| from_reflect.from_reflect(asset.as_partial_reflect()))
+            .ok_or_else(construct_error)?
+    };
+    asset
+        .into_any()
+        .downcast::<T>()
+        .map(|asset| *asset)
+        .map_err(|_| construct_error())
+}
+
+/// Build an asset from the Lua table stored under `data`: Mesh primitives, registered
+/// constructors, Default + field population, then plain reflection, followed by bitflags fields
+/// Shared by process_pending_assets and the generated world:create_asset dispatcher
+fn build_asset_from_lua(
+    world: &World,
+    registration: &TypeRegistration,
+    data: &RegistryKey,
+    lua: &Lua,
+) -> Option<Box<dyn Reflect>> {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let asset_registry = world.resource::<AssetRegistry>().clone();
+    let asset_server = world.resource::<AssetServer>().clone();
+    // Get BitflagsRegistry if available (optional - for generic bitflags handling)
+    let bitflags_registry = world
+        .get_resource::<crate::bitflags_registry::BitflagsRegistry>()
+        .cloned();
+    let type_path = registration.type_info().type_path();
+
+    // Special handling for Mesh - convert primitives to mesh
+    let asset = if type_path == "bevy_mesh::mesh::Mesh" {
+        debug!("[ASSET_PROCESS] Processing Mesh asset");
+        match create_mesh_from_primitive(
+            data,
+            lua,
+            &type_registry,
+        ) {
+            Ok(mesh) => {
+                if let Some(mesh_ref) = mesh.as_reflect().downcast_ref::<Mesh>() {
+                    debug!(
+                        "[ASSET_PROCESS] Mesh created successfully, vertices: {}",
+                        mesh_ref.count_vertices()
+                    );
+                } else {
+                    debug!(
+                        "[ASSET_PROCESS] Mesh created, but downcast to Mesh failed!"
+                    );
+                }
+                Some(mesh)
+            }
+            Err(e) => {
+                error!("Failed to create mesh from primitive: {}", e);
+                None
+            }
+        }
+    } else {
+        // Try registered asset constructor first (for generated bindings like Image)
+        let constructor_result: Option<LuaResult<Box<dyn Reflect>>> = {
+            if let Ok(lua_value) =
+                lua.registry_value::<LuaValue>(data)
+            {
+                if let LuaValue::Table(table) = lua_value {
+                    asset_registry.try_construct_asset(type_path, &table)
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        };
+
+        if let Some(result) = constructor_result {
+            debug!(
+                "[ASSET_PROCESS] Using registered constructor for {}",
+                type_path
+            );
+            match result {
+                Ok(asset) => Some(asset),
+                Err(e) => {
+                    error!(
+                        "Asset constructor failed for {}: {}",
+                        type_path, e
+                    );
+                    None
+                }
+            }
+        } else if let Some(reflect_default) = registration.data::<ReflectDefault>() {
+            // Use Default if available
+            let mut asset = reflect_default.default();
+            debug!(
+                "[ASSET_PROCESS] Created default for {}, now populating from Lua",
+                type_path
+            );
+
+            // Populate fields from Lua data (with handle resolution!)
+            if let Err(e) = populate_asset_from_lua(
+                asset.as_partial_reflect_mut(),
+                data,
+                lua,
+                &asset_registry,
+                &asset_server,
+            ) {
+                error!(
+                    "Failed to populate asset {}: {}",
+                    type_path, e
+                );
+                return None;
+            }
+
+            Some(asset)
+        } else {
+            // Try to construct from Lua data directly using reflection
+            match construct_asset_from_lua(
+                registration,
+                data,
+                lua,
+                &type_registry,
+            ) {
+                Ok(asset) => Some(asset),
+                Err(e) => {
+                    error!(
+                        "Failed to construct asset {}: {}",
+                        type_path, e
+                    );
+                    None
+                }
+            }
+        }
+    };
+    let mut asset = asset?;
+
+    // Generic post-construction: apply any bitflags fields from Lua table
+    // Uses BitflagsRegistry for generic type detection and value mapping
+    if let Ok(lua_value) =
+        lua.registry_value::<LuaValue>(data)
+    {
+        if let LuaValue::Table(ref t) = lua_value {
+            apply_post_construction_bitflags(
+                asset.as_mut(),
+                t,
+                type_path,
+                bitflags_registry.as_ref(),
+            );
+
+            // SPECIAL CASE: Image texture_usages - wgpu types don't support Bevy reflection
+            // so we need to manually set texture_descriptor.usage
+            if type_path == "bevy_image::image::Image" {
+                if let Some(image) = asset.as_any_mut().downcast_mut::<Image>() {
+                    if let Ok(usages_str) = t.get::<String>("texture_usages") {
+                        let flags: Vec<&str> =
+                            usages_str.split('|').map(|s| s.trim()).collect();
+                        let mut usage_bits = 0u32;
+                        for flag in flags {
+                            match flag {
+                                "COPY_SRC" => usage_bits |= 1,
+                                "COPY_DST" => usage_bits |= 2,
+                                "TEXTURE_BINDING" => usage_bits |= 4,
+                                "STORAGE_BINDING" => usage_bits |= 8,
+                                "RENDER_ATTACHMENT" => usage_bits |= 16,
+                                _ => {
+                                    warn!("[IMAGE] Unknown texture usage: {}", flag)
+                                }
+                            }
+                        }
+                        image.texture_descriptor.usage =
+                            TextureUsages::from_bits_truncate(usage_bits);
+                        debug!(
+                            "[IMAGE] ✓ Set texture_usages to {:?}",
+                            image.texture_descriptor.usage
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    Some(asset)
+}
+
 /// Create a Mesh from primitive shape data using reflection (generic for any primitive type)
 fn create_mesh_from_primitive(
     registry_key: &RegistryKey,
@@ -2616,8 +2885,16 @@ pub fn add_asset_loading_to_lua(
     asset_server: AssetServer,
     asset_registry: AssetRegistry,
 ) -> Result<(), LuaError> {
-    let lua = &lua_ctx.lua;
-    let script_cache = lua_ctx.script_cache.clone();
+    add_asset_loading_to_lua_state(&lua_ctx.lua, lua_ctx.script_cache.clone(), asset_server, asset_registry)
+}
+
+/// Add asset loading functions to a single Lua state (used for sandbox states)
+pub fn add_asset_loading_to_lua_state(
+    lua: &Lua,
+    script_cache: crate::script_cache::ScriptCache,
+    asset_server: AssetServer,
+    asset_registry: AssetRegistry,
+) -> Result<(), LuaError> {
 
     // load_asset(path, options) - sync loading with blocking network download
     // Works exactly like require() - yields coroutine if file needs to be downloaded
@@ -3094,3 +3371,29 @@ fn try_set_bitflags_from_names(
 
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_from_lua() {
+        let lua = Lua::new();
+        let table = lua.create_sequence_from([137, 80, 78, 71]).unwrap();
+        assert_eq!(bytes_from_lua(&LuaValue::Table(table)).unwrap(), vec![137, 80, 78, 71]);
+
+        let string = lua.create_string(b"\x89PNG").unwrap();
+        assert_eq!(bytes_from_lua(&LuaValue::String(string)).unwrap(), b"\x89PNG".to_vec());
+
+        let out_of_range = lua.create_sequence_from([1, 256]).unwrap();
+        assert!(bytes_from_lua(&LuaValue::Table(out_of_range)).is_err());
+        assert!(bytes_from_lua(&LuaValue::Boolean(true)).is_err());
+    }
+
+    #[test]
+    fn test_lua_bytes_assets_paths() {
+        let assets = LuaBytesAssets::default();
+        assert_eq!(assets.insert(vec![1, 2, 3], "png"), "lua_bytes://0.png");
+        assert_eq!(assets.insert(vec![4], ".ttf"), "lua_bytes://1.ttf");
+    }
+}
@@ -5,6 +5,7 @@
 //! this registry at startup, allowing asset_loading.rs to apply bitflags generically.
 
 use bevy::prelude::*;
+use mlua::prelude::*;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
@@ -65,4 +66,127 @@ impl BitflagsRegistry {
         }
         Some(value)
     }
+
+    /// Look up a bitflags type by full path or short name (e.g. "TextureUsages")
+    pub fn find_by_name(&self, type_name: &str) -> Option<BitflagsEntry> {
+        {
+            let entries = self.entries.read().unwrap();
+            if let Some(entry) = entries.get(type_name) {
+                return Some(entry.clone());
+            }
+            let suffix = format!("::{}", type_name);
+            if let Some(entry) = entries.values().find(|entry| entry.type_path.ends_with(&suffix)) {
+                return Some(entry.clone());
+            }
+        }
+        self.find_by_type_path(type_name)
+    }
+
+    /// Parse a flag string like "TEXTURE_BINDING | RENDER_ATTACHMENT" (separated by `|` or `,`)
+    /// Unknown types or flag names are errors rather than contributing zero
+    pub fn flags_from_str(&self, type_name: &str, flag_string: &str) -> Result<u32, String> {
+        let entry = self
+            .find_by_name(type_name)
+            .ok_or_else(|| format!("unknown bitflags type '{}'", type_name))?;
+        let mut value: u32 = 0;
+        for name in flag_string.split(['|', ',']).map(str::trim).filter(|name| !name.is_empty()) {
+            let bits = entry
+                .variants
+                .get(name)
+                .ok_or_else(|| format!("unknown flag '{}' for {}", name, entry.type_path))?;
+            value |= bits;
+        }
+        Ok(value)
+    }
+
+    /// Names of the single-bit flags set in `value` (ordered by bit), the reverse of `flags_from_str`
+    pub fn flags_to_names(&self, type_name: &str, value: u32) -> Result<Vec<String>, String> {
+        let entry = self
+            .find_by_name(type_name)
+            .ok_or_else(|| format!("unknown bitflags type '{}'", type_name))?;
+        let mut single_bits: Vec<(&String, u32)> = entry
+            .variants
+            .iter()
+            .filter(|(_, bits)| bits.is_power_of_two())
+            .map(|(name, bits)| (name, *bits))
+            .collect();
+        single_bits.sort_by_key(|(_, bits)| *bits);
+
+        let mut names = Vec::new();
+        let mut covered = 0;
+        for (name, bits) in single_bits {
+            if value & bits != 0 && covered & bits == 0 {
+                names.push(name.clone());
+                covered |= bits;
+            }
+        }
+        if covered != value {
+            return Err(format!(
+                "value {:#x} has bits {:#x} with no flag name in {}",
+                value,
+                value & !covered,
+                entry.type_path
+            ));
+        }
+        Ok(names)
+    }
+}
+
+/// Add the `flags(type_name, flag_string)` and `flags_to_string(type_name, value)` Lua globals
+/// They read the `BitflagsRegistry` stored as Lua app data
+pub fn add_bitflags_functions_to_lua(lua: &Lua) -> LuaResult<()> {
+    fn registry(lua: &Lua) -> LuaResult<BitflagsRegistry> {
+        lua.app_data_ref::<BitflagsRegistry>()
+            .map(|registry| registry.clone())
+            .ok_or_else(|| LuaError::RuntimeError("BitflagsRegistry resource not found".to_string()))
+    }
+
+    // Usage: local usages = flags("TextureUsages", "TEXTURE_BINDING | RENDER_ATTACHMENT")
+    let flags = lua.create_function(|lua, (type_name, flag_string): (String, String)| {
+        registry(lua)?
+            .flags_from_str(&type_name, &flag_string)
+            .map_err(|e| LuaError::RuntimeError(format!("flags: {}", e)))
+    })?;
+    lua.globals().set("flags", flags)?;
+
+    // Usage: print(flags_to_string("TextureUsages", usages))  -- "TEXTURE_BINDING | RENDER_ATTACHMENT"
+    let flags_to_string = lua.create_function(|lua, (type_name, value): (String, u32)| {
+        registry(lua)?
+            .flags_to_names(&type_name, value)
+            .map(|names| names.join(" | "))
+            .map_err(|e| LuaError::RuntimeError(format!("flags_to_string: {}", e)))
+    })?;
+    lua.globals().set("flags_to_string", flags_to_string)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_round_trip_through_lua() {
+        let registry = BitflagsRegistry::new();
+        registry.register(
+            "wgpu_types::TextureUsages",
+            &[("COPY_SRC", 1), ("COPY_DST", 2), ("TEXTURE_BINDING", 4), ("RENDER_ATTACHMENT", 16)],
+        );
+        let lua = Lua::new();
+        lua.set_app_data(registry);
+        add_bitflags_functions_to_lua(&lua).unwrap();
+
+        let value: u32 = lua
+            .load(r#"return flags("TextureUsages", "TEXTURE_BINDING | RENDER_ATTACHMENT, COPY_SRC")"#)
+            .eval()
+            .unwrap();
+        assert_eq!(value, 21);
+
+        let names: String = lua.load(r#"return flags_to_string("TextureUsages", 20)"#).eval().unwrap();
+        assert_eq!(names, "TEXTURE_BINDING | RENDER_ATTACHMENT");
+
+        assert!(lua.load(r#"return flags("NotFlags", "A")"#).exec().is_err());
+        assert!(lua.load(r#"return flags("TextureUsages", "STORAGE")"#).exec().is_err());
+        assert!(lua.load(r#"return flags_to_string("TextureUsages", 8)"#).exec().is_err());
+    }
 }
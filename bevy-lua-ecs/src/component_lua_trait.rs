@@ -10,6 +10,46 @@ use std::sync::{Arc, Mutex};
 pub type ComponentMethod =
     Arc<dyn Fn(&Lua, &mut World, Entity, LuaMultiValue) -> LuaResult<LuaValue> + Send + Sync>;
 
+/// Check run against the would-be value of a component before a queued update is applied
+/// `Err(msg)` drops the update and logs `msg`
+pub type ComponentValidatorFn = Arc<dyn Fn(&dyn Reflect) -> Result<(), String> + Send + Sync>;
+
+/// A validator registered from Rust or from a Lua script
+#[derive(Clone)]
+pub enum ComponentValidator {
+    Rust(ComponentValidatorFn),
+    /// Lua function `function(value) -> ok[, message]` held in the registry of the state that set it
+    Lua(Arc<LuaRegistryKey>),
+}
+
+impl ComponentValidator {
+    /// Run the validator against `value`
+    /// Lua validators receive the value as a table and reject it by returning false/nil
+    pub fn validate(
+        &self,
+        lua_ctx: &crate::LuaScriptContext,
+        value: &dyn Reflect,
+    ) -> Result<(), String> {
+        match self {
+            Self::Rust(validator) => validator(value),
+            Self::Lua(key) => {
+                let lua = lua_ctx.state_for_key(key);
+                let validator: LuaFunction = lua.registry_value(key).map_err(|e| e.to_string())?;
+                let table = crate::lua_world_api::reflection_to_lua(&lua, value.as_partial_reflect())
+                    .map_err(|e| e.to_string())?;
+                let (ok, message): (LuaValue, Option<String>) =
+                    validator.call(table).map_err(|e| e.to_string())?;
+                match ok {
+                    LuaValue::Nil | LuaValue::Boolean(false) => {
+                        Err(message.unwrap_or_else(|| "rejected by Lua validator".to_string()))
+                    }
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
+}
+
 /// Builder for registering component methods (type-safe version)
 pub struct LuaComponentMethods<C: Component> {
     methods: HashMap<String, ComponentMethod>,
@@ -75,6 +115,8 @@ impl<C: Component + 'static> LuaComponentMethods<C> {
 pub struct LuaComponentRegistry {
     components: Arc<Mutex<HashMap<TypeId, HashMap<String, ComponentMethod>>>>,
     type_names: Arc<Mutex<HashMap<String, TypeId>>>,
+    /// Validators by component name (short name or full type path)
+    validators: Arc<Mutex<HashMap<String, ComponentValidator>>>,
 }
 
 impl LuaComponentRegistry {
@@ -112,6 +154,86 @@ impl LuaComponentRegistry {
         debug!("✓ Registered Lua component: {}", type_name);
     }
 
+    /// Register a single method on a component type, keeping methods registered earlier
+    /// Scripts call it on an entity with `entity:call_method(type_name, method_name, ...)`
+    ///
+    /// # Example
+    /// ```ignore
+    /// registry.register_component_method::<Health, _, _, _>("Health", "take_damage", |health, _lua, amount: f32| {
+    ///     health.current -= amount;
+    ///     Ok(health.current)
+    /// });
+    /// ```
+    pub fn register_component_method<C, F, Args, Ret>(&self, type_name: &str, method_name: &str, handler: F)
+    where
+        C: Component<Mutability = bevy::ecs::component::Mutable> + 'static,
+        F: Fn(&mut C, &Lua, Args) -> LuaResult<Ret> + Send + Sync + 'static,
+        Args: FromLuaMulti,
+        Ret: IntoLua,
+    {
+        let mut methods_builder = LuaComponentMethods::<C>::new();
+        methods_builder.add(method_name, handler);
+
+        let type_id = TypeId::of::<C>();
+
+        self.components
+            .lock()
+            .unwrap()
+            .entry(type_id)
+            .or_default()
+            .extend(methods_builder.into_map());
+        self.type_names
+            .lock()
+            .unwrap()
+            .insert(type_name.to_string(), type_id);
+
+        debug!("✓ Registered Lua component method: {}::{}", type_name, method_name);
+    }
+
+    /// Register a validator that every queued update of `type_name` must pass before it is applied
+    /// `type_name` is the short name ("Health") or full type path; replaces any earlier validator
+    ///
+    /// # Example
+    /// ```ignore
+    /// registry.register_with_validator("Health", |value| {
+    ///     let health = value.downcast_ref::<Health>().ok_or("not a Health")?;
+    ///     (0.0..=100.0).contains(&health.current).then_some(()).ok_or_else(|| "out of range".to_string())
+    /// });
+    /// ```
+    pub fn register_with_validator<F>(&self, type_name: &str, validator: F)
+    where
+        F: Fn(&dyn Reflect) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.set_validator(type_name, ComponentValidator::Rust(Arc::new(validator)));
+    }
+
+    /// Set (or replace) the validator for `type_name`
+    pub fn set_validator(&self, type_name: &str, validator: ComponentValidator) {
+        self.validators
+            .lock()
+            .unwrap()
+            .insert(type_name.to_string(), validator);
+        debug!("✓ Registered validator for component: {}", type_name);
+    }
+
+    /// Remove the validator for `type_name`, returning whether one was set
+    pub fn remove_validator(&self, type_name: &str) -> bool {
+        self.validators.lock().unwrap().remove(type_name).is_some()
+    }
+
+    /// Validator for a reflected type, registered under its short name or full type path
+    pub fn validator_for(&self, registration: &bevy::reflect::TypeRegistration) -> Option<ComponentValidator> {
+        let validators = self.validators.lock().unwrap();
+        if validators.is_empty() {
+            return None;
+        }
+        let type_path = registration.type_info().type_path_table();
+        validators
+            .get(type_path.short_path())
+            .or_else(|| validators.get(type_path.path()))
+            .cloned()
+    }
+
     /// Call a method on a component
     pub fn call_method(
         &self,
@@ -122,23 +244,47 @@ impl LuaComponentRegistry {
         method_name: &str,
         args: LuaMultiValue,
     ) -> LuaResult<LuaValue> {
-        let type_names = self.type_names.lock().unwrap();
-        let type_id = type_names.get(type_name).ok_or_else(|| {
+        let type_id = *self.type_names.lock().unwrap().get(type_name).ok_or_else(|| {
             LuaError::RuntimeError(format!("Component type '{}' not registered", type_name))
         })?;
 
-        let components = self.components.lock().unwrap();
-        let methods = components.get(type_id).ok_or_else(|| {
-            LuaError::RuntimeError(format!("Component type '{}' has no methods", type_name))
-        })?;
+        // Clone the handler so the lock is released before calling it (handlers may call back into Lua)
+        let method = {
+            let components = self.components.lock().unwrap();
+            let methods = components.get(&type_id).ok_or_else(|| {
+                LuaError::RuntimeError(format!("Component type '{}' has no methods", type_name))
+            })?;
 
-        let method = methods.get(method_name).ok_or_else(|| {
-            LuaError::RuntimeError(format!(
-                "Method '{}' not found on '{}'",
-                method_name, type_name
-            ))
-        })?;
+            methods.get(method_name).cloned().ok_or_else(|| {
+                LuaError::RuntimeError(format!(
+                    "Method '{}' not found on '{}'",
+                    method_name, type_name
+                ))
+            })?
+        };
 
         method(lua, world, entity, args)
     }
 }
+
+/// Lua access to validators: `world:component_registry():set_validator("Health", fn)`
+impl LuaUserData for LuaComponentRegistry {
+    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+        // set_validator(type_name, function(value) -> ok[, message]) - nil removes the validator
+        methods.add_method(
+            "set_validator",
+            |lua, this, (type_name, validator): (String, Option<LuaFunction>)| {
+                match validator {
+                    Some(validator) => this.set_validator(
+                        &type_name,
+                        ComponentValidator::Lua(Arc::new(lua.create_registry_value(validator)?)),
+                    ),
+                    None => {
+                        this.remove_validator(&type_name);
+                    }
+                }
+                Ok(())
+            },
+        );
+    }
+}
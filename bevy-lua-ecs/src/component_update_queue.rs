@@ -1,13 +1,90 @@
 use bevy::prelude::*;
 use mlua::prelude::*;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Predicate evaluated against the current component value before a conditional update is applied
+pub type UpdatePredicate = Box<dyn Fn(&dyn Reflect) -> bool + Send>;
+
+/// Identifies a group of updates applied all-or-nothing (see `begin_transaction`)
+pub type TransactionId = u64;
+
+/// Encoding of a queued update's data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SerializationFormat {
+    /// A Lua table patched onto the component (reflection) or converted through serde JSON
+    #[default]
+    Json,
+    /// A Lua string of bincode bytes, decoded by a `SerdeComponentRegistry::register_binary` codec
+    Binary,
+}
 
 /// Update request for a component on an entity
 pub struct ComponentUpdateRequest {
     pub entity: Entity,
     pub component_name: String,
     pub data: Arc<LuaRegistryKey>,
+    /// Additional components applied together with this one (LuaEntitySnapshot:flush_all)
+    /// Empty for regular single-component updates
+    pub batched: Vec<(String, Arc<LuaRegistryKey>)>,
+    /// Only apply when this holds for the component's value at processing time (update_if)
+    pub predicate: Option<UpdatePredicate>,
+    /// Set for updates committed as part of a transaction; the whole transaction is skipped if
+    /// any of its entities no longer exists
+    pub transaction: Option<TransactionId>,
+    /// How `data` is encoded
+    pub format: SerializationFormat,
+    /// Lua system that queued the update (see `ComponentUpdateQueue::set_current_system`)
+    /// None for updates queued outside a Lua system (script load, tweens, callbacks)
+    pub system: Option<String>,
+}
+
+impl ComponentUpdateRequest {
+    /// Names of every component this request writes (the batched ones included)
+    fn component_names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.component_name.as_str())
+            .chain(self.batched.iter().map(|(name, _)| name.as_str()))
+    }
+
+    /// Keep, replace or drop each component (`slot` 0 is `component_name`, then `batched`)
+    /// None if nothing is left
+    fn filter_map_components(
+        self,
+        mut f: impl FnMut(usize, &str, Arc<LuaRegistryKey>) -> Option<Arc<LuaRegistryKey>>,
+    ) -> Option<Self> {
+        let ComponentUpdateRequest { entity, component_name, data, batched, predicate, transaction, format, system } = self;
+        let mut components = std::iter::once((component_name, data))
+            .chain(batched)
+            .enumerate()
+            .filter_map(|(slot, (name, data))| f(slot, &name, data).map(|data| (name, data)));
+        let (component_name, data) = components.next()?;
+        Some(ComponentUpdateRequest {
+            entity,
+            component_name,
+            data,
+            batched: components.collect(),
+            predicate,
+            transaction,
+            format,
+            system,
+        })
+    }
+}
+
+/// What to do when different Lua systems write the same fields of an (entity, component) in one
+/// frame. Only the overlapping fields are affected; the rest of each update is merged as usual.
+/// A warning naming the entity, component, fields and queued values is logged in every case.
+/// Updates committed in a transaction are never trimmed, so they stay all-or-nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    /// Apply every update in queue order, so later writes overwrite earlier ones (default)
+    #[default]
+    LastWins,
+    /// Keep the overlapping fields of the first system that wrote them
+    FirstWins,
+    /// Drop the overlapping fields from every system and log an error
+    Error,
 }
 
 /// Removal request for a component on an entity
@@ -23,6 +100,12 @@ pub struct ComponentUpdateQueue {
     removal_queue: Arc<Mutex<Vec<ComponentRemovalRequest>>>,
     /// Lock-free flag for fast-path empty check (optimization)
     has_updates: Arc<AtomicBool>,
+    /// Open transactions: owning script instance and updates staged until `commit_transaction`
+    transactions: Arc<Mutex<HashMap<TransactionId, (u64, Vec<ComponentUpdateRequest>)>>>,
+    next_transaction: Arc<AtomicU64>,
+    conflict_policy: Arc<Mutex<ConflictPolicy>>,
+    /// Lua system currently running; stamped on every request queued while it runs
+    current_system: Arc<Mutex<Option<String>>>,
 }
 
 impl Default for ComponentUpdateQueue {
@@ -31,6 +114,10 @@ impl Default for ComponentUpdateQueue {
             queue: Arc::new(Mutex::new(Vec::new())),
             removal_queue: Arc::new(Mutex::new(Vec::new())),
             has_updates: Arc::new(AtomicBool::new(false)),
+            transactions: Arc::new(Mutex::new(HashMap::new())),
+            next_transaction: Arc::new(AtomicU64::new(1)),
+            conflict_policy: Arc::new(Mutex::new(ConflictPolicy::default())),
+            current_system: Arc::new(Mutex::new(None)),
         }
     }
 }
@@ -42,11 +129,157 @@ impl ComponentUpdateQueue {
             entity,
             component_name,
             data: Arc::new(data),
+            batched: Vec::new(),
+            predicate: None,
+            transaction: None,
+            format: SerializationFormat::Json,
+            system: self.current_system(),
+        };
+        self.queue.lock().unwrap().push(request);
+        self.has_updates.store(true, Ordering::Relaxed);
+    }
+
+    /// Add a component update that is only applied if `predicate` holds for the component's
+    /// current value when `process_component_updates` runs. The update is dropped if the entity
+    /// was despawned or no longer has the (reflected) component.
+    pub fn update_if(
+        &self,
+        entity: Entity,
+        component_name: String,
+        data: LuaRegistryKey,
+        predicate: UpdatePredicate,
+    ) {
+        let request = ComponentUpdateRequest {
+            entity,
+            component_name,
+            data: Arc::new(data),
+            batched: Vec::new(),
+            predicate: Some(predicate),
+            transaction: None,
+            format: SerializationFormat::Json,
+            system: self.current_system(),
         };
         self.queue.lock().unwrap().push(request);
         self.has_updates.store(true, Ordering::Relaxed);
     }
 
+    /// Add a batched update for several components on the same entity
+    /// All components are applied together by the queue processor in one entity access
+    pub fn queue_batch_update(&self, entity: Entity, components: Vec<(String, LuaRegistryKey)>) {
+        let mut components = components
+            .into_iter()
+            .map(|(name, data)| (name, Arc::new(data)));
+        let Some((component_name, data)) = components.next() else {
+            return;
+        };
+        let request = ComponentUpdateRequest {
+            entity,
+            component_name,
+            data,
+            batched: components.collect(),
+            predicate: None,
+            transaction: None,
+            format: SerializationFormat::Json,
+            system: self.current_system(),
+        };
+        self.queue.lock().unwrap().push(request);
+        self.has_updates.store(true, Ordering::Relaxed);
+    }
+
+    /// Add a bincode-encoded component update (`data` holds a Lua string of the bytes)
+    /// The component is decoded with its `register_binary` codec and inserted, replacing any
+    /// existing value
+    pub fn queue_binary_update(&self, entity: Entity, component_name: String, data: LuaRegistryKey) {
+        let request = ComponentUpdateRequest {
+            entity,
+            component_name,
+            data: Arc::new(data),
+            batched: Vec::new(),
+            predicate: None,
+            transaction: None,
+            format: SerializationFormat::Binary,
+            system: self.current_system(),
+        };
+        self.queue.lock().unwrap().push(request);
+        self.has_updates.store(true, Ordering::Relaxed);
+    }
+
+    /// Open a transaction for script instance `instance_id`; updates queued into it are applied
+    /// together or not at all. Transactions left open are dropped with their instance
+    /// (see `clear_instance_transactions`)
+    pub fn begin_transaction(&self, instance_id: u64) -> TransactionId {
+        let id = self.next_transaction.fetch_add(1, Ordering::SeqCst);
+        self.transactions.lock().unwrap().insert(id, (instance_id, Vec::new()));
+        id
+    }
+
+    /// Stage an update in an open transaction
+    /// Returns false if the transaction doesn't exist (never opened, committed or rolled back)
+    pub fn queue_in_transaction(
+        &self,
+        transaction_id: TransactionId,
+        entity: Entity,
+        component_name: String,
+        data: LuaRegistryKey,
+    ) -> bool {
+        let mut transactions = self.transactions.lock().unwrap();
+        let Some((_, staged)) = transactions.get_mut(&transaction_id) else {
+            return false;
+        };
+        staged.push(ComponentUpdateRequest {
+            entity,
+            component_name,
+            data: Arc::new(data),
+            batched: Vec::new(),
+            predicate: None,
+            transaction: Some(transaction_id),
+            format: SerializationFormat::Json,
+            system: self.current_system(),
+        });
+        true
+    }
+
+    /// Move a transaction's staged updates into the queue
+    /// `process_component_updates` checks every entity in the transaction before applying any
+    /// of them. Returns false if the transaction doesn't exist.
+    pub fn commit_transaction(&self, transaction_id: TransactionId) -> bool {
+        let Some((_, staged)) = self.transactions.lock().unwrap().remove(&transaction_id) else {
+            return false;
+        };
+        if !staged.is_empty() {
+            self.queue.lock().unwrap().extend(staged);
+            self.has_updates.store(true, Ordering::Relaxed);
+        }
+        true
+    }
+
+    /// Discard a transaction without applying anything
+    /// Returns the staged registry keys so the caller can free them, or None if it doesn't exist
+    pub fn rollback_transaction(&self, transaction_id: TransactionId) -> Option<Vec<Arc<LuaRegistryKey>>> {
+        let (_, staged) = self.transactions.lock().unwrap().remove(&transaction_id)?;
+        Some(staged.into_iter().map(|request| request.data).collect())
+    }
+
+    /// Discard every transaction a script instance left open (e.g. it errored before
+    /// committing). Returns the staged registry keys so the caller can free them
+    pub fn clear_instance_transactions(&self, instance_id: u64) -> Vec<Arc<LuaRegistryKey>> {
+        let mut transactions = self.transactions.lock().unwrap();
+        let open: Vec<TransactionId> = transactions
+            .iter()
+            .filter(|(_, (owner, _))| *owner == instance_id)
+            .map(|(id, _)| *id)
+            .collect();
+        open.into_iter()
+            .filter_map(|id| transactions.remove(&id))
+            .flat_map(|(_, staged)| staged.into_iter().map(|request| request.data))
+            .collect()
+    }
+
+    /// Number of transactions neither committed nor rolled back yet
+    pub fn open_transaction_count(&self) -> usize {
+        self.transactions.lock().unwrap().len()
+    }
+
     /// Add a component removal request
     pub fn queue_removal(&self, entity: Entity, component_name: String) {
         let request = ComponentRemovalRequest {
@@ -74,6 +307,25 @@ impl ComponentUpdateQueue {
         self.has_updates.load(Ordering::Relaxed)
     }
 
+    /// Choose how conflicting updates to the same (entity, component) in one frame are resolved
+    pub fn set_conflict_policy(&self, policy: ConflictPolicy) {
+        *self.conflict_policy.lock().unwrap() = policy;
+    }
+
+    pub fn conflict_policy(&self) -> ConflictPolicy {
+        *self.conflict_policy.lock().unwrap()
+    }
+
+    /// Name the Lua system whose updates are queued next (None once it returns)
+    /// Set by `run_lua_systems` so conflicts can tell systems apart
+    pub fn set_current_system(&self, system: Option<String>) {
+        *self.current_system.lock().unwrap() = system;
+    }
+
+    pub fn current_system(&self) -> Option<String> {
+        self.current_system.lock().unwrap().clone()
+    }
+
     /// Remove all pending updates for specific entities (e.g., when they're despawned)
     pub fn clear_for_entities(&self, entities: &[Entity]) -> Vec<Arc<LuaRegistryKey>> {
         let mut queue = self.queue.lock().unwrap();
@@ -92,21 +344,391 @@ impl ComponentUpdateQueue {
         // Put back the remaining requests
         *queue = remaining;
 
-        // Return the registry keys that need to be cleaned up
-        removed_requests.into_iter().map(|r| r.data).collect()
+        // Return the registry keys that need to be cleaned up (including batched ones)
+        removed_requests
+            .into_iter()
+            .flat_map(|r| std::iter::once(r.data).chain(r.batched.into_iter().map(|(_, data)| data)))
+            .collect()
     }
 
     /// Peek at pending update for a specific entity+component (for read-through cache)
-    /// Returns a reference to the most recent pending update's data if one exists
-    pub fn peek_pending(&self, entity: Entity, component_name:&str) -> Option<Arc<LuaRegistryKey>> {
+    /// Returns the most recent pending update's data and how it is encoded, if one exists.
+    /// Binary data is a Lua string of bincode bytes; use `peek_pending_value` to get a table.
+    pub fn peek_pending(&self, entity: Entity, component_name:&str) -> Option<(Arc<LuaRegistryKey>, SerializationFormat)> {
         let queue = self.queue.lock().unwrap();
         
         // Find the LAST (most recent) update for this entity+component and clone the Arc
         queue.iter()
             .rev()
-            .find(|req| req.entity == entity && req.component_name == component_name)
-            .map(|req| Arc::clone(&req.data))
+            .filter(|req| req.entity == entity)
+            .find_map(|req| {
+                if req.component_name == component_name {
+                    return Some((Arc::clone(&req.data), req.format));
+                }
+                req.batched
+                    .iter()
+                    .find(|(name, _)| name == component_name)
+                    .map(|(_, data)| (Arc::clone(data), req.format))
+            })
+    }
+
+    /// Lua value of the most recent pending update for an entity+component
+    /// Binary updates are decoded with the component's `register_binary` codec (the
+    /// `SerdeComponentRegistry` app data of `lua`), so callers always get the table form
+    pub fn peek_pending_value(&self, lua: &Lua, entity: Entity, component_name: &str) -> LuaResult<Option<LuaValue>> {
+        let Some((key, format)) = self.peek_pending(entity, component_name) else {
+            return Ok(None);
+        };
+        match format {
+            SerializationFormat::Json => lua.registry_value(&*key).map(Some),
+            SerializationFormat::Binary => {
+                let bytes: LuaString = lua.registry_value(&*key)?;
+                let registry = lua
+                    .app_data_ref::<crate::serde_components::SerdeComponentRegistry>()
+                    .ok_or_else(|| LuaError::RuntimeError("SerdeComponentRegistry not available".to_string()))?;
+                registry
+                    .decode_binary_to_lua(component_name, lua, &bytes.as_bytes())
+                    .unwrap_or_else(|| {
+                        Err(LuaError::RuntimeError(format!(
+                            "{} has no binary codec (register it with SerdeComponentRegistry::register_binary)",
+                            component_name
+                        )))
+                    })
+                    .map(Some)
+            }
+        }
+    }
+}
+
+/// Top-level fields an update writes; None when it replaces the whole value (binary data,
+/// non-table values or tables with non-string keys)
+fn written_fields(lua: &Lua, request: &ComponentUpdateRequest, data: &LuaRegistryKey) -> Option<Vec<String>> {
+    if request.format == SerializationFormat::Binary {
+        return None;
+    }
+    let LuaValue::Table(table) = lua.registry_value::<LuaValue>(data).ok()? else {
+        return None;
+    };
+    table
+        .pairs::<LuaValue, LuaValue>()
+        .map(|pair| match pair.ok()?.0 {
+            LuaValue::String(key) => Some(key.to_str().ok()?.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Copy of the table behind `data` without `fields`; None if no field is left
+fn without_fields(lua: &Lua, data: &LuaRegistryKey, fields: &[&str]) -> LuaResult<Option<LuaRegistryKey>> {
+    let table: LuaTable = lua.registry_value(data)?;
+    let copy = lua.create_table()?;
+    let mut kept = 0;
+    for pair in table.pairs::<LuaValue, LuaValue>() {
+        let (key, value) = pair?;
+        if let LuaValue::String(name) = &key {
+            if fields.iter().any(|field| name.as_bytes() == field.as_bytes()) {
+                continue;
+            }
+        }
+        copy.raw_set(key, value)?;
+        kept += 1;
+    }
+    if kept == 0 {
+        return Ok(None);
     }
+    lua.create_registry_value(copy).map(Some)
+}
+
+/// Find fields of an (entity, component) that more than one Lua system wrote in the drained
+/// requests, warn about them and apply `policy` to just those fields; non-overlapping fields
+/// of every update are kept. Repeated writes by the same system aren't conflicts, and updates
+/// committed in a transaction are never trimmed. `canonical_name` maps a component name to a
+/// stable key (e.g. its full type path, so "Transform" and
+/// "bevy_transform::components::transform::Transform" match) and `lua_for` returns the state
+/// owning a request's registry key.
+pub fn resolve_update_conflicts(
+    requests: Vec<ComponentUpdateRequest>,
+    policy: ConflictPolicy,
+    canonical_name: impl Fn(&str) -> String,
+    lua_for: impl Fn(&LuaRegistryKey) -> Arc<Lua>,
+) -> Vec<ComponentUpdateRequest> {
+    // (entity, component) -> (request index, component slot) of every write, in queue order
+    let mut writers: HashMap<(Entity, String), Vec<(usize, usize)>> = HashMap::new();
+    for (index, request) in requests.iter().enumerate() {
+        for (slot, name) in request.component_names().enumerate() {
+            writers
+                .entry((request.entity, canonical_name(name)))
+                .or_default()
+                .push((index, slot));
+        }
+    }
+    let system_of = |index: usize| requests[index].system.as_deref();
+    writers.retain(|_, writes| {
+        writes.iter().any(|&(index, _)| system_of(index) != system_of(writes[0].0))
+    });
+    if writers.is_empty() {
+        return requests;
+    }
+
+    let slot_data = |index: usize, slot: usize| -> &Arc<LuaRegistryKey> {
+        let request = &requests[index];
+        match slot {
+            0 => &request.data,
+            _ => &request.batched[slot - 1].1,
+        }
+    };
+
+    // (request index, slot) -> overlapping fields to drop; an empty list drops the whole component
+    let mut trimmed: HashMap<(usize, usize), Vec<String>> = HashMap::new();
+    for ((entity, component), writes) in &writers {
+        let fields: Vec<Option<Vec<String>>> = writes
+            .iter()
+            .map(|&(index, slot)| {
+                let data = slot_data(index, slot);
+                written_fields(&lua_for(data), &requests[index], data)
+            })
+            .collect();
+        let mut all_fields: Vec<&str> = fields.iter().flatten().flatten().map(String::as_str).collect();
+        all_fields.sort_unstable();
+        all_fields.dedup();
+        // A whole-value write overlaps every field, including when no table names one
+        let whole = fields.iter().any(Option::is_none);
+        if whole {
+            all_fields.push("");
+        }
+        let writes_field = |write: usize, field: &str| match &fields[write] {
+            None => true,
+            Some(names) => names.iter().any(|name| name == field),
+        };
+
+        let mut conflicting = Vec::new();
+        for field in &all_fields {
+            let field_writers: Vec<usize> = (0..writes.len()).filter(|&w| writes_field(w, field)).collect();
+            let first_system = system_of(writes[field_writers[0]].0);
+            if field_writers.iter().all(|&w| system_of(writes[w].0) == first_system) {
+                continue;
+            }
+            conflicting.push(if field.is_empty() { "<whole value>" } else { field });
+            for &w in &field_writers {
+                let (index, slot) = writes[w];
+                let keep = match policy {
+                    ConflictPolicy::LastWins => true,
+                    ConflictPolicy::FirstWins => system_of(index) == first_system,
+                    ConflictPolicy::Error => false,
+                };
+                if keep || requests[index].transaction.is_some() {
+                    continue;
+                }
+                let dropped = trimmed.entry((index, slot)).or_default();
+                if fields[w].is_some() && !field.is_empty() {
+                    dropped.push(field.to_string());
+                } else {
+                    dropped.clear();
+                    dropped.push(String::new());
+                }
+            }
+        }
+        if conflicting.is_empty() {
+            continue;
+        }
+
+        let values: Vec<String> = writes
+            .iter()
+            .map(|&(index, slot)| {
+                let data = slot_data(index, slot);
+                let value = lua_for(data)
+                    .registry_value::<LuaValue>(data)
+                    .ok()
+                    .and_then(|value| serde_json::to_string(&value).ok())
+                    .unwrap_or_else(|| "<unreadable>".to_string());
+                format!("{}: {}", system_of(index).unwrap_or("<no system>"), value)
+            })
+            .collect();
+        match policy {
+            ConflictPolicy::Error => error!(
+                "[COMPONENT_UPDATE] Systems wrote {:?} of '{}' on {:?} in one frame, those fields not applied: {}",
+                conflicting, component, entity, values.join(" | ")
+            ),
+            _ => warn!(
+                "[COMPONENT_UPDATE] Systems wrote {:?} of '{}' on {:?} in one frame ({:?}): {}",
+                conflicting, component, entity, policy, values.join(" | ")
+            ),
+        }
+    }
+
+    if trimmed.is_empty() {
+        return requests;
+    }
+    requests
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, request)| {
+            request.filter_map_components(|slot, name, data| {
+                let Some(fields) = trimmed.get(&(index, slot)) else {
+                    return Some(data);
+                };
+                if fields.iter().any(String::is_empty) {
+                    return None;
+                }
+                let fields: Vec<&str> = fields.iter().map(String::as_str).collect();
+                match without_fields(&lua_for(&data), &data, &fields) {
+                    Ok(rest) => rest.map(Arc::new),
+                    Err(e) => {
+                        warn!("[COMPONENT_UPDATE] Couldn't trim conflicting fields of '{}': {}", name, e);
+                        None
+                    }
+                }
+            })
+        })
+        .collect()
+}
+
+/// Comparison operator for `FieldPredicate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PredicateOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Literal on the right-hand side of a `FieldPredicate`
+#[derive(Debug, Clone, PartialEq)]
+enum PredicateLiteral {
+    Number(f64),
+    Bool(bool),
+    Str(String),
+}
+
+/// Mini expression used by `entity:set_if`: `current_value.<field path> <op> <literal>`
+///
+/// Supported operators are `==`, `~=` (or `!=`), `<`, `<=`, `>` and `>=`; literals are numbers,
+/// `true`/`false` and quoted strings (compared against strings or enum variant names).
+/// e.g. `"current_value.value > 0"` or `"current_value.state == 'Idle'"`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldPredicate {
+    path: String,
+    op: PredicateOp,
+    literal: PredicateLiteral,
+}
+
+impl FieldPredicate {
+    /// Parse a predicate expression
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        const OPS: [(&str, PredicateOp); 7] = [
+            ("==", PredicateOp::Eq),
+            ("~=", PredicateOp::Ne),
+            ("!=", PredicateOp::Ne),
+            ("<=", PredicateOp::Le),
+            (">=", PredicateOp::Ge),
+            ("<", PredicateOp::Lt),
+            (">", PredicateOp::Gt),
+        ];
+
+        let (index, token, op) = OPS
+            .iter()
+            .filter_map(|(token, op)| expression.find(token).map(|index| (index, *token, *op)))
+            .min_by_key(|(index, token, _)| (*index, std::cmp::Reverse(token.len())))
+            .ok_or_else(|| format!("Predicate '{}' has no comparison operator", expression))?;
+
+        let lhs = expression[..index].trim();
+        let rhs = expression[index + token.len()..].trim();
+
+        let path = lhs
+            .strip_prefix("current_value")
+            .ok_or_else(|| format!("Predicate '{}' must start with 'current_value'", expression))?;
+        let path = path.strip_prefix('.').unwrap_or(path).to_string();
+
+        let literal = if rhs == "true" || rhs == "false" {
+            PredicateLiteral::Bool(rhs == "true")
+        } else if let Some(quoted) = rhs
+            .strip_prefix('"')
+            .and_then(|r| r.strip_suffix('"'))
+            .or_else(|| rhs.strip_prefix('\'').and_then(|r| r.strip_suffix('\'')))
+        {
+            PredicateLiteral::Str(quoted.to_string())
+        } else {
+            PredicateLiteral::Number(
+                rhs.parse::<f64>()
+                    .map_err(|_| format!("Predicate '{}' has an invalid value '{}'", expression, rhs))?,
+            )
+        };
+
+        if matches!(literal, PredicateLiteral::Bool(_) | PredicateLiteral::Str(_))
+            && !matches!(op, PredicateOp::Eq | PredicateOp::Ne)
+        {
+            return Err(format!("Predicate '{}' can only compare strings/booleans with == or ~=", expression));
+        }
+
+        Ok(Self { path, op, literal })
+    }
+
+    /// Evaluate against a component value; missing fields and type mismatches are false
+    pub fn evaluate(&self, value: &dyn Reflect) -> bool {
+        use bevy::reflect::{GetPath, ReflectRef};
+        use std::cmp::Ordering as CmpOrdering;
+
+        let field = if self.path.is_empty() {
+            value.as_partial_reflect()
+        } else {
+            match value.reflect_path(self.path.as_str()) {
+                Ok(field) => field,
+                Err(_) => return false,
+            }
+        };
+
+        let ordering = match &self.literal {
+            PredicateLiteral::Number(expected) => {
+                let Some(actual) = reflect_as_f64(field) else {
+                    return false;
+                };
+                match actual.partial_cmp(expected) {
+                    Some(ordering) => ordering,
+                    None => return false,
+                }
+            }
+            PredicateLiteral::Bool(expected) => match field.try_downcast_ref::<bool>() {
+                Some(actual) if actual == expected => CmpOrdering::Equal,
+                Some(_) => CmpOrdering::Less,
+                None => return false,
+            },
+            PredicateLiteral::Str(expected) => {
+                let actual = match field.reflect_ref() {
+                    ReflectRef::Enum(e) => Some(e.variant_name().to_string()),
+                    _ => field.try_downcast_ref::<String>().cloned(),
+                };
+                match actual {
+                    Some(actual) if &actual == expected => CmpOrdering::Equal,
+                    Some(_) => CmpOrdering::Less,
+                    None => return false,
+                }
+            }
+        };
+
+        match self.op {
+            PredicateOp::Eq => ordering == CmpOrdering::Equal,
+            PredicateOp::Ne => ordering != CmpOrdering::Equal,
+            PredicateOp::Lt => ordering == CmpOrdering::Less,
+            PredicateOp::Le => ordering != CmpOrdering::Greater,
+            PredicateOp::Gt => ordering == CmpOrdering::Greater,
+            PredicateOp::Ge => ordering != CmpOrdering::Less,
+        }
+    }
+}
+
+/// Read a numeric reflected value as f64
+fn reflect_as_f64(value: &dyn PartialReflect) -> Option<f64> {
+    macro_rules! try_numeric {
+        ($($ty:ty),*) => {
+            $(if let Some(n) = value.try_downcast_ref::<$ty>() {
+                return Some(*n as f64);
+            })*
+        };
+    }
+    try_numeric!(f32, f64, i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+    None
 }
 
 /// Get a component from the ECS, merging any queued updates if present
@@ -134,7 +756,7 @@ pub fn get_component_with_queue<T: Component + Clone + bevy::reflect::Reflect>(
     };
     
     // Check for pending update
-    let Some(pending_key) = update_queue.peek_pending(entity, component_name) else {
+    let Some(pending_value) = update_queue.peek_pending_value(lua, entity, component_name)? else {
         // No pending update for this specific component, use ECS state
         debug!("[QUEUE_MERGE] No pending update for entity {:?} component {}", entity, component_name);
         return Ok(Some(component));
@@ -142,7 +764,7 @@ pub fn get_component_with_queue<T: Component + Clone + bevy::reflect::Reflect>(
     
     // Merge pending update into component
     debug!("[QUEUE_MERGE] ✓ Found pending update for entity {:?} component {}, merging!", entity, component_name);
-    let pending_table: LuaTable = lua.registry_value(&pending_key)?;
+    let pending_table: LuaTable = lua.unpack(pending_value)?;
 
     
     // Use reflection to merge the pending Lua table into the component
@@ -187,3 +809,137 @@ pub fn get_component_with_queue<T: Component + Clone + bevy::reflect::Reflect>(
     
     Ok(Some(component))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Reflect, Default)]
+    struct Health {
+        value: f32,
+        label: String,
+    }
+
+    #[test]
+    fn test_field_predicate_parse_and_evaluate() {
+        let health = Health { value: 5.0, label: "ok".to_string() };
+
+        assert!(FieldPredicate::parse("current_value.value > 0").unwrap().evaluate(&health));
+        assert!(FieldPredicate::parse("current_value.value >= 5").unwrap().evaluate(&health));
+        assert!(!FieldPredicate::parse("current_value.value < 5").unwrap().evaluate(&health));
+        assert!(FieldPredicate::parse("current_value.label == 'ok'").unwrap().evaluate(&health));
+        assert!(FieldPredicate::parse("current_value.label ~= \"dead\"").unwrap().evaluate(&health));
+        assert!(!FieldPredicate::parse("current_value.missing > 0").unwrap().evaluate(&health));
+
+        assert!(FieldPredicate::parse("value > 0").is_err());
+        assert!(FieldPredicate::parse("current_value.value").is_err());
+        assert!(FieldPredicate::parse("current_value.label > 'a'").is_err());
+    }
+
+    #[test]
+    fn test_conflicting_updates_follow_policy() {
+        let lua = Arc::new(Lua::new());
+        let first = Entity::from_bits(1 << 32 | 1);
+        let second = Entity::from_bits(1 << 32 | 2);
+        let table = |source: &str| -> LuaRegistryKey {
+            let value: LuaTable = lua.load(source).eval().unwrap();
+            lua.create_registry_value(value).unwrap()
+        };
+        let queue_conflicts = |queue: &ComponentUpdateQueue| {
+            queue.set_current_system(Some("Physics".to_string()));
+            queue.queue_update(first, "Transform".to_string(), table("{ x = 1, y = 1 }"));
+            queue.queue_update(first, "Transform".to_string(), table("{ x = 5 }"));
+            queue.set_current_system(Some("Ai".to_string()));
+            queue.queue_batch_update(
+                first,
+                vec![
+                    ("Health".to_string(), table("{ current = 2 }")),
+                    ("bevy::Transform".to_string(), table("{ x = 3, z = 3 }")),
+                ],
+            );
+            queue.queue_update(second, "Transform".to_string(), table("{ x = 4 }"));
+            queue.set_current_system(Some("Teleport".to_string()));
+            let tx = queue.begin_transaction(1);
+            queue.queue_in_transaction(tx, first, "Transform".to_string(), table("{ y = 7 }"));
+            queue.commit_transaction(tx);
+            queue.set_current_system(None);
+        };
+        let canonical = |name: &str| name.rsplit("::").next().unwrap().to_string();
+        let lua_for = |_: &LuaRegistryKey| lua.clone();
+        // "Component{field=value,...}" per applied component, fields sorted
+        let written = |requests: &[ComponentUpdateRequest]| -> Vec<String> {
+            requests
+                .iter()
+                .flat_map(|r| std::iter::once((&r.component_name, &r.data)).chain(r.batched.iter().map(|(name, data)| (name, data))))
+                .map(|(name, data)| {
+                    let mut fields: Vec<String> = lua
+                        .registry_value::<LuaTable>(data)
+                        .unwrap()
+                        .pairs::<String, i64>()
+                        .map(|pair| pair.map(|(k, v)| format!("{}={}", k, v)).unwrap())
+                        .collect();
+                    fields.sort();
+                    format!("{}{{{}}}", canonical(name), fields.join(","))
+                })
+                .collect()
+        };
+
+        let queue = ComponentUpdateQueue::default();
+        assert_eq!(queue.conflict_policy(), ConflictPolicy::LastWins);
+        queue_conflicts(&queue);
+        let drained = queue.drain();
+        assert_eq!(drained[0].system.as_deref(), Some("Physics"));
+        assert_eq!(drained[4].system.as_deref(), Some("Teleport"));
+        let kept = resolve_update_conflicts(drained, queue.conflict_policy(), canonical, lua_for);
+        assert_eq!(
+            written(&kept),
+            vec!["Transform{x=1,y=1}", "Transform{x=5}", "Health{current=2}", "Transform{x=3,z=3}", "Transform{x=4}", "Transform{y=7}"]
+        );
+
+        // Physics keeps `x`; Ai's `z` and the transaction still apply
+        queue.set_conflict_policy(ConflictPolicy::FirstWins);
+        queue_conflicts(&queue);
+        let kept = resolve_update_conflicts(queue.drain(), queue.conflict_policy(), canonical, lua_for);
+        assert_eq!(
+            written(&kept),
+            vec!["Transform{x=1,y=1}", "Transform{x=5}", "Health{current=2}", "Transform{z=3}", "Transform{x=4}", "Transform{y=7}"]
+        );
+
+        // Nobody's `x` or non-transactional `y` applies; everything else is merged
+        queue.set_conflict_policy(ConflictPolicy::Error);
+        queue_conflicts(&queue);
+        let kept = resolve_update_conflicts(queue.drain(), queue.conflict_policy(), canonical, lua_for);
+        assert_eq!(
+            written(&kept),
+            vec!["Health{current=2}", "Transform{z=3}", "Transform{x=4}", "Transform{y=7}"]
+        );
+        assert_eq!(kept[0].component_name, "Health");
+        assert!(kept[2].transaction.is_some());
+    }
+
+    #[test]
+    fn test_transaction_updates_queue_only_on_commit() {
+        let lua = Lua::new();
+        let queue = ComponentUpdateQueue::default();
+        let entity = Entity::PLACEHOLDER;
+
+        let tx = queue.begin_transaction(1);
+        assert!(queue.queue_in_transaction(tx, entity, "Health".to_string(), lua.create_registry_value(1).unwrap()));
+        assert!(!queue.has_pending());
+
+        assert!(queue.commit_transaction(tx));
+        let drained = queue.drain();
+        assert_eq!(drained.len(), 1);
+        assert_eq!(drained[0].transaction, Some(tx));
+
+        // Committed transactions are closed
+        assert!(!queue.commit_transaction(tx));
+        assert!(!queue.queue_in_transaction(tx, entity, "Health".to_string(), lua.create_registry_value(2).unwrap()));
+
+        let rolled_back = queue.begin_transaction(1);
+        queue.queue_in_transaction(rolled_back, entity, "Health".to_string(), lua.create_registry_value(3).unwrap());
+        assert_eq!(queue.rollback_transaction(rolled_back).map(|keys| keys.len()), Some(1));
+        assert!(!queue.commit_transaction(rolled_back));
+        assert!(queue.drain().is_empty());
+    }
+}
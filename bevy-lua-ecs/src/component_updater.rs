@@ -1,10 +1,35 @@
-use crate::component_update_queue::ComponentUpdateQueue;
+use crate::component_update_queue::{ComponentUpdateQueue, ComponentUpdateRequest, SerializationFormat};
 use crate::components::LuaCustomComponents;
 use crate::lua_integration::LuaScriptContext;
 use bevy::prelude::*;
 use bevy::reflect::ReflectFromPtr;
 use mlua::prelude::*;
 
+/// Evaluate an `update_if` predicate against the component's current value
+/// False if the entity is gone or doesn't have the component (only reflected components can be checked)
+fn update_predicate_holds(
+    world: &World,
+    entity: Entity,
+    component_name: &str,
+    predicate: &crate::component_update_queue::UpdatePredicate,
+    type_registry: &AppTypeRegistry,
+) -> bool {
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        return false;
+    };
+    let registry = type_registry.read();
+    let Some(reflect_component) = registry
+        .get_with_type_path(component_name)
+        .or_else(|| registry.get_with_short_type_path(component_name))
+        .and_then(|registration| registration.data::<ReflectComponent>())
+    else {
+        return false;
+    };
+    reflect_component
+        .reflect(entity_ref)
+        .is_some_and(|current| predicate(current))
+}
+
 /// System that processes the component update queue
 /// Uses reflection for updating ANY Bevy component (Text2d, Transform, etc.)
 pub fn process_component_updates(
@@ -29,211 +54,83 @@ pub fn process_component_updates(
     let type_registry = world.resource::<AppTypeRegistry>().clone();
     let asset_registry = world.get_resource::<crate::asset_loading::AssetRegistry>().cloned();
     let spawn_queue = world.resource::<crate::spawn_queue::SpawnQueue>().clone();
+
+    // Binary updates are decoded to tables first so they go through the same conflict,
+    // transaction, predicate and validator handling as table updates
+    let (requests, undecodable_transactions) = decode_binary_updates(world, requests);
+
+    // Several systems wrote the same fields of an (entity, component) this frame: warn and apply
+    // the queue's policy
+    let requests = {
+        let policy = world.resource::<ComponentUpdateQueue>().conflict_policy();
+        let lua_ctx = world.resource::<LuaScriptContext>();
+        let registry = type_registry.read();
+        crate::component_update_queue::resolve_update_conflicts(
+            requests,
+            policy,
+            |name| {
+                registry
+                    .get_with_type_path(name)
+                    .or_else(|| registry.get_with_short_type_path(name))
+                    .map(|registration| registration.type_info().type_path().to_string())
+                    .unwrap_or_else(|| name.to_string())
+            },
+            |key| lua_ctx.state_for_key(key),
+        )
+    };
+
+    // Transactions apply all-or-nothing: drop every update of a transaction that touches a
+    // despawned entity
+    let mut failed_transactions: std::collections::HashSet<_> = requests
+        .iter()
+        .filter(|request| world.get_entity(request.entity).is_err())
+        .filter_map(|request| request.transaction)
+        .collect();
+    failed_transactions.extend(undecodable_transactions);
     
     for request in requests {
-        let type_path = request.component_name.clone();
-        debug!("[COMPONENT_UPDATE] Processing request for component '{}' on entity {:?} (bits={})", type_path, request.entity, request.entity.to_bits());
-        
-        // Get Lua data before we start mutable access
-        let data_value: LuaValue = {
-            let lua_ctx = world.resource::<LuaScriptContext>();
-            match lua_ctx.lua.registry_value(&*request.data) {
-                Ok(value) => value,
-                Err(e) => {
-                    error!("Failed to retrieve Lua value for {}: {}", type_path, e);
-                    continue;
-                }
+        if let Some(transaction) = request.transaction {
+            if failed_transactions.contains(&transaction) {
+                debug!(
+                    "[COMPONENT_UPDATE] Skipping '{}' on {:?}: transaction {} references a missing entity",
+                    request.component_name, request.entity, transaction
+                );
+                continue;
             }
-        };
-        
-        // Pre-resolve entity references in the data (handles temp_id -> Entity conversion)
-        // This is critical for components like UiTargetCamera that reference entities from spawn()
-        let resolved_data = {
-            let lua_ctx = world.resource::<LuaScriptContext>();
-            resolve_entity_references_in_table(&lua_ctx.lua, &data_value, &spawn_queue)
-        };
-        
-        // Check entity exists
-        if world.get_entity(request.entity).is_err() {
-            // Arc will be dropped automatically - don't need to remove_registry_value
-            // (multiple Arcs might reference same key)
-            continue;
         }
-        
-        // Try reflection-based update using in-place mutation
-        // This is the generic path that works for all reflected components like Text2d
-        let registry = type_registry.read();
-        let registration = registry.get_with_type_path(&type_path)
-            .or_else(|| registry.get_with_short_type_path(&type_path));
-        
-        debug!("[COMPONENT_UPDATE] Looking up type: '{}' -> found: {}", type_path, registration.is_some());
-        
-        let mut component_updated = false;
-        
-        if let Some(registration) = registration {
-            if let (Some(_reflect_component), Some(reflect_from_ptr)) = (
-                registration.data::<ReflectComponent>(),
-                registration.data::<ReflectFromPtr>().cloned(),
-            ) {
-                let component_id = world.components().get_id(registration.type_id());
-                
-                if let Some(comp_id) = component_id {
-                    // Release read lock before mutable access
-                    drop(registry);
-                    
-                    // Now do mutable entity access
-                    if let Ok(mut entity_mut) = world.get_entity_mut(request.entity) {
-                        if let Ok(mut component_ptr) = entity_mut.get_mut_by_id(comp_id) {
-                            // SAFETY: We have the correct TypeId and exclusive access via EntityMut
-                            let component_mut = unsafe { 
-                                reflect_from_ptr.as_reflect_mut(component_ptr.as_mut()) 
-                            };
-                            
-                            // Update fields from Lua table (using resolved_data with temp_ids converted)
-                            if let LuaValue::Table(ref table) = resolved_data {
-                                if let Err(e) = update_component_from_lua(
-                                    component_mut.as_partial_reflect_mut(),
-                                    table,
-                                    asset_registry.as_ref(),
-                                    &type_registry,
-                                ) {
-                                    error!("Failed to update component {} from Lua: {}", type_path, e);
-                                } else {
-                                    debug!("[COMPONENT_UPDATE] Updated {} via reflection", type_path);
-                                    component_updated = true;
-                                }
-                            }
-                        } else {
-                            // Component doesn't exist - try to INSERT it
-                            debug!("[COMPONENT_UPDATE] Component {} not found on entity, attempting insert", type_path);
-                            
-                            // We need to re-acquire registry for insert
-                            drop(entity_mut);
-                            
-                            // Try PATH 1: ReflectDefault-based creation
-                            let insert_data: Option<(Box<dyn bevy::reflect::Reflect>, ReflectComponent)> = {
-                                let registry = type_registry.read();
-                                registry.get_with_type_path(&type_path)
-                                    .or_else(|| registry.get_with_short_type_path(&type_path))
-                                    .and_then(|registration| {
-                                        // Get ReflectDefault and ReflectComponent
-                                        let reflect_default = registration.data::<ReflectDefault>()?;
-                                        let reflect_component = registration.data::<ReflectComponent>()?.clone();
-                                        let new_component = reflect_default.default();
-                                        Some((new_component, reflect_component))
-                                    })
-                            };
-                            
-                            if let Some((mut new_component, reflect_component)) = insert_data {
-                                // PATH 1: Use ReflectDefault
-                                if let LuaValue::Table(ref table) = resolved_data {
-                                    if let Err(e) = update_component_from_lua(
-                                        new_component.as_partial_reflect_mut(),
-                                        table,
-                                        asset_registry.as_ref(),
-                                        &type_registry,
-                                    ) {
-                                        error!("[COMPONENT_UPDATE] Failed to patch new component {}: {}", type_path, e);
-                                    } else {
-                                        // Insert the component using ReflectComponent
-                                        let registry_read = type_registry.read();
-                                        if let Ok(mut entity_mut) = world.get_entity_mut(request.entity) {
-                                            reflect_component.insert(&mut entity_mut, new_component.as_partial_reflect(), &registry_read);
-                                            debug!("[COMPONENT_UPDATE] ✓ Inserted new component {} on entity", type_path);
-                                            component_updated = true;
-                                        }
-                                    }
-                                }
-                            } else {
-                                // PATH 2: Try entity wrapper components (like UiTargetCamera(Entity))
-                                // These don't have Default but can be constructed if we have the entity value
-                                debug!("[COMPONENT_UPDATE] Trying entity wrapper path for {}", type_path);
-                                
-                                if let LuaValue::Table(ref table) = resolved_data {
-                                    // Look for 'entity' field containing the entity ID
-                                    if let Ok(entity_bits) = table.get::<i64>("entity") {
-                                        let target_entity = bevy::ecs::entity::Entity::from_bits(entity_bits as u64);
-                                        debug!("[COMPONENT_UPDATE] Found entity field: {} -> {:?}", entity_bits, target_entity);
-                                        
-                                        // Get ReflectFromReflect and ReflectComponent from registry
-                                        let registry = type_registry.read();
-                                        if let Some(registration) = registry.get_with_type_path(&type_path)
-                                            .or_else(|| registry.get_with_short_type_path(&type_path))
-                                        {
-                                            if let (Some(from_reflect), Some(reflect_component)) = (
-                                                registration.data::<bevy::reflect::ReflectFromReflect>(),
-                                                registration.data::<ReflectComponent>().cloned()
-                                            ) {
-                                                // Create a DynamicTupleStruct with the entity
-                                                let mut dynamic = bevy::reflect::DynamicTupleStruct::default();
-                                                dynamic.insert_boxed(Box::new(target_entity));
-                                                
-                                                // Set the represented type for from_reflect to work
-                                                let represented_type = registration.type_info();
-                                                dynamic.set_represented_type(Some(represented_type));
-                                                
-                                                if let Some(concrete) = from_reflect.from_reflect(dynamic.as_partial_reflect()) {
-                                                    drop(registry);
-                                                    let registry_read = type_registry.read();
-                                                    if let Ok(mut entity_mut) = world.get_entity_mut(request.entity) {
-                                                        reflect_component.insert(&mut entity_mut, concrete.as_partial_reflect(), &registry_read);
-                                                        debug!("[COMPONENT_UPDATE] ✓ Inserted entity wrapper component {} on entity", type_path);
-                                                        component_updated = true;
-                                                    }
-                                                } else {
-                                                    error!("[COMPONENT_UPDATE] from_reflect failed for {}", type_path);
-                                                }
-                                            } else {
-                                                debug!("[COMPONENT_UPDATE] Component {} has no ReflectFromReflect or ReflectComponent", type_path);
-                                            }
-                                        }
-                                    } else {
-                                        debug!("[COMPONENT_UPDATE] No 'entity' field found for entity wrapper {}", type_path);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                } else {
-                    drop(registry);
-                }
-            } else {
-                drop(registry);
+
+        if let Some(predicate) = &request.predicate {
+            if !update_predicate_holds(world, request.entity, &request.component_name, predicate, &type_registry) {
+                debug!(
+                    "[COMPONENT_UPDATE] Skipping conditional update of '{}' on {:?}: predicate not met",
+                    request.component_name, request.entity
+                );
+                continue;
             }
-        } else {
-            drop(registry);
         }
-        
-        if component_updated {
-            // Arc will be dropped automatically
-            continue;
-        }
-        
-        // Fallback: It's a generic Lua component - store in LuaCustomComponents
-        // Get current tick for change tracking BEFORE borrowing world mutably
-        let current_tick = world.read_change_tick().get();
-
-        if let Ok(mut entity_mut) = world.get_entity_mut(request.entity) {
-            if let Some(mut lua_components) = entity_mut.get_mut::<LuaCustomComponents>() {
-                // Check if this is a NEW component (for Added filter support)
-                let is_new = !lua_components.components.contains_key(&request.component_name);
-                lua_components.components.insert(request.component_name.clone(), request.data.clone());
-                lua_components.changed_ticks.insert(request.component_name.clone(), current_tick);
-                // Only set added_ticks on first insertion
-                if is_new {
-                    lua_components.added_ticks.insert(request.component_name.clone(), current_tick);
-                }
-            } else {
-                // Entity doesn't have LuaCustomComponents yet - this is definitely a new component
-                let mut lua_components = LuaCustomComponents::default();
-                lua_components.components.insert(request.component_name.clone(), request.data.clone());
-                lua_components.changed_ticks.insert(request.component_name.clone(), current_tick);
-                lua_components.added_ticks.insert(request.component_name.clone(), current_tick);
-                entity_mut.insert(lua_components);
-            }
+
+        if request.batched.is_empty() {
+            apply_component_update(
+                world,
+                request.entity,
+                &request.component_name,
+                &request.data,
+                &type_registry,
+                asset_registry.as_ref(),
+                &spawn_queue,
+            );
         } else {
-            warn!("Entity {:?} not found for component update", request.entity);
-            // Arc will be dropped automatically
+            // Batched write-back (LuaEntitySnapshot:flush_all) - apply all components together
+            let mut components = vec![(request.component_name, request.data)];
+            components.extend(request.batched);
+            apply_batched_update(
+                world,
+                request.entity,
+                components,
+                &type_registry,
+                asset_registry.as_ref(),
+                &spawn_queue,
+            );
         }
     }
     
@@ -289,8 +186,439 @@ pub fn process_component_updates(
     }
 }
 
+/// Replace bincode updates (queue_binary_update) with table updates decoded by the component's
+/// codec. Undecodable updates are dropped; their transactions are returned so they fail as a whole
+fn decode_binary_updates(
+    world: &World,
+    requests: Vec<ComponentUpdateRequest>,
+) -> (Vec<ComponentUpdateRequest>, Vec<crate::component_update_queue::TransactionId>) {
+    if requests.iter().all(|request| request.format != SerializationFormat::Binary) {
+        return (requests, Vec::new());
+    }
+    let lua_ctx = world.resource::<LuaScriptContext>();
+    let serde_registry = world.resource::<crate::serde_components::SerdeComponentRegistry>();
+
+    let mut decoded = Vec::with_capacity(requests.len());
+    let mut failed_transactions = Vec::new();
+    for mut request in requests {
+        if request.format != SerializationFormat::Binary {
+            decoded.push(request);
+            continue;
+        }
+        let lua = lua_ctx.state_for_key(&request.data);
+        let table = lua
+            .registry_value::<LuaString>(&*request.data)
+            .and_then(|bytes| {
+                serde_registry
+                    .decode_binary_to_lua(&request.component_name, &lua, &bytes.as_bytes())
+                    .unwrap_or_else(|| {
+                        Err(LuaError::RuntimeError(format!(
+                            "{} has no binary codec (register it with SerdeComponentRegistry::register_binary)",
+                            request.component_name
+                        )))
+                    })
+            })
+            .and_then(|value| lua.create_registry_value(value));
+        match table {
+            Ok(key) => {
+                request.data = std::sync::Arc::new(key);
+                request.format = SerializationFormat::Json;
+                decoded.push(request);
+            }
+            Err(e) => {
+                error!("[COMPONENT_UPDATE] Failed to decode binary {}: {}", request.component_name, e);
+                failed_transactions.extend(request.transaction);
+            }
+        }
+    }
+    (decoded, failed_transactions)
+}
+
+/// Apply a single queued component update to an entity
+/// Updates reflected components in place, inserts missing ones, or falls back to LuaCustomComponents
+fn apply_component_update(
+    world: &mut World,
+    entity: Entity,
+    component_name: &str,
+    data: &std::sync::Arc<LuaRegistryKey>,
+    type_registry: &AppTypeRegistry,
+    asset_registry: Option<&crate::asset_loading::AssetRegistry>,
+    spawn_queue: &crate::spawn_queue::SpawnQueue,
+) {
+    let type_path = component_name.to_string();
+    debug!("[COMPONENT_UPDATE] Processing request for component '{}' on entity {:?} (bits={})", type_path, entity, entity.to_bits());
+    
+    // Get Lua data before we start mutable access
+    // The key belongs to the state that queued the update (a sandbox state or the primary)
+    let lua = world.resource::<LuaScriptContext>().state_for_key(data);
+    let data_value: LuaValue = match lua.registry_value(&**data) {
+        Ok(value) => value,
+        Err(e) => {
+            error!("Failed to retrieve Lua value for {}: {}", type_path, e);
+            return;
+        }
+    };
+    
+    // Pre-resolve entity references in the data (handles temp_id -> Entity conversion)
+    // This is critical for components like UiTargetCamera that reference entities from spawn()
+    let resolved_data = resolve_entity_references_in_table(&lua, &data_value, spawn_queue);
+    
+    // Check entity exists
+    if world.get_entity(entity).is_err() {
+        // Arc will be dropped automatically - don't need to remove_registry_value
+        // (multiple Arcs might reference same key)
+        return;
+    }
+    
+    // Try reflection-based update using in-place mutation
+    // This is the generic path that works for all reflected components like Text2d
+    let registry = type_registry.read();
+    let registration = registry.get_with_type_path(&type_path)
+        .or_else(|| registry.get_with_short_type_path(&type_path));
+    
+    debug!("[COMPONENT_UPDATE] Looking up type: '{}' -> found: {}", type_path, registration.is_some());
+    
+    let mut component_updated = false;
+    let validator = registration.and_then(|registration| {
+        world
+            .get_resource::<crate::component_lua_trait::LuaComponentRegistry>()?
+            .validator_for(registration)
+    });
+    let lua_ctx = world.resource::<LuaScriptContext>().clone();
+    
+    if let Some(registration) = registration {
+        if let (Some(_reflect_component), Some(reflect_from_ptr)) = (
+            registration.data::<ReflectComponent>(),
+            registration.data::<ReflectFromPtr>().cloned(),
+        ) {
+            let component_id = world.components().get_id(registration.type_id());
+            
+            if let Some(comp_id) = component_id {
+                // Release read lock before mutable access
+                drop(registry);
+                
+                // Now do mutable entity access
+                if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+                    if let Ok(mut component_ptr) = entity_mut.get_mut_by_id(comp_id) {
+                        // SAFETY: We have the correct TypeId and exclusive access via EntityMut
+                        let component_mut = unsafe { 
+                            reflect_from_ptr.as_reflect_mut(component_ptr.as_mut()) 
+                        };
+                        
+                        // Update fields from Lua table (using resolved_data with temp_ids converted)
+                        if let LuaValue::Table(ref table) = resolved_data {
+                            match update_component_validated(
+                                component_mut,
+                                table,
+                                asset_registry,
+                                type_registry,
+                                validator.as_ref(),
+                                &lua_ctx,
+                                &type_path,
+                            ) {
+                                Err(e) => error!("Failed to update component {} from Lua: {}", type_path, e),
+                                Ok(applied) => {
+                                    if applied {
+                                        debug!("[COMPONENT_UPDATE] Updated {} via reflection", type_path);
+                                    }
+                                    // A rejected update is still handled: don't fall back to LuaCustomComponents
+                                    component_updated = true;
+                                }
+                            }
+                        }
+                    } else {
+                        // Component doesn't exist - try to INSERT it
+                        debug!("[COMPONENT_UPDATE] Component {} not found on entity, attempting insert", type_path);
+                        
+                        // We need to re-acquire registry for insert
+                        drop(entity_mut);
+                        
+                        // Try PATH 1: ReflectDefault-based creation
+                        let insert_data: Option<(Box<dyn bevy::reflect::Reflect>, ReflectComponent)> = {
+                            let registry = type_registry.read();
+                            registry.get_with_type_path(&type_path)
+                                .or_else(|| registry.get_with_short_type_path(&type_path))
+                                .and_then(|registration| {
+                                    // Get ReflectDefault and ReflectComponent
+                                    let reflect_default = registration.data::<ReflectDefault>()?;
+                                    let reflect_component = registration.data::<ReflectComponent>()?.clone();
+                                    let new_component = reflect_default.default();
+                                    Some((new_component, reflect_component))
+                                })
+                        };
+                        
+                        if let Some((mut new_component, reflect_component)) = insert_data {
+                            // PATH 1: Use ReflectDefault
+                            if let LuaValue::Table(ref table) = resolved_data {
+                                match update_component_validated(
+                                    new_component.as_mut(),
+                                    table,
+                                    asset_registry,
+                                    type_registry,
+                                    validator.as_ref(),
+                                    &lua_ctx,
+                                    &type_path,
+                                ) {
+                                    Err(e) => error!("[COMPONENT_UPDATE] Failed to patch new component {}: {}", type_path, e),
+                                    Ok(false) => component_updated = true,
+                                    Ok(true) => {
+                                        // Insert the component using ReflectComponent
+                                        let registry_read = type_registry.read();
+                                        if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+                                            reflect_component.insert(&mut entity_mut, new_component.as_partial_reflect(), &registry_read);
+                                            debug!("[COMPONENT_UPDATE] ✓ Inserted new component {} on entity", type_path);
+                                            component_updated = true;
+                                        }
+                                    }
+                                }
+                            }
+                        } else {
+                            // PATH 2: Try entity wrapper components (like UiTargetCamera(Entity))
+                            // These don't have Default but can be constructed if we have the entity value
+                            debug!("[COMPONENT_UPDATE] Trying entity wrapper path for {}", type_path);
+                            
+                            if let LuaValue::Table(ref table) = resolved_data {
+                                // Look for 'entity' field containing the entity ID
+                                if let Ok(entity_bits) = table.get::<i64>("entity") {
+                                    let target_entity = bevy::ecs::entity::Entity::from_bits(entity_bits as u64);
+                                    debug!("[COMPONENT_UPDATE] Found entity field: {} -> {:?}", entity_bits, target_entity);
+                                    
+                                    // Get ReflectFromReflect and ReflectComponent from registry
+                                    let registry = type_registry.read();
+                                    if let Some(registration) = registry.get_with_type_path(&type_path)
+                                        .or_else(|| registry.get_with_short_type_path(&type_path))
+                                    {
+                                        if let (Some(from_reflect), Some(reflect_component)) = (
+                                            registration.data::<bevy::reflect::ReflectFromReflect>(),
+                                            registration.data::<ReflectComponent>().cloned()
+                                        ) {
+                                            // Create a DynamicTupleStruct with the entity
+                                            let mut dynamic = bevy::reflect::DynamicTupleStruct::default();
+                                            dynamic.insert_boxed(Box::new(target_entity));
+                                            
+                                            // Set the represented type for from_reflect to work
+                                            let represented_type = registration.type_info();
+                                            dynamic.set_represented_type(Some(represented_type));
+                                            
+                                            if let Some(concrete) = from_reflect.from_reflect(dynamic.as_partial_reflect()) {
+                                                drop(registry);
+                                                let registry_read = type_registry.read();
+                                                if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+                                                    reflect_component.insert(&mut entity_mut, concrete.as_partial_reflect(), &registry_read);
+                                                    debug!("[COMPONENT_UPDATE] ✓ Inserted entity wrapper component {} on entity", type_path);
+                                                    component_updated = true;
+                                                }
+                                            } else {
+                                                error!("[COMPONENT_UPDATE] from_reflect failed for {}", type_path);
+                                            }
+                                        } else {
+                                            debug!("[COMPONENT_UPDATE] Component {} has no ReflectFromReflect or ReflectComponent", type_path);
+                                        }
+                                    }
+                                } else {
+                                    debug!("[COMPONENT_UPDATE] No 'entity' field found for entity wrapper {}", type_path);
+                                }
+                            }
+                        }
+                    }
+                }
+            } else {
+                drop(registry);
+            }
+        } else {
+            drop(registry);
+        }
+    } else {
+        drop(registry);
+    }
+    
+    if component_updated {
+        // Arc will be dropped automatically
+        return;
+    }
+
+    // Non-reflected Component enums (discovered at build time) are written as variant name strings
+    if let LuaValue::String(ref variant) = resolved_data {
+        let setter = world
+            .get_resource::<crate::components::ComponentRegistry>()
+            .filter(|registry| registry.is_non_reflected_component(component_name))
+            .and_then(|registry| registry.non_reflected_setter());
+        if let Some(setter) = setter {
+            let variant = variant.to_string_lossy();
+            if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+                match setter(&mut entity_mut, component_name, &variant) {
+                    Ok(()) => debug!("[COMPONENT_UPDATE] Set enum {} = {}", component_name, variant),
+                    Err(e) => error!("[COMPONENT_UPDATE] Failed to set {}: {}", component_name, e),
+                }
+            }
+            return;
+        }
+    }
+
+    // Non-reflected serde components (e.g. decoded binary updates) are inserted by their handler
+    let serde_registry = world.resource::<crate::serde_components::SerdeComponentRegistry>().clone();
+    let serde_result = world.get_entity(entity).is_ok().then(|| {
+        let mut commands = world.commands();
+        serde_registry.try_handle(component_name, &resolved_data, &mut commands.entity(entity))
+    });
+    if let Some(Some(result)) = serde_result {
+        world.flush();
+        match result {
+            Ok(()) => debug!("[COMPONENT_UPDATE] Inserted {} via serde", component_name),
+            Err(e) => error!("[COMPONENT_UPDATE] Failed to apply {}: {}", component_name, e),
+        }
+        return;
+    }
+
+    // Fallback: It's a generic Lua component - store in LuaCustomComponents
+    // Get current tick for change tracking BEFORE borrowing world mutably
+    let current_tick = world.read_change_tick().get();
+
+    if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+        if let Some(mut lua_components) = entity_mut.get_mut::<LuaCustomComponents>() {
+            // Check if this is a NEW component (for Added filter support)
+            let is_new = !lua_components.components.contains_key(component_name);
+            lua_components.components.insert(component_name.to_string(), data.clone());
+            lua_components.changed_ticks.insert(component_name.to_string(), current_tick);
+            // Only set added_ticks on first insertion
+            if is_new {
+                lua_components.added_ticks.insert(component_name.to_string(), current_tick);
+            }
+        } else {
+            // Entity doesn't have LuaCustomComponents yet - this is definitely a new component
+            let mut lua_components = LuaCustomComponents::default();
+            lua_components.components.insert(component_name.to_string(), data.clone());
+            lua_components.changed_ticks.insert(component_name.to_string(), current_tick);
+            lua_components.added_ticks.insert(component_name.to_string(), current_tick);
+            entity_mut.insert(lua_components);
+        }
+    } else {
+        warn!("Entity {:?} not found for component update", entity);
+        // Arc will be dropped automatically
+    }
+}
+
+/// Apply several component updates for one entity at once
+/// Reflected components already present on the entity are patched through a single
+/// `get_entity_mut` call; the rest (inserts, Lua components) go through `apply_component_update`
+fn apply_batched_update(
+    world: &mut World,
+    entity: Entity,
+    components: Vec<(String, std::sync::Arc<LuaRegistryKey>)>,
+    type_registry: &AppTypeRegistry,
+    asset_registry: Option<&crate::asset_loading::AssetRegistry>,
+    spawn_queue: &crate::spawn_queue::SpawnQueue,
+) {
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        return;
+    };
+    let lua_ctx = world.resource::<LuaScriptContext>().clone();
+    let component_registry = world
+        .get_resource::<crate::component_lua_trait::LuaComponentRegistry>()
+        .cloned();
+
+    // Split into in-place updates and fallbacks before taking mutable access
+    let mut in_place = Vec::new();
+    let mut fallback = Vec::new();
+    {
+        let registry = type_registry.read();
+        for (component_name, data) in components {
+            let target = registry
+                .get_with_type_path(&component_name)
+                .or_else(|| registry.get_with_short_type_path(&component_name))
+                .and_then(|registration| {
+                    registration.data::<ReflectComponent>()?;
+                    let reflect_from_ptr = registration.data::<ReflectFromPtr>()?.clone();
+                    let component_id = world.components().get_id(registration.type_id())?;
+                    let validator = component_registry
+                        .as_ref()
+                        .and_then(|component_registry| component_registry.validator_for(registration));
+                    Some((component_id, reflect_from_ptr, validator))
+                });
+
+            match target {
+                Some((component_id, reflect_from_ptr, validator)) if entity_ref.contains_id(component_id) => {
+                    in_place.push((component_name, data, component_id, reflect_from_ptr, validator));
+                }
+                _ => fallback.push((component_name, data)),
+            }
+        }
+    }
+
+    if !in_place.is_empty() {
+        if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+            for (component_name, data, component_id, reflect_from_ptr, validator) in in_place {
+                let lua = lua_ctx.state_for_key(&data);
+                let data_value: LuaValue = match lua.registry_value(&*data) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        error!("Failed to retrieve Lua value for {}: {}", component_name, e);
+                        continue;
+                    }
+                };
+                let LuaValue::Table(table) = resolve_entity_references_in_table(&lua, &data_value, spawn_queue) else {
+                    continue;
+                };
+
+                if let Ok(mut component_ptr) = entity_mut.get_mut_by_id(component_id) {
+                    // SAFETY: component_id and reflect_from_ptr come from the same type registration
+                    let component_mut = unsafe { reflect_from_ptr.as_reflect_mut(component_ptr.as_mut()) };
+                    match update_component_validated(
+                        component_mut,
+                        &table,
+                        asset_registry,
+                        type_registry,
+                        validator.as_ref(),
+                        &lua_ctx,
+                        &component_name,
+                    ) {
+                        Err(e) => error!("Failed to update component {} from Lua: {}", component_name, e),
+                        Ok(true) => debug!("[COMPONENT_UPDATE] Updated {} via reflection (batched)", component_name),
+                        Ok(false) => {}
+                    }
+                }
+            }
+        }
+    }
+
+    for (component_name, data) in fallback {
+        apply_component_update(world, entity, &component_name, &data, type_registry, asset_registry, spawn_queue);
+    }
+}
+
+/// Update a component from a Lua table, first checking the result against the component's
+/// validator (LuaComponentRegistry::register_with_validator / set_validator)
+/// Returns Ok(false) if the validator rejected the update; the component is left untouched
+fn update_component_validated(
+    component: &mut dyn Reflect,
+    table: &LuaTable,
+    asset_registry: Option<&crate::asset_loading::AssetRegistry>,
+    type_registry: &AppTypeRegistry,
+    validator: Option<&crate::component_lua_trait::ComponentValidator>,
+    lua_ctx: &LuaScriptContext,
+    component_name: &str,
+) -> LuaResult<bool> {
+    let Some(validator) = validator else {
+        update_component_from_lua(component.as_partial_reflect_mut(), table, asset_registry, type_registry)?;
+        return Ok(true);
+    };
+
+    // Validate a patched copy so a rejected update leaves no partial writes behind
+    let mut candidate = component.reflect_clone().map_err(|e| {
+        LuaError::RuntimeError(format!("Cannot clone {} for validation: {}", component_name, e))
+    })?;
+    update_component_from_lua(candidate.as_partial_reflect_mut(), table, asset_registry, type_registry)?;
+    if let Err(message) = validator.validate(lua_ctx, candidate.as_ref()) {
+        warn!("[COMPONENT_UPDATE] Dropped update of {}: {}", component_name, message);
+        return Ok(false);
+    }
+    component.apply(candidate.as_partial_reflect());
+    Ok(true)
+}
+
 /// Update a component's fields from a Lua table using reflection
-fn update_component_from_lua(
+/// Also used by `world:set_asset` for asset structs
+pub(crate) fn update_component_from_lua(
     component: &mut dyn bevy::reflect::PartialReflect,
     table: &LuaTable,
     asset_registry: Option<&crate::asset_loading::AssetRegistry>,
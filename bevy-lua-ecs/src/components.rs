@@ -26,6 +26,12 @@ pub type NonReflectedSerializer = Arc<
     dyn Fn(&bevy::ecs::world::EntityRef, &str) -> Result<Option<String>, String> + Send + Sync,
 >;
 
+/// Type for non-reflected enum component setter callback
+/// Takes entity, component name and variant name; inserts the parsed enum value
+pub type NonReflectedSetter = Arc<
+    dyn Fn(&mut bevy::ecs::world::EntityWorldMut, &str, &str) -> Result<(), String> + Send + Sync,
+>;
+
 /// Registry of component handlers using reflection
 #[derive(Resource)]
 pub struct ComponentRegistry {
@@ -36,6 +42,8 @@ pub struct ComponentRegistry {
     non_reflected_components: HashMap<String, std::any::TypeId>,
     /// Optional serializer for non-reflected components (set by generated code)
     non_reflected_serializer: Option<NonReflectedSerializer>,
+    /// Optional setter for non-reflected enum components (set by generated code)
+    non_reflected_setter: Option<NonReflectedSetter>,
 }
 
 
@@ -48,6 +56,7 @@ impl ComponentRegistry {
             asset_registry: None,
             non_reflected_components: HashMap::new(),
             non_reflected_serializer: None,
+            non_reflected_setter: None,
         };
 
 
@@ -138,6 +147,50 @@ impl ComponentRegistry {
         self.non_reflected_components.get(short_name)
     }
 
+    /// List the short names of all components currently on an entity
+    /// Rust components come from the entity's archetype; Lua components from `LuaCustomComponents`.
+    /// Internal `bevy_ecs::` book-keeping components are filtered out.
+    pub fn list_components_on_entity(&self, world: &World, entity: Entity) -> Option<Vec<String>> {
+        let entity_ref = world.get_entity(entity).ok()?;
+        let type_registry = self.type_registry.read();
+        let lua_components_id = world.components().component_id::<LuaCustomComponents>();
+
+        let mut names = Vec::new();
+        for component_id in entity_ref.archetype().components() {
+            if Some(component_id) == lua_components_id {
+                continue;
+            }
+            let Some(info) = world.components().get_info(component_id) else {
+                continue;
+            };
+
+            let registration = info.type_id().and_then(|type_id| type_registry.get(type_id));
+            let (full_path, short_name) = match registration {
+                Some(registration) => {
+                    let table = registration.type_info().type_path_table();
+                    (table.path().to_string(), table.short_path().to_string())
+                }
+                None => {
+                    let full = format!("{}", info.name());
+                    let short = short_type_name(&full);
+                    (full, short)
+                }
+            };
+
+            if full_path.starts_with("bevy_ecs::") {
+                continue;
+            }
+            names.push(short_name);
+        }
+
+        if let Some(lua_components) = entity_ref.get::<LuaCustomComponents>() {
+            names.extend(lua_components.components.keys().cloned());
+        }
+
+        names.sort();
+        Some(names)
+    }
+
     /// Get access to the type registry
     pub fn type_registry(&self) -> &AppTypeRegistry {
         &self.type_registry
@@ -149,6 +202,17 @@ impl ComponentRegistry {
         self.non_reflected_serializer = Some(serializer);
     }
 
+    /// Set the non-reflected enum setter callback
+    /// This is called by generated code so Lua can write enum components as variant strings
+    pub fn set_non_reflected_setter(&mut self, setter: NonReflectedSetter) {
+        self.non_reflected_setter = Some(setter);
+    }
+
+    /// Get the non-reflected enum setter (cloned so it can be used while the world is borrowed mutably)
+    pub fn non_reflected_setter(&self) -> Option<NonReflectedSetter> {
+        self.non_reflected_setter.clone()
+    }
+
     /// Serialize a non-reflected component using the registered callback
     /// Returns Some(serialized_string) if component exists and serializer is set, None otherwise
     pub fn serialize_non_reflected(
@@ -1100,6 +1164,11 @@ pub fn set_field_from_lua(
     type_registry: &bevy::prelude::AppTypeRegistry,
     field_name: Option<&str>,
 ) -> LuaResult<()> {
+    // Vec2/Vec3/Vec4/Quat are set directly, before any type-path formatting
+    if crate::reflection::lua_to_math(field, lua_value) {
+        return Ok(());
+    }
+
     // Fully generic Handle<T> resolution using type-erased handle setters!
     // The AssetRegistry was populated at startup with setters for all asset types in TypeRegistry.
     let type_path = field.reflect_type_path().to_string();
@@ -1110,6 +1179,40 @@ pub fn set_field_from_lua(
     );
     if type_path.contains("Handle<") {
         debug!("[FIELD_SET] Handle detected: {}", type_path);
+
+        // Handle table from world:create_asset - { id = <asset index bits>, type = "Image" }
+        if let LuaValue::Table(handle_table) = lua_value {
+            if let (Some(registry), Ok(short_name), Ok(asset_id)) = (
+                asset_registry,
+                handle_table.get::<String>("type"),
+                handle_table.get::<i64>("id"),
+            ) {
+                if let Some(handle) = registry.get_created_handle(&short_name, asset_id as u64) {
+                    if registry.try_set_handle_field(field, &type_path, handle) {
+                        return Ok(());
+                    }
+                }
+            }
+            // Still loading (load_asset_from_bytes) - a typed load of the same path shares its handle
+            if let (Some(registry), Ok(path)) = (asset_registry, handle_table.get::<String>("path")) {
+                if let Some(asset_server) = registry.asset_server.as_ref() {
+                    if let Some(handle) = registry.try_load_from_path(&path, &type_path, asset_server) {
+                        if registry.try_set_handle_field(field, &type_path, handle) {
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+            if let Ok(asset_id) = handle_table.get::<i64>("id") {
+                return set_field_from_lua(
+                    field,
+                    &LuaValue::Integer(asset_id),
+                    asset_registry,
+                    type_registry,
+                    field_name,
+                );
+            }
+        }
         
         // NEW: Handle path string - for network replication where Handle is serialized as path
         if let LuaValue::String(path_str) = lua_value {
@@ -1256,27 +1359,6 @@ pub fn set_field_from_lua(
         } else {
             warn!("[COLOR_SET] Expected Table for Color, got: {:?}", lua_value);
         }
-    } else if let Some(vec3_field) = field.try_downcast_mut::<Vec3>() {
-        if let LuaValue::Table(vec_table) = lua_value {
-            let x: f32 = vec_table.get("x").unwrap_or(0.0);
-            let y: f32 = vec_table.get("y").unwrap_or(0.0);
-            let z: f32 = vec_table.get("z").unwrap_or(0.0);
-            *vec3_field = Vec3::new(x, y, z);
-        }
-    } else if let Some(vec2_field) = field.try_downcast_mut::<Vec2>() {
-        if let LuaValue::Table(vec_table) = lua_value {
-            let x: f32 = vec_table.get("x").unwrap_or(0.0);
-            let y: f32 = vec_table.get("y").unwrap_or(0.0);
-            *vec2_field = Vec2::new(x, y);
-        }
-    } else if let Some(quat_field) = field.try_downcast_mut::<Quat>() {
-        if let LuaValue::Table(quat_table) = lua_value {
-            let x: f32 = quat_table.get("x").unwrap_or(0.0);
-            let y: f32 = quat_table.get("y").unwrap_or(0.0);
-            let z: f32 = quat_table.get("z").unwrap_or(0.0);
-            let w: f32 = quat_table.get("w").unwrap_or(1.0);
-            *quat_field = Quat::from_xyzw(x, y, z, w);
-        }
     } else if let LuaValue::Table(nested_table) = lua_value {
         // Generic nested struct/enum handling using reflection
         if let Err(e) =
@@ -1439,6 +1521,40 @@ fn set_nested_field_from_lua(
                 }
             }
         }
+        ReflectMut::List(list) => {
+            // Vec<T> fields: the Lua array replaces the list contents. Existing items are
+            // updated in place (partial tables keep their other fields), extra ones start
+            // from T::default()
+            let Some(TypeInfo::List(list_info)) = list.get_represented_type_info() else {
+                return Ok(());
+            };
+            // Growing the list needs T::default(); fail before touching it rather than
+            // silently dropping the extra array elements
+            let item_default = type_registry
+                .read()
+                .get_type_data::<ReflectDefault>(list_info.item_ty().id())
+                .cloned();
+            if item_default.is_none() && table.raw_len() > list.len() {
+                return Err(LuaError::RuntimeError(format!(
+                    "Cannot grow list of {} from {} to {} items: {} has no ReflectDefault",
+                    list_info.type_path(),
+                    list.len(),
+                    table.raw_len(),
+                    list_info.item_ty().path()
+                )));
+            }
+            let mut existing = list.drain().into_iter();
+            for value in table.clone().sequence_values::<LuaValue>() {
+                let value = value?;
+                let Some(mut item) = existing.next().or_else(|| {
+                    item_default.as_ref().map(|default| default.default().into_partial_reflect())
+                }) else {
+                    break;
+                };
+                set_field_from_lua(item.as_mut(), &value, asset_registry, type_registry, None)?;
+                list.push(item);
+            }
+        }
         ReflectMut::Enum(_enum_mut) => {
             // Get the type info to understand the enum structure
             let type_path = field.reflect_type_path().to_string();
@@ -1889,3 +2005,21 @@ fn set_nested_field_from_lua(
 
     Ok(())
 }
+
+/// Strip module paths from a type name, keeping generic arguments readable
+/// e.g. "bevy_sprite::Sprite" -> "Sprite", "a::Wrapper<b::Inner>" -> "Wrapper<Inner>"
+fn short_type_name(full: &str) -> String {
+    let mut short = String::with_capacity(full.len());
+    let mut segment_start = 0;
+    for (i, c) in full.char_indices() {
+        if matches!(c, '<' | '>' | ',' | ' ' | '(' | ')' | '[' | ']' | ';' | '&') {
+            let segment = &full[segment_start..i];
+            short.push_str(segment.rsplit("::").next().unwrap_or(segment));
+            short.push(c);
+            segment_start = i + c.len_utf8();
+        }
+    }
+    let segment = &full[segment_start..];
+    short.push_str(segment.rsplit("::").next().unwrap_or(segment));
+    short
+}
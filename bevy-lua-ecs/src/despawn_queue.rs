@@ -1,25 +1,55 @@
 use bevy::prelude::*;
-use std::collections::HashSet;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
+/// How a queued entity should be despawned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DespawnMode {
+    /// Despawn only the entity - its children are detached and left in the world
+    Single,
+    /// Despawn the entity and all of its descendants
+    Recursive,
+}
+
 /// Queue for despawning entities from Lua
 #[derive(Resource, Clone)]
 pub struct DespawnQueue {
-    queue: Arc<Mutex<HashSet<Entity>>>,
+    queue: Arc<Mutex<HashMap<Entity, DespawnMode>>>,
 }
 
 impl Default for DespawnQueue {
     fn default() -> Self {
         Self {
-            queue: Arc::new(Mutex::new(HashSet::new())),
+            queue: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
 
 impl DespawnQueue {
-    /// Queue an entity for despawning (duplicates are automatically ignored)
+    /// Queue an entity for despawning along with its descendants (duplicates are automatically ignored)
+    /// This is Bevy's default despawn behavior and what script cleanup relies on
     pub fn queue_despawn(&self, entity: Entity) {
-        self.queue.lock().unwrap().insert(entity);
+        self.queue_with_mode(entity, DespawnMode::Recursive);
+    }
+
+    /// Queue an entity for despawning without touching its children
+    pub fn queue_single(&self, entity: Entity) {
+        self.queue_with_mode(entity, DespawnMode::Single);
+    }
+
+    /// Queue an entity and all of its descendants for despawning
+    pub fn queue_recursive(&self, entity: Entity) {
+        self.queue_with_mode(entity, DespawnMode::Recursive);
+    }
+
+    /// Queue an entity with an explicit mode
+    /// If the entity is already queued, Recursive wins over Single
+    pub fn queue_with_mode(&self, entity: Entity, mode: DespawnMode) {
+        let mut queue = self.queue.lock().unwrap();
+        let entry = queue.entry(entity).or_insert(mode);
+        if mode == DespawnMode::Recursive {
+            *entry = DespawnMode::Recursive;
+        }
     }
 }
 
@@ -31,8 +61,9 @@ pub fn process_despawn_queue(
     lua_ctx: Res<crate::lua_integration::LuaScriptContext>,
 ) {
     let mut queue = despawn_queue.queue.lock().unwrap();
-    let entities_to_despawn: Vec<Entity> = queue.drain().collect();
+    let queued: Vec<(Entity, DespawnMode)> = queue.drain().collect();
     drop(queue);
+    let entities_to_despawn: Vec<Entity> = queued.iter().map(|(entity, _)| *entity).collect();
 
     if entities_to_despawn.is_empty() {
         return;
@@ -46,7 +77,7 @@ pub fn process_despawn_queue(
     for key_arc in removed_keys {
         // Try to unwrap Arc - if this is the last reference, clean up
         if let Ok(key) = Arc::try_unwrap(key_arc) {
-            if let Err(e) = lua_ctx.lua.remove_registry_value(key) {
+            if let Err(e) = lua_ctx.state_for_key(&key).remove_registry_value(key) {
                 warn!(
                     "Failed to remove registry value for despawned entity: {}",
                     e
@@ -58,8 +89,16 @@ pub fn process_despawn_queue(
     }
 
     // Despawn the entities (Bevy 0.16+ despawn() handles children automatically)
-    for entity in entities_to_despawn {
-        commands.entity(entity).despawn();
-        debug!("Despawned entity: {:?}", entity);
+    for (entity, mode) in queued {
+        match mode {
+            DespawnMode::Single => {
+                // Detach children first so only this entity is removed
+                commands.entity(entity).detach_all_children().despawn();
+            }
+            DespawnMode::Recursive => {
+                commands.entity(entity).despawn();
+            }
+        }
+        debug!("Despawned entity: {:?} ({:?})", entity, mode);
     }
 }
@@ -0,0 +1,274 @@
+//! Saving single entities to disk as Bevy scenes and loading them back from Lua
+//!
+//! ```lua
+//! world:persist_entity(player, "player.scn.ron")     -- writes <save dir>/player.scn.ron
+//! local id = world:load_entity("player.scn.ron")      -- queues a copy, returns its temp id
+//! world:load_entity("player.scn.ron", player)          -- loads the saved copy into `player`
+//! ```
+//!
+//! Every component with `#[reflect(Component)]` is written as a `DynamicScene` in RON.
+//! Components defined with `world:define_component` are saved as one `LuaDefinedComponents`.
+//! Hierarchy and script ownership are left out: entity references would be stale when
+//! the file is loaded, and a loaded entity belongs to the script that loaded it.
+//! Loaded components go through the spawn queue like `spawn()` tables.
+
+use bevy::asset::ron;
+use bevy::prelude::*;
+use bevy::scene::serde::SceneDeserializer;
+use bevy::scene::{DynamicScene, DynamicSceneBuilder};
+use mlua::prelude::*;
+use serde::de::DeserializeSeed;
+use std::path::{Component as PathComponent, PathBuf};
+
+/// Where `persist_entity` / `load_entity` read and write files
+/// Defaults to `<OS data dir>/<executable name>/saves`; insert your own to override
+#[derive(Resource, Clone, Debug)]
+pub struct SaveConfig {
+    pub save_dir: PathBuf,
+}
+
+impl Default for SaveConfig {
+    fn default() -> Self {
+        let app_name = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| "bevy_lua_ecs".to_string());
+        let save_dir = dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(app_name)
+            .join("saves");
+        Self { save_dir }
+    }
+}
+
+impl SaveConfig {
+    /// Resolve a path relative to the save directory
+    /// Absolute paths and `..` are rejected so scripts can't write outside it
+    pub fn resolve(&self, path: &str) -> Result<PathBuf, String> {
+        let relative = PathBuf::from(path);
+        let escapes = relative.components().any(|c| {
+            matches!(
+                c,
+                PathComponent::ParentDir | PathComponent::RootDir | PathComponent::Prefix(_)
+            )
+        });
+        if path.is_empty() || escapes {
+            return Err(format!(
+                "save path '{}' must be relative to the save directory",
+                path
+            ));
+        }
+        Ok(self.save_dir.join(relative))
+    }
+}
+
+/// Extract the reflected components of `entity` as a single-entity scene, without
+/// hierarchy or script ownership
+pub fn extract_entity_scene(world: &World, entity: Entity) -> Result<DynamicScene, String> {
+    if world.get_entity(entity).is_err() {
+        return Err(format!("entity {:?} does not exist", entity));
+    }
+    Ok(DynamicSceneBuilder::from_world(world)
+        .deny_component::<ChildOf>()
+        .deny_component::<Children>()
+        .deny_component::<crate::script_entities::ScriptOwned>()
+        .extract_entity(entity)
+        .build())
+}
+
+/// Serialize the reflected and Lua-defined components of `entity` as a single-entity scene (RON)
+pub fn serialize_entity(world: &World, entity: Entity) -> Result<String, String> {
+    let mut scene = extract_entity_scene(world, entity)?;
+    if let Some(lua_components) = lua_defined_components(world, entity)? {
+        if let Some(saved) = scene.entities.first_mut() {
+            saved.components.push(Box::new(lua_components));
+        }
+    }
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    scene.serialize(&type_registry).map_err(|e| e.to_string())
+}
+
+/// The components of `entity` defined with `world:define_component`, if it has any
+fn lua_defined_components(
+    world: &World,
+    entity: Entity,
+) -> Result<Option<crate::serde_components::LuaDefinedComponents>, String> {
+    let Some(custom) = world.get::<crate::components::LuaCustomComponents>(entity) else {
+        return Ok(None);
+    };
+    let (Some(serde_registry), Some(lua_ctx)) = (
+        world.get_resource::<crate::serde_components::SerdeComponentRegistry>(),
+        world.get_resource::<crate::lua_integration::LuaScriptContext>(),
+    ) else {
+        return Ok(None);
+    };
+    let components = serde_registry
+        .serialize_lua_defined_components(lua_ctx, custom)
+        .map_err(|e| e.to_string())?;
+    if components.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(crate::serde_components::LuaDefinedComponents(components)))
+}
+
+/// Remove what `serialize_entity` would save for this entity, so a loaded scene replaces it
+/// (`load_entity` with a replace target). Hierarchy, script ownership, components without
+/// `ReflectComponent` and schemaless Lua components are kept.
+pub fn clear_persisted_components(mut entity: EntityWorldMut) {
+    let kept = [
+        std::any::TypeId::of::<ChildOf>(),
+        std::any::TypeId::of::<Children>(),
+        std::any::TypeId::of::<crate::script_entities::ScriptOwned>(),
+    ];
+    let persisted: Vec<bevy::ecs::component::ComponentId> = {
+        let world = entity.world();
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        entity
+            .archetype()
+            .components()
+            .filter(|component_id| {
+                world
+                    .components()
+                    .get_info(*component_id)
+                    .and_then(|info| info.type_id())
+                    .is_some_and(|type_id| {
+                        !kept.contains(&type_id)
+                            && type_registry.get_type_data::<ReflectComponent>(type_id).is_some()
+                    })
+            })
+            .collect()
+    };
+    for component_id in persisted {
+        entity.remove_by_id(component_id);
+    }
+
+    // Lua-defined components are saved out of LuaCustomComponents; schemaless ones stay
+    let Some(serde_registry) = entity
+        .world()
+        .get_resource::<crate::serde_components::SerdeComponentRegistry>()
+        .cloned()
+    else {
+        return;
+    };
+    let removed_keys: Vec<_> = match entity.get_mut::<crate::components::LuaCustomComponents>() {
+        Some(mut custom) => {
+            let defined: Vec<String> = custom
+                .components
+                .keys()
+                .filter(|name| serde_registry.is_lua_defined_component(name))
+                .cloned()
+                .collect();
+            defined
+                .into_iter()
+                .filter_map(|name| {
+                    custom.changed_ticks.remove(&name);
+                    custom.added_ticks.remove(&name);
+                    custom.components.remove(&name)
+                })
+                .collect()
+        }
+        None => return,
+    };
+    let Some(lua_ctx) = entity
+        .world()
+        .get_resource::<crate::lua_integration::LuaScriptContext>()
+    else {
+        return;
+    };
+    for key_arc in removed_keys {
+        if let Ok(key) = std::sync::Arc::try_unwrap(key_arc) {
+            let _ = lua_ctx.state_for_key(&key).remove_registry_value(key);
+        }
+    }
+}
+
+/// Deserialize a scene written by `serialize_entity` into `(component name, data)` pairs
+/// ready for the spawn queue
+pub fn deserialize_entity(
+    lua: &Lua,
+    world: &World,
+    source: &str,
+) -> LuaResult<Vec<(String, LuaValue)>> {
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let mut deserializer =
+        ron::de::Deserializer::from_str(source).map_err(|e| LuaError::RuntimeError(e.to_string()))?;
+    let scene = SceneDeserializer {
+        type_registry: &type_registry,
+    }
+    .deserialize(&mut deserializer)
+    .map_err(|e| LuaError::RuntimeError(format!("invalid entity scene: {}", e)))?;
+
+    let mut entities = scene.entities.into_iter();
+    let entity = entities
+        .next()
+        .ok_or_else(|| LuaError::RuntimeError("entity scene contains no entities".to_string()))?;
+    if entities.next().is_some() {
+        warn!("[PERSIST] Scene contains more than one entity, only the first is loaded");
+    }
+
+    let mut components = Vec::with_capacity(entity.components.len());
+    for component in &entity.components {
+        let Some(type_info) = component.get_represented_type_info() else {
+            continue;
+        };
+        // Lua-defined components come back as individual components, as they were spawned
+        if type_info.type_id() == std::any::TypeId::of::<crate::serde_components::LuaDefinedComponents>() {
+            let lua_components =
+                crate::serde_components::LuaDefinedComponents::from_reflect(component.as_ref())
+                    .ok_or_else(|| LuaError::RuntimeError("invalid LuaDefinedComponents data".to_string()))?;
+            for (name, data) in lua_components.0 {
+                components.push((name, data.to_lua_value(lua)?));
+            }
+            continue;
+        }
+        let name = type_info.type_path_table().short_path().to_string();
+        components.push((name, crate::lua_world_api::reflection_to_lua(lua, component.as_ref())?));
+    }
+    Ok(components)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_rejects_paths_outside_save_dir() {
+        let config = SaveConfig {
+            save_dir: PathBuf::from("saves"),
+        };
+        assert_eq!(
+            config.resolve("slot1/player.scn.ron").unwrap(),
+            PathBuf::from("saves/slot1/player.scn.ron")
+        );
+        assert!(config.resolve("../player.scn.ron").is_err());
+        assert!(config.resolve("/tmp/player.scn.ron").is_err());
+        assert!(config.resolve("").is_err());
+    }
+
+    #[test]
+    fn test_entity_round_trip() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Transform>();
+            registry.register::<Vec3>();
+            registry.register::<Quat>();
+        }
+        let entity = world.spawn(Transform::from_xyz(1.0, 2.0, 3.0)).id();
+
+        let source = serialize_entity(&world, entity).unwrap();
+        let lua = Lua::new();
+        let components = deserialize_entity(&lua, &world, &source).unwrap();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].0, "Transform");
+
+        let LuaValue::Table(transform) = &components[0].1 else {
+            panic!("Transform should be a table");
+        };
+        let translation: LuaTable = transform.get("translation").unwrap();
+        assert_eq!(translation.get::<f32>("y").unwrap(), 2.0);
+
+        assert!(serialize_entity(&world, Entity::PLACEHOLDER).is_err());
+    }
+}
@@ -16,26 +16,66 @@ pub fn process_spawn_queue(
     serde_registry: Res<crate::serde_components::SerdeComponentRegistry>,
     lua_ctx: Res<LuaScriptContext>,
     query: Query<Entity>,
+    entities: &bevy::ecs::entity::Entities,
     system_tick: SystemChangeTick,
+    script_registry: Res<crate::script_registry::ScriptRegistry>,
+    bundle_registry: Option<Res<crate::reflection::BundleRegistry>>,
+    post_spawn: Option<Res<crate::lua_spawn_builder::PostSpawnCallbackQueue>>,
 ) {
-    let requests = queue.drain();
+    let entries = queue.drain();
+    let ui_nodes = queue.drain_ui_nodes();
 
-    if requests.is_empty() {
+    if entries.is_empty() {
+        spawn_ui_nodes(&mut commands, &queue, &script_registry, ui_nodes);
         return;
     }
 
     let entity_count_before = query.iter().count();
     debug!(
         "[SPAWN_QUEUE] Processing {} spawn requests (current world has {} entities)",
-        requests.len(),
+        entries.len(),
         entity_count_before
     );
 
     let mut spawned_count = 0;
 
-    for request in requests {
+    // Single spawns and batches go in queue order so a temp_id from either resolves for the other
+    for entry in entries {
+        let request = match entry {
+            crate::spawn_queue::SpawnQueueEntry::Single(request) => request,
+            crate::spawn_queue::SpawnQueueEntry::Batch(batch) => {
+                spawned_count += batch.count as usize;
+                spawn_batch(
+                    &mut commands,
+                    entities,
+                    &queue,
+                    &component_registry,
+                    &serde_registry,
+                    &lua_ctx,
+                    &script_registry,
+                    batch,
+                    system_tick.this_run().get(),
+                );
+                continue;
+            }
+        };
+
+        // load_entity's replace loads into the existing entity so its id (and references to it) stay valid
+        let replacing = request.replace.filter(|target| entities.contains(*target));
+        if let Some(target) = request.replace.filter(|_| replacing.is_none()) {
+            warn!("[SPAWN_QUEUE] Entity {:?} to replace no longer exists, spawning a new one", target);
+        }
+
         // Spawn entity
-        let entity_id = commands.spawn_empty().id();
+        let entity_id = match replacing {
+            Some(target) => {
+                commands
+                    .entity(target)
+                    .queue(crate::entity_persistence::clear_persisted_components);
+                target
+            }
+            None => commands.spawn_empty().id(),
+        };
         debug!(
             "[SPAWN_QUEUE] Spawning entity {:?} with {} components",
             entity_id,
@@ -65,19 +105,30 @@ pub fn process_spawn_queue(
             "[SPAWN_QUEUE] Registered temp_id {} -> {:?}",
             request.temp_id, entity_id
         );
+        if let Some(post_spawn) = &post_spawn {
+            post_spawn.entity_spawned(request.temp_id, entity_id);
+        }
 
         // Sort components to ensure consistent ordering for Required Components
         // This ensures "Camera" is processed before "Camera2d", so when Camera2d is
         // inserted and its Required Components are checked, our Camera already exists
         // and won't be replaced by Bevy's default Camera.
         let mut components = request.components;
+        if let Some(index) = components.iter().position(|(name, _)| name == "bundle") {
+            let (_, bundle_key) = components.remove(index);
+            if let Err(e) = expand_bundle(&lua_ctx, bundle_registry.as_deref(), bundle_key, &mut components) {
+                error!("[SPAWN_QUEUE] Failed to expand bundle for temp_id {}: {}", request.temp_id, e);
+            }
+        }
         components.sort_by(|a, b| a.0.cmp(&b.0));
 
         // Apply each component
         for (component_name, registry_key) in components {
             debug!("[SPAWN_QUEUE] Processing component: {}", component_name);
             // Retrieve the Lua value from the registry (can be string, table, number, etc.)
-            let data_value: LuaValue = match lua_ctx.lua.registry_value(&registry_key) {
+            // The key belongs to the state that queued the spawn (a sandbox state or the primary)
+            let lua = lua_ctx.state_for_key(&registry_key);
+            let data_value: LuaValue = match lua.registry_value(&registry_key) {
                 Ok(value) => value,
                 Err(e) => {
                     error!("Failed to retrieve Lua value for {}: {}", component_name, e);
@@ -104,26 +155,7 @@ pub fn process_spawn_queue(
 
                 // Pre-resolve entity references: If data has an 'entity' field,
                 // resolve it using the unified method that handles both temp_ids and real entity bits
-                let resolved_data = if let LuaValue::Table(ref table) = data_value {
-                    if let Ok(entity_id) = table.get::<u64>("entity") {
-                        // Use resolve_entity which handles both temp_ids and real entity bits
-                        let resolved_entity = queue.resolve_entity(entity_id);
-                        // Create a new table with resolved entity bits
-                        let new_table = lua_ctx.lua.create_table().unwrap();
-                        new_table.set("entity", resolved_entity.to_bits()).unwrap();
-                        debug!(
-                            "[SPAWN_QUEUE] Resolved entity reference: {} -> {:?} (bits: {})",
-                            entity_id,
-                            resolved_entity,
-                            resolved_entity.to_bits()
-                        );
-                        LuaValue::Table(new_table)
-                    } else {
-                        data_value.clone()
-                    }
-                } else {
-                    data_value.clone()
-                };
+                let resolved_data = resolve_entity_reference(&lua, &queue, &data_value);
 
                 // Apply component via Reflect
                 if let Err(e) = handler(&resolved_data, &mut entity) {
@@ -153,7 +185,7 @@ pub fn process_spawn_queue(
             }
 
             // Remove the registry value to free memory (only if NOT stored in custom components)
-            if let Err(e) = lua_ctx.lua.remove_registry_value(registry_key) {
+            if let Err(e) = lua.remove_registry_value(registry_key) {
                 warn!(
                     "Failed to remove registry value for {}: {}",
                     component_name, e
@@ -162,15 +194,33 @@ pub fn process_spawn_queue(
         }
 
         // Add generic Lua components if any
+        // A replaced entity merges them so its schemaless Lua components survive the load
         if !lua_custom_components.components.is_empty() {
-            entity.insert(lua_custom_components);
+            if replacing.is_some() {
+                entity.queue(move |mut target: EntityWorldMut| {
+                    match target.get_mut::<crate::components::LuaCustomComponents>() {
+                        Some(mut existing) => {
+                            existing.components.extend(lua_custom_components.components);
+                            existing.changed_ticks.extend(lua_custom_components.changed_ticks);
+                            existing.added_ticks.extend(lua_custom_components.added_ticks);
+                        }
+                        None => {
+                            target.insert(lua_custom_components);
+                        }
+                    }
+                });
+            } else {
+                entity.insert(lua_custom_components);
+            }
         }
 
         // Tag entity with script ownership using the captured instance_id and spawn_phase
-        if let Some(instance_id) = request.instance_id {
+        // A replaced entity keeps the owner it already had
+        if let Some(instance_id) = request.instance_id.filter(|_| replacing.is_none()) {
             entity.insert(crate::script_entities::ScriptOwned { 
                 instance_id,
                 spawn_phase: request.spawn_phase.clone(),
+                script_version: script_registry.get_script_version(instance_id).unwrap_or(0),
             });
         }
 
@@ -185,8 +235,204 @@ pub fn process_spawn_queue(
             commands.entity(parent_entity).add_child(entity_id);
         }
 
-        spawned_count += 1;
+        if replacing.is_none() {
+            spawned_count += 1;
+        }
     }
     let entity_count = entity_count_before + spawned_count;
     debug!("[SPAWN_QUEUE] Total entities in the game: {}", entity_count);
+
+    // UI nodes go last so children spawned earlier this frame already have temp_id mappings
+    spawn_ui_nodes(&mut commands, &queue, &script_registry, ui_nodes);
+}
+
+/// Spawn UI nodes queued by `world:spawn_ui_node` and attach their children
+fn spawn_ui_nodes(
+    commands: &mut Commands,
+    queue: &SpawnQueue,
+    script_registry: &crate::script_registry::ScriptRegistry,
+    ui_nodes: Vec<crate::spawn_queue::UiNodeSpawnRequest>,
+) {
+    for request in ui_nodes {
+        let props = request.props;
+        let mut entity = commands.spawn(props.node);
+        if let Some(background_color) = props.background_color {
+            entity.insert(background_color);
+        }
+        if let Some(instance_id) = request.instance_id {
+            entity.insert(crate::script_entities::ScriptOwned {
+                instance_id,
+                spawn_phase: request.spawn_phase,
+                script_version: script_registry.get_script_version(instance_id).unwrap_or(0),
+            });
+        }
+        let entity_id = entity.id();
+        queue.register_entity(request.temp_id, entity_id);
+
+        if !props.children.is_empty() {
+            let children: Vec<Entity> = props
+                .children
+                .iter()
+                .map(|child_id| queue.resolve_entity(*child_id))
+                .collect();
+            commands.entity(entity_id).add_children(&children);
+        }
+        debug!(
+            "[SPAWN_QUEUE] Spawned UI node {:?} (temp_id {}) with {} children",
+            entity_id,
+            request.temp_id,
+            props.children.len()
+        );
+    }
+}
+
+/// Replace `components` with the components of the reflected bundle named by `bundle_key`,
+/// using the existing entries as overrides. On error `components` is left untouched.
+fn expand_bundle(
+    lua_ctx: &LuaScriptContext,
+    bundle_registry: Option<&crate::reflection::BundleRegistry>,
+    bundle_key: LuaRegistryKey,
+    components: &mut Vec<(String, LuaRegistryKey)>,
+) -> LuaResult<()> {
+    let lua = lua_ctx.state_for_key(&bundle_key);
+    let bundle_name: String = lua.registry_value(&bundle_key)?;
+    let _ = lua.remove_registry_value(bundle_key);
+    let bundle_registry = bundle_registry
+        .ok_or_else(|| LuaError::RuntimeError("BundleRegistry resource not found".to_string()))?;
+
+    let overrides = components
+        .iter()
+        .map(|(name, key)| Ok((name.clone(), lua.registry_value::<LuaValue>(key)?)))
+        .collect::<LuaResult<Vec<_>>>()?;
+    let expanded = bundle_registry.instantiate(&lua, &bundle_name, overrides)?;
+
+    let mut keys = Vec::with_capacity(expanded.len());
+    for (component_name, value) in expanded {
+        keys.push((component_name, lua.create_registry_value(value)?));
+    }
+    for (_, key) in std::mem::replace(components, keys) {
+        let _ = lua.remove_registry_value(key);
+    }
+    debug!("[SPAWN_QUEUE] Expanded bundle '{}' into {} components", bundle_name, components.len());
+    Ok(())
+}
+
+/// Resolve an `entity` field (temp_id or real entity bits) in component data to real entity bits
+fn resolve_entity_reference(lua: &Lua, queue: &SpawnQueue, data_value: &LuaValue) -> LuaValue {
+    if let LuaValue::Table(table) = data_value {
+        if let Ok(entity_id) = table.get::<u64>("entity") {
+            // Use resolve_entity which handles both temp_ids and real entity bits
+            let resolved_entity = queue.resolve_entity(entity_id);
+            // Create a new table with resolved entity bits
+            let new_table = lua.create_table().unwrap();
+            new_table.set("entity", resolved_entity.to_bits()).unwrap();
+            debug!(
+                "[SPAWN_QUEUE] Resolved entity reference: {} -> {:?} (bits: {})",
+                entity_id,
+                resolved_entity,
+                resolved_entity.to_bits()
+            );
+            return LuaValue::Table(new_table);
+        }
+    }
+    data_value.clone()
+}
+
+/// Spawn one batch entry queued by `spawn_batch(count, template)`
+/// Template values are read from the Lua registry once per batch instead of once per entity,
+/// the batch's entities are reserved together and the shared ScriptOwned tag goes through
+/// `Commands::insert_batch`
+fn spawn_batch(
+    commands: &mut Commands,
+    entities: &bevy::ecs::entity::Entities,
+    queue: &SpawnQueue,
+    component_registry: &ComponentRegistry,
+    serde_registry: &crate::serde_components::SerdeComponentRegistry,
+    lua_ctx: &LuaScriptContext,
+    script_registry: &crate::script_registry::ScriptRegistry,
+    batch: crate::spawn_queue::BatchSpawnEntry,
+    current_tick: u32,
+) {
+    debug!(
+        "[SPAWN_QUEUE] Spawning batch of {} entities with {} components",
+        batch.count,
+        batch.template.len()
+    );
+
+    // Resolve the template once, keeping the same ordering rules as single spawns
+    // All template keys come from the state that called spawn_batch
+    let lua = match batch.template.first() {
+        Some((_, registry_key)) => lua_ctx.state_for_key(registry_key),
+        None => lua_ctx.lua.clone(),
+    };
+    let mut template: Vec<(String, LuaValue)> = Vec::with_capacity(batch.template.len());
+    for (component_name, registry_key) in &batch.template {
+        match lua.registry_value::<LuaValue>(registry_key) {
+            Ok(value) => {
+                let value = resolve_entity_reference(&lua, queue, &value);
+                template.push((component_name.clone(), value));
+            }
+            Err(e) => error!("Failed to retrieve Lua value for {}: {}", component_name, e),
+        }
+    }
+    template.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let entity_ids: Vec<Entity> = entities.reserve_entities(batch.count).collect();
+    for (i, entity_id) in entity_ids.iter().enumerate() {
+        queue.register_entity(batch.first_temp_id + i as u64, *entity_id);
+
+        let mut entity = commands.entity(*entity_id);
+        let mut lua_custom_components = crate::components::LuaCustomComponents::default();
+
+        for (component_name, data_value) in &template {
+            if let Some(result) = serde_registry.try_handle(component_name, data_value, &mut entity) {
+                if let Err(e) = result {
+                    error!("Failed to add serde component {}: {}", component_name, e);
+                }
+            } else if let Some(handler) = component_registry.get(component_name) {
+                if let Err(e) = handler(data_value, &mut entity) {
+                    error!("Failed to add component {}: {}", component_name, e);
+                }
+            } else {
+                // Each entity gets its own copy so runtime mutations stay per-entity
+                let copy = crate::prefab_registry::deep_copy_lua_value(&lua, data_value)
+                    .and_then(|v| lua.create_registry_value(v));
+                match copy {
+                    Ok(key) => {
+                        lua_custom_components
+                            .components
+                            .insert(component_name.clone(), std::sync::Arc::new(key));
+                        lua_custom_components.changed_ticks.insert(component_name.clone(), current_tick);
+                        lua_custom_components.added_ticks.insert(component_name.clone(), current_tick);
+                    }
+                    Err(e) => error!("Failed to copy Lua component {}: {}", component_name, e),
+                }
+            }
+        }
+
+        if !lua_custom_components.components.is_empty() {
+            entity.insert(lua_custom_components);
+        }
+    }
+
+    if let Some(instance_id) = batch.instance_id {
+        let script_owned = crate::script_entities::ScriptOwned {
+            instance_id,
+            spawn_phase: batch.spawn_phase.clone(),
+            script_version: script_registry.get_script_version(instance_id).unwrap_or(0),
+        };
+        commands.insert_batch(
+            entity_ids
+                .into_iter()
+                .map(move |entity_id| (entity_id, script_owned.clone()))
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    // Free the template registry values
+    for (component_name, registry_key) in batch.template {
+        if let Err(e) = lua.remove_registry_value(registry_key) {
+            warn!("Failed to remove registry value for {}: {}", component_name, e);
+        }
+    }
 }
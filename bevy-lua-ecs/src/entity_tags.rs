@@ -0,0 +1,166 @@
+//! String tags on entities, set from Lua
+//!
+//! ```lua
+//! world:tag(goblin, "enemy")
+//! if world:has_tag(goblin, "enemy") then ... end
+//! for _, id in ipairs(world:query_by_tag("enemy")) do ... end
+//! world:untag(goblin, "enemy")
+//! world:clear_tags(goblin)
+//! ```
+//!
+//! Tags live in a reflected `Tags` component, so Rust systems can query them and they are
+//! saved with `persist_entity`. Changes are queued and applied after the spawn queue (like
+//! `set_parent`) instead of going through `ComponentUpdateQueue`, whose table updates would
+//! replace the whole set and lose tags added by another script in the same frame.
+//! Reads merge the queued changes, so `has_tag` sees a `tag` made earlier in the same frame.
+
+use crate::spawn_queue::SpawnQueue;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+/// Set of string tags on an entity
+#[derive(Component, Reflect, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[reflect(Component, Default, Serialize, Deserialize)]
+pub struct Tags(pub HashSet<String>);
+
+impl Tags {
+    pub fn contains(&self, tag: &str) -> bool {
+        self.0.contains(tag)
+    }
+}
+
+/// A queued change to an entity's tags
+#[derive(Clone, Debug, PartialEq)]
+pub enum TagOp {
+    Add(String),
+    Remove(String),
+    Clear,
+}
+
+impl TagOp {
+    fn apply(&self, tags: &mut HashSet<String>) {
+        match self {
+            TagOp::Add(tag) => {
+                tags.insert(tag.clone());
+            }
+            TagOp::Remove(tag) => {
+                tags.remove(tag);
+            }
+            TagOp::Clear => tags.clear(),
+        }
+    }
+}
+
+/// Queue of tag changes from Lua, keyed by raw id (temp id or entity bits)
+#[derive(Resource, Clone, Default)]
+pub struct TagQueue {
+    queue: Arc<Mutex<Vec<(u64, TagOp)>>>,
+}
+
+impl TagQueue {
+    pub fn queue(&self, entity_id: u64, op: TagOp) {
+        self.queue.lock().unwrap().push((entity_id, op));
+    }
+
+    fn drain(&self) -> Vec<(u64, TagOp)> {
+        self.queue.lock().unwrap().drain(..).collect()
+    }
+
+    /// Tags of `entity` with queued changes applied
+    pub fn effective_tags(&self, world: &World, spawn_queue: &SpawnQueue, entity: Entity) -> HashSet<String> {
+        let mut tags = world
+            .get::<Tags>(entity)
+            .map(|tags| tags.0.clone())
+            .unwrap_or_default();
+        for (id, op) in self.queue.lock().unwrap().iter() {
+            if spawn_queue.resolve_entity(*id) == entity {
+                op.apply(&mut tags);
+            }
+        }
+        tags
+    }
+
+    /// Entities carrying `tag`, including queued changes
+    pub fn entities_with_tag(&self, world: &World, spawn_queue: &SpawnQueue, tag: &str) -> Vec<Entity> {
+        let mut candidates: Vec<Entity> = world
+            .try_query::<(Entity, &Tags)>()
+            .map(|mut query| query.iter(world).map(|(entity, _)| entity).collect())
+            .unwrap_or_default();
+        for (id, _) in self.queue.lock().unwrap().iter() {
+            let entity = spawn_queue.resolve_entity(*id);
+            if !candidates.contains(&entity) {
+                candidates.push(entity);
+            }
+        }
+        candidates
+            .into_iter()
+            .filter(|entity| self.effective_tags(world, spawn_queue, *entity).contains(tag))
+            .collect()
+    }
+}
+
+/// System that applies queued tag changes, inserting `Tags` where it is missing
+pub fn process_tag_queue(
+    mut commands: Commands,
+    tag_queue: Res<TagQueue>,
+    spawn_queue: Res<SpawnQueue>,
+    mut tagged: Query<&mut Tags>,
+    entities: Query<()>,
+) {
+    // Entities without Tags get a single insert with all of their changes applied
+    let mut inserts: HashMap<Entity, HashSet<String>> = HashMap::new();
+
+    for (entity_id, op) in tag_queue.drain() {
+        let entity = spawn_queue.resolve_entity(entity_id);
+        if !entities.contains(entity) {
+            warn!("[TAGS] Ignoring {:?} for missing entity {}", op, entity_id);
+            continue;
+        }
+        match tagged.get_mut(entity) {
+            Ok(mut tags) => op.apply(&mut tags.0),
+            Err(_) => op.apply(inserts.entry(entity).or_default()),
+        }
+    }
+
+    for (entity, tags) in inserts {
+        if !tags.is_empty() {
+            commands.entity(entity).insert(Tags(tags));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn test_tag_queue_merges_pending_changes() {
+        let mut world = World::new();
+        world.init_resource::<TagQueue>();
+        world.init_resource::<SpawnQueue>();
+        let goblin = world.spawn_empty().id();
+        let chest = world.spawn(Tags(HashSet::from(["collectible".to_string()]))).id();
+
+        let queue = world.resource::<TagQueue>().clone();
+        let spawn_queue = world.resource::<SpawnQueue>().clone();
+        queue.queue(goblin.to_bits(), TagOp::Add("enemy".to_string()));
+        queue.queue(goblin.to_bits(), TagOp::Add("boss".to_string()));
+        queue.queue(goblin.to_bits(), TagOp::Remove("boss".to_string()));
+        queue.queue(chest.to_bits(), TagOp::Clear);
+
+        // Reads see queued changes before they are applied
+        assert!(queue.effective_tags(&world, &spawn_queue, goblin).contains("enemy"));
+        assert_eq!(queue.entities_with_tag(&world, &spawn_queue, "enemy"), vec![goblin]);
+        assert!(queue.entities_with_tag(&world, &spawn_queue, "collectible").is_empty());
+
+        world.run_system_once(process_tag_queue).unwrap();
+        assert_eq!(
+            world.get::<Tags>(goblin),
+            Some(&Tags(HashSet::from(["enemy".to_string()])))
+        );
+        assert!(world.get::<Tags>(chest).unwrap().0.is_empty());
+    }
+}
@@ -13,6 +13,9 @@ pub fn reflection_to_lua(
 
     match value.reflect_ref() {
         ReflectRef::Struct(s) => {
+            if let Some(math) = crate::reflection::math_to_lua(lua, value)? {
+                return Ok(math);
+            }
             let table = lua.create_table()?;
             for i in 0..s.field_len() {
                 let field_name = s.name_at(i).unwrap();
@@ -61,13 +64,29 @@ pub fn reflection_to_lua(
         ReflectRef::Map(m) => {
             let table = lua.create_table()?;
             for (key, value) in m.iter() {
-                let lua_key = reflection_to_lua(lua, key, registry)?;
+                // Keep keys addressable from Lua: primitives map to string/integer keys,
+                // anything else (structs, enums) falls back to its debug representation
+                let lua_key = match reflection_to_lua(lua, key, registry)? {
+                    LuaValue::Table(_) => LuaValue::String(lua.create_string(format!("{:?}", key))?),
+                    other => other,
+                };
                 let lua_value = reflection_to_lua(lua, value, registry)?;
                 table.set(lua_key, lua_value)?;
             }
             Ok(LuaValue::Table(table))
         }
         ReflectRef::Enum(e) => {
+            // Option<T> reads as nil / the inner value rather than a variant table
+            if value
+                .get_represented_type_info()
+                .is_some_and(|info| is_option_type_path(info.type_path()))
+            {
+                return match (e.variant_name(), e.field_at(0)) {
+                    ("Some", Some(inner)) => reflection_to_lua(lua, inner, registry),
+                    _ => Ok(LuaValue::Nil),
+                };
+            }
+
             // For enums, create a table with the variant name as key
             let table = lua.create_table()?;
             let variant_name = e.variant_name();
@@ -93,7 +112,8 @@ pub fn reflection_to_lua(
                     table.set(variant_name, variant_table)?;
                 }
                 bevy::reflect::VariantType::Unit => {
-                    table.set(variant_name, true)?;
+                    // Unit variants read as their name (`"Visible"`), matching what setters accept
+                    return Ok(LuaValue::String(lua.create_string(variant_name)?));
                 }
             }
 
@@ -349,7 +369,7 @@ pub fn lua_table_to_dynamic_with_assets(
             if let Ok(lua_val) = table.get::<LuaValue>(field_name) {
                 // If value is nil, check if it's an Option field
                 if matches!(lua_val, LuaValue::Nil) {
-                    if field_type_path.starts_with("core::option::Option") || field_type_path.starts_with("Option") {
+                    if is_option_type_path(field_type_path) {
                          bevy::log::debug!("[LUA_TO_DYNAMIC] Field '{}': Missing/Nil for Option type, inserting None", field_name);
                          let none_variant = bevy::reflect::DynamicEnum::new("None", bevy::reflect::DynamicVariant::Unit);
                          dynamic.insert_boxed(field_name, Box::new(none_variant));
@@ -386,7 +406,7 @@ pub fn lua_table_to_dynamic_with_assets(
                 dynamic.insert_boxed(field_name, field_value);
             } else {
                 // Check if missing field is Option type
-                if field_type_path.starts_with("core::option::Option") || field_type_path.starts_with("Option") {
+                if is_option_type_path(field_type_path) {
                      bevy::log::debug!("[LUA_TO_DYNAMIC] Field '{}': Missing for Option type, inserting None", field_name);
                      let none_variant = bevy::reflect::DynamicEnum::new("None", bevy::reflect::DynamicVariant::Unit);
                      dynamic.insert_boxed(field_name, Box::new(none_variant));
@@ -403,6 +423,185 @@ pub fn lua_table_to_dynamic_with_assets(
     Ok(dynamic)
 }
 
+/// Build a DynamicTupleStruct from a Lua table for tuple structs like `Velocity(Vec3)`
+///
+/// Positional fields are read from integer keys (`{ 1.5 }` / `{ [1] = 1.5 }`), falling back
+/// to the `_0`, `_1`, ... keys used by `world:set_resource`.
+pub fn lua_table_to_dynamic_tuple_struct(
+    lua: &Lua,
+    table: &LuaTable,
+    type_info: &bevy::reflect::TypeInfo,
+    registry: &AppTypeRegistry,
+    asset_registry: Option<&crate::asset_loading::AssetRegistry>,
+) -> LuaResult<bevy::reflect::DynamicTupleStruct> {
+    use bevy::reflect::{DynamicTupleStruct, TypeInfo};
+
+    let TypeInfo::TupleStruct(tuple_info) = type_info else {
+        return Err(LuaError::RuntimeError(format!(
+            "Expected a tuple struct type, got {}",
+            type_info.type_path()
+        )));
+    };
+
+    let mut dynamic = DynamicTupleStruct::default();
+    {
+        let reg = registry.read();
+        if let Some(registration) = reg.get(type_info.ty().id()) {
+            dynamic.set_represented_type(Some(registration.type_info()));
+        }
+    }
+
+    for i in 0..tuple_info.field_len() {
+        let field = tuple_info.field_at(i).unwrap();
+        let lua_val = match table.get::<LuaValue>(i + 1)? {
+            LuaValue::Nil => table.get::<LuaValue>(format!("_{}", i))?,
+            value => value,
+        };
+
+        // Positional fields can't be skipped - stop at the first missing one so FromReflect
+        // reports the incomplete value instead of shifting later fields
+        if matches!(lua_val, LuaValue::Nil) {
+            bevy::log::debug!(
+                "[LUA_TO_DYNAMIC] Tuple field {} of {} not provided",
+                i,
+                tuple_info.type_path()
+            );
+            break;
+        }
+
+        let field_type_info = field.type_info().or_else(|| {
+            let reg = registry.read();
+            reg.get(field.type_id()).map(|r| r.type_info())
+        });
+        let field_value = lua_value_to_box_reflect_with_assets(
+            lua,
+            &lua_val,
+            field_type_info,
+            registry,
+            asset_registry,
+        )?;
+        dynamic.insert_boxed(field_value);
+    }
+
+    Ok(dynamic)
+}
+
+/// Build a DynamicMap from a Lua table for `HashMap<K, V>`-style fields
+///
+/// Each key/value pair of the table becomes a map entry. Keys and values are converted
+/// using the map's key/value type info, so `{ fire = 0.5 }` populates a `HashMap<String, f32>`
+/// and `{ [3] = "three" }` populates a `HashMap<u32, String>`.
+pub fn lua_table_to_dynamic_map(
+    lua: &Lua,
+    table: &LuaTable,
+    type_info: &bevy::reflect::TypeInfo,
+    registry: &AppTypeRegistry,
+    asset_registry: Option<&crate::asset_loading::AssetRegistry>,
+) -> LuaResult<bevy::reflect::DynamicMap> {
+    use bevy::reflect::{DynamicMap, Map, TypeInfo};
+
+    let TypeInfo::Map(map_info) = type_info else {
+        return Err(LuaError::RuntimeError(format!(
+            "Expected a map type, got {}",
+            type_info.type_path()
+        )));
+    };
+
+    let mut dynamic = DynamicMap::default();
+
+    // Get type info from the registry for 'static lifetime, falling back to the MapInfo
+    let (key_info, value_info) = {
+        let reg = registry.read();
+        if let Some(registration) = reg.get(type_info.ty().id()) {
+            dynamic.set_represented_type(Some(registration.type_info()));
+        }
+        (
+            map_info
+                .key_info()
+                .or_else(|| reg.get(map_info.key_ty().id()).map(|r| r.type_info())),
+            map_info
+                .value_info()
+                .or_else(|| reg.get(map_info.value_ty().id()).map(|r| r.type_info())),
+        )
+    };
+
+    let key_is_string = map_info.key_ty().is::<String>();
+
+    for pair in table.clone().pairs::<LuaValue, LuaValue>() {
+        let (key, value) = pair?;
+
+        // Lua keys are either strings or numbers; coerce to the map's key kind
+        let key = match key {
+            LuaValue::String(s) if !key_is_string => match s.to_str()?.parse::<i64>() {
+                Ok(i) => LuaValue::Integer(i),
+                Err(_) => LuaValue::String(s),
+            },
+            LuaValue::Integer(i) if key_is_string => LuaValue::String(lua.create_string(i.to_string())?),
+            other => other,
+        };
+
+        let boxed_key = lua_value_to_box_reflect_with_assets(lua, &key, key_info, registry, asset_registry)?;
+        let boxed_value = lua_value_to_box_reflect_with_assets(lua, &value, value_info, registry, asset_registry)?;
+        dynamic.insert_boxed(boxed_key, boxed_value);
+    }
+
+    bevy::log::debug!(
+        "[LUA_TO_DYNAMIC] DynamicMap built with {} entries for {}",
+        dynamic.len(),
+        map_info.type_path()
+    );
+
+    Ok(dynamic)
+}
+
+/// Build a DynamicList from a Lua array for `Vec<T>`-style fields
+///
+/// Array elements (`t[1]`, `t[2]`, ...) are converted in order using the list's item type
+/// info, so `{ { x = 1, y = 0, z = 0 }, { x = 2, y = 0, z = 0 } }` populates a `Vec<Vec3>`.
+pub fn lua_table_to_dynamic_list(
+    lua: &Lua,
+    table: &LuaTable,
+    type_info: &bevy::reflect::TypeInfo,
+    registry: &AppTypeRegistry,
+    asset_registry: Option<&crate::asset_loading::AssetRegistry>,
+) -> LuaResult<bevy::reflect::DynamicList> {
+    use bevy::reflect::{DynamicList, List, TypeInfo};
+
+    let TypeInfo::List(list_info) = type_info else {
+        return Err(LuaError::RuntimeError(format!(
+            "Expected a list type, got {}",
+            type_info.type_path()
+        )));
+    };
+
+    let mut dynamic = DynamicList::default();
+
+    // Get type info from the registry for 'static lifetime, falling back to the ListInfo
+    let item_info = {
+        let reg = registry.read();
+        if let Some(registration) = reg.get(type_info.ty().id()) {
+            dynamic.set_represented_type(Some(registration.type_info()));
+        }
+        list_info
+            .item_info()
+            .or_else(|| reg.get(list_info.item_ty().id()).map(|r| r.type_info()))
+    };
+
+    for value in table.clone().sequence_values::<LuaValue>() {
+        let value = value?;
+        let boxed = lua_value_to_box_reflect_with_assets(lua, &value, item_info, registry, asset_registry)?;
+        dynamic.push_box(boxed);
+    }
+
+    bevy::log::debug!(
+        "[LUA_TO_DYNAMIC] DynamicList built with {} items for {}",
+        dynamic.len(),
+        list_info.type_path()
+    );
+
+    Ok(dynamic)
+}
+
 /// Convert a Lua value to a boxed Reflect value based on type info
 fn lua_value_to_box_reflect(
     lua: &Lua,
@@ -413,6 +612,63 @@ fn lua_value_to_box_reflect(
     lua_value_to_box_reflect_with_assets(lua, lua_value, type_info, registry, None)
 }
 
+/// Whether a reflected type path names `Option<T>`
+pub(crate) fn is_option_type_path(type_path: &str) -> bool {
+    type_path.starts_with("core::option::Option<") || type_path.starts_with("Option<")
+}
+
+/// Build an `Option<T>` value from Lua: `nil` becomes `None`, anything else `Some(v)`
+///
+/// Explicit variant tables (`{ Some = v }` / `{ None = true }`) return `None` so the
+/// regular enum path can handle them.
+fn lua_value_to_dynamic_option(
+    lua: &Lua,
+    lua_value: &LuaValue,
+    enum_info: &bevy::reflect::EnumInfo,
+    registry: &AppTypeRegistry,
+    asset_registry: Option<&crate::asset_loading::AssetRegistry>,
+) -> LuaResult<Option<bevy::reflect::DynamicEnum>> {
+    use bevy::reflect::{DynamicEnum, DynamicTuple, DynamicVariant, VariantInfo};
+
+    let mut dyn_enum = match lua_value {
+        LuaValue::Nil => DynamicEnum::new("None", DynamicVariant::Unit),
+        LuaValue::Table(table)
+            if table.contains_key("Some")? || table.contains_key("None")? =>
+        {
+            return Ok(None);
+        }
+        value => {
+            let Some(VariantInfo::Tuple(some_info)) = enum_info.variant("Some") else {
+                return Ok(None);
+            };
+            let inner_field = some_info.field_at(0);
+            let inner_type_info = inner_field.and_then(|f| f.type_info()).or_else(|| {
+                let reg = registry.read();
+                inner_field.and_then(|f| reg.get(f.type_id()).map(|r| r.type_info()))
+            });
+            let inner = lua_value_to_box_reflect_with_assets(
+                lua,
+                value,
+                inner_type_info,
+                registry,
+                asset_registry,
+            )?;
+            let mut tuple = DynamicTuple::default();
+            tuple.insert_boxed(inner);
+            DynamicEnum::new("Some", DynamicVariant::Tuple(tuple))
+        }
+    };
+
+    {
+        let reg = registry.read();
+        if let Some(registration) = reg.get(enum_info.type_id()) {
+            dyn_enum.set_represented_type(Some(registration.type_info()));
+        }
+    }
+
+    Ok(Some(dyn_enum))
+}
+
 /// Convert a Lua value to a boxed Reflect value with optional asset registry for handle lookup
 fn lua_value_to_box_reflect_with_assets(
     lua: &Lua,
@@ -423,6 +679,17 @@ fn lua_value_to_box_reflect_with_assets(
 ) -> LuaResult<Box<dyn bevy::reflect::PartialReflect>> {
     use bevy::reflect::{DynamicStruct, TypeInfo};
 
+    // Option<T> fields: nil -> None, any other value -> Some(v)
+    if let Some(TypeInfo::Enum(enum_info)) = type_info {
+        if is_option_type_path(enum_info.type_path()) {
+            if let Some(option) =
+                lua_value_to_dynamic_option(lua, lua_value, enum_info, registry, asset_registry)?
+            {
+                return Ok(Box::new(option));
+            }
+        }
+    }
+
     match lua_value {
         LuaValue::Number(n) => {
             // Check if type might expect a Handle or newtype wrapper - purely via reflection
@@ -542,7 +809,12 @@ fn lua_value_to_box_reflect_with_assets(
             // Default to i64, but check for other int types
             if let Some(TypeInfo::Opaque(info)) = type_info {
                 let type_path = info.type_path();
-                if type_path.contains("i32") {
+                if type_path == "f32" {
+                    // Whole numbers in Lua arrive as integers even for float fields
+                    return Ok(Box::new(*i as f32));
+                } else if type_path == "f64" {
+                    return Ok(Box::new(*i as f64));
+                } else if type_path.contains("i32") {
                     return Ok(Box::new(*i as i32));
                 } else if type_path.contains("u32") {
                     return Ok(Box::new(*i as u32));
@@ -745,6 +1017,24 @@ fn lua_value_to_box_reflect_with_assets(
                     bevy::log::debug!("[ENUM_REFLECT] No matching variant found in table for enum");
                 }
 
+                // Tuple structs: positional fields from integer keys
+                if let TypeInfo::TupleStruct(_) = type_info {
+                    let tuple = lua_table_to_dynamic_tuple_struct(lua, table, type_info, registry, asset_registry)?;
+                    return Ok(Box::new(tuple));
+                }
+
+                // HashMap-style fields: table keys become map keys
+                if let TypeInfo::Map(_) = type_info {
+                    let map = lua_table_to_dynamic_map(lua, table, type_info, registry, asset_registry)?;
+                    return Ok(Box::new(map));
+                }
+
+                // Vec-style fields: array elements become list items
+                if let TypeInfo::List(_) = type_info {
+                    let list = lua_table_to_dynamic_list(lua, table, type_info, registry, asset_registry)?;
+                    return Ok(Box::new(list));
+                }
+
                 // Recursively build nested struct
                 if let TypeInfo::Struct(struct_info) = type_info {
                     let nested = lua_table_to_dynamic_with_assets(
@@ -809,6 +1099,9 @@ pub fn lua_to_reflection(
             }
         }
         LuaValue::Table(table) => {
+            if crate::reflection::lua_to_math(field, lua_value) {
+                return Ok(());
+            }
             // Handle nested structs
             if let bevy::reflect::ReflectMut::Struct(struct_mut) = field.reflect_mut() {
                 for i in 0..struct_mut.field_len() {
@@ -826,20 +1119,217 @@ pub fn lua_to_reflection(
                     }
                 }
             }
-            // Handle Vec2, Vec3 specially
-            if let Some(vec2_field) = field.try_downcast_mut::<bevy::math::Vec2>() {
-                let x: f32 = table.get("x").unwrap_or(0.0);
-                let y: f32 = table.get("y").unwrap_or(0.0);
-                *vec2_field = bevy::math::Vec2::new(x, y);
-            } else if let Some(vec3_field) = field.try_downcast_mut::<bevy::math::Vec3>() {
-                let x: f32 = table.get("x").unwrap_or(0.0);
-                let y: f32 = table.get("y").unwrap_or(0.0);
-                let z: f32 = table.get("z").unwrap_or(0.0);
-                *vec3_field = bevy::math::Vec3::new(x, y, z);
-            }
         }
         _ => {}
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::reflect::{FromReflect, TypeInfo};
+    use std::collections::HashMap;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct TagWeights(HashMap<String, f32>);
+
+    #[test]
+    fn test_map_field_round_trips_through_lua() {
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<TagWeights>();
+        let lua = Lua::new();
+
+        let table: LuaTable = lua.load("{ fire = 0.5, ice = 2 }").eval().unwrap();
+        let field_info = {
+            let reg = registry.read();
+            let TypeInfo::TupleStruct(info) = reg.get(std::any::TypeId::of::<TagWeights>()).unwrap().type_info() else {
+                panic!("TagWeights should be a tuple struct");
+            };
+            info.field_at(0).unwrap().type_info().unwrap()
+        };
+
+        let map = lua_value_to_box_reflect(&lua, &LuaValue::Table(table), Some(field_info), &registry).unwrap();
+        let mut dynamic = bevy::reflect::DynamicTupleStruct::default();
+        dynamic.insert_boxed(map);
+        let weights = TagWeights::from_reflect(&dynamic).expect("DynamicMap should convert to HashMap");
+        assert_eq!(weights.0.get("fire"), Some(&0.5));
+        assert_eq!(weights.0.get("ice"), Some(&2.0));
+
+        let lua_value = reflection_to_lua(&lua, &weights, &registry).unwrap();
+        let LuaValue::Table(outer) = lua_value else { panic!("expected table") };
+        let inner: LuaTable = outer.get(1).unwrap();
+        assert_eq!(inner.get::<f32>("fire").unwrap(), 0.5);
+        assert_eq!(inner.get::<f32>("ice").unwrap(), 2.0);
+    }
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct MaybeName {
+        name: Option<String>,
+    }
+
+    #[test]
+    fn test_option_field_round_trips_through_lua() {
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<MaybeName>();
+        let lua = Lua::new();
+        let type_info = registry.read().get(std::any::TypeId::of::<MaybeName>()).unwrap().type_info();
+
+        let table: LuaTable = lua.load("{ name = 'bob' }").eval().unwrap();
+        let dynamic = lua_table_to_dynamic(&lua, &table, type_info, &registry).unwrap();
+        let named = MaybeName::from_reflect(&dynamic).expect("Some(String) should convert");
+        assert_eq!(named.name.as_deref(), Some("bob"));
+
+        let LuaValue::Table(lua_named) = reflection_to_lua(&lua, &named, &registry).unwrap() else {
+            panic!("expected table");
+        };
+        assert_eq!(lua_named.get::<String>("name").unwrap(), "bob");
+
+        let table: LuaTable = lua.load("{ name = nil }").eval().unwrap();
+        let dynamic = lua_table_to_dynamic(&lua, &table, type_info, &registry).unwrap();
+        let unnamed = MaybeName::from_reflect(&dynamic).expect("None should convert");
+        assert_eq!(unnamed, MaybeName { name: None });
+
+        let LuaValue::Table(lua_unnamed) = reflection_to_lua(&lua, &unnamed, &registry).unwrap() else {
+            panic!("expected table");
+        };
+        assert!(matches!(lua_unnamed.get::<LuaValue>("name").unwrap(), LuaValue::Nil));
+
+        // Component snapshots (lua_world_api) read Option fields the same way
+        let LuaValue::Table(snapshot) =
+            crate::lua_world_api::reflection_to_lua_with_assets(&lua, &named, None).unwrap()
+        else {
+            panic!("expected table");
+        };
+        assert_eq!(snapshot.get::<String>("name").unwrap(), "bob");
+        let LuaValue::Table(snapshot) =
+            crate::lua_world_api::reflection_to_lua_with_assets(&lua, &unnamed, None).unwrap()
+        else {
+            panic!("expected table");
+        };
+        assert!(matches!(snapshot.get::<LuaValue>("name").unwrap(), LuaValue::Nil));
+    }
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Radius(f32);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Label(String, u32);
+
+    #[test]
+    fn test_tuple_struct_round_trips_through_lua() {
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Radius>();
+        registry.write().register::<Label>();
+        let lua = Lua::new();
+
+        let type_info = |id: std::any::TypeId| registry.read().get(id).unwrap().type_info();
+
+        let table: LuaTable = lua.load("{ 2 }").eval().unwrap();
+        let dynamic = lua_table_to_dynamic_tuple_struct(&lua, &table, type_info(std::any::TypeId::of::<Radius>()), &registry, None).unwrap();
+        assert_eq!(Radius::from_reflect(&dynamic), Some(Radius(2.0)));
+
+        // `_0`-style keys (as used by set_resource) work too
+        let table: LuaTable = lua.load("{ _0 = 'orc', _1 = 3 }").eval().unwrap();
+        let dynamic = lua_table_to_dynamic_tuple_struct(&lua, &table, type_info(std::any::TypeId::of::<Label>()), &registry, None).unwrap();
+        let label = Label::from_reflect(&dynamic).unwrap();
+        assert_eq!(label, Label("orc".to_string(), 3));
+
+        let LuaValue::Table(lua_label) = reflection_to_lua(&lua, &label, &registry).unwrap() else {
+            panic!("expected table");
+        };
+        assert_eq!(lua_label.get::<String>(1).unwrap(), "orc");
+        assert_eq!(lua_label.get::<u32>(2).unwrap(), 3);
+    }
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Toggle {
+        visibility: Visibility,
+    }
+
+    #[test]
+    fn test_unit_enum_field_round_trips_as_string() {
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Toggle>();
+        let lua = Lua::new();
+        let type_info = registry.read().get(std::any::TypeId::of::<Toggle>()).unwrap().type_info();
+
+        let table: LuaTable = lua.load("{ visibility = 'Hidden' }").eval().unwrap();
+        let dynamic = lua_table_to_dynamic(&lua, &table, type_info, &registry).unwrap();
+        let toggle = Toggle::from_reflect(&dynamic).expect("unit variant name should convert");
+        assert_eq!(toggle.visibility, Visibility::Hidden);
+
+        let LuaValue::Table(lua_toggle) = reflection_to_lua(&lua, &toggle, &registry).unwrap() else {
+            panic!("expected table");
+        };
+        assert_eq!(lua_toggle.get::<String>("visibility").unwrap(), "Hidden");
+        assert_eq!(
+            crate::lua_world_api::reflection_to_lua(&lua, &Visibility::Visible)
+                .unwrap()
+                .as_string()
+                .unwrap()
+                .to_string_lossy(),
+            "Visible"
+        );
+    }
+
+    #[test]
+    fn test_data_carrying_enum_round_trips_through_lua() {
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<BackgroundColor>();
+        let lua = Lua::new();
+        let type_info = registry.read().get(std::any::TypeId::of::<BackgroundColor>()).unwrap().type_info();
+
+        let table: LuaTable = lua
+            .load("{ { Srgba = { red = 1, green = 0.5, blue = 0, alpha = 1 } } }")
+            .eval()
+            .unwrap();
+        let dynamic = lua_table_to_dynamic_tuple_struct(&lua, &table, type_info, &registry, None).unwrap();
+        let color = BackgroundColor::from_reflect(&dynamic).expect("Color::Srgba should convert");
+        assert_eq!(color.0, Color::srgba(1.0, 0.5, 0.0, 1.0));
+
+        let LuaValue::Table(lua_color) = reflection_to_lua(&lua, &color, &registry).unwrap() else {
+            panic!("expected table");
+        };
+        let variant: LuaTable = lua_color.get::<LuaTable>(1).unwrap().get("Srgba").unwrap();
+        let srgba: LuaTable = variant.get(1).unwrap();
+        assert_eq!(srgba.get::<f32>("green").unwrap(), 0.5);
+    }
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct PathNode {
+        waypoints: Vec<Vec3>,
+    }
+
+    #[test]
+    fn test_list_field_round_trips_through_lua() {
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<PathNode>();
+        let lua = Lua::new();
+        let type_info = registry.read().get(std::any::TypeId::of::<PathNode>()).unwrap().type_info();
+
+        let table: LuaTable = lua
+            .load("{ waypoints = { { x = 1, y = 2, z = 3 }, { x = 4.5, y = 0, z = -1 } } }")
+            .eval()
+            .unwrap();
+        let dynamic = lua_table_to_dynamic(&lua, &table, type_info, &registry).unwrap();
+        let path = PathNode::from_reflect(&dynamic).expect("DynamicList should convert to Vec<Vec3>");
+        assert_eq!(path.waypoints, vec![Vec3::new(1.0, 2.0, 3.0), Vec3::new(4.5, 0.0, -1.0)]);
+
+        let LuaValue::Table(lua_path) = reflection_to_lua(&lua, &path, &registry).unwrap() else {
+            panic!("expected table");
+        };
+        let waypoints: LuaTable = lua_path.get("waypoints").unwrap();
+        assert_eq!(waypoints.raw_len(), 2);
+        let second: LuaTable = waypoints.get(2).unwrap();
+        assert_eq!(second.get::<f32>("x").unwrap(), 4.5);
+        assert_eq!(second.get::<f32>("z").unwrap(), -1.0);
+    }
+}
@@ -4,58 +4,145 @@
 //! Events are queued and dispatched to their concrete types by a generated system.
 
 use bevy::prelude::*;
+use mlua::RegistryKey;
 use serde_json::Value;
 use std::sync::{Arc, Mutex};
 
+/// Dispatch order of a pending Lua event within a frame
+/// Events of the same priority keep the order they were queued in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EventPriority {
+    High,
+    #[default]
+    Normal,
+    Low,
+}
+
+impl std::str::FromStr for EventPriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "high" => Ok(Self::High),
+            "normal" => Ok(Self::Normal),
+            "low" => Ok(Self::Low),
+            _ => Err(format!(
+                "unknown event priority '{}' (expected \"high\", \"normal\" or \"low\")",
+                s
+            )),
+        }
+    }
+}
+
 /// Resource that holds pending events to be sent from Lua scripts.
 ///
 /// Events are stored as JSON values with their type names, then dispatched
 /// to concrete EventWriter<T> by the generated `dispatch_lua_events` system.
 #[derive(Resource, Default, Clone)]
 pub struct PendingLuaEvents {
-    /// Events waiting to be dispatched: (type_name, json_data)
-    pub events: Arc<Mutex<Vec<(String, Value)>>>,
+    /// Events waiting to be dispatched: (type_name, json_data, priority)
+    pub events: Arc<Mutex<Vec<(String, Value, EventPriority)>>>,
 }
 
 impl PendingLuaEvents {
     /// Queue an event to be sent on the next frame
     pub fn queue_event(&self, type_name: String, data: Value) {
+        self.queue_event_with_priority(type_name, data, EventPriority::Normal);
+    }
+
+    /// Queue an event that is dispatched ahead of all normal-priority events this frame
+    pub fn send_high_priority(&self, type_name: String, data: Value) {
+        self.queue_event_with_priority(type_name, data, EventPriority::High);
+    }
+
+    /// Queue an event with an explicit dispatch priority
+    pub fn queue_event_with_priority(&self, type_name: String, data: Value, priority: EventPriority) {
         if let Ok(mut events) = self.events.lock() {
-            events.push((type_name, data));
+            events.push((type_name, data, priority));
         }
     }
 
-    /// Take all pending events for dispatch
-    pub fn drain_events(&self) -> Vec<(String, Value)> {
+    /// Queue several events of one type under a single lock
+    pub fn queue_events(&self, type_name: &str, batch: Vec<Value>) -> usize {
+        let count = batch.len();
         if let Ok(mut events) = self.events.lock() {
-            std::mem::take(&mut *events)
-        } else {
-            Vec::new()
+            events.extend(
+                batch
+                    .into_iter()
+                    .map(|data| (type_name.to_string(), data, EventPriority::Normal)),
+            );
         }
+        count
+    }
+
+    /// Queue several events of one type, dropping events whose fields are identical
+    /// to an earlier event in the same batch. Returns the number of events queued.
+    pub fn queue_events_deduped(&self, type_name: &str, batch: Vec<Value>) -> usize {
+        let mut seen = std::collections::HashSet::new();
+        let unique: Vec<Value> = batch
+            .into_iter()
+            .filter(|data| seen.insert(data.to_string()))
+            .collect();
+        self.queue_events(type_name, unique)
+    }
+
+    /// Take all pending events for dispatch, high priority first, then normal, then low
+    pub fn drain_events(&self) -> Vec<(String, Value)> {
+        let Ok(mut events) = self.events.lock() else {
+            return Vec::new();
+        };
+        let mut drained = std::mem::take(&mut *events);
+        // Stable sort keeps queue order within a priority
+        drained.sort_by_key(|(_, _, priority)| *priority);
+        drained
+            .into_iter()
+            .map(|(type_name, data, _)| (type_name, data))
+            .collect()
     }
 }
 
+/// Data of a message waiting in `PendingLuaMessages`
+pub enum LuaMessageData {
+    /// Produced outside a Lua state; converted to a Lua table at dispatch
+    Json(Value),
+    /// Copy of the table passed to write_message, held in the Lua registry
+    /// Resolve the owning state with `LuaScriptContext::state_for_key`
+    Lua(RegistryKey),
+}
+
 /// Resource that holds pending messages to be sent from Lua scripts.
 ///
-/// Messages are stored as JSON values with their type names, then dispatched
-/// to concrete MessageWriter<M> by the generated `dispatch_lua_messages` system.
+/// Messages sent from Lua keep a copy of their table in the Lua registry and are dispatched
+/// to concrete MessageWriter<M> by the generated `dispatch_lua_messages` system without
+/// a JSON round-trip. Messages produced outside a Lua state are queued as JSON instead.
+/// Both kinds share one queue, so messages are written in the order they were sent.
 /// This is similar to PendingLuaEvents but for Bevy's message system (e.g. PointerInput).
 #[derive(Resource, Default, Clone)]
 pub struct PendingLuaMessages {
-    /// Messages waiting to be dispatched: (type_name, json_data)
-    pub messages: Arc<Mutex<Vec<(String, Value)>>>,
+    /// Messages waiting to be dispatched, in send order: (type_name, data)
+    pub messages: Arc<Mutex<Vec<(String, LuaMessageData)>>>,
 }
 
 impl PendingLuaMessages {
     /// Queue a message to be sent on the next frame
     pub fn queue_message(&self, type_name: String, data: Value) {
         if let Ok(mut messages) = self.messages.lock() {
-            messages.push((type_name, data));
+            messages.push((type_name, LuaMessageData::Json(data)));
         }
     }
 
-    /// Take all pending messages for dispatch
-    pub fn drain_messages(&self) -> Vec<(String, Value)> {
+    /// Queue a message from Lua, copying `data` so later edits to the table don't change it
+    pub fn queue_lua_message(&self, lua: &mlua::Lua, type_name: String, data: &mlua::Table) -> mlua::Result<()> {
+        let copy = crate::prefab_registry::deep_copy_lua_value(lua, &mlua::Value::Table(data.clone()))?;
+        let key = lua.create_registry_value(copy)?;
+        if let Ok(mut messages) = self.messages.lock() {
+            messages.push((type_name, LuaMessageData::Lua(key)));
+        }
+        Ok(())
+    }
+
+    /// Take all pending messages for dispatch, in send order
+    pub fn drain_messages(&self) -> Vec<(String, LuaMessageData)> {
         if let Ok(mut messages) = self.messages.lock() {
             std::mem::take(&mut *messages)
         } else {
@@ -71,6 +158,85 @@ impl Plugin for LuaEventSenderPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PendingLuaEvents>();
         app.init_resource::<PendingLuaMessages>();
-        // The dispatch systems are added by generated code
+        // Batched events from world:send_events are written once per frame
+        app.add_systems(Update, crate::auto_bindings::dispatch_lua_events);
+        // The message dispatch system is added by generated code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_queue_events_batch_and_dedup() {
+        let pending = PendingLuaEvents::default();
+        let queued = pending.queue_events("Collision", vec![json!({ "a": 1 }), json!({ "a": 2 })]);
+        assert_eq!(queued, 2);
+
+        let queued = pending.queue_events_deduped(
+            "KeyboardInput",
+            vec![
+                json!({ "key": "W", "pressed": true }),
+                json!({ "pressed": true, "key": "W" }),
+                json!({ "key": "S", "pressed": true }),
+            ],
+        );
+        assert_eq!(queued, 2);
+
+        let drained = pending.drain_events();
+        assert_eq!(drained.len(), 4);
+        assert_eq!(drained[0].0, "Collision");
+        assert_eq!(drained[3].1, json!({ "key": "S", "pressed": true }));
+        assert!(pending.drain_events().is_empty());
+    }
+
+    #[test]
+    fn test_high_priority_events_drain_first() {
+        let pending = PendingLuaEvents::default();
+        pending.queue_event("ScoreUpdated".to_string(), json!({ "score": 10 }));
+        pending.queue_event_with_priority("Ambient".to_string(), json!({}), EventPriority::Low);
+        pending.send_high_priority("PlayerDied".to_string(), json!({ "player": 1 }));
+        pending.queue_event("ScoreUpdated".to_string(), json!({ "score": 20 }));
+
+        let order: Vec<_> = pending
+            .drain_events()
+            .into_iter()
+            .map(|(name, data)| format!("{}{}", name, data))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                r#"PlayerDied{"player":1}"#,
+                r#"ScoreUpdated{"score":10}"#,
+                r#"ScoreUpdated{"score":20}"#,
+                "Ambient{}",
+            ]
+        );
+        assert_eq!("high".parse::<EventPriority>(), Ok(EventPriority::High));
+        assert!("urgent".parse::<EventPriority>().is_err());
+    }
+
+    #[test]
+    fn test_lua_messages_are_copied_and_drain_in_send_order() {
+        let lua = mlua::Lua::new();
+        let pending = PendingLuaMessages::default();
+        let table = lua.create_table().unwrap();
+        table.set("button", "Primary").unwrap();
+        pending.queue_lua_message(&lua, "PointerInput".to_string(), &table).unwrap();
+        pending.queue_message("PointerInput".to_string(), json!({ "button": "Secondary" }));
+        // Editing the table after sending doesn't change the queued message
+        table.set("button", "Middle").unwrap();
+
+        let messages = pending.drain_messages();
+        assert_eq!(messages.len(), 2);
+        let LuaMessageData::Lua(key) = &messages[0].1 else {
+            panic!("first message should be the Lua one");
+        };
+        let data: mlua::Table = lua.registry_value(key).unwrap();
+        assert_eq!(data.get::<String>("button").unwrap(), "Primary");
+        assert!(matches!(&messages[1].1, LuaMessageData::Json(data) if data["button"] == "Secondary"));
+        assert!(pending.drain_messages().is_empty());
     }
 }
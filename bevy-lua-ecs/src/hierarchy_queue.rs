@@ -0,0 +1,54 @@
+//! Parent/child changes requested from Lua
+//!
+//! ```lua
+//! world:set_parent(child, panel)
+//! local parent = world:get_parent(child)   -- entity id or nil
+//! local kids = world:get_children(panel)   -- array of entity ids
+//! ```
+//!
+//! Reads go straight to `ChildOf`/`Children`; writes are queued and applied after the
+//! spawn queue so ids returned by `spawn()` earlier in the same frame resolve correctly.
+
+use crate::spawn_queue::SpawnQueue;
+use bevy::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// Queue of `(child, parent)` pairs from Lua, stored as raw ids (temp ids or entity bits)
+#[derive(Resource, Clone, Default)]
+pub struct HierarchyQueue {
+    queue: Arc<Mutex<Vec<(u64, u64)>>>,
+}
+
+impl HierarchyQueue {
+    /// Queue `child` to be added to `parent`'s children
+    pub fn queue_set_parent(&self, child: u64, parent: u64) {
+        self.queue.lock().unwrap().push((child, parent));
+    }
+
+    fn drain(&self) -> Vec<(u64, u64)> {
+        self.queue.lock().unwrap().drain(..).collect()
+    }
+}
+
+/// System that applies queued parent changes
+pub fn process_hierarchy_queue(
+    mut commands: Commands,
+    hierarchy_queue: Res<HierarchyQueue>,
+    spawn_queue: Res<SpawnQueue>,
+    entities: Query<()>,
+) {
+    for (child_id, parent_id) in hierarchy_queue.drain() {
+        let child = spawn_queue.resolve_entity(child_id);
+        let parent = spawn_queue.resolve_entity(parent_id);
+
+        if child == parent || !entities.contains(child) || !entities.contains(parent) {
+            warn!(
+                "[HIERARCHY] Ignoring set_parent({}, {}): entity missing or self-parenting",
+                child_id, parent_id
+            );
+            continue;
+        }
+
+        commands.entity(parent).add_child(child);
+    }
+}
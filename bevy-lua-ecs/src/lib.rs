@@ -1,3 +1,10 @@
+// The bindings rely on Lua 5.4 semantics; mlua picks the interpreter from its feature flags
+#[cfg(not(feature = "lua54"))]
+compile_error!(
+    "bevy-lua-ecs requires Lua 5.4. Enable the `lua54` feature of bevy-lua-ecs (on by default) \
+     so mlua is built with `features = [\"lua54\"]`; see https://docs.rs/mlua/latest/mlua/#feature-flags"
+);
+
 // Modules
 pub mod asset_loading;
 pub mod auto_bindings;
@@ -7,24 +14,40 @@ pub mod component_update_queue;
 pub mod component_updater;
 pub mod components;
 pub mod despawn_queue;
+pub mod entity_persistence;
 pub mod entity_spawner;
+pub mod entity_tags;
 pub mod event_reader;
 pub mod event_accumulator;
 pub mod event_sender;
+pub mod hierarchy_queue;
+pub mod lua_animation;
+pub mod lua_coroutines;
+pub mod lua_eval;
 pub mod lua_file_watcher;
 pub mod lua_frame_budget;
 pub mod lua_integration;
 pub mod lua_observers;
 pub mod lua_parallel;
+pub mod lua_plugin_order;
 pub mod lua_resource;
+pub mod lua_sandbox;
 pub mod lua_spawn_builder;
+pub mod lua_stdlib;
 pub mod lua_systems;
+pub mod lua_timers;
+pub mod lua_tween;
 pub mod lua_world_api;
 pub mod lua_world_context;
 pub mod network_asset_trait;
+pub mod observer_persistence;
+pub mod one_shot_systems;
 pub mod os_utilities;
 pub mod path_utils;
+pub mod physics_debug;
+pub mod prefab_registry;
 pub mod query_cache;
+pub mod query_cursor;
 pub mod reflection;
 pub mod removed_components;
 pub mod resource_builder;
@@ -32,70 +55,124 @@ pub mod resource_constructors;
 pub mod resource_inserter;
 pub mod resource_lua_trait;
 pub mod resource_queue;
+pub mod resource_watchers;
 pub mod script_cache;
 pub mod script_entities;
 pub mod script_registry;
 pub mod serde_components;
 pub mod spawn_queue;
 pub mod systemparam_lua_trait;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod ui_node_builder;
+pub mod undo_stack;
+
+// Re-export the proc-macro and the crates its generated code refers to
+pub use bevy_lua_ecs_macros::lua_export;
+pub use inventory;
+pub use mlua;
 
 // Re-export commonly used types
 pub use asset_loading::{
-    add_asset_loading_to_lua, parse_enum_from_string, register_asset_types_runtime,
+    add_asset_loading_to_lua, asset_from_lua_table, parse_enum_from_string, register_asset_types_runtime,
     register_cloner_if_clone, AssetAdder, AssetCloner, AssetRegistry, HandleCreator, HandleSetter,
-    NewtypeWrapperCreator, ReflectDirectAssetAdd,
+    LuaBytesAssetSourcePlugin, LuaBytesAssets, NewtypeWrapperCreator, ReflectDirectAssetAdd,
 };
 pub use auto_bindings::{
     dispatch_lua_events, dispatch_lua_messages, dispatch_systemparam_method,
     register_auto_bindings, register_auto_events,
 };
-pub use bitflags_registry::BitflagsRegistry;
-pub use component_lua_trait::LuaComponentRegistry;
-pub use component_update_queue::{ComponentUpdateQueue, get_component_with_queue};
+pub use bitflags_registry::{add_bitflags_functions_to_lua, BitflagsRegistry};
+pub use component_lua_trait::{ComponentValidator, ComponentValidatorFn, LuaComponentRegistry};
+pub use component_update_queue::{ComponentUpdateQueue, ConflictPolicy, FieldPredicate, SerializationFormat, UpdatePredicate, get_component_with_queue};
 pub use component_updater::process_component_updates;
-pub use components::{register_entity_wrappers_runtime, ComponentRegistry, LuaCustomComponents, NonReflectedSerializer};
+pub use components::{register_entity_wrappers_runtime, ComponentRegistry, LuaCustomComponents, NonReflectedSerializer, NonReflectedSetter};
 
-pub use despawn_queue::{process_despawn_queue, DespawnQueue};
+pub use despawn_queue::{process_despawn_queue, DespawnMode, DespawnQueue};
 pub use entity_spawner::process_spawn_queue;
+pub use entity_tags::{process_tag_queue, TagOp, TagQueue, Tags};
 pub use event_reader::{
-    lua_table_to_dynamic, lua_table_to_dynamic_with_assets, lua_to_reflection, reflection_to_lua,
-    reflect_to_json,
+    lua_table_to_dynamic, lua_table_to_dynamic_list, lua_table_to_dynamic_map, lua_table_to_dynamic_tuple_struct,
+    lua_table_to_dynamic_with_assets, lua_to_reflection, reflection_to_lua, reflect_to_json,
 };
 pub use event_accumulator::{LuaEventAccumulator, json_to_lua_value};
-pub use event_sender::{LuaEventSenderPlugin, PendingLuaEvents, PendingLuaMessages};
-pub use lua_file_watcher::{LuaFileChangeEvent, LuaFileWatcherPlugin};
-pub use lua_integration::{LuaScriptContext, LuaSpawnPlugin};
+pub use hierarchy_queue::{process_hierarchy_queue, HierarchyQueue};
+pub use event_sender::{EventPriority, LuaEventSenderPlugin, LuaMessageData, PendingLuaEvents, PendingLuaMessages};
+pub use lua_animation::{process_animation_queue, AnimationLoopMode, AnimationQueue};
+pub use lua_coroutines::{advance_lua_coroutines, LuaCoroutineScheduler};
+pub use lua_timers::{tick_lua_timers, TimerRegistry};
+pub use entity_persistence::SaveConfig;
+pub use lua_eval::{process_eval_requests, EvalLuaExpression, LuaExpressionResult};
+pub use lua_file_watcher::{
+    LuaDependencyWatcher, LuaFileChangeDebouncer, LuaFileChangeEvent, LuaFileWatcherPlugin, LuaHotReloadConfig,
+};
+pub use lua_integration::{LuaScriptContext, LuaSpawnPlugin, LuaStateSetup};
+pub use lua_sandbox::MultiLuaContextPlugin;
 pub use lua_observers::{
-    attach_lua_observers, dispatch_lua_observer_internal, process_observer_registrations,
-    set_observer_attacher, LuaObserverRegistry, LuaObserversAttached,
+    attach_component_lifecycle_observer, attach_lifecycle_observers, attach_lua_observers,
+    dispatch_lua_observer_event, dispatch_lua_observer_internal, process_observer_registrations,
+    set_observer_attacher, ComponentLifecycle, LuaObserverCallback, LuaObserverRegistry,
+    LuaObserversAttached,
 };
-pub use lua_spawn_builder::LuaSpawnBuilder;
-pub use lua_frame_budget::{LuaFrameBudget, LuaSystemProgress};
+pub use lua_spawn_builder::{run_post_spawn_callbacks, LuaSpawnBuilder, PostSpawnCallbackQueue};
+pub use lua_stdlib::{load_stdlib_extensions, LuaStdlibExtensions};
+pub use lua_frame_budget::{LuaFrameBudget, LuaSystemProfiles, LuaSystemProgress, SystemProfile};
 pub use lua_parallel::LuaParallelConfig;
 pub use lua_resource::LuaTableResourceRegistry;
-pub use lua_systems::{run_lua_systems, LuaSystemEntry, LuaSystemRegistry};
-pub use lua_world_api::{execute_query, LuaEntitySnapshot, LuaQueryBuilder};
-pub use network_asset_trait::{AssetDownloadStatus, NetworkAssetLoader, NetworkAssetRequestor};
-pub use path_utils::{normalize_path, normalize_path_separators, to_forward_slash};
+pub use lua_systems::{run_lua_systems, LuaSystemCondition, LuaSystemEntry, LuaSystemOrdering, LuaSystemRegistry};
+pub use lua_plugin_order::{LuaPluginOrder, StartupStage};
+pub use lua_tween::{process_tweens, EasingFn, TweenQueue};
+pub use lua_world_api::{collect_query_entities, count_query, execute_query, FieldFilter, FieldOp, FieldValue, LuaEntitySnapshot, LuaQueryBuilder, RadiusFilter};
+pub use observer_persistence::{
+    restore_instance_observers, snapshot_instance_observers, CallbackFingerprint, PersistentObserverSnapshot,
+};
+pub use one_shot_systems::{
+    register_lua_one_shot_system, run_one_shot_system, LuaSystemInput, OneShotSystemRegistry,
+};
+pub use network_asset_trait::{
+    retry_failed_downloads, AssetByteProgress, AssetDownloadStatus, DownloadProgress,
+    DownloadRetryState, NetworkAssetLoader, NetworkAssetRequestor,
+};
+#[cfg(feature = "physics")]
+pub use physics_debug::{process_physics_debug_queue, PhysicsDebugQueue};
+pub use physics_debug::PhysicsDebugOp;
+pub use path_utils::{canonicalize_lua_path, normalize_path, normalize_path_separators, to_asset_server_path, to_forward_slash};
+pub use prefab_registry::{Prefab, PrefabRegistry};
 pub use query_cache::LuaQueryCache;
+pub use query_cursor::{process_async_queries, QueryCursor, QueryIterState};
+pub use reflection::{reflect_field_mut, BundleRegistry};
 pub use removed_components::{update_removed_components_tracker, RemovedComponentsTracker};
 pub use resource_builder::ResourceBuilderRegistry;
-pub use resource_constructors::{OsUtilities, ResourceConstructorRegistry};
-pub use resource_inserter::process_resource_queue;
-pub use resource_lua_trait::LuaResourceRegistry;
-pub use resource_queue::ResourceQueue;
-pub use script_cache::ScriptCache;
-pub use script_entities::{despawn_instance_entities, ScriptInstance, ScriptOwned};
-pub use script_registry::ScriptRegistry;
-pub use serde_components::SerdeComponentRegistry;
-pub use spawn_queue::SpawnQueue;
+pub use os_utilities::OsUtilities;
+pub use resource_constructors::{
+    insert_reflected_resource, ParamResourceFactory, ResourceConstructorRegistry, ResourceFactory,
+};
+pub use resource_inserter::{process_high_priority_resources, process_resource_queue};
+pub use resource_lua_trait::{LuaExportedResource, LuaResourceChain, LuaResourceRegistry};
+pub use resource_queue::{ResourcePriority, ResourceQueue, ResourceRequestKind};
+pub use resource_watchers::{check_resource_watchers, ResourceWatcherRegistry};
+pub use script_cache::{ScriptCache, ScriptCacheConfig};
+pub use script_entities::{
+    despawn_instance_entities, gc_orphaned_script_entities, PooledScriptInstance, ScriptGcConfig,
+    ScriptInstance, ScriptInstancePool, ScriptOwned,
+};
+pub use script_registry::{reap_stale_script_entities, HotReloadPolicy, ScriptRegistry};
+pub use serde_components::{
+    LuaComponentSchema, LuaDefinedComponents, LuaJsonComponent, SerdeComponentRegistry,
+};
+pub use spawn_queue::{BatchSpawnEntry, SpawnQueue, SpawnQueueEntry, UiNodeSpawnRequest};
 pub use systemparam_lua_trait::{
-    call_component_method_global, call_read_events_global, call_static_method_global,
-    call_systemparam_method_global, call_write_events_global, call_write_messages_global,
-    set_component_method_dispatcher, set_event_dispatcher, set_event_write_dispatcher,
-    set_message_write_dispatcher, set_static_method_dispatcher, set_systemparam_dispatcher,
+    call_component_method_global, call_create_asset_global, call_read_events_global,
+    call_static_method_global, call_systemparam_method_global, call_write_events_global,
+    call_write_messages_global, set_asset_create_dispatcher, set_component_method_dispatcher,
+    set_event_dispatcher, set_event_write_dispatcher, set_message_write_dispatcher,
+    set_static_method_dispatcher, set_systemparam_dispatcher,
     LuaSystemParamMethods, LuaSystemParamRegistry,
 };
+pub use ui_node_builder::UiNodeProps;
+pub use undo_stack::{EntityStateSnapshot, UndoStack};
+#[cfg(feature = "testing")]
+pub use testing::{TestAssertions, TestLuaApp};
 
 /// Register common Bevy event types for Lua access via world:read_events()
 ///
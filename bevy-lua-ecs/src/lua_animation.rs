@@ -0,0 +1,260 @@
+//! Skeletal animation playback driven from Lua
+//!
+//! ```lua
+//! world:animate(fox, "models/Fox.glb#Animation0", "repeat")   -- "once", "repeat" or "ping_pong"
+//! local t = world:animation_time(fox)                          -- seconds into the clip, or nil
+//! world:stop_animation(fox)
+//! ```
+//!
+//! `animate` loads the clip through the `AssetServer` and queues the request; the clip starts
+//! once the asset has finished loading (same wait-then-apply flow as pending network assets).
+//! The `AnimationPlayer` is looked up on the entity itself or its descendants, since glTF scenes
+//! put it on the skeleton root rather than the scene root.
+
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// How a clip started from Lua repeats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnimationLoopMode {
+    #[default]
+    Once,
+    Repeat,
+    /// Plays forward then backward, forever
+    PingPong,
+}
+
+impl AnimationLoopMode {
+    /// Parse a loop mode name from Lua
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "once" => Some(AnimationLoopMode::Once),
+            "repeat" => Some(AnimationLoopMode::Repeat),
+            "ping_pong" => Some(AnimationLoopMode::PingPong),
+            _ => None,
+        }
+    }
+}
+
+enum AnimationCommand {
+    Play {
+        player: Entity,
+        clip: Handle<AnimationClip>,
+        mode: AnimationLoopMode,
+    },
+    Stop {
+        player: Entity,
+    },
+}
+
+/// Queue of animation requests from Lua, applied by `process_animation_queue`
+#[derive(Resource, Clone, Default)]
+pub struct AnimationQueue {
+    /// Commands in request order; `Play` entries stay queued until their clip has loaded
+    commands: Arc<Mutex<Vec<AnimationCommand>>>,
+    /// Players running a ping-pong clip -> completions seen so far
+    ping_pong: Arc<Mutex<HashMap<Entity, u32>>>,
+}
+
+impl AnimationQueue {
+    /// Queue `clip` to play on `player` once it has loaded
+    /// Replaces any earlier request for the same player that hasn't started yet
+    pub fn queue_play(&self, player: Entity, clip: Handle<AnimationClip>, mode: AnimationLoopMode) {
+        let mut commands = self.commands.lock().unwrap();
+        commands.retain(|command| !matches!(command, AnimationCommand::Play { player: p, .. } if *p == player));
+        commands.push(AnimationCommand::Play { player, clip, mode });
+    }
+
+    /// Queue stopping all animations on `player` (also drops a pending `queue_play`)
+    pub fn queue_stop(&self, player: Entity) {
+        let mut commands = self.commands.lock().unwrap();
+        commands.retain(|command| !matches!(command, AnimationCommand::Play { player: p, .. } if *p == player));
+        commands.push(AnimationCommand::Stop { player });
+    }
+}
+
+/// Find the `AnimationPlayer` for `entity`: the entity itself, or the first descendant that has one
+pub fn find_animation_player(world: &World, entity: Entity) -> Option<Entity> {
+    let mut pending = vec![entity];
+    while let Some(current) = pending.pop() {
+        if world.get::<AnimationPlayer>(current).is_some() {
+            return Some(current);
+        }
+        if let Some(children) = world.get::<Children>(current) {
+            pending.extend(children.iter().rev());
+        }
+    }
+    None
+}
+
+/// Playback position (seconds) of the first playing animation on `player`
+pub fn animation_time(world: &World, player: Entity) -> Option<f32> {
+    world
+        .get::<AnimationPlayer>(player)?
+        .playing_animations()
+        .next()
+        .map(|(_, active)| active.seek_time())
+}
+
+/// System that starts loaded clips and applies stop requests
+pub fn process_animation_queue(world: &mut World) {
+    let Some(queue) = world.get_resource::<AnimationQueue>().cloned() else {
+        return;
+    };
+    let commands = std::mem::take(&mut *queue.commands.lock().unwrap());
+    if commands.is_empty() {
+        return;
+    }
+
+    let mut still_loading = Vec::new();
+    for command in commands {
+        match command {
+            AnimationCommand::Stop { player } => {
+                queue.ping_pong.lock().unwrap().remove(&player);
+                if let Some(mut animation_player) = world.get_mut::<AnimationPlayer>(player) {
+                    animation_player.stop_all();
+                }
+            }
+            AnimationCommand::Play { player, clip, mode } => {
+                // Clips added straight to Assets (not loaded from a path) are ready immediately
+                let in_assets = world
+                    .get_resource::<Assets<AnimationClip>>()
+                    .is_some_and(|clips| clips.contains(&clip));
+                match world.resource::<AssetServer>().load_state(&clip) {
+                    _ if in_assets => {}
+                    LoadState::Loaded => {}
+                    LoadState::Failed(e) => {
+                        error!("[ANIMATION] Failed to load clip {:?}: {}", clip.path(), e);
+                        continue;
+                    }
+                    _ => {
+                        still_loading.push(AnimationCommand::Play { player, clip, mode });
+                        continue;
+                    }
+                }
+                if world.get::<AnimationPlayer>(player).is_none() {
+                    // Entity despawned (or player removed) while the clip was loading
+                    continue;
+                }
+
+                let (graph, node) = AnimationGraph::from_clip(clip);
+                let graph = world.resource_mut::<Assets<AnimationGraph>>().add(graph);
+                world.entity_mut(player).insert(AnimationGraphHandle(graph));
+
+                let mut animation_player = world.get_mut::<AnimationPlayer>(player).unwrap();
+                animation_player.stop_all();
+                let active = animation_player.play(node);
+                match mode {
+                    AnimationLoopMode::Once => {
+                        active.set_repeat(bevy::animation::RepeatAnimation::Never);
+                    }
+                    AnimationLoopMode::Repeat | AnimationLoopMode::PingPong => {
+                        active.repeat();
+                    }
+                }
+
+                let mut ping_pong = queue.ping_pong.lock().unwrap();
+                if mode == AnimationLoopMode::PingPong {
+                    ping_pong.insert(player, 0);
+                } else {
+                    ping_pong.remove(&player);
+                }
+            }
+        }
+    }
+
+    // Keep unloaded clips queued ahead of anything Lua queued while we were processing
+    let mut commands = queue.commands.lock().unwrap();
+    still_loading.append(&mut commands);
+    *commands = still_loading;
+}
+
+/// System that reverses ping-pong clips each time they reach an end
+/// Bevy wraps repeating clips back to the start, so on each completion the seek time is
+/// mirrored and the playback speed negated
+pub fn update_ping_pong_animations(
+    queue: Res<AnimationQueue>,
+    clips: Option<Res<Assets<AnimationClip>>>,
+    graphs: Option<Res<Assets<AnimationGraph>>>,
+    mut players: Query<(&mut AnimationPlayer, &AnimationGraphHandle)>,
+) {
+    // Headless apps without AnimationPlugin have nothing to drive
+    let (Some(clips), Some(graphs)) = (clips, graphs) else {
+        return;
+    };
+    let mut ping_pong = queue.ping_pong.lock().unwrap();
+    ping_pong.retain(|player, seen_completions| {
+        let Ok((mut animation_player, graph_handle)) = players.get_mut(*player) else {
+            return false;
+        };
+        let Some((&node, active)) = animation_player.playing_animations_mut().next() else {
+            return false;
+        };
+        if active.completions() <= *seen_completions {
+            return true;
+        }
+        *seen_completions = active.completions();
+
+        let duration = graphs
+            .get(&graph_handle.0)
+            .and_then(|graph| graph.get(node))
+            .and_then(|node| match &node.node_type {
+                bevy::animation::graph::AnimationNodeType::Clip(clip) => clips.get(clip),
+                _ => None,
+            })
+            .map(|clip| clip.duration());
+        if let Some(duration) = duration {
+            let mirrored = (duration - active.seek_time()).clamp(0.0, duration);
+            let speed = -active.speed();
+            active.set_speed(speed).seek_to(mirrored);
+        }
+        true
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loop_mode_names() {
+        assert_eq!(AnimationLoopMode::from_name("once"), Some(AnimationLoopMode::Once));
+        assert_eq!(AnimationLoopMode::from_name("repeat"), Some(AnimationLoopMode::Repeat));
+        assert_eq!(AnimationLoopMode::from_name("ping_pong"), Some(AnimationLoopMode::PingPong));
+        assert_eq!(AnimationLoopMode::from_name("bounce"), None);
+    }
+
+    #[test]
+    fn test_find_animation_player_searches_descendants() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let armature = world.spawn(ChildOf(root)).id();
+        let skeleton = world.spawn((AnimationPlayer::default(), ChildOf(armature))).id();
+        let bare = world.spawn_empty().id();
+
+        assert_eq!(find_animation_player(&world, root), Some(skeleton));
+        assert_eq!(find_animation_player(&world, skeleton), Some(skeleton));
+        assert_eq!(find_animation_player(&world, bare), None);
+        assert_eq!(animation_time(&world, skeleton), None);
+    }
+
+    #[test]
+    fn test_queue_replaces_pending_play_requests() {
+        let queue = AnimationQueue::default();
+        let player = World::new().spawn_empty().id();
+        queue.queue_play(player, Handle::default(), AnimationLoopMode::Once);
+        queue.queue_play(player, Handle::default(), AnimationLoopMode::Repeat);
+        {
+            let commands = queue.commands.lock().unwrap();
+            assert_eq!(commands.len(), 1);
+            assert!(matches!(commands[0], AnimationCommand::Play { mode: AnimationLoopMode::Repeat, .. }));
+        }
+
+        queue.queue_stop(player);
+        let commands = queue.commands.lock().unwrap();
+        assert_eq!(commands.len(), 1);
+        assert!(matches!(commands[0], AnimationCommand::Stop { .. }));
+    }
+}
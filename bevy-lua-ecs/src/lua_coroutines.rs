@@ -0,0 +1,371 @@
+//! Cooperative Lua coroutines scheduled by Bevy
+//!
+//! Lua scripts start a coroutine with `world:start_coroutine(fn)` and suspend it
+//! with `coroutine.yield(...)`. The yielded value tells the scheduler when the
+//! coroutine should be resumed:
+//!
+//! ```lua
+//! local handle = world:start_coroutine(function()
+//!     coroutine.yield("next_frame")            -- resume next frame
+//!     coroutine.yield("after_seconds", 2.0)    -- resume after 2 seconds of game time
+//!     coroutine.yield("frames", 10)            -- resume after 10 frames
+//!     coroutine.yield("until", function()      -- resume once the predicate returns true
+//!         return get_lua_resource("GameState").ready
+//!     end)
+//! end)
+//! world:cancel_coroutine(handle)
+//! ```
+//!
+//! Resumed coroutines receive the frame delta time (seconds) and a fresh `world` as the results
+//! of `coroutine.yield`. The `world` of the system that started the coroutine is only valid until
+//! the first yield, so re-bind it:
+//!
+//! ```lua
+//! world:start_coroutine(function()
+//!     local dt, world = coroutine.yield("after_seconds", 1.0)
+//!     world:spawn({ Explosion = {} })
+//! end)
+//! ```
+//!
+//! `until` predicates are called with the same fresh `world`.
+
+use bevy::prelude::*;
+use mlua::prelude::*;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Condition that must be met before a suspended coroutine is resumed
+pub enum ResumeCondition {
+    /// Resume on the next frame
+    NextFrame,
+    /// Resume after the given number of frames
+    Frames(u32),
+    /// Resume once the remaining game time (seconds) reaches zero
+    AfterSeconds(f32),
+    /// Resume once the Lua predicate function returns a truthy value
+    Until(Arc<LuaRegistryKey>),
+}
+
+impl ResumeCondition {
+    /// Interpret the values yielded by a coroutine as a resume condition
+    /// Unknown or missing values fall back to `NextFrame`
+    pub fn from_yield(lua: &Lua, values: LuaMultiValue) -> LuaResult<Self> {
+        let mut values = values.into_iter();
+        let kind = values.next().unwrap_or(LuaValue::Nil);
+        let arg = values.next().unwrap_or(LuaValue::Nil);
+
+        let condition = match kind {
+            LuaValue::Nil => ResumeCondition::NextFrame,
+            // coroutine.yield(2.0) is shorthand for after_seconds
+            LuaValue::Integer(i) => ResumeCondition::AfterSeconds(i as f32),
+            LuaValue::Number(n) => ResumeCondition::AfterSeconds(n as f32),
+            LuaValue::Function(f) => {
+                ResumeCondition::Until(Arc::new(lua.create_registry_value(f)?))
+            }
+            LuaValue::String(s) => match s.to_str()?.as_ref() {
+                "next_frame" => ResumeCondition::NextFrame,
+                "frames" => ResumeCondition::Frames(lua_number(&arg).unwrap_or(1.0).max(1.0) as u32),
+                "after_seconds" | "wait" => {
+                    ResumeCondition::AfterSeconds(lua_number(&arg).unwrap_or(0.0) as f32)
+                }
+                "until" => match arg {
+                    LuaValue::Function(f) => {
+                        ResumeCondition::Until(Arc::new(lua.create_registry_value(f)?))
+                    }
+                    other => {
+                        return Err(LuaError::RuntimeError(format!(
+                            "coroutine.yield(\"until\", fn) expects a function, got {}",
+                            other.type_name()
+                        )));
+                    }
+                },
+                other => {
+                    warn!("[COROUTINE] Unknown resume condition '{}', resuming next frame", other);
+                    ResumeCondition::NextFrame
+                }
+            },
+            other => {
+                warn!("[COROUTINE] Unsupported yield value {}, resuming next frame", other.type_name());
+                ResumeCondition::NextFrame
+            }
+        };
+
+        Ok(condition)
+    }
+}
+
+fn lua_number(value: &LuaValue) -> Option<f64> {
+    match value {
+        LuaValue::Integer(i) => Some(*i as f64),
+        LuaValue::Number(n) => Some(*n),
+        _ => None,
+    }
+}
+
+/// A suspended Lua coroutine waiting for its resume condition
+pub struct ScheduledCoroutine {
+    pub handle: u64,
+    pub thread_key: Arc<LuaRegistryKey>,
+    pub condition: ResumeCondition,
+    /// Lua state the coroutine was created in (0=primary, >=1=instanced)
+    pub state_id: usize,
+    /// Script instance that started the coroutine (for cleanup on reload/stop)
+    pub instance_id: u64,
+    /// Change tick of the last resume, for change detection in the coroutine's queries
+    pub last_run: Option<u32>,
+}
+
+/// Resource holding all suspended Lua coroutines
+#[derive(Resource, Clone)]
+pub struct LuaCoroutineScheduler {
+    coroutines: Arc<Mutex<Vec<ScheduledCoroutine>>>,
+    /// Handles taken out of `coroutines` while the advance system resumes them
+    advancing: Arc<Mutex<HashSet<u64>>>,
+    cancelled: Arc<Mutex<HashSet<u64>>>,
+    next_handle: Arc<AtomicU64>,
+}
+
+impl Default for LuaCoroutineScheduler {
+    fn default() -> Self {
+        Self {
+            coroutines: Arc::new(Mutex::new(Vec::new())),
+            advancing: Arc::new(Mutex::new(HashSet::new())),
+            cancelled: Arc::new(Mutex::new(HashSet::new())),
+            next_handle: Arc::new(AtomicU64::new(1)),
+        }
+    }
+}
+
+impl LuaCoroutineScheduler {
+    /// Create a coroutine from a Lua function, run it until its first yield and schedule it
+    /// Returns the coroutine handle (usable with `cancel`)
+    pub fn start(&self, lua: &Lua, func: LuaFunction) -> LuaResult<u64> {
+        let handle = self.next_handle.fetch_add(1, Ordering::SeqCst);
+        let state_id: usize = lua.globals().get("__LUA_STATE_ID__").unwrap_or(0);
+        let instance_id: u64 = lua.globals().get("__INSTANCE_ID__").unwrap_or(0);
+
+        let thread = lua.create_thread(func)?;
+        let yielded = thread.resume::<LuaMultiValue>(())?;
+
+        if thread.status() != LuaThreadStatus::Resumable {
+            // Finished without yielding - nothing to schedule
+            return Ok(handle);
+        }
+
+        let condition = ResumeCondition::from_yield(lua, yielded)?;
+        let thread_key = Arc::new(lua.create_registry_value(thread)?);
+        self.coroutines.lock().unwrap().push(ScheduledCoroutine {
+            handle,
+            thread_key,
+            condition,
+            state_id,
+            instance_id,
+            last_run: None,
+        });
+
+        debug!("[COROUTINE] Started coroutine {} (instance {})", handle, instance_id);
+        Ok(handle)
+    }
+
+    /// Cancel a coroutine by handle. Returns true if it was still scheduled.
+    pub fn cancel(&self, handle: u64) -> bool {
+        let mut coroutines = self.coroutines.lock().unwrap();
+        let before = coroutines.len();
+        coroutines.retain(|c| c.handle != handle);
+        let removed = coroutines.len() != before;
+        drop(coroutines);
+
+        // The coroutine may be mid-resume (taken out of the list) - remember the cancellation
+        if !removed && self.advancing.lock().unwrap().contains(&handle) {
+            self.cancelled.lock().unwrap().insert(handle);
+            return true;
+        }
+        removed
+    }
+
+    /// Cancel all coroutines started by a script instance
+    pub fn clear_instance_coroutines(&self, instance_id: u64) {
+        self.coroutines
+            .lock()
+            .unwrap()
+            .retain(|c| c.instance_id != instance_id);
+    }
+
+    /// Number of coroutines currently suspended
+    pub fn len(&self) -> usize {
+        self.coroutines.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Take all scheduled coroutines (used by the advance system)
+    fn take_all(&self) -> Vec<ScheduledCoroutine> {
+        let taken = std::mem::take(&mut *self.coroutines.lock().unwrap());
+        self.advancing
+            .lock()
+            .unwrap()
+            .extend(taken.iter().map(|coroutine| coroutine.handle));
+        taken
+    }
+
+    /// Whether a coroutine taken by `take_all` was cancelled since
+    fn is_cancelled(&self, handle: u64) -> bool {
+        self.cancelled.lock().unwrap().contains(&handle)
+    }
+
+    /// Put a coroutine back unless it was cancelled while being advanced
+    fn reschedule(&self, coroutine: ScheduledCoroutine) {
+        if self.is_cancelled(coroutine.handle) {
+            return;
+        }
+        self.coroutines.lock().unwrap().push(coroutine);
+    }
+
+    /// Forget the handles of the pass that just finished
+    fn finish_advance(&self) {
+        self.advancing.lock().unwrap().clear();
+        self.cancelled.lock().unwrap().clear();
+    }
+}
+
+/// System that resumes suspended Lua coroutines whose resume condition is met
+/// Each resume (and each `until` predicate) runs in a Lua world scope of its own, like a Lua
+/// system call, and gets that scope's `world`.
+pub fn advance_lua_coroutines(world: &mut World) {
+    let (Some(scheduler), Some(lua_ctx)) = (
+        world.get_resource::<LuaCoroutineScheduler>().cloned(),
+        world.get_resource::<crate::lua_integration::LuaScriptContext>().cloned(),
+    ) else {
+        return;
+    };
+    if scheduler.is_empty() {
+        return;
+    }
+
+    let delta = world.get_resource::<Time>().map(|time| time.delta_secs()).unwrap_or(0.0);
+    let this_run = world.read_change_tick().get();
+    let world: &World = world;
+
+    for mut scheduled in scheduler.take_all() {
+        if scheduler.is_cancelled(scheduled.handle) {
+            continue;
+        }
+        let lua = lua_ctx.get_lua_state(scheduled.state_id);
+        let last_run = scheduled.last_run.unwrap_or(this_run);
+
+        // Run in the script context the coroutine was started from
+        let globals = lua.globals();
+        let previous_instance_id: Option<u64> = globals.get("__INSTANCE_ID__").ok();
+        let previous_phase: Option<String> = globals.get("__SPAWN_PHASE__").ok();
+        let _ = globals.set("__INSTANCE_ID__", scheduled.instance_id);
+        let _ = globals.set("__SPAWN_PHASE__", "runtime");
+
+        let outcome = crate::lua_systems::with_lua_world_scope(&lua, world, last_run, this_run, |world_ud| {
+            // Check whether this coroutine is ready to resume
+            let ready = match &mut scheduled.condition {
+                ResumeCondition::NextFrame => true,
+                ResumeCondition::Frames(remaining) => {
+                    *remaining = remaining.saturating_sub(1);
+                    *remaining == 0
+                }
+                ResumeCondition::AfterSeconds(remaining) => {
+                    *remaining -= delta;
+                    *remaining <= 0.0
+                }
+                ResumeCondition::Until(predicate_key) => lua
+                    .registry_value::<LuaFunction>(predicate_key)?
+                    .call::<bool>(world_ud.clone())
+                    .map_err(|e| LuaError::RuntimeError(format!("until() predicate failed: {}", e)))?,
+            };
+            if !ready {
+                return Ok(None);
+            }
+
+            let thread = lua.registry_value::<LuaThread>(&scheduled.thread_key)?;
+            let yielded = thread.resume::<LuaMultiValue>((delta, world_ud.clone()))?;
+            Ok(Some((thread.status(), yielded)))
+        });
+
+        if let Some(prev_id) = previous_instance_id {
+            let _ = globals.set("__INSTANCE_ID__", prev_id);
+        }
+        if let Some(prev_phase) = previous_phase {
+            let _ = globals.set("__SPAWN_PHASE__", prev_phase);
+        }
+
+        match outcome {
+            // Condition not met yet
+            Ok(None) => scheduler.reschedule(scheduled),
+            Ok(Some((LuaThreadStatus::Resumable, yielded))) => {
+                match ResumeCondition::from_yield(&lua, yielded) {
+                    Ok(condition) => {
+                        scheduled.condition = condition;
+                        scheduled.last_run = Some(this_run);
+                        scheduler.reschedule(scheduled);
+                    }
+                    Err(e) => error!("[COROUTINE] Coroutine {} yielded invalid condition: {}", scheduled.handle, e),
+                }
+            }
+            Ok(Some(_)) => debug!("[COROUTINE] Coroutine {} finished", scheduled.handle),
+            Err(e) => error!("[COROUTINE] Coroutine {} failed and was removed: {}", scheduled.handle, e),
+        }
+    }
+    scheduler.finish_advance();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(lua: &Lua, chunk: &str) -> ResumeCondition {
+        let values: LuaMultiValue = lua.load(chunk).eval().unwrap();
+        ResumeCondition::from_yield(lua, values).unwrap()
+    }
+
+    #[test]
+    fn test_from_yield_conditions() {
+        let lua = Lua::new();
+
+        assert!(matches!(parse(&lua, "return nil"), ResumeCondition::NextFrame));
+        assert!(matches!(parse(&lua, "return 'next_frame'"), ResumeCondition::NextFrame));
+        assert!(matches!(parse(&lua, "return 'frames', 3"), ResumeCondition::Frames(3)));
+        assert!(matches!(parse(&lua, "return 'after_seconds', 1.5"), ResumeCondition::AfterSeconds(s) if s == 1.5));
+        assert!(matches!(parse(&lua, "return 2"), ResumeCondition::AfterSeconds(s) if s == 2.0));
+        assert!(matches!(parse(&lua, "return 'until', function() return true end"), ResumeCondition::Until(_)));
+    }
+
+    #[test]
+    fn test_until_requires_function() {
+        let lua = Lua::new();
+        let values: LuaMultiValue = lua.load("return 'until', 5").eval().unwrap();
+        assert!(ResumeCondition::from_yield(&lua, values).is_err());
+    }
+
+    #[test]
+    fn test_start_and_cancel() {
+        let lua = Lua::new();
+        let scheduler = LuaCoroutineScheduler::default();
+
+        // Finishes without yielding - never scheduled
+        let func: LuaFunction = lua.load("return function() end").eval().unwrap();
+        scheduler.start(&lua, func).unwrap();
+        assert!(scheduler.is_empty());
+
+        let func: LuaFunction = lua
+            .load("return function() coroutine.yield('next_frame') end")
+            .eval()
+            .unwrap();
+        let handle = scheduler.start(&lua, func).unwrap();
+        assert_eq!(scheduler.len(), 1);
+
+        assert!(scheduler.cancel(handle));
+        assert!(scheduler.is_empty());
+
+        // Unknown handles are not remembered
+        assert!(!scheduler.cancel(handle));
+        assert!(scheduler.cancelled.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,50 @@
+//! Console/editor support for evaluating Lua expressions at runtime
+//!
+//! Tools write an `EvalLuaExpression` message; the result is delivered as a
+//! `LuaExpressionResult` message on the same frame.
+//!
+//! ```ignore
+//! fn console(mut eval: MessageWriter<EvalLuaExpression>) {
+//!     eval.write(EvalLuaExpression { expr: "get_lua_resource('GameState').score".into() });
+//! }
+//! ```
+
+use bevy::prelude::*;
+
+/// Request to evaluate a Lua expression (see `LuaScriptContext::eval_expression`)
+#[derive(Message, Clone, Debug)]
+pub struct EvalLuaExpression {
+    pub expr: String,
+}
+
+/// Result of an `EvalLuaExpression` request
+#[derive(Message, Clone, Debug)]
+pub struct LuaExpressionResult {
+    pub expr: String,
+    /// JSON value of the expression, or the Lua error message
+    pub result: Result<serde_json::Value, String>,
+}
+
+/// System that evaluates pending `EvalLuaExpression` messages
+pub fn process_eval_requests(
+    mut requests: MessageReader<EvalLuaExpression>,
+    mut results: MessageWriter<LuaExpressionResult>,
+    lua_ctx: Option<Res<crate::lua_integration::LuaScriptContext>>,
+) {
+    let Some(lua_ctx) = lua_ctx else {
+        return;
+    };
+
+    for request in requests.read() {
+        let result = lua_ctx
+            .eval_expression(&request.expr)
+            .map_err(|e| e.to_string());
+        if let Err(e) = &result {
+            debug!("[EVAL] '{}' failed: {}", request.expr, e);
+        }
+        results.write(LuaExpressionResult {
+            expr: request.expr.clone(),
+            result,
+        });
+    }
+}
@@ -1,22 +1,294 @@
 use bevy::prelude::*;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Mutex;
 use std::time::Duration;
 
 /// Message emitted when a Lua script file changes
 #[derive(Message, Clone, Debug, Reflect)]
 #[reflect(Debug)]
 pub struct LuaFileChangeEvent {
+    /// Path relative to `assets/` with forward slashes, as `AssetServer` expects
+    /// (e.g. "scripts/examples/physics.lua"); see `path_utils::to_asset_server_path`
     pub path: PathBuf,
 }
 
+/// Default quiet period before a changed file is reported
+pub const DEFAULT_DEBOUNCE_MS: u64 = 100;
+
 /// Plugin that watches Lua script files for changes
-pub struct LuaFileWatcherPlugin;
+pub struct LuaFileWatcherPlugin {
+    /// Only re-define changed top-level functions on reload instead of re-executing the whole script
+    /// (falls back to a full reload when top-level code changes or a changed function uses a
+    /// file-level `local`). See `LuaHotReloadConfig`.
+    pub hot_reload_diff: bool,
+    /// A change is reported once no further changes to the file arrived for this long,
+    /// so an editor's swap-file/rename/write sequence triggers a single reload
+    pub debounce_ms: u64,
+}
+
+impl Default for LuaFileWatcherPlugin {
+    fn default() -> Self {
+        Self {
+            hot_reload_diff: false,
+            debounce_ms: DEFAULT_DEBOUNCE_MS,
+        }
+    }
+}
+
+/// Hot-reload behavior settings
+/// Insert before adding `LuaSpawnPlugin` to override the plugin defaults
+#[derive(Resource, Clone, Debug)]
+pub struct LuaHotReloadConfig {
+    /// Re-define only changed functions when a script's top-level code is unchanged
+    /// Globals keep their values and init()/setup() calls are not re-run
+    pub hot_reload_diff: bool,
+    /// Debounce window for file changes (see `LuaFileWatcherPlugin::debounce_ms`)
+    pub debounce_ms: u64,
+}
+
+impl Default for LuaHotReloadConfig {
+    fn default() -> Self {
+        Self {
+            hot_reload_diff: false,
+            debounce_ms: DEFAULT_DEBOUNCE_MS,
+        }
+    }
+}
 
 impl Plugin for LuaFileWatcherPlugin {
     fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<LuaHotReloadConfig>() {
+            app.insert_resource(LuaHotReloadConfig {
+                hot_reload_diff: self.hot_reload_diff,
+                debounce_ms: self.debounce_ms,
+            });
+        }
+        let debounce_ms = app.world().resource::<LuaHotReloadConfig>().debounce_ms;
         app.add_message::<LuaFileChangeEvent>();
+        app.insert_resource(LuaDependencyWatcher::new());
+        app.insert_resource(LuaFileChangeDebouncer::new(Duration::from_millis(debounce_ms)));
         app.add_systems(Startup, setup_file_watcher);
-        app.add_systems(Update, poll_file_changes);
+        app.add_systems(
+            Update,
+            (
+                sync_dependency_watches,
+                poll_dependency_changes,
+                poll_file_changes,
+                flush_debounced_changes,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Holds detected file changes back until the file has been quiet for the debounce window
+#[derive(Resource, Debug)]
+pub struct LuaFileChangeDebouncer {
+    window: Duration,
+    /// Path -> time of the most recent change
+    last_change: HashMap<PathBuf, std::time::Instant>,
+}
+
+impl LuaFileChangeDebouncer {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            last_change: HashMap::new(),
+        }
+    }
+
+    /// Record a change, re-arming the window if the path is already pending
+    pub fn note_change(&mut self, path: PathBuf, now: std::time::Instant) {
+        self.last_change.insert(path, now);
+    }
+
+    /// Remove and return the paths that have been quiet for the whole window
+    pub fn take_settled(&mut self, now: std::time::Instant) -> Vec<PathBuf> {
+        let window = self.window;
+        let mut settled = Vec::new();
+        self.last_change.retain(|path, last_change| {
+            if now.duration_since(*last_change) < window {
+                return true;
+            }
+            settled.push(path.clone());
+            false
+        });
+        settled.sort();
+        settled
+    }
+
+    pub fn pending_len(&self) -> usize {
+        self.last_change.len()
+    }
+}
+
+/// Report changes that have settled as LuaFileChangeEvents
+fn flush_debounced_changes(
+    mut debouncer: ResMut<LuaFileChangeDebouncer>,
+    mut events: MessageWriter<LuaFileChangeEvent>,
+) {
+    if debouncer.pending_len() == 0 {
+        return;
+    }
+    for path in debouncer.take_settled(std::time::Instant::now()) {
+        debug!("Lua file change settled: {:?}", path);
+        events.write(LuaFileChangeEvent {
+            path: PathBuf::from(crate::path_utils::to_asset_server_path(&path)),
+        });
+    }
+}
+
+/// Watches the modules each running script `require`s so edits to them reload the script
+/// without waiting for the next full poll of assets/
+///
+/// Dependencies come from the require graph recorded in `ScriptCache`; the watch set is
+/// refreshed as scripts start, stop and require new modules.
+#[derive(Resource)]
+pub struct LuaDependencyWatcher {
+    watcher: Option<RecommendedWatcher>,
+    rx: Mutex<Receiver<notify::Result<notify::Event>>>,
+    /// Script instance -> transitively required module paths (e.g. "assets/scripts/utils.lua")
+    dependencies: HashMap<u64, Vec<PathBuf>>,
+    /// Canonical watched path -> assets-relative path reported in LuaFileChangeEvent
+    watched: HashMap<PathBuf, PathBuf>,
+    sync_interval: Duration,
+    last_sync: std::time::Instant,
+}
+
+impl LuaDependencyWatcher {
+    fn new() -> Self {
+        let (tx, rx) = channel();
+        let watcher = notify::recommended_watcher(tx).ok();
+        if watcher.is_none() {
+            warn!("Lua dependency watcher failed to initialize, relying on polling");
+        }
+        Self {
+            watcher,
+            rx: Mutex::new(rx),
+            dependencies: HashMap::new(),
+            watched: HashMap::new(),
+            sync_interval: Duration::from_secs(1),
+            last_sync: std::time::Instant::now(),
+        }
+    }
+
+    /// Module files a script instance currently depends on
+    pub fn dependencies_of(&self, instance_id: u64) -> &[PathBuf] {
+        self.dependencies
+            .get(&instance_id)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Watch exactly `paths`, unwatching files no script depends on anymore
+    fn update_watches(&mut self, paths: HashSet<PathBuf>) {
+        let Some(watcher) = self.watcher.as_mut() else {
+            return;
+        };
+
+        self.watched.retain(|canonical, relative| {
+            if paths.contains(relative) {
+                return true;
+            }
+            let _ = watcher.unwatch(canonical);
+            debug!("Stopped watching Lua dependency: {:?}", relative);
+            false
+        });
+
+        for relative in paths {
+            let Ok(canonical) = relative.canonicalize() else {
+                continue; // Not on disk yet (e.g. still downloading)
+            };
+            if self.watched.contains_key(&canonical) {
+                continue;
+            }
+            match watcher.watch(&canonical, RecursiveMode::NonRecursive) {
+                Ok(()) => {
+                    debug!("Watching Lua dependency: {:?}", relative);
+                    self.watched.insert(canonical, relative);
+                }
+                Err(e) => warn!("Failed to watch Lua dependency {:?}: {}", relative, e),
+            }
+        }
+    }
+}
+
+/// Rebuild the script -> dependencies map from the require graph and update the watch set
+fn sync_dependency_watches(
+    mut dep_watcher: ResMut<LuaDependencyWatcher>,
+    lua_ctx: Option<Res<crate::LuaScriptContext>>,
+    script_registry: Option<Res<crate::script_registry::ScriptRegistry>>,
+) {
+    let (Some(lua_ctx), Some(script_registry)) = (lua_ctx, script_registry) else {
+        return;
+    };
+    let now = std::time::Instant::now();
+    if now.duration_since(dep_watcher.last_sync) < dep_watcher.sync_interval {
+        return;
+    }
+    dep_watcher.last_sync = now;
+
+    let mut dependencies = HashMap::new();
+    let mut all_paths = HashSet::new();
+    for instance_id in script_registry.all_active_instance_ids() {
+        let Some(script_path) = script_registry.get_instance_path(instance_id) else {
+            continue;
+        };
+        let script_path = crate::path_utils::to_forward_slash(&script_path);
+        let module_path = script_path.strip_prefix("assets/").unwrap_or(&script_path);
+
+        let deps: Vec<PathBuf> = lua_ctx
+            .script_cache
+            .get_dependencies(module_path)
+            .into_iter()
+            .map(|dep| Path::new("assets").join(dep))
+            .collect();
+        all_paths.extend(deps.iter().cloned());
+        dependencies.insert(instance_id, deps);
+    }
+
+    dep_watcher.dependencies = dependencies;
+    dep_watcher.update_watches(all_paths);
+}
+
+/// Forward dependency file changes reported by notify to the debouncer
+fn poll_dependency_changes(
+    dep_watcher: Res<LuaDependencyWatcher>,
+    mut state: Option<ResMut<FileWatcherState>>,
+    mut debouncer: ResMut<LuaFileChangeDebouncer>,
+) {
+    let mut changed = HashSet::new();
+    {
+        let rx = dep_watcher.rx.lock().unwrap();
+        while let Ok(result) = rx.try_recv() {
+            match result {
+                Ok(event) if matches!(event.kind, notify::EventKind::Modify(_)) => {
+                    for path in event.paths {
+                        if let Some(relative) = dep_watcher.watched.get(&path) {
+                            changed.insert(relative.clone());
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Lua dependency watcher error: {}", e),
+            }
+        }
+    }
+
+    let now = std::time::Instant::now();
+    for path in changed {
+        debug!("Detected change in Lua dependency: {:?}", path);
+        // Record the new mtime so the periodic assets/ scan doesn't report it again
+        if let Some(state) = state.as_mut() {
+            if let Ok(modified) = std::fs::metadata(&path).and_then(|m| m.modified()) {
+                state.last_modified.insert(path.clone(), modified);
+            }
+        }
+        debouncer.note_change(path, now);
     }
 }
 
@@ -50,7 +322,7 @@ fn setup_file_watcher(mut commands: Commands) {
 
 fn poll_file_changes(
     mut state: ResMut<FileWatcherState>,
-    mut events: MessageWriter<LuaFileChangeEvent>,
+    mut debouncer: ResMut<LuaFileChangeDebouncer>,
 ) {
     // Rate limit: only poll filesystem once per poll_interval
     let now = std::time::Instant::now();
@@ -67,14 +339,14 @@ fn poll_file_changes(
     }
 
     // Recursively walk the assets directory for .lua files
-    visit_lua_files(script_dir, &mut state, &mut events);
+    visit_lua_files(script_dir, &mut state, &mut debouncer);
 }
 
 /// Recursively visit all .lua files in a directory
 fn visit_lua_files(
     dir: &Path,
     state: &mut FileWatcherState,
-    events: &mut MessageWriter<LuaFileChangeEvent>,
+    debouncer: &mut LuaFileChangeDebouncer,
 ) {
     if let Ok(entries) = std::fs::read_dir(dir) {
         for entry in entries.flatten() {
@@ -82,7 +354,7 @@ fn visit_lua_files(
 
             // Recursively check subdirectories
             if path.is_dir() {
-                visit_lua_files(&path, state, events);
+                visit_lua_files(&path, state, debouncer);
                 continue;
             }
 
@@ -101,7 +373,7 @@ fn visit_lua_files(
                             if let Ok(duration) = modified.duration_since(*last_mod) {
                                 if duration >= state.debounce_duration {
                                     debug!("Detected change in Lua script: {:?}", path);
-                                    events.write(LuaFileChangeEvent { path: path.clone() });
+                                    debouncer.note_change(path.clone(), std::time::Instant::now());
                                     state.last_modified.insert(path.clone(), modified);
                                 }
                             }
@@ -115,3 +387,476 @@ fn visit_lua_files(
         }
     }
 }
+
+/// Name of the chunk holding all top-level code that is not a function definition
+pub const TOPLEVEL_CHUNK: &str = "__toplevel__";
+
+/// A top-level region of a Lua script, used for hot-reload diffing
+#[derive(Debug, Clone, PartialEq)]
+pub struct LuaChunk {
+    /// Function name (e.g. "update", "Player.move", "local helper") or TOPLEVEL_CHUNK
+    pub name: String,
+    pub source: String,
+    /// `local function` definitions can't be re-defined in place (closures capture them)
+    pub is_local: bool,
+}
+
+/// What a diff-based hot reload needs to do
+#[derive(Debug, PartialEq)]
+pub enum HotReloadPlan {
+    /// Nothing changed
+    Unchanged,
+    /// Only these function chunks changed or were added - re-define them
+    Functions(Vec<LuaChunk>),
+    /// Top-level code changed, a function was removed/local, or a changed function uses a
+    /// file-level local - re-execute the whole script
+    Full,
+}
+
+/// Split a Lua script into top-level function definitions and the remaining top-level code
+///
+/// Uses a lightweight token scan (skipping strings and comments) that tracks block depth via
+/// `function`/`if`/`do`/`repeat` and `end`/`until`. Only named function statements at depth 0
+/// become their own chunk; everything else is collected into the TOPLEVEL_CHUNK.
+pub fn split_lua_chunks(source: &str) -> Vec<LuaChunk> {
+    let bytes = source.as_bytes();
+    let mut chunks = Vec::new();
+    let mut toplevel = String::new();
+    let mut name_counts: HashMap<String, usize> = HashMap::new();
+
+    let mut depth: i32 = 0;
+    let mut i = 0;
+    let mut last_end = 0;
+    // Start index of a `local` token directly preceding the current position (depth 0 only)
+    let mut pending_local: Option<usize> = None;
+    // (chunk start, name, is_local) of the function currently being scanned
+    let mut current: Option<(usize, String, bool)> = None;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+
+        // Comments
+        if c == b'-' && bytes.get(i + 1) == Some(&b'-') {
+            i += 2;
+            if let Some(close) = long_bracket_level(bytes, i) {
+                i = skip_long_bracket(bytes, i, close);
+            } else {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            continue;
+        }
+
+        // Strings
+        if c == b'"' || c == b'\'' {
+            i += 1;
+            while i < bytes.len() && bytes[i] != c && bytes[i] != b'\n' {
+                if bytes[i] == b'\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i += 1;
+            pending_local = None;
+            continue;
+        }
+        if c == b'[' {
+            if let Some(level) = long_bracket_level(bytes, i) {
+                i = skip_long_bracket(bytes, i, level);
+                pending_local = None;
+                continue;
+            }
+        }
+
+        // Identifiers / keywords
+        if c.is_ascii_alphabetic() || c == b'_' {
+            let start = i;
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            // Field access like `t.end` is not a keyword
+            let is_field = start > 0 && (bytes[start - 1] == b'.' || bytes[start - 1] == b':')
+                && !(start > 1 && bytes[start - 2] == b'.');
+            let word = &source[start..i];
+
+            if is_field {
+                pending_local = None;
+                continue;
+            }
+
+            match word {
+                "local" if depth == 0 && current.is_none() => {
+                    pending_local = Some(start);
+                    continue;
+                }
+                "function" => {
+                    if depth == 0 && current.is_none() {
+                        if let Some(name) = function_statement_name(source, i) {
+                            let is_local = pending_local.is_some();
+                            let chunk_start = line_start(bytes, pending_local.unwrap_or(start));
+                            let chunk_start = chunk_start.max(last_end);
+                            toplevel.push_str(&source[last_end..chunk_start]);
+                            last_end = chunk_start;
+                            let name = if is_local { format!("local {}", name) } else { name };
+                            current = Some((chunk_start, name, is_local));
+                        }
+                    }
+                    depth += 1;
+                }
+                "if" | "do" | "repeat" => depth += 1,
+                "end" | "until" => {
+                    depth -= 1;
+                    if depth == 0 {
+                        if let Some((chunk_start, name, is_local)) = current.take() {
+                            // Include the rest of the line (e.g. trailing comments)
+                            let mut chunk_end = i;
+                            while chunk_end < bytes.len() && bytes[chunk_end] != b'\n' {
+                                chunk_end += 1;
+                            }
+                            let count = name_counts.entry(name.clone()).or_insert(0);
+                            *count += 1;
+                            let name = if *count > 1 { format!("{}#{}", name, count) } else { name };
+                            chunks.push(LuaChunk {
+                                name,
+                                source: source[chunk_start..chunk_end].to_string(),
+                                is_local,
+                            });
+                            last_end = chunk_end;
+                            i = chunk_end;
+                        }
+                    }
+                }
+                _ => {}
+            }
+            pending_local = None;
+            continue;
+        }
+
+        if !c.is_ascii_whitespace() {
+            pending_local = None;
+        }
+        i += 1;
+    }
+
+    // Remaining code (including an unterminated function, so a full reload reports the error)
+    toplevel.push_str(&source[last_end..]);
+
+    chunks.push(LuaChunk {
+        name: TOPLEVEL_CHUNK.to_string(),
+        source: toplevel,
+        is_local: false,
+    });
+    chunks
+}
+
+/// Hash each chunk's source (whitespace-only line changes are ignored)
+pub fn chunk_hashes(chunks: &[LuaChunk]) -> HashMap<String, u64> {
+    chunks
+        .iter()
+        .map(|chunk| {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            for line in chunk.source.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                line.hash(&mut hasher);
+            }
+            (chunk.name.clone(), hasher.finish())
+        })
+        .collect()
+}
+
+/// Decide how to hot-reload a script given the chunk hashes of the previously loaded version
+pub fn plan_diff_reload(old_hashes: &HashMap<String, u64>, new_chunks: &[LuaChunk]) -> HotReloadPlan {
+    let new_hashes = chunk_hashes(new_chunks);
+
+    // Top-level code changed or a function was removed - only a full reload is correct
+    if old_hashes.get(TOPLEVEL_CHUNK) != new_hashes.get(TOPLEVEL_CHUNK) {
+        return HotReloadPlan::Full;
+    }
+    if old_hashes.keys().any(|name| !new_hashes.contains_key(name)) {
+        return HotReloadPlan::Full;
+    }
+
+    let changed: Vec<LuaChunk> = new_chunks
+        .iter()
+        .filter(|chunk| old_hashes.get(&chunk.name) != new_hashes.get(&chunk.name))
+        .cloned()
+        .collect();
+
+    if changed.is_empty() {
+        return HotReloadPlan::Unchanged;
+    }
+    if changed.iter().any(|chunk| chunk.is_local) {
+        return HotReloadPlan::Full;
+    }
+
+    // A re-defined function is compiled on its own, so names of file-level locals would resolve
+    // to globals instead of the old upvalues - only a full reload keeps those bindings
+    let file_locals = file_local_names(new_chunks);
+    if changed
+        .iter()
+        .any(|chunk| lua_identifiers(&chunk.source).any(|word| file_locals.contains(word)))
+    {
+        return HotReloadPlan::Full;
+    }
+    HotReloadPlan::Functions(changed)
+}
+
+/// Names declared by `local` statements and `local function`s at the top level of a script
+pub fn file_local_names(chunks: &[LuaChunk]) -> HashSet<String> {
+    let mut names: HashSet<String> = chunks
+        .iter()
+        .filter(|chunk| chunk.is_local)
+        .filter_map(|chunk| chunk.name.strip_prefix("local "))
+        .map(|name| name.split('#').next().unwrap_or(name).to_string())
+        .collect();
+
+    let Some(toplevel) = chunks.iter().find(|chunk| chunk.name == TOPLEVEL_CHUNK) else {
+        return names;
+    };
+    let mut depth: i32 = 0;
+    for (start, word, is_field) in lua_words(&toplevel.source) {
+        if is_field {
+            continue;
+        }
+        match word {
+            "function" | "if" | "do" | "repeat" => depth += 1,
+            "end" | "until" => depth -= 1,
+            "local" if depth == 0 => {
+                // `local a, b = ...` - the identifiers before `=` (lua_words skips punctuation)
+                let rest = &toplevel.source[start + word.len()..];
+                let declaration = rest.split(['=', '\n', ';']).next().unwrap_or("");
+                for name in declaration.split(',') {
+                    let name = name.split('<').next().unwrap_or("").trim();
+                    if !name.is_empty() && name != "function" {
+                        names.insert(name.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Identifiers in `source` that are not field names (`t.x`, `obj:method`)
+fn lua_identifiers(source: &str) -> impl Iterator<Item = &str> {
+    lua_words(source).filter(|(_, _, is_field)| !is_field).map(|(_, word, _)| word)
+}
+
+/// Words (identifiers and keywords) of a Lua source, skipping strings and comments
+/// Yields each word's byte offset, the word, and whether it follows `.` or `:` (a field access).
+fn lua_words(source: &str) -> impl Iterator<Item = (usize, &str, bool)> {
+    let bytes = source.as_bytes();
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            let c = bytes[i];
+            if c == b'-' && bytes.get(i + 1) == Some(&b'-') {
+                i += 2;
+                if let Some(close) = long_bracket_level(bytes, i) {
+                    i = skip_long_bracket(bytes, i, close);
+                } else {
+                    while i < bytes.len() && bytes[i] != b'\n' {
+                        i += 1;
+                    }
+                }
+                continue;
+            }
+            if c == b'"' || c == b'\'' {
+                i += 1;
+                while i < bytes.len() && bytes[i] != c && bytes[i] != b'\n' {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                i += 1;
+                continue;
+            }
+            if c == b'[' {
+                if let Some(level) = long_bracket_level(bytes, i) {
+                    i = skip_long_bracket(bytes, i, level);
+                    continue;
+                }
+            }
+            if c.is_ascii_alphabetic() || c == b'_' {
+                let start = i;
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+                let is_field = start > 0
+                    && (bytes[start - 1] == b'.' || bytes[start - 1] == b':')
+                    && !(start > 1 && bytes[start - 2] == b'.');
+                return Some((start, &source[start..i], is_field));
+            }
+            // Skip numbers so `1e5` / `0x1f` don't yield identifiers
+            if c.is_ascii_digit() {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'.') {
+                    i += 1;
+                }
+                continue;
+            }
+            i += 1;
+        }
+        None
+    })
+}
+
+/// If `function` at `after` starts a named function statement, return its name
+fn function_statement_name(source: &str, after: usize) -> Option<String> {
+    let rest = source[after..].trim_start();
+    let name: String = rest
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '.' || *c == ':')
+        .collect();
+    if name.is_empty() || !rest[name.len()..].trim_start().starts_with('(') {
+        return None;
+    }
+    Some(name)
+}
+
+/// Start of the line containing `index`
+fn line_start(bytes: &[u8], index: usize) -> usize {
+    bytes[..index]
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map(|pos| pos + 1)
+        .unwrap_or(0)
+}
+
+/// If a long bracket (`[[`, `[==[`) starts at `i`, return its level
+fn long_bracket_level(bytes: &[u8], i: usize) -> Option<usize> {
+    if bytes.get(i) != Some(&b'[') {
+        return None;
+    }
+    let mut j = i + 1;
+    while bytes.get(j) == Some(&b'=') {
+        j += 1;
+    }
+    (bytes.get(j) == Some(&b'[')).then_some(j - i - 1)
+}
+
+/// Skip past a long bracket string/comment starting at `i` with the given level
+fn skip_long_bracket(bytes: &[u8], i: usize, level: usize) -> usize {
+    let mut close = Vec::with_capacity(level + 2);
+    close.push(b']');
+    close.extend(std::iter::repeat(b'=').take(level));
+    close.push(b']');
+
+    let body_start = i + level + 2;
+    bytes[body_start.min(bytes.len())..]
+        .windows(close.len())
+        .position(|w| w == close.as_slice())
+        .map(|pos| body_start + pos + close.len())
+        .unwrap_or(bytes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = r#"
+local counter = 0
+
+function update(dt)
+    if counter > 10 then
+        counter = 0
+    end
+    for i = 1, 3 do counter = counter + i end
+end
+
+local function helper()
+    return "end" -- not a keyword
+end
+
+--[[ function commented() end ]]
+init()
+"#;
+
+    #[test]
+    fn test_debouncer_waits_for_quiet_window() {
+        let mut debouncer = LuaFileChangeDebouncer::new(Duration::from_millis(100));
+        let start = std::time::Instant::now();
+        let path = PathBuf::from("assets/scripts/game.lua");
+
+        debouncer.note_change(path.clone(), start);
+        assert!(debouncer.take_settled(start + Duration::from_millis(50)).is_empty());
+
+        // Another write re-arms the window
+        debouncer.note_change(path.clone(), start + Duration::from_millis(80));
+        assert!(debouncer.take_settled(start + Duration::from_millis(150)).is_empty());
+        assert_eq!(debouncer.take_settled(start + Duration::from_millis(180)), vec![path]);
+        assert_eq!(debouncer.pending_len(), 0);
+    }
+
+    #[test]
+    fn test_settled_changes_report_asset_server_paths() {
+        let mut app = App::new();
+        app.add_message::<LuaFileChangeEvent>();
+        let mut debouncer = LuaFileChangeDebouncer::new(Duration::ZERO);
+        debouncer.note_change(Path::new("assets").join("scripts").join("game.lua"), std::time::Instant::now());
+        app.insert_resource(debouncer);
+        app.add_systems(Update, flush_debounced_changes);
+        app.update();
+
+        let messages = app.world().resource::<Messages<LuaFileChangeEvent>>();
+        let paths: Vec<PathBuf> = messages.iter_current_update_messages().map(|event| event.path.clone()).collect();
+        assert_eq!(paths, vec![PathBuf::from("scripts/game.lua")]);
+    }
+
+    #[test]
+    fn test_split_lua_chunks() {
+        let chunks = split_lua_chunks(SCRIPT);
+        let names: Vec<&str> = chunks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["update", "local helper", TOPLEVEL_CHUNK]);
+
+        let toplevel = &chunks[2].source;
+        assert!(toplevel.contains("local counter = 0"));
+        assert!(toplevel.contains("init()"));
+        assert!(!toplevel.contains("function update"));
+        assert!(chunks[1].is_local);
+
+        let locals = file_local_names(&chunks);
+        assert!(locals.contains("counter"));
+        assert!(locals.contains("helper"));
+        assert!(!locals.contains("i"), "loop variables are not file-level locals");
+    }
+
+    #[test]
+    fn test_plan_diff_reload() {
+        let old = chunk_hashes(&split_lua_chunks(SCRIPT));
+
+        // Unchanged (whitespace only)
+        let same = SCRIPT.replace("counter = 0\n    end", "counter = 0\n\n    end");
+        assert_eq!(plan_diff_reload(&old, &split_lua_chunks(&same)), HotReloadPlan::Unchanged);
+
+        // `update` captures the file-level local `counter`; re-defining it alone would rebind
+        // `counter` to a global
+        let changed = SCRIPT.replace("counter > 10", "counter > 20");
+        assert_eq!(plan_diff_reload(&old, &split_lua_chunks(&changed)), HotReloadPlan::Full);
+
+        // A function that only uses its arguments and globals is re-defined in place
+        let with_draw = format!("{}\nfunction draw(dt)\n    render(dt)\nend\n", SCRIPT);
+        let old_draw = chunk_hashes(&split_lua_chunks(&with_draw));
+        let changed_draw = with_draw.replace("render(dt)", "render(dt * 2)");
+        match plan_diff_reload(&old_draw, &split_lua_chunks(&changed_draw)) {
+            HotReloadPlan::Functions(chunks) => {
+                assert_eq!(chunks.len(), 1);
+                assert_eq!(chunks[0].name, "draw");
+            }
+            other => panic!("Expected Functions plan, got {:?}", other),
+        }
+
+        // Calling a `local function` captures it as an upvalue too
+        let calls_helper = with_draw.replace("render(dt)", "render(helper())");
+        assert_eq!(plan_diff_reload(&old_draw, &split_lua_chunks(&calls_helper)), HotReloadPlan::Full);
+
+        // Top-level code changed
+        let toplevel = SCRIPT.replace("local counter = 0", "local counter = 5");
+        assert_eq!(plan_diff_reload(&old, &split_lua_chunks(&toplevel)), HotReloadPlan::Full);
+
+        // Local function changed
+        let local = SCRIPT.replace("return \"end\"", "return \"done\"");
+        assert_eq!(plan_diff_reload(&old, &split_lua_chunks(&local)), HotReloadPlan::Full);
+    }
+}
@@ -5,6 +5,7 @@
 
 use bevy::prelude::*;
 use std::time::{Duration, Instant};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
 /// Resource controlling the maximum time Lua systems can run per frame
@@ -119,8 +120,8 @@ impl LuaSystemProgress {
             }
             inner.last_frame = current_frame;
             inner.time_this_frame = Duration::ZERO;
-            // NOTE: We don't reset next_index - we continue from where we left off
-            // This ensures fairness: deferred systems get to run first next frame
+            // NOTE: We don't reset next_index - deferred systems run first next frame,
+            // followed by the rest of the execution order (never wrapping around mid-frame)
         }
     }
     
@@ -129,6 +130,11 @@ impl LuaSystemProgress {
         self.inner.lock().unwrap().next_index
     }
     
+    /// Set the index of the next system to run (e.g. back to 0 after systems were removed)
+    pub fn resume_at(&self, index: usize) {
+        self.inner.lock().unwrap().next_index = index;
+    }
+
     /// Advance to the next system, wrapping around at total_count
     pub fn advance(&self, total_count: usize) {
         let mut inner = self.inner.lock().unwrap();
@@ -212,6 +218,99 @@ impl LuaSystemProgress {
     }
 }
 
+/// Number of samples kept per system by `LuaSystemProfiles`
+pub const PROFILE_WINDOW: usize = 60;
+
+/// Environment variable that turns per-system profiling on (`1`) or off (`0`)
+pub const PROFILE_ENV_VAR: &str = "BEVY_LUA_PROFILE";
+
+/// Min/max/average over a system's rolling window of frame times
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SystemProfile {
+    pub min_ms: f64,
+    pub max_ms: f64,
+    pub avg_ms: f64,
+    pub samples: usize,
+}
+
+/// Rolling window of the last `PROFILE_WINDOW` run times of each Lua system
+///
+/// Enabled when `BEVY_LUA_PROFILE=1` is set at startup. Without the variable it is on in
+/// debug builds and off in release builds; `BEVY_LUA_PROFILE=0` always turns it off.
+#[derive(Resource, Clone)]
+pub struct LuaSystemProfiles {
+    enabled: bool,
+    samples: Arc<Mutex<std::collections::HashMap<String, std::collections::VecDeque<Duration>>>>,
+    /// Total entities despawned by `gc_orphaned_script_entities` (counted even when profiling is off)
+    gc_collected: Arc<AtomicU64>,
+}
+
+impl Default for LuaSystemProfiles {
+    fn default() -> Self {
+        let enabled = match std::env::var(PROFILE_ENV_VAR) {
+            Ok(value) => value == "1",
+            Err(_) => cfg!(debug_assertions),
+        };
+        Self::new(enabled)
+    }
+}
+
+impl LuaSystemProfiles {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            samples: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            gc_collected: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Push one run time for `system_name`, dropping the oldest sample past the window
+    pub fn record(&self, system_name: &str, elapsed: Duration) {
+        if !self.enabled {
+            return;
+        }
+        let mut samples = self.samples.lock().unwrap();
+        let window = samples.entry(system_name.to_string()).or_default();
+        if window.len() == PROFILE_WINDOW {
+            window.pop_front();
+        }
+        window.push_back(elapsed);
+    }
+
+    /// Summary of the current window, or None if the system has no samples
+    pub fn profile(&self, system_name: &str) -> Option<SystemProfile> {
+        let samples = self.samples.lock().unwrap();
+        let window = samples.get(system_name).filter(|w| !w.is_empty())?;
+        let to_ms = |d: &Duration| d.as_secs_f64() * 1000.0;
+        let total: f64 = window.iter().map(to_ms).sum();
+        Some(SystemProfile {
+            min_ms: window.iter().map(to_ms).fold(f64::INFINITY, f64::min),
+            max_ms: window.iter().map(to_ms).fold(0.0, f64::max),
+            avg_ms: total / window.len() as f64,
+            samples: window.len(),
+        })
+    }
+
+    /// Forget the samples of one system (e.g. after it was unregistered)
+    pub fn clear(&self, system_name: &str) {
+        self.samples.lock().unwrap().remove(system_name);
+    }
+
+    /// Add to the number of orphaned script entities despawned by the GC
+    pub fn record_gc(&self, count: u64) {
+        self.gc_collected.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Total orphaned script entities despawned by the GC since startup
+    pub fn gc_collected(&self) -> u64 {
+        self.gc_collected.load(Ordering::Relaxed)
+    }
+}
+
 /// Timer for measuring individual system execution time
 pub struct SystemTimer {
     start: Instant,
@@ -226,3 +325,33 @@ impl SystemTimer {
         self.start.elapsed()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_profile_rolling_window() {
+        let profiles = LuaSystemProfiles::new(true);
+        assert!(profiles.profile("Spawner").is_none());
+
+        profiles.record("Spawner", Duration::from_millis(4));
+        for _ in 0..PROFILE_WINDOW {
+            profiles.record("Spawner", Duration::from_millis(2));
+        }
+        // The 4ms sample has been pushed out of the window
+        let profile = profiles.profile("Spawner").unwrap();
+        assert_eq!(profile.samples, PROFILE_WINDOW);
+        assert_eq!(profile.max_ms, 2.0);
+        assert_eq!(profile.avg_ms, 2.0);
+
+        profiles.record("Spawner", Duration::from_millis(8));
+        let profile = profiles.profile("Spawner").unwrap();
+        assert_eq!((profile.min_ms, profile.max_ms), (2.0, 8.0));
+        assert_eq!(profile.avg_ms, (2.0 * 59.0 + 8.0) / 60.0);
+
+        let disabled = LuaSystemProfiles::new(false);
+        disabled.record("Spawner", Duration::from_millis(1));
+        assert!(disabled.profile("Spawner").is_none());
+    }
+}
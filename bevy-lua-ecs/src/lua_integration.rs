@@ -1,5 +1,5 @@
 use crate::lua_systems::LuaSystemRegistry;
-use crate::path_utils::{normalize_path, normalize_path_separators, to_forward_slash};
+use crate::path_utils::{canonicalize_lua_path, normalize_path_separators, to_forward_slash};
 use crate::spawn_queue::SpawnQueue;
 use bevy::prelude::*;
 use mlua::prelude::*;
@@ -22,7 +22,47 @@ pub struct LuaScriptContext {
     next_state_id: Arc<AtomicUsize>,
     pub script_cache: crate::script_cache::ScriptCache,
     pub script_instance: crate::script_entities::ScriptInstance,
+    /// Per-instance parameters for scripts started with `execute_script_with_params`
+    pub instance_pool: crate::script_entities::ScriptInstancePool,
+    /// Queues and registries shared by every Lua state's bridge globals
+    bridge: LuaStateBridge,
+    /// Extra setup applied to each sandbox state (app data, asset loading, stdlib)
+    /// Installed by `setup_lua_context`
+    state_setup: Option<LuaStateSetup>,
+    /// Give every new script instance its own Lua state (`MultiLuaContextPlugin`)
+    sandbox_instances: bool,
+    /// Sandboxed script instances: instance_id -> state_id
+    sandboxes: Arc<Mutex<HashMap<u64, usize>>>,
+    /// Module tables returned by script instances, for `call_named_function`
+    instance_modules: Arc<Mutex<HashMap<u64, Arc<LuaRegistryKey>>>>,
+    /// Script body coroutines suspended for a download: instance_id -> coroutine
+    /// Their return value becomes the instance's module once they finish
+    pending_script_threads: Arc<Mutex<HashMap<u64, Arc<LuaRegistryKey>>>>,
+    /// Environments of instances started with `execute_script_with_params`: instance_id -> table
+    /// Diff hot reload re-defines changed functions there instead of in the shared globals
+    instance_environments: Arc<Mutex<HashMap<u64, Arc<LuaRegistryKey>>>>,
 }
+
+/// Setup run on a freshly created sandbox state after the bridge globals are installed
+pub type LuaStateSetup = Arc<dyn Fn(&Lua) -> Result<(), LuaError> + Send + Sync>;
+
+/// Everything the global Lua functions (`spawn`, `require`, `register_system`, ...) capture
+/// Kept on the context so sandbox states get the same ECS bridge as the primary state
+#[derive(Clone)]
+struct LuaStateBridge {
+    queue: SpawnQueue,
+    despawn_queue: crate::despawn_queue::DespawnQueue,
+    resource_queue: crate::resource_queue::ResourceQueue,
+    update_queue: crate::component_update_queue::ComponentUpdateQueue,
+    system_registry: LuaSystemRegistry,
+    script_instance: crate::script_entities::ScriptInstance,
+    script_registry: crate::script_registry::ScriptRegistry,
+    table_resource_registry: crate::lua_resource::LuaTableResourceRegistry,
+    script_cache: crate::script_cache::ScriptCache,
+    lua_states: Arc<Mutex<Vec<Arc<Lua>>>>,
+    next_state_id: Arc<AtomicUsize>,
+}
+
 impl LuaScriptContext {
     /// Create a new Lua context with component-based spawn function
     pub fn new(
@@ -36,6 +76,55 @@ impl LuaScriptContext {
         script_registry: crate::script_registry::ScriptRegistry,
         table_resource_registry: crate::lua_resource::LuaTableResourceRegistry,
     ) -> Result<Self, LuaError> {
+        let bridge = LuaStateBridge {
+            queue,
+            despawn_queue,
+            resource_queue,
+            update_queue,
+            system_registry,
+            script_instance: script_instance.clone(),
+            script_registry,
+            table_resource_registry,
+            // Create script cache for module loading
+            script_cache: crate::script_cache::ScriptCache::new(),
+            lua_states: Arc::new(Mutex::new(Vec::new())),
+            next_state_id: Arc::new(AtomicUsize::new(1)), // 0 is primary, start at 1
+        };
+        let lua = Self::create_bridged_state(&bridge)?;
+
+        Ok(Self {
+            lua,
+            lua_states: bridge.lua_states.clone(),
+            next_state_id: bridge.next_state_id.clone(),
+            script_cache: bridge.script_cache.clone(),
+            script_instance,
+            instance_pool: crate::script_entities::ScriptInstancePool::default(),
+            bridge,
+            state_setup: None,
+            sandbox_instances: false,
+            sandboxes: Arc::new(Mutex::new(HashMap::new())),
+            instance_modules: Arc::new(Mutex::new(HashMap::new())),
+            pending_script_threads: Arc::new(Mutex::new(HashMap::new())),
+            instance_environments: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// Create a Lua state with the ECS bridge globals (spawn, despawn, resources, register_system,
+    /// file helpers, require/require_async) wired to the shared queues
+    fn create_bridged_state(bridge: &LuaStateBridge) -> Result<Arc<Lua>, LuaError> {
+        let LuaStateBridge {
+            queue,
+            despawn_queue,
+            resource_queue,
+            update_queue,
+            system_registry,
+            script_instance,
+            script_registry,
+            table_resource_registry,
+            script_cache,
+            lua_states,
+            next_state_id,
+        } = bridge.clone();
         let lua = Lua::new();
 
         // Clone what we need for the closure
@@ -50,10 +139,6 @@ impl LuaScriptContext {
         let lua_for_get_resource = lua_clone.clone();
         let lua_for_remove_resource = lua_clone.clone();
 
-        // Create state management Arcs early so they can be captured by require closure
-        let lua_states: Arc<Mutex<Vec<Arc<Lua>>>> = Arc::new(Mutex::new(Vec::new()));
-        let next_state_id: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(1)); // 0 is primary, start at 1
-
         // Track entity ID counter for immediate return (will be synced in process_spawn_queue)
         let entity_counter = Arc::new(std::sync::atomic::AtomicU32::new(0));
 
@@ -80,6 +165,11 @@ impl LuaScriptContext {
             // Iterate over components table
             for pair in components.pairs::<String, LuaValue>() {
                 let (component_name, component_value) = pair?;
+                let component_value = crate::serde_components::validate_lua_defined_component(
+                    lua_ctx,
+                    &component_name,
+                    component_value,
+                )?;
 
                 // Store everything as registry value
                 let registry_key = lua_for_closure.create_registry_value(component_value)?;
@@ -102,6 +192,52 @@ impl LuaScriptContext {
             Ok(builder)
         })?;
 
+        // Create spawn_batch(count, template) - spawn many entities sharing one component template
+        // Queues a single batch entry; returns a table of temp_ids usable like spawn():id()
+        let queue_for_batch = queue.clone();
+        let lua_for_batch = lua_clone.clone();
+        let spawn_batch = lua_clone.create_function(
+            move |lua_ctx, (count, template): (u32, LuaTable)| {
+                let instance_id: Option<u64> = lua_ctx.globals().get("__INSTANCE_ID__").ok();
+                let spawn_phase_str: String = lua_ctx.globals()
+                    .get("__SPAWN_PHASE__")
+                    .unwrap_or_else(|_| "script".to_string());
+                let spawn_phase = match spawn_phase_str.as_str() {
+                    "runtime" => crate::script_entities::SpawnPhase::Runtime,
+                    _ => crate::script_entities::SpawnPhase::Script,
+                };
+
+                let mut template_components = Vec::new();
+                for pair in template.pairs::<String, LuaValue>() {
+                    let (component_name, component_value) = pair?;
+                    let component_value = crate::serde_components::validate_lua_defined_component(
+                        lua_ctx,
+                        &component_name,
+                        component_value,
+                    )?;
+                    let registry_key = lua_for_batch.create_registry_value(component_value)?;
+                    template_components.push((component_name, registry_key));
+                }
+
+                let ids = lua_ctx.create_table_with_capacity(count as usize, 0)?;
+                if count == 0 {
+                    return Ok(ids);
+                }
+
+                debug!("[SPAWN] Queuing batch of {} entities with instance_id: {:?}", count, instance_id);
+                let temp_ids = queue_for_batch.queue_spawn_batch(
+                    count,
+                    template_components,
+                    instance_id,
+                    spawn_phase,
+                );
+                for (i, temp_id) in temp_ids.enumerate() {
+                    ids.raw_set(i + 1, temp_id)?;
+                }
+                Ok(ids)
+            },
+        )?;
+
         // Create spawn_with_parent function (legacy - prefer spawn().with_parent())
         let queue_for_parent = queue.clone();
         let lua_for_parent = lua_clone.clone();
@@ -225,8 +361,12 @@ impl LuaScriptContext {
         let system_reg = system_registry.clone();
         // Track system count per (instance_id, schedule) for unique naming
         let system_counters: Arc<Mutex<HashMap<(u64, String), usize>>> = Arc::new(Mutex::new(HashMap::new()));
+        // Optional third argument declares ordering: { name = "ai", before = {"animation"}, after = {"input"} }
+        // and/or a run condition: { condition = { resource = "GameState", field = "phase", equals = "combat" } }
+        // and/or a group that can be paused as a whole: { group = "gameplay" }
+        // and/or a rate limit in runs per second: { max_hz = 10 }
         let register_system = lua_clone.create_function(
-            move |lua_ctx, (schedule, func): (String, LuaFunction)| {
+            move |lua_ctx, (schedule, func, options): (String, LuaFunction, Option<LuaTable>)| {
                 // Get the current instance ID and state_id from globals
                 let instance_id: u64 = lua_ctx.globals().get("__INSTANCE_ID__").unwrap_or(0);
                 let state_id: usize = lua_ctx.globals().get("__LUA_STATE_ID__").unwrap_or(0);
@@ -251,66 +391,68 @@ impl LuaScriptContext {
                     .unwrap_or(&script_name);
                 let system_name = format!("{}:{}#{}", schedule, script_basename, system_num);
 
+                let mut ordering = crate::lua_systems::LuaSystemOrdering::default();
+                let mut condition = None;
+                let mut group = None;
+                let mut max_hz = None;
+                if let Some(options) = options {
+                    group = options.get::<Option<String>>("group")?;
+                    max_hz = options.get::<Option<f32>>("max_hz")?;
+                    if let Some(condition_table) = options.get::<Option<LuaTable>>("condition")? {
+                        condition = Some(crate::lua_systems::LuaSystemCondition::from_lua_table(lua_ctx, &condition_table)?);
+                    }
+                    ordering.label = options.get::<Option<String>>("name")?;
+                    if let Some(before) = options.get::<Option<Vec<String>>>("before")? {
+                        ordering.before = before;
+                    }
+                    if let Some(after) = options.get::<Option<Vec<String>>>("after")? {
+                        ordering.after = after;
+                    }
+                }
+                let system_name = match &ordering.label {
+                    Some(label) => format!("{}:{}", schedule, label),
+                    None => system_name,
+                };
+                if let Some(max_hz) = max_hz {
+                    system_reg.set_system_hz(instance_id, ordering.label.as_deref().unwrap_or(&system_name), max_hz);
+                }
+
                 let registry_key = lua_ctx.create_registry_value(func)?;
-                system_reg.register_system(instance_id, Arc::new(registry_key), state_id, system_name);
+                system_reg.register_system_full(instance_id, Arc::new(registry_key), state_id, system_name, ordering, condition, group);
                 Ok(())
             },
         )?;
 
+        // File helpers go through the OsUtilities app data (sandboxed when configured)
         // Create copy_file function for file operations
-        let copy_file = lua_clone.create_function(|_lua_ctx, (src, dest): (String, String)| {
-            use std::fs;
-            use std::path::Path;
-
-            // Create destination directory if it doesn't exist
-            if let Some(parent) = Path::new(&dest).parent() {
-                fs::create_dir_all(parent).map_err(|e| {
-                    LuaError::RuntimeError(format!("Failed to create directory: {}", e))
-                })?;
-            }
-
-            // Copy the file
-            fs::copy(&src, &dest)
-                .map_err(|e| LuaError::RuntimeError(format!("Failed to copy file: {}", e)))?;
-
-            Ok(())
+        let copy_file = lua_clone.create_function(|lua_ctx, (src, dest): (String, String)| {
+            os_utilities(lua_ctx)
+                .copy_file(&src, &dest)
+                .map_err(LuaError::RuntimeError)
         })?;
 
         // Create read_file_bytes function to read binary file contents
         let read_file_bytes = lua_clone.create_function(|lua_ctx, path: String| {
-            use std::fs;
-            let bytes = fs::read(&path)
-                .map_err(|e| LuaError::RuntimeError(format!("Failed to read file: {}", e)))?;
+            let bytes = os_utilities(lua_ctx)
+                .read_file(&path)
+                .map_err(LuaError::RuntimeError)?;
             lua_ctx.create_string(&bytes)
         })?;
 
         // Create write_file_bytes function to write binary file contents
         let write_file_bytes =
-            lua_clone.create_function(|_lua_ctx, (path, data): (String, LuaString)| {
-                use std::fs;
-                use std::path::Path;
-
-                // Create destination directory if it doesn't exist
-                if let Some(parent) = Path::new(&path).parent() {
-                    fs::create_dir_all(parent).map_err(|e| {
-                        LuaError::RuntimeError(format!("Failed to create directory: {}", e))
-                    })?;
-                }
-
-                fs::write(&path, data.as_bytes())
-                    .map_err(|e| LuaError::RuntimeError(format!("Failed to write file: {}", e)))?;
-                Ok(())
+            lua_clone.create_function(|lua_ctx, (path, data): (String, LuaString)| {
+                os_utilities(lua_ctx)
+                    .write_file(&path, &data.as_bytes())
+                    .map_err(LuaError::RuntimeError)
             })?;
 
         // Create create_directory function to create directories
         let create_directory =
-            lua_clone.create_function(|_lua_ctx, path: String| {
-                use std::fs;
-
-                fs::create_dir_all(&path).map_err(|e| {
-                    LuaError::RuntimeError(format!("Failed to create directory: {}", e))
-                })?;
-                Ok(())
+            lua_clone.create_function(|lua_ctx, path: String| {
+                os_utilities(lua_ctx)
+                    .create_directory(&path)
+                    .map_err(LuaError::RuntimeError)
             })?;
 
         // OS Utilities for networking and other low-level operations
@@ -344,8 +486,6 @@ impl LuaScriptContext {
             Ok(table)
         })?;
 
-        // Create script cache for module loading
-        let script_cache = crate::script_cache::ScriptCache::new();
         let cache_for_require = script_cache.clone();
 
         // Synchronous require() function
@@ -410,9 +550,10 @@ impl LuaScriptContext {
                 let current_path = std::path::Path::new(&current_script);
                 if let Some(parent) = current_path.parent() {
                     // Try relative to current script's directory
-                    let relative = parent.join(&path);
-                    // Normalize the path (resolve .. and .)
-                    let normalized = normalize_path(&relative.to_string_lossy());
+                    // Resolves .. and . in memory; errors if the path escapes assets/
+                    let normalized = to_forward_slash(
+                        canonicalize_lua_path(parent, &path).map_err(LuaError::RuntimeError)?,
+                    );
                     
                     // Check if relative path exists locally
                     let full_check_path = std::path::Path::new("assets").join(&normalized);
@@ -614,9 +755,10 @@ impl LuaScriptContext {
                 let current_path = std::path::Path::new(&current_script);
                 if let Some(parent) = current_path.parent() {
                     // Try relative to current script's directory
-                    let relative = parent.join(&path);
-                    // Normalize the path (resolve .. and .)
-                    let normalized = normalize_path(&relative.to_string_lossy());
+                    // Resolves .. and . in memory; errors if the path escapes assets/
+                    let normalized = to_forward_slash(
+                        canonicalize_lua_path(parent, &path).map_err(LuaError::RuntimeError)?,
+                    );
                     
                     // Check if relative path exists locally
                     let full_check_path = std::path::Path::new("assets").join(&normalized);
@@ -840,6 +982,7 @@ impl LuaScriptContext {
 
         // Inject into globals
         lua_clone.globals().set("spawn", spawn)?;
+        lua_clone.globals().set("spawn_batch", spawn_batch)?;
         lua_clone
             .globals()
             .set("spawn_with_parent", spawn_with_parent)?;
@@ -848,6 +991,7 @@ impl LuaScriptContext {
         // Entity ID may be a temp_id from spawn() or a real entity ID from query()
         // Use resolve_entity to handle both cases - it looks up temp_id->entity mapping
         // and falls back to from_bits() for real entity IDs
+        // Like entity:despawn(), this also despawns descendants; world:despawn_single() keeps them
         let queue_for_despawn = queue.clone();
         let despawn = lua_clone.create_function(move |_lua_ctx, entity_value: LuaValue| {
             // Handle both entity ID (u64) and LuaEntitySnapshot userdata
@@ -918,20 +1062,34 @@ impl LuaScriptContext {
             .set("parse_socket_addr", parse_socket_addr)?;
         lua_clone.globals().set("get_args", get_args)?;
 
+        // Clipboard (os.read_clipboard() -> string|nil, os.write_clipboard(text) -> bool)
+        if let Ok(os_table) = lua_clone.globals().get::<LuaTable>("os") {
+            os_table.set(
+                "read_clipboard",
+                lua_clone.create_function(|lua_ctx, ()| Ok(os_utilities(lua_ctx).read_clipboard()))?,
+            )?;
+            os_table.set(
+                "write_clipboard",
+                lua_clone.create_function(|lua_ctx, text: String| {
+                    Ok(os_utilities(lua_ctx).write_clipboard(&text))
+                })?,
+            )?;
+        }
+
         // Script importing
         lua_clone.globals().set("require", require)?;
         lua_clone.globals().set("require_async", require_async)?;
 
+        // Route print/io.write/log.* through Bevy's logging
+        install_lua_logging(&lua_clone)?;
+
+        // Lua's own file functions (io.open, dofile, os.remove, ...) honour the sandbox root too
+        install_sandboxed_file_access(&lua_clone)?;
+
         // Note: load_asset will be added via add_asset_loading_to_lua()
         // Note: query_resource will be added to world table in lua_systems
 
-        Ok(Self {
-            lua: lua_clone,
-            lua_states,
-            next_state_id,
-            script_cache,
-            script_instance,
-        })
+        Ok(lua_clone)
     }
 
     /// Execute a Lua script from a string
@@ -964,18 +1122,20 @@ impl LuaScriptContext {
             Some(id) => id,
             None => script_instance.start(script_name.to_string()),
         };
+        // Sandboxed instances run in their own Lua state
+        let lua = self.prepare_instance_state(instance_id)?;
 
         // Set both instance ID and script name as Lua globals
-        self.lua.globals().set("__INSTANCE_ID__", instance_id)?;
-        self.lua.globals().set("__SCRIPT_NAME__", script_name)?;
+        lua.globals().set("__INSTANCE_ID__", instance_id)?;
+        lua.globals().set("__SCRIPT_NAME__", script_name)?;
         
         // Set __SPAWN_PHASE__ to "script" so entities spawned during script execution
         // are cleaned up on hot-reload (vs "runtime" entities which persist)
-        self.lua.globals().set("__SPAWN_PHASE__", "script")?;
+        lua.globals().set("__SPAWN_PHASE__", "script")?;
 
         // Register a wrapped require that checks for __PENDING_DOWNLOAD__ and yields
         // This wrapper is called by scripts, it calls the real _require_internal, checks result
-        self.lua.load(r#"
+        lua.load(r#"
             -- Store originals in special globals the FIRST time only
             -- This prevents re-capturing the wrapper on hot reload
             if not __RUST_REQUIRE__ then
@@ -1236,14 +1396,20 @@ impl LuaScriptContext {
         "#).exec()?;
 
         // Load script as a function
-        let script_fn = self
-            .lua
-            .load(script_content)
-            .set_name(script_name)
-            .into_function()?;
+        // Pooled instances run in their own environment with SCRIPT_PARAMS set
+        let mut chunk = lua.load(script_content).set_name(script_name);
+        if let Some(params) = self.instance_pool.params(instance_id) {
+            let env = Self::instance_environment(&lua, &params)?;
+            self.instance_environments
+                .lock()
+                .unwrap()
+                .insert(instance_id, Arc::new(lua.create_registry_value(env.clone())?));
+            chunk = chunk.set_environment(env);
+        }
+        let script_fn = chunk.into_function()?;
 
         // Create a coroutine to run the script
-        let coroutine = self.lua.create_thread(script_fn)?;
+        let coroutine = lua.create_thread(script_fn)?;
 
         // Resume the coroutine (execute until completion or yield)
         match coroutine.resume::<mlua::Value>(()) {
@@ -1256,6 +1422,7 @@ impl LuaScriptContext {
                             "Script '{}' completed with instance ID: {}",
                             script_name, instance_id
                         );
+                        self.set_instance_module(&lua, instance_id, yield_value)?;
                     }
                     mlua::ThreadStatus::Resumable => {
                         // Script yielded - it needs a download
@@ -1267,7 +1434,11 @@ impl LuaScriptContext {
 
                             // Store the coroutine for later resumption when download completes
                             let coroutine_key =
-                                std::sync::Arc::new(self.lua.create_registry_value(coroutine)?);
+                                std::sync::Arc::new(lua.create_registry_value(coroutine)?);
+                            self.pending_script_threads
+                                .lock()
+                                .unwrap()
+                                .insert(instance_id, coroutine_key.clone());
 
                             // Register for resumption after this path is downloaded
                             self.script_cache.register_pending_download_coroutine(
@@ -1275,7 +1446,7 @@ impl LuaScriptContext {
                                 coroutine_key,
                                 instance_id,
                                 false, // is_binary=false for scripts
-                                self.lua.globals().get::<String>("__SCRIPT_NAME__").ok(), // context for server
+                                lua.globals().get::<String>("__SCRIPT_NAME__").ok(), // context for server
                                 false, // should_subscribe=false for internal resumption (subscription handled at require level)
                             );
 
@@ -1349,6 +1520,191 @@ impl LuaScriptContext {
         Ok(instance_id)
     }
 
+    /// Execute a script as a new instance with its own initialization parameters
+    /// `init_params` is available to the script as the global `SCRIPT_PARAMS` table. Globals the
+    /// script defines stay in the instance's own environment, so several instances of the same
+    /// file don't overwrite each other. Parameters are kept in `ScriptInstancePool` and
+    /// re-applied on hot reload.
+    pub fn execute_script_with_params(
+        &self,
+        script_content: &str,
+        script_name: &str,
+        script_path: std::path::PathBuf,
+        init_params: serde_json::Value,
+        script_instance: &crate::script_entities::ScriptInstance,
+        script_registry: &crate::script_registry::ScriptRegistry,
+    ) -> Result<u64, LuaError> {
+        let module_path = to_forward_slash(&script_path);
+        let module_name = module_path.strip_prefix("assets/").unwrap_or(&module_path);
+
+        let instance_id = script_instance.start(module_name.to_string());
+        self.instance_pool.insert(instance_id, script_path.clone(), init_params);
+
+        if let Err(e) =
+            self.execute_script_tracked_with_id(script_content, module_name, script_instance, Some(instance_id))
+        {
+            self.instance_pool.remove(instance_id);
+            return Err(e);
+        }
+
+        script_registry.register_script(script_path, instance_id, script_content.to_string());
+        Ok(instance_id)
+    }
+
+    /// Call a function from the module table a script instance returned, without re-running it
+    /// `args` must belong to the instance's Lua state (`instance_lua(instance_id)`).
+    ///
+    /// ```lua
+    /// -- scripts/enemy.lua
+    /// local enemy = {}
+    /// function enemy.on_hit(damage) ... end
+    /// return enemy
+    /// ```
+    /// Scripts that yield for a download get their module table once the download handler
+    /// resumes them (see `finish_resumed_coroutine`).
+    pub fn call_named_function(
+        &self,
+        instance_id: u64,
+        function_name: &str,
+        args: mlua::MultiValue,
+    ) -> mlua::Result<mlua::MultiValue> {
+        let module_key = self
+            .instance_modules
+            .lock()
+            .unwrap()
+            .get(&instance_id)
+            .cloned()
+            .ok_or_else(|| {
+                LuaError::RuntimeError(format!("Script instance {} returned no module table", instance_id))
+            })?;
+        let lua = self.instance_lua(instance_id);
+        let module: LuaTable = lua.registry_value(&module_key)?;
+        match module.get::<LuaValue>(function_name)? {
+            LuaValue::Function(function) => function.call(args),
+            LuaValue::Nil => Err(LuaError::RuntimeError(format!(
+                "Script instance {} has no function '{}'",
+                instance_id, function_name
+            ))),
+            other => Err(LuaError::RuntimeError(format!(
+                "'{}' in script instance {} is a {}, not a function",
+                function_name,
+                instance_id,
+                other.type_name()
+            ))),
+        }
+    }
+
+    /// Forget the module table (and pooled environment) of a script instance that has been stopped
+    pub fn remove_instance_module(&self, instance_id: u64) -> bool {
+        self.pending_script_threads.lock().unwrap().remove(&instance_id);
+        self.instance_environments.lock().unwrap().remove(&instance_id);
+        self.instance_modules.lock().unwrap().remove(&instance_id).is_some()
+    }
+
+    /// A returned table is the instance's module (replaced on hot reload)
+    fn set_instance_module(&self, lua: &Lua, instance_id: u64, result: LuaValue) -> LuaResult<()> {
+        let mut modules = self.instance_modules.lock().unwrap();
+        match result {
+            LuaValue::Table(module) => {
+                modules.insert(instance_id, Arc::new(lua.create_registry_value(module)?));
+            }
+            _ => {
+                modules.remove(&instance_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Record what a resumed download coroutine returned once it finishes
+    /// When it is the body of script `instance_id` (suspended by `require`/`load_asset`), a
+    /// returned table becomes the instance's module, as if the script had never yielded.
+    /// Call this from download handlers after resuming coroutines taken from
+    /// `ScriptCache::take_pending_download_coroutines`.
+    pub fn finish_resumed_coroutine(
+        &self,
+        lua: &Lua,
+        instance_id: u64,
+        coroutine: &LuaThread,
+        result: LuaValue,
+    ) -> LuaResult<()> {
+        if coroutine.status() != LuaThreadStatus::Finished {
+            return Ok(());
+        }
+        let mut pending = self.pending_script_threads.lock().unwrap();
+        let Some(key) = pending.get(&instance_id) else {
+            return Ok(());
+        };
+        if lua.registry_value::<LuaThread>(key).ok().as_ref() != Some(coroutine) {
+            return Ok(());
+        }
+        pending.remove(&instance_id);
+        drop(pending);
+        self.set_instance_module(lua, instance_id, result)
+    }
+
+    /// Environment table of an instance started with `execute_script_with_params`
+    /// None for instances that run directly in their state's globals.
+    pub fn instance_environment_table(&self, instance_id: u64) -> Option<LuaTable> {
+        let key = self.instance_environments.lock().unwrap().get(&instance_id).cloned()?;
+        self.instance_lua(instance_id).registry_value(&key).ok()
+    }
+
+    /// Build the environment for a pooled instance: `SCRIPT_PARAMS` plus the shared globals
+    /// as a read fallback; assignments stay in the environment
+    fn instance_environment(lua: &Lua, params: &serde_json::Value) -> Result<LuaTable, LuaError> {
+        let env = lua.create_table()?;
+        env.set("SCRIPT_PARAMS", lua.to_value(params)?)?;
+        let env_meta = lua.create_table()?;
+        env_meta.set("__index", lua.globals())?;
+        env.set_metatable(Some(env_meta));
+        Ok(env)
+    }
+
+    /// Evaluate a single Lua expression against the primary state and return it as JSON
+    /// Used by consoles and debug tools. The expression runs in a whitelist environment with
+    /// no fallback to the state's globals: it sees the side-effect-free builtins, copies of the
+    /// `math`, `string`, `table` and `utf8` libraries, and `get_lua_resource` for reading game
+    /// state. `require`, `package`, `load*`, `io`, `os` and script globals are not reachable, and
+    /// assignments stay local to the evaluation.
+    pub fn eval_expression(&self, expr: &str) -> Result<serde_json::Value, LuaError> {
+        const FUNCTIONS: &[&str] = &[
+            "assert", "error", "ipairs", "next", "pairs", "pcall", "select", "tonumber",
+            "tostring", "type", "xpcall", "get_lua_resource",
+        ];
+        const LIBRARIES: &[&str] = &["math", "string", "table", "utf8"];
+
+        let lua = &self.lua;
+        let globals = lua.globals();
+
+        let env = lua.create_table()?;
+        for name in FUNCTIONS {
+            env.raw_set(*name, globals.raw_get::<LuaValue>(*name)?)?;
+        }
+        // Copies, so an expression can't replace library functions for every script
+        for name in LIBRARIES {
+            if let LuaValue::Table(library) = globals.raw_get::<LuaValue>(*name)? {
+                let copy = lua.create_table()?;
+                for pair in library.pairs::<LuaValue, LuaValue>() {
+                    let (key, value) = pair?;
+                    copy.raw_set(key, value)?;
+                }
+                env.raw_set(*name, copy)?;
+            }
+        }
+
+        let value: LuaValue = lua
+            .load(format!("return ({})", expr))
+            .set_name("=eval")
+            .set_environment(env)
+            .eval()?;
+
+        // Values without a serde representation (functions, userdata) fall back to tostring()
+        match lua.from_value::<serde_json::Value>(value.clone()) {
+            Ok(json) => Ok(json),
+            Err(_) => Ok(serde_json::Value::String(value.to_string()?)),
+        }
+    }
+
     /// Execute a script with automatic cleanup and tracking
     /// This despawns all entities from the previous instance before running the script again
     pub fn reload_script(
@@ -1409,6 +1765,90 @@ impl LuaScriptContext {
     pub fn state_count(&self) -> usize {
         1 + self.lua_states.lock().unwrap().len()
     }
+
+    // ======================== Script Sandboxes ========================
+
+    /// Lua state that runs `instance_id`: its sandbox state, or the primary state
+    pub fn instance_lua(&self, instance_id: u64) -> Arc<Lua> {
+        match self.sandboxes.lock().unwrap().get(&instance_id) {
+            Some(state_id) => self.get_lua_state(*state_id),
+            None => self.lua.clone(),
+        }
+    }
+
+    /// Whether `instance_id` runs in its own sandbox state
+    pub fn is_sandboxed(&self, instance_id: u64) -> bool {
+        self.sandboxes.lock().unwrap().contains_key(&instance_id)
+    }
+
+    /// Lua states created for sandboxed script instances
+    pub fn sandbox_states(&self) -> Vec<Arc<Lua>> {
+        let state_ids: Vec<usize> = self.sandboxes.lock().unwrap().values().copied().collect();
+        state_ids.into_iter().map(|state_id| self.get_lua_state(state_id)).collect()
+    }
+
+    /// Lua state whose registry holds `key`
+    /// Queued spawns, updates and resources carry registry keys from the state that queued them
+    pub fn state_for_key(&self, key: &LuaRegistryKey) -> Arc<Lua> {
+        if self.lua.owns_registry_value(key) {
+            return self.lua.clone();
+        }
+        self.lua_states
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|state| state.owns_registry_value(key))
+            .cloned()
+            .unwrap_or_else(|| self.lua.clone())
+    }
+
+    /// Give every new script instance its own Lua state (see `MultiLuaContextPlugin`)
+    pub fn set_sandbox_instances(&mut self, enabled: bool) {
+        self.sandbox_instances = enabled;
+    }
+
+    /// Setup applied to each new sandbox state after the bridge globals
+    pub fn set_state_setup(&mut self, setup: LuaStateSetup) {
+        self.state_setup = Some(setup);
+    }
+
+    /// Create an isolated Lua state for `instance_id`
+    /// The state gets the same ECS bridge globals as the primary state but shares no Lua values
+    /// with it; scripts talk to each other only through the ECS (events, components, resources).
+    pub fn create_sandbox(&self, instance_id: u64) -> Result<Arc<Lua>, LuaError> {
+        let lua = Self::create_bridged_state(&self.bridge)?;
+        let state_id = self.allocate_state_id();
+        lua.globals().set("__LUA_STATE_ID__", state_id)?;
+        if let Some(setup) = &self.state_setup {
+            setup(&lua)?;
+        }
+        self.store_lua_state(state_id, lua.clone());
+        self.sandboxes.lock().unwrap().insert(instance_id, state_id);
+        debug!("Created sandbox Lua state {} for instance {}", state_id, instance_id);
+        Ok(lua)
+    }
+
+    /// Drop the sandbox state of a stopped instance
+    /// The slot is reset to the primary-state placeholder so other state ids stay valid.
+    pub fn remove_sandbox(&self, instance_id: u64) -> bool {
+        let Some(state_id) = self.sandboxes.lock().unwrap().remove(&instance_id) else {
+            return false;
+        };
+        let mut states = self.lua_states.lock().unwrap();
+        if let Some(slot) = states.get_mut(state_id - 1) {
+            *slot = self.lua.clone();
+        }
+        debug!("Removed sandbox Lua state {} for instance {}", state_id, instance_id);
+        true
+    }
+
+    /// Lua state to run `instance_id` in, creating its sandbox on first use when sandboxing is on
+    fn prepare_instance_state(&self, instance_id: u64) -> Result<Arc<Lua>, LuaError> {
+        if self.sandbox_instances && !self.is_sandboxed(instance_id) {
+            return self.create_sandbox(instance_id);
+        }
+        Ok(self.instance_lua(instance_id))
+    }
 }
 
 /// Plugin that sets up Lua scripting with component-based spawn function
@@ -1420,10 +1860,48 @@ impl LuaScriptContext {
 /// - All processing systems (spawn, updates, resources, assets)
 ///
 /// Users only need to add this plugin after DefaultPlugins (or other plugins that register types).
+/// Insert a `LuaPluginOrder` resource before adding the plugin to move its startup systems
+/// into other startup schedules.
 pub struct LuaSpawnPlugin;
 
+impl LuaSpawnPlugin {
+    /// `LuaSpawnPlugin` plus a Lua file (relative to `assets/`) that runs before any user script
+    /// e.g. `app.add_plugins(LuaSpawnPlugin::with_lua_stdlib_extensions("scripts/stdlib.lua"))`
+    pub fn with_lua_stdlib_extensions(
+        asset_path: &str,
+    ) -> (crate::lua_stdlib::LuaStdlibExtensions, LuaSpawnPlugin) {
+        (crate::lua_stdlib::LuaStdlibExtensions::new(asset_path), LuaSpawnPlugin)
+    }
+}
+
+/// Lua version the bindings are written against (integer division, bitwise ops, warn, ...)
+const REQUIRED_LUA_VERSION: &str = "Lua 5.4";
+
+/// Check that mlua was linked against Lua 5.4, returning the `_VERSION` it reports
+/// Another mlua feature (lua51, luajit, ...) in the dependency graph can swap the interpreter
+fn check_lua_version(lua: &Lua) -> Result<String, String> {
+    let version: String = lua
+        .globals()
+        .get("_VERSION")
+        .map_err(|e| format!("could not read _VERSION: {}", e))?;
+    if version != REQUIRED_LUA_VERSION {
+        return Err(format!(
+            "bevy-lua-ecs requires {} but mlua is running {}. Build mlua with \
+             `features = [\"lua54\"]` and no other Lua version feature \
+             (https://docs.rs/mlua/latest/mlua/#feature-flags)",
+            REQUIRED_LUA_VERSION, version
+        ));
+    }
+    Ok(version)
+}
+
 impl Plugin for LuaSpawnPlugin {
     fn build(&self, app: &mut App) {
+        if let Err(message) = check_lua_version(&Lua::new()) {
+            error!("[LUA] {}", message);
+            panic!("{}", message);
+        }
+
         // Initialize all required resources
         // Note: ComponentRegistry needs AppTypeRegistry, so we create it in a startup system
         app.init_resource::<SpawnQueue>();
@@ -1431,6 +1909,7 @@ impl Plugin for LuaSpawnPlugin {
         app.init_resource::<crate::component_update_queue::ComponentUpdateQueue>();
         app.init_resource::<crate::resource_queue::ResourceQueue>();
         app.init_resource::<crate::resource_builder::ResourceBuilderRegistry>();
+        app.init_resource::<crate::resource_constructors::ResourceConstructorRegistry>();
         app.init_resource::<crate::serde_components::SerdeComponentRegistry>();
         app.init_resource::<crate::resource_lua_trait::LuaResourceRegistry>();
         app.init_resource::<crate::component_lua_trait::LuaComponentRegistry>();
@@ -1441,19 +1920,58 @@ impl Plugin for LuaSpawnPlugin {
         app.init_resource::<crate::lua_frame_budget::LuaFrameBudget>();
         app.init_resource::<crate::lua_parallel::LuaParallelConfig>();
         app.init_resource::<crate::lua_frame_budget::LuaSystemProgress>();
+        app.init_resource::<crate::lua_frame_budget::LuaSystemProfiles>();
         app.init_resource::<crate::event_accumulator::LuaEventAccumulator>();
         app.init_resource::<crate::removed_components::RemovedComponentsTracker>();
         app.init_resource::<crate::lua_resource::LuaTableResourceRegistry>();
+        app.init_resource::<crate::lua_coroutines::LuaCoroutineScheduler>();
+        app.init_resource::<crate::lua_timers::TimerRegistry>();
+        app.init_resource::<crate::resource_watchers::ResourceWatcherRegistry>();
+        app.init_resource::<crate::script_entities::ScriptGcConfig>();
+        app.init_resource::<crate::lua_spawn_builder::PostSpawnCallbackQueue>();
+        app.init_resource::<crate::network_asset_trait::DownloadProgress>();
+        app.init_resource::<crate::prefab_registry::PrefabRegistry>();
+        app.init_resource::<crate::reflection::BundleRegistry>();
+        app.init_resource::<crate::entity_persistence::SaveConfig>();
+        app.init_resource::<crate::undo_stack::UndoStack>();
+        app.init_resource::<crate::lua_tween::TweenQueue>();
+        app.init_resource::<crate::hierarchy_queue::HierarchyQueue>();
+        app.init_resource::<crate::entity_tags::TagQueue>();
+        app.init_resource::<crate::observer_persistence::PersistentObserverSnapshot>();
+        #[cfg(feature = "physics")]
+        app.init_resource::<crate::physics_debug::PhysicsDebugQueue>();
+        app.register_type::<crate::entity_tags::Tags>();
+        // Lua-defined components are saved in entity scenes through these
+        app.register_type::<crate::serde_components::LuaJsonComponent>();
+        app.register_type::<crate::serde_components::LuaDefinedComponents>();
+        app.init_resource::<crate::lua_animation::AnimationQueue>();
+        app.init_resource::<crate::one_shot_systems::OneShotSystemRegistry>();
+        app.init_resource::<crate::query_cursor::QueryCursor>();
+        app.init_resource::<crate::query_cursor::QueryIterState>();
+        app.init_resource::<crate::bitflags_registry::BitflagsRegistry>();
 
         // Add file watcher plugin for auto-reload
-        app.add_plugins(crate::lua_file_watcher::LuaFileWatcherPlugin);
+        app.add_plugins(crate::lua_file_watcher::LuaFileWatcherPlugin::default());
+
+        // Expression evaluation for consoles/editor tools
+        app.add_message::<crate::lua_eval::EvalLuaExpression>();
+        app.add_message::<crate::lua_eval::LuaExpressionResult>();
+        app.add_systems(Update, crate::lua_eval::process_eval_requests);
 
         // Add event/message sender plugin for Lua event and message dispatch
         app.add_plugins(crate::event_sender::LuaEventSenderPlugin);
 
+        // Startup stages come from LuaPluginOrder when configured (defaults otherwise)
+        let (registry_stage, lua_init_stage, report_stage) = app
+            .world()
+            .get_resource::<crate::lua_plugin_order::LuaPluginOrder>()
+            .cloned()
+            .unwrap_or_default()
+            .resolve();
+
         // Register auto-generated resource method bindings
         // This must happen after LuaResourceRegistry is initialized
-        app.add_systems(PreStartup, register_resource_methods);
+        app.add_systems(registry_stage.label(), register_resource_methods);
 
         // Note: EventReaderRegistry is no longer needed!
         // Event reading is now fully generic via reflection in world:read_events()
@@ -1463,29 +1981,55 @@ impl Plugin for LuaSpawnPlugin {
         // adding RepliconPlugins to ensure consistent registration order.
 
         // Add all required systems
-        // Initialize ComponentRegistry in PreStartup to ensure it exists before setup_lua_context
-        app.add_systems(PreStartup, initialize_component_registry);
-        app.add_systems(Startup, setup_lua_context);
-        app.add_systems(PostStartup, log_available_events);
+        // Initialize ComponentRegistry (PreStartup by default) to ensure it exists before setup_lua_context
+        app.add_systems(registry_stage.label(), initialize_component_registry);
+        app.add_systems(
+            lua_init_stage.label(),
+            setup_lua_context
+                .after(initialize_component_registry)
+                .after(register_resource_methods),
+        );
+        app.add_systems(report_stage.label(), log_available_events);
         app.add_systems(
             Update,
             (
+                // Stdlib helpers reload before the scripts that use them
+                crate::lua_stdlib::reload_stdlib_extensions.before(auto_reload_changed_scripts),
                 // Auto-reload must run first to queue despawns/spawns before processing
                 auto_reload_changed_scripts,
+                // Queue despawns for entities left over from older versions of `@hot_reload: respawn` scripts
+                crate::script_registry::reap_stale_script_entities.after(auto_reload_changed_scripts),
+                // Periodically queue despawns for entities whose script instance is gone
+                crate::script_entities::gc_orphaned_script_entities.after(auto_reload_changed_scripts),
                 // Despawn old entities first (critical for hot-reload)
-                crate::despawn_queue::process_despawn_queue.after(auto_reload_changed_scripts),
+                crate::despawn_queue::process_despawn_queue
+                    .after(auto_reload_changed_scripts)
+                    .after(crate::script_registry::reap_stale_script_entities)
+                    .after(crate::script_entities::gc_orphaned_script_entities),
+                // Undo restores go after despawns so an undone delete can respawn the entity
+                crate::undo_stack::apply_entity_restores
+                    .after(crate::despawn_queue::process_despawn_queue),
                 // Then create new assets
                 crate::asset_loading::process_pending_assets
                     .after(crate::despawn_queue::process_despawn_queue),
                 // Then spawn new entities with those assets
                 crate::entity_spawner::process_spawn_queue
                     .after(crate::asset_loading::process_pending_assets),
+                // Re-parent entities once queued spawns exist
+                crate::hierarchy_queue::process_hierarchy_queue
+                    .after(crate::entity_spawner::process_spawn_queue),
+                // spawn_with_init callbacks, once the spawn commands have been applied
+                crate::lua_spawn_builder::run_post_spawn_callbacks
+                    .after(crate::hierarchy_queue::process_hierarchy_queue),
                 // Process observer registrations after entities are spawned
                 crate::lua_observers::process_observer_registrations
                     .after(crate::entity_spawner::process_spawn_queue),
                 // Attach observers to entities that have callbacks
                 crate::lua_observers::attach_lua_observers
                     .after(crate::lua_observers::process_observer_registrations),
+                // Spawn Bevy observers for component lifecycle callbacks
+                crate::lua_observers::attach_lifecycle_observers
+                    .after(crate::lua_observers::process_observer_registrations),
                 // Finally apply component updates
                 crate::component_updater::process_component_updates
                     .after(crate::lua_observers::attach_lua_observers),
@@ -1494,9 +2038,67 @@ impl Plugin for LuaSpawnPlugin {
                     .after(crate::component_updater::process_component_updates),
                 crate::lua_systems::run_lua_systems
                     .after(crate::removed_components::update_removed_components_tracker),
+                // Resume suspended coroutines after Lua systems have run
+                crate::lua_coroutines::advance_lua_coroutines
+                    .after(crate::lua_systems::run_lua_systems),
+                // Fire expired timers alongside coroutines
+                crate::lua_timers::tick_lua_timers
+                    .after(crate::lua_systems::run_lua_systems),
+            ),
+        );
+        app.add_systems(
+            PreUpdate,
+            crate::resource_inserter::process_high_priority_resources,
+        );
+        app.add_systems(
+            PreUpdate,
+            crate::network_asset_trait::sync_download_progress_resource
+                .run_if(resource_added::<crate::network_asset_trait::NetworkAssetLoader>),
+        );
+        app.add_systems(
+            PreUpdate,
+            crate::network_asset_trait::retry_failed_downloads
+                .run_if(resource_exists::<crate::network_asset_trait::NetworkAssetLoader>),
+        );
+        // Paged query passes hand out one page per frame after Lua systems have run
+        app.add_systems(
+            Update,
+            crate::query_cursor::process_async_queries.after(crate::lua_systems::run_lua_systems),
+        );
+        // Tag changes apply once queued spawns exist, like parent changes
+        app.add_systems(
+            Update,
+            crate::entity_tags::process_tag_queue
+                .after(crate::entity_spawner::process_spawn_queue)
+                .before(crate::lua_systems::run_lua_systems),
+        );
+        // Debug color overrides need the entity, so apply after queued spawns
+        #[cfg(feature = "physics")]
+        app.add_systems(
+            Update,
+            crate::physics_debug::process_physics_debug_queue
+                .after(crate::entity_spawner::process_spawn_queue),
+        );
+        // Tweens queue interpolated values before the component update queue is processed
+        app.add_systems(
+            Update,
+            crate::lua_tween::process_tweens
+                .before(crate::component_updater::process_component_updates),
+        );
+        // Start clips once loaded; ping-pong clips flip direction after Bevy advanced them last frame
+        app.add_systems(
+            Update,
+            (
+                crate::lua_animation::process_animation_queue
+                    .after(crate::hierarchy_queue::process_hierarchy_queue),
+                crate::lua_animation::update_ping_pong_animations,
             ),
         );
         app.add_systems(Update, (crate::resource_inserter::process_resource_queue,));
+        // Resource subscriptions see every change made during Update
+        app.add_systems(PostUpdate, crate::resource_watchers::check_resource_watchers);
+        // Release Lua registry values for modules evicted from the ScriptCache
+        app.add_systems(Last, crate::script_cache::release_evicted_script_modules);
     }
 }
 
@@ -1506,6 +2108,8 @@ fn register_resource_methods(
     lua_resource_registry: Res<crate::resource_lua_trait::LuaResourceRegistry>,
 ) {
     crate::auto_bindings::register_auto_bindings(&lua_resource_registry);
+    // Types annotated with #[lua_export] register themselves via inventory
+    lua_resource_registry.register_exported_resources();
 }
 
 /// System to initialize ComponentRegistry from AppTypeRegistry
@@ -1541,6 +2145,167 @@ fn log_available_events(type_registry: Res<AppTypeRegistry>) {
     }
 }
 
+/// File access policy for Lua file helpers (unrestricted unless an OsUtilities resource was inserted)
+fn os_utilities(lua: &Lua) -> crate::os_utilities::OsUtilities {
+    lua.app_data_ref::<crate::os_utilities::OsUtilities>()
+        .map(|os| os.clone())
+        .unwrap_or_default()
+}
+
+/// Wrap the standard library functions that take file paths (`io.open`, `io.lines`, `io.input`,
+/// `io.output`, `os.remove`, `os.rename`, `dofile`, `loadfile`) so their paths go through
+/// `OsUtilities::resolve_path`, and refuse `io.popen`/`os.execute` while a sandbox root is set.
+/// The policy is read on every call, so an OsUtilities set after the state was created still applies.
+fn install_sandboxed_file_access(lua: &Lua) -> LuaResult<()> {
+    let globals = lua.globals();
+    let io = globals.get::<Option<LuaTable>>("io")?;
+    let os = globals.get::<Option<LuaTable>>("os")?;
+
+    // (table, function, number of leading arguments that are paths)
+    let mut path_functions = vec![(globals.clone(), "dofile", 1), (globals.clone(), "loadfile", 1)];
+    if let Some(io) = &io {
+        for name in ["open", "lines", "input", "output"] {
+            path_functions.push((io.clone(), name, 1));
+        }
+    }
+    if let Some(os) = &os {
+        path_functions.push((os.clone(), "remove", 1));
+        path_functions.push((os.clone(), "rename", 2));
+    }
+
+    for (table, name, path_args) in path_functions {
+        let Some(original) = table.get::<Option<LuaFunction>>(name)? else {
+            continue;
+        };
+        let wrapped = lua.create_function(move |lua, mut args: LuaMultiValue| {
+            let os_utils = os_utilities(lua);
+            if os_utils.sandbox_root().is_some() {
+                // Non-string arguments (file handles, nil for stdin) are passed through untouched
+                for arg in args.iter_mut().take(path_args) {
+                    let LuaValue::String(path) = &*arg else {
+                        continue;
+                    };
+                    let resolved = os_utils
+                        .resolve_path(&path.to_str()?)
+                        .map_err(LuaError::RuntimeError)?;
+                    *arg = LuaValue::String(lua.create_string(resolved.to_string_lossy().as_bytes())?);
+                }
+            }
+            original.call::<LuaMultiValue>(args)
+        })?;
+        table.set(name, wrapped)?;
+    }
+
+    // Shell commands can reach any path, so they are unavailable in sandbox mode
+    let shell_functions = [(io, "io", "popen"), (os, "os", "execute")];
+    for (table, table_name, name) in shell_functions {
+        let Some(table) = table else {
+            continue;
+        };
+        let Some(original) = table.get::<Option<LuaFunction>>(name)? else {
+            continue;
+        };
+        let wrapped = lua.create_function(move |lua, args: LuaMultiValue| {
+            if os_utilities(lua).sandbox_root().is_some() {
+                return Err(LuaError::RuntimeError(format!(
+                    "{}.{} is not available while file access is sandboxed",
+                    table_name, name
+                )));
+            }
+            original.call::<LuaMultiValue>(args)
+        })?;
+        table.set(name, wrapped)?;
+    }
+
+    Ok(())
+}
+
+/// Replace `print` and `io.write` with versions that log through Bevy's `info!`, and add a
+/// `log` table (`log.info/warn/error/debug`), so Lua output shares the Rust log stream and level
+fn install_lua_logging(lua: &Lua) -> LuaResult<()> {
+    let globals = lua.globals();
+
+    let print = lua.create_function(|lua, args: LuaMultiValue| {
+        info!("[Lua] {}", join_lua_args(lua, args)?);
+        Ok(())
+    })?;
+    globals.set("print", print)?;
+
+    let log = lua.create_table()?;
+    log.set("info", lua.create_function(|lua, args: LuaMultiValue| {
+        info!("[Lua] {}", join_lua_args(lua, args)?);
+        Ok(())
+    })?)?;
+    log.set("warn", lua.create_function(|lua, args: LuaMultiValue| {
+        warn!("[Lua] {}", join_lua_args(lua, args)?);
+        Ok(())
+    })?)?;
+    log.set("error", lua.create_function(|lua, args: LuaMultiValue| {
+        error!("[Lua] {}", join_lua_args(lua, args)?);
+        Ok(())
+    })?)?;
+    log.set("debug", lua.create_function(|lua, args: LuaMultiValue| {
+        debug!("[Lua] {}", join_lua_args(lua, args)?);
+        Ok(())
+    })?)?;
+    globals.set("log", log)?;
+
+    // io.write has no implicit newline, so buffer until a line is complete
+    if let Ok(io) = globals.get::<LuaTable>("io") {
+        let stdout: LuaValue = io.get("stdout")?;
+        let pending = Arc::new(Mutex::new(String::new()));
+        let write = lua.create_function(move |_, args: LuaMultiValue| {
+            let mut text = String::new();
+            for arg in args {
+                match arg {
+                    LuaValue::String(s) => text.push_str(&s.to_str()?),
+                    LuaValue::Integer(n) => text.push_str(&n.to_string()),
+                    LuaValue::Number(n) => text.push_str(&n.to_string()),
+                    other => {
+                        return Err(LuaError::RuntimeError(format!(
+                            "bad argument to 'write' (string expected, got {})",
+                            other.type_name()
+                        )))
+                    }
+                }
+            }
+            for line in take_complete_lines(&mut pending.lock().unwrap(), &text) {
+                info!("[Lua] {}", line);
+            }
+            // Like io.write, return the file so calls can be chained
+            Ok(stdout.clone())
+        })?;
+        io.set("write", write)?;
+    }
+
+    Ok(())
+}
+
+/// Format values like Lua's print: tostring() on each, separated by tabs
+fn join_lua_args(lua: &Lua, args: LuaMultiValue) -> LuaResult<String> {
+    let tostring: LuaFunction = lua.globals().get("tostring")?;
+    let parts = args
+        .into_iter()
+        .map(|arg| tostring.call::<String>(arg))
+        .collect::<LuaResult<Vec<_>>>()?;
+    Ok(parts.join("\t"))
+}
+
+/// Append `text` to `pending` and return every line it completes (without the newline)
+fn take_complete_lines(pending: &mut String, text: &str) -> Vec<String> {
+    pending.push_str(text);
+    let Some(last_newline) = pending.rfind('\n') else {
+        return Vec::new();
+    };
+    let rest = pending.split_off(last_newline + 1);
+    let complete = std::mem::replace(pending, rest);
+    complete
+        .trim_end_matches('\n')
+        .split('\n')
+        .map(|line| line.trim_end_matches('\r').to_string())
+        .collect()
+}
+
 /// System to initialize Lua context
 fn setup_lua_context(
     mut commands: Commands,
@@ -1555,6 +2320,17 @@ fn setup_lua_context(
     script_instance: Res<crate::script_entities::ScriptInstance>,
     script_registry: Res<crate::script_registry::ScriptRegistry>,
     table_resource_registry: Res<crate::lua_resource::LuaTableResourceRegistry>,
+    (cache_config, stdlib_extensions, multi_context): (
+        Option<Res<crate::script_cache::ScriptCacheConfig>>,
+        Option<Res<crate::lua_stdlib::LuaStdlibExtensions>>,
+        Option<Res<crate::lua_sandbox::MultiLuaContextPlugin>>,
+    ),
+    serde_registry: Res<crate::serde_components::SerdeComponentRegistry>,
+    observer_registry: Res<crate::lua_observers::LuaObserverRegistry>,
+    (os_utilities, bitflags_registry): (
+        Option<Res<crate::os_utilities::OsUtilities>>,
+        Res<crate::bitflags_registry::BitflagsRegistry>,
+    ),
 ) {
     let system_registry = LuaSystemRegistry::default();
 
@@ -1583,16 +2359,69 @@ fn setup_lua_context(
         script_registry.clone(),
         table_resource_registry.clone(),
     ) {
-        Ok(ctx) => {
-            // Add asset loading to Lua
-            if let Err(e) = crate::asset_loading::add_asset_loading_to_lua(
-                &ctx,
-                asset_server.clone(),
-                asset_registry.clone(),
-            ) {
-                error!("Failed to add asset loading to Lua: {}", e);
+        Ok(mut ctx) => {
+            // Apply optional module cache capacity override
+            if let Some(config) = cache_config {
+                ctx.script_cache.set_capacity(config.capacity);
             }
 
+            // Setup shared by the primary state and every sandbox state
+            let serde_registry = serde_registry.clone();
+            let observer_registry = observer_registry.clone();
+            let os_utilities = os_utilities.as_deref().cloned().unwrap_or_default();
+            let bitflags_registry = bitflags_registry.clone();
+            let setup_despawn_queue = despawn_queue.clone();
+            let script_cache = ctx.script_cache.clone();
+            let setup_asset_server = asset_server.clone();
+            let setup_asset_registry = asset_registry.clone();
+            let state_setup: LuaStateSetup = Arc::new(move |lua: &Lua| {
+                // Lua-defined component schemas are validated at set-time from Lua callbacks
+                lua.set_app_data(serde_registry.clone());
+                // Observer callbacks can be removed at runtime via entity:remove_observer
+                lua.set_app_data(observer_registry.clone());
+                // File helpers (read_file_bytes, write_file_bytes, ...) honour the sandbox root
+                lua.set_app_data(os_utilities.clone());
+                // flags()/flags_to_string() resolve names through the bitflags registry
+                lua.set_app_data(bitflags_registry.clone());
+                // entity:despawn()/despawn_single() queue through the shared despawn queue
+                lua.set_app_data(setup_despawn_queue.clone());
+                crate::bitflags_registry::add_bitflags_functions_to_lua(lua)?;
+
+                // Add asset loading to Lua
+                crate::asset_loading::add_asset_loading_to_lua_state(
+                    lua,
+                    script_cache.clone(),
+                    setup_asset_server.clone(),
+                    setup_asset_registry.clone(),
+                )?;
+
+                // Sandbox states replay the stdlib helpers already checked in the primary state
+                if let Some((chunk_name, source)) = script_cache.stdlib_chunk() {
+                    lua.load(&source).set_name(chunk_name).exec()?;
+                }
+                Ok(())
+            });
+            if let Err(e) = state_setup(&ctx.lua) {
+                error!("Failed to set up Lua state: {}", e);
+            }
+
+            // Project helpers must exist before any user script runs
+            if let Some(extensions) = stdlib_extensions {
+                match crate::lua_stdlib::load_stdlib_extensions(&ctx.lua, &extensions.full_path()) {
+                    Ok(source) => ctx.script_cache.set_stdlib_chunk(
+                        crate::lua_stdlib::stdlib_chunk_name(&extensions.full_path()),
+                        source,
+                    ),
+                    Err(e) => {
+                        panic!("LuaSpawnPlugin: could not load Lua stdlib extensions '{}': {}", extensions.path, e)
+                    }
+                }
+            }
+
+            ctx.set_state_setup(state_setup);
+            ctx.set_sandbox_instances(multi_context.is_some());
+
+            commands.insert_resource(ctx.instance_pool.clone());
             commands.insert_resource(ctx);
             commands.insert_resource(system_registry);
             commands.insert_resource(asset_registry);
@@ -1603,443 +2432,527 @@ fn setup_lua_context(
     }
 }
 
+/// Try to hot-reload a script by re-defining only its changed top-level functions
+/// Returns false when a full reload is required (top-level code changed, local functions
+/// changed, or re-definition failed). Chunk hashes are recorded in ScriptCache either way.
+fn try_diff_reload(
+    lua_ctx: &LuaScriptContext,
+    script_path: &str,
+    old_content: &str,
+    new_content: &str,
+    instance_id: u64,
+) -> bool {
+    use crate::lua_file_watcher::{chunk_hashes, plan_diff_reload, split_lua_chunks, HotReloadPlan};
+
+    let old_hashes = lua_ctx
+        .script_cache
+        .get_chunk_hashes(script_path, instance_id)
+        .unwrap_or_else(|| chunk_hashes(&split_lua_chunks(old_content)));
+    let new_chunks = split_lua_chunks(new_content);
+    lua_ctx
+        .script_cache
+        .set_chunk_hashes(script_path.to_string(), instance_id, chunk_hashes(&new_chunks));
+
+    let changed = match plan_diff_reload(&old_hashes, &new_chunks) {
+        HotReloadPlan::Unchanged => {
+            debug!("🔄 [HOT_RELOAD] '{}' unchanged (diff mode), skipping reload", script_path);
+            return true;
+        }
+        HotReloadPlan::Full => return false,
+        HotReloadPlan::Functions(changed) => changed,
+    };
+
+    // Run re-definitions in the script's own context (its sandbox state, if any), and in its
+    // own environment when it was started with parameters
+    let lua = lua_ctx.instance_lua(instance_id);
+    let environment = lua_ctx.instance_environment_table(instance_id);
+    let globals = lua.globals();
+    let previous_instance_id: Option<u64> = globals.get("__INSTANCE_ID__").ok();
+    let previous_script_name: Option<String> = globals.get("__SCRIPT_NAME__").ok();
+    let _ = globals.set("__INSTANCE_ID__", instance_id);
+    let _ = globals.set("__SCRIPT_NAME__", script_path);
+
+    let mut success = true;
+    for chunk in &changed {
+        let mut loaded = lua.load(chunk.source.as_str()).set_name(format!("@{}", script_path));
+        if let Some(environment) = &environment {
+            loaded = loaded.set_environment(environment.clone());
+        }
+        if let Err(e) = loaded.exec() {
+            warn!(
+                "🔄 [HOT_RELOAD] Failed to re-define '{}' in '{}', falling back to full reload: {}",
+                chunk.name, script_path, e
+            );
+            success = false;
+            break;
+        }
+    }
+
+    if let Some(prev_id) = previous_instance_id {
+        let _ = globals.set("__INSTANCE_ID__", prev_id);
+    }
+    if let Some(prev_name) = previous_script_name {
+        let _ = globals.set("__SCRIPT_NAME__", prev_name);
+    }
+
+    if success {
+        debug!(
+            "🔄 [HOT_RELOAD] Re-defined {} function(s) in '{}' (diff mode)",
+            changed.len(),
+            script_path
+        );
+    }
+    success
+}
+
 /// System that automatically reloads scripts when file changes are detected
 fn auto_reload_changed_scripts(
     mut events: MessageReader<crate::lua_file_watcher::LuaFileChangeEvent>,
     script_registry: Res<crate::script_registry::ScriptRegistry>,
     lua_ctx: Res<LuaScriptContext>,
     script_instance: Res<crate::script_entities::ScriptInstance>,
+    hot_reload_config: Option<Res<crate::lua_file_watcher::LuaHotReloadConfig>>,
     world: &World,
 ) {
+    let hot_reload_diff = hot_reload_config.is_some_and(|config| config.hot_reload_diff);
     for event in events.read() {
-        // Normalize path to forward slashes for consistent comparison
-        let event_path_str = to_forward_slash(&event.path);
+        // Events carry asset-server paths (e.g. "scripts/examples/foo.lua"); older producers
+        // that still send "assets/..." are normalized the same way
+        let module_path = crate::path_utils::to_asset_server_path(&event.path);
+        // The script registry keys scripts by their on-disk path
+        let script_path = std::path::Path::new("assets").join(&module_path);
         debug!(
-            "🔄 [HOT_RELOAD] File change event received: {} (normalized: {})",
+            "🔄 [HOT_RELOAD] File change event received: {} (asset path: {})",
             event.path.display(),
-            event_path_str
+            module_path
         );
 
         let mut reloaded_paths = std::collections::HashSet::new();
 
-        // Check if this is a module file (.lua file in assets/)
-        // Invalidate module cache if it's a module
+        // Invalidate the module cache if it's a module
         // Support scripts in any location under assets/, not just assets/scripts/
-        debug!(
-            "🔍 [HOT_RELOAD] Checking path: starts_with('assets/')={}, ends_with('.lua')={}",
-            event_path_str.starts_with("assets/"),
-            event_path_str.ends_with(".lua")
-        );
-        if event_path_str.starts_with("assets/") && event_path_str.ends_with(".lua") {
-            // Get relative path from assets/ (keeps full subpath like "scripts/examples/foo.lua")
-            if let Some(module_path_str) = event_path_str.strip_prefix("assets/") {
-                // Convert to owned String for use in cache operations
-                let module_path = module_path_str.to_string();
-
-                // Invalidate the module cache and get all dependent scripts
-                debug!(
-                    "🔄 [HOT_RELOAD] Module path: '{}', invalidating cache...",
-                    module_path
-                );
-                let invalidated = lua_ctx.script_cache.invalidate_module(&module_path);
-                debug!("🔄 [HOT_RELOAD] Invalidated modules: {:?}", invalidated);
-
-                if !invalidated.is_empty() {
-                    debug!("Invalidated module cache for: {:?}", invalidated);
-
-                    // Get the invalidate function once
-                    let invalidate_fn_result = lua_ctx.lua.globals().get::<LuaFunction>("__invalidate_module_cache__");
-
-                    // Invalidate source cache for ALL invalidated modules to force fresh disk read
-                    for invalidated_path in &invalidated {
-                        lua_ctx.script_cache.invalidate_source_cache(invalidated_path);
-
-                        // Check if this module has any reload=false callbacks
-                        // If ALL callbacks have reload=false, skip Lua cache invalidation to preserve state
-                        let callbacks = lua_ctx.script_cache.get_hot_reload_callbacks(invalidated_path);
-                        let all_non_reloadable = !callbacks.is_empty() &&
-                            callbacks.iter().all(|(_, _, should_invoke, _)| !should_invoke);
-
-                        // Invalidate Lua-level module cache unless ALL callbacks have reload=false
-                        if !all_non_reloadable {
-                            if let Ok(ref invalidate_fn) = invalidate_fn_result {
-                                if let Err(e) = invalidate_fn.call::<()>(invalidated_path.clone()) {
-                                    warn!("Failed to invalidate Lua module cache for '{}': {}", invalidated_path, e);
-                                }
+        if module_path.ends_with(".lua") {
+            // Invalidate the module cache and get all dependent scripts
+            debug!(
+                "🔄 [HOT_RELOAD] Module path: '{}', invalidating cache...",
+                module_path
+            );
+            let invalidated = lua_ctx.script_cache.invalidate_module(&module_path);
+            debug!("🔄 [HOT_RELOAD] Invalidated modules: {:?}", invalidated);
+
+            if !invalidated.is_empty() {
+                debug!("Invalidated module cache for: {:?}", invalidated);
+
+                // Get the invalidate function once
+                let invalidate_fn_result = lua_ctx.lua.globals().get::<LuaFunction>("__invalidate_module_cache__");
+
+                // Invalidate source cache for ALL invalidated modules to force fresh disk read
+                for invalidated_path in &invalidated {
+                    lua_ctx.script_cache.invalidate_source_cache(invalidated_path);
+
+                    // Check if this module has any reload=false callbacks
+                    // If ALL callbacks have reload=false, skip Lua cache invalidation to preserve state
+                    let callbacks = lua_ctx.script_cache.get_hot_reload_callbacks(invalidated_path);
+                    let all_non_reloadable = !callbacks.is_empty() &&
+                        callbacks.iter().all(|(_, _, should_invoke, _)| !should_invoke);
+
+                    // Invalidate Lua-level module cache unless ALL callbacks have reload=false
+                    if !all_non_reloadable {
+                        if let Ok(ref invalidate_fn) = invalidate_fn_result {
+                            if let Err(e) = invalidate_fn.call::<()>(invalidated_path.clone()) {
+                                warn!("Failed to invalidate Lua module cache for '{}': {}", invalidated_path, e);
                             }
-                        } else {
-                            debug!(
-                                "Skipping Lua cache invalidation for '{}' (all callbacks have reload=false)",
-                                invalidated_path
-                            );
                         }
-
-                        // NOTE: We do NOT clear caches for dependencies (modules this one imports).
-                        // Dependencies only need to reload when THEY change, not when a module
-                        // that imports them changes. Clearing dependencies causes state loss
-                        // in modules like net_role.lua that hold session state.
+                    } else {
+                        debug!(
+                            "Skipping Lua cache invalidation for '{}' (all callbacks have reload=false)",
+                            invalidated_path
+                        );
                     }
 
-                    // Update source cache for the changed module
-                    // This ensures hot reload uses the new source
-                    if let Ok((new_source, _)) =
-                        lua_ctx.script_cache.load_module_source(&module_path)
-                    {
-                        // load_module_source already updated the cache, but we force it here to be explicit
-                        lua_ctx.script_cache.update_source(&module_path, new_source);
-                    }
+                    // NOTE: We do NOT clear caches for dependencies (modules this one imports).
+                    // Dependencies only need to reload when THEY change, not when a module
+                    // that imports them changes. Clearing dependencies causes state loss
+                    // in modules like net_role.lua that hold session state.
+                }
 
-                    // For each invalidated module, reload scripts that depend on it
-                    for invalidated_path in &invalidated {
-                        // Find and reload any main scripts that imported this module
-                        // We need to construct the full path for the script registry lookup
-                        // invalidated_path includes the scripts/ prefix (e.g., "scripts/examples/foo.lua")
-                        let full_path = std::path::Path::new("assets").join(invalidated_path);
+                // Update source cache for the changed module
+                // This ensures hot reload uses the new source
+                if let Ok((new_source, _)) =
+                    lua_ctx.script_cache.load_module_source(&module_path)
+                {
+                    // load_module_source already updated the cache, but we force it here to be explicit
+                    lua_ctx.script_cache.update_source(&module_path, new_source);
+                }
 
-                        // Get active instances for this dependent script
-                        let instances = script_registry.get_active_instances(&full_path);
+                // For each invalidated module, reload scripts that depend on it
+                for invalidated_path in &invalidated {
+                    // Find and reload any main scripts that imported this module
+                    // We need to construct the full path for the script registry lookup
+                    // invalidated_path includes the scripts/ prefix (e.g., "scripts/examples/foo.lua")
+                    let full_path = std::path::Path::new("assets").join(invalidated_path);
 
-                        if !instances.is_empty() {
-                            debug!(
-                                "Reloading {} dependent instance(s) of '{}'",
-                                instances.len(),
-                                invalidated_path
-                            );
+                    // Get active instances for this dependent script
+                    let instances = script_registry.get_active_instances(&full_path);
 
-                            // Read script content
-                            let script_content = match std::fs::read_to_string(&full_path) {
-                                Ok(c) => c,
-                                Err(e) => {
-                                    error!(
-                                        "Failed to read dependent script {:?}: {}",
-                                        full_path, e
-                                    );
-                                    continue;
-                                }
-                            };
+                    if !instances.is_empty() {
+                        debug!(
+                            "Reloading {} dependent instance(s) of '{}'",
+                            instances.len(),
+                            invalidated_path
+                        );
 
-                            for (instance_id, _old_content) in instances {
-                                cleanup_script_instance(instance_id, world, true, true); // Recursive, skip resources: hot-reload
+                        // Read script content
+                        let script_content = match std::fs::read_to_string(&full_path) {
+                            Ok(c) => c,
+                            Err(e) => {
+                                error!(
+                                    "Failed to read dependent script {:?}: {}",
+                                    full_path, e
+                                );
+                                continue;
+                            }
+                        };
 
-                                // NOTE: We do NOT clear caches for dependencies here.
-                                // Dependencies keep their cached state - they only reload when THEY change.
+                        for (instance_id, old_content) in instances {
+                            // Diff mode: re-define only changed functions, keeping entities and globals
+                            if hot_reload_diff
+                                && try_diff_reload(&lua_ctx, invalidated_path, &old_content, &script_content, instance_id)
+                            {
+                                script_registry.register_script(
+                                    full_path.clone(),
+                                    instance_id,
+                                    script_content.clone(),
+                                );
+                                continue;
+                            }
 
-                                // IMPORTANT: Reuse the old instance_id to preserve resources scoped by instance_id
-                                match lua_ctx.execute_script_tracked_with_id(
-                                    &script_content,
-                                    invalidated_path,
-                                    &script_instance,
-                                    Some(instance_id),
-                                ) {
-                                    Ok(reused_id) => {
-                                        debug!(
-                                            "✓ Reloaded dependent instance {} (reused) for '{}'",
-                                            reused_id, invalidated_path
-                                        );
-                                        // Re-register with same instance_id (content may have changed)
-                                        script_registry.register_script(
-                                            full_path.clone(),
-                                            reused_id,
-                                            script_content.clone(),
-                                        );
-                                        // Don't remove_instance since we're reusing the same ID
-                                    }
-                                    Err(e) => error!(
-                                        "Failed to reload dependent script '{}': {}",
-                                        invalidated_path, e
-                                    ),
+                            // Observers on entities that survive the reload keep unchanged callbacks
+                            crate::observer_persistence::snapshot_instance_observers(world, |key| lua_ctx.state_for_key(key), instance_id);
+                            cleanup_script_instance(instance_id, world, true, true); // Recursive, skip resources: hot-reload
+                            crate::observer_persistence::restore_instance_observers(world, |key| lua_ctx.state_for_key(key), instance_id, &script_content);
+
+                            // NOTE: We do NOT clear caches for dependencies here.
+                            // Dependencies keep their cached state - they only reload when THEY change.
+
+                            // IMPORTANT: Reuse the old instance_id to preserve resources scoped by instance_id
+                            match lua_ctx.execute_script_tracked_with_id(
+                                &script_content,
+                                invalidated_path,
+                                &script_instance,
+                                Some(instance_id),
+                            ) {
+                                Ok(reused_id) => {
+                                    debug!(
+                                        "✓ Reloaded dependent instance {} (reused) for '{}'",
+                                        reused_id, invalidated_path
+                                    );
+                                    // Re-register with same instance_id (content may have changed)
+                                    script_registry.register_script(
+                                        full_path.clone(),
+                                        reused_id,
+                                        script_content.clone(),
+                                    );
+                                    // Don't remove_instance since we're reusing the same ID
                                 }
+                                Err(e) => error!(
+                                    "Failed to reload dependent script '{}': {}",
+                                    invalidated_path, e
+                                ),
                             }
-                            reloaded_paths.insert(full_path);
                         }
+                        reloaded_paths.insert(full_path);
                     }
                 }
+            }
 
-                // Trigger hot reload callbacks for ALL invalidated modules (not just the changed file)
-                // This handles transitive dependencies: if A requires B, changing B should trigger A's callbacks
-                for reload_module_path in &invalidated {
-                    let callbacks = lua_ctx.script_cache.get_hot_reload_callbacks(reload_module_path);
-                    if callbacks.is_empty() {
-                        continue;
-                    }
+            // Trigger hot reload callbacks for ALL invalidated modules (not just the changed file)
+            // This handles transitive dependencies: if A requires B, changing B should trigger A's callbacks
+            for reload_module_path in &invalidated {
+                let callbacks = lua_ctx.script_cache.get_hot_reload_callbacks(reload_module_path);
+                if callbacks.is_empty() {
+                    continue;
+                }
 
+                debug!(
+                    "Triggering {} hot reload callbacks for '{}'",
+                    callbacks.len(),
+                    reload_module_path
+                );
+
+                // Check if ALL callbacks have should_invoke_callback=false (reload=false)
+                // If so, skip cleanup entirely to preserve systems/entities/resources
+                let all_non_reloadable = callbacks.iter().all(|(_, _, should_invoke, _)| !should_invoke);
+
+                if all_non_reloadable {
                     debug!(
-                        "Triggering {} hot reload callbacks for '{}'",
-                        callbacks.len(),
+                        "Hot reload: Skipping cleanup for '{}' (all callbacks have reload=false)",
                         reload_module_path
                     );
 
-                    // Check if ALL callbacks have should_invoke_callback=false (reload=false)
-                    // If so, skip cleanup entirely to preserve systems/entities/resources
-                    let all_non_reloadable = callbacks.iter().all(|(_, _, should_invoke, _)| !should_invoke);
+                    // Still update module cache so proxies point to new code
+                    if let Ok((source, _)) = lua_ctx.script_cache.load_module_source(reload_module_path) {
+                        let module_name = format!("@{}", reload_module_path);
+                        
+                        // Save old instance_ids for reuse (same as normal path)
+                        let mut old_instance_id_map: HashMap<u64, u64> = HashMap::new();
+                        for (_, parent_instance_id, _, _) in &callbacks {
+                            if let Some(old_id) = lua_ctx.script_cache.get_module_instance(reload_module_path, *parent_instance_id) {
+                                old_instance_id_map.insert(*parent_instance_id, old_id);
+                            }
+                        }
+
+                        for (_, parent_instance_id, _, state_id) in &callbacks {
+                            // Re-execute in the state that required the module
+                            let lua = lua_ctx.get_lua_state(*state_id);
 
-                    if all_non_reloadable {
-                        debug!(
-                            "Hot reload: Skipping cleanup for '{}' (all callbacks have reload=false)",
-                            reload_module_path
-                        );
+                            // Save current globals to restore after
+                            let previous_state_id: usize =
+                                lua.globals().get("__LUA_STATE_ID__").unwrap_or(0);
+                            let previous_instance_id: Option<u64> =
+                                lua.globals().get("__INSTANCE_ID__").ok();
 
-                        // Still update module cache so proxies point to new code
-                        if let Ok((source, _)) = lua_ctx.script_cache.load_module_source(reload_module_path) {
-                            let module_name = format!("@{}", reload_module_path);
+                            // Set __LUA_STATE_ID__ to the callback's state_id before executing
+                            if let Err(e) = lua.globals().set("__LUA_STATE_ID__", *state_id) {
+                                error!("Failed to set __LUA_STATE_ID__ for reload=false hot reload: {}", e);
+                                continue;
+                            }
                             
-                            // Save old instance_ids for reuse (same as normal path)
-                            let mut old_instance_id_map: HashMap<u64, u64> = HashMap::new();
-                            for (_, parent_instance_id, _, _) in &callbacks {
-                                if let Some(old_id) = lua_ctx.script_cache.get_module_instance(reload_module_path, *parent_instance_id) {
-                                    old_instance_id_map.insert(*parent_instance_id, old_id);
-                                }
+                            // CRITICAL: Set __INSTANCE_ID__ using the reused old instance_id
+                            let module_instance_id = old_instance_id_map.get(parent_instance_id).copied().unwrap_or(0);
+                            if let Err(e) = lua.globals().set("__INSTANCE_ID__", module_instance_id) {
+                                error!("Failed to set __INSTANCE_ID__ for reload=false hot reload: {}", e);
+                                continue;
                             }
 
-                            for (_, parent_instance_id, _, state_id) in &callbacks {
-                                // Save current globals to restore after
-                                let previous_state_id: usize =
-                                    lua_ctx.lua.globals().get("__LUA_STATE_ID__").unwrap_or(0);
-                                let previous_instance_id: Option<u64> =
-                                    lua_ctx.lua.globals().get("__INSTANCE_ID__").ok();
-
-                                // Set __LUA_STATE_ID__ to the callback's state_id before executing
-                                if let Err(e) = lua_ctx.lua.globals().set("__LUA_STATE_ID__", *state_id) {
-                                    error!("Failed to set __LUA_STATE_ID__ for reload=false hot reload: {}", e);
-                                    continue;
-                                }
-                                
-                                // CRITICAL: Set __INSTANCE_ID__ using the reused old instance_id
-                                let module_instance_id = old_instance_id_map.get(parent_instance_id).copied().unwrap_or(0);
-                                if let Err(e) = lua_ctx.lua.globals().set("__INSTANCE_ID__", module_instance_id) {
-                                    error!("Failed to set __INSTANCE_ID__ for reload=false hot reload: {}", e);
-                                    continue;
-                                }
-
-                                // Execute module to get new code
-                                match crate::script_cache::execute_module(
-                                    &lua_ctx.lua,
-                                    &source,
-                                    &module_name,
-                                ) {
-                                    Ok(result) => {
-                                        // Update Rust-side cache
-                                        if let Ok(registry_key) =
-                                            lua_ctx.lua.create_registry_value(result.clone())
-                                        {
-                                            lua_ctx.script_cache.cache_module(
-                                                reload_module_path.clone(),
-                                                *state_id,
-                                                Arc::new(registry_key),
-                                            );
-                                        }
-
-                                        // Update Lua-side cache (__MODULE_CACHE__)
-                                        // This makes proxies point to new module code
-                                        let cache_key = format!("{}::{}", reload_module_path, state_id);
-                                        if let Ok(module_cache) = lua_ctx.lua.globals().get::<LuaTable>("__MODULE_CACHE__") {
-                                            let _ = module_cache.set(cache_key, result);
-                                        }
-                                    }
-                                    Err(e) => {
-                                        error!(
-                                            "Failed to execute module '{}' during hot reload (reload=false): {}",
-                                            reload_module_path, e
+                            // Execute module to get new code
+                            match crate::script_cache::execute_module(
+                                &lua,
+                                &source,
+                                &module_name,
+                            ) {
+                                Ok(result) => {
+                                    // Update Rust-side cache
+                                    if let Ok(registry_key) =
+                                        lua.create_registry_value(result.clone())
+                                    {
+                                        lua_ctx.script_cache.cache_module(
+                                            reload_module_path.clone(),
+                                            *state_id,
+                                            Arc::new(registry_key),
                                         );
                                     }
-                                }
 
-                                // Restore previous globals
-                                let _ = lua_ctx.lua.globals().set("__LUA_STATE_ID__", previous_state_id);
-                                if let Some(prev_id) = previous_instance_id {
-                                    let _ = lua_ctx.lua.globals().set("__INSTANCE_ID__", prev_id);
+                                    // Update Lua-side cache (__MODULE_CACHE__)
+                                    // This makes proxies point to new module code
+                                    let cache_key = format!("{}::{}", reload_module_path, state_id);
+                                    if let Ok(module_cache) = lua.globals().get::<LuaTable>("__MODULE_CACHE__") {
+                                        let _ = module_cache.set(cache_key, result);
+                                    }
+                                }
+                                Err(e) => {
+                                    error!(
+                                        "Failed to execute module '{}' during hot reload (reload=false): {}",
+                                        reload_module_path, e
+                                    );
                                 }
                             }
-                        }
 
-                        continue; // Skip cleanup and callback invocation
+                            // Restore previous globals
+                            let _ = lua.globals().set("__LUA_STATE_ID__", previous_state_id);
+                            if let Some(prev_id) = previous_instance_id {
+                                let _ = lua.globals().set("__INSTANCE_ID__", prev_id);
+                            }
+                        }
                     }
 
-                    // Normal flow: get instances and cleanup
-                    let old_instance_ids =
-                        lua_ctx.script_cache.get_all_module_instances(reload_module_path);
-
-                    // For each module instance, also get all its descendants (nested requires)
-                    // This ensures we clean up entities spawned in nested callbacks
-                    let mut all_instances_to_cleanup = HashSet::new();
-                    for old_id in &old_instance_ids {
-                        all_instances_to_cleanup.insert(*old_id);
-                        // Add all descendants (modules loaded within this instance's callbacks)
-                        let descendants =
-                            lua_ctx.script_cache.get_all_descendant_instances(*old_id);
-                        all_instances_to_cleanup.extend(descendants);
+                    continue; // Skip cleanup and callback invocation
+                }
+
+                // Normal flow: get instances and cleanup
+                let old_instance_ids =
+                    lua_ctx.script_cache.get_all_module_instances(reload_module_path);
+
+                // For each module instance, also get all its descendants (nested requires)
+                // This ensures we clean up entities spawned in nested callbacks
+                let mut all_instances_to_cleanup = HashSet::new();
+                for old_id in &old_instance_ids {
+                    all_instances_to_cleanup.insert(*old_id);
+                    // Add all descendants (modules loaded within this instance's callbacks)
+                    let descendants =
+                        lua_ctx.script_cache.get_all_descendant_instances(*old_id);
+                    all_instances_to_cleanup.extend(descendants);
+                }
+                
+                // IMPORTANT: Save old instance_ids BEFORE clearing, so we can reuse them
+                // This ensures resources scoped by instance_id persist across hot-reloads
+                let mut old_instance_id_map: HashMap<u64, u64> = HashMap::new();
+                for (callback_key, parent_instance_id, _, _) in &callbacks {
+                    if let Some(old_id) = lua_ctx.script_cache.get_module_instance(reload_module_path, *parent_instance_id) {
+                        old_instance_id_map.insert(*parent_instance_id, old_id);
+                        debug!(
+                            "Hot reload: Saved old instance_id {} for parent {} (will reuse)",
+                            old_id, parent_instance_id
+                        );
                     }
-                    
-                    // IMPORTANT: Save old instance_ids BEFORE clearing, so we can reuse them
-                    // This ensures resources scoped by instance_id persist across hot-reloads
-                    let mut old_instance_id_map: HashMap<u64, u64> = HashMap::new();
-                    for (callback_key, parent_instance_id, _, _) in &callbacks {
-                        if let Some(old_id) = lua_ctx.script_cache.get_module_instance(reload_module_path, *parent_instance_id) {
-                            old_instance_id_map.insert(*parent_instance_id, old_id);
-                            debug!(
-                                "Hot reload: Saved old instance_id {} for parent {} (will reuse)",
-                                old_id, parent_instance_id
-                            );
-                        }
+                }
+
+                // Clean up entities from ALL previous module instances and their descendants
+                if !all_instances_to_cleanup.is_empty() {
+                    debug!("Hot reload: Cleaning up {} total instance(s) for '{}' (including descendants): {:?}",
+                        all_instances_to_cleanup.len(), reload_module_path, all_instances_to_cleanup);
+                    for old_id in &all_instances_to_cleanup {
+                        debug!(
+                            "Hot reload: Cleaning up instance {} for '{}' or its descendants",
+                            old_id, reload_module_path
+                        );
+                        cleanup_script_instance(*old_id, world, false, true); // Non-recursive, skip resources: hot-reload
                     }
+                } else {
+                    warn!(
+                        "Hot reload: No module instances found for '{}'",
+                        reload_module_path
+                    );
+                }
 
-                    // Clean up entities from ALL previous module instances and their descendants
-                    if !all_instances_to_cleanup.is_empty() {
-                        debug!("Hot reload: Cleaning up {} total instance(s) for '{}' (including descendants): {:?}",
-                            all_instances_to_cleanup.len(), reload_module_path, all_instances_to_cleanup);
-                        for old_id in &all_instances_to_cleanup {
+                // Clear all instance mappings for this module (they'll be recreated when we re-execute)
+                lua_ctx.script_cache.clear_module_instances(reload_module_path);
+
+                // Load the module source (uses cache if unchanged, disk if new)
+                if let Ok((source, _)) = lua_ctx.script_cache.load_module_source(reload_module_path) {
+                    // Note: source cache was already updated by load_module_source if it read from disk
+                    let module_name = format!("@{}", reload_module_path);
+
+                    // Execute module and call callbacks - each with its parent's __INSTANCE_ID__ context
+                    // We need to execute the module separately for each parent because module execution
+                    // has side effects (nested require_async calls) that depend on __INSTANCE_ID__
+                    for (callback_key, parent_instance_id, should_invoke_callback, state_id) in callbacks {
+                        // Check if parent instance was cleaned up (it might be in all_instances_to_cleanup)
+                        // If so, skip this callback to avoid creating instances with stale parents
+                        if all_instances_to_cleanup.contains(&parent_instance_id) {
                             debug!(
-                                "Hot reload: Cleaning up instance {} for '{}' or its descendants",
-                                old_id, reload_module_path
+                                "Hot reload: Skipping callback for parent {} (was cleaned up)",
+                                parent_instance_id
                             );
-                            cleanup_script_instance(*old_id, world, false, true); // Non-recursive, skip resources: hot-reload
+                            continue;
                         }
-                    } else {
-                        warn!(
-                            "Hot reload: No module instances found for '{}'",
-                            reload_module_path
-                        );
-                    }
-
-                    // Clear all instance mappings for this module (they'll be recreated when we re-execute)
-                    lua_ctx.script_cache.clear_module_instances(reload_module_path);
-
-                    // Load the module source (uses cache if unchanged, disk if new)
-                    if let Ok((source, _)) = lua_ctx.script_cache.load_module_source(reload_module_path) {
-                        // Note: source cache was already updated by load_module_source if it read from disk
-                        let module_name = format!("@{}", reload_module_path);
 
-                        // Execute module and call callbacks - each with its parent's __INSTANCE_ID__ context
-                        // We need to execute the module separately for each parent because module execution
-                        // has side effects (nested require_async calls) that depend on __INSTANCE_ID__
-                        for (callback_key, parent_instance_id, should_invoke_callback, state_id) in callbacks {
-                            // Check if parent instance was cleaned up (it might be in all_instances_to_cleanup)
-                            // If so, skip this callback to avoid creating instances with stale parents
-                            if all_instances_to_cleanup.contains(&parent_instance_id) {
-                                debug!(
-                                    "Hot reload: Skipping callback for parent {} (was cleaned up)",
-                                    parent_instance_id
-                                );
+                        // Re-execute in the state that registered the callback
+                        let lua = lua_ctx.state_for_key(&callback_key);
+                        if let Ok(callback) = lua.registry_value::<LuaFunction>(&*callback_key)
+                        {
+                            // Save current globals to restore after
+                            let previous_instance_id: Option<u64> =
+                                lua.globals().get("__INSTANCE_ID__").ok();
+                            let previous_state_id: usize =
+                                lua.globals().get("__LUA_STATE_ID__").unwrap_or(0);
+
+                            // CRITICAL: Restore __LUA_STATE_ID__ to the original value from when
+                            // this callback was registered. This ensures module caches are looked
+                            // up with the correct state_id, preserving instanced isolation.
+                            if let Err(e) = lua.globals().set("__LUA_STATE_ID__", state_id) {
+                                error!("Failed to set __LUA_STATE_ID__ for hot reload: {}", e);
                                 continue;
                             }
+                            debug!(
+                                "Hot reload: Restored __LUA_STATE_ID__={} for '{}'",
+                                state_id, reload_module_path
+                            );
 
-                            if let Ok(callback) =
-                                lua_ctx.lua.registry_value::<LuaFunction>(&*callback_key)
-                            {
-                                // Save current globals to restore after
-                                let previous_instance_id: Option<u64> =
-                                    lua_ctx.lua.globals().get("__INSTANCE_ID__").ok();
-                                let previous_state_id: usize =
-                                    lua_ctx.lua.globals().get("__LUA_STATE_ID__").unwrap_or(0);
-
-                                // CRITICAL: Restore __LUA_STATE_ID__ to the original value from when
-                                // this callback was registered. This ensures module caches are looked
-                                // up with the correct state_id, preserving instanced isolation.
-                                if let Err(e) = lua_ctx.lua.globals().set("__LUA_STATE_ID__", state_id) {
-                                    error!("Failed to set __LUA_STATE_ID__ for hot reload: {}", e);
-                                    continue;
-                                }
+                            // REUSE old module instance_id if we have one, otherwise create new
+                            // This ensures resources scoped by instance_id persist across hot-reloads
+                            let module_instance_id = if let Some(old_id) = old_instance_id_map.get(&parent_instance_id) {
                                 debug!(
-                                    "Hot reload: Restored __LUA_STATE_ID__={} for '{}'",
-                                    state_id, reload_module_path
+                                    "Hot reload: Reusing old instance {} for '{}' with parent {}",
+                                    old_id, reload_module_path, parent_instance_id
                                 );
-
-                                // REUSE old module instance_id if we have one, otherwise create new
-                                // This ensures resources scoped by instance_id persist across hot-reloads
-                                let module_instance_id = if let Some(old_id) = old_instance_id_map.get(&parent_instance_id) {
-                                    debug!(
-                                        "Hot reload: Reusing old instance {} for '{}' with parent {}",
-                                        old_id, reload_module_path, parent_instance_id
-                                    );
-                                    *old_id
-                                } else {
-                                    // No old instance found (first load or cleaned up parent), create new
-                                    let new_id = script_instance.start(reload_module_path.clone());
-                                    debug!(
-                                        "Hot reload: Created new instance {} for '{}' with parent {} (no old instance)",
-                                        new_id, reload_module_path, parent_instance_id
-                                    );
-                                    new_id
-                                };
-                                
-                                lua_ctx.script_cache.set_module_instance(
-                                    reload_module_path.clone(),
-                                    parent_instance_id,
-                                    module_instance_id,
+                                *old_id
+                            } else {
+                                // No old instance found (first load or cleaned up parent), create new
+                                let new_id = script_instance.start(reload_module_path.clone());
+                                debug!(
+                                    "Hot reload: Created new instance {} for '{}' with parent {} (no old instance)",
+                                    new_id, reload_module_path, parent_instance_id
                                 );
-                                lua_ctx
-                                    .script_cache
-                                    .set_module_parent(module_instance_id, parent_instance_id);
-
-                                // Set __INSTANCE_ID__ to the MODULE's instance before executing
-                                // This ensures nested require_async calls use correct parent
-                                if let Err(e) = lua_ctx
-                                    .lua
-                                    .globals()
-                                    .set("__INSTANCE_ID__", module_instance_id)
-                                {
-                                    error!("Failed to set module __INSTANCE_ID__: {}", e);
-                                    continue;
-                                }
+                                new_id
+                            };
+                            
+                            lua_ctx.script_cache.set_module_instance(
+                                reload_module_path.clone(),
+                                parent_instance_id,
+                                module_instance_id,
+                            );
+                            lua_ctx
+                                .script_cache
+                                .set_module_parent(module_instance_id, parent_instance_id);
+
+                            // Set __INSTANCE_ID__ to the MODULE's instance before executing
+                            // This ensures nested require_async calls use correct parent
+                            if let Err(e) = lua
+                                .globals()
+                                .set("__INSTANCE_ID__", module_instance_id)
+                            {
+                                error!("Failed to set module __INSTANCE_ID__: {}", e);
+                                continue;
+                            }
 
-                                // Execute the module with this instance context
-                                match crate::script_cache::execute_module(
-                                    &lua_ctx.lua,
-                                    &source,
-                                    &module_name,
-                                ) {
-                                    Ok(result) => {
-                                        // Get current state_id from Lua global (0 = primary, >=1 = instanced)
-                                        let state_id: usize = lua_ctx.lua.globals()
-                                            .get::<usize>("__LUA_STATE_ID__")
-                                            .unwrap_or(0);
-                                        
-                                        // Cache the result (will overwrite previous, but they should be equivalent)
-                                        if let Ok(registry_key) =
-                                            lua_ctx.lua.create_registry_value(result.clone())
-                                        {
-                                            lua_ctx.script_cache.cache_module(
-                                                reload_module_path.clone(),
-                                                state_id,
-                                                Arc::new(registry_key),
-                                            );
-                                        }
+                            // Execute the module with this instance context
+                            match crate::script_cache::execute_module(
+                                &lua,
+                                &source,
+                                &module_name,
+                            ) {
+                                Ok(result) => {
+                                    // Get current state_id from Lua global (0 = primary, >=1 = instanced)
+                                    let state_id: usize = lua.globals()
+                                        .get::<usize>("__LUA_STATE_ID__")
+                                        .unwrap_or(0);
+                                    
+                                    // Cache the result (will overwrite previous, but they should be equivalent)
+                                    if let Ok(registry_key) =
+                                        lua.create_registry_value(result.clone())
+                                    {
+                                        lua_ctx.script_cache.cache_module(
+                                            reload_module_path.clone(),
+                                            state_id,
+                                            Arc::new(registry_key),
+                                        );
+                                    }
 
-                                        // Only invoke callback if should_invoke_callback is true
-                                        // When reload=false, the module is reloaded but callback is not invoked
-                                        if should_invoke_callback {
-                                            // Execute callback - entities spawned will be tagged with module's instance
-                                            if let Err(e) = callback.call::<()>(result) {
-                                                error!(
-                                                    "Error in hot reload callback for '{}': {}",
-                                                    reload_module_path, e
-                                                );
-                                            }
-                                        } else {
-                                            debug!(
-                                                "Hot reload: Skipping callback invocation for '{}' (reload=false)",
-                                                reload_module_path
+                                    // Only invoke callback if should_invoke_callback is true
+                                    // When reload=false, the module is reloaded but callback is not invoked
+                                    if should_invoke_callback {
+                                        // Execute callback - entities spawned will be tagged with module's instance
+                                        if let Err(e) = callback.call::<()>(result) {
+                                            error!(
+                                                "Error in hot reload callback for '{}': {}",
+                                                reload_module_path, e
                                             );
                                         }
-                                    }
-                                    Err(e) => {
-                                        error!(
-                                            "Failed to execute module '{}' during hot reload: {}",
-                                            reload_module_path, e
+                                    } else {
+                                        debug!(
+                                            "Hot reload: Skipping callback invocation for '{}' (reload=false)",
+                                            reload_module_path
                                         );
                                     }
                                 }
-
-                                // Restore previous __INSTANCE_ID__
-                                if let Some(prev_id) = previous_instance_id {
-                                    let _ = lua_ctx.lua.globals().set("__INSTANCE_ID__", prev_id);
-                                } else {
-                                    let _ = lua_ctx.lua.globals().set("__INSTANCE_ID__", mlua::Nil);
+                                Err(e) => {
+                                    error!(
+                                        "Failed to execute module '{}' during hot reload: {}",
+                                        reload_module_path, e
+                                    );
                                 }
-                                
-                                // Restore previous __LUA_STATE_ID__
-                                let _ = lua_ctx.lua.globals().set("__LUA_STATE_ID__", previous_state_id);
                             }
+
+                            // Restore previous __INSTANCE_ID__
+                            if let Some(prev_id) = previous_instance_id {
+                                let _ = lua.globals().set("__INSTANCE_ID__", prev_id);
+                            } else {
+                                let _ = lua.globals().set("__INSTANCE_ID__", mlua::Nil);
+                            }
+                            
+                            // Restore previous __LUA_STATE_ID__
+                            let _ = lua.globals().set("__LUA_STATE_ID__", previous_state_id);
                         }
                     }
                 }
@@ -2047,29 +2960,28 @@ fn auto_reload_changed_scripts(
         }
 
         // Check if we already reloaded this file as a dependent
-        if reloaded_paths.contains(&event.path) {
+        if reloaded_paths.contains(&script_path) {
             continue;
         }
 
         // Get all active instances of this script
-        let instances = script_registry.get_active_instances(&event.path);
+        let instances = script_registry.get_active_instances(&script_path);
 
         if instances.is_empty() {
-            debug!("No active instances found for {:?}", event.path);
+            debug!("No active instances found for {:?}", script_path);
             continue;
         }
 
         // Read the new script content
-        let script_content = match std::fs::read_to_string(&event.path) {
+        let script_content = match std::fs::read_to_string(&script_path) {
             Ok(content) => content,
             Err(e) => {
-                error!("Failed to read script file {:?}: {}", event.path, e);
+                error!("Failed to read script file {:?}: {}", script_path, e);
                 continue;
             }
         };
 
-        let script_name = event
-            .path
+        let script_name = script_path
             .file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("unknown.lua");
@@ -2081,8 +2993,10 @@ fn auto_reload_changed_scripts(
         );
 
         for (instance_id, _old_content) in instances {
-            // Cleanup the instance
+            // Cleanup the instance; observers on entities that survive the reload keep unchanged callbacks
+            crate::observer_persistence::snapshot_instance_observers(world, |key| lua_ctx.state_for_key(key), instance_id);
             cleanup_script_instance(instance_id, world, true, true); // Recursive, skip resources: hot-reload
+            crate::observer_persistence::restore_instance_observers(world, |key| lua_ctx.state_for_key(key), instance_id, &script_content);
 
             // NOTE: We do NOT clear caches for dependencies here.
             // Dependencies keep their cached state - they only reload when THEY change.
@@ -2098,7 +3012,7 @@ fn auto_reload_changed_scripts(
 
                     // Re-register with same instance_id (content may have changed)
                     script_registry.register_script(
-                        event.path.clone(),
+                        script_path.clone(),
                         reused_instance_id,
                         script_content.clone(),
                     );
@@ -2162,7 +3076,7 @@ fn cleanup_script_instance(instance_id: u64, world: &World, recursive: bool, ski
             for key_arc in cleared_keys {
                 // Try to unwrap Arc - if this is the last reference, clean up
                 if let Ok(key) = Arc::try_unwrap(key_arc) {
-                    let _ = lua_ctx.lua.remove_registry_value(key);
+                    let _ = lua_ctx.state_for_key(&key).remove_registry_value(key);
                 }
             }
         }
@@ -2177,7 +3091,38 @@ fn cleanup_script_instance(instance_id: u64, world: &World, recursive: bool, ski
     // 3. Clear all systems registered by this instance
     // Note: Per-system ticks are cleaned up automatically with the system entries
     let system_registry = world.resource::<LuaSystemRegistry>().clone();
-    system_registry.clear_instance_systems(instance_id);
+    let removed_systems = system_registry.clear_instance_systems(instance_id);
+    if let Some(profiles) = world.get_resource::<crate::lua_frame_budget::LuaSystemProfiles>() {
+        for system_name in &removed_systems {
+            profiles.clear(system_name);
+        }
+    }
+
+    // 3b. Cancel coroutines started by this instance
+    if let Some(scheduler) = world.get_resource::<crate::lua_coroutines::LuaCoroutineScheduler>() {
+        scheduler.clear_instance_coroutines(instance_id);
+    }
+    if let Some(timers) = world.get_resource::<crate::lua_timers::TimerRegistry>() {
+        timers.clear_instance_timers(instance_id);
+    }
+    if let Some(watchers) = world.get_resource::<crate::resource_watchers::ResourceWatcherRegistry>() {
+        watchers.clear_instance_watchers(instance_id);
+    }
+    if let Some(post_spawn) = world.get_resource::<crate::lua_spawn_builder::PostSpawnCallbackQueue>() {
+        post_spawn.clear_instance_callbacks(instance_id);
+    }
+
+    // 3c. Drop update transactions the instance never committed or rolled back
+    let open_transaction_keys = world
+        .resource::<crate::component_update_queue::ComponentUpdateQueue>()
+        .clear_instance_transactions(instance_id);
+    if let Some(lua_ctx) = world.get_resource::<LuaScriptContext>() {
+        for key_arc in open_transaction_keys {
+            if let Ok(key) = Arc::try_unwrap(key_arc) {
+                let _ = lua_ctx.state_for_key(&key).remove_registry_value(key);
+            }
+        }
+    }
 
     // 4. Remove all resources inserted by this instance (skip during hot-reload)
     // IMPORTANT: Skip this during hot-reload to preserve Rust resources like RenetServer/RenetClient
@@ -2219,6 +3164,11 @@ fn cleanup_script_instance(instance_id: u64, world: &World, recursive: bool, ski
         lua_ctx.script_cache.clear_asset_dependencies(instance_id);
     }
 
+    // 5c. Forget the instance's module table (re-executing the script stores a new one)
+    if let Some(lua_ctx) = world.get_resource::<LuaScriptContext>() {
+        lua_ctx.remove_instance_module(instance_id);
+    }
+
     // 6. Unsubscribe from file sync for this instance
     // NOTE: Commenting out automatic unsubscribe - subscriptions should persist for hot reload
     // even after the script finishes executing. The subscription is for the file path, not the instance.
@@ -2233,4 +3183,89 @@ fn cleanup_script_instance(instance_id: u64, world: &World, recursive: bool, ski
 
     // 7. Despawn all entities owned by this instance
     crate::script_entities::despawn_instance_entities(world_mut, instance_id);
+
+    // 8. Drop the instance's sandbox state (skip during hot-reload: the reused instance keeps
+    // its state so define_resource tables and observer callbacks survive)
+    if !skip_resources {
+        if let Some(lua_ctx) = world.get_resource::<LuaScriptContext>() {
+            lua_ctx.remove_sandbox(instance_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_write_lines_are_buffered_until_newline() {
+        let mut pending = String::new();
+        assert!(take_complete_lines(&mut pending, "Loading").is_empty());
+        assert!(take_complete_lines(&mut pending, "...").is_empty());
+        assert_eq!(take_complete_lines(&mut pending, " done\nnext: "), vec!["Loading... done"]);
+        assert_eq!(take_complete_lines(&mut pending, "a\r\nb\n\n"), vec!["next: a", "b", ""]);
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn test_lua_version_check_accepts_lua54() {
+        assert_eq!(check_lua_version(&Lua::new()).unwrap(), REQUIRED_LUA_VERSION);
+
+        let lua = Lua::new();
+        lua.globals().set("_VERSION", "Lua 5.1").unwrap();
+        let message = check_lua_version(&lua).unwrap_err();
+        assert!(message.contains("Lua 5.1") && message.contains("lua54"));
+    }
+
+    #[test]
+    fn test_diff_reload_patches_pooled_instance_environment() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), LuaSpawnPlugin));
+        app.update();
+        let world = app.world();
+        let lua_ctx = world.resource::<LuaScriptContext>().clone();
+        let script_instance = world.resource::<crate::script_entities::ScriptInstance>().clone();
+        let script_registry = world.resource::<crate::script_registry::ScriptRegistry>().clone();
+
+        let old_source = "rate = SCRIPT_PARAMS.rate\nfunction speed()\n    return rate\nend\n";
+        let new_source = "rate = SCRIPT_PARAMS.rate\nfunction speed()\n    return rate * 10\nend\n";
+        let path = std::path::PathBuf::from("assets/scripts/pooled.lua");
+        let instance_id = lua_ctx
+            .execute_script_with_params(
+                old_source,
+                "pooled.lua",
+                path,
+                serde_json::json!({ "rate": 2 }),
+                &script_instance,
+                &script_registry,
+            )
+            .unwrap();
+
+        assert!(try_diff_reload(&lua_ctx, "scripts/pooled.lua", old_source, new_source, instance_id));
+
+        let env = lua_ctx.instance_environment_table(instance_id).unwrap();
+        let speed: LuaFunction = env.get("speed").unwrap();
+        assert_eq!(speed.call::<i64>(()).unwrap(), 20);
+        assert!(lua_ctx.lua.globals().get::<Option<LuaFunction>>("speed").unwrap().is_none());
+
+        lua_ctx.remove_instance_module(instance_id);
+        assert!(lua_ctx.instance_environment_table(instance_id).is_none());
+    }
+
+    #[test]
+    fn test_logging_globals_installed() {
+        let lua = Lua::new();
+        install_lua_logging(&lua).unwrap();
+        let chained: bool = lua
+            .load(r#"
+                print("hello", 1, nil, true)
+                log.warn("careful")
+                log.debug("details")
+                return io.write("partial ", 2, "\n") == io.stdout
+            "#)
+            .eval()
+            .unwrap();
+        assert!(chained);
+        assert!(lua.load("io.write({})").exec().is_err());
+    }
 }
@@ -9,8 +9,22 @@
 //!     :observe("Pointer<Over>", function(entity, event)
 //!         entity:set({ BackgroundColor = { color = { r = 1.0, g = 0.0, b = 0.0, a = 1.0 } } })
 //!     end)
+//!
+//! -- One handler for several events; it also receives the event type
+//! spawn({ Button = {} })
+//!     :observe_any({ "Pointer<Down>", "Pointer<Up>" }, function(entity, event_type, event)
+//!         dragging = event_type == "Pointer<Down>"
+//!     end)
+//!
+//! -- Component lifecycle: fires with the component value when Health is added later
+//! entity:observe_component_add("Health", function(entity, health)
+//!     print("health added", health.current)
+//! end)
 //! ```
 //!
+//! Lifecycle observers use the event types `OnAdd<Name>`, `OnInsert<Name>` and `OnRemove<Name>`
+//! and work for any reflected component. For `OnRemove` the value is read before removal.
+//!
 //! Observer handlers are auto-generated by build.rs based on discovered event types.
 //! The consuming crate must call `set_observer_attacher()` with the generated
 //! `attach_observer_by_name` function to enable observer attachment.
@@ -39,29 +53,194 @@ fn get_observer_attacher() -> Option<ObserverAttacherFn> {
     OBSERVER_ATTACHER.get().copied()
 }
 
+/// Reject observer registration from a sandboxed Lua state
+/// Observer callbacks are dispatched in the primary state, which can't call a function owned by
+/// another state, so the script gets an error instead of an observer that never fires
+pub fn ensure_primary_state(lua: &Lua, method: &str) -> LuaResult<()> {
+    let state_id: usize = lua.globals().get("__LUA_STATE_ID__").unwrap_or(0);
+    if state_id != 0 {
+        return Err(LuaError::RuntimeError(format!(
+            "{}: observers can't be registered from a sandboxed script (Lua state {}); \
+             they are dispatched in the primary state",
+            method, state_id
+        )));
+    }
+    Ok(())
+}
+
+/// Bevy component lifecycle event a Lua observer can listen for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentLifecycle {
+    Add,
+    Insert,
+    Remove,
+}
+
+impl ComponentLifecycle {
+    /// Split an event type like `OnAdd<Health>` into its lifecycle and component name
+    pub fn parse(event_type: &str) -> Option<(Self, &str)> {
+        let (prefix, rest) = event_type.split_once('<')?;
+        let component = rest.strip_suffix('>')?;
+        let lifecycle = match prefix {
+            "OnAdd" => Self::Add,
+            "OnInsert" => Self::Insert,
+            "OnRemove" => Self::Remove,
+            _ => return None,
+        };
+        (!component.is_empty()).then_some((lifecycle, component))
+    }
+
+    /// Event type name used in the observer registry
+    pub fn event_type(self, component: &str) -> String {
+        let prefix = match self {
+            Self::Add => "OnAdd",
+            Self::Insert => "OnInsert",
+            Self::Remove => "OnRemove",
+        };
+        format!("{}<{}>", prefix, component)
+    }
+}
+
+/// A Lua observer callback registered for one event type
+/// Callbacks from `observe_any` share one registry key across all of their event types
+pub struct LuaObserverCallback {
+    pub key: Arc<LuaRegistryKey>,
+    /// Called as `callback(entity, event_type, event)` instead of `callback(entity, event)`
+    pub receives_event_type: bool,
+}
+
+impl LuaObserverCallback {
+    /// Callback registered with `:observe(event_type, callback)`
+    pub fn new(key: LuaRegistryKey) -> Self {
+        Self {
+            key: Arc::new(key),
+            receives_event_type: false,
+        }
+    }
+
+    /// Callback registered with `:observe_any(event_types, callback)`; clone the Arc per event type
+    pub fn shared(key: Arc<LuaRegistryKey>) -> Self {
+        Self {
+            key,
+            receives_event_type: true,
+        }
+    }
+
+    /// Call the callback with the arguments matching how it was registered
+    pub fn call(
+        callback: &LuaFunction,
+        receives_event_type: bool,
+        entity: crate::lua_world_api::LuaEntitySnapshot,
+        event_type: &str,
+        event: LuaTable,
+    ) -> LuaResult<()> {
+        if receives_event_type {
+            callback.call::<()>((entity, event_type, event))
+        } else {
+            callback.call::<()>((entity, event))
+        }
+    }
+
+    /// Free the Lua registry value once no other event type shares it
+    pub(crate) fn release(self, lua: &Lua) {
+        if let Ok(key) = Arc::try_unwrap(self.key) {
+            let _ = lua.remove_registry_value(key);
+        }
+    }
+}
+
 /// Resource storing Lua observer callbacks mapped by Entity
-/// Structure: Entity -> Vec<(event_type, LuaRegistryKey)>
+/// Structure: Entity -> Vec<(event_type, LuaObserverCallback)>
 #[derive(Resource, Default, Clone)]
 pub struct LuaObserverRegistry {
     callbacks:
-        Arc<std::sync::Mutex<std::collections::HashMap<Entity, Vec<(String, LuaRegistryKey)>>>>,
+        Arc<std::sync::Mutex<std::collections::HashMap<Entity, Vec<(String, LuaObserverCallback)>>>>,
+    /// Lifecycle observers waiting for a Bevy observer to be spawned
+    pending_lifecycle: Arc<std::sync::Mutex<Vec<(Entity, String)>>>,
+    /// Lifecycle observers that have (or are about to get) a Bevy observer on their entity,
+    /// with the observer entity once it has been spawned
+    attached_lifecycle: Arc<std::sync::Mutex<HashMap<(Entity, String), Option<Entity>>>>,
+    /// Bevy observer entities whose Lua callbacks were removed, despawned by `attach_lifecycle_observers`
+    detached_lifecycle: Arc<std::sync::Mutex<Vec<Entity>>>,
 }
 
 impl LuaObserverRegistry {
     /// Register observer callbacks for an entity
-    pub fn register(&self, entity: Entity, observers: Vec<(String, LuaRegistryKey)>) {
+    pub fn register(&self, entity: Entity, observers: Vec<(String, LuaObserverCallback)>) {
         if !observers.is_empty() {
             debug!(
                 "[LUA_OBSERVER] Registering {} observers for {:?}",
                 observers.len(),
                 entity
             );
+            for (event_type, _) in &observers {
+                self.queue_lifecycle_attachment(entity, event_type);
+            }
             self.callbacks.lock().unwrap().insert(entity, observers);
         }
     }
 
+    /// Add one observer callback to an entity, keeping the ones already registered
+    pub fn add_observer(&self, entity: Entity, event_type: String, callback: LuaObserverCallback) {
+        self.queue_lifecycle_attachment(entity, &event_type);
+        self.callbacks
+            .lock()
+            .unwrap()
+            .entry(entity)
+            .or_default()
+            .push((event_type, callback));
+    }
+
+    /// Queue a Bevy observer for a lifecycle event type unless the entity already has one
+    fn queue_lifecycle_attachment(&self, entity: Entity, event_type: &str) {
+        if ComponentLifecycle::parse(event_type).is_none() {
+            return;
+        }
+        let mut attached = self.attached_lifecycle.lock().unwrap();
+        if let std::collections::hash_map::Entry::Vacant(entry) =
+            attached.entry((entity, event_type.to_string()))
+        {
+            entry.insert(None);
+            self.pending_lifecycle
+                .lock()
+                .unwrap()
+                .push((entity, event_type.to_string()));
+        }
+    }
+
+    /// Remember the Bevy observer spawned for a lifecycle event type on an entity
+    /// If its callbacks were removed while it was being spawned, it is despawned right away.
+    pub fn record_lifecycle_observer(&self, entity: Entity, event_type: &str, observer: Entity) {
+        let mut attached = self.attached_lifecycle.lock().unwrap();
+        match attached.get_mut(&(entity, event_type.to_string())) {
+            Some(slot) => *slot = Some(observer),
+            None => self.detached_lifecycle.lock().unwrap().push(observer),
+        }
+    }
+
+    /// Drop the Bevy lifecycle observers of `entity` whose event type matches `matches`
+    /// Spawned observers are queued for despawn; ones not spawned yet are never spawned.
+    fn detach_lifecycle(&self, entity: Entity, matches: impl Fn(&str) -> bool) {
+        let mut detached = Vec::new();
+        self.attached_lifecycle
+            .lock()
+            .unwrap()
+            .retain(|(attached, event_type), observer| {
+                if *attached != entity || !matches(event_type) {
+                    return true;
+                }
+                detached.extend(observer.take());
+                false
+            });
+        self.detached_lifecycle.lock().unwrap().extend(detached);
+        self.pending_lifecycle
+            .lock()
+            .unwrap()
+            .retain(|(pending, event_type)| *pending != entity || !matches(event_type));
+    }
+
     /// Take all callbacks (destructive - for cleanup)
-    pub fn take_callbacks(&self, entity: Entity) -> Vec<(String, LuaRegistryKey)> {
+    pub fn take_callbacks(&self, entity: Entity) -> Vec<(String, LuaObserverCallback)> {
         self.callbacks
             .lock()
             .unwrap()
@@ -69,10 +248,94 @@ impl LuaObserverRegistry {
             .unwrap_or_default()
     }
 
+    /// Remove all observer callbacks for an entity and free their Lua registry values
+    /// Returns the number of callbacks removed
+    pub fn remove_observers_for_entity(&self, lua: &Lua, entity: Entity) -> usize {
+        self.detach_lifecycle(entity, |_| true);
+        let removed = self.take_callbacks(entity);
+        let count = removed.len();
+        for (_, callback) in removed {
+            callback.release(lua);
+        }
+        if count > 0 {
+            debug!(
+                "[LUA_OBSERVER] Removed {} observers for {:?}",
+                count, entity
+            );
+        }
+        count
+    }
+
+    /// Remove the observer callbacks for a single event type on an entity
+    /// Returns the number of callbacks removed
+    pub fn remove_observer(&self, lua: &Lua, entity: Entity, event_type: &str) -> usize {
+        self.detach_lifecycle(entity, |attached| attached == event_type);
+        let removed: Vec<LuaObserverCallback> = {
+            let mut callbacks = self.callbacks.lock().unwrap();
+            let Some(observers) = callbacks.get_mut(&entity) else {
+                return 0;
+            };
+            let (removed, kept): (Vec<_>, Vec<_>) = std::mem::take(observers)
+                .into_iter()
+                .partition(|(ev_type, _)| ev_type == event_type);
+            if kept.is_empty() {
+                callbacks.remove(&entity);
+            } else {
+                *observers = kept;
+            }
+            removed.into_iter().map(|(_, callback)| callback).collect()
+        };
+
+        let count = removed.len();
+        for callback in removed {
+            callback.release(lua);
+        }
+        debug!(
+            "[LUA_OBSERVER] Removed {} '{}' observers for {:?}",
+            count, event_type, entity
+        );
+        count
+    }
+
+    /// Resolve the Lua callbacks registered on an entity for an event type
+    /// Each callback is paired with whether it receives the event type
+    fn matching_callbacks(&self, lua: &Lua, entity: Entity, event_type: &str) -> Vec<(LuaFunction, bool)> {
+        let callbacks = self.callbacks.lock().unwrap();
+
+        bevy::log::debug!(
+            "[OBSERVER_DISPATCH] Looking for entity {:?}, event '{}', registered entities: {:?}",
+            entity,
+            event_type,
+            callbacks.keys().collect::<Vec<_>>()
+        );
+
+        let mut matching = Vec::new();
+        if let Some(observers) = callbacks.get(&entity) {
+            bevy::log::debug!(
+                "[OBSERVER_DISPATCH] Found {} observers for entity {:?}",
+                observers.len(),
+                entity
+            );
+            for (ev_type, observer) in observers {
+                bevy::log::debug!(
+                    "[OBSERVER_DISPATCH] Checking observer: ev_type='{}' vs event_type='{}'",
+                    ev_type,
+                    event_type
+                );
+                if ev_type == event_type {
+                    if let Ok(callback) = lua.registry_value::<LuaFunction>(&observer.key) {
+                        matching.push((callback, observer.receives_event_type));
+                    }
+                }
+            }
+        }
+        matching
+    }
+
     /// Get access to callbacks (for generated observer handlers)
     pub fn callbacks(
         &self,
-    ) -> &Arc<std::sync::Mutex<std::collections::HashMap<Entity, Vec<(String, LuaRegistryKey)>>>>
+    ) -> &Arc<std::sync::Mutex<std::collections::HashMap<Entity, Vec<(String, LuaObserverCallback)>>>>
     {
         &self.callbacks
     }
@@ -128,8 +391,10 @@ pub fn attach_lua_observers(
             );
 
             // Collect unique event types first
+            // Lifecycle event types are attached by attach_lifecycle_observers
             let event_types: std::collections::HashSet<_> = observers
                 .iter()
+                .filter(|(event_type, _)| ComponentLifecycle::parse(event_type).is_none())
                 .map(|(event_type, _)| event_type.clone())
                 .collect();
 
@@ -144,6 +409,133 @@ pub fn attach_lua_observers(
     }
 }
 
+/// System that spawns Bevy observers for queued lifecycle observers (`OnAdd<Health>`, ...)
+/// and despawns the ones whose Lua callbacks were removed
+/// Goes through the generated attacher when one is registered
+pub fn attach_lifecycle_observers(mut commands: Commands, observer_registry: Res<LuaObserverRegistry>) {
+    let detached = std::mem::take(&mut *observer_registry.detached_lifecycle.lock().unwrap());
+    for observer in detached {
+        // Observers watching a despawned entity are already gone
+        if let Ok(mut observer) = commands.get_entity(observer) {
+            observer.try_despawn();
+        }
+    }
+
+    let pending = std::mem::take(&mut *observer_registry.pending_lifecycle.lock().unwrap());
+    let attacher = get_observer_attacher().unwrap_or(attach_component_lifecycle_observer);
+    for (entity, event_type) in pending {
+        attacher(&mut commands, entity, &event_type);
+    }
+}
+
+/// Attach a Bevy lifecycle observer for an event type like `OnAdd<Health>` to an entity
+/// The observer is filtered to the component's `ComponentId`, resolved once the command runs
+pub fn attach_component_lifecycle_observer(commands: &mut Commands, entity: Entity, event_type: &str) {
+    let Some((lifecycle, component_name)) = ComponentLifecycle::parse(event_type) else {
+        warn!("[LUA_OBSERVER] Not a component lifecycle event: {}", event_type);
+        return;
+    };
+    let component_name = component_name.to_string();
+    let event_type = event_type.to_string();
+
+    commands.queue(move |world: &mut World| {
+        let Some(component_id) = resolve_reflected_component_id(world, &component_name) else {
+            warn!(
+                "[LUA_OBSERVER] '{}' is not a reflected component, cannot observe {}",
+                component_name, event_type
+            );
+            return;
+        };
+        if world.get_entity(entity).is_err() {
+            return;
+        }
+
+        let dispatched_event_type = event_type.clone();
+        let handler = move |world: &World| {
+            dispatch_component_lifecycle(world, entity, component_id, &dispatched_event_type);
+        };
+        let observer = match lifecycle {
+            ComponentLifecycle::Add => lifecycle_observer::<bevy::ecs::lifecycle::Add>(handler),
+            ComponentLifecycle::Insert => lifecycle_observer::<bevy::ecs::lifecycle::Insert>(handler),
+            ComponentLifecycle::Remove => lifecycle_observer::<bevy::ecs::lifecycle::Remove>(handler),
+        };
+        let observer = world.spawn(observer.with_entity(entity).with_component(component_id)).id();
+        if let Some(registry) = world.get_resource::<LuaObserverRegistry>() {
+            registry.record_lifecycle_observer(entity, &event_type, observer);
+        }
+    });
+}
+
+/// Build a Bevy observer for lifecycle event `E` that forwards to `handler`
+fn lifecycle_observer<E: bevy::ecs::event::EntityEvent>(
+    handler: impl Fn(&World) + Send + Sync + 'static,
+) -> Observer {
+    Observer::new(move |_event: On<E>, world: bevy::ecs::world::DeferredWorld| handler(&world))
+}
+
+/// `ComponentId` of a reflected Rust component by its Lua-facing name
+fn resolve_reflected_component_id(world: &World, component_name: &str) -> Option<bevy::ecs::component::ComponentId> {
+    let component_registry = world.get_resource::<crate::components::ComponentRegistry>()?;
+    let type_path = component_registry.get_type_path(component_name)?;
+    let type_registry = component_registry.type_registry().read();
+    let registration = type_registry.get_with_type_path(&type_path)?;
+    world.components().get_id(registration.type_id())
+}
+
+/// Read the observed component from `entity` and dispatch it to the Lua lifecycle callbacks
+fn dispatch_component_lifecycle(
+    world: &World,
+    entity: Entity,
+    component_id: bevy::ecs::component::ComponentId,
+    event_type: &str,
+) {
+    let (Some(lua_ctx), Some(observer_registry), Some(update_queue), Some(component_registry)) = (
+        world.get_resource::<crate::lua_integration::LuaScriptContext>(),
+        world.get_resource::<LuaObserverRegistry>(),
+        world.get_resource::<crate::component_update_queue::ComponentUpdateQueue>(),
+        world.get_resource::<crate::components::ComponentRegistry>(),
+    ) else {
+        return;
+    };
+    let asset_registry = world.get_resource::<crate::asset_loading::AssetRegistry>();
+
+    let value = {
+        let type_registry = component_registry.type_registry().read();
+        world
+            .get_entity(entity)
+            .ok()
+            .and_then(|entity_ref| {
+                world
+                    .components()
+                    .get_info(component_id)
+                    .and_then(|info| info.type_id())
+                    .and_then(|type_id| type_registry.get(type_id))
+                    .and_then(|registration| registration.data::<ReflectComponent>())
+                    .and_then(|reflect_component| reflect_component.reflect(entity_ref))
+                    .map(|component| {
+                        crate::lua_world_api::reflection_to_lua_with_assets(&lua_ctx.lua, component, asset_registry)
+                    })
+            })
+    };
+
+    let event_table = match value {
+        Some(Ok(LuaValue::Table(table))) => table,
+        Some(Ok(other)) => {
+            // Wrap non-table values (unit structs, primitives) in a table with a "value" key
+            let table = lua_ctx.lua.create_table().unwrap();
+            let _ = table.set("value", other);
+            table
+        }
+        Some(Err(e)) => {
+            warn!("[LUA_OBSERVER] Error reflecting component for {}: {}", event_type, e);
+            lua_ctx.lua.create_table().unwrap()
+        }
+        None => lua_ctx.lua.create_table().unwrap(),
+    };
+
+    dispatch_lua_observer_event(lua_ctx, observer_registry, update_queue, entity, event_type, event_table);
+}
+
 /// Internal dispatch function for Lua observer callbacks
 /// This is called by the generated observer handlers
 pub fn dispatch_lua_observer_internal(
@@ -154,52 +546,169 @@ pub fn dispatch_lua_observer_internal(
     event_type: &str,
     position: Option<bevy::math::Vec2>,
 ) {
-    let callbacks = observer_registry.callbacks.lock().unwrap();
+    let event_table = lua_ctx.lua.create_table().unwrap();
+    if let Some(pos) = position {
+        event_table.set("x", pos.x).unwrap();
+        event_table.set("y", pos.y).unwrap();
+    }
+    dispatch_lua_observer_event(lua_ctx, observer_registry, update_queue, entity, event_type, event_table);
+}
 
-    bevy::log::debug!(
-        "[OBSERVER_DISPATCH] Looking for entity {:?}, event '{}', registered entities: {:?}",
-        entity,
-        event_type,
-        callbacks.keys().collect::<Vec<_>>()
-    );
+/// Call every Lua callback registered on `entity` for `event_type` with a prepared event table
+pub fn dispatch_lua_observer_event(
+    lua_ctx: &crate::lua_integration::LuaScriptContext,
+    observer_registry: &LuaObserverRegistry,
+    update_queue: &crate::component_update_queue::ComponentUpdateQueue,
+    entity: Entity,
+    event_type: &str,
+    event_table: LuaTable,
+) {
+    // Callbacks are collected first so the registry lock is released before calling into Lua
+    // (a callback may add or remove observers, e.g. entity:remove_observer)
+    let matching = observer_registry.matching_callbacks(&lua_ctx.lua, entity, event_type);
 
-    if let Some(observers) = callbacks.get(&entity) {
-        bevy::log::debug!(
-            "[OBSERVER_DISPATCH] Found {} observers for entity {:?}",
-            observers.len(),
-            entity
-        );
-        for (ev_type, callback_key) in observers {
-            bevy::log::debug!(
-                "[OBSERVER_DISPATCH] Checking observer: ev_type='{}' vs event_type='{}'",
-                ev_type,
-                event_type
-            );
-            if ev_type == event_type {
-                if let Ok(callback) = lua_ctx.lua.registry_value::<LuaFunction>(callback_key) {
-                    let entity_snapshot = crate::lua_world_api::LuaEntitySnapshot {
-                        entity,
-                        component_data: HashMap::new(),
-                        lua_components: HashMap::new(),
-                        changed_components: HashSet::new(),
-                        added_components: HashSet::new(),
-                        update_queue: update_queue.clone(),
-                    };
-
-                    let event_table = lua_ctx.lua.create_table().unwrap();
-                    if let Some(pos) = position {
-                        event_table.set("x", pos.x).unwrap();
-                        event_table.set("y", pos.y).unwrap();
-                    }
+    for (callback, receives_event_type) in matching {
+        let entity_snapshot = crate::lua_world_api::LuaEntitySnapshot {
+            entity,
+            component_data: HashMap::new(),
+            lua_components: HashMap::new(),
+            changed_components: HashSet::new(),
+            added_components: HashSet::new(),
+            update_queue: update_queue.clone(),
+        };
 
-                    if let Err(e) = callback.call::<()>((entity_snapshot, event_table)) {
-                        error!(
-                            "[LUA_OBSERVER] Error calling {} callback: {}",
-                            event_type, e
-                        );
-                    }
-                }
-            }
+        if let Err(e) = LuaObserverCallback::call(
+            &callback,
+            receives_event_type,
+            entity_snapshot,
+            event_type,
+            event_table.clone(),
+        ) {
+            error!(
+                "[LUA_OBSERVER] Error calling {} callback: {}",
+                event_type, e
+            );
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    fn callback(lua: &Lua) -> LuaObserverCallback {
+        let f: LuaFunction = lua.load("return function() end").eval().unwrap();
+        LuaObserverCallback::new(lua.create_registry_value(f).unwrap())
+    }
+
+    #[test]
+    fn test_remove_observers() {
+        let lua = Lua::new();
+        let registry = LuaObserverRegistry::default();
+        let entity = Entity::PLACEHOLDER;
+
+        registry.register(
+            entity,
+            vec![
+                ("Pointer<Over>".to_string(), callback(&lua)),
+                ("Pointer<Out>".to_string(), callback(&lua)),
+                ("Pointer<Over>".to_string(), callback(&lua)),
+            ],
+        );
+
+        assert_eq!(registry.remove_observer(&lua, entity, "Pointer<Over>"), 2);
+        assert_eq!(registry.remove_observer(&lua, entity, "Pointer<Over>"), 0);
+        assert_eq!(registry.remove_observers_for_entity(&lua, entity), 1);
+        assert!(registry.callbacks().lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_observe_any_shares_one_callback() {
+        let lua = Lua::new();
+        let registry = LuaObserverRegistry::default();
+        let entity = Entity::PLACEHOLDER;
+
+        let f: LuaFunction = lua
+            .load("return function(_, event_type) _G.last = event_type end")
+            .eval()
+            .unwrap();
+        let key = Arc::new(lua.create_registry_value(f).unwrap());
+        registry.register(
+            entity,
+            vec![
+                ("Pointer<Press>".to_string(), LuaObserverCallback::shared(key.clone())),
+                ("Pointer<Release>".to_string(), LuaObserverCallback::shared(key.clone())),
+            ],
+        );
+        drop(key);
+
+        let matching = registry.matching_callbacks(&lua, entity, "Pointer<Release>");
+        assert_eq!(matching.len(), 1);
+        let (callback, receives_event_type) = &matching[0];
+        assert!(*receives_event_type);
+        callback.call::<()>((LuaValue::Nil, "Pointer<Release>")).unwrap();
+        assert_eq!(lua.globals().get::<String>("last").unwrap(), "Pointer<Release>");
+
+        // The shared key survives until the last event type is removed
+        assert_eq!(registry.remove_observer(&lua, entity, "Pointer<Press>"), 1);
+        assert_eq!(registry.matching_callbacks(&lua, entity, "Pointer<Release>").len(), 1);
+        assert_eq!(registry.remove_observer(&lua, entity, "Pointer<Release>"), 1);
+        assert!(registry.callbacks().lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_lifecycle_observers_queue_one_attachment() {
+        assert_eq!(
+            ComponentLifecycle::parse("OnAdd<Health>"),
+            Some((ComponentLifecycle::Add, "Health"))
+        );
+        assert_eq!(ComponentLifecycle::parse("OnRemove<>"), None);
+        assert_eq!(ComponentLifecycle::parse("Pointer<Over>"), None);
+        assert_eq!(ComponentLifecycle::Insert.event_type("Health"), "OnInsert<Health>");
+
+        let lua = Lua::new();
+        let registry = LuaObserverRegistry::default();
+        let entity = Entity::PLACEHOLDER;
+
+        registry.add_observer(entity, "OnAdd<Health>".to_string(), callback(&lua));
+        registry.add_observer(entity, "OnAdd<Health>".to_string(), callback(&lua));
+        registry.add_observer(entity, "Pointer<Over>".to_string(), callback(&lua));
+        assert_eq!(registry.callbacks().lock().unwrap()[&entity].len(), 3);
+        assert_eq!(
+            registry.pending_lifecycle.lock().unwrap().as_slice(),
+            &[(entity, "OnAdd<Health>".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_removed_lifecycle_observers_are_despawned() {
+        let mut world = World::new();
+        world.init_resource::<LuaObserverRegistry>();
+        let lua = Lua::new();
+        let registry = world.resource::<LuaObserverRegistry>().clone();
+        let entity = world.spawn_empty().id();
+        let observer = world.spawn_empty().id();
+
+        registry.add_observer(entity, "OnAdd<Health>".to_string(), callback(&lua));
+        registry.pending_lifecycle.lock().unwrap().clear();
+        registry.record_lifecycle_observer(entity, "OnAdd<Health>", observer);
+
+        // Removing the callback queues its Bevy observer for despawn
+        assert_eq!(registry.remove_observer(&lua, entity, "OnAdd<Health>"), 1);
+        world.run_system_once(attach_lifecycle_observers).unwrap();
+        assert!(world.get_entity(observer).is_err());
+
+        // Re-registering spawns a fresh observer instead of reusing a stale entry
+        registry.add_observer(entity, "OnAdd<Health>".to_string(), callback(&lua));
+        assert_eq!(registry.pending_lifecycle.lock().unwrap().len(), 1);
+
+        // An observer that finishes spawning after cleanup is despawned as well
+        assert_eq!(registry.remove_observers_for_entity(&lua, entity), 1);
+        assert!(registry.pending_lifecycle.lock().unwrap().is_empty());
+        let late = world.spawn_empty().id();
+        registry.record_lifecycle_observer(entity, "OnAdd<Health>", late);
+        world.run_system_once(attach_lifecycle_observers).unwrap();
+        assert!(world.get_entity(late).is_err());
+    }
+}
@@ -156,6 +156,9 @@ mod tests {
                 last_run_real_time: Instant::now(),
                 state_id: 0,
                 system_name: "system_a".to_string(),
+                ordering: Default::default(),
+                condition: None,
+                group: None,
             },
             LuaSystemEntry {
                 instance_id: 2,
@@ -164,6 +167,9 @@ mod tests {
                 last_run_real_time: Instant::now(),
                 state_id: 1,
                 system_name: "system_b".to_string(),
+                ordering: Default::default(),
+                condition: None,
+                group: None,
             },
             LuaSystemEntry {
                 instance_id: 3,
@@ -172,6 +178,9 @@ mod tests {
                 last_run_real_time: Instant::now(),
                 state_id: 0,
                 system_name: "system_c".to_string(),
+                ordering: Default::default(),
+                condition: None,
+                group: None,
             },
         ];
         
@@ -0,0 +1,110 @@
+//! Startup ordering for `LuaSpawnPlugin`
+//!
+//! Insert a `LuaPluginOrder` before adding `LuaSpawnPlugin` to move its startup work into
+//! specific schedules, e.g. so physics or networking plugins finish initializing first:
+//!
+//! ```ignore
+//! app.insert_resource(LuaPluginOrder::new([
+//!     (LuaPluginOrder::REGISTRY_INIT, StartupStage::Startup),
+//!     (LuaPluginOrder::LUA_INIT, StartupStage::PostStartup),
+//! ]));
+//! app.add_plugins(LuaSpawnPlugin);
+//! ```
+//!
+//! Phases that are not listed keep their default stage. Without the resource the plugin
+//! behaves exactly as before.
+
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy::prelude::*;
+
+/// Startup schedule a `LuaSpawnPlugin` phase runs in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StartupStage {
+    PreStartup,
+    Startup,
+    PostStartup,
+}
+
+impl StartupStage {
+    /// The Bevy schedule for this stage
+    pub fn label(self) -> InternedScheduleLabel {
+        match self {
+            StartupStage::PreStartup => PreStartup.intern(),
+            StartupStage::Startup => Startup.intern(),
+            StartupStage::PostStartup => PostStartup.intern(),
+        }
+    }
+}
+
+/// Named startup phases for `LuaSpawnPlugin`, read once when the plugin is built
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LuaPluginOrder {
+    phases: Vec<(String, StartupStage)>,
+}
+
+impl LuaPluginOrder {
+    /// ComponentRegistry creation and auto-generated resource method registration
+    pub const REGISTRY_INIT: &'static str = "registry_init";
+    /// Lua state creation (`setup_lua_context`)
+    pub const LUA_INIT: &'static str = "lua_init";
+    /// Diagnostics such as the available-events log
+    pub const REPORT: &'static str = "report";
+
+    pub fn new<S: Into<String>>(phases: impl IntoIterator<Item = (S, StartupStage)>) -> Self {
+        Self {
+            phases: phases.into_iter().map(|(name, stage)| (name.into(), stage)).collect(),
+        }
+    }
+
+    /// Stage configured for `phase`, or `default` if it isn't listed
+    pub fn stage_for(&self, phase: &str, default: StartupStage) -> StartupStage {
+        self.phases
+            .iter()
+            .find(|(name, _)| name == phase)
+            .map(|(_, stage)| *stage)
+            .unwrap_or(default)
+    }
+
+    /// Resolve the stages for (registry_init, lua_init, report)
+    ///
+    /// The Lua context needs the ComponentRegistry, so a configuration that would run
+    /// `lua_init` before `registry_init` is rejected in favour of the defaults.
+    pub fn resolve(&self) -> (StartupStage, StartupStage, StartupStage) {
+        let registry = self.stage_for(Self::REGISTRY_INIT, StartupStage::PreStartup);
+        let lua_init = self.stage_for(Self::LUA_INIT, StartupStage::Startup);
+        let report = self.stage_for(Self::REPORT, StartupStage::PostStartup);
+
+        if lua_init < registry {
+            warn!(
+                "[LUA_PLUGIN_ORDER] '{}' ({:?}) cannot run before '{}' ({:?}) - using default stages",
+                Self::LUA_INIT, lua_init, Self::REGISTRY_INIT, registry
+            );
+            return (StartupStage::PreStartup, StartupStage::Startup, report);
+        }
+
+        (registry, lua_init, report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_uses_defaults_and_rejects_inverted_phases() {
+        assert_eq!(
+            LuaPluginOrder::default().resolve(),
+            (StartupStage::PreStartup, StartupStage::Startup, StartupStage::PostStartup)
+        );
+
+        let deferred = LuaPluginOrder::new([(LuaPluginOrder::LUA_INIT, StartupStage::PostStartup)]);
+        assert_eq!(deferred.resolve().1, StartupStage::PostStartup);
+
+        let inverted = LuaPluginOrder::new([
+            (LuaPluginOrder::REGISTRY_INIT, StartupStage::PostStartup),
+            (LuaPluginOrder::LUA_INIT, StartupStage::Startup),
+        ]);
+        assert_eq!(inverted.resolve().0, StartupStage::PreStartup);
+        assert_eq!(inverted.resolve().1, StartupStage::Startup);
+    }
+}
@@ -0,0 +1,32 @@
+//! Isolated Lua states for script instances
+//!
+//! ```ignore
+//! app.add_plugins((LuaSpawnPlugin, MultiLuaContextPlugin));
+//! ```
+//!
+//! With this plugin every script instance started through `LuaScriptContext::execute_script*`
+//! gets its own `mlua::Lua` state, so a script that overwrites globals (or `string`, `math`, ...)
+//! can't break another one. Each state gets the same ECS bridge (`spawn`, `register_system`,
+//! `world:*`, `load_asset`, ...) plus its own copy of the stdlib extensions. Scripts share no
+//! Lua values: they communicate through the ECS, e.g. `world:send_event` / `world:read_events`
+//! (`PendingLuaEvents`), components and resources.
+//!
+//! Things that remain tied to one state:
+//! - Lua table resources (`define_resource`) and Lua-only components live in the state that
+//!   created them and are not readable from other states.
+//! - Observer callbacks are dispatched in the primary state, so register them from
+//!   non-sandboxed scripts; `observe`, `observe_any` and `observe_component_*` raise an error
+//!   in a sandboxed state.
+
+use bevy::prelude::*;
+
+/// Run every script instance in its own Lua state
+/// Add alongside `LuaSpawnPlugin`; read once when the Lua context is created
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct MultiLuaContextPlugin;
+
+impl Plugin for MultiLuaContextPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(*self);
+    }
+}
@@ -5,16 +5,123 @@
 //! spawn({ Transform = {...} })
 //!     :with_parent(parent_id)
 //!     :observe("Pointer<Over>", function(entity, event) ... end)
+//!     :observe_any({"Pointer<Down>", "Pointer<Up>"}, function(entity, event_type, event) ... end)
 //!     :id()
 //! ```
+//!
+//! For many identical entities use `spawn_batch(count, template)` instead, which queues a
+//! single batch entry and returns a table of temp IDs.
+//!
+//! Setup that needs the real entity goes in `world:spawn_with_init`, whose callback runs once
+//! the entity exists:
+//! ```lua
+//! world:spawn_with_init({ Transform = {...} }, function(entity) enemies[entity] = true end)
+//! ```
 
 use crate::component_update_queue::ComponentUpdateQueue;
 use crate::spawn_queue::SpawnQueue;
+use bevy::prelude::*;
 use mlua::prelude::*;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Callback waiting for its entity to be spawned
+struct PostSpawnCallback {
+    callback: LuaRegistryKey,
+    /// Lua state the callback was created in (0=primary, >=1=instanced)
+    state_id: usize,
+    /// Script instance that queued the spawn (for cleanup on reload/stop)
+    instance_id: u64,
+}
+
+/// Callbacks registered with `world:spawn_with_init`
+/// `process_spawn_queue` moves them to `ready` once their temp_id has an entity, and
+/// `run_post_spawn_callbacks` calls them after the spawn commands are applied
+#[derive(Resource, Clone, Default)]
+pub struct PostSpawnCallbackQueue {
+    pending: Arc<Mutex<HashMap<u64, PostSpawnCallback>>>,
+    ready: Arc<Mutex<Vec<(Entity, PostSpawnCallback)>>>,
+}
+
+impl PostSpawnCallbackQueue {
+    /// Call `callback(entity)` once the entity queued as `temp_id` is live
+    pub fn register(&self, lua: &Lua, temp_id: u64, callback: LuaFunction) -> LuaResult<()> {
+        let callback = PostSpawnCallback {
+            callback: lua.create_registry_value(callback)?,
+            state_id: lua.globals().get("__LUA_STATE_ID__").unwrap_or(0),
+            instance_id: lua.globals().get("__INSTANCE_ID__").unwrap_or(0),
+        };
+        self.pending.lock().unwrap().insert(temp_id, callback);
+        Ok(())
+    }
+
+    /// Called by `process_spawn_queue` when `temp_id` has been given an entity
+    pub fn entity_spawned(&self, temp_id: u64, entity: Entity) {
+        if let Some(callback) = self.pending.lock().unwrap().remove(&temp_id) {
+            self.ready.lock().unwrap().push((entity, callback));
+        }
+    }
+
+    /// Drop callbacks registered by a script instance
+    pub fn clear_instance_callbacks(&self, instance_id: u64) {
+        self.pending
+            .lock()
+            .unwrap()
+            .retain(|_, callback| callback.instance_id != instance_id);
+        self.ready
+            .lock()
+            .unwrap()
+            .retain(|(_, callback)| callback.instance_id != instance_id);
+    }
+
+    /// Number of callbacks whose entity hasn't been spawned yet
+    pub fn pending_len(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+}
+
+/// System that calls `spawn_with_init` callbacks with their now-live entity
+pub fn run_post_spawn_callbacks(
+    queue: Res<PostSpawnCallbackQueue>,
+    lua_ctx: Option<Res<crate::lua_integration::LuaScriptContext>>,
+) {
+    let Some(lua_ctx) = lua_ctx else {
+        return;
+    };
+    // The lock is released before callbacks run, so they can spawn more entities with init
+    let ready: Vec<_> = queue.ready.lock().unwrap().drain(..).collect();
+    for (entity, callback) in ready {
+        let lua = lua_ctx.get_lua_state(callback.state_id);
+        // A removed sandbox's slot falls back to the primary state, which doesn't own the key
+        if !lua.owns_registry_value(&callback.callback) {
+            debug!(
+                "[SPAWN_INIT] Dropping init callback for {:?}: Lua state {} of instance {} no longer exists",
+                entity, callback.state_id, callback.instance_id
+            );
+            continue;
+        }
+
+        // Run in the script context that spawned the entity, then restore the caller's
+        let globals = lua.globals();
+        let previous_instance_id: Option<u64> = globals.get("__INSTANCE_ID__").ok();
+        let previous_phase: Option<String> = globals.get("__SPAWN_PHASE__").ok();
+        let _ = globals.set("__INSTANCE_ID__", callback.instance_id);
+        let _ = globals.set("__SPAWN_PHASE__", "runtime");
+
+        let result = lua
+            .registry_value::<LuaFunction>(&callback.callback)
+            .and_then(|function| function.call::<()>(entity.to_bits()));
+        let _ = globals.set("__INSTANCE_ID__", previous_instance_id);
+        let _ = globals.set("__SPAWN_PHASE__", previous_phase);
+        if let Err(e) = result {
+            error!("[SPAWN_INIT] Init callback for {:?} failed: {}", entity, e);
+        }
+        let _ = lua.remove_registry_value(callback.callback);
+    }
+}
 
 /// Lua userdata for chainable entity spawning
-/// Returned by spawn() function, allows chaining :with_parent(), :observe(), :id(), :set()
+/// Returned by spawn() function, allows chaining :with_parent(), :observe(), :observe_any(), :id(), :set()
 #[derive(Clone)]
 pub struct LuaSpawnBuilder {
     pub temp_id: u64,
@@ -51,6 +158,7 @@ impl LuaUserData for LuaSpawnBuilder {
         methods.add_method(
             "observe",
             |lua, this, (event_type, callback): (String, LuaFunction)| {
+                crate::lua_observers::ensure_primary_state(lua, "observe")?;
                 // Store the callback as a registry key
                 let registry_key = lua.create_registry_value(callback)?;
 
@@ -62,6 +170,20 @@ impl LuaUserData for LuaSpawnBuilder {
             },
         );
 
+        // Add one observer for several event types - chainable, returns self
+        // The callback also receives the event type so it can branch
+        // Usage: :observe_any({"Pointer<Down>", "Pointer<Up>"}, function(entity, event_type, event) ... end)
+        methods.add_method(
+            "observe_any",
+            |lua, this, (event_types, callback): (Vec<String>, LuaFunction)| {
+                crate::lua_observers::ensure_primary_state(lua, "observe_any")?;
+                let registry_key = lua.create_registry_value(callback)?;
+                this.spawn_queue
+                    .register_observer_callback_any(this.temp_id, event_types, registry_key);
+                Ok(this.clone())
+            },
+        );
+
         // Set/update components using spawn-style syntax - chainable, returns self
         // Usage: :set({ Camera = { target = {...} }, Transform = {...} })
         // Works both during spawn (before entity exists) and after spawn (runtime updates)
@@ -0,0 +1,115 @@
+//! Project-wide Lua helpers loaded before any user script
+//!
+//! ```ignore
+//! app.add_plugins(LuaSpawnPlugin::with_lua_stdlib_extensions("scripts/stdlib.lua"));
+//! ```
+//!
+//! The file runs in the primary Lua state right after it is created, so globals it defines
+//! (`class()`, `mixin()`, `vec3()`, ...) are visible to every script. It is a hard
+//! dependency: a missing file or a syntax error panics at startup. Later edits are picked up
+//! by the file watcher and re-executed; errors on reload are only logged.
+//!
+//! With `MultiLuaContextPlugin`, each sandbox state gets its own copy of the helpers, replayed
+//! from the source cached in `ScriptCache` (mlua does not load precompiled bytecode in safe mode).
+
+use bevy::prelude::*;
+use mlua::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::lua_file_watcher::LuaFileChangeEvent;
+use crate::lua_integration::LuaScriptContext;
+use crate::path_utils::{to_asset_server_path, to_forward_slash};
+
+/// Lua file (relative to `assets/`) executed before any user script
+/// Added by `LuaSpawnPlugin::with_lua_stdlib_extensions`, or insert before adding `LuaSpawnPlugin`
+#[derive(Resource, Clone, Debug)]
+pub struct LuaStdlibExtensions {
+    pub path: String,
+}
+
+impl LuaStdlibExtensions {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Location on disk (`assets/<path>`)
+    pub fn full_path(&self) -> PathBuf {
+        Path::new("assets").join(&self.path)
+    }
+}
+
+impl Plugin for LuaStdlibExtensions {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.clone());
+    }
+}
+
+/// Read and execute the stdlib file at `path` in `lua`
+/// Returns the source so it can be cached for sandbox states
+pub fn load_stdlib_extensions(lua: &Lua, path: &Path) -> Result<String, String> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    lua.load(&source)
+        .set_name(stdlib_chunk_name(path))
+        .exec()
+        .map_err(|e| format!("failed to run {}: {}", path.display(), e))?;
+    Ok(source)
+}
+
+/// Chunk name used for the stdlib file in error messages and tracebacks
+pub fn stdlib_chunk_name(path: &Path) -> String {
+    format!("@{}", to_forward_slash(path))
+}
+
+/// System that re-executes the stdlib file when it changes on disk
+/// Runs before script auto-reload so reloaded scripts see the new helpers
+pub fn reload_stdlib_extensions(
+    mut events: MessageReader<LuaFileChangeEvent>,
+    extensions: Option<Res<LuaStdlibExtensions>>,
+    lua_ctx: Option<Res<LuaScriptContext>>,
+) {
+    let (Some(extensions), Some(lua_ctx)) = (extensions, lua_ctx) else {
+        events.clear();
+        return;
+    };
+    let stdlib_path = to_asset_server_path(&extensions.full_path());
+    if !events.read().any(|event| to_asset_server_path(&event.path) == stdlib_path) {
+        return;
+    }
+
+    match load_stdlib_extensions(&lua_ctx.lua, &extensions.full_path()) {
+        Ok(source) => {
+            let chunk_name = stdlib_chunk_name(&extensions.full_path());
+            // Sandbox states each hold their own copy of the helpers
+            for sandbox in lua_ctx.sandbox_states() {
+                if let Err(e) = sandbox.load(&source).set_name(chunk_name.as_str()).exec() {
+                    error!("[LUA_STDLIB] Reload failed in a sandbox state: {}", e);
+                }
+            }
+            lua_ctx.script_cache.set_stdlib_chunk(chunk_name, source);
+            info!("[LUA_STDLIB] Reloaded {}", stdlib_path);
+        }
+        Err(e) => error!("[LUA_STDLIB] Reload failed, keeping previous helpers: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_stdlib_extensions_defines_globals() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("stdlib.lua");
+        std::fs::write(&path, "function vec3(x, y, z) return { x = x, y = y, z = z } end").unwrap();
+
+        let lua = Lua::new();
+        load_stdlib_extensions(&lua, &path).unwrap();
+        let y: f32 = lua.load("return vec3(1, 2, 3).y").eval().unwrap();
+        assert_eq!(y, 2.0);
+
+        std::fs::write(&path, "function broken(").unwrap();
+        assert!(load_stdlib_extensions(&lua, &path).is_err());
+        assert!(load_stdlib_extensions(&lua, &dir.path().join("missing.lua")).is_err());
+    }
+}
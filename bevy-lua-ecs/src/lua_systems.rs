@@ -21,6 +21,148 @@ pub struct LuaSystemEntry {
     pub last_run_real_time: std::time::Instant, // Real time for delta_time calculation
     pub state_id: usize,  // Which Lua state this system belongs to (0=primary, >=1=instanced)
     pub system_name: String,  // Human-readable name for profiling (e.g., "Update:scripts/main.lua")
+    pub ordering: LuaSystemOrdering,  // Optional run-before/run-after constraints
+    pub condition: Option<LuaSystemCondition>,  // Skip the system (without entering Lua) when false
+    pub group: Option<String>,  // Group tag; all systems in a paused group are skipped
+}
+
+/// Ordering constraints for a Lua system
+///
+/// Systems are identified by `label`. `before`/`after` list labels of other systems;
+/// `run_lua_systems` topologically sorts systems by these constraints each frame.
+#[derive(Clone, Debug, Default)]
+pub struct LuaSystemOrdering {
+    pub label: Option<String>,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+impl LuaSystemOrdering {
+    fn is_empty(&self) -> bool {
+        self.before.is_empty() && self.after.is_empty()
+    }
+}
+
+/// Run condition for a Lua system, evaluated by `run_lua_systems` before entering the Lua VM
+///
+/// From Lua, conditions are declared in the `register_system` options table:
+/// `{ condition = { resource = "GameState", field = "phase", equals = "combat" } }`,
+/// `{ condition = { resource = "GameState", field = "phase", not_equals = "menu" } }` or
+/// `{ condition = { resource_exists = "CombatActive" } }`.
+#[derive(Clone)]
+pub enum LuaSystemCondition {
+    /// Arbitrary Rust predicate
+    Rust(Arc<dyn Fn(&World) -> bool + Send + Sync>),
+    /// A reflected resource field equals (or, when `negate`, differs from) a value.
+    /// Enum fields compare by variant name.
+    ResourceField {
+        resource: String,
+        field: String,
+        value: serde_json::Value,
+        negate: bool,
+    },
+    /// A reflected resource is currently inserted in the world
+    ResourceExists(String),
+}
+
+impl LuaSystemCondition {
+    /// Parse a Lua-side condition table
+    pub fn from_lua_table(lua: &Lua, table: &LuaTable) -> LuaResult<Self> {
+        if let Some(resource) = table.get::<Option<String>>("resource_exists")? {
+            return Ok(Self::ResourceExists(resource));
+        }
+
+        let resource: String = table.get::<Option<String>>("resource")?.ok_or_else(|| {
+            LuaError::RuntimeError("condition requires 'resource' or 'resource_exists'".to_string())
+        })?;
+        let field: String = table.get::<Option<String>>("field")?.unwrap_or_default();
+
+        let (value, negate) = match (table.get::<LuaValue>("equals")?, table.get::<LuaValue>("not_equals")?) {
+            (LuaValue::Nil, LuaValue::Nil) => {
+                return Err(LuaError::RuntimeError(format!(
+                    "condition on '{}' requires 'equals' or 'not_equals'",
+                    resource
+                )))
+            }
+            (value, LuaValue::Nil) => (value, false),
+            (LuaValue::Nil, value) => (value, true),
+            _ => {
+                return Err(LuaError::RuntimeError(
+                    "condition cannot combine 'equals' and 'not_equals'".to_string(),
+                ))
+            }
+        };
+
+        Ok(Self::ResourceField {
+            resource,
+            field,
+            value: lua.from_value(value)?,
+            negate,
+        })
+    }
+
+    /// Evaluate the condition against the world
+    ///
+    /// Unregistered or missing resources make field conditions false (regardless of negation),
+    /// so a `not_equals` system does not run before its resource exists.
+    pub fn evaluate(&self, world: &World) -> bool {
+        match self {
+            Self::Rust(condition) => condition(world),
+            Self::ResourceExists(resource) => {
+                let Some(type_registry) = world.get_resource::<AppTypeRegistry>() else {
+                    return false;
+                };
+                let registry = type_registry.read();
+                find_reflect_resource(&registry, resource)
+                    .is_some_and(|reflect_resource| reflect_resource.reflect(world).is_ok())
+            }
+            Self::ResourceField { resource, field, value, negate } => {
+                use bevy::reflect::{GetPath, ReflectRef};
+
+                let Some(type_registry) = world.get_resource::<AppTypeRegistry>() else {
+                    return false;
+                };
+                let registry = type_registry.read();
+                let Some(reflect_resource) = find_reflect_resource(&registry, resource) else {
+                    return false;
+                };
+                let Ok(resource_value) = reflect_resource.reflect(world) else {
+                    return false;
+                };
+                let current = if field.is_empty() {
+                    resource_value.as_partial_reflect()
+                } else {
+                    match resource_value.reflect_path(field.as_str()) {
+                        Ok(current) => current,
+                        Err(_) => return false,
+                    }
+                };
+
+                let matches = match current.reflect_ref() {
+                    ReflectRef::Enum(e) => value.as_str() == Some(e.variant_name()),
+                    _ => match (crate::event_reader::reflect_to_json(current, &registry), value) {
+                        (Some(serde_json::Value::Number(a)), serde_json::Value::Number(b)) => {
+                            a.as_f64() == b.as_f64()
+                        }
+                        (Some(current), value) => &current == value,
+                        (None, _) => false,
+                    },
+                };
+                matches != *negate
+            }
+        }
+    }
+}
+
+/// Look up a resource's reflection data by short or full type path
+fn find_reflect_resource<'a>(
+    registry: &'a bevy::reflect::TypeRegistry,
+    resource: &str,
+) -> Option<&'a bevy::ecs::reflect::ReflectResource> {
+    registry
+        .get_with_short_type_path(resource)
+        .or_else(|| registry.get_with_type_path(resource))
+        .and_then(|registration| registration.data::<bevy::ecs::reflect::ReflectResource>())
 }
 
 /// Resource that stores registered Lua systems
@@ -29,6 +171,14 @@ pub struct LuaSystemRegistry {
     pub update_systems: Arc<Mutex<Vec<LuaSystemEntry>>>,
     /// Pending coroutines waiting for downloads: path -> list of (coroutine_key, instance_id)
     pub pending_system_coroutines: Arc<Mutex<HashMap<String, Vec<(Arc<LuaRegistryKey>, u64)>>>>,
+    /// Last ordering cycle reported by run_lua_systems (avoids warning every frame)
+    reported_cycle: Arc<Mutex<Vec<String>>>,
+    /// Groups whose systems are currently suspended
+    paused_groups: Arc<Mutex<HashSet<String>>>,
+    /// Throttled systems (instance, label or system name) -> maximum runs per second
+    max_hz: Arc<Mutex<HashMap<(u64, String), f32>>>,
+    /// Throttled systems -> when they last ran
+    throttle_last_run: Arc<Mutex<HashMap<(u64, String), std::time::Instant>>>,
 }
 
 impl Default for LuaSystemRegistry {
@@ -36,6 +186,10 @@ impl Default for LuaSystemRegistry {
         Self {
             update_systems: Arc::new(Mutex::new(Vec::new())),
             pending_system_coroutines: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            reported_cycle: Arc::new(Mutex::new(Vec::new())),
+            paused_groups: Arc::new(Mutex::new(HashSet::new())),
+            max_hz: Arc::new(Mutex::new(HashMap::new())),
+            throttle_last_run: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -45,6 +199,115 @@ impl LuaSystemRegistry {
     /// state_id: Which Lua state this system runs in (0=primary, >=1=instanced)
     /// system_name: Human-readable name for profiling (e.g., "Update:scripts/player.lua")
     pub fn register_system(&self, instance_id: u64, system_key: Arc<LuaRegistryKey>, state_id: usize, system_name: String) {
+        self.register_system_with_ordering(instance_id, system_key, state_id, system_name, LuaSystemOrdering::default());
+    }
+
+    /// Register a labeled system that must run before/after other labeled systems
+    /// e.g. `register_lua_system_ordered(id, key, 0, "ai", &["animation"], &[])`
+    pub fn register_lua_system_ordered(
+        &self,
+        instance_id: u64,
+        system_key: Arc<LuaRegistryKey>,
+        state_id: usize,
+        name: &str,
+        before: &[&str],
+        after: &[&str],
+    ) {
+        let ordering = LuaSystemOrdering {
+            label: Some(name.to_string()),
+            before: before.iter().map(|s| s.to_string()).collect(),
+            after: after.iter().map(|s| s.to_string()).collect(),
+        };
+        self.register_system_with_ordering(instance_id, system_key, state_id, name.to_string(), ordering);
+    }
+
+    /// Register a system that only runs while `condition` holds
+    /// e.g. `register_conditional_lua_system(id, key, 0, "combat_ai", Arc::new(|world| world.contains_resource::<CombatActive>()))`
+    pub fn register_conditional_lua_system(
+        &self,
+        instance_id: u64,
+        system_key: Arc<LuaRegistryKey>,
+        state_id: usize,
+        name: &str,
+        condition: Arc<dyn Fn(&World) -> bool + Send + Sync>,
+    ) {
+        self.register_system_full(
+            instance_id,
+            system_key,
+            state_id,
+            name.to_string(),
+            LuaSystemOrdering::default(),
+            Some(LuaSystemCondition::Rust(condition)),
+            None,
+        );
+    }
+
+    /// Register a system tagged with `group`, so it can be suspended with `pause_group`
+    /// e.g. `register_lua_system_in_group(id, key, 0, "ai_update", "gameplay")`
+    pub fn register_lua_system_in_group(
+        &self,
+        instance_id: u64,
+        system_key: Arc<LuaRegistryKey>,
+        state_id: usize,
+        name: &str,
+        group: &str,
+    ) {
+        self.register_system_full(
+            instance_id,
+            system_key,
+            state_id,
+            name.to_string(),
+            LuaSystemOrdering::default(),
+            None,
+            Some(group.to_string()),
+        );
+    }
+
+    /// Register a system that runs at most `max_hz` times per second
+    /// e.g. `register_lua_system_throttled(id, key, 0, "pathfinding", 10.0)`
+    pub fn register_lua_system_throttled(
+        &self,
+        instance_id: u64,
+        system_key: Arc<LuaRegistryKey>,
+        state_id: usize,
+        name: &str,
+        max_hz: f32,
+    ) {
+        self.set_system_hz(instance_id, name, max_hz);
+        self.register_system_full(
+            instance_id,
+            system_key,
+            state_id,
+            name.to_string(),
+            LuaSystemOrdering::default(),
+            None,
+            None,
+        );
+    }
+
+    /// Register a system with explicit ordering constraints
+    pub fn register_system_with_ordering(
+        &self,
+        instance_id: u64,
+        system_key: Arc<LuaRegistryKey>,
+        state_id: usize,
+        system_name: String,
+        ordering: LuaSystemOrdering,
+    ) {
+        self.register_system_full(instance_id, system_key, state_id, system_name, ordering, None, None);
+    }
+
+    /// Register a system with ordering constraints, an optional run condition and an optional group
+    pub fn register_system_full(
+        &self,
+        instance_id: u64,
+        system_key: Arc<LuaRegistryKey>,
+        state_id: usize,
+        system_name: String,
+        ordering: LuaSystemOrdering,
+        condition: Option<LuaSystemCondition>,
+        group: Option<String>,
+    ) {
         let mut systems = self.update_systems.lock().unwrap();
         systems.push(LuaSystemEntry {
             instance_id,
@@ -53,15 +316,66 @@ impl LuaSystemRegistry {
             last_run_real_time: std::time::Instant::now(),
             state_id,
             system_name,
+            ordering,
+            condition,
+            group,
         });
     }
+
+    /// Suspend every system tagged with `group` until `resume_group` is called
+    /// Returns false if the group was already paused
+    pub fn pause_group(&self, group: &str) -> bool {
+        self.paused_groups.lock().unwrap().insert(group.to_string())
+    }
+
+    /// Resume a paused group; returns false if it wasn't paused
+    pub fn resume_group(&self, group: &str) -> bool {
+        self.paused_groups.lock().unwrap().remove(group)
+    }
+
+    /// Whether systems tagged with `group` are currently suspended
+    pub fn is_group_paused(&self, group: &str) -> bool {
+        self.paused_groups.lock().unwrap().contains(group)
+    }
+
+    /// Limit a system of `instance_id` (by label or system name) to `max_hz` runs per second
+    /// Each script instance is throttled separately. A rate of 0 or less removes the limit, so
+    /// the system runs every frame again
+    pub fn set_system_hz(&self, instance_id: u64, name: &str, max_hz: f32) {
+        let key = (instance_id, name.to_string());
+        let mut rates = self.max_hz.lock().unwrap();
+        if max_hz > 0.0 {
+            rates.insert(key, max_hz);
+        } else {
+            rates.remove(&key);
+            self.throttle_last_run.lock().unwrap().remove(&key);
+        }
+    }
+
+    /// Current rate limit of a system of `instance_id`, if it is throttled
+    pub fn system_hz(&self, instance_id: u64, name: &str) -> Option<f32> {
+        self.max_hz.lock().unwrap().get(&(instance_id, name.to_string())).copied()
+    }
     
     /// Clear all systems registered by a specific script instance
-    pub fn clear_instance_systems(&self, instance_id: u64) {
+    /// Returns the names of the removed systems that no other instance still uses
+    pub fn clear_instance_systems(&self, instance_id: u64) -> Vec<String> {
         let mut systems = self.update_systems.lock().unwrap();
         let initial_count = systems.len();
 
-        systems.retain(|entry| entry.instance_id != instance_id);
+        let mut removed_names = Vec::new();
+        systems.retain(|entry| {
+            if entry.instance_id == instance_id {
+                removed_names.push(entry.system_name.clone());
+                return false;
+            }
+            true
+        });
+        removed_names.sort();
+        removed_names.dedup();
+        removed_names.retain(|name| !systems.iter().any(|entry| &entry.system_name == name));
+        self.max_hz.lock().unwrap().retain(|(owner, _), _| *owner != instance_id);
+        self.throttle_last_run.lock().unwrap().retain(|(owner, _), _| *owner != instance_id);
 
         let removed_count = initial_count - systems.len();
         if removed_count > 0 {
@@ -70,6 +384,7 @@ impl LuaSystemRegistry {
                 removed_count, instance_id
             );
         }
+        removed_names
     }
 
     /// Register a system coroutine waiting for a download
@@ -113,6 +428,100 @@ impl LuaSystemRegistry {
     }
 }
 
+/// Key a system is throttled under: its instance plus its label if it has one, otherwise its
+/// system name
+fn throttle_key(entry: &LuaSystemEntry) -> (u64, String) {
+    let name = entry.ordering.label.as_deref().unwrap_or(&entry.system_name);
+    (entry.instance_id, name.to_string())
+}
+
+/// Whether a throttled system has waited long enough since it last ran
+fn throttle_elapsed(max_hz: f32, last_run: Option<std::time::Instant>, now: std::time::Instant) -> bool {
+    last_run.is_none_or(|last_run| now.duration_since(last_run).as_secs_f32() >= 1.0 / max_hz)
+}
+
+/// The part of `execution_order` to run this frame: from the first system deferred by the
+/// frame budget (`next_index`) to the end. Never wraps around, so before/after constraints hold.
+pub fn systems_for_this_frame(execution_order: &[usize], next_index: usize) -> &[usize] {
+    execution_order
+        .get(next_index..)
+        .filter(|rest| !rest.is_empty())
+        .unwrap_or(execution_order)
+}
+
+/// Compute the execution order of systems from their before/after constraints (Kahn's algorithm)
+///
+/// Unconstrained systems keep registration order. Returns the ordered indices and, if the
+/// constraints contain a cycle, the names of the systems involved (appended in registration order).
+pub fn topological_system_order(systems: &[LuaSystemEntry]) -> (Vec<usize>, Vec<String>) {
+    let count = systems.len();
+    if systems.iter().all(|s| s.ordering.is_empty()) {
+        return ((0..count).collect(), Vec::new());
+    }
+
+    // Label -> indices of systems carrying that label
+    let mut labels: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (idx, entry) in systems.iter().enumerate() {
+        if let Some(label) = &entry.ordering.label {
+            labels.entry(label.as_str()).or_default().push(idx);
+        }
+    }
+
+    // Edges: a -> b means a runs before b
+    let mut edges: Vec<HashSet<usize>> = vec![HashSet::new(); count];
+    for (idx, entry) in systems.iter().enumerate() {
+        for target in &entry.ordering.before {
+            for &other in labels.get(target.as_str()).into_iter().flatten() {
+                if other != idx {
+                    edges[idx].insert(other);
+                }
+            }
+        }
+        for target in &entry.ordering.after {
+            for &other in labels.get(target.as_str()).into_iter().flatten() {
+                if other != idx {
+                    edges[other].insert(idx);
+                }
+            }
+        }
+    }
+
+    let mut in_degree = vec![0usize; count];
+    for targets in &edges {
+        for &target in targets {
+            in_degree[target] += 1;
+        }
+    }
+
+    // BTreeSet keeps ready systems in registration order
+    let mut ready: std::collections::BTreeSet<usize> =
+        (0..count).filter(|&idx| in_degree[idx] == 0).collect();
+    let mut order = Vec::with_capacity(count);
+
+    while let Some(idx) = ready.pop_first() {
+        order.push(idx);
+        for &target in &edges[idx] {
+            in_degree[target] -= 1;
+            if in_degree[target] == 0 {
+                ready.insert(target);
+            }
+        }
+    }
+
+    // Anything left is part of (or blocked by) a cycle - run it anyway in registration order
+    let mut cycle = Vec::new();
+    if order.len() < count {
+        for idx in 0..count {
+            if in_degree[idx] > 0 {
+                cycle.push(systems[idx].system_name.clone());
+                order.push(idx);
+            }
+        }
+    }
+
+    (order, cycle)
+}
+
 /// System that runs registered Lua update systems with time-sliced execution
 /// 
 /// Systems are executed in round-robin order. If the frame budget is exceeded,
@@ -157,6 +566,9 @@ pub fn run_lua_systems(world: &mut World) {
         .get_resource::<crate::lua_frame_budget::LuaSystemProgress>()
         .cloned()
         .unwrap_or_default();
+    let profiles = world
+        .get_resource::<crate::lua_frame_budget::LuaSystemProfiles>()
+        .cloned();
     
     // Get parallel config (defaults to enabled)
     let parallel_config = world
@@ -179,11 +591,28 @@ pub fn run_lua_systems(world: &mut World) {
     // Get the list of systems (with per-system last_run ticks)
     let systems = registry.update_systems.lock().unwrap().clone();
     let total_systems = systems.len();
+    let paused_groups = registry.paused_groups.lock().unwrap().clone();
+    let max_hz = registry.max_hz.lock().unwrap().clone();
     
     if total_systems == 0 {
         return;
     }
     
+    // Resolve run-before/run-after constraints into an execution order
+    let (execution_order, cycle) = topological_system_order(&systems);
+    {
+        let mut reported_cycle = registry.reported_cycle.lock().unwrap();
+        if *reported_cycle != cycle {
+            if !cycle.is_empty() {
+                warn!(
+                    "[LUA_SYSTEM] Ordering cycle detected between systems {:?} - running them in registration order",
+                    cycle
+                );
+            }
+            *reported_cycle = cycle;
+        }
+    }
+
     // Group systems by state_id for potential parallel execution
     let state_groups = crate::lua_parallel::group_systems_by_state(&systems);
     let num_groups = state_groups.len();
@@ -196,8 +625,11 @@ pub fn run_lua_systems(world: &mut World) {
         );
     }
     
-    // Start from where we left off last frame (for fairness)
-    let start_index = progress.next_index();
+    // Resume at the first system the budget deferred last frame; the tail runs in
+    // execution order and the next pass starts from the beginning again
+    let frame_order = systems_for_this_frame(&execution_order, progress.next_index());
+    let start_index = total_systems - frame_order.len();
+    progress.resume_at(start_index);
     
     // Track how many systems we've run this frame
     let mut systems_run = 0;
@@ -208,10 +640,34 @@ pub fn run_lua_systems(world: &mut World) {
     // Track systems that should be removed (one-shot systems that returned true)
     let mut systems_to_remove: Vec<usize> = Vec::new();
     
-    // Run systems in round-robin order
-    for i in 0..total_systems {
-        let actual_index = (start_index + i) % total_systems;
+    for &actual_index in frame_order {
         let entry = &systems[actual_index];
+
+        // Conditional systems that don't apply right now never enter the Lua VM
+        if let Some(condition) = &entry.condition {
+            if !condition.evaluate(world) {
+                progress.advance(total_systems);
+                continue;
+            }
+        }
+
+        // Systems in a paused group are suspended entirely
+        if entry.group.as_ref().is_some_and(|group| paused_groups.contains(group)) {
+            progress.advance(total_systems);
+            continue;
+        }
+
+        // Throttled systems skip frames until 1/max_hz seconds have passed since they last ran
+        let throttle = throttle_key(entry);
+        if let Some(&max_hz) = max_hz.get(&throttle) {
+            let now = std::time::Instant::now();
+            let last_run = registry.throttle_last_run.lock().unwrap().get(&throttle).copied();
+            if !throttle_elapsed(max_hz, last_run, now) {
+                progress.advance(total_systems);
+                continue;
+            }
+            registry.throttle_last_run.lock().unwrap().insert(throttle, now);
+        }
         
         // Get this system's own last_run tracking
         let last_run_for_system = entry.last_run;
@@ -245,8 +701,11 @@ pub fn run_lua_systems(world: &mut World) {
 
         // Time this system
         let timer = crate::lua_frame_budget::SystemTimer::start();
+
+        // Tag queued component updates with this system for conflict detection
+        update_queue.set_current_system(Some(format!("{}#{}", entry.system_name, actual_index)));
         
-        match run_single_lua_system_fast(
+        let result = run_single_lua_system_fast(
             &lua_state,
             &entry.system_key,
             world,
@@ -263,7 +722,10 @@ pub fn run_lua_systems(world: &mut World) {
             this_run,
             query_cache.as_ref(),
             current_frame,
-        ) {
+        );
+        update_queue.set_current_system(None);
+
+        match result {
             Ok(should_remove) => {
                 if should_remove {
                     // One-shot system - mark for removal
@@ -292,6 +754,9 @@ pub fn run_lua_systems(world: &mut World) {
         
         // Record per-system timing for profiler (using the descriptive system_name)
         progress.record_system_time(entry.system_name.clone(), elapsed, entry.state_id);
+        if let Some(profiles) = &profiles {
+            profiles.record(&entry.system_name, elapsed);
+        }
         
         systems_run += 1;
         
@@ -304,7 +769,7 @@ pub fn run_lua_systems(world: &mut World) {
             debug!(
                 "[LUA_PERF] 🚥 Budget exceeded ({:?}ms): ran {} of {} systems. Deferred {} systems.",
                 frame_budget.max_seconds * 1000.0,
-                systems_run, total_systems, total_systems - systems_run
+                systems_run, total_systems, total_systems - start_index - systems_run
             );
             break;
         }
@@ -848,11 +1313,26 @@ fn run_single_lua_system(
                     }
                 }
 
+                // Drop update transactions the instance never committed or rolled back
+                let open_transaction_keys = world
+                    .resource::<crate::component_update_queue::ComponentUpdateQueue>()
+                    .clear_instance_transactions(instance_id);
+                for key_arc in open_transaction_keys {
+                    if let Ok(key) = Arc::try_unwrap(key_arc) {
+                        let _ = lua_ctx.remove_registry_value(key);
+                    }
+                }
+
                 // Despawn all entities from this instance
                 crate::script_entities::despawn_instance_entities(world_mut, instance_id);
 
-                // Remove old instance from registry
+                // Remove old instance from registry and drop its sandbox state
                 script_registry.remove_instance(instance_id);
+                if let Some(script_ctx) =
+                    world.get_resource::<crate::lua_integration::LuaScriptContext>()
+                {
+                    script_ctx.remove_sandbox(instance_id);
+                }
 
                 Ok(())
             };
@@ -1021,6 +1501,34 @@ fn run_single_lua_system(
             })?,
         )?;
 
+        // despawn_recursive(entity_id) - despawn an entity and its descendants (same as despawn())
+        world_table.set(
+            "despawn_recursive",
+            scope.create_function({
+                move |_lua_ctx, (_self, entity_id): (LuaTable, u64)| {
+                    let entity = spawn_queue.resolve_entity(entity_id);
+                    world
+                        .resource::<crate::despawn_queue::DespawnQueue>()
+                        .queue_recursive(entity);
+                    Ok(())
+                }
+            })?,
+        )?;
+
+        // despawn_single(entity_id) - despawn only the entity, detaching its children
+        world_table.set(
+            "despawn_single",
+            scope.create_function({
+                move |_lua_ctx, (_self, entity_id): (LuaTable, u64)| {
+                    let entity = spawn_queue.resolve_entity(entity_id);
+                    world
+                        .resource::<crate::despawn_queue::DespawnQueue>()
+                        .queue_single(entity);
+                    Ok(())
+                }
+            })?,
+        )?;
+
         // despawn_all(tag_name) - despawn all entities with a specific tag component
         world_table.set(
             "despawn_all",
@@ -1211,67 +1719,10 @@ fn run_single_lua_system(
 
         let write_message_fn = scope.create_function({
             let pending_messages = pending_messages.clone();
-            move |_lua_ctx, (_self, message_type_name, data_table): (LuaTable, String, LuaTable)| {
-                // Convert Lua table to JSON value (reuse the same conversion logic)
-                fn lua_to_json(value: &LuaValue) -> serde_json::Value {
-                    match value {
-                        LuaValue::Nil => serde_json::Value::Null,
-                        LuaValue::Boolean(b) => serde_json::Value::Bool(*b),
-                        LuaValue::Integer(i) => serde_json::json!(*i),
-                        LuaValue::Number(n) => serde_json::json!(*n),
-                        LuaValue::String(s) => match s.to_str() {
-                            Ok(cow) => serde_json::Value::String(cow.to_string()),
-                            Err(_) => serde_json::Value::String(String::new()),
-                        },
-                        LuaValue::Table(t) => {
-                            // Determine if array or object
-                            let mut is_array = true;
-                            let mut max_key = 0i64;
-                            for pair in t.clone().pairs::<LuaValue, LuaValue>() {
-                                if let Ok((key, _)) = pair {
-                                    match key {
-                                        LuaValue::Integer(i) if i > 0 => {
-                                            max_key = max_key.max(i);
-                                        }
-                                        _ => {
-                                            is_array = false;
-                                            break;
-                                        }
-                                    }
-                                }
-                            }
-
-                            if is_array && max_key > 0 {
-                                let mut arr = Vec::new();
-                                for i in 1..=max_key {
-                                    if let Ok(val) = t.get::<LuaValue>(i) {
-                                        arr.push(lua_to_json(&val));
-                                    }
-                                }
-                                serde_json::Value::Array(arr)
-                            } else {
-                                let mut map = serde_json::Map::new();
-                                for pair in t.clone().pairs::<String, LuaValue>() {
-                                    if let Ok((key, val)) = pair {
-                                        map.insert(key, lua_to_json(&val));
-                                    }
-                                }
-                                serde_json::Value::Object(map)
-                            }
-                        }
-                        _ => serde_json::Value::Null,
-                    }
-                }
-
-                let json_data = lua_to_json(&LuaValue::Table(data_table.clone()));
-
-                debug!(
-                    "[WRITE_MESSAGE] Queueing message '{}': {:?}",
-                    message_type_name, json_data
-                );
-                pending_messages.queue_message(message_type_name.clone(), json_data);
-
-                Ok(())
+            move |lua_ctx, (_self, message_type_name, data_table): (LuaTable, String, LuaTable)| {
+                // Keep a copy of the table in the Lua registry; dispatch reads it without a JSON round-trip
+                debug!("[WRITE_MESSAGE] Queueing message '{}'", message_type_name);
+                pending_messages.queue_lua_message(lua_ctx, message_type_name, &data_table)
             }
         })?;
 
@@ -1285,6 +1736,76 @@ fn run_single_lua_system(
     })
 }
 
+/// Run `f` with a fresh `world` userdata in `lua`, the way a Lua system call gets one
+/// For Lua code resumed after the system that started it returned (coroutines), whose original
+/// `world` has been destroyed with its scope.
+pub fn with_lua_world_scope<R>(
+    lua: &Lua,
+    world: &World,
+    last_run: u32,
+    this_run: u32,
+    f: impl FnOnce(&LuaAnyUserData) -> LuaResult<R>,
+) -> LuaResult<R> {
+    let missing = |name: &str| LuaError::RuntimeError(format!("{} resource not found", name));
+    let component_registry = world
+        .get_resource::<ComponentRegistry>()
+        .ok_or_else(|| missing("ComponentRegistry"))?;
+    let world_ctx = crate::lua_world_context::LuaWorldContext::new(
+        world,
+        component_registry,
+        world
+            .get_resource::<ComponentUpdateQueue>()
+            .ok_or_else(|| missing("ComponentUpdateQueue"))?
+            .clone(),
+        world.get_resource::<SpawnQueue>().ok_or_else(|| missing("SpawnQueue"))?.clone(),
+        world
+            .get_resource::<crate::serde_components::SerdeComponentRegistry>()
+            .ok_or_else(|| missing("SerdeComponentRegistry"))?
+            .clone(),
+        world
+            .get_resource::<crate::script_registry::ScriptRegistry>()
+            .ok_or_else(|| missing("ScriptRegistry"))?
+            .clone(),
+        world
+            .get_resource::<LuaSystemRegistry>()
+            .ok_or_else(|| missing("LuaSystemRegistry"))?
+            .clone(),
+        world
+            .get_resource::<crate::despawn_queue::DespawnQueue>()
+            .ok_or_else(|| missing("DespawnQueue"))?
+            .clone(),
+        world
+            .get_resource::<crate::event_sender::PendingLuaMessages>()
+            .cloned()
+            .unwrap_or_default(),
+        last_run,
+        std::time::Instant::now(),
+        this_run,
+        world.get_resource::<crate::query_cache::LuaQueryCache>().cloned(),
+        world
+            .get_resource::<bevy::diagnostic::FrameCount>()
+            .map(|f| f.0 as u64)
+            .unwrap_or(0),
+        world.get_resource::<crate::asset_loading::AssetRegistry>().cloned(),
+    );
+
+    lua.scope(|scope| {
+        let active_world = world_ctx.active_world();
+        let world_ud = scope.create_userdata(world_ctx)?;
+        let previous_world = lua.set_app_data(active_world);
+        let result = f(&world_ud);
+        match previous_world {
+            Some(previous) => {
+                lua.set_app_data(previous);
+            }
+            None => {
+                lua.remove_app_data::<crate::lua_world_context::ActiveLuaWorld>();
+            }
+        }
+        result
+    })
+}
+
 /// Optimized version of run_single_lua_system using LuaWorldContext userdata
 /// This eliminates the overhead of creating 10+ closures per system call
 /// by using statically-defined userdata methods instead
@@ -1346,6 +1867,9 @@ pub fn run_single_lua_system_fast<'w>(
         
         let t3 = Instant::now();
 
+        // Expose the world to entity snapshot methods (entity:call_method) for this call
+        let active_world = world_ctx.active_world();
+
         // Create scoped userdata that won't escape this scope
         let world_ud = scope.create_userdata(world_ctx)?;
         
@@ -1357,7 +1881,18 @@ pub fn run_single_lua_system_fast<'w>(
         // Track if this is a one-shot system (returns true)
         let mut should_remove = false;
         
-        match thread.resume::<mlua::Value>(world_ud) {
+        let previous_world = lua.set_app_data(active_world);
+        let resume_result = thread.resume::<mlua::Value>(world_ud);
+        match previous_world {
+            Some(previous) => {
+                lua.set_app_data(previous);
+            }
+            None => {
+                lua.remove_app_data::<crate::lua_world_context::ActiveLuaWorld>();
+            }
+        }
+
+        match resume_result {
             Ok(yield_value) => {
                 match thread.status() {
                     mlua::ThreadStatus::Finished => {
@@ -1431,3 +1966,170 @@ pub fn run_single_lua_system_fast<'w>(
     
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &Arc<LuaRegistryKey>, name: &str, before: &[&str], after: &[&str]) -> LuaSystemEntry {
+        LuaSystemEntry {
+            instance_id: 1,
+            system_key: key.clone(),
+            last_run: 0,
+            last_run_real_time: std::time::Instant::now(),
+            state_id: 0,
+            system_name: name.to_string(),
+            ordering: LuaSystemOrdering {
+                label: Some(name.to_string()),
+                before: before.iter().map(|s| s.to_string()).collect(),
+                after: after.iter().map(|s| s.to_string()).collect(),
+            },
+            condition: None,
+            group: None,
+        }
+    }
+
+    #[test]
+    fn test_topological_order_respects_constraints() {
+        let lua = Lua::new();
+        let key = Arc::new(lua.create_registry_value(LuaValue::Nil).unwrap());
+
+        let systems = vec![
+            entry(&key, "animation", &[], &[]),
+            entry(&key, "ai", &["animation"], &["input"]),
+            entry(&key, "input", &[], &[]),
+        ];
+
+        let (order, cycle) = topological_system_order(&systems);
+        assert!(cycle.is_empty());
+        assert_eq!(order, vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn test_deferred_systems_resume_without_wrapping() {
+        let order = vec![2, 0, 1, 3];
+        // Budget ran out after two systems: the next frame runs only the deferred tail, in order
+        assert_eq!(systems_for_this_frame(&order, 2), &[1, 3]);
+        // A full pass starts from the beginning
+        assert_eq!(systems_for_this_frame(&order, 0), &[2, 0, 1, 3]);
+        // Systems were removed since the index was recorded
+        assert_eq!(systems_for_this_frame(&order, 4), &[2, 0, 1, 3]);
+        assert_eq!(systems_for_this_frame(&order, 9), &[2, 0, 1, 3]);
+    }
+
+    #[test]
+    fn test_topological_order_reports_cycle() {
+        let lua = Lua::new();
+        let key = Arc::new(lua.create_registry_value(LuaValue::Nil).unwrap());
+
+        let systems = vec![
+            entry(&key, "a", &["b"], &[]),
+            entry(&key, "b", &["a"], &[]),
+            entry(&key, "c", &[], &[]),
+        ];
+
+        let (order, cycle) = topological_system_order(&systems);
+        assert_eq!(order, vec![2, 0, 1]);
+        assert_eq!(cycle, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct GameState {
+        phase: String,
+        wave: u32,
+    }
+
+    #[test]
+    fn test_conditions_check_resource_fields_and_existence() {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<GameState>();
+        world.insert_resource(registry);
+
+        let lua = Lua::new();
+        let condition = |source: &str| {
+            let table: LuaTable = lua.load(source).eval().unwrap();
+            LuaSystemCondition::from_lua_table(&lua, &table).unwrap()
+        };
+        let in_combat = condition(r#"{ resource = "GameState", field = "phase", equals = "combat" }"#);
+        let not_menu = condition(r#"{ resource = "GameState", field = "phase", not_equals = "menu" }"#);
+        let wave_two = condition(r#"{ resource = "GameState", field = "wave", equals = 2 }"#);
+        let exists = condition(r#"{ resource_exists = "GameState" }"#);
+
+        // Nothing applies before the resource is inserted
+        assert!(!in_combat.evaluate(&world));
+        assert!(!not_menu.evaluate(&world));
+        assert!(!exists.evaluate(&world));
+
+        world.insert_resource(GameState { phase: "combat".to_string(), wave: 2 });
+        assert!(in_combat.evaluate(&world));
+        assert!(not_menu.evaluate(&world));
+        assert!(wave_two.evaluate(&world));
+        assert!(exists.evaluate(&world));
+
+        world.resource_mut::<GameState>().phase = "menu".to_string();
+        assert!(!in_combat.evaluate(&world));
+        assert!(!not_menu.evaluate(&world));
+
+        let table: LuaTable = lua.load(r#"{ resource = "GameState", field = "phase" }"#).eval().unwrap();
+        assert!(LuaSystemCondition::from_lua_table(&lua, &table).is_err());
+    }
+
+    #[test]
+    fn test_throttled_systems_wait_for_their_interval() {
+        let lua = Lua::new();
+        let key = Arc::new(lua.create_registry_value(mlua::Value::Nil).unwrap());
+        let registry = LuaSystemRegistry::default();
+        registry.register_lua_system_throttled(1, key.clone(), 0, "pathfinding", 10.0);
+        assert_eq!(registry.system_hz(1, "pathfinding"), Some(10.0));
+        assert_eq!(registry.system_hz(2, "pathfinding"), None);
+
+        let start = std::time::Instant::now();
+        assert!(throttle_elapsed(10.0, None, start));
+        assert!(!throttle_elapsed(10.0, Some(start), start + std::time::Duration::from_millis(50)));
+        assert!(throttle_elapsed(10.0, Some(start), start + std::time::Duration::from_millis(100)));
+
+        registry.set_system_hz(1, "pathfinding", 0.0);
+        assert_eq!(registry.system_hz(1, "pathfinding"), None);
+
+        // Stopping an instance forgets its rate limits
+        registry.register_lua_system_throttled(3, key, 0, "pathfinding", 5.0);
+        registry.clear_instance_systems(3);
+        assert_eq!(registry.system_hz(3, "pathfinding"), None);
+    }
+
+    #[test]
+    fn test_clear_instance_systems_returns_unshared_names() {
+        let lua = Lua::new();
+        let key = Arc::new(lua.create_registry_value(mlua::Value::Nil).unwrap());
+        let registry = LuaSystemRegistry::default();
+        registry.register_system(1, key.clone(), 0, "Update:scripts/enemy.lua".to_string());
+        registry.register_system(1, key.clone(), 0, "Update:scripts/shared.lua".to_string());
+        registry.register_system(2, key, 0, "Update:scripts/shared.lua".to_string());
+
+        // The shared name is still run by instance 2, so its profile has to stay
+        assert_eq!(registry.clear_instance_systems(1), vec!["Update:scripts/enemy.lua".to_string()]);
+        assert_eq!(registry.clear_instance_systems(2), vec!["Update:scripts/shared.lua".to_string()]);
+        assert!(registry.clear_instance_systems(2).is_empty());
+    }
+
+    #[test]
+    fn test_pause_and_resume_system_groups() {
+        let lua = Lua::new();
+        let key = Arc::new(lua.create_registry_value(mlua::Value::Nil).unwrap());
+        let registry = LuaSystemRegistry::default();
+        registry.register_lua_system_in_group(1, key.clone(), 0, "ai_update", "gameplay");
+
+        let systems = registry.update_systems.lock().unwrap().clone();
+        assert_eq!(systems[0].group.as_deref(), Some("gameplay"));
+
+        assert!(!registry.is_group_paused("gameplay"));
+        assert!(registry.pause_group("gameplay"));
+        assert!(!registry.pause_group("gameplay"));
+        assert!(registry.is_group_paused("gameplay"));
+        assert!(registry.resume_group("gameplay"));
+        assert!(!registry.resume_group("gameplay"));
+        assert!(!registry.is_group_paused("gameplay"));
+    }
+}
@@ -0,0 +1,274 @@
+//! Game-time timers with Lua callbacks
+//!
+//! ```lua
+//! local handle = world:create_timer(5.0, function() spawn_wave() end, false)
+//! world:create_timer(1.0, function(handle) print("tick") end, true)   -- repeats every second
+//! world:create_timer(0.5, function() return not done end, true)      -- return false to stop
+//! world:cancel_timer(handle)
+//! ```
+//!
+//! Timers advance by the frame delta of `Time` in `tick_lua_timers`. One-shot timers are
+//! removed when they fire; repeating timers carry over the leftover time so they keep their
+//! cadence. Callbacks receive the timer handle; a repeating timer stops when its callback
+//! returns false.
+//!
+//! Handles index a slab inside `TimerRegistry`, so cancellation is O(1). Each slot keeps a
+//! generation counter that is packed into the handle, so a stale handle never cancels a
+//! newer timer that reused the slot.
+
+use bevy::prelude::*;
+use mlua::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// A running timer
+struct LuaTimer {
+    elapsed: f32,
+    duration: f32,
+    callback: Arc<LuaRegistryKey>,
+    repeat: bool,
+    /// Lua state the timer was created in (0=primary, >=1=instanced)
+    state_id: usize,
+    /// Script instance that created the timer (for cleanup on reload/stop)
+    instance_id: u64,
+}
+
+/// Slab slot: the generation increases every time the slot is freed
+#[derive(Default)]
+struct TimerSlot {
+    generation: u32,
+    timer: Option<LuaTimer>,
+}
+
+#[derive(Default)]
+struct TimerSlab {
+    slots: Vec<TimerSlot>,
+    free: Vec<u32>,
+    active: usize,
+}
+
+impl TimerSlab {
+    fn insert(&mut self, timer: LuaTimer) -> u64 {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                self.slots.push(TimerSlot::default());
+                (self.slots.len() - 1) as u32
+            }
+        };
+        let slot = &mut self.slots[index as usize];
+        slot.timer = Some(timer);
+        self.active += 1;
+        pack_handle(index, slot.generation)
+    }
+
+    fn remove_at(&mut self, index: u32) -> Option<LuaTimer> {
+        let slot = self.slots.get_mut(index as usize)?;
+        let timer = slot.timer.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(index);
+        self.active -= 1;
+        Some(timer)
+    }
+
+    fn remove(&mut self, handle: u64) -> Option<LuaTimer> {
+        let (index, generation) = unpack_handle(handle);
+        if self.slots.get(index as usize)?.generation != generation {
+            return None;
+        }
+        self.remove_at(index)
+    }
+}
+
+/// Handles start at 1 so that 0 is never a valid handle
+fn pack_handle(index: u32, generation: u32) -> u64 {
+    ((generation as u64) << 32) | (index as u64 + 1)
+}
+
+fn unpack_handle(handle: u64) -> (u32, u32) {
+    (((handle & 0xFFFF_FFFF) as u32).wrapping_sub(1), (handle >> 32) as u32)
+}
+
+/// A timer that expired this frame, ready to have its callback called
+struct FiredTimer {
+    handle: u64,
+    callback: Arc<LuaRegistryKey>,
+    state_id: usize,
+    instance_id: u64,
+}
+
+/// Resource holding all Lua timers
+#[derive(Resource, Clone, Default)]
+pub struct TimerRegistry {
+    timers: Arc<Mutex<TimerSlab>>,
+}
+
+impl TimerRegistry {
+    /// Start a timer that calls `callback` after `duration` seconds of game time
+    /// Returns the handle used by `cancel`
+    pub fn create(&self, lua: &Lua, duration: f32, callback: LuaFunction, repeat: bool) -> LuaResult<u64> {
+        if !(duration >= 0.0) {
+            return Err(LuaError::RuntimeError(format!(
+                "create_timer: duration must be a non-negative number of seconds, got {}",
+                duration
+            )));
+        }
+        let timer = LuaTimer {
+            elapsed: 0.0,
+            duration,
+            callback: Arc::new(lua.create_registry_value(callback)?),
+            repeat,
+            state_id: lua.globals().get("__LUA_STATE_ID__").unwrap_or(0),
+            instance_id: lua.globals().get("__INSTANCE_ID__").unwrap_or(0),
+        };
+        let handle = self.timers.lock().unwrap().insert(timer);
+        debug!("[LUA_TIMER] Created timer {} ({}s, repeat={})", handle, duration, repeat);
+        Ok(handle)
+    }
+
+    /// Cancel a timer. Returns true if it was still running.
+    pub fn cancel(&self, handle: u64) -> bool {
+        self.timers.lock().unwrap().remove(handle).is_some()
+    }
+
+    /// Cancel all timers created by a script instance
+    pub fn clear_instance_timers(&self, instance_id: u64) {
+        let mut slab = self.timers.lock().unwrap();
+        for index in 0..slab.slots.len() as u32 {
+            let owned = slab.slots[index as usize]
+                .timer
+                .as_ref()
+                .is_some_and(|timer| timer.instance_id == instance_id);
+            if owned {
+                slab.remove_at(index);
+            }
+        }
+    }
+
+    /// Number of running timers
+    pub fn len(&self) -> usize {
+        self.timers.lock().unwrap().active
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Advance every timer by `delta` seconds and collect the ones that expired
+    /// One-shot timers are removed here; repeating timers keep the time past their duration
+    fn advance(&self, delta: f32) -> Vec<FiredTimer> {
+        let mut slab = self.timers.lock().unwrap();
+        let mut fired = Vec::new();
+        let mut finished = Vec::new();
+        for (index, slot) in slab.slots.iter_mut().enumerate() {
+            let Some(timer) = slot.timer.as_mut() else {
+                continue;
+            };
+            timer.elapsed += delta;
+            if timer.elapsed < timer.duration {
+                continue;
+            }
+            fired.push(FiredTimer {
+                handle: pack_handle(index as u32, slot.generation),
+                callback: timer.callback.clone(),
+                state_id: timer.state_id,
+                instance_id: timer.instance_id,
+            });
+            if timer.repeat {
+                // Fire at most once per frame, even if several periods passed
+                timer.elapsed = if timer.duration > 0.0 {
+                    (timer.elapsed - timer.duration).rem_euclid(timer.duration)
+                } else {
+                    0.0
+                };
+            } else {
+                finished.push(index as u32);
+            }
+        }
+        for index in finished {
+            slab.remove_at(index);
+        }
+        fired
+    }
+}
+
+/// System that advances Lua timers and calls the callbacks of expired ones
+pub fn tick_lua_timers(
+    registry: Res<TimerRegistry>,
+    lua_ctx: Option<Res<crate::lua_integration::LuaScriptContext>>,
+    time: Res<Time>,
+) {
+    let Some(lua_ctx) = lua_ctx else {
+        return;
+    };
+    if registry.is_empty() {
+        return;
+    }
+
+    // The lock is released before callbacks run, so they can create or cancel timers
+    for fired in registry.advance(time.delta_secs()) {
+        let lua = lua_ctx.get_lua_state(fired.state_id);
+
+        // Run in the script context that created the timer, then restore the caller's
+        let globals = lua.globals();
+        let previous_instance_id: Option<u64> = globals.get("__INSTANCE_ID__").ok();
+        let previous_phase: Option<String> = globals.get("__SPAWN_PHASE__").ok();
+        let _ = globals.set("__INSTANCE_ID__", fired.instance_id);
+        let _ = globals.set("__SPAWN_PHASE__", "runtime");
+
+        let result = lua
+            .registry_value::<LuaFunction>(&fired.callback)
+            .and_then(|callback| callback.call::<Option<bool>>(fired.handle));
+        let _ = globals.set("__INSTANCE_ID__", previous_instance_id);
+        let _ = globals.set("__SPAWN_PHASE__", previous_phase);
+        match result {
+            // Returning false from a repeating timer's callback stops it
+            Ok(Some(false)) => {
+                registry.cancel(fired.handle);
+            }
+            Ok(_) => {}
+            Err(e) => error!("[LUA_TIMER] Callback of timer {} failed: {}", fired.handle, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn callback(lua: &Lua) -> LuaFunction {
+        lua.load("return function() end").eval().unwrap()
+    }
+
+    #[test]
+    fn test_one_shot_and_repeating_timers() {
+        let lua = Lua::new();
+        let registry = TimerRegistry::default();
+        let once = registry.create(&lua, 1.0, callback(&lua), false).unwrap();
+        let repeating = registry.create(&lua, 0.5, callback(&lua), true).unwrap();
+        assert_eq!(registry.len(), 2);
+
+        let fired: Vec<u64> = registry.advance(0.6).iter().map(|f| f.handle).collect();
+        assert_eq!(fired, vec![repeating]);
+
+        let fired: Vec<u64> = registry.advance(0.6).iter().map(|f| f.handle).collect();
+        assert_eq!(fired, vec![once, repeating]);
+        assert_eq!(registry.len(), 1);
+        assert!(!registry.cancel(once));
+        assert!(registry.cancel(repeating));
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    fn test_stale_handle_does_not_cancel_reused_slot() {
+        let lua = Lua::new();
+        let registry = TimerRegistry::default();
+        let first = registry.create(&lua, 1.0, callback(&lua), false).unwrap();
+        assert!(registry.cancel(first));
+
+        let second = registry.create(&lua, 1.0, callback(&lua), false).unwrap();
+        assert_ne!(first, second);
+        assert!(!registry.cancel(first));
+        assert_eq!(registry.len(), 1);
+        assert!(registry.create(&lua, -1.0, callback(&lua), false).is_err());
+    }
+}
@@ -0,0 +1,446 @@
+//! Numeric component field tweening driven from Lua
+//!
+//! ```lua
+//! -- Tween Transform.translation.x to 100 over 2 seconds
+//! local handle = world:tween(entity, "Transform", "translation.x", 100.0, 2.0, "ease_out")
+//!
+//! -- Chain: starts when the previous tween finishes
+//! world:tween(entity, "Transform", "translation.x", 0.0, 1.0, "bounce", handle)
+//!
+//! world:cancel_tween(handle)
+//! ```
+//!
+//! Intermediate values are written through `ComponentUpdateQueue`, so tweens interact with
+//! other Lua writes the same way `entity:set()` does; all tweens on one entity/component are
+//! merged into a single update per frame. A new (unchained) tween on the same
+//! entity/component/field cancels earlier ones.
+
+use bevy::prelude::*;
+use bevy::reflect::{GetPath, PartialReflect};
+use mlua::prelude::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Easing curve applied to tween progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EasingFn {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    Bounce,
+}
+
+impl EasingFn {
+    /// Parse an easing name from Lua
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "linear" => Some(EasingFn::Linear),
+            "ease_in" => Some(EasingFn::EaseIn),
+            "ease_out" => Some(EasingFn::EaseOut),
+            "ease_in_out" => Some(EasingFn::EaseInOut),
+            "bounce" => Some(EasingFn::Bounce),
+            _ => None,
+        }
+    }
+
+    /// Map linear progress `t` (0..=1) to eased progress
+    pub fn apply(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            EasingFn::Linear => t,
+            EasingFn::EaseIn => t * t,
+            EasingFn::EaseOut => t * (2.0 - t),
+            EasingFn::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            }
+            EasingFn::Bounce => bounce_out(t),
+        }
+    }
+}
+
+fn bounce_out(t: f64) -> f64 {
+    const N: f64 = 7.5625;
+    const D: f64 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+/// A single running (or waiting) tween
+pub struct TweenEntry {
+    pub handle: u64,
+    pub entity: Entity,
+    pub component: String,
+    /// Dot-separated field path, e.g. "translation.x"
+    pub field_path: String,
+    /// Start value, read from the component when the tween becomes active
+    pub start: Option<f64>,
+    pub end: f64,
+    pub duration: f32,
+    pub elapsed: f32,
+    pub easing: EasingFn,
+    /// Handle of a tween that must finish before this one starts
+    pub after: Option<u64>,
+}
+
+impl TweenEntry {
+    fn targets_same_field(&self, other: &TweenEntry) -> bool {
+        self.entity == other.entity
+            && self.component == other.component
+            && self.field_path == other.field_path
+    }
+}
+
+/// Resource holding all active tweens
+#[derive(Resource, Clone)]
+pub struct TweenQueue {
+    tweens: Arc<Mutex<Vec<TweenEntry>>>,
+    next_handle: Arc<AtomicU64>,
+}
+
+impl Default for TweenQueue {
+    fn default() -> Self {
+        Self {
+            tweens: Arc::new(Mutex::new(Vec::new())),
+            next_handle: Arc::new(AtomicU64::new(1)),
+        }
+    }
+}
+
+impl TweenQueue {
+    /// Add a tween and return its handle
+    /// Unchained tweens cancel earlier tweens on the same entity/component/field
+    pub fn add(
+        &self,
+        entity: Entity,
+        component: String,
+        field_path: String,
+        end: f64,
+        duration: f32,
+        easing: EasingFn,
+        after: Option<u64>,
+    ) -> u64 {
+        let handle = self.next_handle.fetch_add(1, Ordering::SeqCst);
+        let entry = TweenEntry {
+            handle,
+            entity,
+            component,
+            field_path,
+            start: None,
+            end,
+            duration: duration.max(0.0),
+            elapsed: 0.0,
+            easing,
+            after,
+        };
+
+        let mut tweens = self.tweens.lock().unwrap();
+        if entry.after.is_none() {
+            tweens.retain(|existing| !existing.targets_same_field(&entry));
+        }
+        tweens.push(entry);
+        handle
+    }
+
+    /// Cancel a tween (and any tweens chained after it). Returns true if it was active.
+    pub fn cancel(&self, handle: u64) -> bool {
+        let mut tweens = self.tweens.lock().unwrap();
+        let before = tweens.len();
+        let mut cancelled = vec![handle];
+        while let Some(current) = cancelled.pop() {
+            tweens.retain(|t| {
+                if t.handle == current {
+                    return false;
+                }
+                if t.after == Some(current) {
+                    cancelled.push(t.handle);
+                    return false;
+                }
+                true
+            });
+        }
+        tweens.len() != before
+    }
+
+    /// Check whether a tween is still running or waiting
+    pub fn is_active(&self, handle: u64) -> bool {
+        self.tweens.lock().unwrap().iter().any(|t| t.handle == handle)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tweens.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Read a numeric field from a reflected value
+fn reflect_to_f64(value: &dyn PartialReflect) -> Option<f64> {
+    if let Some(v) = value.try_downcast_ref::<f32>() {
+        return Some(*v as f64);
+    }
+    if let Some(v) = value.try_downcast_ref::<f64>() {
+        return Some(*v);
+    }
+    if let Some(v) = value.try_downcast_ref::<i32>() {
+        return Some(*v as f64);
+    }
+    if let Some(v) = value.try_downcast_ref::<u32>() {
+        return Some(*v as f64);
+    }
+    None
+}
+
+/// Write a tweened value into the frame's update table for its entity/component
+/// For nested paths ("translation.x") the whole top-level field is sent, since component updates
+/// replace top-level fields. It is built from the component once and shared by every tween on
+/// that field, so sibling tweens ("translation.x" and "translation.y") don't overwrite each other.
+fn set_tween_value(
+    lua: &Lua,
+    update: &LuaTable,
+    component: &dyn PartialReflect,
+    field_path: &str,
+    value: f64,
+) -> LuaResult<()> {
+    let mut segments = field_path.split('.');
+    let top = segments.next().unwrap_or(field_path);
+    let rest: Vec<&str> = segments.collect();
+
+    if rest.is_empty() {
+        return update.set(top, value);
+    }
+
+    let top_table = match update.get::<Option<LuaTable>>(top)? {
+        Some(top_table) => top_table,
+        None => {
+            let top_value = component.reflect_path(top).map_err(|e| {
+                LuaError::RuntimeError(format!("Invalid tween field '{}': {}", field_path, e))
+            })?;
+            let LuaValue::Table(top_table) = crate::lua_world_api::reflection_to_lua(lua, top_value)? else {
+                return Err(LuaError::RuntimeError(format!(
+                    "Tween field '{}' is not a nested struct",
+                    top
+                )));
+            };
+            update.set(top, top_table.clone())?;
+            top_table
+        }
+    };
+
+    let mut table = top_table;
+    for segment in &rest[..rest.len() - 1] {
+        table = table.get::<LuaTable>(*segment)?;
+    }
+    table.set(rest[rest.len() - 1], value)
+}
+
+/// Value of a field in the most recent update still waiting in `ComponentUpdateQueue`
+/// Lets a tween start from a value set earlier this frame instead of the stale component
+fn pending_field_value(
+    lua_ctx: &crate::lua_integration::LuaScriptContext,
+    update_queue: &crate::component_update_queue::ComponentUpdateQueue,
+    entity: Entity,
+    component: &str,
+    field_path: &str,
+) -> Option<f64> {
+    let (key, _) = update_queue.peek_pending(entity, component)?;
+    let lua = lua_ctx.state_for_key(&key);
+    let mut value = update_queue.peek_pending_value(&lua, entity, component).ok()??;
+    for segment in field_path.split('.') {
+        let LuaValue::Table(table) = value else {
+            return None;
+        };
+        value = table.get(segment).ok()?;
+    }
+    match value {
+        LuaValue::Number(n) => Some(n),
+        LuaValue::Integer(i) => Some(i as f64),
+        _ => None,
+    }
+}
+
+/// System that advances all tweens and queues the interpolated values as component updates
+/// Tweens on the same entity/component are merged into a single update per frame
+pub fn process_tweens(world: &mut World) {
+    let Some(tween_queue) = world.get_resource::<TweenQueue>().cloned() else {
+        return;
+    };
+    if tween_queue.is_empty() {
+        return;
+    }
+    let Some(lua_ctx) = world.get_resource::<crate::lua_integration::LuaScriptContext>().cloned() else {
+        return;
+    };
+    let delta = world.resource::<Time>().delta_secs();
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let update_queue = world
+        .resource::<crate::component_update_queue::ComponentUpdateQueue>()
+        .clone();
+
+    let mut tweens = std::mem::take(&mut *tween_queue.tweens.lock().unwrap());
+    let waiting_on: std::collections::HashSet<u64> = tweens.iter().map(|t| t.handle).collect();
+    let registry = type_registry.read();
+    let mut finished = Vec::new();
+    // One update table per (entity, component), in the order the tweens were added
+    let mut updates: Vec<(Entity, String, LuaTable)> = Vec::new();
+
+    for tween in tweens.iter_mut() {
+        // Chained tweens wait for their predecessor to finish
+        if tween.after.is_some_and(|after| waiting_on.contains(&after)) {
+            continue;
+        }
+
+        let Some(reflect_component) = registry
+            .get_with_type_path(&tween.component)
+            .or_else(|| registry.get_with_short_type_path(&tween.component))
+            .and_then(|registration| registration.data::<ReflectComponent>())
+        else {
+            warn!("[TWEEN] Component '{}' is not a registered reflected component", tween.component);
+            finished.push(tween.handle);
+            continue;
+        };
+        let Some(component) = world
+            .get_entity(tween.entity)
+            .ok()
+            .and_then(|entity_ref| reflect_component.reflect(entity_ref))
+        else {
+            // Entity despawned or component removed
+            finished.push(tween.handle);
+            continue;
+        };
+
+        let start = match tween.start {
+            Some(start) => start,
+            None => {
+                let current = pending_field_value(
+                    &lua_ctx,
+                    &update_queue,
+                    tween.entity,
+                    &tween.component,
+                    &tween.field_path,
+                )
+                .or_else(|| {
+                    component
+                        .as_partial_reflect()
+                        .reflect_path(tween.field_path.as_str())
+                        .ok()
+                        .and_then(reflect_to_f64)
+                });
+                let Some(current) = current else {
+                    warn!(
+                        "[TWEEN] Field '{}' on {} is not a numeric field",
+                        tween.field_path, tween.component
+                    );
+                    finished.push(tween.handle);
+                    continue;
+                };
+                tween.start = Some(current);
+                current
+            }
+        };
+
+        tween.elapsed += delta;
+        let t = if tween.duration <= 0.0 {
+            1.0
+        } else {
+            (tween.elapsed / tween.duration) as f64
+        };
+        let value = start + (tween.end - start) * tween.easing.apply(t);
+
+        let update = match updates
+            .iter()
+            .find(|(entity, name, _)| *entity == tween.entity && *name == tween.component)
+        {
+            Some((_, _, update)) => Ok(update.clone()),
+            None => lua_ctx.lua.create_table().inspect(|update| {
+                updates.push((tween.entity, tween.component.clone(), update.clone()));
+            }),
+        };
+        if let Err(e) = update.and_then(|update| {
+            set_tween_value(&lua_ctx.lua, &update, component.as_partial_reflect(), &tween.field_path, value)
+        }) {
+            error!("[TWEEN] Failed to update {}.{}: {}", tween.component, tween.field_path, e);
+            finished.push(tween.handle);
+            continue;
+        }
+
+        if t >= 1.0 {
+            finished.push(tween.handle);
+        }
+    }
+    drop(registry);
+
+    for (entity, component, update) in updates {
+        if update.is_empty() {
+            continue;
+        }
+        match lua_ctx.lua.create_registry_value(update) {
+            Ok(key) => update_queue.queue_update(entity, component, key),
+            Err(e) => error!("[TWEEN] Failed to queue update for {}: {}", component, e),
+        }
+    }
+
+    tweens.retain(|t| !finished.contains(&t.handle));
+    // Tweens added or cancelled from Lua while we were advancing are kept
+    let mut queue = tween_queue.tweens.lock().unwrap();
+    tweens.append(&mut queue);
+    *queue = tweens;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_easing_endpoints() {
+        for easing in [
+            EasingFn::Linear,
+            EasingFn::EaseIn,
+            EasingFn::EaseOut,
+            EasingFn::EaseInOut,
+            EasingFn::Bounce,
+        ] {
+            assert!(easing.apply(0.0).abs() < 1e-9, "{:?} should start at 0", easing);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-9, "{:?} should end at 1", easing);
+        }
+        assert_eq!(EasingFn::Linear.apply(0.25), 0.25);
+        assert!(EasingFn::EaseIn.apply(0.5) < 0.5);
+        assert!(EasingFn::EaseOut.apply(0.5) > 0.5);
+        assert_eq!(EasingFn::from_name("ease_in_out"), Some(EasingFn::EaseInOut));
+        assert_eq!(EasingFn::from_name("wobble"), None);
+    }
+
+    #[test]
+    fn test_same_field_cancels_and_chains() {
+        let queue = TweenQueue::default();
+        let entity = Entity::PLACEHOLDER;
+        let first = queue.add(entity, "Transform".into(), "translation.x".into(), 1.0, 1.0, EasingFn::Linear, None);
+        let second = queue.add(entity, "Transform".into(), "translation.x".into(), 2.0, 1.0, EasingFn::Linear, None);
+        assert!(!queue.is_active(first));
+        assert!(queue.is_active(second));
+
+        let chained = queue.add(entity, "Transform".into(), "translation.x".into(), 3.0, 1.0, EasingFn::Linear, Some(second));
+        assert!(queue.is_active(chained));
+
+        // Cancelling a tween also cancels tweens chained after it
+        assert!(queue.cancel(second));
+        assert!(queue.is_empty());
+    }
+}
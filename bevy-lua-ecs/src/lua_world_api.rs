@@ -24,6 +24,151 @@ impl OrFilters {
     }
 }
 
+/// Spatial pre-filter: keep entities whose `GlobalTransform` lies within `radius` of `center` (XY plane)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RadiusFilter {
+    pub center: Vec2,
+    pub radius: f32,
+}
+
+impl RadiusFilter {
+    /// Entities without a `GlobalTransform` never match
+    pub fn contains(&self, world: &World, entity: Entity) -> bool {
+        world
+            .get::<GlobalTransform>(entity)
+            .is_some_and(|transform| {
+                transform.translation().truncate().distance_squared(self.center) <= self.radius * self.radius
+            })
+    }
+}
+
+/// Comparison operator for `where_field`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldOp {
+    Gt,
+    Lt,
+    Ge,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl FieldOp {
+    pub fn parse(op: &str) -> Option<Self> {
+        match op {
+            ">" => Some(Self::Gt),
+            "<" => Some(Self::Lt),
+            ">=" => Some(Self::Ge),
+            "<=" => Some(Self::Le),
+            "==" => Some(Self::Eq),
+            "!=" | "~=" => Some(Self::Ne),
+            _ => None,
+        }
+    }
+
+    fn is_ordering(self) -> bool {
+        !matches!(self, Self::Eq | Self::Ne)
+    }
+}
+
+/// Right-hand side of a `where_field` comparison
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldValue {
+    Nil,
+    Number(f64),
+    String(String),
+    Boolean(bool),
+}
+
+impl FieldValue {
+    fn from_lua(value: &LuaValue) -> Option<Self> {
+        match value {
+            LuaValue::Nil => Some(Self::Nil),
+            LuaValue::Integer(i) => Some(Self::Number(*i as f64)),
+            LuaValue::Number(n) => Some(Self::Number(*n)),
+            LuaValue::Boolean(b) => Some(Self::Boolean(*b)),
+            LuaValue::String(s) => Some(Self::String(s.to_str().ok()?.to_string())),
+            _ => None,
+        }
+    }
+}
+
+/// where_field("Health.value", ">", 0) - compare a component field after the snapshot is built
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldFilter {
+    pub component: String,
+    /// Field names below the component, e.g. ["translation", "x"]
+    pub path: Vec<String>,
+    pub op: FieldOp,
+    pub value: FieldValue,
+}
+
+impl FieldFilter {
+    /// Parse `"Component.field.subfield"`; a bare component name compares the component value itself
+    pub fn new(path: &str, op: &str, value: &LuaValue) -> LuaResult<Self> {
+        let mut segments = path.split('.').map(str::to_string);
+        let component = segments.next().filter(|name| !name.is_empty()).ok_or_else(|| {
+            LuaError::RuntimeError("where_field: expected a path like \"Health.value\"".to_string())
+        })?;
+        let op = FieldOp::parse(op).ok_or_else(|| {
+            LuaError::RuntimeError(format!(
+                "where_field: unknown operator '{}' (expected >, <, >=, <=, == or !=)",
+                op
+            ))
+        })?;
+        let value = FieldValue::from_lua(value).ok_or_else(|| {
+            LuaError::RuntimeError(format!(
+                "where_field: cannot compare against a {}",
+                value.type_name()
+            ))
+        })?;
+        if op.is_ordering() && !matches!(value, FieldValue::Number(_)) {
+            return Err(LuaError::RuntimeError(format!(
+                "where_field: '{}' needs a number, use == or != for strings and booleans",
+                path
+            )));
+        }
+        Ok(Self {
+            component,
+            path: segments.collect(),
+            op,
+            value,
+        })
+    }
+
+    /// Compare the field of `component_value`; a missing field or mismatched type never matches
+    pub fn matches(&self, component_value: &LuaValue) -> bool {
+        let mut current = component_value.clone();
+        for segment in &self.path {
+            current = match current {
+                LuaValue::Table(table) => match table.get::<LuaValue>(segment.as_str()) {
+                    Ok(value) => value,
+                    Err(_) => return false,
+                },
+                _ => return false,
+            };
+        }
+        let Some(field) = FieldValue::from_lua(&current) else {
+            return false;
+        };
+        match (&field, &self.value) {
+            (FieldValue::Number(lhs), FieldValue::Number(rhs)) => match self.op {
+                FieldOp::Gt => lhs > rhs,
+                FieldOp::Lt => lhs < rhs,
+                FieldOp::Ge => lhs >= rhs,
+                FieldOp::Le => lhs <= rhs,
+                FieldOp::Eq => lhs == rhs,
+                FieldOp::Ne => lhs != rhs,
+            },
+            _ => match self.op {
+                FieldOp::Eq => field == self.value,
+                FieldOp::Ne => field != self.value,
+                _ => false,
+            },
+        }
+    }
+}
+
 /// Lua userdata representing a query builder
 /// Supports Bevy-style filters: With, Without, Changed, Added, AnyOf, Or
 #[derive(Clone)]
@@ -42,6 +187,12 @@ pub struct LuaQueryBuilder {
     pub added_components: Vec<String>,
     /// Or<(F1, F2, ...)> - Union filter combinator
     pub or_filters: OrFilters,
+    /// within_radius(x, y, r) - distance check applied before snapshots are built
+    pub within_radius: Option<RadiusFilter>,
+    /// where_field(path, op, value) - field comparisons applied after snapshots are built (AND logic)
+    pub field_filters: Vec<FieldFilter>,
+    /// (method, name) pairs from filter_changed/filter_added, resolved against the registry in execute_query
+    pub reflected_filters: Vec<(&'static str, String)>,
 }
 
 impl LuaQueryBuilder {
@@ -54,6 +205,9 @@ impl LuaQueryBuilder {
             changed_components: Vec::new(),
             added_components: Vec::new(),
             or_filters: OrFilters::default(),
+            within_radius: None,
+            field_filters: Vec::new(),
+            reflected_filters: Vec::new(),
         }
     }
 
@@ -65,6 +219,18 @@ impl LuaQueryBuilder {
     }
 }
 
+/// Validate a component name passed to a change-detection filter method.
+/// Returns a Lua error (instead of panicking) when the name is missing or empty.
+fn validate_filter_name(method: &str, component_name: Option<String>) -> LuaResult<String> {
+    match component_name {
+        Some(name) if !name.trim().is_empty() => Ok(name),
+        _ => Err(LuaError::RuntimeError(format!(
+            "{}: expected a component type name (e.g. \"Transform\")",
+            method
+        ))),
+    }
+}
+
 impl LuaUserData for LuaQueryBuilder {
     fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
         // With<T> - require component (AND)
@@ -109,6 +275,25 @@ impl LuaUserData for LuaQueryBuilder {
             Ok(new_builder)
         });
 
+        // filter_changed(name) - Changed<T> with validation of the component name
+        // Usage: world:query_builder():with("Player"):filter_changed("Transform")
+        methods.add_method("filter_changed", |_, this, component_name: Option<String>| {
+            let component_name = validate_filter_name("filter_changed", component_name)?;
+            let mut new_builder = this.clone();
+            new_builder.reflected_filters.push(("filter_changed", component_name.clone()));
+            new_builder.changed_components.push(component_name);
+            Ok(new_builder)
+        });
+
+        // filter_added(name) - Added<T> with validation of the component name
+        methods.add_method("filter_added", |_, this, component_name: Option<String>| {
+            let component_name = validate_filter_name("filter_added", component_name)?;
+            let mut new_builder = this.clone();
+            new_builder.reflected_filters.push(("filter_added", component_name.clone()));
+            new_builder.added_components.push(component_name);
+            Ok(new_builder)
+        });
+
         // Or changed - at least one must have changed
         methods.add_method("or_changed", |_, this, component_name: String| {
             let mut new_builder = this.clone();
@@ -129,6 +314,28 @@ impl LuaUserData for LuaQueryBuilder {
             new_builder.or_filters.removed.push(component_name);
             Ok(new_builder)
         });
+
+        // within_radius(x, y, r) - only entities whose GlobalTransform is within r of (x, y)
+        // Usage: world:query_builder():with("Enemy"):within_radius(pos.x, pos.y, 200)
+        methods.add_method("within_radius", |_, this, (x, y, radius): (f32, f32, f32)| {
+            if !(radius >= 0.0) {
+                return Err(LuaError::RuntimeError(format!(
+                    "within_radius: radius must be a non-negative number, got {}",
+                    radius
+                )));
+            }
+            let mut new_builder = this.clone();
+            new_builder.within_radius = Some(RadiusFilter { center: Vec2::new(x, y), radius });
+            Ok(new_builder)
+        });
+
+        // where_field(path, op, value) - compare a component field (AND with other where_field calls)
+        // Usage: world:query_builder():with("Enemy"):where_field("Health.value", ">", 0):where_field("State", "==", "patrol")
+        methods.add_method("where_field", |_, this, (path, op, value): (String, String, LuaValue)| {
+            let mut new_builder = this.clone();
+            new_builder.field_filters.push(FieldFilter::new(&path, &op, &value)?);
+            Ok(new_builder)
+        });
     }
 }
 
@@ -165,8 +372,7 @@ impl LuaUserData for LuaEntitySnapshot {
         methods.add_method("get", |lua, this, component_name: String| {
             // FIRST: Check for pending updates in the queue (read-through cache)
             // This ensures get() returns the most recent set() value, even if not yet applied
-            if let Some(pending_key) = this.update_queue.peek_pending(this.entity, &component_name) {
-                let value: LuaValue = lua.registry_value(&*pending_key)?;
+            if let Some(value) = this.update_queue.peek_pending_value(lua, this.entity, &component_name)? {
                 return Ok(value);
             }
 
@@ -252,6 +458,11 @@ impl LuaUserData for LuaEntitySnapshot {
                 // Iterate through the table - keys are component names, values are component data
                 for pair in components.pairs::<String, LuaValue>() {
                     let (component_name, component_value) = pair?;
+                    let component_value = crate::serde_components::validate_lua_defined_component(
+                        lua,
+                        &component_name,
+                        component_value,
+                    )?;
                     
                     // Convert the value to a table if possible, or create a wrapper for scalar values
                     let component_data = match component_value {
@@ -276,6 +487,74 @@ impl LuaUserData for LuaEntitySnapshot {
             },
         );
 
+        // Conditionally set one component - applied only if the expression holds for the
+        // component's value when the update queue is processed (entity may have changed by then)
+        // Usage: entity:set_if("Health", { value = 0 }, "current_value.value > 0")
+        methods.add_method(
+            "set_if",
+            |lua, this, (component_name, component_value, expression): (String, LuaValue, String)| {
+                let predicate = crate::component_update_queue::FieldPredicate::parse(&expression)
+                    .map_err(LuaError::RuntimeError)?;
+                let component_value = crate::serde_components::validate_lua_defined_component(
+                    lua,
+                    &component_name,
+                    component_value,
+                )?;
+
+                // Same value handling as set(): non-table values become tuple-struct wrappers
+                let component_data = match component_value {
+                    LuaValue::Table(table) => table,
+                    _ => {
+                        let wrapper = lua.create_table()?;
+                        wrapper.set("_0", component_value)?;
+                        wrapper
+                    }
+                };
+
+                let registry_key = lua.create_registry_value(component_data)?;
+                this.update_queue.update_if(
+                    this.entity,
+                    component_name,
+                    registry_key,
+                    Box::new(move |current| predicate.evaluate(current)),
+                );
+                Ok(())
+            },
+        );
+
+        // Write back several components as a single batched update
+        // Usage: entity:flush_all({ Transform = {...}, Sprite = {...}, Health = {...} })
+        // All components are applied together in one entity access by the update processor
+        methods.add_method(
+            "flush_all",
+            |lua, this, components: LuaTable| {
+                let mut batch = Vec::new();
+                for pair in components.pairs::<String, LuaValue>() {
+                    let (component_name, component_value) = pair?;
+                    let component_value = crate::serde_components::validate_lua_defined_component(
+                        lua,
+                        &component_name,
+                        component_value,
+                    )?;
+
+                    // Same value handling as set(): non-table values become tuple-struct wrappers
+                    let component_data = match component_value {
+                        LuaValue::Table(table) => table,
+                        _ => {
+                            let wrapper = lua.create_table()?;
+                            wrapper.set("_0", component_value)?;
+                            wrapper
+                        }
+                    };
+
+                    batch.push((component_name, lua.create_registry_value(component_data)?));
+                }
+
+                this.update_queue.queue_batch_update(this.entity, batch);
+                Ok(())
+            },
+        );
+
         // Patch/merge components - only updates specified fields, preserves other existing fields
         // Usage: entity:patch({ PlayerState = { velocity = {x=1,y=0,z=0} } })
         // This merges with existing PlayerState, preserving fields like model_path, owner_client, etc.
@@ -289,8 +568,8 @@ impl LuaUserData for LuaEntitySnapshot {
                     // Get the current component value
                     // FIRST: Check for pending updates in the queue (read-through cache)
                     // This ensures patch() uses the most recent set/patch value, even if not yet applied
-                    let current_value = if let Some(pending_key) = this.update_queue.peek_pending(this.entity, &component_name) {
-                        lua.registry_value::<LuaValue>(&*pending_key)?
+                    let current_value = if let Some(pending_value) = this.update_queue.peek_pending_value(lua, this.entity, &component_name)? {
+                        pending_value
                     } else if let Some(key) = this.lua_components.get(&component_name) {
                         // Lua component - get from registry
                         lua.registry_value::<LuaValue>(&**key)?
@@ -337,6 +616,13 @@ impl LuaUserData for LuaEntitySnapshot {
                         }
                     };
                     
+                    // Lua-defined components are validated against their schema after merging
+                    let merged_data = crate::serde_components::validate_lua_defined_component(
+                        lua,
+                        &component_name,
+                        LuaValue::Table(merged_data),
+                    )?;
+
                     // Create a registry key for the merged data
                     let registry_key = lua.create_registry_value(merged_data)?;
                     
@@ -349,6 +635,112 @@ impl LuaUserData for LuaEntitySnapshot {
             },
         );
 
+        // Despawn this entity and all of its descendants, same as the global despawn()
+        // Usage: entity:despawn()
+        methods.add_method("despawn", |lua, this, ()| {
+            queue_entity_despawn(lua, this.entity, crate::despawn_queue::DespawnMode::Recursive)
+        });
+
+        // Explicit spelling of entity:despawn()
+        // Usage: entity:despawn_recursive()
+        methods.add_method("despawn_recursive", |lua, this, ()| {
+            queue_entity_despawn(lua, this.entity, crate::despawn_queue::DespawnMode::Recursive)
+        });
+
+        // Despawn this entity only - children are detached and kept
+        // Usage: entity:despawn_single()
+        methods.add_method("despawn_single", |lua, this, ()| {
+            queue_entity_despawn(lua, this.entity, crate::despawn_queue::DespawnMode::Single)
+        });
+
+        // Call a Rust method registered for one of this entity's components
+        // (LuaComponentRegistry::register_component_method), with the component as `self`
+        // Usage: enemy:call_method("Health", "take_damage", 10)
+        methods.add_method(
+            "call_method",
+            |lua, this, (component_name, method_name, args): (String, String, LuaMultiValue)| {
+                let active_world = lua
+                    .app_data_ref::<crate::lua_world_context::ActiveLuaWorld>()
+                    .map(|active| active.clone())
+                    .ok_or_else(|| {
+                        LuaError::RuntimeError(
+                            "call_method: only available while a Lua system is running".to_string(),
+                        )
+                    })?;
+
+                active_world
+                    .with_world_mut(|world| {
+                        let registry = world
+                            .get_resource::<crate::component_lua_trait::LuaComponentRegistry>()
+                            .ok_or_else(|| {
+                                LuaError::RuntimeError("LuaComponentRegistry resource not found".to_string())
+                            })?
+                            .clone();
+                        registry.call_method(lua, world, this.entity, &component_name, &method_name, args)
+                    })
+                    .unwrap_or_else(|| {
+                        Err(LuaError::RuntimeError(
+                            "call_method: the Lua system that produced this entity has finished".to_string(),
+                        ))
+                    })
+            },
+        );
+
+        // Component lifecycle observers; the callback receives the component value
+        // Usage: entity:observe_component_add("Health", function(entity, health) ... end)
+        for (method_name, lifecycle) in [
+            ("observe_component_add", crate::lua_observers::ComponentLifecycle::Add),
+            ("observe_component_insert", crate::lua_observers::ComponentLifecycle::Insert),
+            ("observe_component_remove", crate::lua_observers::ComponentLifecycle::Remove),
+        ] {
+            methods.add_method(
+                method_name,
+                move |lua, this, (component_name, callback): (String, LuaFunction)| {
+                    crate::lua_observers::ensure_primary_state(lua, method_name)?;
+                    let registry = lua
+                        .app_data_ref::<crate::lua_observers::LuaObserverRegistry>()
+                        .ok_or_else(|| {
+                            LuaError::RuntimeError("LuaObserverRegistry not available".to_string())
+                        })?;
+                    registry.add_observer(
+                        this.entity,
+                        lifecycle.event_type(&component_name),
+                        crate::lua_observers::LuaObserverCallback::new(
+                            lua.create_registry_value(callback)?,
+                        ),
+                    );
+                    Ok(())
+                },
+            );
+        }
+
+        // Add a Lua observer callback to an existing entity, e.g. to replace one dropped by a hot
+        // reload (see world:changed_observers()). Pointer events only fire for event types the
+        // entity was already observing when it was spawned.
+        // Usage: entity:observe("Pointer<Click>", function(entity, event) ... end)
+        methods.add_method("observe", |lua, this, (event_type, callback): (String, LuaFunction)| {
+            crate::lua_observers::ensure_primary_state(lua, "observe")?;
+            let registry = lua
+                .app_data_ref::<crate::lua_observers::LuaObserverRegistry>()
+                .ok_or_else(|| LuaError::RuntimeError("LuaObserverRegistry not available".to_string()))?;
+            registry.add_observer(
+                this.entity,
+                event_type,
+                crate::lua_observers::LuaObserverCallback::new(lua.create_registry_value(callback)?),
+            );
+            Ok(())
+        });
+
+        // Remove the Lua observer callbacks for one event type
+        // Usage: entity:remove_observer("Pointer<Click>") -> number of callbacks removed
+        methods.add_method("remove_observer", |lua, this, event_type: String| {
+            let Some(registry) = lua.app_data_ref::<crate::lua_observers::LuaObserverRegistry>()
+            else {
+                return Ok(0);
+            };
+            Ok(registry.remove_observer(lua, this.entity, &event_type))
+        });
+
         // Remove components
         // Usage: entity:remove("ComponentName")
         methods.add_method(
@@ -362,6 +754,15 @@ impl LuaUserData for LuaEntitySnapshot {
     }
 }
 
+/// Queue a despawn through the DespawnQueue installed as Lua app data
+fn queue_entity_despawn(lua: &Lua, entity: Entity, mode: crate::despawn_queue::DespawnMode) -> LuaResult<()> {
+    let despawn_queue = lua
+        .app_data_ref::<crate::despawn_queue::DespawnQueue>()
+        .ok_or_else(|| LuaError::RuntimeError("despawn: no DespawnQueue for this Lua state".to_string()))?;
+    despawn_queue.queue_with_mode(entity, mode);
+    Ok(())
+}
+
 /// Convert a reflected value to Lua using Bevy's reflection API directly.
 /// This preserves struct field names that would be lost through serde serialization.
 pub fn reflection_to_lua(lua: &Lua, value: &dyn bevy::reflect::PartialReflect) -> LuaResult<LuaValue> {
@@ -380,6 +781,9 @@ pub fn reflection_to_lua_with_assets(
     
     match value.reflect_ref() {
         ReflectRef::Struct(s) => {
+            if let Some(math) = crate::reflection::math_to_lua(lua, value)? {
+                return Ok(math);
+            }
             let table = lua.create_table()?;
             for i in 0..s.field_len() {
                 if let Some(field) = s.field_at(i) {
@@ -472,14 +876,26 @@ pub fn reflection_to_lua_with_assets(
                 }
             }
             
-            // For normal enums, create a table with the variant name as key
-            let table = lua.create_table()?;
             let variant_name = e.variant_name();
-            
-            if e.field_len() == 0 {
-                // Unit variant
-                table.set(variant_name, true)?;
-            } else if e.field_len() == 1 {
+            if e.variant_type() == bevy::reflect::VariantType::Unit {
+                // Option::None reads as nil, other unit variants as their name (`"Visible"`)
+                if crate::event_reader::is_option_type_path(&type_path) {
+                    return Ok(LuaValue::Nil);
+                }
+                return Ok(LuaValue::String(lua.create_string(variant_name)?));
+            }
+
+            // Option::Some(v) reads as v itself
+            if crate::event_reader::is_option_type_path(&type_path) {
+                return match e.field_at(0) {
+                    Some(inner) => reflection_to_lua_with_assets(lua, inner, asset_registry),
+                    None => Ok(LuaValue::Nil),
+                };
+            }
+
+            // For other enums, create a table with the variant name as key
+            let table = lua.create_table()?;
+            if e.field_len() == 1 {
                 // Newtype variant
                 if let Some(field) = e.field_at(0) {
                     table.set(variant_name, reflection_to_lua_with_assets(lua, field, asset_registry)?)?;
@@ -680,8 +1096,260 @@ fn resolve_component_info(
     info
 }
 
+/// With/Without filters resolved to ComponentIds (Rust) and component names (Lua)
+/// ComponentIds don't change while a Lua system holds `&World`, so a filter can be resolved once
+/// and reused for the rest of the frame (see `world:query_iter`)
+pub struct ArchetypeFilter {
+    required_ids: Vec<bevy::ecs::component::ComponentId>,
+    excluded_ids: Vec<bevy::ecs::component::ComponentId>,
+    lua_with: Vec<String>,
+    lua_without: Vec<String>,
+    lua_custom_comp_id: Option<bevy::ecs::component::ComponentId>,
+}
+
+impl ArchetypeFilter {
+    /// Resolve component names; returns None when no entity can match
+    pub fn resolve(
+        world: &World,
+        with_components: &[String],
+        without_components: &[String],
+        component_registry: &ComponentRegistry,
+        query_cache: Option<&crate::query_cache::LuaQueryCache>,
+    ) -> Option<Self> {
+        let type_registry = component_registry.type_registry().read();
+        let lua_custom_comp_id = world.components().component_id::<LuaCustomComponents>();
+
+        let mut required_ids = Vec::new();
+        let mut lua_with = Vec::new();
+        for name in with_components {
+            match resolve_component_info(name, query_cache, component_registry, &type_registry, world) {
+                crate::query_cache::CachedComponentInfo::Rust(id) => required_ids.push(id),
+                crate::query_cache::CachedComponentInfo::Lua | crate::query_cache::CachedComponentInfo::NotFound => {
+                    lua_with.push(name.clone());
+                }
+            }
+        }
+        if !lua_with.is_empty() {
+            // No entity has Lua components yet
+            required_ids.push(lua_custom_comp_id?);
+        }
+
+        let mut excluded_ids = Vec::new();
+        let mut lua_without = Vec::new();
+        for name in without_components {
+            match resolve_component_info(name, query_cache, component_registry, &type_registry, world) {
+                crate::query_cache::CachedComponentInfo::Rust(id) => excluded_ids.push(id),
+                crate::query_cache::CachedComponentInfo::Lua | crate::query_cache::CachedComponentInfo::NotFound => {
+                    lua_without.push(name.clone());
+                }
+            }
+        }
+
+        Some(Self {
+            required_ids,
+            excluded_ids,
+            lua_with,
+            lua_without,
+            lua_custom_comp_id,
+        })
+    }
+
+    fn archetype_matches(&self, archetype: &bevy::ecs::archetype::Archetype) -> bool {
+        self.required_ids.iter().all(|id| archetype.contains(*id))
+            && !self.excluded_ids.iter().any(|id| archetype.contains(*id))
+    }
+
+    /// Whether archetype membership decides the match for every entity in it
+    fn archetype_decides(&self, archetype: &bevy::ecs::archetype::Archetype) -> bool {
+        let has_lua_components = self.lua_custom_comp_id.is_some_and(|id| archetype.contains(id));
+        self.lua_with.is_empty() && (self.lua_without.is_empty() || !has_lua_components)
+    }
+
+    /// Per-entity Lua component check for archetypes that don't decide on their own
+    fn entity_matches(&self, world: &World, entity: Entity) -> bool {
+        let custom = world.get::<LuaCustomComponents>(entity);
+        let has = |name: &str| custom.is_some_and(|c| c.components.contains_key(name));
+        self.lua_with.iter().all(|name| has(name)) && !self.lua_without.iter().any(|name| has(name))
+    }
+}
+
+/// Count entities matching With/Without filters without building snapshots
+/// Walks archetypes directly (Lua systems only hold `&World`, so a `QueryState` can't be built);
+/// no component data is reflected or cloned. Lua components are checked per entity.
+pub fn count_query(
+    world: &World,
+    with_components: &[String],
+    without_components: &[String],
+    component_registry: &ComponentRegistry,
+    query_cache: Option<&crate::query_cache::LuaQueryCache>,
+) -> usize {
+    let Some(filter) = ArchetypeFilter::resolve(world, with_components, without_components, component_registry, query_cache) else {
+        return 0;
+    };
+
+    let mut count = 0;
+    for archetype in world.archetypes().iter() {
+        if !filter.archetype_matches(archetype) {
+            continue;
+        }
+        if filter.archetype_decides(archetype) {
+            count += archetype.entities().len();
+            continue;
+        }
+        count += archetype
+            .entities()
+            .iter()
+            .filter(|arch_entity| filter.entity_matches(world, arch_entity.id()))
+            .count();
+    }
+    count
+}
+
+/// Collect the entities matching With/Without filters, in archetype order, without building snapshots
+/// Used by paged queries, which only build snapshots for one page at a time
+pub fn collect_query_entities(
+    world: &World,
+    with_components: &[String],
+    without_components: &[String],
+    component_registry: &ComponentRegistry,
+    query_cache: Option<&crate::query_cache::LuaQueryCache>,
+) -> Vec<Entity> {
+    let Some(filter) = ArchetypeFilter::resolve(world, with_components, without_components, component_registry, query_cache) else {
+        return Vec::new();
+    };
+
+    let mut entities = Vec::new();
+    for archetype in world.archetypes().iter() {
+        if !filter.archetype_matches(archetype) {
+            continue;
+        }
+        let decides = filter.archetype_decides(archetype);
+        entities.extend(
+            archetype
+                .entities()
+                .iter()
+                .map(|arch_entity| arch_entity.id())
+                .filter(|entity| decides || filter.entity_matches(world, *entity)),
+        );
+    }
+    entities
+}
+
+/// Next entity matching `filter` at or after `cursor`, which is moved past it
+/// Returns None once every archetype has been visited. Used by `world:query_iter`, which yields one
+/// snapshot per call instead of collecting the matches up front.
+pub fn next_query_entity(
+    world: &World,
+    filter: &ArchetypeFilter,
+    cursor: &mut crate::query_cursor::QueryIterCursor,
+) -> Option<Entity> {
+    for archetype in world.archetypes().iter().skip(cursor.archetype) {
+        if filter.archetype_matches(archetype) {
+            let decides = filter.archetype_decides(archetype);
+            while let Some(arch_entity) = archetype.entities().get(cursor.entity) {
+                cursor.entity += 1;
+                let entity = arch_entity.id();
+                if decides || filter.entity_matches(world, entity) {
+                    return Some(entity);
+                }
+            }
+        }
+        cursor.archetype += 1;
+        cursor.entity = 0;
+    }
+    None
+}
+
+/// Camera used for world/screen conversion: the active camera with the lowest `order`
+/// Found by walking archetypes since Lua systems only hold `&World`
+pub fn primary_camera(world: &World) -> Option<(&Camera, &GlobalTransform)> {
+    let camera_id = world.components().component_id::<Camera>()?;
+    world
+        .archetypes()
+        .iter()
+        .filter(|archetype| archetype.contains(camera_id))
+        .flat_map(|archetype| archetype.entities().iter().map(|arch_entity| arch_entity.id()))
+        .filter_map(|entity| Some((world.get::<Camera>(entity)?, world.get::<GlobalTransform>(entity)?)))
+        .filter(|(camera, _)| camera.is_active)
+        .min_by_key(|(camera, _)| camera.order)
+}
+
+/// Project an entity's `GlobalTransform` translation to viewport coordinates of `camera`
+/// Returns None if the entity has no `GlobalTransform` or is outside the view (e.g. behind the camera)
+pub fn world_to_screen(
+    world: &World,
+    camera: (&Camera, &GlobalTransform),
+    entity: Entity,
+) -> Option<Vec2> {
+    let transform = world.get::<GlobalTransform>(entity)?;
+    let (camera, camera_transform) = camera;
+    camera
+        .world_to_viewport(camera_transform, transform.translation())
+        .ok()
+}
+
+/// Ray from `camera` through a viewport position - the inverse of `world_to_screen`
+/// Returns None if the camera has no viewport yet or the position can't be unprojected
+pub fn screen_to_world_ray(camera: (&Camera, &GlobalTransform), screen_position: Vec2) -> Option<Ray3d> {
+    let (camera, camera_transform) = camera;
+    camera.viewport_to_world(camera_transform, screen_position).ok()
+}
+
+/// Build a snapshot of `entity` holding the named components (Rust components via reflection,
+/// Lua components from `LuaCustomComponents`). Returns None if the entity no longer exists.
+pub fn snapshot_entity(
+    lua: &Lua,
+    world: &World,
+    entity: Entity,
+    component_names: &[String],
+    component_registry: &ComponentRegistry,
+    update_queue: &ComponentUpdateQueue,
+    query_cache: Option<&crate::query_cache::LuaQueryCache>,
+    asset_registry: Option<&crate::asset_loading::AssetRegistry>,
+) -> LuaResult<Option<LuaEntitySnapshot>> {
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        return Ok(None);
+    };
+    let type_registry = component_registry.type_registry().read();
+    let custom = entity_ref.get::<LuaCustomComponents>();
+
+    let mut lua_components = HashMap::new();
+    for name in component_names {
+        match resolve_component_info(name, query_cache, component_registry, &type_registry, world) {
+            crate::query_cache::CachedComponentInfo::Rust(id) => {
+                let reflected = world
+                    .components()
+                    .get_info(id)
+                    .and_then(|info| info.type_id())
+                    .and_then(|type_id| type_registry.get(type_id))
+                    .and_then(|registration| registration.data::<ReflectComponent>())
+                    .and_then(|reflect_component| reflect_component.reflect(entity_ref));
+                if let Some(component) = reflected {
+                    let lua_value = reflection_to_lua_with_assets(lua, component, asset_registry)?;
+                    lua_components.insert(name.clone(), Arc::new(lua.create_registry_value(lua_value)?));
+                }
+            }
+            crate::query_cache::CachedComponentInfo::Lua | crate::query_cache::CachedComponentInfo::NotFound => {
+                if let Some(key) = custom.and_then(|c| c.components.get(name)) {
+                    lua_components.insert(name.clone(), key.clone());
+                }
+            }
+        }
+    }
+
+    Ok(Some(LuaEntitySnapshot {
+        entity,
+        component_data: HashMap::new(),
+        lua_components,
+        changed_components: HashSet::new(),
+        added_components: HashSet::new(),
+        update_queue: update_queue.clone(),
+    }))
+}
+
 /// Execute a query and collect entity snapshots
-/// Uses per-frame caching with full component data for performance
+/// Uses per-frame caching with full component data for performance.
+/// `where_field` filters are evaluated on the snapshot's component values once they are built.
 pub fn execute_query(
     lua: &Lua,
     world: &World,
@@ -693,6 +1361,76 @@ pub fn execute_query(
     query_cache: Option<&crate::query_cache::LuaQueryCache>,
     current_frame: u64,
     asset_registry: Option<&crate::asset_loading::AssetRegistry>,
+) -> LuaResult<Vec<LuaEntitySnapshot>> {
+    // filter_changed/filter_added map to Bevy's Changed<T>/Added<T>, so the name must be a registered Rust component
+    for (method, name) in &query_builder.reflected_filters {
+        if component_registry.get_non_reflected_type_id(name).is_none()
+            && component_registry.get_type_path(name).is_none()
+        {
+            return Err(LuaError::RuntimeError(format!(
+                "{}: unknown component type '{}'",
+                method, name
+            )));
+        }
+    }
+
+    let results = collect_query_snapshots(
+        lua,
+        world,
+        query_builder,
+        component_registry,
+        update_queue,
+        last_run,
+        this_run,
+        query_cache,
+        current_frame,
+        asset_registry,
+    )?;
+    if query_builder.field_filters.is_empty() {
+        return Ok(results);
+    }
+
+    let mut filtered = Vec::with_capacity(results.len());
+    'snapshots: for snapshot in results {
+        for filter in &query_builder.field_filters {
+            let value = match snapshot.lua_components.get(&filter.component) {
+                Some(key) => lua.registry_value::<LuaValue>(key)?,
+                // Filtered component wasn't requested with with(); reflect it on demand
+                None => snapshot_entity(
+                    lua,
+                    world,
+                    snapshot.entity,
+                    std::slice::from_ref(&filter.component),
+                    component_registry,
+                    update_queue,
+                    query_cache,
+                    asset_registry,
+                )?
+                .and_then(|extra| extra.lua_components.get(&filter.component).cloned())
+                .map(|key| lua.registry_value::<LuaValue>(&key))
+                .transpose()?
+                .unwrap_or(LuaValue::Nil),
+            };
+            if !filter.matches(&value) {
+                continue 'snapshots;
+            }
+        }
+        filtered.push(snapshot);
+    }
+    Ok(filtered)
+}
+
+fn collect_query_snapshots(
+    lua: &Lua,
+    world: &World,
+    query_builder: &LuaQueryBuilder,
+    component_registry: &ComponentRegistry,
+    update_queue: &ComponentUpdateQueue,
+    last_run: u32,
+    this_run: u32,
+    query_cache: Option<&crate::query_cache::LuaQueryCache>,
+    current_frame: u64,
+    asset_registry: Option<&crate::asset_loading::AssetRegistry>,
 ) -> LuaResult<Vec<LuaEntitySnapshot>> {
     // Special handling for 'removed' queries
     // These return entity_bits for entities that had components removed this frame
@@ -747,6 +1485,11 @@ pub fn execute_query(
                 let mut results = Vec::with_capacity(cached_results.len());
                 for cached in cached_results {
                     let entity = Entity::from_bits(cached.entity_bits);
+                    if let Some(filter) = &query_builder.within_radius {
+                        if !filter.contains(world, entity) {
+                            continue;
+                        }
+                    }
                     // Verify entity still exists (could have been despawned)
                     if let Ok(entity_ref) = world.get_entity(entity) {
                         let mut lua_components = cached.component_keys.clone();
@@ -923,6 +1666,13 @@ pub fn execute_query(
                                     component_keys: lua_components.clone(),
                                 });
 
+                                // The cache keeps every match; the radius only narrows this result
+                                if let Some(filter) = &query_builder.within_radius {
+                                    if !filter.contains(world, entity) {
+                                        continue;
+                                    }
+                                }
+
                                 results.push(LuaEntitySnapshot {
                                     entity,
                                     component_data: HashMap::new(),
@@ -1272,6 +2022,12 @@ pub fn execute_query(
         for arch_entity in archetype.entities() {
             entities_checked += 1;
             let entity = arch_entity.id();
+            // Spatial check first so distant entities never get serialized
+            if let Some(filter) = &query_builder.within_radius {
+                if !filter.contains(world, entity) {
+                    continue;
+                }
+            }
             let entity_ref = world.get_entity(entity).expect("Entity in archetype must exist");
             
             let mut entity_changed = HashSet::new();
@@ -1598,10 +2354,11 @@ pub fn execute_query(
     }
     
     // Store in cache if no change detection and no complex filters
-    // (Without and AnyOf filters make caching less effective)
+    // (Without and AnyOf filters make caching less effective; a radius leaves the entries incomplete)
     let should_cache = !query_builder.has_change_detection()
         && query_builder.without_components.is_empty()
-        && query_builder.any_of_components.is_empty();
+        && query_builder.any_of_components.is_empty()
+        && query_builder.within_radius.is_none();
 
     if should_cache {
         if let Some(cache) = query_cache {
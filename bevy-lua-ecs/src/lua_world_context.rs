@@ -18,6 +18,152 @@ use crate::serde_components::SerdeComponentRegistry;
 use crate::ComponentRegistry;
 use crate::lua_systems::LuaSystemRegistry;
 
+/// Look up a resource's type registration by name for get_resource/set_resource
+/// Accepts short paths, full paths, and short generic names like "ButtonInput<KeyCode>"
+fn resolve_resource_registration<'a>(
+    registry: &'a bevy::reflect::TypeRegistry,
+    resource_type_name: &str,
+    method: &str,
+) -> LuaResult<&'a bevy::reflect::TypeRegistration> {
+    // Try direct lookup first (exact short path or full path match)
+    let type_registration = registry
+        .get_with_short_type_path(resource_type_name)
+        .or_else(|| registry.get_with_type_path(resource_type_name))
+        // Fallback: iterate and find by short name match (handles generics)
+        .or_else(|| {
+            // For generic types like "ButtonInput<KeyCode>", the short_type_path might be
+            // "ButtonInput<KeyCode>" but the full path is "bevy_input::ButtonInput<bevy_input::keyboard::KeyCode>"
+            // We need to match the short_type_path which strips crate prefixes from generics
+            registry.iter().find(|reg| {
+                let short = reg.type_info().type_path_table().short_path();
+                short == resource_type_name
+            })
+        });
+
+    let Some(registration) = type_registration else {
+        // Log available similar types for debugging
+        debug!("[GET_RESOURCE] Type '{}' not found. Looking for similar types...", resource_type_name);
+        for reg in registry.iter() {
+            if reg.data::<bevy::ecs::reflect::ReflectResource>().is_some() {
+                let short = reg.type_info().type_path_table().short_path();
+                let full = reg.type_info().type_path();
+                // Log types that might be similar (contain part of the requested name)
+                if let Some(base_name) = resource_type_name.split(['<', '>']).next() {
+                    if short.contains(base_name) || full.contains(base_name) {
+                        debug!("[GET_RESOURCE]   Found similar: short='{}', full='{}'", short, full);
+                    }
+                }
+            }
+        }
+        return Err(LuaError::RuntimeError(format!(
+            "{}: type '{}' is not registered in the TypeRegistry",
+            method, resource_type_name
+        )));
+    };
+
+    Ok(registration)
+}
+
+/// Look up a resource's ReflectResource by type name
+pub(crate) fn resolve_reflect_resource<'a>(
+    registry: &'a bevy::reflect::TypeRegistry,
+    resource_type_name: &str,
+    method: &str,
+) -> LuaResult<&'a bevy::ecs::reflect::ReflectResource> {
+    resolve_resource_registration(registry, resource_type_name, method)?
+        .data::<bevy::ecs::reflect::ReflectResource>()
+        .ok_or_else(|| {
+            LuaError::RuntimeError(format!(
+                "{}: type '{}' is registered but is not a reflected Resource (missing #[reflect(Resource)])",
+                method, resource_type_name
+            ))
+        })
+}
+
+/// Resolve a short ("Image") or full asset type name to (type path, short name)
+fn resolve_asset_type(world: &World, type_name: &str, method: &str) -> LuaResult<(String, String)> {
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let registration = type_registry
+        .get_with_short_type_path(type_name)
+        .or_else(|| type_registry.get_with_type_path(type_name))
+        .filter(|registration| registration.data::<bevy::asset::ReflectAsset>().is_some())
+        .ok_or_else(|| {
+            LuaError::RuntimeError(format!(
+                "{}: '{}' is not a registered asset type",
+                method, type_name
+            ))
+        })?;
+    let table = registration.type_info().type_path_table();
+    Ok((table.path().to_string(), table.short_path().to_string()))
+}
+
+/// Resolve a handle passed from Lua (`{ id = ..., type = ... }` from create_asset or
+/// load_asset_from_bytes, `{ asset_id = ... }` from load_asset, or a bare id) to the handle
+/// registered in AssetRegistry
+/// Ok(None) means the id is known but no typed handle exists yet (a path or bytes still loading)
+fn resolve_asset_handle(
+    asset_registry: &crate::asset_loading::AssetRegistry,
+    handle: &LuaValue,
+    method: &str,
+) -> LuaResult<Option<UntypedHandle>> {
+    if let LuaValue::Table(table) = handle {
+        // load_asset_from_bytes handles get their id once the load finishes
+        if let Some(error) = table.get::<Option<String>>("error")? {
+            return Err(LuaError::RuntimeError(format!("{}: {}", method, error)));
+        }
+        if table.get::<Option<bool>>("loading")?.unwrap_or(false) {
+            return Ok(None);
+        }
+        let type_name = table.get::<Option<String>>("type")?;
+        let id = table.get::<Option<i64>>("id")?;
+        if let Some(handle) = type_name
+            .zip(id)
+            .and_then(|(type_name, id)| asset_registry.get_created_handle(&type_name, id as u64))
+        {
+            return Ok(Some(handle));
+        }
+    }
+
+    let id = match handle {
+        LuaValue::Integer(id) => Some(*id),
+        LuaValue::Table(table) => table
+            .get::<Option<i64>>("id")?
+            .or(table.get::<Option<i64>>("asset_id")?),
+        _ => None,
+    }
+    .and_then(|id| u32::try_from(id).ok())
+    .ok_or_else(|| {
+        LuaError::RuntimeError(format!(
+            "{}: expected a handle table like {{ id = 1, type = \"Image\" }}",
+            method
+        ))
+    })?;
+
+    if let Some(handle) = asset_registry.get_untyped_handle(id) {
+        return Ok(Some(handle));
+    }
+    if asset_registry.get_path(id).is_some() {
+        return Ok(None);
+    }
+    Err(LuaError::RuntimeError(format!("{}: unknown asset id {}", method, id)))
+}
+
+/// Fetch the TagQueue resource used by the tagging methods
+fn tag_queue(world: &World) -> LuaResult<crate::entity_tags::TagQueue> {
+    world
+        .get_resource::<crate::entity_tags::TagQueue>()
+        .cloned()
+        .ok_or_else(|| LuaError::RuntimeError("TagQueue resource not found".to_string()))
+}
+
+/// Convert an array of event tables from Lua into JSON values for PendingLuaEvents
+fn lua_event_batch(lua: &Lua, events: &LuaTable) -> LuaResult<Vec<serde_json::Value>> {
+    events
+        .sequence_values::<LuaTable>()
+        .map(|event| lua.from_value(LuaValue::Table(event?)))
+        .collect()
+}
+
 /// Parse a DSL table into a LuaQueryBuilder
 /// DSL format:
 /// ```lua
@@ -109,6 +255,35 @@ fn parse_query_dsl(table: &LuaTable, builder: &mut LuaQueryBuilder) -> LuaResult
     Ok(())
 }
 
+/// World made available (as Lua app data) to entity snapshot methods while a Lua system runs
+/// The pointer is only dereferenced while `alive` upgrades, i.e. while the creating
+/// `LuaWorldContext` (and its World borrow) still exists
+#[derive(Clone)]
+pub struct ActiveLuaWorld {
+    world: usize,
+    alive: std::sync::Weak<()>,
+}
+
+impl ActiveLuaWorld {
+    /// Run `f` with the world, or return None once the system has returned
+    pub(crate) fn with_world<R>(&self, f: impl FnOnce(&World) -> R) -> Option<R> {
+        let _guard = self.alive.upgrade()?;
+        // SAFETY: `_guard` proves the LuaWorldContext (and its World borrow) is alive
+        let world = unsafe { &*(self.world as *const World) };
+        Some(f(world))
+    }
+
+    /// Run `f` with mutable access to the world, or return None once the system has returned
+    pub(crate) fn with_world_mut<R>(&self, f: impl FnOnce(&mut World) -> R) -> Option<R> {
+        let _guard = self.alive.upgrade()?;
+        // SAFETY: `_guard` proves the LuaWorldContext is alive; component methods need mutable
+        // access the same way call_component_method does
+        #[allow(invalid_reference_casting)]
+        let world = unsafe { &mut *(self.world as *mut World) };
+        Some(f(world))
+    }
+}
+
 /// World userdata context - wraps references that are only valid during system execution
 /// SAFETY: This MUST only be used within a lua.scope() to ensure the world reference is valid
 pub struct LuaWorldContext<'w> {
@@ -130,6 +305,8 @@ pub struct LuaWorldContext<'w> {
     pub current_frame: u64,
     /// Optional AssetRegistry for Handle→path serialization in queries
     pub asset_registry: Option<crate::asset_loading::AssetRegistry>,
+    /// Liveness token for handles that borrow `world` (resource builder chains)
+    alive: std::sync::Arc<()>,
 }
 
 impl<'w> LuaWorldContext<'w> {
@@ -167,6 +344,7 @@ impl<'w> LuaWorldContext<'w> {
             query_cache,
             current_frame,
             asset_registry,
+            alive: std::sync::Arc::new(()),
         }
     }
 
@@ -174,6 +352,14 @@ impl<'w> LuaWorldContext<'w> {
     fn world(&self) -> &World {
         self.world
     }
+
+    /// Handle to this context's world for entity snapshot methods (see `ActiveLuaWorld`)
+    pub fn active_world(&self) -> ActiveLuaWorld {
+        ActiveLuaWorld {
+            world: self.world as *const World as usize,
+            alive: std::sync::Arc::downgrade(&self.alive),
+        }
+    }
 }
 
 impl LuaUserData for LuaWorldContext<'_> {
@@ -185,15 +371,220 @@ impl LuaUserData for LuaWorldContext<'_> {
             Ok(delta)
         });
 
+        // query_builder() - create an empty chainable query builder
+        // Usage: world:query(world:query_builder():with("Player"):filter_changed("Transform"))
+        methods.add_method("query_builder", |_lua, _this, ()| Ok(LuaQueryBuilder::new()));
+
+        // query_count(with, without) - count matching entities without building snapshots
+        // Usage: local enemies = world:query_count({"Enemy", "Health"}, {"Dead"})
+        methods.add_method(
+            "query_count",
+            |_lua, this, (with_table, without_table): (LuaTable, Option<LuaTable>)| {
+                let with_components = with_table
+                    .sequence_values::<String>()
+                    .collect::<LuaResult<Vec<_>>>()?;
+                let without_components = match without_table {
+                    Some(table) => table.sequence_values::<String>().collect::<LuaResult<Vec<_>>>()?,
+                    None => Vec::new(),
+                };
+
+                Ok(crate::lua_world_api::count_query(
+                    this.world(),
+                    &with_components,
+                    &without_components,
+                    this.component_registry,
+                    this.query_cache.as_ref(),
+                ))
+            },
+        );
+
+        // query_page(with, page, page_size, without) - one page of a large query (pages start at 1)
+        // Only the requested page is turned into snapshots; returns { entities, total, has_more }
+        // Usage: local result = world:query_page({"Enemy"}, 2, 100)
+        methods.add_method(
+            "query_page",
+            |lua, this, (with_table, page, page_size, without_table): (LuaTable, usize, usize, Option<LuaTable>)| {
+                if page == 0 || page_size == 0 {
+                    return Err(LuaError::RuntimeError(
+                        "query_page: page and page_size must be at least 1".to_string(),
+                    ));
+                }
+                let with_components = with_table
+                    .sequence_values::<String>()
+                    .collect::<LuaResult<Vec<_>>>()?;
+                let without_components = match without_table {
+                    Some(table) => table.sequence_values::<String>().collect::<LuaResult<Vec<_>>>()?,
+                    None => Vec::new(),
+                };
+                let cursor = this
+                    .world()
+                    .get_resource::<crate::query_cursor::QueryCursor>()
+                    .ok_or_else(|| LuaError::RuntimeError("QueryCursor resource not found".to_string()))?;
+
+                let entities = cursor.matching_entities(
+                    this.world(),
+                    &with_components,
+                    &without_components,
+                    this.component_registry,
+                    this.query_cache.as_ref(),
+                    page == 1,
+                );
+                let (range, has_more) = crate::query_cursor::page_range(entities.len(), page, page_size);
+                let page_entities = crate::query_cursor::page_snapshots(
+                    lua,
+                    this.world(),
+                    &entities,
+                    range,
+                    &with_components,
+                    this.component_registry,
+                    &this.update_queue,
+                    this.query_cache.as_ref(),
+                    this.asset_registry.as_ref(),
+                )?;
+
+                let result = lua.create_table()?;
+                result.set("entities", page_entities)?;
+                result.set("total", entities.len())?;
+                result.set("has_more", has_more)?;
+                Ok(result)
+            },
+        );
+
+        // query_all_async(with, callback, page_size) - walk a large query one page per frame
+        // callback(entities, { page, total, has_more }) runs from the next frame on; return false to stop
+        // Usage: world:query_all_async({"Enemy"}, function(enemies, page) ... end, 100)
+        methods.add_method(
+            "query_all_async",
+            |lua, this, (with_table, callback, page_size): (LuaTable, LuaFunction, Option<usize>)| {
+                let page_size = page_size.unwrap_or(crate::query_cursor::DEFAULT_ASYNC_PAGE_SIZE);
+                if page_size == 0 {
+                    return Err(LuaError::RuntimeError(
+                        "query_all_async: page_size must be at least 1".to_string(),
+                    ));
+                }
+                let with_components = with_table
+                    .sequence_values::<String>()
+                    .collect::<LuaResult<Vec<_>>>()?;
+                let cursor = this
+                    .world()
+                    .get_resource::<crate::query_cursor::QueryCursor>()
+                    .ok_or_else(|| LuaError::RuntimeError("QueryCursor resource not found".to_string()))?;
+
+                let entities = crate::lua_world_api::collect_query_entities(
+                    this.world(),
+                    &with_components,
+                    &[],
+                    this.component_registry,
+                    this.query_cache.as_ref(),
+                );
+                let instance_id: u64 = lua.globals().get("__INSTANCE_ID__").unwrap_or(0);
+                let state_id: usize = lua.globals().get("__LUA_STATE_ID__").unwrap_or(0);
+                cursor.queue_async(
+                    with_components,
+                    entities,
+                    page_size,
+                    Arc::new(lua.create_registry_value(callback)?),
+                    state_id,
+                    instance_id,
+                );
+                Ok(())
+            },
+        );
+
+        // query_iter(with_components) - iterator yielding one entity snapshot per call, so large
+        // queries don't build a result table. Valid for the current frame (and system call) only;
+        // afterwards it returns nil.
+        // Usage: for entity in world:query_iter({"Transform"}) do ... end
+        methods.add_method("query_iter", |lua, this, with_table: LuaTable| {
+            let with_components = with_table
+                .sequence_values::<String>()
+                .collect::<LuaResult<Vec<_>>>()?;
+            let iter_state = this
+                .world()
+                .get_resource::<crate::query_cursor::QueryIterState>()
+                .ok_or_else(|| LuaError::RuntimeError("QueryIterState resource not found".to_string()))?
+                .clone();
+
+            let id = iter_state.start(this.current_frame);
+            // Component names are resolved once here, not on every call of the iterator
+            // None when no entity can match (e.g. Lua components queried before any exist)
+            let filter = crate::lua_world_api::ArchetypeFilter::resolve(
+                this.world(),
+                &with_components,
+                &[],
+                this.component_registry,
+                this.query_cache.as_ref(),
+            );
+            let active_world = this.active_world();
+            let update_queue = this.update_queue.clone();
+            let query_cache = this.query_cache.clone();
+            let asset_registry = this.asset_registry.clone();
+            lua.create_function(move |lua, ()| {
+                let next = active_world.with_world(|world| -> LuaResult<Option<LuaEntitySnapshot>> {
+                    let Some(component_registry) = world.get_resource::<ComponentRegistry>() else {
+                        return Ok(None);
+                    };
+                    let frame = world
+                        .get_resource::<bevy::diagnostic::FrameCount>()
+                        .map(|frame_count| frame_count.0 as u64)
+                        .unwrap_or(0);
+                    loop {
+                        let Some(entity) = filter
+                            .as_ref()
+                            .and_then(|filter| {
+                                iter_state.advance(id, frame, |cursor| {
+                                    crate::lua_world_api::next_query_entity(world, filter, cursor)
+                                })
+                            })
+                            .flatten()
+                        else {
+                            iter_state.finish(id);
+                            return Ok(None);
+                        };
+                        // None only if the entity vanished, which can't happen mid-frame; skip it
+                        if let Some(snapshot) = crate::lua_world_api::snapshot_entity(
+                            lua,
+                            world,
+                            entity,
+                            &with_components,
+                            component_registry,
+                            &update_queue,
+                            query_cache.as_ref(),
+                            asset_registry.as_ref(),
+                        )? {
+                            return Ok(Some(snapshot));
+                        }
+                    }
+                });
+                // The system that created the iterator has returned
+                next.unwrap_or(Ok(None))
+            })
+        });
+
         // query(with_components, changed_components) - executes immediately and returns results
-        // Supports two syntax forms:
-        // 1. Legacy: world:query({"A", "B"}, {"A"}) - array of components, optional changed array
-        // 2. DSL:    world:query({ with = {"A", "B"}, changed = {"A"}, without = {"C"}, ... })
-        methods.add_method("query", |lua, this, (first_arg, changed_comps): (LuaTable, Option<LuaTable>)| {
+        // Supports three syntax forms:
+        // 1. Legacy:  world:query({"A", "B"}, {"A"}) - array of components, optional changed array
+        // 2. DSL:     world:query({ with = {"A", "B"}, changed = {"A"}, without = {"C"}, ... })
+        // 3. Builder: world:query(world:query_builder():with("A"):filter_changed("A"))
+        methods.add_method("query", |lua, this, (query_arg, changed_comps): (LuaValue, Option<LuaTable>)| {
             let t0 = std::time::Instant::now();
 
             let mut builder = LuaQueryBuilder::new();
 
+            let first_arg = match query_arg {
+                LuaValue::Table(table) => table,
+                LuaValue::UserData(ud) => {
+                    builder = ud.borrow::<LuaQueryBuilder>()?.clone();
+                    lua.create_table()?
+                }
+                other => {
+                    return Err(LuaError::RuntimeError(format!(
+                        "query: expected a component list, DSL table or query builder, got {}",
+                        other.type_name()
+                    )));
+                }
+            };
+
             // Detect DSL vs legacy syntax by checking for string keys with Table values
             // Must use matches! to distinguish between Ok(Nil) and Ok(Table(_))
             let is_dsl = matches!(first_arg.get::<LuaValue>("with"), Ok(LuaValue::Table(_)))
@@ -380,6 +771,855 @@ impl LuaUserData for LuaWorldContext<'_> {
             Ok(LuaValue::UserData(lua.create_userdata(snapshot)?))
         });
 
+        // define_component(name, schema) - define a pure-Lua component type with schema validation
+        // Data is stored as JSON-compatible Lua tables; missing required fields raise an error at set-time
+        methods.add_method("define_component", |_lua, this, (name, schema): (String, LuaTable)| {
+            let schema = crate::serde_components::LuaComponentSchema::from_lua_table(&schema)?;
+            debug!("[DEFINE_COMPONENT] Defined Lua component '{}' with {} fields", name, schema.fields.len());
+            this.serde_registry.define_lua_component(name, schema);
+            Ok(())
+        });
+
+        // encode_component(name, data) - bincode bytes (a Lua string) of a register_binary component
+        // Usage: local bytes = world:encode_component("PlayerState", { x = 1, y = 2 })
+        methods.add_method("encode_component", |lua, this, (name, data): (String, LuaValue)| {
+            let bytes = this.serde_registry.encode_binary(&name, &data).ok_or_else(|| {
+                LuaError::RuntimeError(format!(
+                    "encode_component: '{}' is not registered with register_binary",
+                    name
+                ))
+            })??;
+            lua.create_string(bytes)
+        });
+
+        // set_component_binary(entity_id, name, bytes) - queue a component decoded from encode_component bytes
+        methods.add_method(
+            "set_component_binary",
+            |lua, this, (entity_id, name, bytes): (u64, String, LuaString)| {
+                if !this.serde_registry.is_binary(&name) {
+                    return Err(LuaError::RuntimeError(format!(
+                        "set_component_binary: '{}' is not registered with register_binary",
+                        name
+                    )));
+                }
+                let entity = this.spawn_queue.resolve_entity(entity_id);
+                let key = lua.create_registry_value(bytes)?;
+                this.update_queue.queue_binary_update(entity, name, key);
+                Ok(())
+            },
+        );
+
+        // start_coroutine(fn) - run fn as a coroutine resumed by the scheduler
+        // Yield with coroutine.yield("next_frame" | "frames", n | "after_seconds", s | "until", fn)
+        methods.add_method("start_coroutine", |lua, this, func: LuaFunction| {
+            let scheduler = this
+                .world()
+                .get_resource::<crate::lua_coroutines::LuaCoroutineScheduler>()
+                .ok_or_else(|| LuaError::RuntimeError("LuaCoroutineScheduler resource not found".to_string()))?
+                .clone();
+            scheduler.start(lua, func)
+        });
+
+        // cancel_coroutine(handle) - stop a coroutine started with start_coroutine
+        methods.add_method("cancel_coroutine", |_lua, this, handle: u64| {
+            let scheduler = this
+                .world()
+                .get_resource::<crate::lua_coroutines::LuaCoroutineScheduler>()
+                .ok_or_else(|| LuaError::RuntimeError("LuaCoroutineScheduler resource not found".to_string()))?
+                .clone();
+            Ok(scheduler.cancel(handle))
+        });
+
+        // create_timer(duration_secs, callback, repeat) - call callback(handle) after duration_secs
+        // of game time; repeating timers fire every duration_secs until cancelled
+        methods.add_method(
+            "create_timer",
+            |lua, this, (duration, callback, repeat): (f32, LuaFunction, Option<bool>)| {
+                let timers = this
+                    .world()
+                    .get_resource::<crate::lua_timers::TimerRegistry>()
+                    .ok_or_else(|| LuaError::RuntimeError("TimerRegistry resource not found".to_string()))?
+                    .clone();
+                timers.create(lua, duration, callback, repeat.unwrap_or(false))
+            },
+        );
+
+        // cancel_timer(handle) - stop a timer started with create_timer
+        methods.add_method("cancel_timer", |_lua, this, handle: u64| {
+            let timers = this
+                .world()
+                .get_resource::<crate::lua_timers::TimerRegistry>()
+                .ok_or_else(|| LuaError::RuntimeError("TimerRegistry resource not found".to_string()))?
+                .clone();
+            Ok(timers.cancel(handle))
+        });
+
+        // physics_debug_draw(enabled) - toggle Rapier collider debug rendering (physics feature)
+        methods.add_method("physics_debug_draw", |_lua, this, enabled: bool| {
+            crate::physics_debug::queue_from_lua(
+                this.world(),
+                "physics_debug_draw",
+                crate::physics_debug::PhysicsDebugOp::SetEnabled(enabled),
+            )
+        });
+
+        // physics_debug_color(entity_id, r, g, b) - override an entity's collider debug color
+        methods.add_method(
+            "physics_debug_color",
+            |_lua, this, (entity_id, r, g, b): (u64, f32, f32, f32)| {
+                crate::physics_debug::queue_from_lua(
+                    this.world(),
+                    "physics_debug_color",
+                    crate::physics_debug::PhysicsDebugOp::SetColor(entity_id, Color::srgb(r, g, b)),
+                )
+            },
+        );
+
+        // subscribe_resource(resource_type_name, callback) - call callback(value) in PostUpdate
+        // of every frame the resource changed; returns a handle for unsubscribe_resource
+        methods.add_method(
+            "subscribe_resource",
+            |lua, this, (resource_type_name, callback): (String, LuaFunction)| {
+                let watchers = this
+                    .world()
+                    .get_resource::<crate::resource_watchers::ResourceWatcherRegistry>()
+                    .ok_or_else(|| LuaError::RuntimeError("ResourceWatcherRegistry resource not found".to_string()))?
+                    .clone();
+                let type_registry = this.component_registry.type_registry();
+                let registry = type_registry.read();
+                // Only reflected resources can be handed to the callback
+                resolve_reflect_resource(&registry, &resource_type_name, "subscribe_resource")?;
+                let registration = resolve_resource_registration(&registry, &resource_type_name, "subscribe_resource")?;
+                watchers.subscribe(
+                    lua,
+                    registration.type_id(),
+                    registration.type_info().type_path(),
+                    callback,
+                    this.world().read_change_tick(),
+                )
+            },
+        );
+
+        // unsubscribe_resource(resource_type_name, handle) - remove a subscribe_resource callback
+        methods.add_method(
+            "unsubscribe_resource",
+            |_lua, this, (resource_type_name, handle): (String, u64)| {
+                let watchers = this
+                    .world()
+                    .get_resource::<crate::resource_watchers::ResourceWatcherRegistry>()
+                    .ok_or_else(|| LuaError::RuntimeError("ResourceWatcherRegistry resource not found".to_string()))?
+                    .clone();
+                let type_registry = this.component_registry.type_registry();
+                let registry = type_registry.read();
+                let registration = resolve_resource_registration(&registry, &resource_type_name, "unsubscribe_resource")?;
+                Ok(watchers.unsubscribe(registration.type_id(), handle))
+            },
+        );
+
+        // insert_resource_priority(name, data, priority) - queue a resource insert
+        // "high" inserts in PreUpdate ahead of normal inserts; "normal" behaves like insert_resource
+        methods.add_method(
+            "insert_resource_priority",
+            |lua, this, (resource_name, resource_data, priority): (String, LuaValue, Option<String>)| {
+                let priority = match priority.as_deref() {
+                    None => crate::resource_queue::ResourcePriority::Normal,
+                    Some(name) => crate::resource_queue::ResourcePriority::from_name(name)
+                        .ok_or_else(|| {
+                            LuaError::RuntimeError(format!(
+                                "insert_resource_priority: unknown priority '{}' (expected \"high\" or \"normal\")",
+                                name
+                            ))
+                        })?,
+                };
+                let resource_queue = this
+                    .world()
+                    .get_resource::<crate::resource_queue::ResourceQueue>()
+                    .ok_or_else(|| LuaError::RuntimeError("ResourceQueue resource not found".to_string()))?
+                    .clone();
+                let instance_id: Option<u64> = lua.globals().get("__INSTANCE_ID__").ok();
+                let registry_key = lua.create_registry_value(resource_data)?;
+                resource_queue.queue_insert_with_priority(resource_name, registry_key, instance_id, priority);
+                Ok(())
+            },
+        );
+
+        // construct_resource(name, params) - queue a resource built by a factory registered in
+        // ResourceConstructorRegistry (e.g. by a third-party plugin)
+        // Usage: world:construct_resource("MyPluginConfig") or world:construct_resource("MyPluginConfig", { speed = 2 })
+        methods.add_method(
+            "construct_resource",
+            |lua, this, (resource_name, params): (String, Option<LuaTable>)| {
+                let constructor_registry = this
+                    .world()
+                    .get_resource::<crate::resource_constructors::ResourceConstructorRegistry>()
+                    .ok_or_else(|| {
+                        LuaError::RuntimeError("ResourceConstructorRegistry resource not found".to_string())
+                    })?;
+                if !constructor_registry.has_constructor(&resource_name) {
+                    return Err(LuaError::RuntimeError(format!(
+                        "construct_resource: no factory registered for '{}'",
+                        resource_name
+                    )));
+                }
+                let resource_queue = this
+                    .world()
+                    .get_resource::<crate::resource_queue::ResourceQueue>()
+                    .ok_or_else(|| LuaError::RuntimeError("ResourceQueue resource not found".to_string()))?
+                    .clone();
+                let instance_id: Option<u64> = lua.globals().get("__INSTANCE_ID__").ok();
+                let data = params.map(LuaValue::Table).unwrap_or(LuaValue::Nil);
+                resource_queue.queue_insert(resource_name, lua.create_registry_value(data)?, instance_id);
+                Ok(())
+            },
+        );
+
+        // mutate_resource(type_name, method_name, args) - call a registered mutator on a live resource
+        // Usage: world:mutate_resource("RapierConfiguration", "set_gravity", { x = 0, y = -9.8 })
+        methods.add_method(
+            "mutate_resource",
+            |lua, this, (type_name, method_name, args): (String, String, LuaTable)| {
+                let builder_registry = this
+                    .world()
+                    .get_resource::<crate::resource_builder::ResourceBuilderRegistry>()
+                    .ok_or_else(|| LuaError::RuntimeError("ResourceBuilderRegistry resource not found".to_string()))?;
+                if !builder_registry.has_mutator(&type_name, &method_name) {
+                    return Err(LuaError::RuntimeError(format!(
+                        "mutate_resource: no mutator '{}' registered for resource '{}'",
+                        method_name, type_name
+                    )));
+                }
+                let resource_queue = this
+                    .world()
+                    .get_resource::<crate::resource_queue::ResourceQueue>()
+                    .ok_or_else(|| LuaError::RuntimeError("ResourceQueue resource not found".to_string()))?
+                    .clone();
+                let registry_key = lua.create_registry_value(args)?;
+                resource_queue.queue_mutate(type_name, method_name, registry_key);
+                Ok(())
+            },
+        );
+
+        // world_to_screen(entity_id) - project an entity's GlobalTransform through the primary camera
+        // Returns { x, y } in viewport coordinates, or nil if behind the camera / no camera exists
+        methods.add_method("world_to_screen", |lua, this, entity_bits: i64| {
+            let entity = this.spawn_queue.resolve_entity(entity_bits as u64);
+            let Some(position) = crate::lua_world_api::primary_camera(this.world())
+                .and_then(|camera| crate::lua_world_api::world_to_screen(this.world(), camera, entity))
+            else {
+                return Ok(LuaValue::Nil);
+            };
+            let table = lua.create_table()?;
+            table.set("x", position.x)?;
+            table.set("y", position.y)?;
+            Ok(LuaValue::Table(table))
+        });
+
+        // world_to_screen_all(component_name) - project every entity with a component
+        // Returns an array of { entity_id, x, y }; entities behind the camera are skipped
+        methods.add_method("world_to_screen_all", |lua, this, component_name: String| {
+            let results = lua.create_table()?;
+            let Some(camera) = crate::lua_world_api::primary_camera(this.world()) else {
+                return Ok(results);
+            };
+            let entities = crate::lua_world_api::collect_query_entities(
+                this.world(),
+                &[component_name],
+                &[],
+                this.component_registry,
+                this.query_cache.as_ref(),
+            );
+            for entity in entities {
+                if let Some(position) = crate::lua_world_api::world_to_screen(this.world(), camera, entity) {
+                    let entry = lua.create_table()?;
+                    entry.set("entity_id", entity.to_bits())?;
+                    entry.set("x", position.x)?;
+                    entry.set("y", position.y)?;
+                    results.push(entry)?;
+                }
+            }
+            Ok(results)
+        });
+
+        // screen_to_world(screen_x, screen_y, depth) - unproject a viewport position through the
+        // primary camera to the point `depth` units along the ray from the camera
+        // Returns { x, y, z }, or nil if there is no camera / viewport
+        methods.add_method("screen_to_world", |lua, this, (screen_x, screen_y, depth): (f32, f32, f32)| {
+            let Some(ray) = crate::lua_world_api::primary_camera(this.world()).and_then(|camera| {
+                crate::lua_world_api::screen_to_world_ray(camera, Vec2::new(screen_x, screen_y))
+            }) else {
+                return Ok(LuaValue::Nil);
+            };
+            let point = ray.get_point(depth);
+            let table = lua.create_table()?;
+            table.set("x", point.x)?;
+            table.set("y", point.y)?;
+            table.set("z", point.z)?;
+            Ok(LuaValue::Table(table))
+        });
+
+        // screen_to_world_ray(screen_x, screen_y) - world-space ray through a viewport position,
+        // e.g. for picking with world:ray_cast
+        // Returns { origin = {x,y,z}, direction = {x,y,z} }, or nil if there is no camera / viewport
+        methods.add_method("screen_to_world_ray", |lua, this, (screen_x, screen_y): (f32, f32)| {
+            let Some(ray) = crate::lua_world_api::primary_camera(this.world()).and_then(|camera| {
+                crate::lua_world_api::screen_to_world_ray(camera, Vec2::new(screen_x, screen_y))
+            }) else {
+                return Ok(LuaValue::Nil);
+            };
+            let vec3_table = |v: Vec3| -> LuaResult<LuaTable> {
+                let table = lua.create_table()?;
+                table.set("x", v.x)?;
+                table.set("y", v.y)?;
+                table.set("z", v.z)?;
+                Ok(table)
+            };
+            let table = lua.create_table()?;
+            table.set("origin", vec3_table(ray.origin)?)?;
+            table.set("direction", vec3_table(*ray.direction)?)?;
+            Ok(LuaValue::Table(table))
+        });
+
+        // get_download_progress(asset_path_or_request_id) - byte progress of a network asset download
+        // A path reports its most recent request. Returns { downloaded, total, percent }
+        // (total/percent are nil if the size is unknown), or nil if no progress has been reported
+        methods.add_method("get_download_progress", |lua, this, target: LuaValue| {
+            let Some(progress) = this
+                .world()
+                .get_resource::<crate::network_asset_trait::DownloadProgress>()
+                .and_then(|p| match &target {
+                    LuaValue::Integer(request_id) => p.get_request(*request_id as u64),
+                    LuaValue::String(path) => p.get(&path.to_str().ok()?),
+                    _ => None,
+                })
+            else {
+                return Ok(LuaValue::Nil);
+            };
+
+            let table = lua.create_table()?;
+            table.set("downloaded", progress.downloaded)?;
+            table.set("total", progress.total)?;
+            table.set("percent", progress.percent())?;
+            Ok(LuaValue::Table(table))
+        });
+
+        // get_download_status(asset_path) - status of a network asset download including retries
+        // Returns { status, retry_count, next_retry_at } plus progress (downloading) or reason (failed);
+        // status is "local", "pending", "downloading", "complete" or "failed".
+        // next_retry_at is in elapsed seconds and nil unless a retry is scheduled
+        methods.add_method("get_download_status", |lua, this, asset_path: String| {
+            let Some(loader) = this
+                .world()
+                .get_resource::<crate::network_asset_trait::NetworkAssetLoader>()
+            else {
+                return Ok(LuaValue::Nil);
+            };
+
+            let table = lua.create_table()?;
+            match loader.get_status(&asset_path) {
+                crate::network_asset_trait::AssetDownloadStatus::LocalAvailable => table.set("status", "local")?,
+                crate::network_asset_trait::AssetDownloadStatus::Pending => table.set("status", "pending")?,
+                crate::network_asset_trait::AssetDownloadStatus::Downloading(progress) => {
+                    table.set("status", "downloading")?;
+                    table.set("progress", progress)?;
+                }
+                crate::network_asset_trait::AssetDownloadStatus::Complete => table.set("status", "complete")?,
+                crate::network_asset_trait::AssetDownloadStatus::Failed(reason) => {
+                    table.set("status", "failed")?;
+                    table.set("reason", reason)?;
+                }
+            }
+            let retry = loader.retry_state(&asset_path).unwrap_or_default();
+            table.set("retry_count", retry.retry_count)?;
+            table.set("next_retry_at", retry.next_retry_at)?;
+            Ok(LuaValue::Table(table))
+        });
+
+        // register_prefab(name, components) - define a named entity template
+        // Usage: world:register_prefab("goblin", { Transform = {...}, Health = { current = 100 } })
+        methods.add_method("register_prefab", |lua, this, (name, components): (String, LuaTable)| {
+            let prefabs = this
+                .world()
+                .get_resource::<crate::prefab_registry::PrefabRegistry>()
+                .ok_or_else(|| LuaError::RuntimeError("PrefabRegistry resource not found".to_string()))?
+                .clone();
+            prefabs.register_lua(lua, name, components)
+        });
+
+        // spawn_prefab(name, overrides) - spawn a registered prefab, returns the temp entity id
+        // Override tables merge field-by-field: world:spawn_prefab("goblin", { Health = { current = 50 } })
+        methods.add_method("spawn_prefab", |lua, this, (name, overrides): (String, Option<LuaTable>)| {
+            let prefabs = this
+                .world()
+                .get_resource::<crate::prefab_registry::PrefabRegistry>()
+                .ok_or_else(|| LuaError::RuntimeError("PrefabRegistry resource not found".to_string()))?
+                .clone();
+            let instance_id: Option<u64> = lua.globals().get("__INSTANCE_ID__").ok();
+            let spawn_phase_str: String = lua.globals()
+                .get("__SPAWN_PHASE__")
+                .unwrap_or_else(|_| "script".to_string());
+            let spawn_phase = match spawn_phase_str.as_str() {
+                "runtime" => crate::script_entities::SpawnPhase::Runtime,
+                _ => crate::script_entities::SpawnPhase::Script,
+            };
+
+            this.spawn_queue.spawn_from_prefab(
+                lua,
+                &prefabs,
+                &name,
+                overrides.as_ref(),
+                instance_id,
+                spawn_phase,
+            )
+        });
+
+        // spawn_inherit(entity_id, overrides) - spawn a copy of an existing entity's components
+        // (prototype-based, not a child), returns the temp entity id
+        // Usage: world:spawn_inherit(goblin, { Transform = { translation = { x = 5 } } })
+        methods.add_method("spawn_inherit", |lua, this, (entity_bits, overrides): (i64, Option<LuaTable>)| {
+            let parent = this.spawn_queue.resolve_entity(entity_bits as u64);
+            let instance_id: Option<u64> = lua.globals().get("__INSTANCE_ID__").ok();
+            let spawn_phase_str: String = lua.globals()
+                .get("__SPAWN_PHASE__")
+                .unwrap_or_else(|_| "script".to_string());
+            let spawn_phase = match spawn_phase_str.as_str() {
+                "runtime" => crate::script_entities::SpawnPhase::Runtime,
+                _ => crate::script_entities::SpawnPhase::Script,
+            };
+
+            this.spawn_queue.spawn_inherit(
+                lua,
+                this.world(),
+                parent,
+                overrides.as_ref(),
+                instance_id,
+                spawn_phase,
+            )
+        });
+
+        // spawn_with_init(components, init_fn) - spawn like spawn(), then call init_fn(entity) once
+        // the entity exists. Returns the temp entity id
+        methods.add_method("spawn_with_init", |lua, this, (components, init): (LuaTable, LuaFunction)| {
+            let post_spawn = this
+                .world()
+                .get_resource::<crate::lua_spawn_builder::PostSpawnCallbackQueue>()
+                .ok_or_else(|| LuaError::RuntimeError("PostSpawnCallbackQueue resource not found".to_string()))?
+                .clone();
+
+            let mut all_components = Vec::new();
+            for pair in components.pairs::<String, LuaValue>() {
+                let (component_name, component_value) = pair?;
+                let component_value = crate::serde_components::validate_lua_defined_component(
+                    lua,
+                    &component_name,
+                    component_value,
+                )?;
+                all_components.push((component_name, lua.create_registry_value(component_value)?));
+            }
+
+            let instance_id: Option<u64> = lua.globals().get("__INSTANCE_ID__").ok();
+            let spawn_phase = match lua.globals().get::<String>("__SPAWN_PHASE__").as_deref() {
+                Ok("runtime") => crate::script_entities::SpawnPhase::Runtime,
+                _ => crate::script_entities::SpawnPhase::Script,
+            };
+            let temp_id = this.spawn_queue.generate_temp_id();
+            post_spawn.register(lua, temp_id, init)?;
+            this.spawn_queue.queue_spawn(all_components, Vec::new(), instance_id, spawn_phase, temp_id);
+            Ok(temp_id)
+        });
+
+        // persist_entity(entity, path) - write the entity's reflected components to <save dir>/path
+        methods.add_method("persist_entity", |_lua, this, (entity_id, path): (u64, String)| {
+            let entity = this.spawn_queue.resolve_entity(entity_id);
+            let save_config = this
+                .world()
+                .get_resource::<crate::entity_persistence::SaveConfig>()
+                .cloned()
+                .unwrap_or_default();
+            let file_path = save_config.resolve(&path).map_err(LuaError::RuntimeError)?;
+            let scene = crate::entity_persistence::serialize_entity(this.world(), entity)
+                .map_err(|e| LuaError::RuntimeError(format!("persist_entity: {}", e)))?;
+            if let Some(parent) = file_path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| LuaError::RuntimeError(format!("persist_entity: {}", e)))?;
+            }
+            std::fs::write(&file_path, scene)
+                .map_err(|e| LuaError::RuntimeError(format!("persist_entity: {}", e)))?;
+            debug!("[PERSIST] Saved entity {:?} to {:?}", entity, file_path);
+            Ok(())
+        });
+
+        // load_entity(path, replace?) - queue an entity saved by persist_entity, returns its temp id
+        // When `replace` is given, the saved components are loaded into that entity and it keeps its id
+        methods.add_method("load_entity", |lua, this, (path, replace_id): (String, Option<u64>)| {
+            let save_config = this
+                .world()
+                .get_resource::<crate::entity_persistence::SaveConfig>()
+                .cloned()
+                .unwrap_or_default();
+            let file_path = save_config.resolve(&path).map_err(LuaError::RuntimeError)?;
+            let source = std::fs::read_to_string(&file_path)
+                .map_err(|e| LuaError::RuntimeError(format!("load_entity: {:?}: {}", file_path, e)))?;
+
+            let mut components = Vec::new();
+            for (component_name, value) in crate::entity_persistence::deserialize_entity(lua, this.world(), &source)? {
+                let value = crate::serde_components::validate_lua_defined_component(lua, &component_name, value)?;
+                components.push((component_name, lua.create_registry_value(value)?));
+            }
+
+            let instance_id: Option<u64> = lua.globals().get("__INSTANCE_ID__").ok();
+            let spawn_phase = match lua.globals().get::<String>("__SPAWN_PHASE__").as_deref() {
+                Ok("runtime") => crate::script_entities::SpawnPhase::Runtime,
+                _ => crate::script_entities::SpawnPhase::Script,
+            };
+            let temp_id = this.spawn_queue.generate_temp_id();
+            match replace_id {
+                Some(replace_id) => {
+                    let replaced = this.spawn_queue.resolve_entity(replace_id);
+                    this.spawn_queue.queue_replace(replaced, components, instance_id, spawn_phase, temp_id);
+                }
+                None => {
+                    this.spawn_queue.queue_spawn(components, Vec::new(), instance_id, spawn_phase, temp_id);
+                }
+            }
+            debug!("[PERSIST] Queued entity from {:?} as temp_id {}", file_path, temp_id);
+            Ok(temp_id)
+        });
+
+        // snapshot_entity(entity) - capture the entity's reflected components for restore_entity / undo
+        // Also recorded in the open undo frame, if any
+        methods.add_method("snapshot_entity", |_lua, this, entity_id: u64| {
+            let entity = this.spawn_queue.resolve_entity(entity_id);
+            let snapshot = crate::undo_stack::snapshot_entity_state(this.world(), entity)
+                .map_err(|e| LuaError::RuntimeError(format!("snapshot_entity: {}", e)))?;
+            if let Some(undo_stack) = this.world().get_resource::<crate::undo_stack::UndoStack>() {
+                undo_stack.record(&snapshot);
+            }
+            Ok(snapshot)
+        });
+
+        // restore_entity(snapshot) - put the entity back in the snapshotted state (applied next update)
+        methods.add_method("restore_entity", |_lua, this, snapshot: LuaAnyUserData| {
+            let snapshot = snapshot.borrow::<crate::undo_stack::EntityStateSnapshot>()?;
+            this.world()
+                .get_resource::<crate::undo_stack::UndoStack>()
+                .ok_or_else(|| LuaError::RuntimeError("UndoStack resource not found".to_string()))?
+                .queue_restore(snapshot.clone());
+            Ok(())
+        });
+
+        // begin_undo_frame() / end_undo_frame() - group the snapshots taken in between into one undo step
+        methods.add_method("begin_undo_frame", |_lua, this, ()| {
+            this.world()
+                .get_resource::<crate::undo_stack::UndoStack>()
+                .ok_or_else(|| LuaError::RuntimeError("UndoStack resource not found".to_string()))?
+                .begin_frame();
+            Ok(())
+        });
+
+        methods.add_method("end_undo_frame", |_lua, this, ()| {
+            Ok(this
+                .world()
+                .get_resource::<crate::undo_stack::UndoStack>()
+                .ok_or_else(|| LuaError::RuntimeError("UndoStack resource not found".to_string()))?
+                .end_frame())
+        });
+
+        // undo() - restore every entity of the latest undo frame; returns false when the stack is empty
+        methods.add_method("undo", |_lua, this, ()| {
+            Ok(this
+                .world()
+                .get_resource::<crate::undo_stack::UndoStack>()
+                .ok_or_else(|| LuaError::RuntimeError("UndoStack resource not found".to_string()))?
+                .undo())
+        });
+
+        // spawn_ui_node(props) - spawn a Bevy UI node from a flat props table, returns the temp entity id
+        // Usage: world:spawn_ui_node({ width = "100%", height = 40, background_color = "#333333", children = { child } })
+        methods.add_method("spawn_ui_node", |lua, this, props: LuaTable| {
+            let props = crate::ui_node_builder::UiNodeProps::from_lua_table(&props)?;
+            let instance_id: Option<u64> = lua.globals().get("__INSTANCE_ID__").ok();
+            let spawn_phase_str: String = lua.globals()
+                .get("__SPAWN_PHASE__")
+                .unwrap_or_else(|_| "script".to_string());
+            let spawn_phase = match spawn_phase_str.as_str() {
+                "runtime" => crate::script_entities::SpawnPhase::Runtime,
+                _ => crate::script_entities::SpawnPhase::Script,
+            };
+
+            Ok(this.spawn_queue.queue_ui_node(props, instance_id, spawn_phase))
+        });
+
+        // begin_update_transaction() - open a transaction for all-or-nothing updates across entities
+        // Usage:
+        //   local tx = world:begin_update_transaction()
+        //   world:queue_in_transaction(tx, from, { Health = { current = 50 } })
+        //   world:queue_in_transaction(tx, to, { Health = { current = 150 } })
+        //   world:commit_transaction(tx)   -- nothing applies if either entity is gone
+        methods.add_method("begin_update_transaction", |lua, this, ()| {
+            let instance_id: u64 = lua.globals().get("__INSTANCE_ID__").unwrap_or(0);
+            Ok(this.update_queue.begin_transaction(instance_id))
+        });
+
+        // queue_in_transaction(tx, entity, components) - stage entity:set-style updates in a transaction
+        methods.add_method(
+            "queue_in_transaction",
+            |lua, this, (transaction_id, entity_id, components): (u64, u64, LuaTable)| {
+                let entity = this.spawn_queue.resolve_entity(entity_id);
+                for pair in components.pairs::<String, LuaValue>() {
+                    let (component_name, component_value) = pair?;
+                    let component_value = crate::serde_components::validate_lua_defined_component(
+                        lua,
+                        &component_name,
+                        component_value,
+                    )?;
+                    let component_data = match component_value {
+                        LuaValue::Table(table) => table,
+                        other => {
+                            let wrapper = lua.create_table()?;
+                            wrapper.set("_0", other)?;
+                            wrapper
+                        }
+                    };
+                    let registry_key = lua.create_registry_value(component_data)?;
+                    if !this
+                        .update_queue
+                        .queue_in_transaction(transaction_id, entity, component_name, registry_key)
+                    {
+                        return Err(LuaError::RuntimeError(format!(
+                            "queue_in_transaction: transaction {} is not open",
+                            transaction_id
+                        )));
+                    }
+                }
+                Ok(())
+            },
+        );
+
+        // commit_transaction(tx) - queue the staged updates; they apply only if every entity still exists
+        methods.add_method("commit_transaction", |_lua, this, transaction_id: u64| {
+            if !this.update_queue.commit_transaction(transaction_id) {
+                return Err(LuaError::RuntimeError(format!(
+                    "commit_transaction: transaction {} is not open",
+                    transaction_id
+                )));
+            }
+            Ok(())
+        });
+
+        // rollback_transaction(tx) - discard the staged updates; returns false if it wasn't open
+        methods.add_method("rollback_transaction", |lua, this, transaction_id: u64| {
+            let Some(keys) = this.update_queue.rollback_transaction(transaction_id) else {
+                return Ok(false);
+            };
+            for key in keys {
+                if let Ok(key) = Arc::try_unwrap(key) {
+                    let _ = lua.remove_registry_value(key);
+                }
+            }
+            Ok(true)
+        });
+
+        // tween(entity, component, field, to_value, duration_secs, easing, after_handle)
+        // Animates a numeric field; returns a handle for cancel_tween or chaining via after_handle
+        // Usage: world:tween(entity, "Transform", "translation.x", 100.0, 2.0, "ease_out")
+        methods.add_method(
+            "tween",
+            |_lua, this, (entity_id, component, field, to_value, duration, easing, after): (
+                u64,
+                String,
+                String,
+                f64,
+                f32,
+                Option<String>,
+                Option<u64>,
+            )| {
+                let easing = match easing.as_deref() {
+                    None => crate::lua_tween::EasingFn::Linear,
+                    Some(name) => crate::lua_tween::EasingFn::from_name(name).ok_or_else(|| {
+                        LuaError::RuntimeError(format!(
+                            "tween: unknown easing '{}' (expected linear, ease_in, ease_out, ease_in_out or bounce)",
+                            name
+                        ))
+                    })?,
+                };
+                let tween_queue = this
+                    .world()
+                    .get_resource::<crate::lua_tween::TweenQueue>()
+                    .ok_or_else(|| LuaError::RuntimeError("TweenQueue resource not found".to_string()))?
+                    .clone();
+                let entity = this.spawn_queue.resolve_entity(entity_id);
+                Ok(tween_queue.add(entity, component, field, to_value, duration, easing, after))
+            },
+        );
+
+        // cancel_tween(handle) - stop a tween (and anything chained after it)
+        methods.add_method("cancel_tween", |_lua, this, handle: u64| {
+            let tween_queue = this
+                .world()
+                .get_resource::<crate::lua_tween::TweenQueue>()
+                .ok_or_else(|| LuaError::RuntimeError("TweenQueue resource not found".to_string()))?
+                .clone();
+            Ok(tween_queue.cancel(handle))
+        });
+
+        // animate(entity, clip_asset_path, loop_mode) - play an AnimationClip on the entity's skeleton
+        // loop_mode: "once" (default), "repeat" or "ping_pong"; playback starts once the clip has loaded
+        // Usage: world:animate(fox, "models/Fox.glb#Animation0", "repeat")
+        methods.add_method(
+            "animate",
+            |_lua, this, (entity_id, clip_path, loop_mode): (u64, String, Option<String>)| {
+                let mode = match loop_mode.as_deref() {
+                    None => crate::lua_animation::AnimationLoopMode::Once,
+                    Some(name) => crate::lua_animation::AnimationLoopMode::from_name(name).ok_or_else(|| {
+                        LuaError::RuntimeError(format!(
+                            "animate: unknown loop mode '{}' (expected once, repeat or ping_pong)",
+                            name
+                        ))
+                    })?,
+                };
+                let entity = this.spawn_queue.resolve_entity(entity_id);
+                let player = crate::lua_animation::find_animation_player(this.world(), entity)
+                    .ok_or_else(|| {
+                        LuaError::RuntimeError(format!(
+                            "animate: entity {} has no AnimationPlayer (no skeleton to animate)",
+                            entity_id
+                        ))
+                    })?;
+                let asset_server = this
+                    .world()
+                    .get_resource::<AssetServer>()
+                    .ok_or_else(|| LuaError::RuntimeError("AssetServer resource not found".to_string()))?;
+                let clip = asset_server.load::<AnimationClip>(clip_path);
+                let animation_queue = this
+                    .world()
+                    .get_resource::<crate::lua_animation::AnimationQueue>()
+                    .ok_or_else(|| LuaError::RuntimeError("AnimationQueue resource not found".to_string()))?
+                    .clone();
+                animation_queue.queue_play(player, clip, mode);
+                Ok(())
+            },
+        );
+
+        // stop_animation(entity) - stop every clip playing on the entity's skeleton
+        methods.add_method("stop_animation", |_lua, this, entity_id: u64| {
+            let entity = this.spawn_queue.resolve_entity(entity_id);
+            let player = crate::lua_animation::find_animation_player(this.world(), entity)
+                .ok_or_else(|| {
+                    LuaError::RuntimeError(format!(
+                        "stop_animation: entity {} has no AnimationPlayer (no skeleton to animate)",
+                        entity_id
+                    ))
+                })?;
+            let animation_queue = this
+                .world()
+                .get_resource::<crate::lua_animation::AnimationQueue>()
+                .ok_or_else(|| LuaError::RuntimeError("AnimationQueue resource not found".to_string()))?
+                .clone();
+            animation_queue.queue_stop(player);
+            Ok(())
+        });
+
+        // animation_time(entity) - playback position in seconds, or nil when nothing is playing
+        methods.add_method("animation_time", |_lua, this, entity_id: u64| {
+            let entity = this.spawn_queue.resolve_entity(entity_id);
+            let player = crate::lua_animation::find_animation_player(this.world(), entity)
+                .ok_or_else(|| {
+                    LuaError::RuntimeError(format!(
+                        "animation_time: entity {} has no AnimationPlayer (no skeleton to animate)",
+                        entity_id
+                    ))
+                })?;
+            Ok(crate::lua_animation::animation_time(this.world(), player))
+        });
+
+        // list_components(entity) - names of all components currently on an entity (sorted)
+        // Returns nil if the entity does not exist
+        methods.add_method("list_components", |lua, this, entity_id: u64| {
+            let entity = this.spawn_queue.resolve_entity(entity_id);
+            match this.component_registry.list_components_on_entity(this.world(), entity) {
+                Some(names) => Ok(LuaValue::Table(lua.create_sequence_from(names)?)),
+                None => Ok(LuaValue::Nil),
+            }
+        });
+
+        // get_parent(entity) - parent entity id, or nil for root entities
+        methods.add_method("get_parent", |_lua, this, entity_id: u64| {
+            let entity = this.spawn_queue.resolve_entity(entity_id);
+            Ok(this.world().get::<ChildOf>(entity).map(|child_of| child_of.parent().to_bits()))
+        });
+
+        // get_children(entity) - array of child entity ids (empty if none)
+        methods.add_method("get_children", |lua, this, entity_id: u64| {
+            let entity = this.spawn_queue.resolve_entity(entity_id);
+            let children: Vec<u64> = this
+                .world()
+                .get::<Children>(entity)
+                .map(|children| children.iter().map(|child| child.to_bits()).collect())
+                .unwrap_or_default();
+            lua.create_sequence_from(children)
+        });
+
+        // tag(entity, tag) - add a string tag (queued; has_tag/query_by_tag see it right away)
+        methods.add_method("tag", |_lua, this, (entity_id, tag): (u64, String)| {
+            tag_queue(this.world())?.queue(entity_id, crate::entity_tags::TagOp::Add(tag));
+            Ok(())
+        });
+
+        // untag(entity, tag) - remove a tag
+        methods.add_method("untag", |_lua, this, (entity_id, tag): (u64, String)| {
+            tag_queue(this.world())?.queue(entity_id, crate::entity_tags::TagOp::Remove(tag));
+            Ok(())
+        });
+
+        // clear_tags(entity) - remove every tag
+        methods.add_method("clear_tags", |_lua, this, entity_id: u64| {
+            tag_queue(this.world())?.queue(entity_id, crate::entity_tags::TagOp::Clear);
+            Ok(())
+        });
+
+        // has_tag(entity, tag) - whether the entity carries the tag
+        methods.add_method("has_tag", |_lua, this, (entity_id, tag): (u64, String)| {
+            let entity = this.spawn_queue.resolve_entity(entity_id);
+            Ok(tag_queue(this.world())?
+                .effective_tags(this.world(), &this.spawn_queue, entity)
+                .contains(&tag))
+        });
+
+        // query_by_tag(tag) - array of ids of every entity carrying the tag
+        methods.add_method("query_by_tag", |lua, this, tag: String| {
+            let entities: Vec<u64> = tag_queue(this.world())?
+                .entities_with_tag(this.world(), &this.spawn_queue, &tag)
+                .into_iter()
+                .map(|entity| entity.to_bits())
+                .collect();
+            lua.create_sequence_from(entities)
+        });
+
+        // set_parent(child, parent) - queue adding child to parent's children
+        methods.add_method("set_parent", |_lua, this, (child_id, parent_id): (u64, u64)| {
+            let hierarchy_queue = this
+                .world()
+                .get_resource::<crate::hierarchy_queue::HierarchyQueue>()
+                .ok_or_else(|| LuaError::RuntimeError("HierarchyQueue resource not found".to_string()))?
+                .clone();
+            hierarchy_queue.queue_set_parent(child_id, parent_id);
+            Ok(())
+        });
+
+        // despawn_recursive(entity_id) - despawn an entity and its descendants (same as despawn())
+        // despawn_single(entity_id) - despawn only the entity, detaching its children
+        methods.add_method("despawn_recursive", |_lua, this, entity_id: u64| {
+            let entity = this.spawn_queue.resolve_entity(entity_id);
+            this.despawn_queue.queue_recursive(entity);
+            Ok(())
+        });
+
+        methods.add_method("despawn_single", |_lua, this, entity_id: u64| {
+            let entity = this.spawn_queue.resolve_entity(entity_id);
+            this.despawn_queue.queue_single(entity);
+            Ok(())
+        });
+
         // stop_owning_script(entity_id) - stop a script and clean up its resources
         methods.add_method("stop_owning_script", |_lua, this, entity_id: i64| {
             let entity = this.spawn_queue.resolve_entity(entity_id as u64);
@@ -393,6 +1633,11 @@ impl LuaUserData for LuaWorldContext<'_> {
                 
                 // Clear systems registered by this script
                 this.system_registry.clear_instance_systems(instance_id);
+
+                // Forget per-instance parameters
+                if let Some(pool) = this.world().get_resource::<crate::script_entities::ScriptInstancePool>() {
+                    pool.remove(instance_id);
+                }
                 
                 // Queue entity for despawn
                 this.despawn_queue.queue_despawn(entity);
@@ -407,6 +1652,52 @@ impl LuaUserData for LuaWorldContext<'_> {
             }
         });
 
+        // pause_system_group(group) / resume_system_group(group) - suspend or resume every
+        // system registered with { group = "..." }; returns false if nothing changed
+        // Usage: world:pause_system_group("gameplay")
+        methods.add_method("pause_system_group", |_lua, this, group: String| {
+            Ok(this.system_registry.pause_group(&group))
+        });
+
+        methods.add_method("resume_system_group", |_lua, this, group: String| {
+            Ok(this.system_registry.resume_group(&group))
+        });
+
+        // is_system_group_paused(group) - whether a group is currently suspended
+        methods.add_method("is_system_group_paused", |_lua, this, group: String| {
+            Ok(this.system_registry.is_group_paused(&group))
+        });
+
+        // set_system_hz(name, hz) - run one of this script's systems (by its { name = ... } label)
+        // at most hz times per second; 0 removes the limit
+        // Usage: world:set_system_hz("pathfinding", 10.0)
+        methods.add_method("set_system_hz", |lua, this, (name, max_hz): (String, f32)| {
+            let instance_id: u64 = lua.globals().get("__INSTANCE_ID__").unwrap_or(0);
+            this.system_registry.set_system_hz(instance_id, &name, max_hz);
+            Ok(())
+        });
+
+        // changed_observers() - observers this script instance lost in its last hot reload because
+        // their callback changed; returns an array of { entity, event_type } to observe again
+        // Usage: for _, o in ipairs(world:changed_observers()) do world:get_entity(o.entity):observe(o.event_type, fn) end
+        methods.add_method("changed_observers", |lua, this, ()| {
+            let results = lua.create_table()?;
+            let instance_id: u64 = lua.globals().get("__INSTANCE_ID__").unwrap_or(0);
+            let Some(snapshot) = this
+                .world()
+                .get_resource::<crate::observer_persistence::PersistentObserverSnapshot>()
+            else {
+                return Ok(results);
+            };
+            for (entity, event_type) in snapshot.changed_observers(instance_id) {
+                let entry = lua.create_table()?;
+                entry.set("entity", entity.to_bits())?;
+                entry.set("event_type", event_type)?;
+                results.push(entry)?;
+            }
+            Ok(results)
+        });
+
         // read_events(event_type_name) - read any Bevy event via generated dispatch
         methods.add_method("read_events", |lua, this, event_type_name: String| {
             bevy::log::debug!("[READ_EVENTS] Reading events: '{}'", event_type_name);
@@ -453,50 +1744,15 @@ impl LuaUserData for LuaWorldContext<'_> {
         });
 
         // get_resource(resource_type_name) - get a resource by type name via reflection
+        // Returns nil for unknown types and for resources that aren't inserted, so scripts can
+        // poll optional resources
         methods.add_method("get_resource", |lua, this, resource_type_name: String| {
             let type_registry = this.component_registry.type_registry();
             let registry = type_registry.read();
-
-            // Try direct lookup first (exact short path or full path match)
-            let type_registration = registry
-                .get_with_short_type_path(&resource_type_name)
-                .or_else(|| registry.get_with_type_path(&resource_type_name))
-                // Fallback: iterate and find by short name match (handles generics)
-                .or_else(|| {
-                    // For generic types like "ButtonInput<KeyCode>", the short_type_path might be
-                    // "ButtonInput<KeyCode>" but the full path is "bevy_input::ButtonInput<bevy_input::keyboard::KeyCode>"
-                    // We need to match the short_type_path which strips crate prefixes from generics
-                    registry.iter().find(|reg| {
-                        let short = reg.type_info().type_path_table().short_path();
-                        short == resource_type_name
-                    })
-                });
-
-            let registration = match type_registration {
-                Some(r) => r,
-                None => {
-                    // Log available similar types for debugging
-                    debug!("[GET_RESOURCE] Type '{}' not found. Looking for similar types...", resource_type_name);
-                    for reg in registry.iter() {
-                        if reg.data::<bevy::ecs::reflect::ReflectResource>().is_some() {
-                            let short = reg.type_info().type_path_table().short_path();
-                            let full = reg.type_info().type_path();
-                            // Log types that might be similar (contain part of the requested name)
-                            if let Some(base_name) = resource_type_name.split(['<', '>']).next() {
-                                if short.contains(base_name) || full.contains(base_name) {
-                                    debug!("[GET_RESOURCE]   Found similar: short='{}', full='{}'", short, full);
-                                }
-                            }
-                        }
-                    }
-                    return Ok(LuaValue::Nil);
-                }
-            };
-
-            let reflect_resource = match registration.data::<bevy::ecs::reflect::ReflectResource>() {
-                Some(r) => r,
-                None => {
-                    debug!("[GET_RESOURCE] Type '{}' found but has no ReflectResource", resource_type_name);
+            let reflect_resource = match resolve_reflect_resource(&registry, &resource_type_name, "get_resource") {
+                Ok(reflect_resource) => reflect_resource,
+                Err(e) => {
+                    debug!("[GET_RESOURCE] {}", e);
                     return Ok(LuaValue::Nil);
                 }
             };
@@ -521,6 +1777,254 @@ impl LuaUserData for LuaWorldContext<'_> {
             }
         });
 
+        // set_resource(resource_type_name, table) - queue a patch of a resource's fields via reflection
+        // Only the fields present in the table are changed; applied by the ResourceQueue after
+        // earlier queued inserts. Unknown types raise an error immediately.
+        // Usage: world:set_resource("ClearColor", { _0 = { r = 0.1, g = 0.1, b = 0.2, a = 1.0 } })
+        methods.add_method("set_resource", |lua, this, (resource_type_name, data): (String, LuaTable)| {
+            {
+                let type_registry = this.component_registry.type_registry();
+                let registry = type_registry.read();
+                resolve_reflect_resource(&registry, &resource_type_name, "set_resource")?;
+            }
+            let resource_queue = this
+                .world()
+                .get_resource::<crate::resource_queue::ResourceQueue>()
+                .ok_or_else(|| LuaError::RuntimeError("ResourceQueue resource not found".to_string()))?
+                .clone();
+            resource_queue.queue_set(resource_type_name, lua.create_registry_value(data)?);
+            Ok(())
+        });
+
+        // create_asset(type_name, props) - build an asset from a Lua table and add it to Assets<T> now
+        // Accepts a short ("Image") or full ("bevy_image::image::Image") asset type name
+        // Dispatched to the generated SystemState<ResMut<Assets<T>>> arm for the type (LuaBindingsPlugin)
+        // Returns the handle as { id = <asset index bits>, type = "Image" }, usable anywhere a handle
+        // field is set
+        // Usage: local layout = world:create_asset("TextureAtlasLayout", { ... })
+        methods.add_method("create_asset", |lua, this, (type_name, props): (String, LuaTable)| {
+            #[allow(invalid_reference_casting)]
+            let world_mut = unsafe { &mut *(this.world() as *const World as *mut World) };
+            crate::systemparam_lua_trait::call_create_asset_global(lua, world_mut, &type_name, props)
+        });
+
+        // component_registry() - the LuaComponentRegistry, for per-component update validators
+        // Usage: world:component_registry():set_validator("Health", function(h) return h.value >= 0 end)
+        methods.add_method("component_registry", |_lua, this, ()| {
+            this.world()
+                .get_resource::<crate::component_lua_trait::LuaComponentRegistry>()
+                .cloned()
+                .ok_or_else(|| LuaError::RuntimeError("LuaComponentRegistry resource not found".to_string()))
+        });
+
+        // get_asset(handle) - read an asset's data as a Lua table (via reflection)
+        // Returns nil while the asset is loading, and nil with a warning when it no longer exists
+        // Usage: local layout = world:get_asset(atlas); print(layout.size.x)
+        methods.add_method("get_asset", |lua, this, handle: LuaValue| {
+            let world = this.world();
+            let asset_registry = world
+                .get_resource::<crate::asset_loading::AssetRegistry>()
+                .ok_or_else(|| LuaError::RuntimeError("AssetRegistry resource not found".to_string()))?
+                .clone();
+            let Some(handle) = resolve_asset_handle(&asset_registry, &handle, "get_asset")? else {
+                return Ok(LuaValue::Nil);
+            };
+
+            let type_registry = world.resource::<AppTypeRegistry>().read();
+            let reflect_asset = type_registry
+                .get(handle.type_id())
+                .and_then(|registration| registration.data::<bevy::asset::ReflectAsset>())
+                .ok_or_else(|| {
+                    LuaError::RuntimeError(format!(
+                        "get_asset: asset type of {:?} is not registered for reflection",
+                        handle.id()
+                    ))
+                })?;
+
+            match reflect_asset.get(world, handle.id()) {
+                Some(asset) => crate::lua_world_api::reflection_to_lua_with_assets(
+                    lua,
+                    asset.as_partial_reflect(),
+                    Some(&asset_registry),
+                ),
+                None => {
+                    let loading = world.get_resource::<AssetServer>().is_some_and(|server| {
+                        matches!(
+                            server.get_load_state(handle.id()),
+                            Some(bevy::asset::LoadState::Loading | bevy::asset::LoadState::NotLoaded)
+                        )
+                    });
+                    if !loading {
+                        warn!("get_asset: asset {:?} no longer exists", handle.id());
+                    }
+                    Ok(LuaValue::Nil)
+                }
+            }
+        });
+
+        // set_asset(handle, table) - write fields of an asset back, like entity:set does for components
+        // Returns false if the asset isn't available (still loading or removed)
+        // Usage: world:set_asset(atlas, { size = { x = 64, y = 64 } })
+        methods.add_method("set_asset", |_lua, this, (handle, data): (LuaValue, LuaTable)| {
+            let asset_registry = this
+                .world()
+                .get_resource::<crate::asset_loading::AssetRegistry>()
+                .ok_or_else(|| LuaError::RuntimeError("AssetRegistry resource not found".to_string()))?
+                .clone();
+            let Some(handle) = resolve_asset_handle(&asset_registry, &handle, "set_asset")? else {
+                return Ok(false);
+            };
+
+            let type_registry = this.world().resource::<AppTypeRegistry>().clone();
+            let reflect_asset = type_registry
+                .read()
+                .get(handle.type_id())
+                .and_then(|registration| registration.data::<bevy::asset::ReflectAsset>())
+                .cloned()
+                .ok_or_else(|| {
+                    LuaError::RuntimeError(format!(
+                        "set_asset: asset type of {:?} is not registered for reflection",
+                        handle.id()
+                    ))
+                })?;
+
+            #[allow(invalid_reference_casting)]
+            let world_mut = unsafe { &mut *(this.world() as *const World as *mut World) };
+            let Some(asset) = reflect_asset.get_mut(world_mut, handle.id()) else {
+                return Ok(false);
+            };
+            crate::component_updater::update_component_from_lua(
+                asset.as_partial_reflect_mut(),
+                &data,
+                Some(&asset_registry),
+                &type_registry,
+            )?;
+            Ok(true)
+        });
+
+        // load_asset_from_bytes(type_name, bytes, extension) - load raw bytes through the AssetLoader
+        // registered for `extension`, e.g. a generated PNG or a downloaded font
+        // bytes: a (binary) string or an array of integers; needs LuaBytesAssetSourcePlugin
+        // Loads with AssetServer::load_untyped_async; returns { type = "Image", path = ..., loading = true }
+        // and fills in `id` (the asset index, like create_asset) once loaded, or `error` if it fails
+        // Usage: sprite.image = world:load_asset_from_bytes("Image", png_bytes, "png")
+        methods.add_method(
+            "load_asset_from_bytes",
+            |lua, this, (type_name, bytes, extension): (String, LuaValue, String)| {
+                let byte_assets = this
+                    .world()
+                    .get_resource::<crate::asset_loading::LuaBytesAssets>()
+                    .ok_or_else(|| {
+                        LuaError::RuntimeError(
+                            "load_asset_from_bytes: add LuaBytesAssetSourcePlugin before DefaultPlugins".to_string(),
+                        )
+                    })?
+                    .clone();
+                let asset_server = this
+                    .world()
+                    .get_resource::<AssetServer>()
+                    .ok_or_else(|| LuaError::RuntimeError("AssetServer resource not found".to_string()))?
+                    .clone();
+
+                let (type_path, short_name) = resolve_asset_type(this.world(), &type_name, "load_asset_from_bytes")?;
+                if extension.trim_start_matches('.').is_empty() {
+                    return Err(LuaError::RuntimeError(
+                        "load_asset_from_bytes: an extension is needed to pick the asset loader".to_string(),
+                    ));
+                }
+                let bytes = crate::asset_loading::bytes_from_lua(&bytes)
+                    .map_err(|e| LuaError::RuntimeError(format!("load_asset_from_bytes: {}", e)))?;
+                let asset_type = this
+                    .world()
+                    .resource::<AppTypeRegistry>()
+                    .read()
+                    .get_with_type_path(&type_path)
+                    .map(|registration| registration.type_id())
+                    .ok_or_else(|| {
+                        LuaError::RuntimeError(format!("load_asset_from_bytes: '{}' is not registered", type_path))
+                    })?;
+
+                let path = byte_assets.insert(bytes, &extension);
+                debug!("[LOAD_BYTES] Loading '{}' as {}", path, short_name);
+
+                let handle = lua.create_table()?;
+                handle.set("type", short_name.clone())?;
+                handle.set("path", path.clone())?;
+                handle.set("loading", true)?;
+                byte_assets.load(
+                    &asset_server,
+                    path,
+                    short_name,
+                    asset_type,
+                    lua.create_registry_value(handle.clone())?,
+                );
+                Ok(handle)
+            },
+        );
+
+        // run_system(name, args) - run a Rust one-shot system registered in OneShotSystemRegistry
+        // args (optional table) is readable by the system as the LuaSystemInput resource
+        // Usage: world:run_system("heal_all_players", { amount = 10 })
+        methods.add_method("run_system", |lua, this, (name, args): (String, Option<LuaTable>)| {
+            let input = match args {
+                Some(table) => lua.from_value::<serde_json::Value>(LuaValue::Table(table))?,
+                None => serde_json::Value::Null,
+            };
+            #[allow(invalid_reference_casting)]
+            let world_mut = unsafe { &mut *(this.world() as *const World as *mut World) };
+            crate::one_shot_systems::run_one_shot_system(world_mut, &name, input)
+                .map_err(|e| LuaError::RuntimeError(format!("run_system: {}", e)))
+        });
+
+        // ray_cast(ox, oy, oz, dx, dy, dz) - closest mesh hit along a ray via MeshRayCast
+        // Returns { entity, distance, normal = {x,y,z}, position = {x,y,z} } or nil
+        // Usage: local hit = world:ray_cast(0, 10, 0, 0, -1, 0)
+        methods.add_method(
+            "ray_cast",
+            |lua, this, (ox, oy, oz, dx, dy, dz): (f32, f32, f32, f32, f32, f32)| {
+                use bevy::ecs::system::SystemState;
+                use bevy::picking::mesh_picking::ray_cast::{MeshRayCast, MeshRayCastSettings};
+
+                let direction = Dir3::new(Vec3::new(dx, dy, dz)).map_err(|e| {
+                    LuaError::RuntimeError(format!("ray_cast: invalid direction: {}", e))
+                })?;
+                let ray = Ray3d::new(Vec3::new(ox, oy, oz), direction);
+
+                if !this.world().contains_resource::<Assets<Mesh>>() {
+                    return Err(LuaError::RuntimeError(
+                        "ray_cast: Assets<Mesh> resource not found".to_string(),
+                    ));
+                }
+
+                #[allow(invalid_reference_casting)]
+                let world_mut = unsafe { &mut *(this.world() as *const World as *mut World) };
+                let mut system_state = SystemState::<MeshRayCast>::new(world_mut);
+                let mut mesh_ray_cast = system_state.get_mut(world_mut);
+
+                let Some((entity, hit)) = mesh_ray_cast
+                    .cast_ray(ray, &MeshRayCastSettings::default())
+                    .first()
+                else {
+                    return Ok(LuaValue::Nil);
+                };
+
+                let vec3_table = |v: Vec3| -> LuaResult<LuaTable> {
+                    let table = lua.create_table()?;
+                    table.set("x", v.x)?;
+                    table.set("y", v.y)?;
+                    table.set("z", v.z)?;
+                    Ok(table)
+                };
+
+                let result = lua.create_table()?;
+                result.set("entity", entity.to_bits())?;
+                result.set("distance", hit.distance)?;
+                result.set("normal", vec3_table(hit.normal)?)?;
+                result.set("position", vec3_table(hit.point)?)?;
+                Ok(LuaValue::Table(result))
+            },
+        );
+
         // call_systemparam_method(param_name, method_name, ...args)
         methods.add_method("call_systemparam_method", |lua, this, (param_name, method_name, args): (String, String, mlua::MultiValue)| {
             #[allow(invalid_reference_casting)]
@@ -536,14 +2040,16 @@ impl LuaUserData for LuaWorldContext<'_> {
         });
 
         // call_resource_method(resource_name, method_name, ...args) - call a registered method on a resource
+        // Builder methods return a chain handle: world:call_resource_method("Cfg", "with_a", 1):with_b(2)
         methods.add_method("call_resource_method", |lua, this, (resource_name, method_name, args): (String, String, mlua::MultiValue)| {
             let resource_registry = this.world().resource::<crate::resource_lua_trait::LuaResourceRegistry>();
-            resource_registry.call_method(
+            resource_registry.call_method_chained(
                 lua,
                 this.world(),
                 &resource_name,
                 &method_name,
                 args,
+                std::sync::Arc::downgrade(&this.alive),
             )
         });
 
@@ -621,127 +2127,84 @@ impl LuaUserData for LuaWorldContext<'_> {
             }
         });
 
-        // write_message(message_type_name, data_table) - queue a message to be sent
-        // Messages use MessageWriter<M> instead of EventWriter<T>
-        methods.add_method("write_message", |_lua, this, (message_type_name, data_table): (String, LuaTable)| {
-            // Convert Lua table to JSON value
-            fn lua_to_json(value: &LuaValue) -> serde_json::Value {
-                match value {
-                    LuaValue::Nil => serde_json::Value::Null,
-                    LuaValue::Boolean(b) => serde_json::Value::Bool(*b),
-                    LuaValue::Integer(i) => serde_json::json!(*i),
-                    LuaValue::Number(n) => serde_json::json!(*n),
-                    LuaValue::String(s) => match s.to_str() {
-                        Ok(cow) => serde_json::Value::String(cow.to_string()),
-                        Err(_) => serde_json::Value::String(String::new()),
-                    },
-                    LuaValue::Table(t) => {
-                        // Determine if array or object
-                        let mut is_array = true;
-                        let mut max_key = 0i64;
-                        for pair in t.clone().pairs::<LuaValue, LuaValue>() {
-                            if let Ok((key, _)) = pair {
-                                match key {
-                                    LuaValue::Integer(i) if i > 0 => {
-                                        max_key = max_key.max(i);
-                                    }
-                                    _ => {
-                                        is_array = false;
-                                        break;
-                                    }
-                                }
-                            }
-                        }
-
-                        if is_array && max_key > 0 {
-                            let mut arr = Vec::new();
-                            for i in 1..=max_key {
-                                if let Ok(val) = t.get::<LuaValue>(i) {
-                                    arr.push(lua_to_json(&val));
-                                }
-                            }
-                            serde_json::Value::Array(arr)
-                        } else {
-                            let mut map = serde_json::Map::new();
-                            for pair in t.clone().pairs::<String, LuaValue>() {
-                                if let Ok((key, val)) = pair {
-                                    map.insert(key, lua_to_json(&val));
-                                }
-                            }
-                            serde_json::Value::Object(map)
-                        }
-                    }
-                    _ => serde_json::Value::Null,
-                }
-            }
-
-            let json_data = lua_to_json(&LuaValue::Table(data_table.clone()));
+        // send_event_priority(event_type_name, data_table, priority) - queue an event for dispatch_lua_events
+        // priority is "high", "normal" or "low"; high-priority events are written first each frame
+        // Usage: world:send_event_priority("PlayerDied", { player = id }, "high")
+        methods.add_method(
+            "send_event_priority",
+            |lua, this, (event_type_name, data_table, priority): (String, LuaTable, Option<String>)| {
+                let priority = match priority {
+                    Some(priority) => priority
+                        .parse::<crate::event_sender::EventPriority>()
+                        .map_err(LuaError::RuntimeError)?,
+                    None => crate::event_sender::EventPriority::Normal,
+                };
+                let data: serde_json::Value = lua.from_value(LuaValue::Table(data_table))?;
+                let pending = this
+                    .world()
+                    .get_resource::<crate::event_sender::PendingLuaEvents>()
+                    .ok_or_else(|| LuaError::RuntimeError("PendingLuaEvents resource not found".to_string()))?
+                    .clone();
+                pending.queue_event_with_priority(event_type_name, data, priority);
+                Ok(())
+            },
+        );
+
+        // send_events(event_type_name, array_of_tables) - queue many events with one PendingLuaEvents lock
+        // The batch is written by dispatch_lua_events; returns the number of events queued
+        methods.add_method("send_events", |lua, this, (event_type_name, events): (String, LuaTable)| {
+            let batch = lua_event_batch(lua, &events)?;
+            let pending = this
+                .world()
+                .get_resource::<crate::event_sender::PendingLuaEvents>()
+                .ok_or_else(|| LuaError::RuntimeError("PendingLuaEvents resource not found".to_string()))?
+                .clone();
+            Ok(pending.queue_events(&event_type_name, batch))
+        });
 
-            bevy::log::debug!(
-                "[WRITE_MESSAGE] Queueing message '{}': {:?}",
-                message_type_name, json_data
-            );
-            this.pending_messages.queue_message(message_type_name.clone(), json_data);
+        // send_events_deduped(event_type_name, array_of_tables) - like send_events, but events with
+        // identical field values within the batch are only sent once
+        methods.add_method("send_events_deduped", |lua, this, (event_type_name, events): (String, LuaTable)| {
+            let batch = lua_event_batch(lua, &events)?;
+            let pending = this
+                .world()
+                .get_resource::<crate::event_sender::PendingLuaEvents>()
+                .ok_or_else(|| LuaError::RuntimeError("PendingLuaEvents resource not found".to_string()))?
+                .clone();
+            Ok(pending.queue_events_deduped(&event_type_name, batch))
+        });
 
-            Ok(())
+        // write_message(message_type_name, data_table) - queue a message to be sent
+        // Messages use MessageWriter<M> instead of EventWriter<T>
+        // A copy of the table is kept in the Lua registry until dispatch (no JSON round-trip)
+        methods.add_method("write_message", |lua, this, (message_type_name, data_table): (String, LuaTable)| {
+            bevy::log::debug!("[WRITE_MESSAGE] Queueing message '{}'", message_type_name);
+            this.pending_messages
+                .queue_lua_message(lua, message_type_name, &data_table)
         });
 
         // send_message - alias for write_message
-        methods.add_method("send_message", |_lua, this, (message_type_name, data_table): (String, LuaTable)| {
-            // Re-use the same conversion logic
-            fn lua_to_json(value: &LuaValue) -> serde_json::Value {
-                match value {
-                    LuaValue::Nil => serde_json::Value::Null,
-                    LuaValue::Boolean(b) => serde_json::Value::Bool(*b),
-                    LuaValue::Integer(i) => serde_json::json!(*i),
-                    LuaValue::Number(n) => serde_json::json!(*n),
-                    LuaValue::String(s) => match s.to_str() {
-                        Ok(cow) => serde_json::Value::String(cow.to_string()),
-                        Err(_) => serde_json::Value::String(String::new()),
-                    },
-                    LuaValue::Table(t) => {
-                        let mut is_array = true;
-                        let mut max_key = 0i64;
-                        for pair in t.clone().pairs::<LuaValue, LuaValue>() {
-                            if let Ok((key, _)) = pair {
-                                match key {
-                                    LuaValue::Integer(i) if i > 0 => {
-                                        max_key = max_key.max(i);
-                                    }
-                                    _ => {
-                                        is_array = false;
-                                        break;
-                                    }
-                                }
-                            }
-                        }
-
-                        if is_array && max_key > 0 {
-                            let mut arr = Vec::new();
-                            for i in 1..=max_key {
-                                if let Ok(val) = t.get::<LuaValue>(i) {
-                                    arr.push(lua_to_json(&val));
-                                }
-                            }
-                            serde_json::Value::Array(arr)
-                        } else {
-                            let mut map = serde_json::Map::new();
-                            for pair in t.clone().pairs::<String, LuaValue>() {
-                                if let Ok((key, val)) = pair {
-                                    map.insert(key, lua_to_json(&val));
-                                }
-                            }
-                            serde_json::Value::Object(map)
-                        }
-                    }
-                    _ => serde_json::Value::Null,
-                }
-            }
-
-            let json_data = lua_to_json(&LuaValue::Table(data_table.clone()));
-            this.pending_messages.queue_message(message_type_name.clone(), json_data);
+        methods.add_method("send_message", |lua, this, (message_type_name, data_table): (String, LuaTable)| {
+            this.pending_messages
+                .queue_lua_message(lua, message_type_name, &data_table)
+        });
 
-            Ok(())
+        // get_system_profile(name) - { min_ms, max_ms, avg_ms, samples } over the system's last 60 runs
+        // Returns nil if profiling is off (BEVY_LUA_PROFILE) or the system hasn't run yet
+        methods.add_method("get_system_profile", |lua, this, system_name: String| {
+            let Some(profile) = this
+                .world()
+                .get_resource::<crate::lua_frame_budget::LuaSystemProfiles>()
+                .and_then(|profiles| profiles.profile(&system_name))
+            else {
+                return Ok(LuaValue::Nil);
+            };
+            let table = lua.create_table()?;
+            table.set("min_ms", profile.min_ms)?;
+            table.set("max_ms", profile.max_ms)?;
+            table.set("avg_ms", profile.avg_ms)?;
+            table.set("samples", profile.samples)?;
+            Ok(LuaValue::Table(table))
         });
 
         // profiler_stats() - get system timing data from Rust for accurate profiling
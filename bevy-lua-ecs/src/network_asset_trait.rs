@@ -6,7 +6,8 @@
 
 use bevy::prelude::*;
 use mlua::RegistryKey;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 /// Status of an asset download request
 #[derive(Clone, Debug, PartialEq)]
@@ -23,6 +24,78 @@ pub enum AssetDownloadStatus {
     Failed(String),
 }
 
+/// Byte-level progress of a single asset download
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AssetByteProgress {
+    pub downloaded: u64,
+    /// Total size in bytes, `None` if the server did not report a size
+    pub total: Option<u64>,
+}
+
+impl AssetByteProgress {
+    /// Completion percentage (0-100), `None` if the total size is unknown
+    pub fn percent(&self) -> Option<f32> {
+        match self.total {
+            Some(0) => Some(100.0),
+            Some(total) => Some((self.downloaded as f64 / total as f64 * 100.0).min(100.0) as f32),
+            None => None,
+        }
+    }
+}
+
+/// Resource tracking per-download progress, keyed by request ID
+///
+/// Two requests for the same path (e.g. a re-download after a hot reload) are tracked
+/// separately; lookups by path return the most recent request.
+/// Shared with `NetworkAssetLoader` (same underlying map), which updates it as chunks arrive.
+/// Readable from Lua via `world:get_download_progress(asset_path_or_request_id)`.
+#[derive(Resource, Clone, Default)]
+pub struct DownloadProgress {
+    progress: Arc<Mutex<HashMap<u64, (String, AssetByteProgress)>>>,
+}
+
+impl DownloadProgress {
+    /// Record the current progress of request `request_id` for `path`
+    pub fn update(&self, request_id: u64, path: &str, downloaded: u64, total: Option<u64>) {
+        self.progress.lock().unwrap().insert(
+            request_id,
+            (path.to_string(), AssetByteProgress { downloaded, total }),
+        );
+    }
+
+    /// Get the progress of a request, `None` if nothing has been reported for it
+    pub fn get_request(&self, request_id: u64) -> Option<AssetByteProgress> {
+        self.progress.lock().unwrap().get(&request_id).map(|(_, progress)| *progress)
+    }
+
+    /// Get the progress of the most recent request for a path
+    pub fn get(&self, path: &str) -> Option<AssetByteProgress> {
+        self.progress
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, (request_path, _))| request_path == path)
+            .max_by_key(|(request_id, _)| **request_id)
+            .map(|(_, (_, progress))| *progress)
+    }
+
+    /// Stop tracking a request (e.g. after it completed or failed)
+    pub fn clear(&self, request_id: u64) {
+        self.progress.lock().unwrap().remove(&request_id);
+    }
+}
+
+/// Retry bookkeeping for one download tracked by `NetworkAssetLoader`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DownloadRetryState {
+    /// Failed attempts so far
+    pub retry_count: u32,
+    /// `Time::elapsed_secs_f64` at which the next attempt is queued, `None` while an attempt is running
+    pub next_retry_at: Option<f64>,
+    /// Set once `max_retries` is exhausted; the download stays failed
+    pub failed: Option<String>,
+}
+
 /// Trait for requesting network asset downloads
 ///
 /// This trait is implemented by the application crate to provide
@@ -55,6 +128,10 @@ pub trait NetworkAssetRequestor: Send + Sync + 'static {
 
     /// Check if any coroutines are pending for a path
     fn has_pending_coroutines(&self, path: &str) -> bool;
+
+    /// Called when a chunk of a download arrives (optional)
+    /// `total` is `None` when the asset size is unknown
+    fn on_progress(&self, _request_id: u64, _path: &str, _downloaded: u64, _total: Option<u64>) {}
 }
 
 /// Resource that holds the network asset requestor implementation
@@ -65,6 +142,16 @@ pub trait NetworkAssetRequestor: Send + Sync + 'static {
 pub struct NetworkAssetLoader {
     /// The actual requestor implementation (boxed trait object)
     requestor: Arc<dyn NetworkAssetRequestor>,
+    /// Per-asset byte progress, mirrored into the `DownloadProgress` resource
+    progress: DownloadProgress,
+    /// Failed attempts retried before a download is marked failed for good
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled on every further failure
+    pub base_delay_ms: u64,
+    /// Upper bound for the retry delay
+    pub max_delay_ms: u64,
+    /// Retry state of downloads queued through this loader, keyed by asset path
+    retries: Arc<Mutex<HashMap<String, DownloadRetryState>>>,
 }
 
 impl NetworkAssetLoader {
@@ -72,16 +159,109 @@ impl NetworkAssetLoader {
     pub fn new<T: NetworkAssetRequestor>(requestor: T) -> Self {
         Self {
             requestor: Arc::new(requestor),
+            progress: DownloadProgress::default(),
+            max_retries: 3,
+            base_delay_ms: 500,
+            max_delay_ms: 10_000,
+            retries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Configure exponential backoff for failed downloads
+    pub fn with_retry(mut self, max_retries: u32, base_delay_ms: u64, max_delay_ms: u64) -> Self {
+        self.max_retries = max_retries;
+        self.base_delay_ms = base_delay_ms;
+        self.max_delay_ms = max_delay_ms;
+        self
+    }
+
+    /// Delay before retrying after `retry_count` earlier failures: `min(base * 2^retry_count, max)`
+    pub fn retry_delay_ms(&self, retry_count: u32) -> u64 {
+        2u64.checked_pow(retry_count)
+            .and_then(|factor| self.base_delay_ms.checked_mul(factor))
+            .unwrap_or(u64::MAX)
+            .min(self.max_delay_ms)
+    }
+
+    /// Retry state of a download, `None` if the path was never queued through this loader
+    pub fn retry_state(&self, path: &str) -> Option<DownloadRetryState> {
+        self.retries.lock().unwrap().get(path).cloned()
+    }
+
+    /// Schedule retries for failed downloads and re-queue the ones whose delay has passed
+    /// `now` is `Time::elapsed_secs_f64`
+    pub fn update_retries(&self, now: f64) {
+        let mut requeue = Vec::new();
+        {
+            let mut retries = self.retries.lock().unwrap();
+            retries.retain(|path, state| {
+                if state.failed.is_some() {
+                    return true;
+                }
+                if let Some(at) = state.next_retry_at {
+                    if now >= at {
+                        state.next_retry_at = None;
+                        requeue.push(path.clone());
+                    }
+                    return true;
+                }
+                match self.requestor.get_status(path) {
+                    AssetDownloadStatus::Failed(reason) if state.retry_count >= self.max_retries => {
+                        warn!(
+                            "[NETWORK_ASSET] {} failed after {} retries: {}",
+                            path, state.retry_count, reason
+                        );
+                        state.failed = Some(reason);
+                        true
+                    }
+                    AssetDownloadStatus::Failed(reason) => {
+                        let delay_ms = self.retry_delay_ms(state.retry_count);
+                        state.retry_count += 1;
+                        state.next_retry_at = Some(now + delay_ms as f64 / 1000.0);
+                        debug!(
+                            "[NETWORK_ASSET] {} failed ({}), retry {} in {}ms",
+                            path, reason, state.retry_count, delay_ms
+                        );
+                        true
+                    }
+                    // Finished downloads no longer need retry tracking
+                    AssetDownloadStatus::Complete | AssetDownloadStatus::LocalAvailable => false,
+                    AssetDownloadStatus::Pending | AssetDownloadStatus::Downloading(_) => true,
+                }
+            });
+        }
+
+        // Queue outside the lock; the requestor may call back into the loader
+        for path in requeue {
+            self.requestor.queue_download(&path);
         }
     }
 
+    /// Shared download progress tracker (same map as the `DownloadProgress` resource)
+    pub fn progress(&self) -> DownloadProgress {
+        self.progress.clone()
+    }
+
+    /// Report that `downloaded` bytes of request `request_id` for `path` have arrived
+    /// Updates `DownloadProgress` and forwards to the requestor's `on_progress`.
+    /// Called by the download loop for every received chunk.
+    pub fn report_progress(&self, request_id: u64, path: &str, downloaded: u64, total: Option<u64>) {
+        self.progress.update(request_id, path, downloaded, total);
+        self.requestor.on_progress(request_id, path, downloaded, total);
+    }
+
     /// Check if an asset is available locally
     pub fn is_available_locally(&self, path: &str) -> bool {
         self.requestor.is_available_locally(path)
     }
 
     /// Queue a download request
+    /// Starts fresh retry tracking for the path
     pub fn queue_download(&self, path: &str) -> u64 {
+        self.retries
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), DownloadRetryState::default());
         self.requestor.queue_download(path)
     }
 
@@ -96,7 +276,17 @@ impl NetworkAssetLoader {
     }
 
     /// Get download status
+    /// A download waiting for its next retry reports `Pending`; one that ran out of retries
+    /// reports `Failed` regardless of the requestor
     pub fn get_status(&self, path: &str) -> AssetDownloadStatus {
+        if let Some(state) = self.retries.lock().unwrap().get(path) {
+            if let Some(reason) = &state.failed {
+                return AssetDownloadStatus::Failed(reason.clone());
+            }
+            if state.next_retry_at.is_some() {
+                return AssetDownloadStatus::Pending;
+            }
+        }
         self.requestor.get_status(path)
     }
 
@@ -116,3 +306,111 @@ impl NetworkAssetLoader {
         self.requestor.has_pending_coroutines(path)
     }
 }
+
+/// Share the loader's progress tracker as the `DownloadProgress` resource
+/// Runs when a `NetworkAssetLoader` is inserted so Lua reads the same map the loader updates
+pub fn sync_download_progress_resource(mut commands: Commands, loader: Res<NetworkAssetLoader>) {
+    commands.insert_resource(loader.progress());
+}
+
+/// System that drives retry-with-backoff for failed network downloads
+pub fn retry_failed_downloads(loader: Res<NetworkAssetLoader>, time: Res<Time>) {
+    loader.update_retries(time.elapsed_secs_f64());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_progress_percent() {
+        let progress = DownloadProgress::default();
+        assert!(progress.get("a.png").is_none());
+
+        progress.update(1, "a.png", 25, Some(100));
+        assert_eq!(progress.get("a.png").unwrap().percent(), Some(25.0));
+
+        progress.update(2, "b.png", 512, None);
+        assert_eq!(progress.get("b.png").unwrap().percent(), None);
+
+        // A second request for the same path is tracked separately; the path shows the newest
+        progress.update(3, "a.png", 10, Some(200));
+        assert_eq!(progress.get("a.png").unwrap().percent(), Some(5.0));
+        assert_eq!(progress.get_request(1).unwrap().percent(), Some(25.0));
+
+        progress.clear(3);
+        assert_eq!(progress.get("a.png").unwrap().percent(), Some(25.0));
+        progress.clear(1);
+        assert!(progress.get("a.png").is_none());
+    }
+
+    /// Requestor whose status is set directly by the test
+    struct FakeRequestor {
+        status: Arc<Mutex<AssetDownloadStatus>>,
+        queued: Arc<Mutex<u32>>,
+    }
+
+    impl NetworkAssetRequestor for FakeRequestor {
+        fn is_available_locally(&self, _path: &str) -> bool {
+            false
+        }
+        fn queue_download(&self, _path: &str) -> u64 {
+            *self.queued.lock().unwrap() += 1;
+            *self.status.lock().unwrap() = AssetDownloadStatus::Pending;
+            0
+        }
+        fn is_download_complete(&self, _path: &str) -> bool {
+            false
+        }
+        fn take_downloaded_data(&self, _path: &str) -> Option<Vec<u8>> {
+            None
+        }
+        fn get_status(&self, _path: &str) -> AssetDownloadStatus {
+            self.status.lock().unwrap().clone()
+        }
+        fn register_pending_coroutine(&self, _path: &str, _key: Arc<RegistryKey>, _instance_id: u64) {}
+        fn has_pending_coroutines(&self, _path: &str) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_failed_downloads_retry_with_backoff() {
+        let status = Arc::new(Mutex::new(AssetDownloadStatus::Pending));
+        let queued = Arc::new(Mutex::new(0));
+        let loader = NetworkAssetLoader::new(FakeRequestor {
+            status: status.clone(),
+            queued: queued.clone(),
+        })
+        .with_retry(2, 100, 150);
+        assert_eq!(loader.retry_delay_ms(0), 100);
+        assert_eq!(loader.retry_delay_ms(1), 150);
+        assert_eq!(loader.retry_delay_ms(64), 150);
+
+        loader.queue_download("a.png");
+        let fail = || *status.lock().unwrap() = AssetDownloadStatus::Failed("timeout".to_string());
+
+        fail();
+        loader.update_retries(1.0);
+        let state = loader.retry_state("a.png").unwrap();
+        assert_eq!((state.retry_count, state.next_retry_at), (1, Some(1.1)));
+        assert_eq!(loader.get_status("a.png"), AssetDownloadStatus::Pending);
+
+        loader.update_retries(1.05);
+        assert_eq!(*queued.lock().unwrap(), 1);
+        loader.update_retries(1.1);
+        assert_eq!(*queued.lock().unwrap(), 2);
+
+        fail();
+        loader.update_retries(2.0);
+        assert_eq!(loader.retry_state("a.png").unwrap().next_retry_at, Some(2.15));
+        loader.update_retries(3.0);
+
+        fail();
+        loader.update_retries(4.0);
+        assert_eq!(loader.get_status("a.png"), AssetDownloadStatus::Failed("timeout".to_string()));
+        // Permanently failed downloads are not re-queued
+        loader.update_retries(100.0);
+        assert_eq!(*queued.lock().unwrap(), 3);
+    }
+}
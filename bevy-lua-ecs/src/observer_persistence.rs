@@ -0,0 +1,290 @@
+//! Keeps Lua observers on entities that survive a hot reload
+//!
+//! Runtime-phase entities outlive a reload of the script that spawned them, but their observer
+//! callbacks are closures from the old version of the script. Before the reload,
+//! `snapshot_instance_observers` takes those callbacks out of `LuaObserverRegistry` together with a
+//! fingerprint of each callback's source text. `restore_instance_observers` runs before the new
+//! version executes: callbacks whose source text appears unchanged in the new script are registered
+//! again, the others are released and reported so the new script can observe those entities again:
+//!
+//! ```lua
+//! for _, changed in ipairs(world:changed_observers()) do
+//!     world:get_entity(changed.entity):observe(changed.event_type, on_click)
+//! end
+//! ```
+//!
+//! Callbacks defined outside the reloaded script (e.g. in a required module) have no fingerprint
+//! and are always reported as changed.
+
+use crate::lua_observers::{LuaObserverCallback, LuaObserverRegistry};
+use crate::script_entities::{ScriptOwned, SpawnPhase};
+use bevy::prelude::*;
+use mlua::prelude::*;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+/// Fingerprint of a callback's source: a hash of the trimmed lines it spans
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CallbackFingerprint {
+    hash: u64,
+    lines: usize,
+}
+
+impl CallbackFingerprint {
+    fn of_lines<'a>(lines: impl Iterator<Item = &'a str>) -> Self {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        let mut count = 0;
+        for line in lines {
+            line.trim().hash(&mut hasher);
+            count += 1;
+        }
+        Self { hash: hasher.finish(), lines: count }
+    }
+
+    /// Fingerprint lines `first_line..=last_line` (1-based, as reported by Lua debug info)
+    pub fn from_source(source: &str, first_line: usize, last_line: usize) -> Option<Self> {
+        if first_line == 0 || last_line < first_line {
+            return None;
+        }
+        let lines: Vec<&str> = source.lines().collect();
+        let span = lines.get(first_line - 1..last_line)?;
+        Some(Self::of_lines(span.iter().copied()))
+    }
+
+    /// Whether the same lines appear anywhere in `source` (indentation and position may differ)
+    pub fn appears_in(&self, source: &str) -> bool {
+        let lines: Vec<&str> = source.lines().collect();
+        lines.len() >= self.lines
+            && lines
+                .windows(self.lines)
+                .any(|window| Self::of_lines(window.iter().copied()) == *self)
+    }
+}
+
+/// An observer taken off a surviving entity before its script reloaded
+struct SnapshotObserver {
+    entity: Entity,
+    event_type: String,
+    callback: LuaObserverCallback,
+    fingerprint: Option<CallbackFingerprint>,
+}
+
+/// Resource holding observer snapshots of script instances that are being hot-reloaded
+#[derive(Resource, Clone, Default)]
+pub struct PersistentObserverSnapshot {
+    snapshots: Arc<Mutex<HashMap<u64, Vec<SnapshotObserver>>>>,
+    /// Observers dropped by the last reload of each instance because their callback changed
+    changed: Arc<Mutex<HashMap<u64, Vec<(Entity, String)>>>>,
+}
+
+impl PersistentObserverSnapshot {
+    /// Observers of an instance whose callbacks changed in its last reload
+    pub fn changed_observers(&self, instance_id: u64) -> Vec<(Entity, String)> {
+        self.changed
+            .lock()
+            .unwrap()
+            .get(&instance_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Number of observers waiting to be restored
+    pub fn len(&self) -> usize {
+        self.snapshots.lock().unwrap().values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Fingerprint a callback if it was defined in the instance's own script
+fn fingerprint_callback(
+    lua: &Lua,
+    callback: &LuaObserverCallback,
+    script_path: Option<&std::path::Path>,
+    script_source: Option<&str>,
+) -> Option<CallbackFingerprint> {
+    let function: LuaFunction = lua.registry_value(&callback.key).ok()?;
+    let info = function.info();
+    // Chunk names are "@path", "=name" or the bare script name
+    let chunk = info.source?;
+    let chunk = chunk.trim_start_matches(['@', '=']);
+    let defined_in_script = std::path::Path::new(chunk).file_name() == script_path?.file_name();
+    if !defined_in_script {
+        return None;
+    }
+    CallbackFingerprint::from_source(script_source?, info.line_defined?, info.last_line_defined?)
+}
+
+/// Take the observers of an instance's surviving (Runtime-phase) entities out of the registry
+/// Call before the instance is cleaned up for a hot reload. `lua_for` returns the state that owns
+/// a callback's registry key (`LuaScriptContext::state_for_key`), so sandboxed instances work too.
+pub fn snapshot_instance_observers(world: &World, lua_for: impl Fn(&LuaRegistryKey) -> Arc<Lua>, instance_id: u64) {
+    let (Some(registry), Some(snapshot)) = (
+        world.get_resource::<LuaObserverRegistry>(),
+        world.get_resource::<PersistentObserverSnapshot>(),
+    ) else {
+        return;
+    };
+    let script_registry = world.get_resource::<crate::script_registry::ScriptRegistry>();
+    let script_path = script_registry.and_then(|scripts| scripts.get_instance_path(instance_id));
+    let script_source = script_registry.and_then(|scripts| scripts.get_instance_content(instance_id));
+
+    let survivors: Vec<Entity> = world
+        .try_query::<(Entity, &ScriptOwned)>()
+        .map(|mut query| {
+            query
+                .iter(world)
+                .filter(|(_, owned)| owned.instance_id == instance_id && owned.spawn_phase == SpawnPhase::Runtime)
+                .map(|(entity, _)| entity)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut taken = Vec::new();
+    for entity in survivors {
+        for (event_type, callback) in registry.take_callbacks(entity) {
+            let fingerprint = fingerprint_callback(
+                &lua_for(&callback.key),
+                &callback,
+                script_path.as_deref(),
+                script_source.as_deref(),
+            );
+            taken.push(SnapshotObserver { entity, event_type, callback, fingerprint });
+        }
+    }
+    debug!("[OBSERVER_PERSIST] Snapshotted {} observers of instance {}", taken.len(), instance_id);
+
+    // An earlier snapshot that was never restored (e.g. the reload failed) is released
+    if let Some(stale) = snapshot.snapshots.lock().unwrap().insert(instance_id, taken) {
+        for observer in stale {
+            let lua = lua_for(&observer.callback.key);
+            observer.callback.release(&lua);
+        }
+    }
+}
+
+/// Re-register snapshotted observers whose callback source is unchanged in `new_source`
+/// The others are released and listed by `changed_observers`. Returns the number restored.
+pub fn restore_instance_observers(
+    world: &World,
+    lua_for: impl Fn(&LuaRegistryKey) -> Arc<Lua>,
+    instance_id: u64,
+    new_source: &str,
+) -> usize {
+    let (Some(registry), Some(snapshot)) = (
+        world.get_resource::<LuaObserverRegistry>(),
+        world.get_resource::<PersistentObserverSnapshot>(),
+    ) else {
+        return 0;
+    };
+    let Some(observers) = snapshot.snapshots.lock().unwrap().remove(&instance_id) else {
+        return 0;
+    };
+
+    let mut restored = 0;
+    let mut changed = Vec::new();
+    for observer in observers {
+        if world.get_entity(observer.entity).is_err() {
+            let lua = lua_for(&observer.callback.key);
+            observer.callback.release(&lua);
+            continue;
+        }
+        if observer.fingerprint.is_some_and(|fingerprint| fingerprint.appears_in(new_source)) {
+            registry.add_observer(observer.entity, observer.event_type, observer.callback);
+            restored += 1;
+        } else {
+            changed.push((observer.entity, observer.event_type));
+            let lua = lua_for(&observer.callback.key);
+            observer.callback.release(&lua);
+        }
+    }
+    debug!(
+        "[OBSERVER_PERSIST] Instance {}: restored {} observers, {} changed",
+        instance_id,
+        restored,
+        changed.len()
+    );
+    snapshot.changed.lock().unwrap().insert(instance_id, changed);
+    restored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD_SCRIPT: &str = "local count = 0\n\
+        local function on_click(entity, event)\n\
+        \x20   count = count + 1\n\
+        end\n\
+        local function on_hover(entity, event)\n\
+        \x20   print('hover')\n\
+        end\n";
+
+    #[test]
+    fn test_fingerprint_survives_moves_but_not_edits() {
+        let on_click = CallbackFingerprint::from_source(OLD_SCRIPT, 2, 4).unwrap();
+        let moved = "-- new header\nlocal count = 0\n\n  local function on_click(entity, event)\n      count = count + 1\n  end\n";
+        assert!(on_click.appears_in(moved));
+        let edited = "local function on_click(entity, event)\n    count = count + 2\nend\n";
+        assert!(!on_click.appears_in(edited));
+        assert!(CallbackFingerprint::from_source(OLD_SCRIPT, 0, 2).is_none());
+        assert!(CallbackFingerprint::from_source(OLD_SCRIPT, 6, 40).is_none());
+    }
+
+    #[test]
+    fn test_restore_keeps_unchanged_callbacks_and_reports_changed_ones() {
+        // Callbacks live in a sandbox state, not the primary one
+        let primary = Arc::new(Lua::new());
+        let lua = Arc::new(Lua::new());
+        let lua_for = |key: &LuaRegistryKey| {
+            if primary.owns_registry_value(key) { primary.clone() } else { lua.clone() }
+        };
+        let mut world = World::new();
+        world.init_resource::<LuaObserverRegistry>();
+        world.init_resource::<PersistentObserverSnapshot>();
+        world.init_resource::<crate::script_registry::ScriptRegistry>();
+        let script_path = std::path::PathBuf::from("assets/scripts/buttons.lua");
+        world
+            .resource::<crate::script_registry::ScriptRegistry>()
+            .register_script(script_path, 7, OLD_SCRIPT.to_string());
+
+        let callbacks: LuaTable = lua
+            .load(format!("{}return {{ on_click = on_click, on_hover = on_hover }}", OLD_SCRIPT))
+            .set_name("@scripts/buttons.lua")
+            .eval()
+            .unwrap();
+        let owned = ScriptOwned { instance_id: 7, spawn_phase: SpawnPhase::Runtime, script_version: 1 };
+        let button = world.spawn(owned.clone()).id();
+        let script_phase = world.spawn(ScriptOwned { spawn_phase: SpawnPhase::Script, ..owned }).id();
+
+        let registry = world.resource::<LuaObserverRegistry>().clone();
+        for (entity, event_type, name) in [
+            (button, "Pointer<Click>", "on_click"),
+            (button, "Pointer<Over>", "on_hover"),
+            (script_phase, "Pointer<Click>", "on_click"),
+        ] {
+            let function: LuaFunction = callbacks.get(name).unwrap();
+            let key = lua.create_registry_value(function).unwrap();
+            registry.add_observer(entity, event_type.to_string(), LuaObserverCallback::new(key));
+        }
+
+        snapshot_instance_observers(&world, lua_for, 7);
+        assert!(registry.take_callbacks(button).is_empty());
+        assert_eq!(world.resource::<PersistentObserverSnapshot>().len(), 2);
+
+        // on_hover changed, on_click only moved
+        let new_script = "local count = 0\n\nlocal function on_click(entity, event)\n    count = count + 1\nend\n\
+            local function on_hover(entity, event)\n    print('hovering')\nend\n";
+        assert_eq!(restore_instance_observers(&world, lua_for, 7, new_script), 1);
+
+        let restored = registry.take_callbacks(button);
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].0, "Pointer<Click>");
+        let snapshot = world.resource::<PersistentObserverSnapshot>();
+        assert_eq!(snapshot.changed_observers(7), vec![(button, "Pointer<Over>".to_string())]);
+        assert!(snapshot.is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+//! Named Bevy one-shot systems callable from Lua
+//!
+//! ```rust,ignore
+//! fn heal_all_players(input: Res<LuaSystemInput>, mut players: Query<&mut Health, With<Player>>) {
+//!     let amount = input.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+//!     for mut health in &mut players {
+//!         health.current += amount;
+//!     }
+//! }
+//!
+//! register_lua_one_shot_system(app.world_mut(), "heal_all_players", heal_all_players);
+//! ```
+//!
+//! ```lua
+//! world:run_system("heal_all_players", { amount = 10 })
+//! ```
+//!
+//! The system runs immediately (inside the calling Lua system). The argument table is
+//! available to it as the `LuaSystemInput` resource for the duration of the call.
+
+use bevy::ecs::system::SystemId;
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Arguments passed from Lua to a one-shot system (JSON form of the Lua table)
+#[derive(Resource, Clone, Debug, Default)]
+pub struct LuaSystemInput(pub serde_json::Value);
+
+impl LuaSystemInput {
+    /// Look up a top-level argument
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.0.get(key)
+    }
+}
+
+/// Registry of one-shot systems Lua can run by name
+#[derive(Resource, Clone, Default)]
+pub struct OneShotSystemRegistry {
+    systems: Arc<Mutex<HashMap<String, SystemId>>>,
+}
+
+impl OneShotSystemRegistry {
+    /// Expose an already-registered system under `name`
+    pub fn register(&self, name: impl Into<String>, system_id: SystemId) {
+        self.systems.lock().unwrap().insert(name.into(), system_id);
+    }
+
+    /// Get the system registered under `name`
+    pub fn get(&self, name: &str) -> Option<SystemId> {
+        self.systems.lock().unwrap().get(name).copied()
+    }
+
+    /// Names of all registered systems (sorted)
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.systems.lock().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+/// Register `system` with the world as a one-shot system and expose it to Lua as `name`
+pub fn register_lua_one_shot_system<M>(
+    world: &mut World,
+    name: impl Into<String>,
+    system: impl IntoSystem<(), (), M> + 'static,
+) -> SystemId {
+    let system_id = world.register_system(system);
+    world
+        .get_resource_or_init::<OneShotSystemRegistry>()
+        .register(name, system_id);
+    system_id
+}
+
+/// Run the one-shot system registered as `name` with `input` available as `LuaSystemInput`
+/// Any previous `LuaSystemInput` is restored afterwards, so nested calls see their own input
+pub fn run_one_shot_system(world: &mut World, name: &str, input: serde_json::Value) -> Result<(), String> {
+    let system_id = world
+        .get_resource::<OneShotSystemRegistry>()
+        .and_then(|registry| registry.get(name))
+        .ok_or_else(|| format!("no one-shot system registered as '{}'", name))?;
+
+    let previous = world.remove_resource::<LuaSystemInput>();
+    world.insert_resource(LuaSystemInput(input));
+    let result = world.run_system(system_id);
+    match previous {
+        Some(previous) => world.insert_resource(previous),
+        None => {
+            world.remove_resource::<LuaSystemInput>();
+        }
+    }
+
+    result.map_err(|e| format!("one-shot system '{}' failed: {}", name, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Healed(f64);
+
+    fn heal(input: Res<LuaSystemInput>, mut healed: ResMut<Healed>) {
+        healed.0 += input.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0);
+    }
+
+    #[test]
+    fn test_run_one_shot_system_with_input() {
+        let mut world = World::new();
+        world.init_resource::<Healed>();
+        register_lua_one_shot_system(&mut world, "heal", heal);
+
+        run_one_shot_system(&mut world, "heal", serde_json::json!({ "amount": 10 })).unwrap();
+        run_one_shot_system(&mut world, "heal", serde_json::json!({ "amount": 5 })).unwrap();
+
+        assert_eq!(world.resource::<Healed>().0, 15.0);
+        assert!(world.get_resource::<LuaSystemInput>().is_none());
+        assert!(run_one_shot_system(&mut world, "missing", serde_json::Value::Null).is_err());
+    }
+}
@@ -1,9 +1,203 @@
 // Generic OS utilities for Lua scripts
 // These provide low-level OS operations that Lua can compose into higher-level functionality
 
+use bevy::prelude::*;
+use std::fs;
 use std::net::{SocketAddr, UdpSocket};
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Generic OS-level utilities for Lua (reusable across ANY game)
+/// File helpers exposed to Lua (read_file_bytes, write_file_bytes, copy_file, create_directory)
+/// and Lua's own file functions (io.open, dofile, os.remove, ...) go through this resource.
+/// Insert `OsUtilities::sandboxed("assets")` to confine them to a directory.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct OsUtilities {
+    /// When set, every path is resolved relative to this root and may not escape it
+    sandbox_root: Option<PathBuf>,
+    /// Shared by every clone, so the Lua states and the resource use one clipboard handle
+    clipboard: SystemClipboard,
+}
+
+impl OsUtilities {
+    /// Unrestricted file access (paths are used as given)
+    pub fn unrestricted() -> Self {
+        Self {
+            sandbox_root: None,
+            clipboard: SystemClipboard::default(),
+        }
+    }
+
+    /// Restrict file access to `root`; `..` segments and symlinks that would leave it are rejected
+    pub fn sandboxed(root: impl Into<PathBuf>) -> Self {
+        Self {
+            sandbox_root: Some(root.into()),
+            clipboard: SystemClipboard::default(),
+        }
+    }
+
+    pub fn sandbox_root(&self) -> Option<&Path> {
+        self.sandbox_root.as_deref()
+    }
+
+    /// Resolve a path from Lua. In sandbox mode the path is relative to the root
+    /// (a leading "/" also means the root) and must stay inside it, after following symlinks.
+    pub fn resolve_path(&self, path: &str) -> Result<PathBuf, String> {
+        let Some(root) = &self.sandbox_root else {
+            return Ok(PathBuf::from(path));
+        };
+        let outside = || format!("Path '{}' is outside the sandbox root '{}'", path, root.display());
+        let relative =
+            crate::path_utils::canonicalize_lua_path(Path::new(""), path).map_err(|_| outside())?;
+
+        // The root itself may not exist yet (it is created by the first write)
+        let canonical_root = canonicalize_existing_prefix(root)
+            .map_err(|e| format!("Failed to resolve sandbox root '{}': {}", root.display(), e))?;
+        let resolved = canonicalize_existing_prefix(&canonical_root.join(relative))
+            .map_err(|e| format!("Failed to resolve path '{}': {}", path, e))?;
+        if !resolved.starts_with(&canonical_root) {
+            return Err(outside());
+        }
+        Ok(resolved)
+    }
+
+    pub fn read_file(&self, path: &str) -> Result<Vec<u8>, String> {
+        let path = self.resolve_path(path)?;
+        fs::read(&path).map_err(|e| format!("Failed to read file: {}", e))
+    }
+
+    /// Write a file, creating parent directories as needed
+    pub fn write_file(&self, path: &str, data: &[u8]) -> Result<(), String> {
+        let path = self.resolve_path(path)?;
+        create_parent_dirs(&path)?;
+        fs::write(&path, data).map_err(|e| format!("Failed to write file: {}", e))
+    }
+
+    /// Copy a file, creating the destination's parent directories as needed
+    pub fn copy_file(&self, src: &str, dest: &str) -> Result<(), String> {
+        let src = self.resolve_path(src)?;
+        let dest = self.resolve_path(dest)?;
+        create_parent_dirs(&dest)?;
+        fs::copy(&src, &dest)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to copy file: {}", e))
+    }
+
+    pub fn create_directory(&self, path: &str) -> Result<(), String> {
+        let path = self.resolve_path(path)?;
+        fs::create_dir_all(&path).map_err(|e| format!("Failed to create directory: {}", e))
+    }
+
+    /// Text currently on the system clipboard
+    ///
+    /// | Platform                          | Support                                          |
+    /// |-----------------------------------|--------------------------------------------------|
+    /// | Windows, macOS, Linux, FreeBSD    | `arboard` (X11/Wayland on Linux)                 |
+    /// | wasm32 (`web-clipboard` feature)  | None - the browser only allows async reads       |
+    /// | Headless, consoles, mobile, other | None                                             |
+    ///
+    /// Returns None when there is no clipboard or it doesn't hold text.
+    pub fn read_clipboard(&self) -> Option<String> {
+        #[cfg(any(windows, target_os = "macos", target_os = "linux", target_os = "freebsd"))]
+        {
+            self.clipboard.with(|clipboard| clipboard.get_text())
+        }
+        #[cfg(not(any(windows, target_os = "macos", target_os = "linux", target_os = "freebsd")))]
+        {
+            None
+        }
+    }
+
+    /// Put text on the system clipboard
+    ///
+    /// | Platform                          | Support                                          |
+    /// |-----------------------------------|--------------------------------------------------|
+    /// | Windows, macOS, Linux, FreeBSD    | `arboard` (X11/Wayland on Linux)                 |
+    /// | wasm32 (`web-clipboard` feature)  | `navigator.clipboard.writeText` (fire and forget)|
+    /// | Headless, consoles, mobile, other | Not supported, returns false                     |
+    ///
+    /// Returns false when the text could not be written.
+    pub fn write_clipboard(&self, text: &str) -> bool {
+        #[cfg(any(windows, target_os = "macos", target_os = "linux", target_os = "freebsd"))]
+        {
+            self.clipboard.with(|clipboard| clipboard.set_text(text)).is_some()
+        }
+        #[cfg(all(target_arch = "wasm32", feature = "web-clipboard"))]
+        {
+            // The returned promise is dropped: the write completes (or is denied) asynchronously
+            web_sys::window()
+                .map(|window| {
+                    let _ = window.navigator().clipboard().write_text(text);
+                })
+                .is_some()
+        }
+        #[cfg(not(any(
+            windows,
+            target_os = "macos",
+            target_os = "linux",
+            target_os = "freebsd",
+            all(target_arch = "wasm32", feature = "web-clipboard")
+        )))]
+        {
+            let _ = text;
+            false
+        }
+    }
+}
+
+/// Desktop clipboard handle, opened on first use and kept open
+/// On X11 the clipboard owner serves the contents, so a handle dropped right after `set_text`
+/// can lose the write before another application reads it.
+#[derive(Clone, Default)]
+struct SystemClipboard {
+    #[cfg(any(windows, target_os = "macos", target_os = "linux", target_os = "freebsd"))]
+    handle: std::sync::Arc<std::sync::Mutex<Option<arboard::Clipboard>>>,
+}
+
+impl std::fmt::Debug for SystemClipboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SystemClipboard")
+    }
+}
+
+#[cfg(any(windows, target_os = "macos", target_os = "linux", target_os = "freebsd"))]
+impl SystemClipboard {
+    /// Run `op` on the clipboard, opening it first if needed
+    /// Returns None when no clipboard is available or the operation failed
+    fn with<T>(&self, op: impl FnOnce(&mut arboard::Clipboard) -> Result<T, arboard::Error>) -> Option<T> {
+        let mut handle = self.handle.lock().unwrap_or_else(|e| e.into_inner());
+        if handle.is_none() {
+            *handle = arboard::Clipboard::new().ok();
+        }
+        op(handle.as_mut()?).ok()
+    }
+}
+
+/// Canonicalize the longest part of `path` that exists and append the rest unchanged
+/// Files that don't exist yet (write targets) still have every symlink above them followed.
+/// A dangling symlink is an error rather than a missing file, so writes can't go through it.
+fn canonicalize_existing_prefix(path: &Path) -> std::io::Result<PathBuf> {
+    let mut existing = path;
+    let mut missing = Vec::new();
+    while fs::symlink_metadata(existing).is_err() {
+        let (Some(parent), Some(name)) = (existing.parent(), existing.file_name()) else {
+            break;
+        };
+        missing.push(name);
+        existing = parent;
+    }
+    let mut resolved = fs::canonicalize(existing)?;
+    resolved.extend(missing.into_iter().rev());
+    Ok(resolved)
+}
+
+fn create_parent_dirs(path: &Path) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    Ok(())
+}
+
 /// Bind a UDP socket to the given address
 /// Returns the socket on success, or an error message
 pub fn bind_udp_socket(addr: &str) -> Result<UdpSocket, String> {
@@ -35,6 +229,47 @@ mod tests {
         assert!(parse_socket_addr("invalid").is_err());
     }
 
+    #[test]
+    fn test_sandbox_rejects_path_traversal() {
+        let root = tempfile::tempdir().unwrap();
+        let os = OsUtilities::sandboxed(root.path());
+
+        assert!(os.resolve_path("../../etc/passwd").is_err());
+        assert!(os.read_file("../../etc/passwd").is_err());
+        assert!(os.write_file("saves/../../outside.txt", b"x").is_err());
+        assert!(!root.path().parent().unwrap().join("outside.txt").exists());
+
+        // Paths inside the root work, and a leading "/" is rooted at the sandbox
+        os.write_file("saves/slot1.dat", b"data").unwrap();
+        assert_eq!(os.read_file("/saves/slot1.dat").unwrap(), b"data");
+        let canonical_root = root.path().canonicalize().unwrap();
+        assert_eq!(os.resolve_path("/etc/passwd").unwrap(), canonical_root.join("etc/passwd"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sandbox_rejects_symlinks_out_of_the_root() {
+        let root = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        fs::write(outside.path().join("secret.txt"), b"secret").unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.path().join("link")).unwrap();
+        std::os::unix::fs::symlink(outside.path().join("new.txt"), root.path().join("dangling"))
+            .unwrap();
+        let os = OsUtilities::sandboxed(root.path());
+
+        assert!(os.read_file("link/secret.txt").is_err());
+        assert!(os.write_file("link/created.txt", b"x").is_err());
+        assert!(os.write_file("dangling", b"x").is_err());
+        assert!(!outside.path().join("created.txt").exists());
+        assert!(!outside.path().join("new.txt").exists());
+
+        // Symlinks that stay inside the root are fine
+        fs::create_dir(root.path().join("saves")).unwrap();
+        std::os::unix::fs::symlink(root.path().join("saves"), root.path().join("alias")).unwrap();
+        os.write_file("alias/slot.dat", b"data").unwrap();
+        assert_eq!(os.read_file("saves/slot.dat").unwrap(), b"data");
+    }
+
     #[test]
     fn test_current_time() {
         let time = current_time_millis();
@@ -5,7 +5,7 @@
 /// - HashMap/HashSet lookups (paths used as keys)
 /// - Network transmission (client/server may be on different platforms)
 /// - Script module resolution (require() paths must match across platforms)
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Normalize path separators to forward slashes.
 /// This is the primary function for converting paths to a canonical string format
@@ -45,6 +45,49 @@ pub fn normalize_path(path: &str) -> String {
     parts.join("/")
 }
 
+/// Resolve a script path relative to a base directory, purely in memory.
+/// Both paths are relative to the `assets/` root; `..` and `.` segments are resolved
+/// without touching the filesystem, so this works for virtual/network asset paths.
+/// A `relative` path starting with `/` is resolved from the assets root instead of `base`.
+///
+/// Returns an error if the path escapes the `assets/` root.
+///
+/// # Examples
+/// ```
+/// use bevy_lua_ecs::path_utils::{canonicalize_lua_path, to_forward_slash};
+/// use std::path::Path;
+/// let path = canonicalize_lua_path(Path::new("scripts/examples"), "../shared/utils.lua").unwrap();
+/// assert_eq!(to_forward_slash(&path), "scripts/shared/utils.lua");
+/// assert!(canonicalize_lua_path(Path::new("scripts"), "../../secret.lua").is_err());
+/// ```
+pub fn canonicalize_lua_path(base: &Path, relative: &str) -> Result<PathBuf, String> {
+    let relative = normalize_path_separators(relative);
+    let joined = if relative.starts_with('/') {
+        relative.clone()
+    } else {
+        format!("{}/{}", to_forward_slash(base), relative)
+    };
+
+    let mut parts: Vec<&str> = Vec::new();
+    for part in joined.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                if parts.pop().is_none() {
+                    return Err(format!(
+                        "Path '{}' (relative to '{}') escapes the assets/ root",
+                        relative,
+                        to_forward_slash(base)
+                    ));
+                }
+            }
+            _ => parts.push(part),
+        }
+    }
+
+    Ok(PathBuf::from(parts.join("/")))
+}
+
 /// Convert a Path/PathBuf to a forward-slash string.
 /// This is useful when you have a Path type and need a normalized string.
 ///
@@ -58,6 +101,29 @@ pub fn to_forward_slash<P: AsRef<Path>>(path: P) -> String {
     normalize_path_separators(&path.as_ref().to_string_lossy())
 }
 
+/// Convert an OS-native path to the form `AssetServer` expects: forward slashes, relative to
+/// the `assets/` directory (the asset server adds that prefix itself).
+/// Applying it to its own output returns the same string.
+///
+/// # Examples
+/// ```
+/// use bevy_lua_ecs::path_utils::to_asset_server_path;
+/// assert_eq!(to_asset_server_path("assets\\scripts\\examples\\physics.lua"), "scripts/examples/physics.lua");
+/// assert_eq!(to_asset_server_path("./assets/images/player.png"), "images/player.png");
+/// assert_eq!(to_asset_server_path("scripts/main.lua"), "scripts/main.lua");
+/// ```
+pub fn to_asset_server_path(path: impl AsRef<Path>) -> String {
+    let path = to_forward_slash(path);
+    let mut relative = path.as_str();
+    while let Some(rest) = relative
+        .strip_prefix("./")
+        .or_else(|| relative.strip_prefix("assets/"))
+    {
+        relative = rest;
+    }
+    relative.to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,6 +161,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_canonicalize_lua_path() {
+        let base = Path::new("scripts/examples");
+        assert_eq!(
+            to_forward_slash(canonicalize_lua_path(base, "../shared/utils.lua").unwrap()),
+            "scripts/shared/utils.lua"
+        );
+        assert_eq!(
+            to_forward_slash(canonicalize_lua_path(base, "./mod.lua").unwrap()),
+            "scripts/examples/mod.lua"
+        );
+        assert_eq!(
+            to_forward_slash(canonicalize_lua_path(base, "..\\..\\top.lua").unwrap()),
+            "top.lua"
+        );
+        assert_eq!(
+            to_forward_slash(canonicalize_lua_path(base, "/scripts/root.lua").unwrap()),
+            "scripts/root.lua"
+        );
+        assert!(canonicalize_lua_path(base, "../../../outside.lua").is_err());
+        assert!(canonicalize_lua_path(Path::new(""), "../outside.lua").is_err());
+    }
+
+    #[test]
+    fn test_to_asset_server_path() {
+        assert_eq!(to_asset_server_path("assets\\scripts\\test.lua"), "scripts/test.lua");
+        assert_eq!(to_asset_server_path(Path::new("assets").join("scripts").join("test.lua")), "scripts/test.lua");
+        assert_eq!(to_asset_server_path("./assets/./images/a.png"), "images/a.png");
+        assert_eq!(to_asset_server_path("scripts/assets/a.lua"), "scripts/assets/a.lua");
+        assert_eq!(to_asset_server_path("assets"), "assets");
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_to_asset_server_path_is_idempotent(
+            path in r"(\./|assets[/\\]){0,3}([a-zA-Z0-9_. -]{1,8}[/\\]){0,4}[a-zA-Z0-9_. -]{0,8}"
+        ) {
+            let once = to_asset_server_path(&path);
+            proptest::prop_assert_eq!(to_asset_server_path(&once), once.clone());
+            proptest::prop_assert!(!once.contains('\\'));
+            proptest::prop_assert!(!once.starts_with("assets/"));
+        }
+    }
+
     #[test]
     fn test_to_forward_slash() {
         use std::path::PathBuf;
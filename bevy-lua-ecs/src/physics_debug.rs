@@ -0,0 +1,103 @@
+//! Rapier debug rendering controlled from Lua
+//!
+//! ```lua
+//! world:physics_debug_draw(false)              -- hide collider outlines
+//! world:physics_debug_color(player, 0, 1, 0)   -- draw the player's collider in green
+//! ```
+//!
+//! Needs the `physics` feature (which pulls in `bevy_rapier2d`) and `RapierDebugRenderPlugin`
+//! on the app; without the feature both methods raise a Lua error explaining the requirement.
+//! Changes are queued and applied by `process_physics_debug_queue`, like hierarchy changes.
+
+use bevy::prelude::*;
+use mlua::prelude::*;
+
+/// A queued change to Rapier debug rendering
+#[derive(Clone, Debug, PartialEq)]
+pub enum PhysicsDebugOp {
+    /// Turn debug rendering on or off for the whole world
+    SetEnabled(bool),
+    /// Override the debug color of an entity's collider (raw id: temp id or entity bits)
+    SetColor(u64, Color),
+}
+
+#[cfg(feature = "physics")]
+pub use enabled::{process_physics_debug_queue, PhysicsDebugQueue};
+
+#[cfg(feature = "physics")]
+mod enabled {
+    use super::PhysicsDebugOp;
+    use crate::spawn_queue::SpawnQueue;
+    use bevy::prelude::*;
+    use bevy_rapier2d::render::{ColliderDebugColor, DebugRenderContext};
+    use std::sync::{Arc, Mutex};
+
+    /// Queue of debug rendering changes from Lua
+    #[derive(Resource, Clone, Default)]
+    pub struct PhysicsDebugQueue {
+        queue: Arc<Mutex<Vec<PhysicsDebugOp>>>,
+    }
+
+    impl PhysicsDebugQueue {
+        pub fn queue(&self, op: PhysicsDebugOp) {
+            self.queue.lock().unwrap().push(op);
+        }
+
+        fn drain(&self) -> Vec<PhysicsDebugOp> {
+            self.queue.lock().unwrap().drain(..).collect()
+        }
+    }
+
+    /// System that applies queued debug rendering changes
+    pub fn process_physics_debug_queue(
+        mut commands: Commands,
+        queue: Res<PhysicsDebugQueue>,
+        spawn_queue: Res<SpawnQueue>,
+        mut debug_context: Option<ResMut<DebugRenderContext>>,
+        entities: Query<()>,
+    ) {
+        for op in queue.drain() {
+            match op {
+                PhysicsDebugOp::SetEnabled(enabled) => match debug_context.as_mut() {
+                    Some(context) => context.enabled = enabled,
+                    None => warn!("[PHYSICS_DEBUG] DebugRenderContext not found - add RapierDebugRenderPlugin"),
+                },
+                PhysicsDebugOp::SetColor(entity_id, color) => {
+                    let entity = spawn_queue.resolve_entity(entity_id);
+                    if !entities.contains(entity) {
+                        warn!("[PHYSICS_DEBUG] Ignoring debug color for missing entity {}", entity_id);
+                        continue;
+                    }
+                    commands.entity(entity).insert(ColliderDebugColor(color.into()));
+                }
+            }
+        }
+    }
+}
+
+/// Queue a debug rendering change from a Lua method
+#[cfg(feature = "physics")]
+pub(crate) fn queue_from_lua(world: &World, method: &str, op: PhysicsDebugOp) -> LuaResult<()> {
+    if matches!(op, PhysicsDebugOp::SetEnabled(_))
+        && world.get_resource::<bevy_rapier2d::render::DebugRenderContext>().is_none()
+    {
+        return Err(LuaError::RuntimeError(format!(
+            "{}: RapierDebugRenderPlugin is not added to the app",
+            method
+        )));
+    }
+    world
+        .get_resource::<PhysicsDebugQueue>()
+        .ok_or_else(|| LuaError::RuntimeError("PhysicsDebugQueue resource not found".to_string()))?
+        .queue(op);
+    Ok(())
+}
+
+/// Without the `physics` feature there is no Rapier to configure
+#[cfg(not(feature = "physics"))]
+pub(crate) fn queue_from_lua(_world: &World, method: &str, _op: PhysicsDebugOp) -> LuaResult<()> {
+    Err(LuaError::RuntimeError(format!(
+        "{}: physics debug rendering requires bevy_rapier2d - enable the `physics` feature of bevy-lua-ecs",
+        method
+    )))
+}
@@ -0,0 +1,360 @@
+//! Named entity templates (prefabs) spawnable from Lua
+//!
+//! Prefabs are registered from Rust with reflected components, or from Lua with a
+//! component table, and spawned by name:
+//!
+//! ```lua
+//! world:register_prefab("enemy_goblin", {
+//!     Transform = { translation = { x = 0, y = 0, z = 0 } },
+//!     Health = { current = 100, max = 100 },
+//! })
+//!
+//! -- Override tables are merged field-by-field into the prefab's components
+//! world:spawn_prefab("enemy_goblin", { Health = { current = 50 } })
+//!
+//! -- Any existing entity can serve as the template too (prototype-based spawn)
+//! world:spawn_inherit(goblin, { Transform = { translation = { x = 5 } } })
+//! ```
+
+use bevy::prelude::*;
+use mlua::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A registered entity template
+pub enum Prefab {
+    /// Components registered from Rust (converted to Lua tables at spawn time)
+    Reflected(Vec<Box<dyn Reflect>>),
+    /// Component table registered from Lua: { ComponentName = data, ... }
+    Lua(Arc<LuaRegistryKey>),
+}
+
+/// Resource holding all named prefabs
+#[derive(Resource, Clone, Default)]
+pub struct PrefabRegistry {
+    prefabs: Arc<Mutex<HashMap<String, Prefab>>>,
+}
+
+impl PrefabRegistry {
+    /// Register (or replace) a prefab built from reflected Rust components
+    pub fn register(&self, name: impl Into<String>, components: Vec<Box<dyn Reflect>>) {
+        let name = name.into();
+        debug!("[PREFAB] Registered '{}' ({} components)", name, components.len());
+        self.prefabs
+            .lock()
+            .unwrap()
+            .insert(name, Prefab::Reflected(components));
+    }
+
+    /// Register (or replace) a prefab from a Lua component table
+    pub fn register_lua(&self, lua: &Lua, name: impl Into<String>, components: LuaTable) -> LuaResult<()> {
+        let name = name.into();
+        let key = Arc::new(lua.create_registry_value(components)?);
+        debug!("[PREFAB] Registered Lua prefab '{}'", name);
+        self.prefabs.lock().unwrap().insert(name, Prefab::Lua(key));
+        Ok(())
+    }
+
+    /// Check whether a prefab is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.prefabs.lock().unwrap().contains_key(name)
+    }
+
+    /// Remove a prefab. Returns true if it existed.
+    pub fn remove(&self, name: &str) -> bool {
+        self.prefabs.lock().unwrap().remove(name).is_some()
+    }
+
+    /// Build a fresh component list for a prefab, with `overrides` merged in
+    /// Each call produces new tables, so spawned entities never share component data
+    pub fn instantiate(
+        &self,
+        lua: &Lua,
+        name: &str,
+        overrides: Option<&LuaTable>,
+    ) -> LuaResult<Vec<(String, LuaValue)>> {
+        let mut components: Vec<(String, LuaValue)> = {
+            let prefabs = self.prefabs.lock().unwrap();
+            let prefab = prefabs.get(name).ok_or_else(|| {
+                LuaError::RuntimeError(format!("Prefab '{}' is not registered", name))
+            })?;
+
+            match prefab {
+                Prefab::Reflected(reflected) => reflected
+                    .iter()
+                    .map(|component| {
+                        let component_name = component.reflect_short_type_path().to_string();
+                        crate::lua_world_api::reflection_to_lua(lua, component.as_partial_reflect())
+                            .map(|value| (component_name, value))
+                    })
+                    .collect::<LuaResult<_>>()?,
+                Prefab::Lua(key) => {
+                    let table: LuaTable = lua.registry_value(key)?;
+                    let mut components = Vec::new();
+                    for pair in table.pairs::<String, LuaValue>() {
+                        let (component_name, value) = pair?;
+                        components.push((component_name, deep_copy_lua_value(lua, &value)?));
+                    }
+                    components
+                }
+            }
+        };
+
+        if let Some(overrides) = overrides {
+            merge_component_overrides(lua, &mut components, overrides)?;
+        }
+
+        Ok(components)
+    }
+}
+
+/// Build a component list copying every reflected component (and Lua component) of `parent`,
+/// with `overrides` merged in. This is prototype-based spawning, not Bevy's parent/child
+/// hierarchy, so `ChildOf`/`Children` are not copied, and neither are components Bevy computes
+/// (`GlobalTransform`, `InheritedVisibility`, `ViewVisibility`). Asset handles are copied as
+/// their asset paths. Components without `ReflectComponent` can't be read generically and are
+/// skipped with a warning listing them.
+pub fn inherit_components(
+    lua: &Lua,
+    world: &World,
+    parent: Entity,
+    overrides: Option<&LuaTable>,
+) -> LuaResult<Vec<(String, LuaValue)>> {
+    let entity_ref = world
+        .get_entity(parent)
+        .map_err(|_| LuaError::RuntimeError(format!("spawn_inherit: entity {:?} does not exist", parent)))?;
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let asset_registry = world.get_resource::<crate::asset_loading::AssetRegistry>();
+    let skipped = [
+        std::any::TypeId::of::<ChildOf>(),
+        std::any::TypeId::of::<Children>(),
+        std::any::TypeId::of::<GlobalTransform>(),
+        std::any::TypeId::of::<InheritedVisibility>(),
+        std::any::TypeId::of::<ViewVisibility>(),
+        std::any::TypeId::of::<crate::script_entities::ScriptOwned>(),
+        std::any::TypeId::of::<crate::components::LuaCustomComponents>(),
+    ];
+
+    let mut components = Vec::new();
+    let mut not_reflected = Vec::new();
+    for component_id in entity_ref.archetype().components() {
+        let Some(info) = world.components().get_info(component_id) else {
+            continue;
+        };
+        if info.type_id().is_some_and(|type_id| skipped.contains(&type_id)) {
+            continue;
+        }
+        let registration = info.type_id().and_then(|type_id| type_registry.get(type_id));
+        let Some((registration, reflect_component)) = registration
+            .and_then(|registration| Some((registration, registration.data::<ReflectComponent>()?)))
+        else {
+            not_reflected.push(info.name().to_string());
+            continue;
+        };
+        let Some(component) = reflect_component.reflect(entity_ref) else {
+            continue;
+        };
+        let component_name = registration.type_info().type_path_table().short_path().to_string();
+        let value = crate::lua_world_api::reflection_to_lua_with_assets(
+            lua,
+            component.as_partial_reflect(),
+            asset_registry,
+        )?;
+        components.push((component_name, value));
+    }
+    if !not_reflected.is_empty() {
+        warn!(
+            "[PREFAB] spawn_inherit skipped components without ReflectComponent: {}",
+            not_reflected.join(", ")
+        );
+    }
+
+    // Lua components are copied so the new entity never shares tables with its template
+    if let Some(lua_components) = entity_ref.get::<crate::components::LuaCustomComponents>() {
+        for (component_name, key) in &lua_components.components {
+            let Ok(value) = lua.registry_value::<LuaValue>(key) else {
+                continue;
+            };
+            components.push((component_name.clone(), deep_copy_lua_value(lua, &value)?));
+        }
+    }
+
+    if let Some(overrides) = overrides {
+        merge_component_overrides(lua, &mut components, overrides)?;
+    }
+    Ok(components)
+}
+
+/// Merge an override table ({ ComponentName = data, ... }) into a component list
+fn merge_component_overrides(
+    lua: &Lua,
+    components: &mut Vec<(String, LuaValue)>,
+    overrides: &LuaTable,
+) -> LuaResult<()> {
+    for pair in overrides.pairs::<String, LuaValue>() {
+        let (component_name, override_value) = pair?;
+        match components.iter_mut().find(|(name, _)| *name == component_name) {
+            Some((_, value)) => merge_lua_values(lua, value, override_value)?,
+            None => components.push((component_name, override_value)),
+        }
+    }
+    Ok(())
+}
+
+/// Deep copy a Lua value (tables are copied recursively, metatables are shared)
+pub fn deep_copy_lua_value(lua: &Lua, value: &LuaValue) -> LuaResult<LuaValue> {
+    match value {
+        LuaValue::Table(table) => {
+            let copy = lua.create_table()?;
+            for pair in table.pairs::<LuaValue, LuaValue>() {
+                let (key, value) = pair?;
+                copy.set(key, deep_copy_lua_value(lua, &value)?)?;
+            }
+            if let Some(metatable) = table.metatable() {
+                copy.set_metatable(Some(metatable));
+            }
+            Ok(LuaValue::Table(copy))
+        }
+        other => Ok(other.clone()),
+    }
+}
+
+/// Merge `override_value` into `base`: tables merge field-by-field, anything else replaces
+pub(crate) fn merge_lua_values(lua: &Lua, base: &mut LuaValue, override_value: LuaValue) -> LuaResult<()> {
+    match (&*base, override_value) {
+        (LuaValue::Table(base_table), LuaValue::Table(override_table)) => {
+            for pair in override_table.pairs::<LuaValue, LuaValue>() {
+                let (key, value) = pair?;
+                let mut field: LuaValue = base_table.raw_get(key.clone())?;
+                if matches!(field, LuaValue::Table(_)) && matches!(value, LuaValue::Table(_)) {
+                    merge_lua_values(lua, &mut field, value)?;
+                    base_table.raw_set(key, field)?;
+                } else {
+                    base_table.raw_set(key, deep_copy_lua_value(lua, &value)?)?;
+                }
+            }
+            Ok(())
+        }
+        (_, value) => {
+            *base = value;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lua_prefab_overrides_merge() {
+        let lua = Lua::new();
+        let registry = PrefabRegistry::default();
+        let template: LuaTable = lua
+            .load("return { Health = { current = 100, max = 100 }, Marker = {} }")
+            .eval()
+            .unwrap();
+        registry.register_lua(&lua, "goblin", template).unwrap();
+
+        let overrides: LuaTable = lua.load("return { Health = { current = 50 } }").eval().unwrap();
+        let components = registry.instantiate(&lua, "goblin", Some(&overrides)).unwrap();
+        let (_, health) = components.iter().find(|(name, _)| name == "Health").unwrap();
+        let LuaValue::Table(health) = health else {
+            panic!("Health should be a table");
+        };
+        assert_eq!(health.get::<i64>("current").unwrap(), 50);
+        assert_eq!(health.get::<i64>("max").unwrap(), 100);
+
+        // The registered template is untouched
+        let fresh = registry.instantiate(&lua, "goblin", None).unwrap();
+        let (_, health) = fresh.iter().find(|(name, _)| name == "Health").unwrap();
+        let LuaValue::Table(health) = health else {
+            panic!("Health should be a table");
+        };
+        assert_eq!(health.get::<i64>("current").unwrap(), 100);
+
+        assert!(registry.instantiate(&lua, "missing", None).is_err());
+    }
+
+    #[derive(Component)]
+    struct Unreflected;
+
+    #[test]
+    fn test_inherit_components_copies_reflected_components() {
+        let lua = Lua::new();
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Transform>();
+            registry.register::<Name>();
+        }
+        let parent = world.spawn_empty().id();
+        let template = world
+            .spawn((Transform::from_xyz(1.0, 2.0, 3.0), Name::new("goblin"), Unreflected, ChildOf(parent)))
+            .id();
+
+        let overrides: LuaTable = lua
+            .load("return { Transform = { translation = { x = 5 } } }")
+            .eval()
+            .unwrap();
+        let components = inherit_components(&lua, &world, template, Some(&overrides)).unwrap();
+        let mut names: Vec<&str> = components.iter().map(|(name, _)| name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["Name", "Transform"]);
+
+        let (_, transform) = components.iter().find(|(name, _)| name == "Transform").unwrap();
+        let LuaValue::Table(transform) = transform else {
+            panic!("Transform should be a table");
+        };
+        let translation: LuaTable = transform.get("translation").unwrap();
+        assert_eq!(translation.get::<f32>("x").unwrap(), 5.0);
+        assert_eq!(translation.get::<f32>("y").unwrap(), 2.0);
+
+        world.despawn(template);
+        assert!(inherit_components(&lua, &world, template, None).is_err());
+    }
+
+    #[test]
+    fn test_inherit_components_copies_handles_as_paths() {
+        let lua = Lua::new();
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Sprite>();
+            registry.register::<Transform>();
+            registry.register::<GlobalTransform>();
+            registry.register::<InheritedVisibility>();
+            registry.register::<ViewVisibility>();
+        }
+        let mut images = Assets::<Image>::default();
+        let image = images.add(Image::default());
+        let asset_registry = crate::asset_loading::AssetRegistry::new();
+        asset_registry.register_handle_extractor::<Image>();
+        let id = asset_registry.register_path("textures/goblin.png".to_string());
+        asset_registry.register_typed_handle(id, image.clone().untyped());
+        world.insert_resource(asset_registry);
+
+        let template = world
+            .spawn((
+                Sprite::from_image(image),
+                Transform::default(),
+                GlobalTransform::default(),
+                InheritedVisibility::VISIBLE,
+                ViewVisibility::default(),
+            ))
+            .id();
+        let components = inherit_components(&lua, &world, template, None).unwrap();
+        let mut names: Vec<&str> = components.iter().map(|(name, _)| name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["Sprite", "Transform"], "computed components should not be copied");
+
+        let (_, sprite) = components.iter().find(|(name, _)| name == "Sprite").unwrap();
+        let LuaValue::Table(sprite) = sprite else {
+            panic!("Sprite should be a table");
+        };
+        assert_eq!(sprite.get::<String>("image").unwrap(), "textures/goblin.png");
+    }
+}
@@ -0,0 +1,318 @@
+//! Paged queries for large result sets
+//!
+//! ```lua
+//! local result = world:query_page({"Enemy"}, 1, 100)   -- { entities = {...}, total = n, has_more = bool }
+//!
+//! world:query_all_async({"Enemy"}, function(entities, page)
+//!     for _, e in ipairs(entities) do ... end
+//!     -- return false to stop early
+//! end, 100)
+//! ```
+//!
+//! `world:query_iter({"Enemy"})` streams matches one snapshot at a time instead (see
+//! `QueryIterState`).
+//!
+//! `query_page` only builds snapshots for the requested page. The matching entity list is kept
+//! in `QueryCursor` between calls and rebuilt when page 1 is requested (starting a new pass) or
+//! the world may have changed since: new archetypes, a different entity count, or a new change
+//! tick (entities that gained or lost components move between existing archetypes without
+//! changing either of the others). `query_all_async` takes the entity list once and hands one
+//! page per frame to the callback.
+
+use bevy::ecs::archetype::ArchetypeGeneration;
+use bevy::ecs::component::Tick;
+use bevy::prelude::*;
+use mlua::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::component_update_queue::ComponentUpdateQueue;
+use crate::components::ComponentRegistry;
+use crate::lua_world_api::{collect_query_entities, snapshot_entity};
+
+/// Default page size for `world:query_all_async`
+pub const DEFAULT_ASYNC_PAGE_SIZE: usize = 100;
+
+/// World state a cached entity list was collected in
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct CursorStamp {
+    generation: ArchetypeGeneration,
+    entity_count: u32,
+    change_tick: Tick,
+}
+
+impl CursorStamp {
+    fn of(world: &World) -> Self {
+        Self {
+            generation: world.archetypes().generation(),
+            entity_count: world.entities().len(),
+            change_tick: world.read_change_tick(),
+        }
+    }
+}
+
+/// Matching entities cached for one (with, without) query
+struct CursorEntry {
+    stamp: CursorStamp,
+    entities: Arc<Vec<Entity>>,
+}
+
+/// A `query_all_async` pass in progress
+struct AsyncQueryJob {
+    with_components: Vec<String>,
+    entities: Vec<Entity>,
+    next_page: usize,
+    page_size: usize,
+    callback: Arc<LuaRegistryKey>,
+    state_id: usize,
+    instance_id: u64,
+}
+
+/// Cached matches for paged queries, plus pending `query_all_async` passes
+#[derive(Resource, Clone, Default)]
+pub struct QueryCursor {
+    entries: Arc<Mutex<HashMap<(Vec<String>, Vec<String>), CursorEntry>>>,
+    jobs: Arc<Mutex<Vec<AsyncQueryJob>>>,
+}
+
+impl QueryCursor {
+    /// Entities matching the query, reusing the cached list while the world is unchanged (see module docs)
+    /// `restart` forces a fresh collection (used for page 1)
+    pub fn matching_entities(
+        &self,
+        world: &World,
+        with_components: &[String],
+        without_components: &[String],
+        component_registry: &ComponentRegistry,
+        query_cache: Option<&crate::query_cache::LuaQueryCache>,
+        restart: bool,
+    ) -> Arc<Vec<Entity>> {
+        let stamp = CursorStamp::of(world);
+        let key = (with_components.to_vec(), without_components.to_vec());
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(entry) = entries.get(&key) {
+            if !restart && entry.stamp == stamp {
+                return entry.entities.clone();
+            }
+        }
+
+        let entities = Arc::new(collect_query_entities(
+            world,
+            with_components,
+            without_components,
+            component_registry,
+            query_cache,
+        ));
+        entries.insert(key, CursorEntry { stamp, entities: entities.clone() });
+        entities
+    }
+
+    /// Queue a `query_all_async` pass over `entities`
+    pub fn queue_async(
+        &self,
+        with_components: Vec<String>,
+        entities: Vec<Entity>,
+        page_size: usize,
+        callback: Arc<LuaRegistryKey>,
+        state_id: usize,
+        instance_id: u64,
+    ) {
+        self.jobs.lock().unwrap().push(AsyncQueryJob {
+            with_components,
+            entities,
+            next_page: 1,
+            page_size,
+            callback,
+            state_id,
+            instance_id,
+        });
+    }
+
+    /// Number of `query_all_async` passes still running
+    pub fn pending_async(&self) -> usize {
+        self.jobs.lock().unwrap().len()
+    }
+}
+
+/// Position of one `world:query_iter` iterator: the next archetype/entity index to look at
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct QueryIterCursor {
+    /// Frame the iterator was created in; it stops yielding after that frame
+    pub frame: u64,
+    pub archetype: usize,
+    pub entity: usize,
+}
+
+/// Cursors of the `world:query_iter` iterators created this frame, keyed by iterator id
+/// Archetypes only grow while a Lua system holds `&World`, so the indices stay valid within a frame.
+#[derive(Resource, Clone, Default)]
+pub struct QueryIterState {
+    cursors: Arc<Mutex<HashMap<u64, QueryIterCursor>>>,
+    next_id: Arc<Mutex<u64>>,
+}
+
+impl QueryIterState {
+    /// Start a new iterator at the first archetype; cursors left over from earlier frames are dropped
+    pub fn start(&self, frame: u64) -> u64 {
+        let mut cursors = self.cursors.lock().unwrap();
+        cursors.retain(|_, cursor| cursor.frame == frame);
+        let mut next_id = self.next_id.lock().unwrap();
+        *next_id += 1;
+        cursors.insert(*next_id, QueryIterCursor { frame, ..default() });
+        *next_id
+    }
+
+    /// Advance iterator `id` with `f`; None if it finished or belongs to another frame
+    pub fn advance<R>(&self, id: u64, frame: u64, f: impl FnOnce(&mut QueryIterCursor) -> R) -> Option<R> {
+        let mut cursors = self.cursors.lock().unwrap();
+        let cursor = cursors.get_mut(&id)?;
+        if cursor.frame != frame {
+            cursors.remove(&id);
+            return None;
+        }
+        Some(f(cursor))
+    }
+
+    /// Forget iterator `id` (it was exhausted)
+    pub fn finish(&self, id: u64) {
+        self.cursors.lock().unwrap().remove(&id);
+    }
+
+    /// Number of iterators that have not finished
+    pub fn active(&self) -> usize {
+        self.cursors.lock().unwrap().len()
+    }
+}
+
+/// Index range of a 1-based `page` and whether more pages follow
+pub fn page_range(total: usize, page: usize, page_size: usize) -> (std::ops::Range<usize>, bool) {
+    let start = page.saturating_sub(1).saturating_mul(page_size).min(total);
+    let end = start.saturating_add(page_size).min(total);
+    (start..end, end < total)
+}
+
+/// Build the Lua array of snapshots for `entities[range]`, skipping despawned entities
+pub fn page_snapshots(
+    lua: &Lua,
+    world: &World,
+    entities: &[Entity],
+    range: std::ops::Range<usize>,
+    with_components: &[String],
+    component_registry: &ComponentRegistry,
+    update_queue: &ComponentUpdateQueue,
+    query_cache: Option<&crate::query_cache::LuaQueryCache>,
+    asset_registry: Option<&crate::asset_loading::AssetRegistry>,
+) -> LuaResult<LuaTable> {
+    let table = lua.create_table()?;
+    for entity in &entities[range] {
+        if let Some(snapshot) = snapshot_entity(
+            lua,
+            world,
+            *entity,
+            with_components,
+            component_registry,
+            update_queue,
+            query_cache,
+            asset_registry,
+        )? {
+            table.push(snapshot)?;
+        }
+    }
+    Ok(table)
+}
+
+/// System that hands the next page of each `query_all_async` pass to its callback
+pub fn process_async_queries(world: &World) {
+    let Some(cursor) = world.get_resource::<QueryCursor>() else {
+        return;
+    };
+    let jobs = std::mem::take(&mut *cursor.jobs.lock().unwrap());
+    if jobs.is_empty() {
+        return;
+    }
+    let (Some(lua_ctx), Some(component_registry), Some(update_queue)) = (
+        world.get_resource::<crate::lua_integration::LuaScriptContext>(),
+        world.get_resource::<ComponentRegistry>(),
+        world.get_resource::<ComponentUpdateQueue>(),
+    ) else {
+        return;
+    };
+    let query_cache = world.get_resource::<crate::query_cache::LuaQueryCache>();
+    let asset_registry = world.get_resource::<crate::asset_loading::AssetRegistry>();
+
+    let mut still_running = Vec::new();
+    for mut job in jobs {
+        let lua = lua_ctx.get_lua_state(job.state_id);
+        // Run the callback as the script that started the pass, then put the caller's id back
+        let previous_instance_id: LuaValue = lua.globals().get("__INSTANCE_ID__").unwrap_or(LuaValue::Nil);
+        let _ = lua.globals().set("__INSTANCE_ID__", job.instance_id);
+
+        let (range, has_more) = page_range(job.entities.len(), job.next_page, job.page_size);
+        let result = page_snapshots(
+            &lua,
+            world,
+            &job.entities,
+            range,
+            &job.with_components,
+            component_registry,
+            update_queue,
+            query_cache,
+            asset_registry,
+        )
+        .and_then(|entities| {
+            let page = lua.create_table()?;
+            page.set("page", job.next_page)?;
+            page.set("total", job.entities.len())?;
+            page.set("has_more", has_more)?;
+            let callback: LuaFunction = lua.registry_value(&job.callback)?;
+            callback.call::<Option<bool>>((entities, page))
+        });
+        let _ = lua.globals().set("__INSTANCE_ID__", previous_instance_id);
+
+        match result {
+            // Returning false from the callback stops the pass
+            Ok(Some(false)) => {}
+            Ok(_) if has_more => {
+                job.next_page += 1;
+                still_running.push(job);
+            }
+            Ok(_) => {}
+            Err(e) => error!("[QUERY_ASYNC] Callback for {:?} failed: {}", job.with_components, e),
+        }
+    }
+
+    // Keep passes queued by callbacks this frame after the ones still running
+    let mut jobs = cursor.jobs.lock().unwrap();
+    still_running.append(&mut jobs);
+    *jobs = still_running;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_iter_state_expires_cursors_from_earlier_frames() {
+        let state = QueryIterState::default();
+        let first = state.start(3);
+        let second = state.start(3);
+        assert_ne!(first, second);
+        assert_eq!(state.advance(first, 3, |cursor| { cursor.entity += 1; cursor.entity }), Some(1));
+        assert_eq!(state.advance(first, 3, |cursor| cursor.entity), Some(1));
+
+        // A later frame can't resume either iterator, and starting a new one drops them
+        assert_eq!(state.advance(first, 4, |_| ()), None);
+        state.start(4);
+        assert_eq!(state.active(), 1);
+        assert_eq!(state.advance(second, 3, |_| ()), None);
+    }
+
+    #[test]
+    fn test_page_range() {
+        assert_eq!(page_range(250, 1, 100), (0..100, true));
+        assert_eq!(page_range(250, 3, 100), (200..250, false));
+        assert_eq!(page_range(200, 2, 100), (100..200, false));
+        assert_eq!(page_range(10, 5, 100), (10..10, false));
+        assert_eq!(page_range(0, 1, 100), (0..0, false));
+    }
+}
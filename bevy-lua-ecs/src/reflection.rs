@@ -1,10 +1,58 @@
 use bevy::prelude::*;
 #[cfg(feature = "auto-reflection")]
 use bevy::reflect::{Reflect, StructInfo, TypeInfo, TypeRegistry};
-use bevy::reflect::{PartialReflect, ReflectRef};
+use bevy::reflect::{GetPath, PartialReflect, ReflectFromPtr, ReflectMut, ReflectRef};
 use mlua::prelude::*;
 use std::collections::HashMap;
 
+/// Fast path for glam math types: build `{x, y, z, ...}` straight from the concrete value
+/// instead of walking its fields through reflection. Returns None for any other type
+/// (including dynamic values such as `DynamicStruct`), so callers fall back to the generic path.
+pub fn math_to_lua(lua: &Lua, value: &dyn PartialReflect) -> LuaResult<Option<LuaValue>> {
+    fn table(lua: &Lua, fields: &[(&str, f32)]) -> LuaResult<Option<LuaValue>> {
+        let table = lua.create_table_with_capacity(0, fields.len())?;
+        for (name, v) in fields {
+            table.raw_set(*name, *v)?;
+        }
+        Ok(Some(LuaValue::Table(table)))
+    }
+
+    if let Some(v) = value.try_downcast_ref::<Vec3>() {
+        table(lua, &[("x", v.x), ("y", v.y), ("z", v.z)])
+    } else if let Some(v) = value.try_downcast_ref::<Vec2>() {
+        table(lua, &[("x", v.x), ("y", v.y)])
+    } else if let Some(q) = value.try_downcast_ref::<Quat>() {
+        table(lua, &[("x", q.x), ("y", q.y), ("z", q.z), ("w", q.w)])
+    } else if let Some(v) = value.try_downcast_ref::<Vec4>() {
+        table(lua, &[("x", v.x), ("y", v.y), ("z", v.z), ("w", v.w)])
+    } else {
+        Ok(None)
+    }
+}
+
+/// Fast path for writing a Lua table into a glam math field
+/// Missing components default to 0 (`w` of a Quat to 1). Returns false when `field` isn't
+/// a Vec2/Vec3/Vec4/Quat or `value` isn't a table, leaving it to the generic path.
+pub fn lua_to_math(field: &mut dyn PartialReflect, value: &LuaValue) -> bool {
+    let LuaValue::Table(table) = value else {
+        return false;
+    };
+    let get = |name: &str, default: f32| table.get::<Option<f32>>(name).ok().flatten().unwrap_or(default);
+
+    if let Some(v) = field.try_downcast_mut::<Vec3>() {
+        *v = Vec3::new(get("x", 0.0), get("y", 0.0), get("z", 0.0));
+    } else if let Some(v) = field.try_downcast_mut::<Vec2>() {
+        *v = Vec2::new(get("x", 0.0), get("y", 0.0));
+    } else if let Some(q) = field.try_downcast_mut::<Quat>() {
+        *q = Quat::from_xyzw(get("x", 0.0), get("y", 0.0), get("z", 0.0), get("w", 1.0));
+    } else if let Some(v) = field.try_downcast_mut::<Vec4>() {
+        *v = Vec4::new(get("x", 0.0), get("y", 0.0), get("z", 0.0), get("w", 0.0));
+    } else {
+        return false;
+    }
+    true
+}
+
 /// Convert any reflected Bevy value to a Lua value
 /// - Entity → u64 bits (use world:get_entity(bits) to get entity wrapper)
 /// - Structs → Lua tables with field names as keys
@@ -33,6 +81,9 @@ pub fn reflect_to_lua_value(lua: &Lua, value: &dyn PartialReflect) -> LuaResult<
     
     match value.reflect_ref() {
         ReflectRef::Struct(s) => {
+            if let Some(math) = math_to_lua(lua, value)? {
+                return Ok(math);
+            }
             let table = lua.create_table()?;
             for i in 0..s.field_len() {
                 if let (Some(name), Some(field)) = (s.name_at(i), s.field_at(i)) {
@@ -290,9 +341,14 @@ pub fn lua_value_to_type<T: bevy::reflect::Reflect + 'static>(
                 mlua::Value::Table(ref t) => {
                     let rfr = registration.data::<ReflectFromReflect>()
                         .ok_or_else(|| format!("Type '{}' has no FromReflect", std::any::type_name::<T>()))?;
-                    let dynamic = crate::lua_table_to_dynamic(lua, t, type_info, type_registry)
-                        .map_err(|e| format!("Failed to build '{}': {}", std::any::type_name::<T>(), e))?;
-                    let concrete = rfr.from_reflect(&dynamic)
+                    let dynamic: Box<dyn bevy::reflect::PartialReflect> = if let TypeInfo::TupleStruct(_) = type_info {
+                        Box::new(crate::lua_table_to_dynamic_tuple_struct(lua, t, type_info, type_registry, None)
+                            .map_err(|e| format!("Failed to build '{}': {}", std::any::type_name::<T>(), e))?)
+                    } else {
+                        Box::new(crate::lua_table_to_dynamic(lua, t, type_info, type_registry)
+                            .map_err(|e| format!("Failed to build '{}': {}", std::any::type_name::<T>(), e))?)
+                    };
+                    let concrete = rfr.from_reflect(dynamic.as_ref())
                         .ok_or_else(|| format!("FromReflect failed for '{}'", std::any::type_name::<T>()))?;
                     concrete.downcast::<T>()
                         .map(|b| *b)
@@ -448,10 +504,22 @@ pub struct BundleDefinition {
     pub spawn_fn: Box<dyn Fn(&LuaValue, &mut EntityCommands) -> LuaResult<()> + Send + Sync>,
 }
 
+/// Builds a fresh default value of a reflected bundle struct
+type BundleTemplateFn = Box<dyn Fn() -> Box<dyn Reflect> + Send + Sync>;
+
 /// Registry of available bundles for Lua
+///
+/// Reflected bundles registered with `register_reflected` can be spawned by name:
+/// ```lua
+/// spawn({ bundle = "PhysicsBody", velocity = { x = 1, y = 0 } })
+/// ```
+/// Each field of the bundle struct becomes a component. Other keys in the spawn table
+/// override a bundle component when they match its field name (`velocity`) or component
+/// name (`Velocity`) and are merged field-by-field; anything else is spawned as usual.
 #[derive(Resource, Default)]
 pub struct BundleRegistry {
     bundles: HashMap<String, BundleDefinition>,
+    templates: HashMap<String, BundleTemplateFn>,
 }
 
 impl BundleRegistry {
@@ -475,6 +543,67 @@ impl BundleRegistry {
         self.bundles.get(name)
     }
 
+    /// Register a reflected bundle struct whose fields are all components
+    /// `spawn({ bundle = name, ... })` starts from `B::default()`
+    pub fn register_reflected<B: Reflect + Default>(&mut self, name: impl Into<String>) {
+        self.templates
+            .insert(name.into(), Box::new(|| Box::new(B::default()) as Box<dyn Reflect>));
+    }
+
+    /// Check whether a reflected bundle is registered under `name`
+    pub fn has_reflected(&self, name: &str) -> bool {
+        self.templates.contains_key(name)
+    }
+
+    /// Expand a reflected bundle into `(component name, data)` pairs with `overrides` merged in
+    /// Overrides are matched by bundle field name or component name; unmatched ones are appended
+    pub fn instantiate(
+        &self,
+        lua: &Lua,
+        name: &str,
+        overrides: Vec<(String, LuaValue)>,
+    ) -> LuaResult<Vec<(String, LuaValue)>> {
+        let template = self.templates.get(name).ok_or_else(|| {
+            LuaError::RuntimeError(format!("Bundle '{}' is not registered", name))
+        })?;
+        let bundle = template();
+        let ReflectRef::Struct(bundle_struct) = bundle.reflect_ref() else {
+            return Err(LuaError::RuntimeError(format!(
+                "Bundle '{}' ({}) is not a struct",
+                name,
+                bundle.reflect_type_path()
+            )));
+        };
+
+        // (field name, component name, data)
+        let mut fields = Vec::new();
+        for index in 0..bundle_struct.field_len() {
+            let (Some(field_name), Some(field)) = (bundle_struct.name_at(index), bundle_struct.field_at(index)) else {
+                continue;
+            };
+            let component_name = field.reflect_short_type_path().to_string();
+            let data = crate::lua_world_api::reflection_to_lua(lua, field)?;
+            fields.push((field_name.to_string(), component_name, data));
+        }
+
+        let mut extra = Vec::new();
+        for (key, value) in overrides {
+            match fields
+                .iter_mut()
+                .find(|(field_name, component_name, _)| *field_name == key || *component_name == key)
+            {
+                Some((_, _, data)) => crate::prefab_registry::merge_lua_values(lua, data, value)?,
+                None => extra.push((key, value)),
+            }
+        }
+
+        Ok(fields
+            .into_iter()
+            .map(|(_, component_name, data)| (component_name, data))
+            .chain(extra)
+            .collect())
+    }
+
     /// Create registry from TypeRegistry using reflection (auto-reflection feature)
     #[cfg(feature = "auto-reflection")]
     pub fn from_type_registry(type_registry: &AppTypeRegistry) -> Self {
@@ -529,6 +658,95 @@ fn spawn_from_reflection(
     ))
 }
 
+/// Write a single field of a reflected component in place, without cloning the component
+///
+/// `field_path` is dot-separated (e.g. `"translation.x"`). The target field must be an
+/// opaque/primitive value (`ReflectMut::Opaque`); structs, lists etc. are rejected so
+/// callers don't accidentally overwrite a whole sub-tree.
+pub fn reflect_field_mut(
+    world: &mut World,
+    entity: Entity,
+    component_name: &str,
+    field_path: &str,
+    new_value: &LuaValue,
+) -> LuaResult<()> {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let asset_registry = world
+        .get_resource::<crate::asset_loading::AssetRegistry>()
+        .cloned();
+
+    let (component_id, reflect_from_ptr) = {
+        let registry = type_registry.read();
+        let registration = registry
+            .get_with_type_path(component_name)
+            .or_else(|| registry.get_with_short_type_path(component_name))
+            .ok_or_else(|| {
+                LuaError::RuntimeError(format!("Component '{}' is not registered", component_name))
+            })?;
+        if registration.data::<ReflectComponent>().is_none() {
+            return Err(LuaError::RuntimeError(format!(
+                "Type '{}' is not a reflected Component",
+                component_name
+            )));
+        }
+        let reflect_from_ptr = registration
+            .data::<ReflectFromPtr>()
+            .cloned()
+            .ok_or_else(|| {
+                LuaError::RuntimeError(format!("Component '{}' has no ReflectFromPtr", component_name))
+            })?;
+        let component_id = world
+            .components()
+            .get_id(registration.type_id())
+            .ok_or_else(|| {
+                LuaError::RuntimeError(format!(
+                    "Component '{}' is not present on entity {:?}",
+                    component_name, entity
+                ))
+            })?;
+        (component_id, reflect_from_ptr)
+    };
+
+    let mut entity_mut = world
+        .get_entity_mut(entity)
+        .map_err(|_| LuaError::RuntimeError(format!("Entity {:?} does not exist", entity)))?;
+    let mut component_ptr = entity_mut.get_mut_by_id(component_id).map_err(|_| {
+        LuaError::RuntimeError(format!(
+            "Component '{}' is not present on entity {:?}",
+            component_name, entity
+        ))
+    })?;
+
+    // SAFETY: component_id and reflect_from_ptr come from the same type registration
+    let component = unsafe { reflect_from_ptr.as_reflect_mut(component_ptr.as_mut()) };
+    let field = component
+        .as_partial_reflect_mut()
+        .reflect_path_mut(field_path)
+        .map_err(|e| {
+            LuaError::RuntimeError(format!(
+                "Invalid field path '{}' on {}: {}",
+                field_path, component_name, e
+            ))
+        })?;
+
+    if !matches!(field.reflect_mut(), ReflectMut::Opaque(_)) {
+        return Err(LuaError::RuntimeError(format!(
+            "Field '{}' on {} is not a value field ({}); use a path to a primitive field",
+            field_path,
+            component_name,
+            field.reflect_type_path()
+        )));
+    }
+
+    crate::components::set_field_from_lua(
+        field,
+        new_value,
+        asset_registry.as_ref(),
+        &type_registry,
+        Some(field_path),
+    )
+}
+
 /// Trait for types that can be spawned from Lua
 pub trait LuaSpawnable {
     fn from_lua(data: &LuaTable, entity: &mut EntityCommands) -> LuaResult<()>;
@@ -595,3 +813,91 @@ pub fn lua_table_to_reflect(
         ))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Reflect, Default)]
+    struct TestBody {
+        transform: Transform,
+        visibility: Visibility,
+    }
+
+    #[test]
+    fn test_reflected_bundle_instantiate_with_overrides() {
+        let lua = Lua::new();
+        let mut registry = BundleRegistry::default();
+        registry.register_reflected::<TestBody>("TestBody");
+        assert!(registry.has_reflected("TestBody"));
+
+        let overrides: LuaTable = lua
+            .load("return { transform = { translation = { x = 3 } }, Marker = { tag = 'a' } }")
+            .eval()
+            .unwrap();
+        let overrides = overrides.pairs::<String, LuaValue>().collect::<LuaResult<Vec<_>>>().unwrap();
+        let components = registry.instantiate(&lua, "TestBody", overrides).unwrap();
+        let names: Vec<&str> = components.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["Transform", "Visibility", "Marker"]);
+
+        let LuaValue::Table(transform) = &components[0].1 else {
+            panic!("Transform should be a table");
+        };
+        let translation: LuaTable = transform.get("translation").unwrap();
+        assert_eq!(translation.get::<f32>("x").unwrap(), 3.0);
+        assert_eq!(translation.get::<f32>("y").unwrap(), 0.0);
+
+        assert!(registry.instantiate(&lua, "Missing", Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_reflect_field_mut_sets_value_field() {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Transform>();
+        world.insert_resource(registry);
+        let entity = world.spawn(Transform::default()).id();
+
+        reflect_field_mut(&mut world, entity, "Transform", "translation.x", &LuaValue::Number(5.0))
+            .unwrap();
+        assert_eq!(world.get::<Transform>(entity).unwrap().translation.x, 5.0);
+
+        // Struct fields are not value fields
+        assert!(
+            reflect_field_mut(&mut world, entity, "Transform", "translation", &LuaValue::Number(1.0))
+                .is_err()
+        );
+        assert!(
+            reflect_field_mut(&mut world, entity, "Transform", "missing", &LuaValue::Number(1.0))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_math_fast_paths_round_trip() {
+        let lua = Lua::new();
+        let value = math_to_lua(&lua, &Vec3::new(1.0, 2.0, 3.0)).unwrap().unwrap();
+        let mut out = Vec3::ZERO;
+        assert!(lua_to_math(&mut out, &value));
+        assert_eq!(out, Vec3::new(1.0, 2.0, 3.0));
+
+        let value = math_to_lua(&lua, &Quat::from_rotation_z(0.5)).unwrap().unwrap();
+        let mut out = Quat::IDENTITY;
+        assert!(lua_to_math(&mut out, &value));
+        assert_eq!(out, Quat::from_rotation_z(0.5));
+
+        // Missing fields use the defaults; Quat.w defaults to 1
+        let partial: LuaValue = lua.load("{ x = 4 }").eval().unwrap();
+        let mut out = Vec4::ONE;
+        assert!(lua_to_math(&mut out, &partial));
+        assert_eq!(out, Vec4::new(4.0, 0.0, 0.0, 0.0));
+        let mut out = Quat::from_rotation_x(1.0);
+        assert!(lua_to_math(&mut out, &lua.load("{}").eval().unwrap()));
+        assert_eq!(out, Quat::IDENTITY);
+
+        // Dynamic values and other types use the generic path
+        assert!(math_to_lua(&lua, Vec3::ONE.to_dynamic().as_ref()).unwrap().is_none());
+        assert!(math_to_lua(&lua, &Transform::default()).unwrap().is_none());
+        assert!(!lua_to_math(&mut 1.0f32, &partial));
+    }
+}
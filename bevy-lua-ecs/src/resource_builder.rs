@@ -10,12 +10,18 @@ pub type ResourceBuilderFn = Arc<dyn Fn(&Lua, LuaValue, &mut World) -> LuaResult
 /// A resource removal function that removes a resource from the World
 pub type ResourceRemovalFn = Arc<dyn Fn(&mut World) + Send + Sync>;
 
+/// A resource mutator that changes a live resource in place (builder-style setters)
+/// Receives the reflected resource and the argument table from Lua
+pub type ResourceMutatorFn = Arc<dyn Fn(&mut dyn Reflect, LuaTable) + Send + Sync>;
+
 /// Registry for resource builders
 /// This is GENERIC - works for ANY resource type
 #[derive(Resource, Clone)]
 pub struct ResourceBuilderRegistry {
     builders: Arc<Mutex<HashMap<String, ResourceBuilderFn>>>,
     removers: Arc<Mutex<HashMap<String, ResourceRemovalFn>>>,
+    /// (resource type name, method name) -> mutator
+    mutators: Arc<Mutex<HashMap<(String, String), ResourceMutatorFn>>>,
 }
 
 impl Default for ResourceBuilderRegistry {
@@ -23,6 +29,7 @@ impl Default for ResourceBuilderRegistry {
         Self {
             builders: Arc::new(Mutex::new(HashMap::new())),
             removers: Arc::new(Mutex::new(HashMap::new())),
+            mutators: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -51,6 +58,71 @@ impl ResourceBuilderRegistry {
             .insert(name.into(), Arc::new(remover));
     }
 
+    /// Register a mutator for an existing resource
+    /// Lua calls it with `world:mutate_resource(type_name, method_name, args)`
+    pub fn register_mutator<F>(
+        &self,
+        type_name: impl Into<String>,
+        method_name: impl Into<String>,
+        mutator: F,
+    ) where
+        F: Fn(&mut dyn Reflect, LuaTable) + Send + Sync + 'static,
+    {
+        self.mutators
+            .lock()
+            .unwrap()
+            .insert((type_name.into(), method_name.into()), Arc::new(mutator));
+    }
+
+    /// Check if a mutator is registered for a resource type and method
+    pub fn has_mutator(&self, type_name: &str, method_name: &str) -> bool {
+        self.mutators
+            .lock()
+            .unwrap()
+            .contains_key(&(type_name.to_string(), method_name.to_string()))
+    }
+
+    /// Run a registered mutator on the live resource in `world`
+    /// The resource is looked up through `ReflectResource` (short or full type path) and
+    /// mutated through `reflect_mut`, so change detection sees it as re-inserted
+    /// Returns None if no mutator is registered for this type/method
+    pub fn try_mutate(
+        &self,
+        type_name: &str,
+        method_name: &str,
+        args: LuaTable,
+        world: &mut World,
+    ) -> Option<LuaResult<()>> {
+        let mutator = self
+            .mutators
+            .lock()
+            .unwrap()
+            .get(&(type_name.to_string(), method_name.to_string()))
+            .cloned()?;
+
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = type_registry.read();
+        let Some(reflect_resource) = registry
+            .get_with_short_type_path(type_name)
+            .or_else(|| registry.get_with_type_path(type_name))
+            .and_then(|registration| registration.data::<ReflectResource>())
+        else {
+            return Some(Err(LuaError::RuntimeError(format!(
+                "Resource '{}' is not a registered reflected resource",
+                type_name
+            ))));
+        };
+        let Ok(mut resource) = reflect_resource.reflect_mut(world) else {
+            return Some(Err(LuaError::RuntimeError(format!(
+                "Resource '{}' does not exist in the world",
+                type_name
+            ))));
+        };
+
+        mutator(resource.as_reflect_mut(), args);
+        Some(Ok(()))
+    }
+
     /// Try to build and insert a resource from Lua data
     /// Returns None if no builder is registered for this type
     pub fn try_build(
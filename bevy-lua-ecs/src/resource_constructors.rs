@@ -3,29 +3,42 @@ use mlua::prelude::*;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
-/// Generic OS-level utilities for Lua (reusable across ANY game)
-/// These wrap OS operations that Lua cannot do directly
-pub struct OsUtilities;
-
-impl OsUtilities {
-    // Reserved for future generic utilities
-}
+pub use crate::os_utilities::OsUtilities;
 
 /// Type for constructor functions that create resources from Lua data
 /// Takes Lua context, data table, and returns a boxed reflected resource
 type ConstructorFn = Arc<dyn Fn(&Lua, LuaValue) -> LuaResult<Box<dyn Reflect>> + Send + Sync>;
 
+/// Named factory contributed by a plugin: builds a resource without arguments
+pub type ResourceFactory = Arc<dyn Fn() -> Box<dyn Reflect> + Send + Sync>;
+
+/// Named factory that builds a resource from a Lua parameter table
+pub type ParamResourceFactory = Arc<dyn Fn(LuaTable) -> Result<Box<dyn Reflect>, String> + Send + Sync>;
+
 /// Registry for resource constructor functions
 /// This is GENERIC - works for ANY resource type that needs construction
+///
+/// Plugins contribute factories from a startup system:
+/// ```ignore
+/// fn register_my_plugin(registry: Res<ResourceConstructorRegistry>) {
+///     registry.register_factory("MyPluginConfig", Arc::new(|| Box::new(MyPluginConfig::default())));
+/// }
+/// ```
+/// Lua then calls `world:construct_resource("MyPluginConfig")`. The resource type must be
+/// registered with `#[reflect(Resource)]`, since the result is inserted through reflection.
 #[derive(Resource, Clone)]
 pub struct ResourceConstructorRegistry {
     constructors: Arc<Mutex<HashMap<String, ConstructorFn>>>,
+    factories: Arc<Mutex<HashMap<String, ResourceFactory>>>,
+    param_factories: Arc<Mutex<HashMap<String, ParamResourceFactory>>>,
 }
 
 impl Default for ResourceConstructorRegistry {
     fn default() -> Self {
         Self {
             constructors: Arc::new(Mutex::new(HashMap::new())),
+            factories: Arc::new(Mutex::new(HashMap::new())),
+            param_factories: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -60,5 +73,114 @@ impl ResourceConstructorRegistry {
     /// Check if a constructor is registered for a resource type
     pub fn has_constructor(&self, name: &str) -> bool {
         self.constructors.lock().unwrap().contains_key(name)
+            || self.factories.lock().unwrap().contains_key(name)
+            || self.param_factories.lock().unwrap().contains_key(name)
+    }
+
+    /// Register a named factory that builds a resource without arguments
+    pub fn register_factory(&self, name: impl Into<String>, factory: ResourceFactory) {
+        self.factories.lock().unwrap().insert(name.into(), factory);
+    }
+
+    /// Register a named factory that builds a resource from a Lua parameter table
+    pub fn factory_with_params(&self, name: impl Into<String>, factory: ParamResourceFactory) {
+        self.param_factories.lock().unwrap().insert(name.into(), factory);
+    }
+
+    /// Build a resource with whatever is registered under `name`
+    /// Lua constructors are tried first, then parameterized factories, then plain factories.
+    /// A parameterized factory called without a table gets an empty one.
+    /// Returns None if nothing is registered for this name
+    pub fn construct(&self, lua: &Lua, name: &str, data: LuaValue) -> Option<Result<Box<dyn Reflect>, String>> {
+        let constructor = self.constructors.lock().unwrap().get(name).cloned();
+        if let Some(constructor) = constructor {
+            return Some(constructor(lua, data).map_err(|e| e.to_string()));
+        }
+
+        let param_factory = self.param_factories.lock().unwrap().get(name).cloned();
+        if let Some(factory) = param_factory {
+            let params = match data {
+                LuaValue::Table(table) => table,
+                LuaValue::Nil => match lua.create_table() {
+                    Ok(table) => table,
+                    Err(e) => return Some(Err(e.to_string())),
+                },
+                other => {
+                    return Some(Err(format!(
+                        "factory '{}' expects a table of parameters, got {}",
+                        name,
+                        other.type_name()
+                    )))
+                }
+            };
+            return Some(factory(params));
+        }
+
+        let factory = self.factories.lock().unwrap().get(name).cloned();
+        factory.map(|factory| Ok(factory()))
+    }
+}
+
+/// Insert a reflected resource into the world via its `ReflectResource` registration
+pub fn insert_reflected_resource(world: &mut World, resource: Box<dyn Reflect>) -> Result<(), String> {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let type_id = resource.as_any().type_id();
+    let reflect_resource = type_registry
+        .get(type_id)
+        .and_then(|registration| registration.data::<ReflectResource>())
+        .ok_or_else(|| {
+            format!(
+                "{} is not registered with #[reflect(Resource)]",
+                resource.reflect_type_path()
+            )
+        })?;
+    reflect_resource.insert(world, resource.as_partial_reflect(), &type_registry);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct PluginConfig {
+        speed: f32,
+    }
+
+    #[test]
+    fn test_factories_construct_and_insert() {
+        let lua = Lua::new();
+        let registry = ResourceConstructorRegistry::default();
+        registry.register_factory("PluginConfig", Arc::new(|| Box::new(PluginConfig { speed: 1.0 })));
+        registry.factory_with_params(
+            "FastPluginConfig",
+            Arc::new(|params: LuaTable| {
+                let speed: f32 = params.get("speed").map_err(|e| e.to_string())?;
+                Ok(Box::new(PluginConfig { speed }) as Box<dyn Reflect>)
+            }),
+        );
+        assert!(registry.has_constructor("PluginConfig"));
+        assert!(registry.construct(&lua, "Unknown", LuaValue::Nil).is_none());
+
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world.resource::<AppTypeRegistry>().write().register::<PluginConfig>();
+
+        let built = registry.construct(&lua, "PluginConfig", LuaValue::Nil).unwrap().unwrap();
+        insert_reflected_resource(&mut world, built).unwrap();
+        assert_eq!(world.resource::<PluginConfig>().speed, 1.0);
+
+        let params = lua.create_table().unwrap();
+        params.set("speed", 4.0).unwrap();
+        let built = registry
+            .construct(&lua, "FastPluginConfig", LuaValue::Table(params))
+            .unwrap()
+            .unwrap();
+        insert_reflected_resource(&mut world, built).unwrap();
+        assert_eq!(world.resource::<PluginConfig>().speed, 4.0);
+
+        assert!(registry.construct(&lua, "FastPluginConfig", LuaValue::Integer(1)).unwrap().is_err());
     }
 }
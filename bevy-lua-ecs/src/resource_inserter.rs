@@ -1,11 +1,28 @@
 use crate::lua_integration::LuaScriptContext;
-use crate::resource_queue::ResourceQueue;
+use crate::resource_queue::{ResourceQueue, ResourceRequest, ResourceRequestKind};
 use crate::serde_components::SerdeComponentRegistry;
 use bevy::prelude::*;
 use mlua::prelude::*;
 
 /// System that processes the resource queue and inserts resources
+/// High-priority requests are inserted before normal requests
 pub fn process_resource_queue(world: &mut World) {
+    let requests = world.resource::<ResourceQueue>().drain();
+    insert_requested_resources(world, requests);
+}
+
+/// System that inserts only high-priority resources
+/// Runs in `PreUpdate` so they are available to the same frame's update systems
+pub fn process_high_priority_resources(world: &mut World) {
+    let requests = world.resource::<ResourceQueue>().drain_high_priority();
+    insert_requested_resources(world, requests);
+}
+
+fn insert_requested_resources(world: &mut World, requests: Vec<ResourceRequest>) {
+    if requests.is_empty() {
+        return;
+    }
+
     // Get resources we need
     let queue = world.resource::<ResourceQueue>().clone();
     let lua_ctx = world.resource::<LuaScriptContext>().clone();
@@ -13,21 +30,14 @@ pub fn process_resource_queue(world: &mut World) {
     let builder_registry = world
         .resource::<crate::resource_builder::ResourceBuilderRegistry>()
         .clone();
-
-    let requests = queue.drain();
-
-    if requests.is_empty() {
-        return;
-    }
+    let constructor_registry = world
+        .get_resource::<crate::resource_constructors::ResourceConstructorRegistry>()
+        .cloned();
 
     for request in requests {
-        // Track which script instance inserted this resource
-        if let Some(instance_id) = request.instance_id {
-            queue.track_resource(instance_id, request.resource_name.clone());
-        }
-
-        // Retrieve the Lua value from the registry
-        let data_value: LuaValue = match lua_ctx.lua.registry_value(&*request.data) {
+        // Retrieve the Lua value from the registry of the state that queued the request
+        let lua = lua_ctx.state_for_key(&request.data);
+        let data_value: LuaValue = match lua.registry_value(&*request.data) {
             Ok(value) => value,
             Err(e) => {
                 error!(
@@ -38,9 +48,48 @@ pub fn process_resource_queue(world: &mut World) {
             }
         };
 
+        if let ResourceRequestKind::Mutate { method_name } = &request.kind {
+            let LuaValue::Table(args) = data_value else {
+                error!(
+                    "Failed to mutate resource {}: {} expects a table of arguments",
+                    request.resource_name, method_name
+                );
+                continue;
+            };
+            match builder_registry.try_mutate(&request.resource_name, method_name, args, world) {
+                Some(Ok(())) => debug!("✓ Mutated resource '{}' via {}", request.resource_name, method_name),
+                Some(Err(e)) => error!(
+                    "Failed to mutate resource {} via {}: {}",
+                    request.resource_name, method_name, e
+                ),
+                None => warn!(
+                    "No mutator '{}' registered for resource '{}'",
+                    method_name, request.resource_name
+                ),
+            }
+            continue;
+        }
+
+        if request.kind == ResourceRequestKind::Set {
+            let LuaValue::Table(data) = data_value else {
+                error!("Failed to set resource {}: expected a table of fields", request.resource_name);
+                continue;
+            };
+            match patch_reflected_resource(world, &lua, &request.resource_name, &data) {
+                Ok(()) => debug!("✓ Set resource '{}'", request.resource_name),
+                Err(e) => error!("Failed to set resource {}: {}", request.resource_name, e),
+            }
+            continue;
+        }
+
+        // Track which script instance inserted this resource
+        if let Some(instance_id) = request.instance_id {
+            queue.track_resource(instance_id, request.resource_name.clone());
+        }
+
         // Try builder registry first (for resources like RenetServer, NetcodeServerTransport)
         if let Some(result) = builder_registry.try_build(
-            &lua_ctx.lua,
+            &lua,
             &request.resource_name,
             data_value.clone(),
             world,
@@ -66,9 +115,24 @@ pub fn process_resource_queue(world: &mut World) {
             if let Err(e) = result {
                 error!("Failed to insert resource {}: {}", request.resource_name, e);
             }
+        }
+        // Finally, factories contributed by plugins (world:construct_resource)
+        else if let Some(result) = constructor_registry
+            .as_ref()
+            .and_then(|registry| registry.construct(&lua, &request.resource_name, data_value.clone()))
+        {
+            match result.and_then(|resource| {
+                crate::resource_constructors::insert_reflected_resource(world, resource)
+            }) {
+                Ok(()) => {
+                    debug!("✓ Inserted resource '{}' via factory", request.resource_name);
+                    serde_registry.mark_resource_inserted(&request.resource_name);
+                }
+                Err(e) => error!("Failed to construct resource {}: {}", request.resource_name, e),
+            }
         } else {
             warn!(
-                "Resource type '{}' is not registered in builder or serde registry",
+                "Resource type '{}' is not registered in builder, serde or constructor registry",
                 request.resource_name
             );
         }
@@ -76,3 +140,77 @@ pub fn process_resource_queue(world: &mut World) {
         // Arc will be dropped automatically when request goes out of scope
     }
 }
+
+/// Apply the fields of a `world:set_resource` table to an existing resource via reflection
+/// Only the fields present in the table are changed
+fn patch_reflected_resource(
+    world: &mut World,
+    lua: &Lua,
+    resource_name: &str,
+    data: &LuaTable,
+) -> LuaResult<()> {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let asset_registry = world.get_resource::<crate::asset_loading::AssetRegistry>().cloned();
+    let registry = type_registry.read();
+    let reflect_resource =
+        crate::lua_world_context::resolve_reflect_resource(&registry, resource_name, "set_resource")?;
+
+    // SAFETY: we hold exclusive access to the world and only touch this one resource
+    let resource_ref = unsafe { reflect_resource.reflect_unchecked_mut(world.as_unsafe_world_cell()) };
+    let Some(mut resource) = resource_ref else {
+        return Err(LuaError::RuntimeError(format!(
+            "set_resource: resource '{}' does not exist in the world",
+            resource_name
+        )));
+    };
+
+    let type_info = resource.get_represented_type_info().ok_or_else(|| {
+        LuaError::RuntimeError(format!("set_resource: '{}' has no type info", resource_name))
+    })?;
+
+    match type_info {
+        bevy::reflect::TypeInfo::Struct(_) => {
+            let dynamic = crate::event_reader::lua_table_to_dynamic_with_assets(
+                lua,
+                data,
+                type_info,
+                &type_registry,
+                asset_registry.as_ref(),
+            )?;
+            resource.try_apply(&dynamic).map_err(|e| {
+                LuaError::RuntimeError(format!("set_resource: failed to apply '{}': {}", resource_name, e))
+            })?;
+        }
+        bevy::reflect::TypeInfo::TupleStruct(_) => {
+            // Tuple structs like ClearColor(Color) use _0, _1, ... keys
+            if let bevy::reflect::ReflectMut::TupleStruct(tuple_mut) = resource.reflect_mut() {
+                for pair in data.pairs::<String, LuaValue>() {
+                    let (key, value) = pair?;
+                    let field = key
+                        .strip_prefix('_')
+                        .and_then(|index| index.parse::<usize>().ok())
+                        .and_then(|index| tuple_mut.field_mut(index))
+                        .ok_or_else(|| LuaError::RuntimeError(format!(
+                            "set_resource: '{}' has no field '{}'",
+                            resource_name, key
+                        )))?;
+                    crate::components::set_field_from_lua(
+                        field,
+                        &value,
+                        asset_registry.as_ref(),
+                        &type_registry,
+                        Some(&key),
+                    )?;
+                }
+            }
+        }
+        _ => {
+            return Err(LuaError::RuntimeError(format!(
+                "set_resource: '{}' is not a struct resource",
+                resource_name
+            )));
+        }
+    }
+
+    Ok(())
+}
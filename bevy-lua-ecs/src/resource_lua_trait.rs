@@ -1,8 +1,8 @@
 use bevy::prelude::*;
 use mlua::prelude::*;
 use std::any::TypeId;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, Weak};
 
 /// Method handler for a resource
 /// Takes: Lua context, World, method arguments
@@ -13,6 +13,7 @@ pub type ResourceMethod =
 /// Builder for registering resource methods (type-safe version)
 pub struct LuaResourceMethods<R: Resource> {
     methods: HashMap<String, ResourceMethod>,
+    builders: HashSet<String>,
     _phantom: std::marker::PhantomData<R>,
 }
 
@@ -20,10 +21,50 @@ impl<R: Resource + 'static> LuaResourceMethods<R> {
     pub fn new() -> Self {
         Self {
             methods: HashMap::new(),
+            builders: HashSet::new(),
             _phantom: std::marker::PhantomData,
         }
     }
 
+    /// Add a builder method - the handler modifies the resource in place and Lua
+    /// gets the resource back for chaining
+    ///
+    /// # Example
+    /// ```ignore
+    /// methods.add_builder("with_gravity", |config, _lua, gravity: f32| {
+    ///     config.gravity = gravity;
+    ///     Ok(())
+    /// });
+    /// // Lua: world:call_resource_method("PhysicsConfig", "with_gravity", 9.8):with_substeps(4)
+    /// ```
+    pub fn add_builder<F, Args>(&mut self, name: &str, handler: F)
+    where
+        F: Fn(&mut R, &Lua, Args) -> LuaResult<()> + Send + Sync + 'static,
+        Args: FromLuaMulti,
+    {
+        self.add(name, handler);
+        self.builders.insert(name.to_string());
+    }
+
+    /// Add a builder method that consumes the resource (`fn with_x(self, ..) -> Self`)
+    /// The handler gets the resource by value and returns its replacement, so the resource
+    /// doesn't need to implement Clone or Default.
+    ///
+    /// # Example
+    /// ```ignore
+    /// methods.add_consuming_builder("with_gravity", |config, (x, y): (f32, f32)| config.with_gravity(x, y));
+    /// ```
+    pub fn add_consuming_builder<F, Args>(&mut self, name: &str, handler: F)
+    where
+        F: Fn(R, Args) -> R + Send + Sync + 'static,
+        Args: FromLuaMulti,
+    {
+        self.add_builder(name, move |resource: &mut R, _lua, args: Args| {
+            replace_with_or_abort(resource, |owned| handler(owned, args));
+            Ok(())
+        });
+    }
+
     /// Add a method - type-safe!
     ///
     /// # Example
@@ -68,12 +109,87 @@ impl<R: Resource + 'static> LuaResourceMethods<R> {
     }
 }
 
+/// Replace `*dest` with `f(*dest)` without requiring a placeholder value
+/// Aborts if `f` panics, since `*dest` would otherwise be dropped twice.
+fn replace_with_or_abort<T>(dest: &mut T, f: impl FnOnce(T) -> T) {
+    // SAFETY: the value read out of `dest` is always written back before `dest` is used
+    // again; a panic in between aborts instead of unwinding past the moved-out value
+    unsafe {
+        let owned = std::ptr::read(dest);
+        let updated = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(owned)))
+            .unwrap_or_else(|_| std::process::abort());
+        std::ptr::write(dest, updated);
+    }
+}
+
+/// Chainable handle returned from builder methods
+///
+/// Indexing it with a method name calls that method on the resource again, so
+/// `handle:with_a(1):with_b(2)` works. The handle is only usable while the Lua
+/// system call that created it is running.
+pub struct LuaResourceChain {
+    resource_name: String,
+    registry: LuaResourceRegistry,
+    /// `*const World` stored as an address so the handle stays Send
+    world: usize,
+    /// Dropped when the creating LuaWorldContext goes away
+    alive: Weak<()>,
+}
+
+impl LuaResourceChain {
+    pub fn new(resource_name: String, registry: LuaResourceRegistry, world: &World, alive: Weak<()>) -> Self {
+        Self {
+            resource_name,
+            registry,
+            world: world as *const World as usize,
+            alive,
+        }
+    }
+}
+
+impl LuaUserData for LuaResourceChain {
+    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(LuaMetaMethod::Index, |lua, this, method_name: String| {
+            let resource_name = this.resource_name.clone();
+            let registry = this.registry.clone();
+            let world = this.world;
+            let alive = this.alive.clone();
+            lua.create_function(move |lua, (_chain, args): (LuaValue, LuaMultiValue)| {
+                let Some(_guard) = alive.upgrade() else {
+                    return Err(LuaError::RuntimeError(format!(
+                        "Resource chain for '{}' used after its system finished",
+                        resource_name
+                    )));
+                };
+                // SAFETY: `_guard` proves the creating context (and its World borrow) is alive
+                let world = unsafe { &*(world as *const World) };
+                registry.call_method_chained(lua, world, &resource_name, &method_name, args, alive.clone())
+            })
+        });
+
+        methods.add_meta_method(LuaMetaMethod::ToString, |_lua, this, ()| {
+            Ok(format!("ResourceChain({})", this.resource_name))
+        });
+    }
+}
+
+/// Resource type exported to Lua with `#[lua_export]`
+/// Instances are collected with `inventory` and registered by `LuaSpawnPlugin` at startup
+pub struct LuaExportedResource {
+    pub type_name: &'static str,
+    pub register: fn(&LuaResourceRegistry),
+}
+
+inventory::collect!(LuaExportedResource);
+
 /// Registry for Lua-accessible resources
 /// This is the main infrastructure for exposing resource methods to Lua
 #[derive(Resource, Clone, Default)]
 pub struct LuaResourceRegistry {
     resources: Arc<Mutex<HashMap<TypeId, HashMap<String, ResourceMethod>>>>,
     type_names: Arc<Mutex<HashMap<String, TypeId>>>,
+    /// Methods registered with `add_builder` (return the resource for chaining)
+    builder_methods: Arc<Mutex<HashMap<TypeId, HashSet<String>>>>,
 }
 
 impl LuaResourceRegistry {
@@ -99,6 +215,10 @@ impl LuaResourceRegistry {
 
         let type_id = TypeId::of::<R>();
 
+        self.builder_methods
+            .lock()
+            .unwrap()
+            .insert(type_id, std::mem::take(&mut methods_builder.builders));
         self.resources
             .lock()
             .unwrap()
@@ -111,6 +231,81 @@ impl LuaResourceRegistry {
         debug!("✓ Registered Lua resource: {}", type_name);
     }
 
+    /// Add a single chainable builder method to a resource, keeping its other methods
+    ///
+    /// # Example
+    /// ```ignore
+    /// registry.register_resource_builder_method::<PhysicsConfig, _, _>("PhysicsConfig", "with_gravity", |config, _lua, g: f32| {
+    ///     config.gravity = g;
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn register_resource_builder_method<R, F, Args>(&self, type_name: &str, method_name: &str, handler: F)
+    where
+        R: Resource + 'static,
+        F: Fn(&mut R, &Lua, Args) -> LuaResult<()> + Send + Sync + 'static,
+        Args: FromLuaMulti,
+    {
+        let mut methods_builder = LuaResourceMethods::<R>::new();
+        methods_builder.add_builder(method_name, handler);
+
+        let type_id = TypeId::of::<R>();
+        self.builder_methods
+            .lock()
+            .unwrap()
+            .entry(type_id)
+            .or_default()
+            .extend(std::mem::take(&mut methods_builder.builders));
+        self.resources
+            .lock()
+            .unwrap()
+            .entry(type_id)
+            .or_default()
+            .extend(methods_builder.into_map());
+        self.type_names
+            .lock()
+            .unwrap()
+            .insert(type_name.to_string(), type_id);
+    }
+
+    /// Whether `method_name` on `type_name` is a chainable builder method
+    pub fn is_builder_method(&self, type_name: &str, method_name: &str) -> bool {
+        let Some(type_id) = self.type_names.lock().unwrap().get(type_name).copied() else {
+            return false;
+        };
+        self.builder_methods
+            .lock()
+            .unwrap()
+            .get(&type_id)
+            .is_some_and(|builders| builders.contains(method_name))
+    }
+
+    /// Call a method, wrapping builder-method results in a `LuaResourceChain`
+    pub fn call_method_chained(
+        &self,
+        lua: &Lua,
+        world: &World,
+        type_name: &str,
+        method_name: &str,
+        args: LuaMultiValue,
+        alive: Weak<()>,
+    ) -> LuaResult<LuaValue> {
+        let result = self.call_method(lua, world, type_name, method_name, args)?;
+        if !self.is_builder_method(type_name, method_name) {
+            return Ok(result);
+        }
+        let chain = LuaResourceChain::new(type_name.to_string(), self.clone(), world, alive);
+        Ok(LuaValue::UserData(lua.create_userdata(chain)?))
+    }
+
+    /// Register every resource type exported with `#[lua_export]`
+    pub fn register_exported_resources(&self) {
+        for exported in inventory::iter::<LuaExportedResource> {
+            debug!("Registering #[lua_export] resource: {}", exported.type_name);
+            (exported.register)(self);
+        }
+    }
+
     /// Call a method on a resource
     pub fn call_method(
         &self,
@@ -120,23 +315,77 @@ impl LuaResourceRegistry {
         method_name: &str,
         args: LuaMultiValue,
     ) -> LuaResult<LuaValue> {
-        let type_names = self.type_names.lock().unwrap();
-        let type_id = type_names.get(type_name).ok_or_else(|| {
+        let type_id = *self.type_names.lock().unwrap().get(type_name).ok_or_else(|| {
             LuaError::RuntimeError(format!("Resource type '{}' not registered", type_name))
         })?;
 
+        // Clone the handler out so the registry isn't locked while it runs
+        // (builder chains call back into the registry)
         let resources = self.resources.lock().unwrap();
-        let methods = resources.get(type_id).ok_or_else(|| {
+        let methods = resources.get(&type_id).ok_or_else(|| {
             LuaError::RuntimeError(format!("Resource type '{}' has no methods", type_name))
         })?;
 
-        let method = methods.get(method_name).ok_or_else(|| {
+        let method = methods.get(method_name).cloned().ok_or_else(|| {
             LuaError::RuntimeError(format!(
                 "Method '{}' not found on '{}'",
                 method_name, type_name
             ))
         })?;
+        drop(resources);
 
         method(lua, world, args)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Neither Clone nor Default, like most consuming builders
+    #[derive(Resource)]
+    struct PhysicsConfig {
+        gravity: (f32, f32),
+        label: String,
+    }
+
+    impl PhysicsConfig {
+        fn with_gravity(self, x: f32, y: f32) -> Self {
+            Self { gravity: (x, y), ..self }
+        }
+
+        fn with_label(self, label: &str) -> Self {
+            Self { label: label.to_string(), ..self }
+        }
+    }
+
+    #[test]
+    fn test_consuming_builder_replaces_resource() {
+        let lua = Lua::new();
+        let mut world = World::new();
+        world.insert_resource(PhysicsConfig { gravity: (0.0, 0.0), label: String::new() });
+
+        let registry = LuaResourceRegistry::default();
+        registry.register_resource::<PhysicsConfig>("PhysicsConfig", |methods| {
+            methods.add_consuming_builder("with_gravity", |config, (arg0, arg1): (f32, f32)| {
+                config.with_gravity(arg0, arg1)
+            });
+            methods.add_consuming_builder("with_label", |config, arg0: String| config.with_label(&arg0));
+        });
+        assert!(registry.is_builder_method("PhysicsConfig", "with_gravity"));
+
+        let args = (1.5, -9.8).into_lua_multi(&lua).unwrap();
+        registry.call_method(&lua, &world, "PhysicsConfig", "with_gravity", args).unwrap();
+        let args = "moon".into_lua_multi(&lua).unwrap();
+        registry.call_method(&lua, &world, "PhysicsConfig", "with_label", args).unwrap();
+
+        let config = world.resource::<PhysicsConfig>();
+        assert_eq!(config.gravity, (1.5, -9.8));
+        assert_eq!(config.label, "moon");
+
+        // Arguments are converted to the declared types
+        let args = "not a number".into_lua_multi(&lua).unwrap();
+        assert!(registry.call_method(&lua, &world, "PhysicsConfig", "with_gravity", args).is_err());
+        assert_eq!(world.resource::<PhysicsConfig>().gravity, (1.5, -9.8));
+    }
+}
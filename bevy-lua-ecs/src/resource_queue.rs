@@ -2,17 +2,53 @@ use bevy::prelude::*;
 use mlua::prelude::*;
 use std::sync::{Arc, Mutex};
 
+/// Priority of a queued resource insertion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResourcePriority {
+    /// Inserted in `PreUpdate`, before the frame's update systems run
+    High,
+    /// Inserted by `process_resource_queue` in `Update` (FIFO)
+    #[default]
+    Normal,
+}
+
+impl ResourcePriority {
+    /// Parse a priority name from Lua ("high" | "normal")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "high" => Some(ResourcePriority::High),
+            "normal" => Some(ResourcePriority::Normal),
+            _ => None,
+        }
+    }
+}
+
+/// What a queued resource request does
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ResourceRequestKind {
+    /// Build/insert the resource from `data`
+    #[default]
+    Insert,
+    /// Call a registered mutator on the existing resource with `data` as arguments
+    Mutate { method_name: String },
+    /// Patch the existing resource's fields from the `data` table via reflection
+    Set,
+}
+
 /// Resource insertion request with resource type name and data
 pub struct ResourceRequest {
     pub resource_name: String,
     pub data: Arc<LuaRegistryKey>,
     pub instance_id: Option<u64>,
+    pub kind: ResourceRequestKind,
 }
 
 /// Resource that holds the resource insertion queue
 #[derive(Resource, Clone)]
 pub struct ResourceQueue {
     queue: Arc<Mutex<Vec<ResourceRequest>>>,
+    /// High-priority requests, always drained before `queue`
+    high_priority: Arc<Mutex<Vec<ResourceRequest>>>,
     /// Track which resources were inserted by which script instance
     instance_resources: Arc<Mutex<std::collections::HashMap<u64, Vec<String>>>>,
 }
@@ -21,6 +57,7 @@ impl Default for ResourceQueue {
     fn default() -> Self {
         Self {
             queue: Arc::new(Mutex::new(Vec::new())),
+            high_priority: Arc::new(Mutex::new(Vec::new())),
             instance_resources: Arc::new(Mutex::new(std::collections::HashMap::new())),
         }
     }
@@ -38,10 +75,65 @@ impl ResourceQueue {
             resource_name,
             data: Arc::new(data),
             instance_id,
+            kind: ResourceRequestKind::Insert,
         };
         self.queue.lock().unwrap().push(request);
     }
 
+    /// Add a resource insertion request ahead of all normal-priority requests
+    pub fn insert_high_priority(
+        &self,
+        resource_name: String,
+        data: LuaRegistryKey,
+        instance_id: Option<u64>,
+    ) {
+        let request = ResourceRequest {
+            resource_name,
+            data: Arc::new(data),
+            instance_id,
+            kind: ResourceRequestKind::Insert,
+        };
+        self.high_priority.lock().unwrap().push(request);
+    }
+
+    /// Add a request to run mutator `method_name` on an existing resource
+    /// Mutations share the normal FIFO queue so they apply after earlier inserts
+    pub fn queue_mutate(&self, resource_name: String, method_name: String, args: LuaRegistryKey) {
+        let request = ResourceRequest {
+            resource_name,
+            data: Arc::new(args),
+            instance_id: None,
+            kind: ResourceRequestKind::Mutate { method_name },
+        };
+        self.queue.lock().unwrap().push(request);
+    }
+
+    /// Add a request to patch the fields of an existing resource (`world:set_resource`)
+    /// Shares the normal FIFO queue so the patch applies after earlier inserts
+    pub fn queue_set(&self, resource_name: String, data: LuaRegistryKey) {
+        let request = ResourceRequest {
+            resource_name,
+            data: Arc::new(data),
+            instance_id: None,
+            kind: ResourceRequestKind::Set,
+        };
+        self.queue.lock().unwrap().push(request);
+    }
+
+    /// Add a resource insertion request with the given priority
+    pub fn queue_insert_with_priority(
+        &self,
+        resource_name: String,
+        data: LuaRegistryKey,
+        instance_id: Option<u64>,
+        priority: ResourcePriority,
+    ) {
+        match priority {
+            ResourcePriority::High => self.insert_high_priority(resource_name, data, instance_id),
+            ResourcePriority::Normal => self.queue_insert(resource_name, data, instance_id),
+        }
+    }
+
     /// Track that a resource was inserted by a script instance
     pub fn track_resource(&self, instance_id: u64, resource_name: String) {
         let mut map = self.instance_resources.lock().unwrap();
@@ -65,8 +157,39 @@ impl ResourceQueue {
         self.instance_resources.lock().unwrap().remove(&instance_id);
     }
 
-    /// Drain all pending resource requests
+    /// Drain all pending resource requests (high-priority first, then FIFO)
     pub fn drain(&self) -> Vec<ResourceRequest> {
-        self.queue.lock().unwrap().drain(..).collect()
+        let mut requests = self.drain_high_priority();
+        requests.extend(self.queue.lock().unwrap().drain(..));
+        requests
+    }
+
+    /// Drain only the pending high-priority resource requests
+    pub fn drain_high_priority(&self) -> Vec<ResourceRequest> {
+        self.high_priority.lock().unwrap().drain(..).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_high_priority_drains_first() {
+        let lua = Lua::new();
+        let queue = ResourceQueue::default();
+
+        queue.queue_insert("A".to_string(), lua.create_registry_value(1).unwrap(), None);
+        queue.queue_insert_with_priority(
+            "B".to_string(),
+            lua.create_registry_value(2).unwrap(),
+            None,
+            ResourcePriority::High,
+        );
+        queue.queue_insert("C".to_string(), lua.create_registry_value(3).unwrap(), None);
+
+        let names: Vec<_> = queue.drain().into_iter().map(|r| r.resource_name).collect();
+        assert_eq!(names, vec!["B", "A", "C"]);
+        assert!(queue.drain().is_empty());
     }
 }
@@ -0,0 +1,250 @@
+//! Lua callbacks fired when a resource changes
+//!
+//! ```lua
+//! local handle = world:subscribe_resource("ClearColor", function(color)
+//!     print("clear color is now", color._0.r)
+//! end)
+//! world:unsubscribe_resource("ClearColor", handle)
+//! ```
+//!
+//! `check_resource_watchers` runs in `PostUpdate` and compares each watched resource's change
+//! tick (Bevy's `DetectChanges`) with the tick each subscriber last checked, so every subscriber
+//! fires at most once per frame no matter how often the resource was written. A subscription only sees changes
+//! made after it was created, and changes made by the callbacks themselves do not fire them again.
+//! The callback receives the resource converted to a Lua table through reflection, so the resource
+//! must be registered with `#[reflect(Resource)]`.
+
+use crate::lua_integration::LuaScriptContext;
+use bevy::ecs::component::Tick;
+use bevy::prelude::*;
+use bevy::reflect::ReflectFromPtr;
+use mlua::prelude::*;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A Lua callback subscribed to a resource
+struct LuaResourceWatcher {
+    handle: u64,
+    callback: Arc<LuaRegistryKey>,
+    /// Lua state the subscription was made in (0=primary, >=1=instanced)
+    state_id: usize,
+    /// Script instance that subscribed (for cleanup on reload/stop)
+    instance_id: u64,
+    /// Change tick at which this subscriber last checked the resource
+    last_seen: Tick,
+}
+
+/// Subscribers of one resource type
+struct WatchedResource {
+    type_path: String,
+    watchers: Vec<LuaResourceWatcher>,
+}
+
+#[derive(Default)]
+struct WatcherState {
+    next_handle: u64,
+    resources: HashMap<TypeId, WatchedResource>,
+}
+
+/// Resource holding Lua resource-change subscriptions
+#[derive(Resource, Clone, Default)]
+pub struct ResourceWatcherRegistry {
+    state: Arc<Mutex<WatcherState>>,
+}
+
+impl ResourceWatcherRegistry {
+    /// Subscribe `callback` to changes of the resource with `type_id` made after `now`
+    /// Returns the handle used by `unsubscribe`
+    pub fn subscribe(
+        &self,
+        lua: &Lua,
+        type_id: TypeId,
+        type_path: &str,
+        callback: LuaFunction,
+        now: Tick,
+    ) -> LuaResult<u64> {
+        let callback = Arc::new(lua.create_registry_value(callback)?);
+        let state_id = lua.globals().get("__LUA_STATE_ID__").unwrap_or(0);
+        let instance_id = lua.globals().get("__INSTANCE_ID__").unwrap_or(0);
+
+        let mut state = self.state.lock().unwrap();
+        state.next_handle += 1;
+        let handle = state.next_handle;
+        state
+            .resources
+            .entry(type_id)
+            .or_insert_with(|| WatchedResource {
+                type_path: type_path.to_string(),
+                watchers: Vec::new(),
+            })
+            .watchers
+            .push(LuaResourceWatcher { handle, callback, state_id, instance_id, last_seen: now });
+        debug!("[RESOURCE_WATCH] Subscription {} to {}", handle, type_path);
+        Ok(handle)
+    }
+
+    /// Remove a subscription. Returns true if it existed.
+    pub fn unsubscribe(&self, type_id: TypeId, handle: u64) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let Some(watched) = state.resources.get_mut(&type_id) else {
+            return false;
+        };
+        let before = watched.watchers.len();
+        watched.watchers.retain(|watcher| watcher.handle != handle);
+        let removed = watched.watchers.len() != before;
+        if watched.watchers.is_empty() {
+            state.resources.remove(&type_id);
+        }
+        removed
+    }
+
+    /// Remove all subscriptions made by a script instance
+    pub fn clear_instance_watchers(&self, instance_id: u64) {
+        let mut state = self.state.lock().unwrap();
+        for watched in state.resources.values_mut() {
+            watched.watchers.retain(|watcher| watcher.instance_id != instance_id);
+        }
+        state.resources.retain(|_, watched| !watched.watchers.is_empty());
+    }
+
+    /// Number of active subscriptions
+    pub fn len(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.resources.values().map(|watched| watched.watchers.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A subscriber whose resource changed this frame
+struct FiredWatcher {
+    type_id: TypeId,
+    type_path: String,
+    callback: Arc<LuaRegistryKey>,
+    state_id: usize,
+    instance_id: u64,
+}
+
+/// System that calls the subscribers of every watched resource that changed since the last check
+pub fn check_resource_watchers(world: &mut World) {
+    let Some(registry) = world.get_resource::<ResourceWatcherRegistry>().cloned() else {
+        return;
+    };
+    let Some(lua_ctx) = world.get_resource::<LuaScriptContext>().cloned() else {
+        return;
+    };
+    if registry.is_empty() {
+        return;
+    }
+    let this_run = world.read_change_tick();
+
+    // The lock is released before callbacks run, so they can subscribe or unsubscribe
+    let fired: Vec<FiredWatcher> = {
+        let mut state = registry.state.lock().unwrap();
+        let mut fired = Vec::new();
+        for (type_id, watched) in state.resources.iter_mut() {
+            let ticks = world
+                .components()
+                .get_resource_id(*type_id)
+                .and_then(|component_id| world.get_resource_change_ticks_by_id(component_id));
+            for watcher in watched.watchers.iter_mut() {
+                let last_seen = std::mem::replace(&mut watcher.last_seen, this_run);
+                if !ticks.is_some_and(|ticks| ticks.is_changed(last_seen, this_run)) {
+                    continue;
+                }
+                fired.push(FiredWatcher {
+                    type_id: *type_id,
+                    type_path: watched.type_path.clone(),
+                    callback: watcher.callback.clone(),
+                    state_id: watcher.state_id,
+                    instance_id: watcher.instance_id,
+                });
+            }
+        }
+        fired
+    };
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    for watcher in fired {
+        let lua = lua_ctx.get_lua_state(watcher.state_id);
+        let value = match resource_to_lua(&lua, world, &type_registry, watcher.type_id) {
+            Ok(Some(value)) => value,
+            // Removed since the change was detected
+            Ok(None) => continue,
+            Err(e) => {
+                error!("[RESOURCE_WATCH] Failed to convert {}: {}", watcher.type_path, e);
+                continue;
+            }
+        };
+
+        // Run in the script context that subscribed, then restore the caller's
+        let globals = lua.globals();
+        let previous_instance_id: Option<u64> = globals.get("__INSTANCE_ID__").ok();
+        let previous_phase: Option<String> = globals.get("__SPAWN_PHASE__").ok();
+        let _ = globals.set("__INSTANCE_ID__", watcher.instance_id);
+        let _ = globals.set("__SPAWN_PHASE__", "runtime");
+
+        let result = lua
+            .registry_value::<LuaFunction>(&watcher.callback)
+            .and_then(|callback| callback.call::<()>(value));
+        let _ = globals.set("__INSTANCE_ID__", previous_instance_id);
+        let _ = globals.set("__SPAWN_PHASE__", previous_phase);
+        if let Err(e) = result {
+            error!("[RESOURCE_WATCH] Callback for {} failed: {}", watcher.type_path, e);
+        }
+    }
+}
+
+/// Read a resource through reflection and convert it to a Lua value, or None if it isn't inserted
+fn resource_to_lua(
+    lua: &Lua,
+    world: &World,
+    type_registry: &AppTypeRegistry,
+    type_id: TypeId,
+) -> LuaResult<Option<LuaValue>> {
+    let reflect_from_ptr = type_registry
+        .read()
+        .get_type_data::<ReflectFromPtr>(type_id)
+        .cloned()
+        .ok_or_else(|| LuaError::RuntimeError("resource has no ReflectFromPtr".to_string()))?;
+    let Some(ptr) = world
+        .components()
+        .get_resource_id(type_id)
+        .and_then(|component_id| world.get_resource_by_id(component_id))
+    else {
+        return Ok(None);
+    };
+    // SAFETY: the pointer comes from the resource registered under the same TypeId
+    let value = unsafe { reflect_from_ptr.as_reflect(ptr) };
+    crate::event_reader::reflection_to_lua(lua, value.as_partial_reflect(), type_registry).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsubscribe_and_instance_cleanup() {
+        let lua = Lua::new();
+        let registry = ResourceWatcherRegistry::default();
+        let callback = || lua.load("return function() end").eval::<LuaFunction>().unwrap();
+        let type_id = TypeId::of::<ClearColor>();
+
+        lua.globals().set("__INSTANCE_ID__", 1u64).unwrap();
+        let first = registry.subscribe(&lua, type_id, "ClearColor", callback(), Tick::new(0)).unwrap();
+        lua.globals().set("__INSTANCE_ID__", 2u64).unwrap();
+        let second = registry.subscribe(&lua, type_id, "ClearColor", callback(), Tick::new(0)).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(registry.len(), 2);
+
+        assert!(registry.unsubscribe(type_id, first));
+        assert!(!registry.unsubscribe(type_id, first));
+        assert!(!registry.unsubscribe(TypeId::of::<Time>(), second));
+
+        registry.clear_instance_watchers(2);
+        assert!(registry.is_empty());
+    }
+}
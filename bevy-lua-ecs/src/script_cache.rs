@@ -2,10 +2,30 @@ use crate::path_utils::normalize_path_separators as normalize_path;
 use bevy::log::{debug, info};
 use bevy::prelude::Resource;
 use mlua::prelude::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
+/// Default maximum number of cached module results before LRU eviction kicks in
+pub const DEFAULT_SCRIPT_CACHE_CAPACITY: usize = 256;
+
+/// Optional configuration resource for the module cache
+/// Insert before adding `LuaSpawnPlugin` to override the default capacity
+#[derive(Resource, Clone, Debug)]
+pub struct ScriptCacheConfig {
+    /// Maximum number of (path, state_id) module results kept in the cache
+    pub capacity: usize,
+}
+
+impl Default for ScriptCacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_SCRIPT_CACHE_CAPACITY,
+        }
+    }
+}
+
 /// Resource that caches loaded Lua modules and tracks dependencies
 /// 
 /// Modules are cached by (path, state_id) to support isolated instanced states.
@@ -15,6 +35,12 @@ pub struct ScriptCache {
     /// Cached module exports: (path, state_id) -> Lua registry key (execution results)
     /// Using tuple key allows same module to have different cached results in different states
     modules: Arc<Mutex<HashMap<(String, usize), Arc<LuaRegistryKey>>>>,
+    /// Maximum number of entries in `modules` before least-recently-used entries are evicted
+    capacity: Arc<AtomicUsize>,
+    /// Recency order for `modules`: front = least recently used, back = most recently used
+    module_lru: Arc<Mutex<VecDeque<(String, usize)>>>,
+    /// Registry keys evicted from `modules` that still need to be released from the Lua registry
+    evicted_modules: Arc<Mutex<Vec<Arc<LuaRegistryKey>>>>,
     /// Cached module source code: path -> source string
     /// This persists across cache clears to avoid re-reading unchanged files from disk
     source_cache: Arc<Mutex<HashMap<String, String>>>,
@@ -52,6 +78,10 @@ pub struct ScriptCache {
     /// Asset dependencies: asset_path -> set of (script_path, instance_id)
     /// Tracks which scripts depend on which assets for reload when assets change
     asset_dependencies: Arc<Mutex<HashMap<String, HashSet<(String, u64)>>>>,
+    /// Chunk hashes for diff-based hot reload: (script path, instance_id) -> (chunk name -> hash)
+    chunk_hashes: Arc<Mutex<HashMap<(String, u64), HashMap<String, u64>>>>,
+    /// Stdlib extensions chunk (chunk name, source), checked once and replayed into sandbox states
+    stdlib_chunk: Arc<Mutex<Option<(String, String)>>>,
 }
 
 impl Default for ScriptCache {
@@ -62,8 +92,16 @@ impl Default for ScriptCache {
 
 impl ScriptCache {
     pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_SCRIPT_CACHE_CAPACITY)
+    }
+
+    /// Create a cache that keeps at most `capacity` module results (LRU eviction)
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
             modules: Arc::new(Mutex::new(HashMap::new())),
+            capacity: Arc::new(AtomicUsize::new(capacity.max(1))),
+            module_lru: Arc::new(Mutex::new(VecDeque::new())),
+            evicted_modules: Arc::new(Mutex::new(Vec::new())),
             source_cache: Arc::new(Mutex::new(HashMap::new())),
             dependencies: Arc::new(Mutex::new(HashMap::new())),
             async_dependencies: Arc::new(Mutex::new(HashMap::new())),
@@ -78,18 +116,137 @@ impl ScriptCache {
             should_subscribe_on_complete: Arc::new(Mutex::new(HashMap::new())),
             pending_unsubscriptions: Arc::new(Mutex::new(Vec::new())),
             asset_dependencies: Arc::new(Mutex::new(HashMap::new())),
+            chunk_hashes: Arc::new(Mutex::new(HashMap::new())),
+            stdlib_chunk: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// Cache the stdlib extensions chunk after it ran successfully in the primary state
+    pub fn set_stdlib_chunk(&self, chunk_name: String, source: String) {
+        *self.stdlib_chunk.lock().unwrap() = Some((chunk_name, source));
+    }
+
+    /// Stdlib extensions chunk to replay into a new sandbox state
+    pub fn stdlib_chunk(&self) -> Option<(String, String)> {
+        self.stdlib_chunk.lock().unwrap().clone()
+    }
+
     /// Get a cached module if it exists for the given state
     /// state_id=0 is the primary state, >=1 are instanced states
+    /// A hit marks the module as most recently used
     pub fn get_module(&self, path: &str, state_id: usize) -> Option<Arc<LuaRegistryKey>> {
-        self.modules.lock().unwrap().get(&(path.to_string(), state_id)).cloned()
+        let key = (path.to_string(), state_id);
+        let result = self.modules.lock().unwrap().get(&key).cloned();
+        if result.is_some() {
+            self.touch_module(key);
+        }
+        result
     }
 
     /// Cache a loaded module for a specific state
+    /// Evicts the least recently used modules if the cache exceeds its capacity
     pub fn cache_module(&self, path: String, state_id: usize, registry_key: Arc<LuaRegistryKey>) {
-        self.modules.lock().unwrap().insert((path, state_id), registry_key);
+        let key = (path, state_id);
+        if let Some(replaced) = self.modules.lock().unwrap().insert(key.clone(), registry_key) {
+            self.evicted_modules.lock().unwrap().push(replaced);
+        }
+        self.touch_module(key);
+        self.evict_over_capacity();
+    }
+
+    /// Maximum number of cached module results
+    pub fn capacity(&self) -> usize {
+        self.capacity.load(Ordering::Relaxed)
+    }
+
+    /// Change the cache capacity, evicting least recently used modules if needed
+    pub fn set_capacity(&self, capacity: usize) {
+        self.capacity.store(capacity.max(1), Ordering::Relaxed);
+        self.evict_over_capacity();
+    }
+
+    /// Number of module results currently cached
+    pub fn module_count(&self) -> usize {
+        self.modules.lock().unwrap().len()
+    }
+
+    /// Move a module to the most-recently-used end of the LRU order
+    fn touch_module(&self, key: (String, usize)) {
+        let mut lru = self.module_lru.lock().unwrap();
+        if let Some(pos) = lru.iter().position(|k| *k == key) {
+            lru.remove(pos);
+        }
+        lru.push_back(key);
+    }
+
+    /// Evict least recently used modules until the cache fits its capacity
+    /// Entries already removed by invalidation are skipped
+    fn evict_over_capacity(&self) {
+        let capacity = self.capacity();
+        let mut modules = self.modules.lock().unwrap();
+        let mut lru = self.module_lru.lock().unwrap();
+        let mut evicted = self.evicted_modules.lock().unwrap();
+
+        // Drop stale order entries for modules that were invalidated
+        lru.retain(|key| modules.contains_key(key));
+
+        while modules.len() > capacity {
+            let Some(oldest) = lru.pop_front() else {
+                break;
+            };
+            if let Some(registry_key) = modules.remove(&oldest) {
+                debug!(
+                    "🗑️ [SCRIPT_CACHE] Evicted module '{}' (state {}) - capacity {}",
+                    oldest.0, oldest.1, capacity
+                );
+                evicted.push(registry_key);
+            }
+        }
+    }
+
+    /// Release registry values of evicted modules from the Lua registry
+    /// Keys still referenced elsewhere are kept until their last reference is dropped
+    /// Returns the number of registry values removed
+    pub fn release_evicted_modules(&self, lua: &Lua) -> usize {
+        let pending = std::mem::take(&mut *self.evicted_modules.lock().unwrap());
+        let mut released = 0;
+        let mut still_referenced = Vec::new();
+
+        for key_arc in pending {
+            match Arc::try_unwrap(key_arc) {
+                Ok(key) => {
+                    if let Err(e) = lua.remove_registry_value(key) {
+                        debug!("[SCRIPT_CACHE] Failed to release evicted module: {}", e);
+                    } else {
+                        released += 1;
+                    }
+                }
+                Err(key_arc) => still_referenced.push(key_arc),
+            }
+        }
+
+        if !still_referenced.is_empty() {
+            self.evicted_modules.lock().unwrap().extend(still_referenced);
+        }
+
+        released
+    }
+
+    /// Get the chunk hashes recorded for a loaded script instance (for diff-based hot reload)
+    pub fn get_chunk_hashes(&self, script_path: &str, instance_id: u64) -> Option<HashMap<String, u64>> {
+        self.chunk_hashes
+            .lock()
+            .unwrap()
+            .get(&(script_path.to_string(), instance_id))
+            .cloned()
+    }
+
+    /// Record the chunk hashes of the currently loaded version of a script instance
+    pub fn set_chunk_hashes(&self, script_path: String, instance_id: u64, hashes: HashMap<String, u64>) {
+        self.chunk_hashes
+            .lock()
+            .unwrap()
+            .insert((script_path, instance_id), hashes);
     }
 
     /// Track a dependency relationship
@@ -134,6 +291,33 @@ impl ScriptCache {
         }
     }
 
+    /// Get every module a script transitively requires (not including the script itself)
+    /// Circular requires (A -> B -> A) are visited once
+    pub fn get_dependencies(&self, script_path: &str) -> Vec<String> {
+        let root = normalize_path(script_path);
+        let deps = self.dependencies.lock().unwrap();
+
+        let mut result = Vec::new();
+        let mut to_visit = vec![root.clone()];
+        let mut visited = HashSet::new();
+
+        while let Some(current) = to_visit.pop() {
+            if !visited.insert(current.clone()) {
+                continue;
+            }
+            if current != root {
+                result.push(current.clone());
+            }
+            for (imported_path, importers) in deps.iter() {
+                if importers.contains_key(&current) && !visited.contains(imported_path) {
+                    to_visit.push(imported_path.clone());
+                }
+            }
+        }
+
+        result
+    }
+
     /// Clear module cache for all dependencies of a given module path (recursively)
     /// This ensures that when the module re-executes, it sees fresh versions of its dependencies
     /// and their dependencies (transitive closure)
@@ -850,3 +1034,85 @@ pub fn execute_module(lua: &Lua, source: &str, module_name: &str) -> LuaResult<L
 
     result
 }
+
+/// System that releases Lua registry values for modules evicted from the cache
+pub fn release_evicted_script_modules(
+    lua_ctx: Option<bevy::prelude::Res<crate::lua_integration::LuaScriptContext>>,
+) {
+    if let Some(lua_ctx) = lua_ctx {
+        let released = lua_ctx.script_cache.release_evicted_modules(&lua_ctx.lua);
+        if released > 0 {
+            debug!("🗑️ [SCRIPT_CACHE] Released {} evicted module(s)", released);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_value(lua: &Lua, cache: &ScriptCache, path: &str, value: i64) {
+        let key = lua.create_registry_value(value).unwrap();
+        cache.cache_module(path.to_string(), 0, Arc::new(key));
+    }
+
+    #[test]
+    fn test_get_dependencies_handles_cycles() {
+        let cache = ScriptCache::new();
+        cache.add_dependency("scripts/utils.lua".to_string(), "scripts/main.lua".to_string(), true);
+        cache.add_dependency("scripts/a.lua".to_string(), "scripts/utils.lua".to_string(), true);
+        cache.add_dependency("scripts/utils.lua".to_string(), "scripts/a.lua".to_string(), true);
+
+        let mut deps = cache.get_dependencies("scripts/main.lua");
+        deps.sort();
+        assert_eq!(deps, vec!["scripts/a.lua", "scripts/utils.lua"]);
+        assert!(cache.get_dependencies("scripts/unknown.lua").is_empty());
+    }
+
+    #[test]
+    fn test_lru_evicts_oldest_module() {
+        let lua = Lua::new();
+        let cache = ScriptCache::with_capacity(2);
+
+        cache_value(&lua, &cache, "scripts/a.lua", 1);
+        cache_value(&lua, &cache, "scripts/b.lua", 2);
+        cache_value(&lua, &cache, "scripts/c.lua", 3);
+
+        assert_eq!(cache.module_count(), 2);
+        assert!(cache.get_module("scripts/a.lua", 0).is_none());
+        assert!(cache.get_module("scripts/b.lua", 0).is_some());
+        assert!(cache.get_module("scripts/c.lua", 0).is_some());
+        assert_eq!(cache.release_evicted_modules(&lua), 1);
+    }
+
+    #[test]
+    fn test_lru_get_refreshes_recency() {
+        let lua = Lua::new();
+        let cache = ScriptCache::with_capacity(2);
+
+        cache_value(&lua, &cache, "scripts/a.lua", 1);
+        cache_value(&lua, &cache, "scripts/b.lua", 2);
+
+        // Touch a so b becomes the least recently used
+        assert!(cache.get_module("scripts/a.lua", 0).is_some());
+        cache_value(&lua, &cache, "scripts/c.lua", 3);
+
+        assert!(cache.get_module("scripts/a.lua", 0).is_some());
+        assert!(cache.get_module("scripts/b.lua", 0).is_none());
+    }
+
+    #[test]
+    fn test_set_capacity_shrinks_cache() {
+        let lua = Lua::new();
+        let cache = ScriptCache::with_capacity(4);
+
+        for (i, path) in ["a", "b", "c", "d"].iter().enumerate() {
+            cache_value(&lua, &cache, &format!("scripts/{}.lua", path), i as i64);
+        }
+        cache.set_capacity(1);
+
+        assert_eq!(cache.module_count(), 1);
+        assert!(cache.get_module("scripts/d.lua", 0).is_some());
+        assert_eq!(cache.release_evicted_modules(&lua), 3);
+    }
+}
@@ -19,6 +19,8 @@ pub enum SpawnPhase {
 pub struct ScriptOwned {
     pub instance_id: u64,
     pub spawn_phase: SpawnPhase,
+    /// ScriptRegistry version of the script when the entity was spawned (0 if unknown)
+    pub script_version: u64,
 }
 
 /// Resource that tracks the currently executing script instance
@@ -51,6 +53,180 @@ impl ScriptInstance {
     }
 }
 
+/// One entry of `ScriptInstancePool`: a script instance started with its own parameters
+#[derive(Clone, Debug)]
+pub struct PooledScriptInstance {
+    pub instance_id: u64,
+    pub script_path: std::path::PathBuf,
+    /// Exposed to the script as the `SCRIPT_PARAMS` table
+    pub init_params: serde_json::Value,
+}
+
+/// Resource tracking script instances started with `execute_script_with_params`
+/// The same script file can have any number of entries, each with its own parameters and
+/// its own global namespace (a Lua environment that falls back to the shared globals).
+/// Hot reload re-runs an instance with the parameters recorded here.
+#[derive(Resource, Clone, Default)]
+pub struct ScriptInstancePool {
+    instances: Arc<Mutex<std::collections::HashMap<u64, PooledScriptInstance>>>,
+}
+
+impl ScriptInstancePool {
+    /// Record parameters for an instance (replaces any earlier entry for the same id)
+    pub fn insert(&self, instance_id: u64, script_path: std::path::PathBuf, init_params: serde_json::Value) {
+        self.instances.lock().unwrap().insert(
+            instance_id,
+            PooledScriptInstance {
+                instance_id,
+                script_path,
+                init_params,
+            },
+        );
+    }
+
+    /// Get the entry for an instance
+    pub fn get(&self, instance_id: u64) -> Option<PooledScriptInstance> {
+        self.instances.lock().unwrap().get(&instance_id).cloned()
+    }
+
+    /// Initialization parameters of an instance, if it was started with parameters
+    pub fn params(&self, instance_id: u64) -> Option<serde_json::Value> {
+        self.instances
+            .lock()
+            .unwrap()
+            .get(&instance_id)
+            .map(|entry| entry.init_params.clone())
+    }
+
+    /// All pooled instances running `script_path`, ordered by instance id
+    pub fn instances_of(&self, script_path: &std::path::Path) -> Vec<PooledScriptInstance> {
+        let mut entries: Vec<_> = self
+            .instances
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|entry| entry.script_path == script_path)
+            .cloned()
+            .collect();
+        entries.sort_by_key(|entry| entry.instance_id);
+        entries
+    }
+
+    /// Forget an instance (called when it is stopped)
+    pub fn remove(&self, instance_id: u64) -> Option<PooledScriptInstance> {
+        self.instances.lock().unwrap().remove(&instance_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.instances.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// How often `gc_orphaned_script_entities` looks for entities whose script instance is gone
+#[derive(Resource, Clone, Debug)]
+pub struct ScriptGcConfig {
+    /// Time between scans (default 5s)
+    pub interval: std::time::Duration,
+    pub enabled: bool,
+    /// Lua GC work per frame (in KB, as for `Lua::gc_step_kbytes`) after a scan collected entities,
+    /// until a full cycle has freed the Lua values those entities held (default 256)
+    pub lua_step_kb: i32,
+}
+
+impl Default for ScriptGcConfig {
+    fn default() -> Self {
+        Self {
+            interval: std::time::Duration::from_secs(5),
+            enabled: true,
+            lua_step_kb: 256,
+        }
+    }
+}
+
+/// Safety net for error paths that skip `despawn_instance_entities`: every
+/// `ScriptGcConfig::interval`, queue despawns for Script-phase entities whose owning instance
+/// has been removed from `ScriptRegistry`.
+/// Entities spawned before their instance was registered (`script_version == 0`, e.g. instances
+/// run with `execute_script_tracked` alone) are never collected, and Runtime-phase entities are
+/// preserved just like `despawn_instance_entities` preserves them.
+pub fn gc_orphaned_script_entities(
+    time: Res<Time>,
+    config: Res<ScriptGcConfig>,
+    script_registry: Res<crate::script_registry::ScriptRegistry>,
+    despawn_queue: Res<crate::despawn_queue::DespawnQueue>,
+    profiles: Option<Res<crate::lua_frame_budget::LuaSystemProfiles>>,
+    observer_registry: Option<Res<crate::lua_observers::LuaObserverRegistry>>,
+    lua_ctx: Option<Res<crate::lua_integration::LuaScriptContext>>,
+    owned: Query<(Entity, &ScriptOwned)>,
+    mut since_last_scan: Local<std::time::Duration>,
+    mut lua_gc_pending: Local<bool>,
+) {
+    if !config.enabled {
+        return;
+    }
+    // Collected entities are despawned after the scan; their Lua components only give memory
+    // back once the Lua GC has run over the released registry values
+    if *lua_gc_pending {
+        *lua_gc_pending = match &lua_ctx {
+            Some(ctx) => !step_lua_gc(ctx, config.lua_step_kb),
+            None => false,
+        };
+    }
+    *since_last_scan += time.delta();
+    if *since_last_scan < config.interval {
+        return;
+    }
+    *since_last_scan = std::time::Duration::ZERO;
+
+    let mut live: std::collections::HashMap<u64, bool> = std::collections::HashMap::new();
+    let mut collected = 0;
+    for (entity, script_owned) in owned.iter() {
+        if script_owned.spawn_phase != SpawnPhase::Script || script_owned.script_version == 0 {
+            continue;
+        }
+        let alive = *live
+            .entry(script_owned.instance_id)
+            .or_insert_with(|| script_registry.contains_instance(script_owned.instance_id));
+        if alive {
+            continue;
+        }
+        if let (Some(registry), Some(ctx)) = (&observer_registry, &lua_ctx) {
+            registry.remove_observers_for_entity(&ctx.lua, entity);
+        }
+        despawn_queue.queue_despawn(entity);
+        collected += 1;
+    }
+
+    if collected > 0 {
+        warn!(
+            "[SCRIPT_GC] Despawning {} entities owned by script instances that no longer exist",
+            collected
+        );
+        if let Some(profiles) = profiles {
+            profiles.record_gc(collected);
+        }
+        *lua_gc_pending = true;
+    }
+}
+
+/// Run one budgeted incremental GC step on every Lua state
+/// Returns true once every state has finished its collection cycle
+fn step_lua_gc(lua_ctx: &crate::lua_integration::LuaScriptContext, step_kb: i32) -> bool {
+    let mut finished = true;
+    for lua in std::iter::once(lua_ctx.lua.clone()).chain(lua_ctx.sandbox_states()) {
+        lua.expire_registry_values();
+        match lua.gc_step_kbytes(step_kb) {
+            Ok(cycle_done) => finished &= cycle_done,
+            Err(e) => warn!("[SCRIPT_GC] Lua GC step failed: {}", e),
+        }
+    }
+    finished
+}
+
 /// Helper function to despawn all entities owned by a specific script instance
 /// Only despawns entities with SpawnPhase::Script (spawned during script execution)
 /// Entities with SpawnPhase::Runtime (spawned by systems at runtime) are preserved
@@ -73,7 +249,17 @@ pub fn despawn_instance_entities(world: &mut World, instance_id: u64) -> Vec<Ent
     let despawn_queue = world
         .resource::<crate::despawn_queue::DespawnQueue>()
         .clone();
+    // Free Lua observer callbacks registered on the despawned entities
+    let observer_registry = world
+        .get_resource::<crate::lua_observers::LuaObserverRegistry>()
+        .cloned();
+    let lua_ctx = world
+        .get_resource::<crate::lua_integration::LuaScriptContext>()
+        .cloned();
     for entity in &entities_to_despawn {
+        if let (Some(registry), Some(ctx)) = (&observer_registry, &lua_ctx) {
+            registry.remove_observers_for_entity(&ctx.lua, *entity);
+        }
         despawn_queue.queue_despawn(*entity);
         debug!(
             "Queued despawn for entity {:?} owned by instance {} (Script phase)",
@@ -1,14 +1,52 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
+/// What happens to a script's entities when the script is hot-reloaded
+/// Set per script with a metadata comment: `-- @hot_reload: respawn`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HotReloadPolicy {
+    /// Entities kept across reloads (e.g. runtime-spawned or diff-reloaded) keep running
+    #[default]
+    Keep,
+    /// Entities spawned by an older version of the script are despawned
+    RespawnOnChange,
+}
+
+impl HotReloadPolicy {
+    /// Read the policy from a `-- @hot_reload: <policy>` comment in the script source
+    pub fn from_script_source(content: &str) -> Self {
+        for line in content.lines() {
+            let Some(comment) = line.trim().strip_prefix("--") else {
+                continue;
+            };
+            let Some(value) = comment.trim().strip_prefix("@hot_reload:") else {
+                continue;
+            };
+            return match value.trim() {
+                "respawn" => HotReloadPolicy::RespawnOnChange,
+                "keep" => HotReloadPolicy::Keep,
+                other => {
+                    warn!("Unknown @hot_reload policy '{}', expected 'respawn' or 'keep'", other);
+                    HotReloadPolicy::Keep
+                }
+            };
+        }
+        HotReloadPolicy::Keep
+    }
+}
+
 /// Information about a script instance
 #[derive(Clone, Debug)]
 pub struct ScriptInstanceInfo {
     pub instance_id: u64,
     pub script_content: String,
     pub stopped: bool,
+    /// Incremented each time the instance is re-registered with changed content
+    pub script_version: u64,
+    pub hot_reload_policy: HotReloadPolicy,
 }
 
 /// Resource that tracks all loaded script instances for automatic reload
@@ -16,32 +54,42 @@ pub struct ScriptInstanceInfo {
 pub struct ScriptRegistry {
     // Map: script file path -> list of script instances
     scripts: Arc<Mutex<HashMap<PathBuf, Vec<ScriptInstanceInfo>>>>,
+    /// Bumped whenever any instance's version changes (lets reap_stale_script_entities skip idle frames)
+    version_generation: Arc<AtomicU64>,
 }
 
 impl Default for ScriptRegistry {
     fn default() -> Self {
         Self {
             scripts: Arc::new(Mutex::new(HashMap::new())),
+            version_generation: Arc::new(AtomicU64::new(0)),
         }
     }
 }
 
 impl ScriptRegistry {
     /// Register a new script instance
-    /// If an instance with the same ID already exists for this path, it is updated.
+    /// If an instance with the same ID already exists for this path, it is updated
+    /// (and its version incremented when the content changed).
     pub fn register_script(&self, path: PathBuf, instance_id: u64, content: String) {
         let mut scripts = self.scripts.lock().unwrap();
 
         let list = scripts.entry(path.clone()).or_insert_with(Vec::new);
+        let hot_reload_policy = HotReloadPolicy::from_script_source(&content);
 
         // Check for existing instance with same ID
         if let Some(existing) = list.iter_mut().find(|info| info.instance_id == instance_id) {
             // Update existing
+            if existing.script_content != content {
+                existing.script_version += 1;
+                self.version_generation.fetch_add(1, Ordering::SeqCst);
+            }
             existing.script_content = content;
             existing.stopped = false;
+            existing.hot_reload_policy = hot_reload_policy;
             debug!(
-                "Updated script instance {} for path {:?}",
-                instance_id, path
+                "Updated script instance {} for path {:?} (version {})",
+                instance_id, path, existing.script_version
             );
         } else {
             // Add new
@@ -49,6 +97,8 @@ impl ScriptRegistry {
                 instance_id,
                 script_content: content,
                 stopped: false,
+                script_version: 1,
+                hot_reload_policy,
             };
             list.push(info);
             debug!(
@@ -148,6 +198,36 @@ impl ScriptRegistry {
         None
     }
 
+    /// Get the current script version for a specific instance
+    pub fn get_script_version(&self, instance_id: u64) -> Option<u64> {
+        self.find_instance(instance_id, |info| info.script_version)
+    }
+
+    /// Get the hot-reload policy declared by a specific instance's script
+    pub fn get_hot_reload_policy(&self, instance_id: u64) -> HotReloadPolicy {
+        self.find_instance(instance_id, |info| info.hot_reload_policy)
+            .unwrap_or_default()
+    }
+
+    /// Counter bumped whenever any script version changes
+    pub fn version_generation(&self) -> u64 {
+        self.version_generation.load(Ordering::SeqCst)
+    }
+
+    fn find_instance<T>(&self, instance_id: u64, f: impl Fn(&ScriptInstanceInfo) -> T) -> Option<T> {
+        let scripts = self.scripts.lock().unwrap();
+        scripts
+            .values()
+            .flatten()
+            .find(|info| info.instance_id == instance_id)
+            .map(f)
+    }
+
+    /// Whether the instance is still registered (stopped instances count as registered)
+    pub fn contains_instance(&self, instance_id: u64) -> bool {
+        self.find_instance(instance_id, |_| ()).is_some()
+    }
+
     /// Get the script path for a specific instance
     pub fn get_instance_path(&self, instance_id: u64) -> Option<PathBuf> {
         let scripts = self.scripts.lock().unwrap();
@@ -178,3 +258,71 @@ impl ScriptRegistry {
             .collect()
     }
 }
+
+/// Despawn entities spawned by an older version of a script whose policy is
+/// `HotReloadPolicy::RespawnOnChange` (`-- @hot_reload: respawn`)
+pub fn reap_stale_script_entities(
+    script_registry: Res<ScriptRegistry>,
+    despawn_queue: Res<crate::despawn_queue::DespawnQueue>,
+    observer_registry: Option<Res<crate::lua_observers::LuaObserverRegistry>>,
+    lua_ctx: Option<Res<crate::lua_integration::LuaScriptContext>>,
+    owned: Query<(Entity, &crate::script_entities::ScriptOwned)>,
+    mut last_generation: Local<u64>,
+) {
+    let generation = script_registry.version_generation();
+    if generation == *last_generation {
+        return;
+    }
+    *last_generation = generation;
+
+    let mut versions: HashMap<u64, Option<u64>> = HashMap::new();
+    for (entity, script_owned) in owned.iter() {
+        let current = *versions.entry(script_owned.instance_id).or_insert_with(|| {
+            match script_registry.get_hot_reload_policy(script_owned.instance_id) {
+                HotReloadPolicy::RespawnOnChange => {
+                    script_registry.get_script_version(script_owned.instance_id)
+                }
+                HotReloadPolicy::Keep => None,
+            }
+        });
+
+        let Some(current) = current else {
+            continue;
+        };
+        if script_owned.script_version != current {
+            debug!(
+                "Reaping {:?} from script instance {} (version {} != {})",
+                entity, script_owned.instance_id, script_owned.script_version, current
+            );
+            if let (Some(registry), Some(ctx)) = (&observer_registry, &lua_ctx) {
+                registry.remove_observers_for_entity(&ctx.lua, entity);
+            }
+            despawn_queue.queue_despawn(entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_bumps_on_changed_content() {
+        let registry = ScriptRegistry::default();
+        let path = PathBuf::from("assets/scripts/enemy.lua");
+        registry.register_script(path.clone(), 7, "-- @hot_reload: respawn\nspawn({})".to_string());
+        assert_eq!(registry.get_script_version(7), Some(1));
+        assert_eq!(registry.get_hot_reload_policy(7), HotReloadPolicy::RespawnOnChange);
+
+        // Same content (e.g. a re-register without edits) keeps the version
+        registry.register_script(path.clone(), 7, "-- @hot_reload: respawn\nspawn({})".to_string());
+        assert_eq!(registry.get_script_version(7), Some(1));
+        assert_eq!(registry.version_generation(), 0);
+
+        registry.register_script(path, 7, "spawn({ Marker = {} })".to_string());
+        assert_eq!(registry.get_script_version(7), Some(2));
+        assert_eq!(registry.get_hot_reload_policy(7), HotReloadPolicy::Keep);
+        assert_eq!(registry.version_generation(), 1);
+        assert_eq!(registry.get_script_version(99), None);
+    }
+}
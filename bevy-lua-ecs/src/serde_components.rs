@@ -13,6 +13,15 @@ type SerdeResourceHandler = Box<dyn Fn(&LuaValue, &mut World) -> LuaResult<()> +
 /// Function that removes a resource from World
 type ResourceRemovalHandler = Box<dyn Fn(&mut World) + Send + Sync>;
 
+/// Function that encodes Lua component data as bincode bytes
+type BinaryComponentEncoder = Box<dyn Fn(&LuaValue) -> LuaResult<Vec<u8>> + Send + Sync>;
+
+/// Function that decodes bincode bytes and inserts the component
+type BinaryComponentDecoder = Box<dyn Fn(&[u8], &mut EntityWorldMut) -> Result<(), String> + Send + Sync>;
+
+/// Function that decodes bincode bytes into the component's Lua table form
+type BinaryComponentToLua = Box<dyn Fn(&Lua, &[u8]) -> LuaResult<LuaValue> + Send + Sync>;
+
 /// Registry for components and resources that use serde instead of Reflect
 /// This is required for types like `Collider` that implement `Deserialize` but not `Reflect`.
 #[derive(Resource, Default, Clone)]
@@ -22,6 +31,10 @@ pub struct SerdeComponentRegistry {
     resource_removal_handlers: Arc<Mutex<HashMap<String, ResourceRemovalHandler>>>,
     /// Track which resources have been inserted (generic tracking)
     inserted_resources: Arc<Mutex<std::collections::HashSet<String>>>,
+    /// Schemas for components defined from Lua via world:define_component()
+    lua_component_schemas: Arc<Mutex<HashMap<String, LuaComponentSchema>>>,
+    /// bincode encoders/decoders for components registered with register_binary
+    binary_codecs: Arc<Mutex<HashMap<String, (BinaryComponentEncoder, BinaryComponentDecoder, BinaryComponentToLua)>>>,
 }
 
 impl SerdeComponentRegistry {
@@ -51,6 +64,49 @@ impl SerdeComponentRegistry {
             .insert(name, handler);
     }
 
+    /// Register a component that can also be sent as compact bincode bytes
+    /// It is still spawned from Lua tables like `register`; `encode_binary` and `try_insert_binary`
+    /// add a binary wire format for network payloads (several times smaller than JSON)
+    pub fn register_binary<T>(&mut self, name: impl Into<String>)
+    where
+        T: Component + serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let name = name.into();
+        self.register::<T>(name.clone());
+
+        let encoder: BinaryComponentEncoder = Box::new(|data: &LuaValue| {
+            let json_value = serde_json::to_value(data).map_err(|e| {
+                LuaError::SerializeError(format!("Failed to serialize Lua value: {}", e))
+            })?;
+            let component: T = serde_json::from_value(json_value).map_err(|e| {
+                LuaError::DeserializeError(format!("Failed to deserialize component: {}", e))
+            })?;
+            bincode::serialize(&component).map_err(|e| {
+                LuaError::SerializeError(format!("Failed to encode component: {}", e))
+            })
+        });
+        let decoder: BinaryComponentDecoder = Box::new(|bytes: &[u8], entity: &mut EntityWorldMut| {
+            let component: T = bincode::deserialize(bytes)
+                .map_err(|e| format!("Failed to decode component: {}", e))?;
+            entity.insert(component);
+            Ok(())
+        });
+        let to_lua: BinaryComponentToLua = Box::new(|lua: &Lua, bytes: &[u8]| {
+            let component: T = bincode::deserialize(bytes).map_err(|e| {
+                LuaError::DeserializeError(format!("Failed to decode component: {}", e))
+            })?;
+            let json_value = serde_json::to_value(&component).map_err(|e| {
+                LuaError::SerializeError(format!("Failed to serialize component: {}", e))
+            })?;
+            lua.to_value(&json_value)
+        });
+
+        self.binary_codecs
+            .lock()
+            .unwrap()
+            .insert(name, (encoder, decoder, to_lua));
+    }
+
     /// Register a marker component (zero-sized type, no fields)
     pub fn register_marker<T>(&mut self, name: impl Into<String>)
     where
@@ -162,6 +218,38 @@ impl SerdeComponentRegistry {
         }
     }
 
+    /// Check if a component was registered with `register_binary`
+    pub fn is_binary(&self, name: &str) -> bool {
+        self.binary_codecs.lock().unwrap().contains_key(name)
+    }
+
+    /// Encode Lua component data as bincode bytes
+    /// Returns None if the component was not registered with `register_binary`
+    pub fn encode_binary(&self, name: &str, data: &LuaValue) -> Option<LuaResult<Vec<u8>>> {
+        let codecs = self.binary_codecs.lock().unwrap();
+        codecs.get(name).map(|(encode, _, _)| encode(data))
+    }
+
+    /// Decode bincode bytes and insert the component on `entity`
+    /// Returns None if the component was not registered with `register_binary`
+    pub fn try_insert_binary(
+        &self,
+        name: &str,
+        bytes: &[u8],
+        entity: &mut EntityWorldMut,
+    ) -> Option<Result<(), String>> {
+        let codecs = self.binary_codecs.lock().unwrap();
+        codecs.get(name).map(|(_, decode, _)| decode(bytes, entity))
+    }
+
+    /// Decode bincode bytes into the Lua table form of the component, so binary updates can be
+    /// applied like table updates
+    /// Returns None if the component was not registered with `register_binary`
+    pub fn decode_binary_to_lua(&self, name: &str, lua: &Lua, bytes: &[u8]) -> Option<LuaResult<LuaValue>> {
+        let codecs = self.binary_codecs.lock().unwrap();
+        codecs.get(name).map(|(_, _, to_lua)| to_lua(lua, bytes))
+    }
+
     /// Try to insert a resource via serde
     pub fn try_insert_resource(
         &self,
@@ -198,6 +286,223 @@ impl SerdeComponentRegistry {
     pub fn mark_resource_inserted(&self, name: impl Into<String>) {
         self.inserted_resources.lock().unwrap().insert(name.into());
     }
+
+    /// Define (or redefine) a Lua component type with a field schema
+    pub fn define_lua_component(&self, name: impl Into<String>, schema: LuaComponentSchema) {
+        self.lua_component_schemas
+            .lock()
+            .unwrap()
+            .insert(name.into(), schema);
+    }
+
+    /// Get the schema of a Lua-defined component
+    pub fn lua_component_schema(&self, name: &str) -> Option<LuaComponentSchema> {
+        self.lua_component_schemas.lock().unwrap().get(name).cloned()
+    }
+
+    /// Check if a component was defined from Lua
+    pub fn is_lua_defined_component(&self, name: &str) -> bool {
+        self.lua_component_schemas.lock().unwrap().contains_key(name)
+    }
+
+    /// Serialize all Lua-defined components stored on an entity (for saving)
+    /// Each value is read from the Lua state that stored it.
+    pub fn serialize_lua_defined_components(
+        &self,
+        lua_ctx: &crate::lua_integration::LuaScriptContext,
+        components: &crate::components::LuaCustomComponents,
+    ) -> LuaResult<HashMap<String, LuaJsonComponent>> {
+        let schemas = self.lua_component_schemas.lock().unwrap();
+        let mut result = HashMap::new();
+        for (name, key) in &components.components {
+            if schemas.contains_key(name) {
+                let value: LuaValue = lua_ctx.state_for_key(key).registry_value(&**key)?;
+                result.insert(name.clone(), LuaJsonComponent::from_lua_value(&value)?);
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// JSON data of a Lua-defined component (field name -> value)
+/// Lua-defined components are validated and normalized through this type so they always serialize
+/// Reflected as an opaque serde value so saved scenes can carry it (see `LuaDefinedComponents`).
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize, Reflect)]
+#[reflect(opaque)]
+#[reflect(Debug, Default, PartialEq, Serialize, Deserialize, Clone)]
+pub struct LuaJsonComponent(pub HashMap<String, serde_json::Value>);
+
+/// The Lua-defined components of one entity (component name -> data), as a Bevy component
+/// Lua-defined components live in `LuaCustomComponents` at runtime; `persist_entity` adds this
+/// to the saved scene and `load_entity` turns it back into individual Lua components.
+#[derive(Component, Reflect, Debug, Clone, Default, PartialEq)]
+#[reflect(Component, Default)]
+pub struct LuaDefinedComponents(pub HashMap<String, LuaJsonComponent>);
+
+impl LuaJsonComponent {
+    /// Convert a Lua table into component JSON data
+    pub fn from_lua_value(value: &LuaValue) -> LuaResult<Self> {
+        match value {
+            LuaValue::Table(table) if table.is_empty() => Ok(Self::default()),
+            LuaValue::Table(_) => {
+                let json = serde_json::to_value(value).map_err(|e| {
+                    LuaError::SerializeError(format!("Failed to serialize Lua component: {}", e))
+                })?;
+                serde_json::from_value(json).map(Self).map_err(|e| {
+                    LuaError::DeserializeError(format!("Lua component must be a table of named fields: {}", e))
+                })
+            }
+            other => Err(LuaError::RuntimeError(format!(
+                "Lua component data must be a table, got {}",
+                other.type_name()
+            ))),
+        }
+    }
+
+    /// Convert component JSON data back into a Lua table
+    pub fn to_lua_value(&self, lua: &Lua) -> LuaResult<LuaValue> {
+        let table = lua.create_table()?;
+        for (field, value) in &self.0 {
+            table.set(field.as_str(), lua.to_value(value)?)?;
+        }
+        Ok(LuaValue::Table(table))
+    }
+}
+
+/// A field in a Lua-defined component schema
+#[derive(Debug, Clone)]
+pub struct LuaSchemaField {
+    pub name: String,
+    /// Expected type: "number", "integer", "string", "boolean", "table" or "any"
+    pub field_type: String,
+    /// Required fields must be present when the component is set
+    pub required: bool,
+    /// Value used when the field is missing
+    pub default: Option<serde_json::Value>,
+}
+
+/// Schema for a component defined from Lua
+///
+/// ```lua
+/// world:define_component("Health", {
+///     current = "number",                          -- required
+///     max = { type = "number", default = 100 },    -- filled in when missing
+///     label = { type = "string", optional = true },
+/// })
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LuaComponentSchema {
+    pub fields: Vec<LuaSchemaField>,
+}
+
+const LUA_SCHEMA_TYPES: &[&str] = &["number", "integer", "string", "boolean", "table", "any"];
+
+impl LuaComponentSchema {
+    /// Parse a schema table (field name -> type string or { type, default, optional })
+    pub fn from_lua_table(table: &LuaTable) -> LuaResult<Self> {
+        let mut fields = Vec::new();
+        for pair in table.pairs::<String, LuaValue>() {
+            let (name, spec) = pair?;
+            let field = match spec {
+                LuaValue::String(type_name) => LuaSchemaField {
+                    name,
+                    field_type: type_name.to_str()?.to_string(),
+                    required: true,
+                    default: None,
+                },
+                LuaValue::Table(spec) => {
+                    let field_type: Option<String> = spec.get("type")?;
+                    let optional: Option<bool> = spec.get("optional")?;
+                    let default_value: LuaValue = spec.get("default")?;
+                    let default = if default_value.is_nil() {
+                        None
+                    } else {
+                        Some(serde_json::to_value(&default_value).map_err(|e| {
+                            LuaError::SerializeError(format!("Invalid default for field '{}': {}", name, e))
+                        })?)
+                    };
+                    LuaSchemaField {
+                        required: !optional.unwrap_or(false) && default.is_none(),
+                        field_type: field_type.unwrap_or_else(|| "any".to_string()),
+                        name,
+                        default,
+                    }
+                }
+                other => {
+                    return Err(LuaError::RuntimeError(format!(
+                        "define_component: field '{}' must be a type name or a table, got {}",
+                        name,
+                        other.type_name()
+                    )));
+                }
+            };
+
+            if !LUA_SCHEMA_TYPES.contains(&field.field_type.as_str()) {
+                return Err(LuaError::RuntimeError(format!(
+                    "define_component: field '{}' has unknown type '{}' (expected one of {:?})",
+                    field.name, field.field_type, LUA_SCHEMA_TYPES
+                )));
+            }
+            fields.push(field);
+        }
+        fields.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(Self { fields })
+    }
+
+    /// Validate component data against the schema, filling in defaults for missing fields
+    /// Returns the normalized component data
+    pub fn validate(&self, component_name: &str, data: &LuaJsonComponent) -> Result<LuaJsonComponent, String> {
+        let mut normalized = data.clone();
+        for field in &self.fields {
+            match normalized.0.get(&field.name) {
+                None | Some(serde_json::Value::Null) => {
+                    if let Some(default) = &field.default {
+                        normalized.0.insert(field.name.clone(), default.clone());
+                    } else if field.required {
+                        return Err(format!(
+                            "{}: missing required field '{}'",
+                            component_name, field.name
+                        ));
+                    }
+                }
+                Some(value) => {
+                    let matches = match field.field_type.as_str() {
+                        "number" => value.is_number(),
+                        "integer" => value.is_i64() || value.is_u64(),
+                        "string" => value.is_string(),
+                        "boolean" => value.is_boolean(),
+                        "table" => value.is_object() || value.is_array(),
+                        _ => true,
+                    };
+                    if !matches {
+                        return Err(format!(
+                            "{}: field '{}' expected {}, got {}",
+                            component_name, field.name, field.field_type, value
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(normalized)
+    }
+}
+
+/// Validate a component value set from Lua if it is a Lua-defined component
+/// Returns the (possibly normalized) value to store; other components pass through unchanged
+pub fn validate_lua_defined_component(lua: &Lua, name: &str, value: LuaValue) -> LuaResult<LuaValue> {
+    let schema = match lua.app_data_ref::<SerdeComponentRegistry>() {
+        Some(registry) => registry.lua_component_schema(name),
+        None => None,
+    };
+    let Some(schema) = schema else {
+        return Ok(value);
+    };
+
+    let data = LuaJsonComponent::from_lua_value(&value)?;
+    let normalized = schema
+        .validate(name, &data)
+        .map_err(LuaError::RuntimeError)?;
+    normalized.to_lua_value(lua)
 }
 
 /// Macro to create a SerdeComponentRegistry with multiple components
@@ -217,6 +522,96 @@ macro_rules! serde_components {
     }};
 }
 
+/// Type marker used by `register_serde_components!` to pick per-type registration at compile time
+/// (autoref specialization: the `&self` impls on `SerdeTypeRegistration<T>` win when their bounds hold)
+#[doc(hidden)]
+pub struct SerdeTypeRegistration<T>(std::marker::PhantomData<T>);
+
+impl<T> SerdeTypeRegistration<T> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+/// Reflected types are also added to Bevy's `AppTypeRegistry`
+#[doc(hidden)]
+pub trait RegisterReflectedSerdeType {
+    fn register_type(&self, app: &mut App);
+}
+
+impl<T: bevy::reflect::GetTypeRegistration> RegisterReflectedSerdeType for SerdeTypeRegistration<T> {
+    fn register_type(&self, app: &mut App) {
+        app.register_type::<T>();
+    }
+}
+
+/// Types without `Reflect` are only reachable through the serde handler
+#[doc(hidden)]
+pub trait RegisterUnreflectedSerdeType {
+    fn register_type(&self, app: &mut App);
+}
+
+impl<T> RegisterUnreflectedSerdeType for &SerdeTypeRegistration<T> {
+    fn register_type(&self, _app: &mut App) {
+        debug!(
+            "[SERDE_COMPONENTS] {} does not implement Reflect, registered for serde only",
+            std::any::type_name::<T>()
+        );
+    }
+}
+
+/// Passes for types that serialize in both directions
+#[doc(hidden)]
+pub trait CheckSerdeRoundTrip {
+    fn check_serialize(&self) {}
+}
+
+impl<T: serde::Serialize + for<'de> serde::Deserialize<'de>> CheckSerdeRoundTrip for SerdeTypeRegistration<T> {}
+
+/// Fallback that turns a missing `Serialize` impl into a compile-time warning
+#[doc(hidden)]
+pub trait WarnMissingSerialize {
+    #[deprecated(note = "type registered with register_serde_components! does not implement serde::Serialize + serde::Deserialize; it can be spawned from Lua but not saved")]
+    fn check_serialize(&self) {}
+}
+
+impl<T> WarnMissingSerialize for &SerdeTypeRegistration<T> {}
+
+/// Register serde-backed components on an app's `SerdeComponentRegistry`
+///
+/// Each type is registered under its short type name. Types that implement `Reflect` are also
+/// registered with the `AppTypeRegistry`; other types fall back to serde only. A type without
+/// `Serialize` produces a deprecation warning at the call site (`Deserialize` is required).
+///
+/// ```ignore
+/// bevy_lua_ecs::register_serde_components!(app, Collider, AudioSource);
+/// ```
+#[macro_export]
+macro_rules! register_serde_components {
+    ($app:expr, $($ty:ty),* $(,)?) => {{
+        #[allow(unused_imports)]
+        use $crate::serde_components::{
+            CheckSerdeRoundTrip as _, RegisterReflectedSerdeType as _,
+            RegisterUnreflectedSerdeType as _, WarnMissingSerialize as _,
+        };
+        let app: &mut ::bevy::prelude::App = &mut $app;
+        app.init_resource::<$crate::serde_components::SerdeComponentRegistry>();
+        $(
+            app.world_mut()
+                .resource_mut::<$crate::serde_components::SerdeComponentRegistry>()
+                .register::<$ty>(
+                    std::any::type_name::<$ty>()
+                        .split("::")
+                        .last()
+                        .unwrap()
+                );
+            (&$crate::serde_components::SerdeTypeRegistration::<$ty>::new()).register_type(app);
+            (&$crate::serde_components::SerdeTypeRegistration::<$ty>::new()).check_serialize();
+        )*
+    }};
+}
+
 /// Macro to create a SerdeComponentRegistry with multiple resources
 #[macro_export]
 macro_rules! serde_resources {
@@ -233,3 +628,79 @@ macro_rules! serde_resources {
         registry
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Non-reflected component, only available through serde
+    #[derive(Component, serde::Serialize, serde::Deserialize)]
+    struct AudioSource {
+        path: String,
+        volume: f32,
+    }
+
+    #[derive(Component, Reflect, Default, serde::Serialize, serde::Deserialize)]
+    #[reflect(Component)]
+    struct Volume {
+        level: f32,
+    }
+
+    #[test]
+    fn test_register_serde_components_macro() {
+        let mut app = App::new();
+        crate::register_serde_components!(app, AudioSource, Volume);
+
+        let registry = app.world().resource::<SerdeComponentRegistry>();
+        let handlers = registry.component_handlers.lock().unwrap();
+        assert!(handlers.contains_key("AudioSource"));
+        assert!(handlers.contains_key("Volume"));
+
+        let type_registry = app.world().resource::<AppTypeRegistry>().read();
+        assert!(type_registry.get(std::any::TypeId::of::<Volume>()).is_some());
+        assert!(type_registry.get(std::any::TypeId::of::<AudioSource>()).is_none());
+    }
+
+    #[test]
+    fn test_binary_component_round_trip() {
+        let lua = Lua::new();
+        let mut registry = SerdeComponentRegistry::default();
+        registry.register_binary::<AudioSource>("AudioSource");
+        assert!(registry.is_binary("AudioSource"));
+        assert!(registry.component_handlers.lock().unwrap().contains_key("AudioSource"));
+
+        let data: LuaValue = lua
+            .load(r#"return { path = "sounds/jump.ogg", volume = 0.5 }"#)
+            .eval()
+            .unwrap();
+        let bytes = registry.encode_binary("AudioSource", &data).unwrap().unwrap();
+        assert!(bytes.len() < serde_json::to_vec(&data).unwrap().len());
+        assert!(registry.encode_binary("Volume", &data).is_none());
+
+        let mut world = World::new();
+        let mut entity = world.spawn_empty();
+        assert_eq!(registry.try_insert_binary("AudioSource", &bytes, &mut entity), Some(Ok(())));
+        assert!(registry.try_insert_binary("AudioSource", &bytes[..2], &mut entity).unwrap().is_err());
+        let audio = entity.get::<AudioSource>().unwrap();
+        assert_eq!(audio.path, "sounds/jump.ogg");
+        assert_eq!(audio.volume, 0.5);
+
+        let LuaValue::Table(decoded) = registry.decode_binary_to_lua("AudioSource", &lua, &bytes).unwrap().unwrap() else {
+            panic!("binary component should decode to a table");
+        };
+        assert_eq!(decoded.get::<String>("path").unwrap(), "sounds/jump.ogg");
+        assert_eq!(decoded.get::<f32>("volume").unwrap(), 0.5);
+    }
+
+    #[cfg(feature = "bevy_rapier2d")]
+    #[test]
+    fn test_register_serde_components_with_rapier_collider() {
+        use bevy_rapier2d::prelude::Collider;
+
+        let mut app = App::new();
+        crate::register_serde_components!(app, Collider);
+
+        let registry = app.world().resource::<SerdeComponentRegistry>();
+        assert!(registry.component_handlers.lock().unwrap().contains_key("Collider"));
+    }
+}
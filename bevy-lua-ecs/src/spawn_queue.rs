@@ -16,6 +16,42 @@ pub struct SpawnRequest {
     pub spawn_phase: SpawnPhase,
     /// Temporary ID returned to Lua before actual entity is spawned
     pub temp_id: u64,
+    /// Existing entity to load the components into instead of spawning a new one (load_entity's replace)
+    pub replace: Option<Entity>,
+}
+
+/// Batch spawn request: `count` entities sharing one component template
+pub struct BatchSpawnEntry {
+    pub count: u32,
+    pub template: Vec<(String, LuaRegistryKey)>,
+    pub instance_id: Option<u64>,
+    pub spawn_phase: SpawnPhase,
+    /// First temp_id of the contiguous range reserved for this batch
+    pub first_temp_id: u64,
+}
+
+/// One entry in the spawn queue, kept in the order scripts queued them so temp_id references
+/// between single spawns and batches resolve
+pub enum SpawnQueueEntry {
+    Single(SpawnRequest),
+    Batch(BatchSpawnEntry),
+}
+
+impl SpawnQueueEntry {
+    fn as_single_mut(&mut self) -> Option<&mut SpawnRequest> {
+        match self {
+            SpawnQueueEntry::Single(request) => Some(request),
+            SpawnQueueEntry::Batch(_) => None,
+        }
+    }
+}
+
+/// UI node spawn request built by `world:spawn_ui_node` (no reflection involved)
+pub struct UiNodeSpawnRequest {
+    pub props: crate::ui_node_builder::UiNodeProps,
+    pub instance_id: Option<u64>,
+    pub spawn_phase: SpawnPhase,
+    pub temp_id: u64,
 }
 
 /// Observer registration request
@@ -28,7 +64,10 @@ pub struct ObserverRequest {
 /// Resource that holds the spawn queue
 #[derive(Resource, Clone)]
 pub struct SpawnQueue {
-    queue: Arc<Mutex<Vec<SpawnRequest>>>,
+    /// Single and batched spawn requests (one entry per spawn()/spawn_batch() call)
+    queue: Arc<Mutex<Vec<SpawnQueueEntry>>>,
+    /// UI nodes queued by spawn_ui_node
+    ui_node_queue: Arc<Mutex<Vec<UiNodeSpawnRequest>>>,
     /// Entities that were spawned and need to be returned to Lua
     spawned_entities: Arc<Mutex<Vec<Entity>>>,
     /// Mapping from temp_id (returned to Lua) to actual Entity (created during spawn)
@@ -40,13 +79,14 @@ pub struct SpawnQueue {
     observer_queue: Arc<Mutex<Vec<ObserverRequest>>>,
     /// Mapping from temp_id to registered Lua callback registry keys
     /// Structure: temp_id -> Vec<(event_type, LuaRegistryKey)>
-    observer_callbacks: Arc<Mutex<HashMap<u64, Vec<(String, LuaRegistryKey)>>>>,
+    observer_callbacks: Arc<Mutex<HashMap<u64, Vec<(String, crate::lua_observers::LuaObserverCallback)>>>>,
 }
 
 impl Default for SpawnQueue {
     fn default() -> Self {
         Self {
             queue: Arc::new(Mutex::new(Vec::new())),
+            ui_node_queue: Arc::new(Mutex::new(Vec::new())),
             spawned_entities: Arc::new(Mutex::new(Vec::new())),
             temp_id_to_entity: Arc::new(Mutex::new(HashMap::new())),
             next_temp_id: Arc::new(std::sync::atomic::AtomicU64::new(1)), // Start at 1, 0 is reserved
@@ -63,6 +103,12 @@ impl SpawnQueue {
             .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
     }
 
+    /// Reserve a contiguous range of `count` temp IDs, returning the first one
+    pub fn generate_temp_ids(&self, count: u32) -> u64 {
+        self.next_temp_id
+            .fetch_add(count as u64, std::sync::atomic::Ordering::Relaxed)
+    }
+
     /// Add a spawn request with a temp_id
     pub fn queue_spawn(
         &self,
@@ -79,8 +125,31 @@ impl SpawnQueue {
             instance_id,
             spawn_phase,
             temp_id,
+            replace: None,
         };
-        self.queue.lock().unwrap().push(request);
+        self.queue.lock().unwrap().push(SpawnQueueEntry::Single(request));
+    }
+
+    /// Add a request that loads `components` into the existing `target` entity, keeping its id
+    /// The target's persisted components are replaced; hierarchy and script ownership are kept
+    pub fn queue_replace(
+        &self,
+        target: Entity,
+        components: Vec<(String, LuaRegistryKey)>,
+        instance_id: Option<u64>,
+        spawn_phase: SpawnPhase,
+        temp_id: u64,
+    ) {
+        let request = SpawnRequest {
+            components,
+            lua_components: Vec::new(),
+            parent_temp_id: None,
+            instance_id,
+            spawn_phase,
+            temp_id,
+            replace: Some(target),
+        };
+        self.queue.lock().unwrap().push(SpawnQueueEntry::Single(request));
     }
 
     /// Add a spawn request with a parent temp_id (resolved during spawn processing)
@@ -100,15 +169,104 @@ impl SpawnQueue {
             instance_id,
             spawn_phase,
             temp_id,
+            replace: None,
         };
-        self.queue.lock().unwrap().push(request);
+        self.queue.lock().unwrap().push(SpawnQueueEntry::Single(request));
     }
 
-    /// Drain all pending spawn requests
-    pub fn drain(&self) -> Vec<SpawnRequest> {
+    /// Queue a spawn request built from a registered prefab, with optional override tables
+    /// Returns the temp_id of the new entity
+    pub fn spawn_from_prefab(
+        &self,
+        lua: &Lua,
+        prefabs: &crate::prefab_registry::PrefabRegistry,
+        prefab_name: &str,
+        overrides: Option<&LuaTable>,
+        instance_id: Option<u64>,
+        spawn_phase: SpawnPhase,
+    ) -> LuaResult<u64> {
+        let mut components = Vec::new();
+        for (component_name, value) in prefabs.instantiate(lua, prefab_name, overrides)? {
+            let value = crate::serde_components::validate_lua_defined_component(lua, &component_name, value)?;
+            components.push((component_name, lua.create_registry_value(value)?));
+        }
+
+        let temp_id = self.generate_temp_id();
+        self.queue_spawn(components, Vec::new(), instance_id, spawn_phase, temp_id);
+        debug!("[SPAWN_QUEUE] Queued prefab '{}' as temp_id {}", prefab_name, temp_id);
+        Ok(temp_id)
+    }
+
+    /// Queue a spawn request copying the components of an existing entity, with optional
+    /// override tables. Returns the temp_id of the new entity
+    pub fn spawn_inherit(
+        &self,
+        lua: &Lua,
+        world: &World,
+        parent: Entity,
+        overrides: Option<&LuaTable>,
+        instance_id: Option<u64>,
+        spawn_phase: SpawnPhase,
+    ) -> LuaResult<u64> {
+        let mut components = Vec::new();
+        for (component_name, value) in crate::prefab_registry::inherit_components(lua, world, parent, overrides)? {
+            let value = crate::serde_components::validate_lua_defined_component(lua, &component_name, value)?;
+            components.push((component_name, lua.create_registry_value(value)?));
+        }
+
+        let temp_id = self.generate_temp_id();
+        self.queue_spawn(components, Vec::new(), instance_id, spawn_phase, temp_id);
+        debug!("[SPAWN_QUEUE] Queued entity inheriting from {:?} as temp_id {}", parent, temp_id);
+        Ok(temp_id)
+    }
+
+    /// Drain all pending spawn requests and batches, in the order they were queued
+    pub fn drain(&self) -> Vec<SpawnQueueEntry> {
         self.queue.lock().unwrap().drain(..).collect()
     }
 
+    /// Queue `count` entities built from the same component template
+    /// Returns the temp_ids reserved for the batch (first_temp_id..first_temp_id + count)
+    pub fn queue_spawn_batch(
+        &self,
+        count: u32,
+        template: Vec<(String, LuaRegistryKey)>,
+        instance_id: Option<u64>,
+        spawn_phase: SpawnPhase,
+    ) -> std::ops::Range<u64> {
+        let first_temp_id = self.generate_temp_ids(count);
+        self.queue.lock().unwrap().push(SpawnQueueEntry::Batch(BatchSpawnEntry {
+            count,
+            template,
+            instance_id,
+            spawn_phase,
+            first_temp_id,
+        }));
+        first_temp_id..first_temp_id + count as u64
+    }
+
+    /// Queue a UI node built from Rust types, returning its temp_id
+    pub fn queue_ui_node(
+        &self,
+        props: crate::ui_node_builder::UiNodeProps,
+        instance_id: Option<u64>,
+        spawn_phase: SpawnPhase,
+    ) -> u64 {
+        let temp_id = self.generate_temp_id();
+        self.ui_node_queue.lock().unwrap().push(UiNodeSpawnRequest {
+            props,
+            instance_id,
+            spawn_phase,
+            temp_id,
+        });
+        temp_id
+    }
+
+    /// Drain all queued UI nodes
+    pub fn drain_ui_nodes(&self) -> Vec<UiNodeSpawnRequest> {
+        self.ui_node_queue.lock().unwrap().drain(..).collect()
+    }
+
     /// Register a temp_id -> Entity mapping (called when entity is actually spawned)
     pub fn register_entity(&self, temp_id: u64, entity: Entity) {
         self.temp_id_to_entity
@@ -155,7 +313,7 @@ impl SpawnQueue {
     /// Set parent for an existing spawn request (for chainable :with_parent())
     pub fn set_parent(&self, temp_id: u64, parent_temp_id: u64) {
         let mut queue = self.queue.lock().unwrap();
-        for request in queue.iter_mut() {
+        for request in queue.iter_mut().filter_map(SpawnQueueEntry::as_single_mut) {
             if request.temp_id == temp_id {
                 request.parent_temp_id = Some(parent_temp_id);
                 debug!(
@@ -176,7 +334,7 @@ impl SpawnQueue {
         data: LuaRegistryKey,
     ) {
         let mut queue = self.queue.lock().unwrap();
-        for request in queue.iter_mut() {
+        for request in queue.iter_mut().filter_map(SpawnQueueEntry::as_single_mut) {
             if request.temp_id == temp_id {
                 // Add to existing spawn request's components
                 // Note: This will be processed after the original components, so it can override/modify them
@@ -215,7 +373,23 @@ impl SpawnQueue {
         callbacks
             .entry(temp_id)
             .or_default()
-            .push((event_type, callback));
+            .push((event_type, crate::lua_observers::LuaObserverCallback::new(callback)));
+    }
+
+    /// Register one Lua callback for several event types (for chainable :observe_any())
+    /// Every event type shares the same registry key
+    pub fn register_observer_callback_any(
+        &self,
+        temp_id: u64,
+        event_types: Vec<String>,
+        callback: LuaRegistryKey,
+    ) {
+        let key = Arc::new(callback);
+        let mut callbacks = self.observer_callbacks.lock().unwrap();
+        let observers = callbacks.entry(temp_id).or_default();
+        for event_type in event_types {
+            observers.push((event_type, crate::lua_observers::LuaObserverCallback::shared(key.clone())));
+        }
     }
 
     /// Drain observer queue
@@ -225,7 +399,7 @@ impl SpawnQueue {
 
     /// Take observer callbacks for an entity by temp_id (removes them from storage)
     /// LuaRegistryKey doesn't implement Clone, so we must take ownership
-    pub fn take_observer_callbacks(&self, temp_id: u64) -> Vec<(String, LuaRegistryKey)> {
+    pub fn take_observer_callbacks(&self, temp_id: u64) -> Vec<(String, crate::lua_observers::LuaObserverCallback)> {
         self.observer_callbacks
             .lock()
             .unwrap()
@@ -234,7 +408,7 @@ impl SpawnQueue {
     }
 
     /// Take all observer callbacks (for processing during spawn)
-    pub fn take_all_observer_callbacks(&self) -> HashMap<u64, Vec<(String, LuaRegistryKey)>> {
+    pub fn take_all_observer_callbacks(&self) -> HashMap<u64, Vec<(String, crate::lua_observers::LuaObserverCallback)>> {
         std::mem::take(&mut *self.observer_callbacks.lock().unwrap())
     }
 }
@@ -221,6 +221,37 @@ pub fn call_static_method_global(
     }
 }
 
+/// Function signature for the auto-generated asset creation dispatcher
+/// Takes: Lua, World, asset type name (short or full path), props table
+pub type AssetCreateDispatchFn = fn(&Lua, &mut World, &str, LuaTable) -> LuaResult<LuaValue>;
+
+/// Global dispatch function for world:create_asset (adds to Assets<T> via SystemState)
+static ASSET_CREATE_DISPATCHER: std::sync::OnceLock<AssetCreateDispatchFn> = std::sync::OnceLock::new();
+
+/// Set the global asset creation dispatcher
+/// This should be called by the parent crate's initialization code
+/// to register the auto-generated dispatch_create_asset function
+pub fn set_asset_create_dispatcher(dispatcher: AssetCreateDispatchFn) {
+    let _ = ASSET_CREATE_DISPATCHER.set(dispatcher);
+}
+
+/// Call the registered asset creation dispatcher
+/// Returns an error if no dispatcher has been set
+pub fn call_create_asset_global(
+    lua: &Lua,
+    world: &mut World,
+    type_name: &str,
+    props: LuaTable,
+) -> LuaResult<LuaValue> {
+    if let Some(dispatcher) = ASSET_CREATE_DISPATCHER.get() {
+        dispatcher(lua, world, type_name, props)
+    } else {
+        Err(LuaError::RuntimeError(format!(
+            "Asset creation dispatch not configured. Call set_asset_create_dispatcher() at initialization."
+        )))
+    }
+}
+
 /// This is the main infrastructure for exposing SystemParam methods to Lua
 #[derive(Resource, Clone, Default)]
 pub struct LuaSystemParamRegistry {
@@ -0,0 +1,204 @@
+// In-process test harness for Lua scripts (enabled with the `testing` feature)
+//
+// Builds a headless App with LuaSpawnPlugin, runs Lua source through the normal script
+// pipeline and exposes assertions over the resulting World.
+
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::asset::AssetPlugin;
+use bevy::prelude::*;
+use mlua::prelude::*;
+use std::path::PathBuf;
+
+use crate::components::LuaCustomComponents;
+use crate::lua_integration::{LuaScriptContext, LuaSpawnPlugin};
+use crate::script_entities::ScriptInstance;
+use crate::script_registry::ScriptRegistry;
+
+/// Frames run after each script so queued spawns, updates and systems settle
+pub const DEFAULT_SETTLE_FRAMES: usize = 3;
+
+/// Headless Bevy app with `LuaSpawnPlugin` for testing scripts in-process
+pub struct TestLuaApp {
+    pub app: App,
+    /// Frames run by `run_script` after executing the script
+    pub settle_frames: usize,
+    script_count: usize,
+}
+
+impl Default for TestLuaApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestLuaApp {
+    /// Create the app (no window, no rendering) and run startup so the Lua context exists
+    pub fn new() -> Self {
+        Self::with_setup(|_| {})
+    }
+
+    /// Like `new`, but `setup` runs before `LuaSpawnPlugin` is added
+    /// (register reflected types, insert resources, ...)
+    pub fn with_setup(setup: impl FnOnce(&mut App)) -> Self {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins.set(ScheduleRunnerPlugin::run_once()));
+        app.add_plugins(AssetPlugin::default());
+        setup(&mut app);
+        app.add_plugins(LuaSpawnPlugin);
+        app.update();
+
+        Self {
+            app,
+            settle_frames: DEFAULT_SETTLE_FRAMES,
+            script_count: 0,
+        }
+    }
+
+    /// Execute Lua source as a new script instance, run `settle_frames` updates and
+    /// return assertions over the resulting world
+    pub fn run_script(&mut self, lua_code: &str) -> Result<TestAssertions<'_>, String> {
+        self.execute(lua_code)?;
+        self.update(self.settle_frames);
+        Ok(self.assertions())
+    }
+
+    /// Execute Lua source without running any frames; returns the script instance id
+    pub fn execute(&mut self, lua_code: &str) -> Result<u64, String> {
+        let world = self.app.world();
+        let lua_ctx = world.resource::<LuaScriptContext>().clone();
+        let script_instance = world.resource::<ScriptInstance>().clone();
+        let script_registry = world.resource::<ScriptRegistry>().clone();
+
+        self.script_count += 1;
+        let name = format!("test_{}.lua", self.script_count);
+        let path = PathBuf::from("scripts").join(&name);
+
+        lua_ctx
+            .execute_script(lua_code, &name, path, &script_instance, &script_registry)
+            .map_err(|e| format!("{}", e))
+    }
+
+    /// Run `frames` App::update() calls
+    pub fn update(&mut self, frames: usize) {
+        for _ in 0..frames {
+            self.app.update();
+        }
+    }
+
+    /// Assertions over the current world state
+    pub fn assertions(&self) -> TestAssertions<'_> {
+        TestAssertions { app: &self.app }
+    }
+
+    /// Read a Lua global (e.g. a value a script stored for the test to inspect)
+    pub fn lua_global<T: FromLua>(&self, name: &str) -> LuaResult<T> {
+        self.app.world().resource::<LuaScriptContext>().lua.globals().get(name)
+    }
+}
+
+/// Assertions over a `TestLuaApp` world; each method panics with a descriptive message
+pub struct TestAssertions<'a> {
+    app: &'a App,
+}
+
+impl TestAssertions<'_> {
+    /// Assert the entity has a reflected Rust component or a Lua-defined component named `component`
+    pub fn assert_entity_has_component(&self, entity_id: u64, component: &str) -> &Self {
+        assert!(
+            self.entity_has_component(entity_id, component),
+            "expected entity {} to have component '{}'",
+            entity_id,
+            component
+        );
+        self
+    }
+
+    /// Assert the entity does not have the component
+    pub fn assert_entity_lacks_component(&self, entity_id: u64, component: &str) -> &Self {
+        assert!(
+            !self.entity_has_component(entity_id, component),
+            "expected entity {} not to have component '{}'",
+            entity_id,
+            component
+        );
+        self
+    }
+
+    /// Assert a reflected resource matches `expected`
+    /// Only the fields present in `expected` are compared (recursively)
+    pub fn assert_resource_value(&self, resource: &str, expected: serde_json::Value) -> &Self {
+        let actual = self
+            .resource_json(resource)
+            .unwrap_or_else(|| panic!("resource '{}' not found (is it registered for reflection?)", resource));
+        assert!(
+            json_contains(&actual, &expected),
+            "resource '{}' mismatch\n  expected (subset): {}\n  actual: {}",
+            resource,
+            expected,
+            actual
+        );
+        self
+    }
+
+    /// Assert how many entities have the component
+    pub fn assert_entity_count_with(&self, component: &str, expected: usize) -> &Self {
+        let world = self.app.world();
+        let count = world
+            .iter_entities()
+            .filter(|entity| self.entity_has_component(entity.id().to_bits(), component))
+            .count();
+        assert_eq!(
+            count, expected,
+            "expected {} entities with '{}', found {}",
+            expected, component, count
+        );
+        self
+    }
+
+    /// Whether the entity has a reflected or Lua-defined component with this name
+    pub fn entity_has_component(&self, entity_id: u64, component: &str) -> bool {
+        let world = self.app.world();
+        let Ok(entity) = world.get_entity(Entity::from_bits(entity_id)) else {
+            return false;
+        };
+
+        if entity
+            .get::<LuaCustomComponents>()
+            .is_some_and(|custom| custom.components.contains_key(component))
+        {
+            return true;
+        }
+
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        type_registry
+            .get_with_short_type_path(component)
+            .or_else(|| type_registry.get_with_type_path(component))
+            .and_then(|registration| registration.data::<ReflectComponent>())
+            .is_some_and(|reflect_component| reflect_component.contains(entity))
+    }
+
+    /// A reflected resource as JSON (same shape scripts see)
+    pub fn resource_json(&self, resource: &str) -> Option<serde_json::Value> {
+        let world = self.app.world();
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        let reflect_resource = type_registry
+            .get_with_short_type_path(resource)
+            .or_else(|| type_registry.get_with_type_path(resource))
+            .and_then(|registration| registration.data::<ReflectResource>())?;
+        let value = reflect_resource.reflect(world).ok()?;
+        crate::event_reader::reflect_to_json(value.as_partial_reflect(), &type_registry)
+    }
+}
+
+/// Whether `actual` contains every field of `expected` (arrays and scalars compare exactly,
+/// numbers compare as f64 so 10 matches 10.0)
+fn json_contains(actual: &serde_json::Value, expected: &serde_json::Value) -> bool {
+    use serde_json::Value;
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => expected
+            .iter()
+            .all(|(key, value)| actual.get(key).is_some_and(|a| json_contains(a, value))),
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        (a, b) => a == b,
+    }
+}
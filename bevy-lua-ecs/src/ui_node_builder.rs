@@ -0,0 +1,332 @@
+//! Direct Bevy UI node construction from a flat Lua props table
+//!
+//! ```lua
+//! local panel = world:spawn_ui_node({
+//!     width = "100%",
+//!     height = 64,                 -- numbers are pixels
+//!     padding = "8px",
+//!     flex_direction = "row",
+//!     justify_content = "space_between",
+//!     background_color = "#202030cc",
+//!     children = { title_id, close_button_id },
+//! })
+//! ```
+//!
+//! The props are converted straight into `Node` / `BackgroundColor` values, so no reflection
+//! is involved. Unknown keys and invalid values are reported as errors.
+
+use bevy::prelude::*;
+use mlua::prelude::*;
+
+/// Keys accepted by `world:spawn_ui_node`
+pub const UI_NODE_KEYS: &[&str] = &[
+    "width",
+    "height",
+    "min_width",
+    "min_height",
+    "max_width",
+    "max_height",
+    "left",
+    "right",
+    "top",
+    "bottom",
+    "padding",
+    "margin",
+    "row_gap",
+    "column_gap",
+    "flex_grow",
+    "flex_shrink",
+    "flex_direction",
+    "justify_content",
+    "align_items",
+    "position_type",
+    "display",
+    "background_color",
+    "children",
+];
+
+/// UI components built from a props table, plus the child entity ids to attach
+#[derive(Debug, Clone, Default)]
+pub struct UiNodeProps {
+    pub node: Node,
+    pub background_color: Option<BackgroundColor>,
+    /// Child entity ids (temp ids from spawn() or real entity bits)
+    pub children: Vec<u64>,
+}
+
+impl UiNodeProps {
+    /// Build UI node props from a Lua table, rejecting unknown keys
+    pub fn from_lua_table(props: &LuaTable) -> LuaResult<Self> {
+        let mut result = UiNodeProps::default();
+
+        for pair in props.pairs::<LuaValue, LuaValue>() {
+            let (key, value) = pair?;
+            let LuaValue::String(key) = key else {
+                return Err(LuaError::RuntimeError(format!(
+                    "spawn_ui_node: property keys must be strings, got {}",
+                    key.type_name()
+                )));
+            };
+            let key = key.to_str()?.to_string();
+            let node = &mut result.node;
+
+            match key.as_str() {
+                "width" => node.width = parse_val(&key, &value)?,
+                "height" => node.height = parse_val(&key, &value)?,
+                "min_width" => node.min_width = parse_val(&key, &value)?,
+                "min_height" => node.min_height = parse_val(&key, &value)?,
+                "max_width" => node.max_width = parse_val(&key, &value)?,
+                "max_height" => node.max_height = parse_val(&key, &value)?,
+                "left" => node.left = parse_val(&key, &value)?,
+                "right" => node.right = parse_val(&key, &value)?,
+                "top" => node.top = parse_val(&key, &value)?,
+                "bottom" => node.bottom = parse_val(&key, &value)?,
+                "padding" => node.padding = parse_rect(&key, &value)?,
+                "margin" => node.margin = parse_rect(&key, &value)?,
+                "row_gap" => node.row_gap = parse_val(&key, &value)?,
+                "column_gap" => node.column_gap = parse_val(&key, &value)?,
+                "flex_grow" => node.flex_grow = parse_number(&key, &value)?,
+                "flex_shrink" => node.flex_shrink = parse_number(&key, &value)?,
+                "flex_direction" => {
+                    node.flex_direction = match parse_name(&key, &value)?.as_str() {
+                        "row" => FlexDirection::Row,
+                        "column" => FlexDirection::Column,
+                        "row_reverse" => FlexDirection::RowReverse,
+                        "column_reverse" => FlexDirection::ColumnReverse,
+                        other => return Err(invalid_choice(&key, other, "row, column, row_reverse, column_reverse")),
+                    }
+                }
+                "justify_content" => {
+                    node.justify_content = match parse_name(&key, &value)?.as_str() {
+                        "default" => JustifyContent::Default,
+                        "start" => JustifyContent::Start,
+                        "end" => JustifyContent::End,
+                        "flex_start" => JustifyContent::FlexStart,
+                        "flex_end" => JustifyContent::FlexEnd,
+                        "center" => JustifyContent::Center,
+                        "stretch" => JustifyContent::Stretch,
+                        "space_between" => JustifyContent::SpaceBetween,
+                        "space_around" => JustifyContent::SpaceAround,
+                        "space_evenly" => JustifyContent::SpaceEvenly,
+                        other => return Err(invalid_choice(
+                            &key,
+                            other,
+                            "default, start, end, flex_start, flex_end, center, stretch, space_between, space_around, space_evenly",
+                        )),
+                    }
+                }
+                "align_items" => {
+                    node.align_items = match parse_name(&key, &value)?.as_str() {
+                        "default" => AlignItems::Default,
+                        "start" => AlignItems::Start,
+                        "end" => AlignItems::End,
+                        "flex_start" => AlignItems::FlexStart,
+                        "flex_end" => AlignItems::FlexEnd,
+                        "center" => AlignItems::Center,
+                        "baseline" => AlignItems::Baseline,
+                        "stretch" => AlignItems::Stretch,
+                        other => return Err(invalid_choice(
+                            &key,
+                            other,
+                            "default, start, end, flex_start, flex_end, center, baseline, stretch",
+                        )),
+                    }
+                }
+                "position_type" => {
+                    node.position_type = match parse_name(&key, &value)?.as_str() {
+                        "relative" => PositionType::Relative,
+                        "absolute" => PositionType::Absolute,
+                        other => return Err(invalid_choice(&key, other, "relative, absolute")),
+                    }
+                }
+                "display" => {
+                    node.display = match parse_name(&key, &value)?.as_str() {
+                        "flex" => Display::Flex,
+                        "grid" => Display::Grid,
+                        "block" => Display::Block,
+                        "none" => Display::None,
+                        other => return Err(invalid_choice(&key, other, "flex, grid, block, none")),
+                    }
+                }
+                "background_color" => {
+                    result.background_color = Some(BackgroundColor(parse_color(&key, &value)?))
+                }
+                "children" => {
+                    let LuaValue::Table(children) = value else {
+                        return Err(LuaError::RuntimeError(
+                            "spawn_ui_node: 'children' must be an array of entity ids".to_string(),
+                        ));
+                    };
+                    result.children = children.sequence_values::<u64>().collect::<LuaResult<_>>()?;
+                }
+                unknown => {
+                    return Err(LuaError::RuntimeError(format!(
+                        "spawn_ui_node: unknown property '{}' (expected one of: {})",
+                        unknown,
+                        UI_NODE_KEYS.join(", ")
+                    )))
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Parse a UI length: numbers are pixels; strings accept "auto", "50%", "10px", "10vw", "10vh", "10vmin", "10vmax"
+pub fn parse_val(key: &str, value: &LuaValue) -> LuaResult<Val> {
+    match value {
+        LuaValue::Integer(i) => Ok(Val::Px(*i as f32)),
+        LuaValue::Number(n) => Ok(Val::Px(*n as f32)),
+        LuaValue::String(s) => {
+            let s = s.to_str()?;
+            let s = s.trim();
+            if s == "auto" {
+                return Ok(Val::Auto);
+            }
+            let (number, constructor): (&str, fn(f32) -> Val) = if let Some(n) = s.strip_suffix('%') {
+                (n, Val::Percent)
+            } else if let Some(n) = s.strip_suffix("px") {
+                (n, Val::Px)
+            } else if let Some(n) = s.strip_suffix("vmin") {
+                (n, Val::VMin)
+            } else if let Some(n) = s.strip_suffix("vmax") {
+                (n, Val::VMax)
+            } else if let Some(n) = s.strip_suffix("vw") {
+                (n, Val::Vw)
+            } else if let Some(n) = s.strip_suffix("vh") {
+                (n, Val::Vh)
+            } else {
+                (s, Val::Px)
+            };
+            number.trim().parse::<f32>().map(constructor).map_err(|_| {
+                LuaError::RuntimeError(format!("spawn_ui_node: invalid length '{}' for '{}'", s, key))
+            })
+        }
+        other => Err(LuaError::RuntimeError(format!(
+            "spawn_ui_node: '{}' must be a number or length string, got {}",
+            key,
+            other.type_name()
+        ))),
+    }
+}
+
+/// Parse padding/margin: a single length for all sides, or { left, right, top, bottom }
+fn parse_rect(key: &str, value: &LuaValue) -> LuaResult<UiRect> {
+    let LuaValue::Table(table) = value else {
+        return Ok(UiRect::all(parse_val(key, value)?));
+    };
+
+    let mut rect = UiRect::ZERO;
+    for pair in table.pairs::<String, LuaValue>() {
+        let (side, side_value) = pair?;
+        let val = parse_val(&format!("{}.{}", key, side), &side_value)?;
+        match side.as_str() {
+            "left" => rect.left = val,
+            "right" => rect.right = val,
+            "top" => rect.top = val,
+            "bottom" => rect.bottom = val,
+            other => return Err(invalid_choice(key, other, "left, right, top, bottom")),
+        }
+    }
+    Ok(rect)
+}
+
+/// Parse a color: "#rrggbb" / "#rrggbbaa" hex string, or { r, g, b, a } / { r, g, b, a } array (0..1)
+pub fn parse_color(key: &str, value: &LuaValue) -> LuaResult<Color> {
+    match value {
+        LuaValue::String(s) => {
+            let s = s.to_str()?;
+            Srgba::hex(s.trim_start_matches('#'))
+                .map(Color::Srgba)
+                .map_err(|e| LuaError::RuntimeError(format!("spawn_ui_node: invalid color '{}' for '{}': {}", &*s, key, e)))
+        }
+        LuaValue::Table(table) => {
+            let component = |name: &str, index: i64, default: f32| -> LuaResult<f32> {
+                if let Some(v) = table.get::<Option<f32>>(name)? {
+                    return Ok(v);
+                }
+                Ok(table.get::<Option<f32>>(index)?.unwrap_or(default))
+            };
+            Ok(Color::srgba(
+                component("r", 1, 0.0)?,
+                component("g", 2, 0.0)?,
+                component("b", 3, 0.0)?,
+                component("a", 4, 1.0)?,
+            ))
+        }
+        other => Err(LuaError::RuntimeError(format!(
+            "spawn_ui_node: '{}' must be a hex string or color table, got {}",
+            key,
+            other.type_name()
+        ))),
+    }
+}
+
+fn parse_number(key: &str, value: &LuaValue) -> LuaResult<f32> {
+    match value {
+        LuaValue::Integer(i) => Ok(*i as f32),
+        LuaValue::Number(n) => Ok(*n as f32),
+        other => Err(LuaError::RuntimeError(format!(
+            "spawn_ui_node: '{}' must be a number, got {}",
+            key,
+            other.type_name()
+        ))),
+    }
+}
+
+fn parse_name(key: &str, value: &LuaValue) -> LuaResult<String> {
+    match value {
+        LuaValue::String(s) => Ok(s.to_str()?.to_lowercase()),
+        other => Err(LuaError::RuntimeError(format!(
+            "spawn_ui_node: '{}' must be a string, got {}",
+            key,
+            other.type_name()
+        ))),
+    }
+}
+
+fn invalid_choice(key: &str, value: &str, expected: &str) -> LuaError {
+    LuaError::RuntimeError(format!(
+        "spawn_ui_node: invalid value '{}' for '{}' (expected one of: {})",
+        value, key, expected
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ui_node_props_from_table() {
+        let lua = Lua::new();
+        let props: LuaTable = lua
+            .load(
+                r##"return {
+                    width = "50%", height = 40, padding = "4px",
+                    margin = { left = 2, top = "auto" },
+                    flex_direction = "column", justify_content = "space_between",
+                    background_color = "#ff0000", children = { 7, 9 },
+                }"##,
+            )
+            .eval()
+            .unwrap();
+        let ui = UiNodeProps::from_lua_table(&props).unwrap();
+        assert_eq!(ui.node.width, Val::Percent(50.0));
+        assert_eq!(ui.node.height, Val::Px(40.0));
+        assert_eq!(ui.node.padding, UiRect::all(Val::Px(4.0)));
+        assert_eq!(ui.node.margin.left, Val::Px(2.0));
+        assert_eq!(ui.node.margin.top, Val::Auto);
+        assert_eq!(ui.node.flex_direction, FlexDirection::Column);
+        assert_eq!(ui.node.justify_content, JustifyContent::SpaceBetween);
+        assert_eq!(ui.background_color, Some(BackgroundColor(Color::srgb(1.0, 0.0, 0.0))));
+        assert_eq!(ui.children, vec![7, 9]);
+
+        let unknown: LuaTable = lua.load("return { widht = 10 }").eval().unwrap();
+        let err = UiNodeProps::from_lua_table(&unknown).unwrap_err().to_string();
+        assert!(err.contains("unknown property 'widht'"));
+
+        let bad_value: LuaTable = lua.load("return { flex_direction = 'diagonal' }").eval().unwrap();
+        assert!(UiNodeProps::from_lua_table(&bad_value).is_err());
+    }
+}
@@ -0,0 +1,290 @@
+//! Undo support for Lua level editors
+//!
+//! ```lua
+//! world:begin_undo_frame()
+//! world:snapshot_entity(wall)          -- state before the edit
+//! wall:set({ Transform = { translation = { x = 10, y = 0, z = 0 } } })
+//! world:end_undo_frame()
+//!
+//! world:undo()                         -- puts `wall` back where it was
+//! ```
+//!
+//! Snapshots hold a `DynamicScene` of the entity's reflected components (hierarchy and script
+//! ownership are left out, as with `persist_entity`). Lua-defined components are not included.
+//! Restoring happens in `apply_entity_restores`, which overwrites the snapshotted components,
+//! removes reflected components added since, and respawns the entity if it was despawned.
+
+use bevy::ecs::entity::EntityHashMap;
+use bevy::ecs::reflect::ReflectComponent;
+use bevy::prelude::*;
+use bevy::scene::DynamicScene;
+use mlua::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+/// Default number of undo frames kept by `UndoStack`
+pub const DEFAULT_UNDO_DEPTH: usize = 50;
+
+/// Opaque Lua userdata returned by `world:snapshot_entity`
+#[derive(Clone)]
+pub struct EntityStateSnapshot {
+    pub entity: Entity,
+    pub scene: Arc<DynamicScene>,
+}
+
+impl LuaUserData for EntityStateSnapshot {
+    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+        methods.add_method("entity", |_, this, ()| Ok(this.entity.to_bits()));
+    }
+}
+
+/// Snapshot the reflected components of `entity`
+pub fn snapshot_entity_state(world: &World, entity: Entity) -> Result<EntityStateSnapshot, String> {
+    let scene = crate::entity_persistence::extract_entity_scene(world, entity)?;
+    Ok(EntityStateSnapshot {
+        entity,
+        scene: Arc::new(scene),
+    })
+}
+
+/// Stack of undo frames, each holding the state of the entities snapshotted while it was open
+/// Only the oldest frames are dropped once `max_depth` is reached
+#[derive(Resource, Clone)]
+pub struct UndoStack {
+    frames: Arc<Mutex<Vec<HashMap<Entity, Arc<DynamicScene>>>>>,
+    open_frame: Arc<Mutex<Option<HashMap<Entity, Arc<DynamicScene>>>>>,
+    /// Restores queued by `restore_entity` / `undo`, applied by `apply_entity_restores`
+    pending: Arc<Mutex<Vec<EntityStateSnapshot>>>,
+    max_depth: usize,
+}
+
+impl Default for UndoStack {
+    fn default() -> Self {
+        Self::with_max_depth(DEFAULT_UNDO_DEPTH)
+    }
+}
+
+impl UndoStack {
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        Self {
+            frames: Arc::new(Mutex::new(Vec::new())),
+            open_frame: Arc::new(Mutex::new(None)),
+            pending: Arc::new(Mutex::new(Vec::new())),
+            max_depth,
+        }
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Start collecting snapshots into a new frame (an already open frame is closed first)
+    pub fn begin_frame(&self) {
+        self.end_frame();
+        *self.open_frame.lock().unwrap() = Some(HashMap::new());
+    }
+
+    /// Record a snapshot in the open frame, if any
+    /// The first snapshot of an entity in a frame wins, so undo returns to the state at its start
+    pub fn record(&self, snapshot: &EntityStateSnapshot) {
+        if let Some(frame) = self.open_frame.lock().unwrap().as_mut() {
+            frame
+                .entry(snapshot.entity)
+                .or_insert_with(|| snapshot.scene.clone());
+        }
+    }
+
+    /// Close the open frame and push it on the stack; empty frames are discarded
+    /// Returns false if no frame was open
+    pub fn end_frame(&self) -> bool {
+        let Some(frame) = self.open_frame.lock().unwrap().take() else {
+            return false;
+        };
+        if !frame.is_empty() {
+            let mut frames = self.frames.lock().unwrap();
+            frames.push(frame);
+            if frames.len() > self.max_depth {
+                let excess = frames.len() - self.max_depth;
+                frames.drain(..excess);
+            }
+        }
+        true
+    }
+
+    /// Pop the latest frame and queue all of its snapshots for restoring
+    /// Returns false when there is nothing to undo
+    pub fn undo(&self) -> bool {
+        let Some(frame) = self.frames.lock().unwrap().pop() else {
+            return false;
+        };
+        let mut pending = self.pending.lock().unwrap();
+        pending.extend(
+            frame
+                .into_iter()
+                .map(|(entity, scene)| EntityStateSnapshot { entity, scene }),
+        );
+        true
+    }
+
+    /// Queue a single snapshot for restoring
+    pub fn queue_restore(&self, snapshot: EntityStateSnapshot) {
+        self.pending.lock().unwrap().push(snapshot);
+    }
+
+    /// Number of closed frames on the stack
+    pub fn len(&self) -> usize {
+        self.frames.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn take_pending(&self) -> Vec<EntityStateSnapshot> {
+        std::mem::take(&mut *self.pending.lock().unwrap())
+    }
+}
+
+/// Exclusive system applying restores queued by `world:restore_entity` and `world:undo`
+pub fn apply_entity_restores(world: &mut World) {
+    let Some(undo_stack) = world.get_resource::<UndoStack>().cloned() else {
+        return;
+    };
+    for snapshot in undo_stack.take_pending() {
+        if let Err(e) = restore_entity_state(world, &snapshot) {
+            warn!("[UNDO] Failed to restore entity {:?}: {}", snapshot.entity, e);
+        }
+    }
+}
+
+/// Write `snapshot` back onto its entity, respawning it if it no longer exists
+/// Returns the entity that now holds the restored state
+pub fn restore_entity_state(world: &mut World, snapshot: &EntityStateSnapshot) -> Result<Entity, String> {
+    let Some(scene_entity) = snapshot.scene.entities.first() else {
+        return Err("snapshot contains no entity".to_string());
+    };
+    let target = if world.get_entity(snapshot.entity).is_ok() {
+        snapshot.entity
+    } else {
+        world.spawn_empty().id()
+    };
+
+    remove_components_missing_from(world, target, scene_entity)?;
+
+    let mut entity_map = EntityHashMap::default();
+    entity_map.insert(scene_entity.entity, target);
+    snapshot
+        .scene
+        .write_to_world(world, &mut entity_map)
+        .map_err(|e| e.to_string())?;
+    Ok(target)
+}
+
+/// Remove reflected components that were added to `target` after the snapshot was taken
+fn remove_components_missing_from(
+    world: &mut World,
+    target: Entity,
+    scene_entity: &bevy::scene::DynamicEntity,
+) -> Result<(), String> {
+    let kept: HashSet<std::any::TypeId> = scene_entity
+        .components
+        .iter()
+        .filter_map(|component| component.get_represented_type_info())
+        .map(|info| info.type_id())
+        .collect();
+    let skipped = [
+        std::any::TypeId::of::<ChildOf>(),
+        std::any::TypeId::of::<Children>(),
+        std::any::TypeId::of::<crate::script_entities::ScriptOwned>(),
+    ];
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let entity_ref = world.get_entity(target).map_err(|e| e.to_string())?;
+    let to_remove: Vec<ReflectComponent> = entity_ref
+        .archetype()
+        .components()
+        .filter_map(|component_id| world.components().get_info(component_id)?.type_id())
+        .filter(|type_id| !kept.contains(type_id) && !skipped.contains(type_id))
+        .filter_map(|type_id| type_registry.get(type_id)?.data::<ReflectComponent>().cloned())
+        .collect();
+
+    let mut entity_mut = world.entity_mut(target);
+    for reflect_component in to_remove {
+        reflect_component.remove(&mut entity_mut);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Transform>();
+            registry.register::<Vec3>();
+            registry.register::<Quat>();
+            registry.register::<Name>();
+        }
+        world
+    }
+
+    #[test]
+    fn test_undo_restores_frame_state() {
+        let mut world = test_world();
+        let entity = world.spawn(Transform::from_xyz(1.0, 0.0, 0.0)).id();
+        let stack = UndoStack::default();
+
+        stack.begin_frame();
+        stack.record(&snapshot_entity_state(&world, entity).unwrap());
+        world.get_mut::<Transform>(entity).unwrap().translation.x = 5.0;
+        // A later snapshot in the same frame doesn't replace the first one
+        stack.record(&snapshot_entity_state(&world, entity).unwrap());
+        world.entity_mut(entity).insert(Name::new("added"));
+        assert!(stack.end_frame());
+        assert_eq!(stack.len(), 1);
+
+        assert!(stack.undo());
+        assert!(!stack.undo());
+        apply_entity_restores_with(&mut world, &stack);
+
+        assert_eq!(world.get::<Transform>(entity).unwrap().translation.x, 1.0);
+        assert!(world.get::<Name>(entity).is_none());
+    }
+
+    #[test]
+    fn test_restore_respawns_despawned_entity() {
+        let mut world = test_world();
+        let entity = world.spawn(Transform::from_xyz(2.0, 0.0, 0.0)).id();
+        let snapshot = snapshot_entity_state(&world, entity).unwrap();
+        world.despawn(entity);
+
+        let restored = restore_entity_state(&mut world, &snapshot).unwrap();
+        assert_eq!(world.get::<Transform>(restored).unwrap().translation.x, 2.0);
+    }
+
+    #[test]
+    fn test_max_depth_drops_oldest_frames() {
+        let mut world = test_world();
+        let entity = world.spawn(Transform::default()).id();
+        let stack = UndoStack::with_max_depth(2);
+        for _ in 0..3 {
+            stack.begin_frame();
+            stack.record(&snapshot_entity_state(&world, entity).unwrap());
+            stack.end_frame();
+        }
+        // Frames without snapshots aren't kept
+        stack.begin_frame();
+        stack.end_frame();
+        assert_eq!(stack.len(), 2);
+    }
+
+    fn apply_entity_restores_with(world: &mut World, stack: &UndoStack) {
+        world.insert_resource(stack.clone());
+        apply_entity_restores(world);
+    }
+}
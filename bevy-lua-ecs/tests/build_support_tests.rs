@@ -0,0 +1,299 @@
+//! Tests for the build script's code generation helpers
+//! build.rs can't run `#[cfg(test)]` tests, so the helpers live in build_support/ and are
+//! included here as modules
+
+#[path = "../build_support/signatures.rs"]
+#[allow(dead_code)]
+mod signatures;
+
+use signatures::*;
+
+#[path = "../build_support/file_cache.rs"]
+#[allow(dead_code)]
+mod file_cache;
+
+use file_cache::*;
+
+#[path = "../build_support/derives.rs"]
+#[allow(dead_code)]
+mod derives;
+
+use derives::*;
+
+#[path = "../build_support/type_paths.rs"]
+#[allow(dead_code)]
+mod type_paths;
+
+use type_paths::*;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::time::Duration;
+
+fn return_type(source: &str) -> syn::ReturnType {
+    syn::parse_str::<syn::Signature>(source).unwrap().output
+}
+
+#[test]
+fn test_returns_self_or_type() {
+    let returns = |sig: &str| returns_self_or_type(&return_type(sig), "GameConfig");
+    assert_eq!(returns("fn new() -> Self"), Some(SelfReturn::Plain));
+    assert_eq!(returns("fn new() -> GameConfig"), Some(SelfReturn::Plain));
+    assert_eq!(returns("fn new() -> crate::config::GameConfig"), Some(SelfReturn::Plain));
+    assert_eq!(returns("fn parse_config(s: &str) -> Result<Self, String>"), Some(SelfReturn::Result));
+    assert_eq!(returns("fn load() -> std::result::Result<GameConfig, Error>"), Some(SelfReturn::Result));
+    assert_eq!(returns("fn find(id: u32) -> Option<Self>"), Some(SelfReturn::Option));
+    assert_eq!(returns("fn count() -> Result<u32, String>"), None);
+    assert_eq!(returns("fn reset()"), None);
+
+    // Only an exact last path segment counts
+    assert_eq!(returns("fn all() -> Vec<Self>"), None);
+    assert_eq!(returns("fn builder() -> Option<GameConfigBuilder>"), None);
+    assert_eq!(returns("fn builder() -> GameConfigBuilder"), None);
+    assert_eq!(returns("fn wrapped() -> Result<Vec<GameConfig>, String>"), None);
+    assert_eq!(returns("fn boxed() -> Box<GameConfig>"), None);
+
+    assert!(returns_result(Some("Result < u32 , String >")));
+    assert!(returns_result(Some("std :: io :: Result < () >")));
+    assert!(!returns_result(Some("Vec < Result < u32 , String > >")));
+    assert!(!returns_result(None));
+}
+
+#[test]
+fn test_result_constructor_propagates_parse_errors() {
+    let sig = "fn parse_config(s: &str) -> Result<Self, String>";
+    let returns = returns_self_or_type(&return_type(sig), "GameConfig").unwrap();
+    let call = quote::quote! { my_game::GameConfig::parse_config(s) };
+    let code = unwrap_constructor_call(returns, call, "my_game::GameConfig", "parse_config").to_string();
+    assert!(code.starts_with("my_game :: GameConfig :: parse_config (s) . map_err"));
+    assert!(code.contains("mlua :: Error :: RuntimeError"));
+    assert!(code.ends_with("?"));
+
+    let code = unwrap_constructor_call(SelfReturn::Option, quote::quote! { find(id) }, "GameConfig", "find").to_string();
+    assert!(code.contains("ok_or_else") && code.contains("returned None"));
+    let code = unwrap_constructor_call(SelfReturn::Plain, quote::quote! { new() }, "GameConfig", "new").to_string();
+    assert_eq!(code, "new ()");
+}
+
+#[test]
+fn test_result_methods_raise_lua_errors() {
+    let code = propagate_result_error(Some("Result < u32 , String >"), "GameConfig", "difficulty").to_string();
+    assert!(code.starts_with(". map_err"));
+    assert!(code.contains("mlua :: Error :: RuntimeError"));
+    assert!(propagate_result_error(Some("u32"), "GameConfig", "difficulty").is_empty());
+    assert!(propagate_result_error(None, "GameConfig", "reset").is_empty());
+}
+
+#[test]
+fn test_builder_return_kind() {
+    assert_eq!(builder_return_kind(Some("Self"), "Mesh"), Some(BuilderReturn::Owned));
+    assert_eq!(builder_return_kind(Some("Mesh"), "Mesh"), Some(BuilderReturn::Owned));
+    assert_eq!(builder_return_kind(Some("& mut Self"), "Mesh"), Some(BuilderReturn::MutRef));
+    assert_eq!(builder_return_kind(Some("& 'a mut Self"), "Mesh"), Some(BuilderReturn::MutRef));
+    assert_eq!(builder_return_kind(Some("& Self"), "Mesh"), None);
+    assert_eq!(builder_return_kind(Some("Option < Self >"), "Mesh"), None);
+    assert_eq!(builder_return_kind(None, "Mesh"), None);
+}
+
+#[test]
+fn test_builder_args_convert_to_declared_types() {
+    let binding = |args: &[(&str, &str)]| {
+        let args: Vec<_> = args.iter().map(|(name, ty)| (name.to_string(), ty.to_string())).collect();
+        builder_call_args(&args).map(|(parameter, call)| (parameter.to_string(), call.to_string()))
+    };
+
+    assert_eq!(binding(&[]), Some(("_args : ()".to_string(), "".to_string())));
+    assert_eq!(binding(&[("gravity", "f32")]), Some(("arg0 : f32".to_string(), "arg0".to_string())));
+    assert_eq!(
+        binding(&[("x", "f32"), ("label", "& str"), ("tags", "& [u32]"), ("seen", "& mut HashSet < u32 >")]),
+        Some((
+            "(arg0 , arg1 , arg2 , mut arg3) : (f32 , String , Vec < u32 > , HashSet < u32 >)".to_string(),
+            "arg0 , & arg1 , & arg2 , & mut arg3".to_string()
+        ))
+    );
+    assert_eq!(
+        binding(&[("name", "& 'a str")]),
+        Some(("arg0 : String".to_string(), "& arg0".to_string()))
+    );
+
+    // `impl Trait` arguments can't be named, so the builder isn't bound
+    assert_eq!(binding(&[("x", "f32"), ("name", "impl Into < String >")]), None);
+    assert_eq!(binding(&[("f", "& impl Fn ()")]), None);
+}
+
+/// Scan `path` for `pub struct` names through the cache
+fn scan_structs(
+    entries: &mut HashMap<String, FileScanEntry<String>>,
+    stats: &mut FileScanStats,
+    path: &std::path::Path,
+    context: u64,
+) -> Vec<String> {
+    scan_file_cached(entries, &mut HashSet::new(), stats, path, context, |source, found| {
+        found.extend(source.split("pub struct ").skip(1).map(|rest| rest.trim_end_matches(';').to_string()));
+    })
+}
+
+/// Overwrite `path` with `contents`, then put its modification time back to `modified`
+fn rewrite_keeping_mtime(path: &std::path::Path, contents: &str, modified: std::time::SystemTime) {
+    fs::write(path, contents).unwrap();
+    fs::File::options().write(true).open(path).unwrap().set_modified(modified).unwrap();
+}
+
+#[test]
+fn test_scan_file_cached_skips_unchanged_files() {
+    let path = std::env::temp_dir().join(format!("file_cache_unchanged_{}.rs", std::process::id()));
+    fs::write(&path, "pub struct Level;").unwrap();
+
+    let mut entries = HashMap::new();
+    let mut stats = FileScanStats::default();
+    let mut seen = HashSet::new();
+    assert_eq!(scan_structs(&mut entries, &mut stats, &path, 0), ["Level"]);
+    // Same stamp and context: results come from the cache
+    let cached = scan_file_cached(&mut entries, &mut seen, &mut stats, &path, 0, |_, _| panic!("re-parsed"));
+    assert_eq!(cached, ["Level"]);
+    // A different context forces a re-parse
+    assert_eq!(scan_structs(&mut entries, &mut stats, &path, 1), ["Level"]);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!((stats.reused, stats.parsed), (1, 2));
+    assert!(seen.contains(&path.to_string_lossy().to_string()));
+}
+
+#[test]
+fn test_scan_file_cached_detects_edits_within_the_same_second() {
+    let path = std::env::temp_dir().join(format!("file_cache_edits_{}.rs", std::process::id()));
+    fs::write(&path, "pub struct Level;").unwrap();
+    let modified = std::time::UNIX_EPOCH + Duration::new(1_700_000_000, 100_000_000);
+    fs::File::options().write(true).open(&path).unwrap().set_modified(modified).unwrap();
+
+    let mut entries = HashMap::new();
+    let mut stats = FileScanStats::default();
+    assert_eq!(scan_structs(&mut entries, &mut stats, &path, 0), ["Level"]);
+
+    // Same mtime, different size
+    rewrite_keeping_mtime(&path, "pub struct Chapter;", modified);
+    assert_eq!(scan_structs(&mut entries, &mut stats, &path, 0), ["Chapter"]);
+
+    // Same size, mtime differs only below a second
+    rewrite_keeping_mtime(&path, "pub struct Episode;", modified + Duration::from_millis(5));
+    assert_eq!(scan_structs(&mut entries, &mut stats, &path, 0), ["Episode"]);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!((stats.reused, stats.parsed), (0, 3));
+}
+
+#[test]
+fn test_hash_map_context_ignores_insertion_order() {
+    let forward: HashMap<String, String> = (0..16).map(|i| (format!("T{i}"), format!("c::T{i}"))).collect();
+    let backward: HashMap<String, String> = (0..16).rev().map(|i| (format!("T{i}"), format!("c::T{i}"))).collect();
+    assert_eq!(hash_map_context(&forward), hash_map_context(&backward));
+
+    let mut changed = forward.clone();
+    changed.insert("T0".to_string(), "c::prelude::T0".to_string());
+    assert_ne!(hash_map_context(&forward), hash_map_context(&changed));
+}
+
+fn first_enum(source: &str) -> syn::ItemEnum {
+    let file = syn::parse_file(source).unwrap();
+    file.items
+        .into_iter()
+        .find_map(|item| match item {
+            syn::Item::Enum(item_enum) => Some(item_enum),
+            _ => None,
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_component_enum_derives() {
+    let settable = first_enum(
+        "#[derive(Component, Reflect, Default, Clone, Copy, Debug)]\n\
+         pub enum HandBone { #[default] Palm, Wrist }",
+    );
+    let derives = parse_component_enum_derives(&settable).unwrap();
+    assert!(derives.has_debug);
+    assert!(derives.string_enum);
+
+    // Without Reflect/Default the enum is still discovered, but can't be set from a string
+    let read_only = first_enum("#[derive(bevy::prelude::Component, Clone)] pub enum Mode { A, B }");
+    let derives = parse_component_enum_derives(&read_only).unwrap();
+    assert!(!derives.has_debug);
+    assert!(!derives.string_enum);
+
+    let not_component = first_enum("#[derive(Reflect, Default, Clone)] pub enum Plain { #[default] A }");
+    assert!(parse_component_enum_derives(&not_component).is_none());
+}
+
+#[test]
+fn test_enum_setter_arm_uses_parse_enum_from_string() {
+    let arm = generate_enum_setter_arm("HandBone", "my_crate::hands::HandBone")
+        .unwrap()
+        .to_string();
+    assert!(arm.starts_with("\"HandBone\" =>"));
+    assert!(arm.contains("parse_enum_from_string :: < my_crate :: hands :: HandBone >"));
+    assert!(arm.contains("entity_mut . insert"));
+
+    assert!(generate_enum_setter_arm("Bad", "not a path").is_none());
+}
+
+#[test]
+fn test_resolve_option_type_path() {
+    assert_eq!(
+        resolve_short_type_to_full_path("Option < Vec3 >").as_deref(),
+        Some("core::option::Option<bevy::math::Vec3>")
+    );
+    assert_eq!(
+        resolve_short_type_to_full_path("Option<f32>").as_deref(),
+        Some("core::option::Option<f32>")
+    );
+    assert!(resolve_short_type_to_full_path("Option<Unknown>").is_none());
+}
+
+#[test]
+fn test_path_overrides_rewrite_module_prefix() {
+    let manifest: toml::Value = toml::from_str(
+        r#"
+        [package.metadata.lua_path_overrides]
+        "hello_game" = "hello"
+        "hello_game::components" = "hello::components"
+        "#,
+    )
+    .unwrap();
+    let overrides = parse_path_overrides(&manifest);
+    assert_eq!(overrides[0].0, "hello_game::components");
+
+    assert_eq!(
+        apply_path_override("hello_game::components::Player", &overrides).as_deref(),
+        Some("hello::components::Player")
+    );
+    assert_eq!(
+        apply_path_override("hello_game::ui::Hud", &overrides).as_deref(),
+        Some("hello::ui::Hud")
+    );
+    assert_eq!(apply_path_override("hello_gamepad::Pad", &overrides), None);
+    assert_eq!(apply_path_override("bevy_ui::Node", &overrides), None);
+}
+
+#[test]
+fn test_reflect_component_attribute_detection() {
+    let source = r#"
+        #[derive(Component, Reflect)]
+        #[reflect(Component, Default)]
+        pub struct Reflected(pub Entity);
+
+        #[derive(Component, Reflect)]
+        #[reflect(Default)]
+        pub struct NotReflectedComponent(pub Entity);
+
+        #[derive(Component)]
+        pub enum Plain { A }
+    "#;
+
+    let reflected = parse_reflect_component_types_from_source(source);
+    assert!(reflected.contains("Reflected"));
+    assert!(!reflected.contains("NotReflectedComponent"));
+    assert!(!reflected.contains("Plain"));
+
+    let item: syn::ItemStruct =
+        syn::parse_str("#[reflect(bevy::prelude::Component)] pub struct Qualified(pub Entity);").unwrap();
+    assert!(has_reflect_component_attr(&item.attrs));
+}
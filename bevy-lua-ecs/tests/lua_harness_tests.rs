@@ -0,0 +1,206 @@
+//! Integration tests using the `TestLuaApp` harness (requires the `testing` feature)
+
+use bevy::prelude::*;
+use bevy_lua_ecs::*;
+use serde_json::json;
+
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+struct Score {
+    value: i32,
+}
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+struct PathNode {
+    waypoints: Vec<Vec3>,
+}
+
+/// Exported with `#[lua_export]` - no manual `register_resource` call
+#[derive(Resource, Default)]
+struct Inventory {
+    items: Vec<String>,
+}
+
+#[lua_export(name = "PlayerInventory")]
+impl Inventory {
+    pub fn add_item(&mut self, item: &str) -> usize {
+        self.items.push(item.to_string());
+        self.items.len()
+    }
+
+    pub fn first_item(&self) -> Option<String> {
+        self.items.first().cloned()
+    }
+
+    /// Not public, so not exported
+    #[allow(dead_code)]
+    fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+/// List item without `ReflectDefault`, so a Lua array can't grow a `Vec<Stop>`
+#[derive(Reflect, Debug, PartialEq)]
+struct Stop {
+    id: u32,
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Route {
+    stops: Vec<Stop>,
+}
+
+fn app_with_score() -> TestLuaApp {
+    TestLuaApp::with_setup(|app| {
+        app.register_type::<Score>();
+        app.init_resource::<Score>();
+    })
+}
+
+#[test]
+fn test_spawned_entity_has_rust_and_lua_components() {
+    let mut test = TestLuaApp::new();
+    test.run_script(r#"
+        spawn({ Transform = {}, Health = { current = 10 } })
+        register_system("FindPlayer", function(world)
+            for _, e in ipairs(world:query({"Health"})) do
+                _G.player = e:id()
+            end
+        end)
+    "#).unwrap();
+
+    let player: u64 = test.lua_global("player").unwrap();
+    test.assertions()
+        .assert_entity_has_component(player, "Transform")
+        .assert_entity_has_component(player, "Health")
+        .assert_entity_lacks_component(player, "Velocity");
+}
+
+#[test]
+fn test_resource_value_set_from_lua() {
+    let mut test = app_with_score();
+    test.run_script(r#"
+        register_system("AddScore", function(world)
+            world:set_resource("Score", { value = 10 })
+            return true
+        end)
+    "#)
+    .unwrap()
+    .assert_resource_value("Score", json!({ "value": 10 }));
+}
+
+#[test]
+fn test_entity_count_after_despawn() {
+    let mut test = TestLuaApp::new();
+    test.run_script(r#"
+        for i = 1, 3 do
+            spawn({ Transform = {}, Enemy = { index = i } })
+        end
+    "#)
+    .unwrap()
+    .assert_entity_count_with("Enemy", 3);
+
+    test.run_script(r#"
+        register_system("KillTwo", function(world)
+            local enemies = world:query({"Enemy"})
+            enemies[1]:despawn()
+            enemies[2]:despawn()
+            return true
+        end)
+    "#)
+    .unwrap()
+    .assert_entity_count_with("Enemy", 1);
+}
+
+#[test]
+fn test_script_errors_are_reported() {
+    let mut test = TestLuaApp::new();
+    assert!(test.run_script("this is not lua").is_err());
+    assert!(test.run_script("error('boom')").unwrap_err().contains("boom"));
+}
+
+#[test]
+fn test_systems_run_once_per_update() {
+    let mut test = TestLuaApp::new();
+    test.settle_frames = 0;
+    test.run_script(r#"
+        _G.frames = 0
+        register_system("CountFrames", function(world)
+            _G.frames = _G.frames + 1
+        end)
+    "#).unwrap();
+
+    test.update(5);
+    let frames: u32 = test.lua_global("frames").unwrap();
+    assert_eq!(frames, 5);
+}
+
+#[test]
+fn test_vec_field_updates_through_component_update_queue() {
+    let mut test = TestLuaApp::with_setup(|app| {
+        app.register_type::<PathNode>();
+    });
+    let node = test
+        .app
+        .world_mut()
+        .spawn(PathNode { waypoints: vec![Vec3::ZERO, Vec3::ONE, Vec3::X] })
+        .id();
+
+    test.run_script(r#"
+        register_system("Reroute", function(world)
+            for _, e in ipairs(world:query({"PathNode"})) do
+                e:set({ PathNode = { waypoints = { { x = 1, y = 2, z = 3 }, { x = -4, y = 0.5, z = 0 } } } })
+            end
+            return true
+        end)
+    "#).unwrap();
+
+    let waypoints = &test.app.world().get::<PathNode>(node).unwrap().waypoints;
+    assert_eq!(waypoints, &vec![Vec3::new(1.0, 2.0, 3.0), Vec3::new(-4.0, 0.5, 0.0)]);
+}
+
+#[test]
+fn test_lua_export_methods_are_callable_from_lua() {
+    let mut test = TestLuaApp::with_setup(|app| {
+        app.init_resource::<Inventory>();
+    });
+    test.run_script(r#"
+        register_system("UseInventory", function(world)
+            _G.count = world:call_resource_method("PlayerInventory", "add_item", "sword")
+            _G.first = world:call_resource_method("PlayerInventory", "first_item")
+            _G.private_hidden = not pcall(function()
+                world:call_resource_method("PlayerInventory", "clear")
+            end)
+            return true
+        end)
+    "#).unwrap();
+
+    assert_eq!(test.lua_global::<usize>("count").unwrap(), 1);
+    assert_eq!(test.lua_global::<String>("first").unwrap(), "sword");
+    assert!(test.lua_global::<bool>("private_hidden").unwrap(), "Private methods must not be exported");
+    assert_eq!(test.app.world().resource::<Inventory>().items, vec!["sword".to_string()]);
+}
+
+#[test]
+fn test_vec_field_without_default_items_is_not_truncated() {
+    let mut test = TestLuaApp::with_setup(|app| {
+        app.register_type::<Route>();
+    });
+    let route = test.app.world_mut().spawn(Route { stops: vec![Stop { id: 1 }] }).id();
+
+    // Growing to two items needs Stop::default(); the update must fail instead of
+    // keeping only the first array element
+    test.run_script(r#"
+        register_system("Extend", function(world)
+            for _, e in ipairs(world:query({"Route"})) do
+                e:set({ Route = { stops = { { id = 5 }, { id = 6 } } } })
+            end
+            return true
+        end)
+    "#).unwrap();
+
+    let stops = &test.app.world().get::<Route>(route).unwrap().stops;
+    assert_eq!(stops, &vec![Stop { id: 1 }]);
+}
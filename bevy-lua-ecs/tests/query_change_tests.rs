@@ -354,3 +354,301 @@ fn test_query_removed_filter() {
     let success: bool = test.app.world().get_resource::<LuaScriptContext>().unwrap().lua.globals().get("removed_test_success").unwrap_or(false);
     assert!(success, "Test script did not detect the removed component via query({{ removed = {{...}} }})");
 }
+
+#[test]
+fn test_query_builder_filter_changed() {
+    let mut test = TestApp::new();
+
+    // 1. Spawn two marked entities - only one will have its Transform mutated
+    test.execute_script(r#"
+        spawn({
+            Transform = { translation = {x=0, y=0, z=0} },
+            MyMarker = { name = "moved" }
+        })
+        spawn({
+            Transform = { translation = {x=5, y=0, z=0} },
+            MyMarker = { name = "still" }
+        })
+    "#).unwrap();
+    test.update();
+
+    // 2. Register system that uses the chainable builder with filter_changed
+    test.execute_script(r#"
+        register_system("CheckFilterChanged", function(world)
+            local ok, err = pcall(function()
+                return world:query_builder():filter_changed("")
+            end)
+            assert(not ok, "Expected filter_changed('') to raise a Lua error")
+            assert(tostring(err):find("filter_changed"), "Expected a descriptive error, got: " .. tostring(err))
+
+            for _, method in ipairs({"filter_changed", "filter_added"}) do
+                local ok, err = pcall(function()
+                    local builder = world:query_builder()
+                    return world:query(builder[method](builder, "NotAComponent"))
+                end)
+                assert(not ok, "Expected " .. method .. "('NotAComponent') to raise a Lua error")
+                assert(tostring(err):find(method .. ": unknown component type 'NotAComponent'", 1, true),
+                    "Expected an unknown component error, got: " .. tostring(err))
+            end
+
+            local builder = world:query_builder():with("MyMarker"):filter_changed("Transform")
+            local entities = world:query(builder)
+
+            if not _G.builder_warmed_up then
+                _G.builder_warmed_up = true
+                return
+            end
+
+            if _G.builder_mutated and #entities > 0 then
+                assert(#entities == 1, "Expected 1 changed entity, got " .. #entities)
+                assert(entities[1]:is_changed("Transform"), "Expected Transform to be flagged as changed")
+                _G.builder_success = true
+            end
+        end)
+    "#).unwrap();
+    test.update(); // Warmup
+
+    // 3. Mutate only the first entity's Transform
+    test.execute_script(r#"
+        register_system("MoveOnce", function(world)
+            local entities = world:query({"Transform", "MyMarker"})
+            for _, e in ipairs(entities) do
+                if e:get("MyMarker").name == "moved" then
+                    e:set({ Transform = { translation = {x=1, y=0, z=0} } })
+                end
+            end
+            _G.builder_mutated = true
+            return true
+        end)
+    "#).unwrap();
+
+    for _ in 0..4 {
+        test.update();
+    }
+
+    let success: bool = test.app.world().get_resource::<LuaScriptContext>().unwrap().lua.globals().get("builder_success").unwrap_or(false);
+    assert!(success, "filter_changed builder query did not return only the mutated entity");
+}
+
+#[test]
+fn test_query_builder_within_radius() {
+    let mut test = TestApp::new();
+
+    // GlobalTransform is set directly since MinimalPlugins has no transform propagation
+    for x in [0.0, 3.0, 50.0] {
+        test.app.world_mut().spawn((
+            Transform::from_xyz(x, 4.0, 0.0),
+            GlobalTransform::from_translation(Vec3::new(x, 4.0, 0.0)),
+        ));
+    }
+
+    test.execute_script(r#"
+        register_system("CheckWithinRadius", function(world)
+            local ok, err = pcall(function()
+                return world:query_builder():within_radius(0, 0, -1)
+            end)
+            assert(not ok and tostring(err):find("within_radius"), "Expected a negative radius to be rejected")
+
+            _G.all_count = #world:query({"Transform"})
+            _G.nearby_count = #world:query(world:query_builder():with("Transform"):within_radius(0, 4, 5))
+            _G.none_count = #world:query(world:query_builder():with("Transform"):within_radius(-100, 0, 1))
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+    test.update();
+
+    let lua = test.app.world().get_resource::<LuaScriptContext>().unwrap().lua.clone();
+    assert_eq!(lua.globals().get::<u32>("all_count").unwrap(), 3);
+    assert_eq!(lua.globals().get::<u32>("nearby_count").unwrap(), 2);
+    assert_eq!(lua.globals().get::<u32>("none_count").unwrap(), 0);
+}
+
+#[test]
+fn test_query_builder_where_field() {
+    let mut test = TestApp::new();
+
+    for x in [-5.0, 0.0, 5.0] {
+        test.app.world_mut().spawn(Transform::from_xyz(x, 0.0, 0.0));
+    }
+    test.execute_script(r#"
+        spawn({ Enemy = { health = 10, state = "patrol" } })
+        spawn({ Enemy = { health = 0, state = "patrol" } })
+        spawn({ Enemy = { health = 3, state = "chase" } })
+    "#).unwrap();
+    test.update();
+
+    test.execute_script(r#"
+        register_system("CheckWhereField", function(world)
+            assert(not pcall(function() return world:query_builder():where_field("Enemy.health", "=~", 0) end),
+                "Expected an unknown operator to be rejected")
+            assert(not pcall(function() return world:query_builder():where_field("Enemy.state", ">", "a") end),
+                "Expected an ordering operator on a string to be rejected")
+
+            local enemies = world:query_builder():with("Enemy")
+            _G.alive_count = #world:query(enemies:where_field("Enemy.health", ">", 0))
+            _G.alive_patrol_count = #world:query(enemies:where_field("Enemy.health", ">", 0):where_field("Enemy.state", "==", "patrol"))
+            _G.not_patrol_count = #world:query(enemies:where_field("Enemy.state", "!=", "patrol"))
+            _G.right_count = #world:query(world:query_builder():with("Transform"):where_field("Transform.translation.x", ">=", 0))
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+    test.update();
+
+    let lua = test.app.world().get_resource::<LuaScriptContext>().unwrap().lua.clone();
+    assert_eq!(lua.globals().get::<u32>("alive_count").unwrap(), 2);
+    assert_eq!(lua.globals().get::<u32>("alive_patrol_count").unwrap(), 1);
+    assert_eq!(lua.globals().get::<u32>("not_patrol_count").unwrap(), 1);
+    assert_eq!(lua.globals().get::<u32>("right_count").unwrap(), 2);
+}
+
+#[test]
+fn test_snapshot_flush_all_batches_updates() {
+    let mut test = TestApp::new();
+
+    test.execute_script(r#"
+        spawn({
+            Transform = { translation = {x=0, y=0, z=0} },
+            MyMarker = { name = "batched" }
+        })
+    "#).unwrap();
+    test.update();
+
+    // Write back a Rust component and two Lua components in one batch
+    test.execute_script(r#"
+        register_system("FlushAllOnce", function(world)
+            local entities = world:query({"Transform", "MyMarker"})
+            for _, e in ipairs(entities) do
+                e:flush_all({
+                    Transform = { translation = {x=3, y=4, z=0} },
+                    MyMarker = { name = "flushed" },
+                    Health = { value = 42 }
+                })
+                -- Pending batched values are visible before the queue is processed
+                assert(e:get("Health").value == 42, "Expected read-through of batched Health")
+            end
+            return true
+        end)
+
+        register_system("CheckFlushAll", function(world)
+            local entities = world:query({"Transform", "MyMarker", "Health"})
+            for _, e in ipairs(entities) do
+                local t = e:get("Transform")
+                if t.translation.x == 3 and t.translation.y == 4
+                    and e:get("MyMarker").name == "flushed"
+                    and e:get("Health").value == 42 then
+                    _G.flush_all_success = true
+                end
+            end
+        end)
+    "#).unwrap();
+
+    for _ in 0..4 {
+        test.update();
+    }
+
+    let success: bool = test.app.world().get_resource::<LuaScriptContext>().unwrap().lua.globals().get("flush_all_success").unwrap_or(false);
+    assert!(success, "flush_all did not apply all batched component updates");
+}
+
+#[test]
+fn test_spawn_batch_spawns_template_entities() {
+    let mut test = TestApp::new();
+
+    test.execute_script(r#"
+        _G.batch_ids = spawn_batch(5, {
+            Transform = { translation = {x=1, y=2, z=3} },
+            BatchMarker = { hp = 10 }
+        })
+    "#).unwrap();
+    test.update();
+
+    test.execute_script(r#"
+        register_system("CountBatch", function(world)
+            local entities = world:query({"Transform", "BatchMarker"})
+            _G.batch_count = #entities
+            _G.batch_hp_ok = true
+            for _, e in ipairs(entities) do
+                if e:get("BatchMarker").hp ~= 10 then _G.batch_hp_ok = false end
+            end
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+
+    let lua = test.app.world().get_resource::<LuaScriptContext>().unwrap().lua.clone();
+    let ids: mlua::Table = lua.globals().get("batch_ids").unwrap();
+    assert_eq!(ids.raw_len(), 5);
+    let count: i64 = lua.globals().get("batch_count").unwrap_or(0);
+    assert_eq!(count, 5, "Expected 5 batch-spawned entities");
+    let hp_ok: bool = lua.globals().get("batch_hp_ok").unwrap_or(false);
+    assert!(hp_ok, "Batch-spawned Lua components should copy the template");
+}
+
+#[derive(Component)]
+struct BatchTarget(Entity);
+
+#[test]
+fn test_spawn_batch_resolves_temp_ids_in_queue_order() {
+    let mut test = TestApp::new();
+    test.app
+        .world_mut()
+        .resource_mut::<ComponentRegistry>()
+        .register_entity_component::<BatchTarget, _>("BatchTarget", BatchTarget);
+
+    // The batch template points at an earlier spawn; the later spawn is parented to a batch member
+    test.execute_script(r#"
+        _G.target_id = spawn({ Transform = {} }):id()
+        _G.batch_ids = spawn_batch(3, { Transform = {}, BatchTarget = { entity = _G.target_id } })
+        spawn({ Transform = {} }):with_parent(_G.batch_ids[1])
+    "#).unwrap();
+    test.update();
+
+    let spawn_queue = test.app.world().resource::<SpawnQueue>().clone();
+    let lua = test.app.world().resource::<LuaScriptContext>().lua.clone();
+    let target = spawn_queue.resolve_entity(lua.globals().get::<u64>("target_id").unwrap());
+    let batch_ids: mlua::Table = lua.globals().get("batch_ids").unwrap();
+    let first_member = spawn_queue.resolve_entity(batch_ids.get::<u64>(1).unwrap());
+
+    let world = test.app.world_mut();
+    let mut targets = world.query::<&BatchTarget>();
+    let targets: Vec<Entity> = targets.iter(world).map(|t| t.0).collect();
+    assert_eq!(targets.len(), 3);
+    assert!(targets.iter().all(|t| *t == target), "Batch template should resolve the earlier spawn's temp_id");
+
+    let children = world.get::<Children>(first_member).expect("Later spawn should be parented to the batch member");
+    assert_eq!(children.len(), 1);
+}
+
+#[test]
+fn test_query_count_matches_query() {
+    let mut test = TestApp::new();
+
+    test.execute_script(r#"
+        spawn({ Transform = {}, Enemy = {} })
+        spawn({ Transform = {}, Enemy = {}, Dead = {} })
+        spawn({ Transform = {} })
+    "#).unwrap();
+    test.update();
+
+    test.execute_script(r#"
+        register_system("CountEnemies", function(world)
+            _G.enemy_count = world:query_count({"Enemy"})
+            _G.alive_count = world:query_count({"Enemy"}, {"Dead"})
+            _G.transform_count = world:query_count({"Transform"})
+            _G.expected_transform_count = #world:query({"Transform"})
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+
+    let lua = test.app.world().get_resource::<LuaScriptContext>().unwrap().lua.clone();
+    let enemy_count: i64 = lua.globals().get("enemy_count").unwrap_or(-1);
+    let alive_count: i64 = lua.globals().get("alive_count").unwrap_or(-1);
+    let transform_count: i64 = lua.globals().get("transform_count").unwrap_or(-1);
+    let expected: i64 = lua.globals().get("expected_transform_count").unwrap_or(-2);
+    assert_eq!(enemy_count, 2);
+    assert_eq!(alive_count, 1);
+    assert_eq!(transform_count, expected);
+}
@@ -151,9 +151,8 @@ impl TestApp {
 
     /// Trigger hot-reload for a specific file path
     fn trigger_hot_reload(&mut self, relative_path: &str) {
-        // The hot reload system expects paths relative to CWD starting with "assets/"
-        // Since we've changed CWD to temp_dir, use a relative path
-        let path = PathBuf::from("assets").join(relative_path);
+        // File change events carry asset-server paths (relative to assets/)
+        let path = PathBuf::from(to_asset_server_path(relative_path));
         // Use write_message for Message types (not send_event which is for Events)
         self.app
             .world_mut()
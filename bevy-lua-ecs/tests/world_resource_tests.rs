@@ -0,0 +1,1858 @@
+use bevy::prelude::*;
+use bevy::asset::ReflectAsset;
+use bevy::app::ScheduleRunnerPlugin;
+use bevy_lua_ecs::*;
+use std::sync::Mutex;
+use std::path::PathBuf;
+use tempfile::TempDir;
+use std::fs;
+
+static TEST_MUTEX: Mutex<()> = Mutex::new(());
+
+struct TestApp {
+    app: App,
+    temp_dir: TempDir,
+    original_dir: PathBuf,
+    #[allow(dead_code)]
+    lock: std::sync::MutexGuard<'static, ()>,
+}
+
+impl TestApp {
+    fn new() -> Self {
+        Self::with_asset_sources(|_| {})
+    }
+
+    /// Like `new`, with `add_sources` run before the AssetPlugin is added (asset sources must exist by then)
+    fn with_asset_sources(add_sources: impl FnOnce(&mut App)) -> Self {
+        let lock = TEST_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let original_dir = std::env::current_dir().expect("Failed to get current dir");
+        let assets_dir = temp_dir.path().join("assets").join("scripts");
+        fs::create_dir_all(&assets_dir).expect("Failed to create assets/scripts dir");
+        std::env::set_current_dir(temp_dir.path()).expect("Failed to change to temp dir");
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins.set(ScheduleRunnerPlugin::run_once()));
+        add_sources(&mut app);
+        app.add_plugins(AssetPlugin::default());
+        app.register_type::<ClearColor>();
+        app.insert_resource(ClearColor(Color::srgba(0.0, 0.0, 0.0, 1.0)));
+        app.add_plugins(LuaSpawnPlugin);
+        app.update();
+
+        Self {
+            app,
+            temp_dir,
+            original_dir,
+            lock,
+        }
+    }
+
+    fn execute_script(&mut self, content: &str) -> Result<u64, String> {
+        let lua_ctx = self.app.world().get_resource::<LuaScriptContext>().expect("LuaScriptContext not found").clone();
+        let script_instance = self.app.world().get_resource::<ScriptInstance>().expect("ScriptInstance not found").clone();
+        let script_registry = self.app.world().get_resource::<ScriptRegistry>().expect("ScriptRegistry not found").clone();
+        let path = PathBuf::from("scripts").join("test.lua");
+
+        lua_ctx.execute_script(content, "test.lua", path, &script_instance, &script_registry)
+            .map_err(|e| format!("{}", e))
+    }
+
+    fn update(&mut self) {
+        self.app.update();
+    }
+
+    fn lua_global_bool(&self, name: &str) -> bool {
+        self.app.world().get_resource::<LuaScriptContext>().unwrap().lua.globals().get(name).unwrap_or(false)
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        let _ = std::env::set_current_dir(&self.original_dir);
+    }
+}
+
+#[test]
+fn test_get_and_set_resource_clear_color() {
+    let mut test = TestApp::new();
+
+    test.execute_script(r#"
+        register_system("ClearColorSystem", function(world)
+            local before = world:get_resource("ClearColor")
+            assert(before ~= nil, "Expected ClearColor to be readable")
+
+            world:set_resource("ClearColor", { _0 = { r = 0.25, g = 0.5, b = 0.75, a = 1.0 } })
+            _G.set_resource_done = true
+            return true
+        end)
+    "#).unwrap();
+
+    // The patch is queued and applied by the ResourceQueue, up to a frame later
+    for _ in 0..3 {
+        test.update();
+    }
+
+    assert!(test.lua_global_bool("set_resource_done"), "ClearColor system did not run");
+    let color = test.app.world().resource::<ClearColor>().0.to_srgba();
+    assert!((color.red - 0.25).abs() < 1e-4, "Expected red=0.25, got {}", color.red);
+    assert!((color.green - 0.5).abs() < 1e-4, "Expected green=0.5, got {}", color.green);
+    assert!((color.blue - 0.75).abs() < 1e-4, "Expected blue=0.75, got {}", color.blue);
+}
+
+#[test]
+fn test_resource_access_errors() {
+    let mut test = TestApp::new();
+
+    test.execute_script(r#"
+        register_system("ResourceErrors", function(world)
+            assert(world:get_resource("NotARealResource") == nil, "Expected nil for an unknown resource type")
+
+            local ok, err = pcall(function() world:set_resource("NotARealResource", {}) end)
+            assert(not ok and tostring(err):find("not registered"), "Expected unregistered type error, got: " .. tostring(err))
+
+            _G.resource_errors_ok = true
+            return true
+        end)
+    "#).unwrap();
+
+    for _ in 0..2 {
+        test.update();
+    }
+
+    assert!(test.lua_global_bool("resource_errors_ok"), "Resource error checks did not pass");
+}
+
+#[test]
+fn test_define_component_validates_schema() {
+    let mut test = TestApp::new();
+
+    test.execute_script(r#"
+        register_system("DefineHealth", function(world)
+            world:define_component("Health", {
+                current = "number",
+                max = { type = "number", default = 100 },
+                label = { type = "string", optional = true },
+            })
+
+            local ok, err = pcall(function() spawn({ Health = { max = 10 } }) end)
+            assert(not ok and tostring(err):find("current"), "Expected missing field error, got: " .. tostring(err))
+
+            ok, err = pcall(function() spawn({ Health = { current = "full" } }) end)
+            assert(not ok and tostring(err):find("expected number"), "Expected type error, got: " .. tostring(err))
+
+            spawn({ Health = { current = 50 } })
+            return true
+        end)
+
+        register_system("CheckHealth", function(world)
+            for _, e in ipairs(world:query({"Health"})) do
+                local health = e:get("Health")
+                if health.current == 50 and health.max == 100 then
+                    _G.define_component_ok = true
+                end
+            end
+        end)
+    "#).unwrap();
+
+    for _ in 0..4 {
+        test.update();
+    }
+
+    assert!(test.lua_global_bool("define_component_ok"), "Lua-defined component was not stored with defaults");
+}
+
+#[test]
+fn test_register_and_spawn_prefab() {
+    let mut test = TestApp::new();
+
+    test.execute_script(r#"
+        register_system("PrefabSetup", function(world)
+            world:register_prefab("goblin", {
+                Transform = { translation = { x = 1, y = 2, z = 3 } },
+                GoblinStats = { health = 100, speed = 2 }
+            })
+            world:spawn_prefab("goblin")
+            world:spawn_prefab("goblin", { GoblinStats = { health = 50 } })
+            _G.missing_prefab_errors = not pcall(function() world:spawn_prefab("dragon") end)
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+    test.update();
+
+    test.execute_script(r#"
+        register_system("PrefabCheck", function(world)
+            local goblins = world:query({"Transform", "GoblinStats"})
+            local total_health = 0
+            for _, e in ipairs(goblins) do
+                local stats = e:get("GoblinStats")
+                total_health = total_health + stats.health
+                assert(stats.speed == 2, "override should keep unspecified fields")
+            end
+            _G.prefab_ok = #goblins == 2 and total_health == 150
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+
+    assert!(test.lua_global_bool("missing_prefab_errors"), "Spawning an unknown prefab should error");
+    assert!(test.lua_global_bool("prefab_ok"), "Expected two goblins with merged overrides");
+}
+
+#[test]
+fn test_eval_expression_is_sandboxed() {
+    let mut test = TestApp::new();
+    test.execute_script(r#"
+        score = 41
+    "#).unwrap();
+    test.update();
+
+    let lua_ctx = test.app.world().get_resource::<LuaScriptContext>().unwrap().clone();
+    assert_eq!(lua_ctx.eval_expression("math.max(41, 42)").unwrap(), serde_json::json!(42));
+    assert_eq!(
+        lua_ctx.eval_expression("{ name = string.format('%s-%d', 'arena', 2), players = #{1, 2} }").unwrap(),
+        serde_json::json!({ "name": "arena-2", "players": 2 })
+    );
+    assert!(lua_ctx.eval_expression("(").is_err());
+
+    // Only whitelisted names are visible: no script globals and no way out of the sandbox
+    for hidden in ["score", "require", "package", "load", "dofile", "loadfile", "io", "os", "spawn", "_G"] {
+        assert_eq!(lua_ctx.eval_expression(hidden).unwrap(), serde_json::Value::Null, "{} is visible", hidden);
+    }
+    assert!(lua_ctx.eval_expression("require('x')").is_err());
+    assert!(lua_ctx.eval_expression("package.path").is_err());
+
+    // Library tables are copies, so patching them doesn't leak into the scripts' state
+    lua_ctx.eval_expression("(function() string.format = nil end)()").unwrap();
+    assert_eq!(lua_ctx.eval_expression("string.format('%d', 7)").unwrap(), serde_json::json!("7"));
+    let format_intact: bool = lua_ctx.lua.load("return string.format ~= nil").eval().unwrap();
+    assert!(format_intact);
+}
+
+#[test]
+fn test_list_components_on_entity() {
+    let mut test = TestApp::new();
+    test.execute_script(r#"
+        _G.listed_id = spawn({ Transform = {}, Inventory = { slots = 4 } }):id()
+    "#).unwrap();
+    test.update();
+
+    test.execute_script(r#"
+        register_system("ListComponents", function(world)
+            local names = world:list_components(_G.listed_id)
+            local found = {}
+            for _, name in ipairs(names) do found[name] = true end
+            _G.has_transform = found["Transform"] == true
+            _G.has_inventory = found["Inventory"] == true
+            _G.hides_internal = found["LuaCustomComponents"] == nil
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+
+    assert!(test.lua_global_bool("has_transform"));
+    assert!(test.lua_global_bool("has_inventory"));
+    assert!(test.lua_global_bool("hides_internal"));
+}
+
+#[test]
+fn test_spawn_ui_node_with_children() {
+    let mut test = TestApp::new();
+    test.execute_script(r#"
+        register_system("UiSetup", function(world)
+            if _G.ui_spawned then return true end
+            _G.ui_spawned = true
+            local label = world:spawn_ui_node({ width = 10, height = 10 })
+            world:spawn_ui_node({
+                width = "50%",
+                flex_direction = "column",
+                background_color = "#00ff00",
+                children = { label },
+            })
+            _G.unknown_key_errors = not pcall(function() world:spawn_ui_node({ colour = "#fff" }) end)
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+    test.update();
+
+    assert!(test.lua_global_bool("unknown_key_errors"), "Unknown props should be rejected");
+
+    let world = test.app.world_mut();
+    let mut parents = world.query::<(&Node, &BackgroundColor, &Children)>();
+    let (node, background, children) = parents.single(world).expect("Expected one parent UI node");
+    assert_eq!(node.width, Val::Percent(50.0));
+    assert_eq!(node.flex_direction, FlexDirection::Column);
+    assert_eq!(background.0, Color::srgb(0.0, 1.0, 0.0));
+    assert_eq!(children.len(), 1);
+}
+
+#[test]
+fn test_sandboxed_file_helpers_reject_path_traversal() {
+    let mut test = TestApp::new();
+    let sandbox_root = test.temp_dir.path().join("assets");
+    let lua_ctx = test.app.world().get_resource::<LuaScriptContext>().unwrap().clone();
+    lua_ctx.lua.set_app_data(OsUtilities::sandboxed(&sandbox_root));
+
+    test.execute_script(r#"
+        local ok, err = pcall(read_file_bytes, "../../etc/passwd")
+        _G.traversal_rejected = not ok and tostring(err):find("outside the sandbox") ~= nil
+        _G.write_rejected = not pcall(write_file_bytes, "../escaped.txt", "x")
+        write_file_bytes("saves/slot.txt", "hello")
+        _G.inside_ok = read_file_bytes("saves/slot.txt") == "hello"
+    "#).unwrap();
+
+    assert!(test.lua_global_bool("traversal_rejected"), "Path traversal should raise a Lua error");
+    assert!(test.lua_global_bool("write_rejected"), "Writes outside the sandbox should fail");
+    assert!(!test.temp_dir.path().join("escaped.txt").exists());
+    assert!(test.lua_global_bool("inside_ok"));
+    assert!(sandbox_root.join("saves").join("slot.txt").exists());
+}
+
+#[test]
+fn test_hierarchy_get_and_set_parent() {
+    let mut test = TestApp::new();
+    test.execute_script(r#"
+        _G.panel_id = spawn({ Transform = {} }):id()
+        _G.label_id = spawn({ Transform = {} }):id()
+        register_system("Reparent", function(world)
+            world:set_parent(_G.label_id, _G.panel_id)
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+    test.update();
+
+    test.execute_script(r#"
+        register_system("CheckHierarchy", function(world)
+            local children = world:get_children(_G.panel_id)
+            _G.one_child = #children == 1
+            local parent = world:get_parent(_G.label_id)
+            _G.parent_matches = parent ~= nil and parent == world:get_parent(children[1])
+            _G.root_has_no_parent = world:get_parent(_G.panel_id) == nil
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+
+    assert!(test.lua_global_bool("one_child"));
+    assert!(test.lua_global_bool("parent_matches"));
+    assert!(test.lua_global_bool("root_has_no_parent"));
+
+    let world = test.app.world_mut();
+    let mut children = world.query::<(&ChildOf, &Transform)>();
+    assert_eq!(children.iter(world).count(), 1);
+}
+
+#[test]
+fn test_despawn_modes_match_between_global_and_entity() {
+    let mut test = TestApp::new();
+    let world = test.app.world_mut();
+    let parent_a = world.spawn(Transform::default()).id();
+    let child_a = world.spawn((Transform::default(), ChildOf(parent_a))).id();
+    let parent_b = world.spawn(Transform::default()).id();
+    let child_b = world.spawn((Transform::default(), ChildOf(parent_b))).id();
+    let parent_c = world.spawn(Transform::default()).id();
+    let child_c = world.spawn((Transform::default(), ChildOf(parent_c))).id();
+    let globals = test.app.world().resource::<LuaScriptContext>().lua.globals();
+    globals.set("parent_a", parent_a.to_bits()).unwrap();
+    globals.set("parent_b", parent_b.to_bits()).unwrap();
+    globals.set("parent_c", parent_c.to_bits()).unwrap();
+
+    // Entity methods must not depend on globals a script is free to overwrite
+    test.execute_script(r#"
+        despawn_single = nil
+        despawn_recursive = nil
+        local done = false
+        register_system("Despawn", function(world)
+            if done then return end
+            done = true
+            despawn(_G.parent_a)
+            world:get_entity(_G.parent_b):despawn()
+            world:despawn_single(_G.parent_c)
+        end)
+    "#).unwrap();
+    test.update();
+    test.update();
+
+    let world = test.app.world();
+    assert!(world.get_entity(parent_a).is_err());
+    assert!(world.get_entity(child_a).is_err(), "despawn() should take descendants with it");
+    assert!(world.get_entity(parent_b).is_err());
+    assert!(world.get_entity(child_b).is_err(), "entity:despawn() should match despawn()");
+    assert!(world.get_entity(parent_c).is_err());
+    assert!(world.get_entity(child_c).is_ok(), "despawn_single should keep the children");
+    assert!(world.get::<ChildOf>(child_c).is_none());
+}
+
+/// Plugins that share startup schedules register in whatever order they were added, which is
+/// how the Replicon protocol mismatch arises (events must be registered before RepliconPlugins).
+/// LuaPluginOrder lets the Lua context be created in a later, deterministic stage.
+#[test]
+fn test_plugin_order_defers_lua_init() {
+    let _lock = TEST_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins.set(ScheduleRunnerPlugin::run_once()));
+    app.add_plugins(AssetPlugin::default());
+    app.insert_resource(LuaPluginOrder::new([(LuaPluginOrder::LUA_INIT, StartupStage::PostStartup)]));
+    app.add_plugins(LuaSpawnPlugin);
+    app.finish();
+    app.cleanup();
+
+    app.world_mut().run_schedule(PreStartup);
+    app.world_mut().run_schedule(Startup);
+    assert!(app.world().get_resource::<ComponentRegistry>().is_some());
+    assert!(app.world().get_resource::<LuaScriptContext>().is_none(), "lua_init should wait for PostStartup");
+
+    app.world_mut().run_schedule(PostStartup);
+    assert!(app.world().get_resource::<LuaScriptContext>().is_some());
+}
+
+#[derive(Resource, Default)]
+struct PhysicsConfig {
+    gravity: f32,
+    substeps: u32,
+}
+
+#[test]
+fn test_resource_builder_methods_chain() {
+    let mut test = TestApp::new();
+    test.app.insert_resource(PhysicsConfig::default());
+    {
+        let registry = test.app.world().resource::<LuaResourceRegistry>();
+        registry.register_resource_builder_method::<PhysicsConfig, _, _>("PhysicsConfig", "with_gravity", |config, _lua, gravity: f32| {
+            config.gravity = gravity;
+            Ok(())
+        });
+        registry.register_resource_builder_method::<PhysicsConfig, _, _>("PhysicsConfig", "with_substeps", |config, _lua, substeps: u32| {
+            config.substeps = substeps;
+            Ok(())
+        });
+    }
+
+    test.execute_script(r#"
+        register_system("ConfigurePhysics", function(world)
+            _G.chain = world:call_resource_method("PhysicsConfig", "with_gravity", 9.5):with_substeps(4)
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+
+    let config = test.app.world().resource::<PhysicsConfig>();
+    assert_eq!(config.gravity, 9.5);
+    assert_eq!(config.substeps, 4);
+
+    // The handle is tied to the system call that produced it
+    let result = test.execute_script("_G.chain:with_substeps(8)");
+    assert!(result.is_err());
+    assert_eq!(test.app.world().resource::<PhysicsConfig>().substeps, 4);
+}
+
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+struct PluginTuning {
+    speed: f32,
+}
+
+#[test]
+fn test_construct_resource_calls_registered_factories() {
+    let mut test = TestApp::new();
+    test.app.register_type::<PluginTuning>();
+    {
+        let registry = test.app.world().resource::<ResourceConstructorRegistry>();
+        registry.register_factory("DefaultTuning", std::sync::Arc::new(|| Box::new(PluginTuning { speed: 1.0 })));
+        registry.factory_with_params(
+            "FastTuning",
+            std::sync::Arc::new(|params: mlua::Table| {
+                let speed: f32 = params.get("speed").map_err(|e| e.to_string())?;
+                Ok(Box::new(PluginTuning { speed }) as Box<dyn Reflect>)
+            }),
+        );
+    }
+
+    test.execute_script(r#"
+        local frame = 0
+        register_system("Tune", function(world)
+            frame = frame + 1
+            if frame == 1 then
+                world:construct_resource("DefaultTuning")
+                local ok, err = pcall(function() world:construct_resource("MissingTuning") end)
+                _G.missing_factory_error = not ok and tostring(err):find("no factory registered", 1, true) ~= nil
+            elseif frame == 3 then
+                world:construct_resource("FastTuning", { speed = 4 })
+            end
+        end)
+    "#).unwrap();
+
+    // The resource queue may run before or after Lua systems, so give each request a spare frame
+    test.update();
+    test.update();
+    assert_eq!(test.app.world().resource::<PluginTuning>().speed, 1.0, "the plain factory should insert the resource");
+    assert!(test.lua_global_bool("missing_factory_error"), "construct_resource should error for unregistered names");
+
+    test.update();
+    test.update();
+    assert_eq!(test.app.world().resource::<PluginTuning>().speed, 4.0, "the parameterized factory should receive the Lua table");
+}
+
+#[test]
+fn test_mutate_resource_runs_registered_mutator() {
+    let mut test = TestApp::new();
+    test.app
+        .world()
+        .resource::<ResourceBuilderRegistry>()
+        .register_mutator("ClearColor", "set_red", |resource, args| {
+            let clear_color = resource.downcast_mut::<ClearColor>().unwrap();
+            let red: f32 = args.get("r").unwrap();
+            clear_color.0 = Color::srgb(red, 0.0, 0.0);
+        });
+
+    test.execute_script(r#"
+        register_system("Redden", function(world)
+            world:mutate_resource("ClearColor", "set_red", { r = 0.5 })
+            local ok, err = pcall(function() world:mutate_resource("ClearColor", "nope", {}) end)
+            assert(not ok and tostring(err):find("no mutator"), "Expected missing mutator error, got: " .. tostring(err))
+            _G.mutate_done = true
+            return true
+        end)
+    "#).unwrap();
+
+    for _ in 0..2 {
+        test.update();
+    }
+
+    assert!(test.lua_global_bool("mutate_done"), "Mutate system did not run");
+    let color = test.app.world().resource::<ClearColor>().0.to_srgba();
+    assert!((color.red - 0.5).abs() < 1e-4, "Expected red=0.5, got {}", color.red);
+}
+
+#[test]
+fn test_execute_script_with_params_isolates_instances() {
+    let mut test = TestApp::new();
+    let script = r#"
+        rate = SCRIPT_PARAMS.rate
+        _G.rates = _G.rates or {}
+        table.insert(_G.rates, rate)
+    "#;
+
+    let (lua_ctx, script_instance, script_registry) = {
+        let world = test.app.world();
+        (
+            world.resource::<LuaScriptContext>().clone(),
+            world.resource::<ScriptInstance>().clone(),
+            world.resource::<ScriptRegistry>().clone(),
+        )
+    };
+    let path = PathBuf::from("assets/scripts/spawner.lua");
+    let slow = lua_ctx
+        .execute_script_with_params(script, "spawner.lua", path.clone(), serde_json::json!({ "rate": 1 }), &script_instance, &script_registry)
+        .unwrap();
+    let fast = lua_ctx
+        .execute_script_with_params(script, "spawner.lua", path.clone(), serde_json::json!({ "rate": 5 }), &script_instance, &script_registry)
+        .unwrap();
+    test.update();
+
+    let lua = &lua_ctx.lua;
+    let rates: Vec<i64> = lua.load("return _G.rates").eval::<mlua::Table>().unwrap().sequence_values().collect::<Result<_, _>>().unwrap();
+    assert_eq!(rates, vec![1, 5]);
+    // Each instance's globals live in its own environment
+    assert!(lua.globals().get::<Option<i64>>("rate").unwrap().is_none());
+
+    let pool = test.app.world().resource::<ScriptInstancePool>();
+    let ids: Vec<u64> = pool.instances_of(&path).iter().map(|entry| entry.instance_id).collect();
+    assert_eq!(ids, vec![slow, fast]);
+    assert_eq!(pool.params(fast), Some(serde_json::json!({ "rate": 5 })));
+}
+
+#[test]
+fn test_update_transaction_skips_all_when_entity_missing() {
+    let mut test = TestApp::new();
+    let a = test.app.world_mut().spawn(Transform::default()).id();
+    let b = test.app.world_mut().spawn(Transform::default()).id();
+    let gone = test.app.world_mut().spawn(Transform::default()).id();
+    test.app.world_mut().despawn(gone);
+
+    {
+        let lua = &test.app.world().resource::<LuaScriptContext>().lua;
+        lua.globals().set("a", a.to_bits()).unwrap();
+        lua.globals().set("b", b.to_bits()).unwrap();
+        lua.globals().set("gone", gone.to_bits()).unwrap();
+    }
+
+    test.execute_script(r#"
+        local function at(x) return { Transform = { translation = { x = x, y = 0, z = 0 } } } end
+        register_system("Transactions", function(world)
+            local ok = world:begin_update_transaction()
+            world:queue_in_transaction(ok, a, at(1))
+            world:queue_in_transaction(ok, b, at(2))
+            world:commit_transaction(ok)
+
+            local bad = world:begin_update_transaction()
+            world:queue_in_transaction(bad, a, at(99))
+            world:queue_in_transaction(bad, gone, at(99))
+            world:commit_transaction(bad)
+
+            local discarded = world:begin_update_transaction()
+            world:queue_in_transaction(discarded, b, at(99))
+            assert(world:rollback_transaction(discarded))
+            _G.transactions_done = true
+            return true
+        end)
+    "#).unwrap();
+
+    for _ in 0..2 {
+        test.update();
+    }
+
+    assert!(test.lua_global_bool("transactions_done"), "Transaction system did not run");
+    assert_eq!(test.app.world().get::<Transform>(a).unwrap().translation.x, 1.0);
+    assert_eq!(test.app.world().get::<Transform>(b).unwrap().translation.x, 2.0);
+}
+
+#[test]
+fn test_open_transaction_dropped_with_instance_after_script_error() {
+    let mut test = TestApp::new();
+    let target = test.app.world_mut().spawn(Transform::default()).id();
+    test.app.world().resource::<LuaScriptContext>().lua.globals().set("target", target.to_bits()).unwrap();
+
+    test.execute_script(r#"
+        local frame = 0
+        register_system("FailedTransfer", function(world)
+            frame = frame + 1
+            if frame == 1 then
+                local tx = world:begin_update_transaction()
+                world:queue_in_transaction(tx, target, { Transform = { translation = { x = 99, y = 0, z = 0 } } })
+                error("failed before commit")
+            end
+            world:stop_current_script()
+        end)
+    "#).unwrap();
+
+    test.update();
+    let queue = test.app.world().resource::<ComponentUpdateQueue>().clone();
+    assert_eq!(queue.open_transaction_count(), 1, "the errored system should have left its transaction open");
+
+    test.update();
+    assert_eq!(queue.open_transaction_count(), 0, "stopping the instance should drop its open transaction");
+    assert_eq!(test.app.world().get::<Transform>(target).unwrap().translation.x, 0.0);
+}
+
+#[test]
+fn test_query_page_and_query_all_async() {
+    let mut test = TestApp::new();
+
+    test.execute_script(r#"
+        for i = 1, 5 do
+            spawn({ Crate = { index = i } })
+        end
+    "#).unwrap();
+    test.update();
+
+    test.execute_script(r#"
+        _G.async_pages = 0
+        _G.async_seen = 0
+        register_system("Paging", function(world)
+            local first = world:query_page({"Crate"}, 1, 2)
+            assert(#first.entities == 2 and first.total == 5 and first.has_more, "unexpected first page")
+            local last = world:query_page({"Crate"}, 3, 2)
+            assert(#last.entities == 1 and not last.has_more, "unexpected last page")
+            assert(last.entities[1]:get("Crate").index ~= nil, "page snapshot is missing component data")
+            assert(not pcall(function() world:query_page({"Crate"}, 0, 2) end), "page 0 should error")
+
+            world:query_all_async({"Crate"}, function(crates, page)
+                _G.async_pages = _G.async_pages + 1
+                _G.async_seen = _G.async_seen + #crates
+                _G.async_done = not page.has_more
+            end, 2)
+            _G.paging_ok = true
+            return true
+        end)
+    "#).unwrap();
+
+    for _ in 0..5 {
+        test.update();
+    }
+
+    assert!(test.lua_global_bool("paging_ok"), "Paging system did not run");
+    assert!(test.lua_global_bool("async_done"), "query_all_async did not finish");
+    let globals = test.app.world().resource::<LuaScriptContext>().lua.globals();
+    assert_eq!(globals.get::<u32>("async_pages").unwrap(), 3);
+    assert_eq!(globals.get::<u32>("async_seen").unwrap(), 5);
+}
+
+#[test]
+fn test_query_page_sees_entities_spawned_into_existing_archetypes() {
+    let mut test = TestApp::new();
+
+    test.execute_script(r#"
+        for i = 1, 3 do
+            spawn({ Crate = { index = i } })
+        end
+    "#).unwrap();
+    test.update();
+
+    test.execute_script(r#"
+        local frame = 0
+        register_system("PagingAcrossFrames", function(world)
+            frame = frame + 1
+            if frame == 1 then
+                _G.first_total = world:query_page({"Crate"}, 1, 2).total
+                -- Same components, so no new archetype is created
+                spawn({ Crate = { index = 4 } })
+            elseif frame == 3 then
+                _G.second_total = world:query_page({"Crate"}, 2, 2).total
+                return true
+            end
+        end)
+    "#).unwrap();
+
+    for _ in 0..4 {
+        test.update();
+    }
+
+    let globals = test.app.world().resource::<LuaScriptContext>().lua.globals();
+    assert_eq!(globals.get::<u32>("first_total").unwrap(), 3);
+    assert_eq!(globals.get::<u32>("second_total").unwrap(), 4, "Cached page list was not invalidated");
+}
+
+#[test]
+fn test_query_iter_streams_snapshots_for_one_frame() {
+    let mut test = TestApp::new();
+
+    test.execute_script(r#"
+        for i = 1, 4 do
+            spawn({ Crate = { index = i } })
+        end
+        spawn({ Barrel = {} })
+    "#).unwrap();
+    test.update();
+
+    test.execute_script(r#"
+        register_system("Iterating", function(world)
+            if _G.stale_iter then
+                _G.stale_is_nil = _G.stale_iter() == nil
+                return true
+            end
+            local sum = 0
+            for crate in world:query_iter({"Crate"}) do
+                sum = sum + crate:get("Crate").index
+            end
+            assert(sum == 10, "query_iter visited the wrong crates: " .. sum)
+            _G.stale_iter = world:query_iter({"Crate"})
+            _G.iter_ok = true
+        end)
+    "#).unwrap();
+
+    for _ in 0..3 {
+        test.update();
+    }
+
+    assert!(test.lua_global_bool("iter_ok"), "Iterating system did not run");
+    assert!(test.lua_global_bool("stale_is_nil"), "query_iter kept yielding after its frame");
+}
+
+#[test]
+fn test_observe_component_lifecycle_receives_component_value() {
+    let mut test = TestApp::new();
+    let target = test.app.world_mut().spawn(Name::new("target")).id();
+    test.app
+        .world()
+        .resource::<LuaScriptContext>()
+        .lua
+        .globals()
+        .set("target_bits", target.to_bits())
+        .unwrap();
+
+    test.execute_script(r#"
+        register_system("ObserveTransform", function(world)
+            local target = world:get_entity(target_bits)
+            target:observe_component_add("Transform", function(entity, transform)
+                _G.added_x = transform.translation.x
+            end)
+            target:observe_component_remove("Transform", function(entity, transform)
+                _G.removed_x = transform.translation.x
+            end)
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+    test.update();
+
+    test.app.world_mut().entity_mut(target).insert(Transform::from_xyz(3.0, 0.0, 0.0));
+    test.app.world_mut().entity_mut(target).get_mut::<Transform>().unwrap().translation.x = 7.0;
+    test.app.world_mut().entity_mut(target).remove::<Transform>();
+
+    let globals = test.app.world().resource::<LuaScriptContext>().lua.globals();
+    assert_eq!(globals.get::<f32>("added_x").unwrap(), 3.0);
+    assert_eq!(globals.get::<f32>("removed_x").unwrap(), 7.0);
+}
+
+#[test]
+fn test_animation_api_finds_skeleton_and_stops_clips() {
+    let mut test = TestApp::new();
+    test.app.init_asset::<AnimationClip>().init_asset::<AnimationGraph>();
+
+    let root = test.app.world_mut().spawn(Transform::default()).id();
+    let skeleton = test.app.world_mut().spawn((AnimationPlayer::default(), ChildOf(root))).id();
+    let bare = test.app.world_mut().spawn(Transform::default()).id();
+
+    // Start a clip that is already in Assets, as a loaded glTF clip would be
+    let mut clip = AnimationClip::default();
+    clip.set_duration(2.0);
+    let clip = test.app.world_mut().resource_mut::<Assets<AnimationClip>>().add(clip);
+    test.app.world().resource::<AnimationQueue>().queue_play(skeleton, clip, AnimationLoopMode::Repeat);
+    test.update();
+    assert!(
+        test.app.world().get::<AnimationGraphHandle>(skeleton).is_some(),
+        "the queued clip should start on the skeleton's AnimationPlayer"
+    );
+
+    let globals = test.app.world().resource::<LuaScriptContext>().lua.globals();
+    globals.set("root_bits", root.to_bits()).unwrap();
+    globals.set("bare_bits", bare.to_bits()).unwrap();
+
+    test.execute_script(r#"
+        local frame = 0
+        register_system("Animate", function(world)
+            frame = frame + 1
+            if frame == 1 then
+                _G.playing_time = world:animation_time(root_bits)
+
+                local ok, err = pcall(function() world:animate(bare_bits, "clip.anim", "once") end)
+                _G.no_skeleton_error = not ok and tostring(err):find("no AnimationPlayer", 1, true) ~= nil
+                ok, err = pcall(function() world:animate(root_bits, "clip.anim", "bounce") end)
+                _G.bad_mode_error = not ok and tostring(err):find("unknown loop mode", 1, true) ~= nil
+                _G.time_no_skeleton_error = not pcall(function() world:animation_time(bare_bits) end)
+
+                world:stop_animation(root_bits)
+            elseif frame == 3 then
+                _G.stopped = world:animation_time(root_bits) == nil
+            end
+        end)
+    "#).unwrap();
+    for _ in 0..3 {
+        test.update();
+    }
+
+    let globals = test.app.world().resource::<LuaScriptContext>().lua.globals();
+    assert_eq!(globals.get::<Option<f32>>("playing_time").unwrap(), Some(0.0), "animation_time should report the playing clip");
+    assert!(test.lua_global_bool("no_skeleton_error"), "animate should error for entities without a skeleton");
+    assert!(test.lua_global_bool("bad_mode_error"), "animate should reject unknown loop modes");
+    assert!(test.lua_global_bool("time_no_skeleton_error"), "animation_time should error for entities without a skeleton");
+    assert!(test.lua_global_bool("stopped"), "stop_animation should stop the clip");
+}
+
+#[test]
+fn test_world_to_screen_without_viewport_returns_nil() {
+    let mut test = TestApp::new();
+    let marker = test.app.world_mut().spawn((Transform::default(), GlobalTransform::default())).id();
+    test.app
+        .world()
+        .resource::<LuaScriptContext>()
+        .lua
+        .globals()
+        .set("marker_bits", marker.to_bits())
+        .unwrap();
+
+    test.execute_script(r#"
+        register_system("Project", function(world)
+            _G.no_camera_nil = world:world_to_screen(marker_bits) == nil
+            _G.no_camera_empty = #world:world_to_screen_all("Transform") == 0
+            _G.no_camera_unproject = world:screen_to_world(10, 20, 5) == nil
+                and world:screen_to_world_ray(10, 20) == nil
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+
+    assert!(test.lua_global_bool("no_camera_nil"), "world_to_screen should be nil without a camera");
+    assert!(test.lua_global_bool("no_camera_empty"), "world_to_screen_all should be empty without a camera");
+    assert!(test.lua_global_bool("no_camera_unproject"), "screen_to_world should be nil without a camera");
+}
+
+/// 800x600 orthographic camera at the origin looking down -Z, one world unit per pixel
+/// Its viewport is filled in by hand since the headless test app has no camera/window systems
+fn spawn_test_camera(world: &mut World) -> Entity {
+    let mut camera = Camera::default();
+    // Reversed-Z like Bevy's own projections (near = 0, far = 1000)
+    camera.computed.clip_from_view = Mat4::orthographic_rh(-400.0, 400.0, -300.0, 300.0, 1000.0, 0.0);
+    camera.computed.target_info = Some(bevy::camera::RenderTargetInfo {
+        physical_size: UVec2::new(800, 600),
+        scale_factor: 1.0,
+    });
+    world.spawn((camera, GlobalTransform::IDENTITY)).id()
+}
+
+#[test]
+fn test_world_to_screen_projects_through_camera() {
+    let mut test = TestApp::new();
+    spawn_test_camera(test.app.world_mut());
+    let visible = test.app.world_mut().spawn((Transform::default(), GlobalTransform::from_xyz(100.0, 50.0, -10.0))).id();
+    let behind = test.app.world_mut().spawn((Transform::default(), GlobalTransform::from_xyz(0.0, 0.0, 10.0))).id();
+    let globals = test.app.world().resource::<LuaScriptContext>().lua.globals();
+    globals.set("visible_bits", visible.to_bits()).unwrap();
+    globals.set("behind_bits", behind.to_bits()).unwrap();
+
+    test.execute_script(r#"
+        register_system("Project", function(world)
+            local screen = world:world_to_screen(visible_bits)
+            _G.screen_x = screen.x
+            _G.screen_y = screen.y
+            _G.behind_nil = world:world_to_screen(behind_bits) == nil
+
+            _G.all_has_visible = false
+            _G.all_has_behind = false
+            for _, entry in ipairs(world:world_to_screen_all("Transform")) do
+                if entry.entity_id == visible_bits then
+                    _G.all_has_visible = entry.x == screen.x and entry.y == screen.y
+                elseif entry.entity_id == behind_bits then
+                    _G.all_has_behind = true
+                end
+            end
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+
+    let globals = test.app.world().resource::<LuaScriptContext>().lua.globals();
+    assert!((globals.get::<f32>("screen_x").unwrap() - 500.0).abs() < 1e-3);
+    assert!((globals.get::<f32>("screen_y").unwrap() - 250.0).abs() < 1e-3);
+    assert!(test.lua_global_bool("behind_nil"), "an entity behind the camera should project to nil");
+    assert!(test.lua_global_bool("all_has_visible"), "world_to_screen_all should include the visible entity");
+    assert!(!test.lua_global_bool("all_has_behind"), "world_to_screen_all should skip entities behind the camera");
+}
+
+#[test]
+fn test_screen_to_world_unprojects_through_camera() {
+    let mut test = TestApp::new();
+    spawn_test_camera(test.app.world_mut());
+
+    test.execute_script(r#"
+        register_system("Unproject", function(world)
+            local point = world:screen_to_world(500, 250, 10)
+            _G.point = { point.x, point.y, point.z }
+
+            local ray = world:screen_to_world_ray(500, 250)
+            _G.ray_origin = { ray.origin.x, ray.origin.y, ray.origin.z }
+            _G.ray_direction = { ray.direction.x, ray.direction.y, ray.direction.z }
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+
+    let lua = test.app.world().resource::<LuaScriptContext>().lua.clone();
+    let vec3 = |name: &str| -> Vec3 {
+        let values: Vec<f32> = lua.load(format!("return _G.{}", name)).eval::<mlua::Table>().unwrap()
+            .sequence_values().collect::<Result<_, _>>().unwrap();
+        Vec3::new(values[0], values[1], values[2])
+    };
+    // The inverse of world_to_screen: (500, 250) is world (100, 50) on this camera
+    assert!(vec3("point").abs_diff_eq(Vec3::new(100.0, 50.0, -10.0), 1e-3), "point = {:?}", vec3("point"));
+    assert!(vec3("ray_origin").abs_diff_eq(Vec3::new(100.0, 50.0, 0.0), 1e-3), "origin = {:?}", vec3("ray_origin"));
+    assert!(vec3("ray_direction").abs_diff_eq(Vec3::NEG_Z, 1e-3), "direction = {:?}", vec3("ray_direction"));
+}
+
+#[test]
+fn test_sandboxed_instances_have_isolated_globals() {
+    let mut test = TestApp::new();
+    test.app.world_mut().resource_mut::<LuaScriptContext>().set_sandbox_instances(true);
+
+    let first = test.execute_script(r#"
+        shared = "first"
+        spawn({ Transform = { translation = { x = 4.0, y = 0.0, z = 0.0 } } })
+    "#).unwrap();
+    let second = test.execute_script(r#"
+        saw_first = shared ~= nil
+        register_system("SandboxedSystem", function(world)
+            system_ran = #world:query({"Transform"}) == 1
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+    test.update();
+
+    let lua_ctx = test.app.world().resource::<LuaScriptContext>().clone();
+    assert!(lua_ctx.is_sandboxed(first) && lua_ctx.is_sandboxed(second));
+    assert_eq!(lua_ctx.instance_lua(first).globals().get::<String>("shared").unwrap(), "first");
+    assert!(!lua_ctx.instance_lua(second).globals().get::<bool>("saw_first").unwrap());
+    assert!(lua_ctx.lua.globals().get::<Option<String>>("shared").unwrap().is_none());
+    assert!(lua_ctx.instance_lua(second).globals().get::<bool>("system_ran").unwrap());
+
+    let mut transforms = test.app.world_mut().query::<&Transform>();
+    let spawned: Vec<_> = transforms.iter(test.app.world()).collect();
+    assert_eq!(spawned.len(), 1);
+    assert_eq!(spawned[0].translation.x, 4.0);
+}
+
+#[test]
+fn test_sandboxed_instances_cannot_register_observers() {
+    let mut test = TestApp::new();
+    test.app.world_mut().resource_mut::<LuaScriptContext>().set_sandbox_instances(true);
+
+    let sandboxed = test.execute_script(r#"
+        local ok, err = pcall(function()
+            spawn({ Transform = {} }):observe("Pointer<Click>", function() end)
+        end)
+        observe_failed = not ok
+        observe_error = tostring(err)
+    "#).unwrap();
+
+    let lua = test.app.world().resource::<LuaScriptContext>().instance_lua(sandboxed);
+    assert!(lua.globals().get::<bool>("observe_failed").unwrap(), "observe should fail in a sandbox");
+    let message: String = lua.globals().get("observe_error").unwrap();
+    assert!(message.contains("sandboxed"), "unexpected error: {}", message);
+}
+
+#[test]
+fn test_stopped_sandboxed_instance_drops_its_state() {
+    let mut test = TestApp::new();
+    test.app.world_mut().resource_mut::<LuaScriptContext>().set_sandbox_instances(true);
+
+    let stopped = test.execute_script(r#"
+        register_system("StopSelf", function(world)
+            world:stop_current_script()
+            return true
+        end)
+    "#).unwrap();
+    let kept = test.execute_script(r#"kept = true"#).unwrap();
+    let lua_ctx = test.app.world().resource::<LuaScriptContext>().clone();
+    let state_count = lua_ctx.state_count();
+    test.update();
+
+    assert!(!lua_ctx.is_sandboxed(stopped), "stopped instance should lose its sandbox");
+    assert!(std::sync::Arc::ptr_eq(&lua_ctx.instance_lua(stopped), &lua_ctx.lua));
+    assert!(lua_ctx.is_sandboxed(kept));
+    assert!(lua_ctx.instance_lua(kept).globals().get::<bool>("kept").unwrap());
+    assert_eq!(lua_ctx.state_count(), state_count, "other state ids must stay stable");
+}
+
+#[test]
+fn test_throttled_systems_are_limited_per_instance() {
+    let mut test = TestApp::new();
+    let script = |counter: &str| format!(r#"
+        {counter} = 0
+        register_system("Update", function(world)
+            {counter} = {counter} + 1
+        end, {{ name = "pathfinding", max_hz = 0.01 }})
+    "#);
+    test.execute_script(&script("first_runs")).unwrap();
+    test.execute_script(&script("second_runs")).unwrap();
+    test.execute_script(r#"
+        unthrottled_runs = 0
+        register_system("Update", function(world)
+            unthrottled_runs = unthrottled_runs + 1
+        end)
+    "#).unwrap();
+    for _ in 0..3 {
+        test.update();
+    }
+
+    let lua_ctx = test.app.world().resource::<LuaScriptContext>().clone();
+    let runs = |name: &str| lua_ctx.lua.globals().get::<u32>(name).unwrap();
+    assert_eq!(runs("first_runs"), 1, "throttled system should run once, then wait");
+    assert_eq!(runs("second_runs"), 1, "another instance's system with the same label has its own throttle");
+    assert_eq!(runs("unthrottled_runs"), 3);
+}
+
+#[test]
+fn test_create_and_cancel_timers() {
+    let mut test = TestApp::new();
+    test.execute_script(r#"
+        one_shot_fired = 0
+        repeat_fired = 0
+        register_system("StartTimers", function(world)
+            world:create_timer(0, function() one_shot_fired = one_shot_fired + 1 end, false)
+            world:create_timer(0, function()
+                repeat_fired = repeat_fired + 1
+                return repeat_fired < 3
+            end, true)
+            local cancelled = world:create_timer(0, function() cancelled_fired = true end)
+            cancel_result = world:cancel_timer(cancelled)
+            cancel_again = world:cancel_timer(cancelled)
+            return true
+        end)
+    "#).unwrap();
+    for _ in 0..5 {
+        test.update();
+    }
+
+    assert!(test.lua_global_bool("cancel_result"));
+    assert!(!test.lua_global_bool("cancel_again"));
+    assert!(!test.lua_global_bool("cancelled_fired"));
+    let lua_ctx = test.app.world().resource::<LuaScriptContext>().clone();
+    assert_eq!(lua_ctx.lua.globals().get::<u32>("one_shot_fired").unwrap(), 1);
+    assert_eq!(lua_ctx.lua.globals().get::<u32>("repeat_fired").unwrap(), 3);
+    assert!(test.app.world().resource::<TimerRegistry>().is_empty());
+}
+
+#[test]
+fn test_spawn_with_init_runs_callback_with_live_entity() {
+    let mut test = TestApp::new();
+    test.execute_script(r#"
+        register_system("SpawnWithInit", function(world)
+            temp_id = world:spawn_with_init({ Transform = { translation = { x = 4 } } }, function(entity)
+                spawned_entity = entity
+                init_calls = (init_calls or 0) + 1
+            end)
+            return true
+        end)
+    "#).unwrap();
+    for _ in 0..3 {
+        test.update();
+    }
+
+    let lua_ctx = test.app.world().resource::<LuaScriptContext>().clone();
+    assert_eq!(lua_ctx.lua.globals().get::<u32>("init_calls").unwrap(), 1);
+    let entity = Entity::from_bits(lua_ctx.lua.globals().get::<u64>("spawned_entity").unwrap());
+    let transform = test.app.world().get::<Transform>(entity).expect("init callback should see the spawned entity");
+    assert_eq!(transform.translation.x, 4.0);
+    assert_eq!(test.app.world().resource::<PostSpawnCallbackQueue>().pending_len(), 0);
+}
+
+#[test]
+fn test_spawn_init_callbacks_restore_globals_and_skip_removed_sandboxes() {
+    use bevy::ecs::system::RunSystemOnce;
+
+    let mut test = TestApp::new();
+    let lua_ctx = test.app.world().resource::<LuaScriptContext>().clone();
+    let queue = test.app.world().resource::<PostSpawnCallbackQueue>().clone();
+    let live = test.app.world_mut().spawn_empty().id();
+    let orphaned = test.app.world_mut().spawn_empty().id();
+
+    // Primary-state callback registered by instance 9
+    let globals = lua_ctx.lua.globals();
+    globals.set("__INSTANCE_ID__", 9u64).unwrap();
+    let callback = lua_ctx.lua.load("return function(entity) init_instance = __INSTANCE_ID__ end").eval().unwrap();
+    queue.register(&lua_ctx.lua, 1, callback).unwrap();
+    queue.entity_spawned(1, live);
+
+    // Callback from a sandbox that is removed before its entity is ready
+    let sandbox = lua_ctx.create_sandbox(4242).unwrap();
+    let callback = sandbox.load("return function(entity) error('must not run') end").eval().unwrap();
+    queue.register(&sandbox, 2, callback).unwrap();
+    queue.entity_spawned(2, orphaned);
+    assert!(lua_ctx.remove_sandbox(4242));
+
+    globals.set("__INSTANCE_ID__", 5u64).unwrap();
+    globals.set("__SPAWN_PHASE__", "load").unwrap();
+    test.app.world_mut().run_system_once(run_post_spawn_callbacks).unwrap();
+
+    assert_eq!(globals.get::<u64>("init_instance").unwrap(), 9);
+    assert_eq!(globals.get::<u64>("__INSTANCE_ID__").unwrap(), 5, "init callbacks should restore __INSTANCE_ID__");
+    assert_eq!(globals.get::<String>("__SPAWN_PHASE__").unwrap(), "load");
+}
+
+#[test]
+fn test_gc_despawns_entities_of_removed_script_instances() {
+    let mut test = TestApp::new();
+    test.app.world_mut().resource_mut::<ScriptGcConfig>().interval = std::time::Duration::ZERO;
+
+    let registry = test.app.world().resource::<ScriptRegistry>().clone();
+    registry.register_script(PathBuf::from("assets/scripts/live.lua"), 9001, "-- live".to_string());
+
+    let owned = |instance_id, spawn_phase, script_version| ScriptOwned {
+        instance_id,
+        spawn_phase,
+        script_version,
+    };
+    let live = test.app.world_mut().spawn(owned(9001, script_entities::SpawnPhase::Script, 1)).id();
+    let orphan = test.app.world_mut().spawn(owned(9002, script_entities::SpawnPhase::Script, 1)).id();
+    let runtime = test.app.world_mut().spawn(owned(9002, script_entities::SpawnPhase::Runtime, 1)).id();
+    let never_registered = test.app.world_mut().spawn(owned(9003, script_entities::SpawnPhase::Script, 0)).id();
+
+    test.update();
+    test.update();
+
+    let world = test.app.world();
+    assert!(world.get_entity(live).is_ok());
+    assert!(world.get_entity(orphan).is_err(), "orphaned Script-phase entity should be collected");
+    assert!(world.get_entity(runtime).is_ok());
+    assert!(world.get_entity(never_registered).is_ok());
+    assert_eq!(world.resource::<LuaSystemProfiles>().gc_collected(), 1);
+}
+
+#[test]
+fn test_gc_frees_lua_memory_of_collected_entities() {
+    let mut test = TestApp::new();
+    {
+        let mut config = test.app.world_mut().resource_mut::<ScriptGcConfig>();
+        config.interval = std::time::Duration::ZERO;
+        config.lua_step_kb = 1024;
+    }
+    let lua = test.app.world().resource::<LuaScriptContext>().lua.clone();
+
+    for _ in 0..20 {
+        let payload: mlua::Table = lua
+            .load("local t = {} for i = 1, 2000 do t[i] = string.rep('x', 32) .. i end return t")
+            .eval()
+            .unwrap();
+        let mut custom = LuaCustomComponents::default();
+        custom
+            .components
+            .insert("Payload".to_string(), std::sync::Arc::new(lua.create_registry_value(payload).unwrap()));
+        test.app.world_mut().spawn((
+            custom,
+            ScriptOwned {
+                instance_id: 9002,
+                spawn_phase: script_entities::SpawnPhase::Script,
+                script_version: 1,
+            },
+        ));
+    }
+    lua.gc_collect().unwrap();
+    let before = lua.used_memory();
+
+    for _ in 0..20 {
+        test.update();
+    }
+
+    let after = lua.used_memory();
+    assert_eq!(test.app.world().resource::<LuaSystemProfiles>().gc_collected(), 20);
+    assert!(
+        after < before / 2,
+        "Lua memory should drop once collected entities are freed ({} -> {} bytes)",
+        before,
+        after
+    );
+}
+
+#[test]
+fn test_component_validators_drop_invalid_updates() {
+    let mut test = TestApp::new();
+    let entity = test.app.world_mut().spawn(Transform::default()).id();
+    test.app.world().resource::<LuaComponentRegistry>().register_with_validator("Transform", |value| {
+        let transform = value.downcast_ref::<Transform>().ok_or("not a Transform")?;
+        if transform.translation.x <= 50.0 {
+            Ok(())
+        } else {
+            Err(format!("x = {} is out of bounds", transform.translation.x))
+        }
+    });
+    test.app.world().resource::<LuaScriptContext>().lua.globals().set("target", entity.to_bits()).unwrap();
+
+    test.execute_script(r#"
+        local frame = 0
+        register_system("Validated", function(world)
+            frame = frame + 1
+            local e = world:get_entity(target)
+            if frame == 1 then
+                e:set({ Transform = { translation = { x = 10, y = 0, z = 0 } } })
+            elseif frame == 2 then
+                e:set({ Transform = { translation = { x = 99, y = 0, z = 0 } } })
+            elseif frame == 3 then
+                -- A Lua validator replaces the Rust one
+                world:component_registry():set_validator("Transform", function(t)
+                    return t.translation.y >= 0, "y must not be negative"
+                end)
+                e:set({ Transform = { translation = { x = 99, y = -1, z = 0 } } })
+            elseif frame == 4 then
+                e:set({ Transform = { translation = { x = 99, y = 1, z = 0 } } })
+            end
+            return true
+        end)
+    "#).unwrap();
+
+    let mut observed = Vec::new();
+    for _ in 0..6 {
+        test.update();
+        observed.push(test.app.world().get::<Transform>(entity).unwrap().translation.truncate());
+    }
+
+    assert!(observed.contains(&Vec2::new(10.0, 0.0)), "valid update was dropped: {:?}", observed);
+    // Frame 2 sets x = 99, which the Rust validator rejects; x stays 10 until the Lua validator takes over
+    assert_eq!(observed[1], Vec2::new(10.0, 0.0), "x = 99 passed the Rust validator: {:?}", observed);
+    assert!(!observed.contains(&Vec2::new(99.0, 0.0)), "x = 99 passed the Rust validator: {:?}", observed);
+    assert!(!observed.iter().any(|t| t.y < 0.0), "rejected update was applied: {:?}", observed);
+    assert_eq!(*observed.last().unwrap(), Vec2::new(99.0, 1.0));
+}
+
+#[derive(Component, Reflect, Default, serde::Serialize, serde::Deserialize)]
+#[reflect(Component, Default)]
+struct Stamina {
+    value: f32,
+}
+
+#[test]
+fn test_binary_updates_go_through_validators() {
+    let mut test = TestApp::new();
+    test.app.register_type::<Stamina>();
+    test.app
+        .world_mut()
+        .resource_mut::<SerdeComponentRegistry>()
+        .register_binary::<Stamina>("Stamina");
+    let entity = test.app.world_mut().spawn(Stamina::default()).id();
+    test.app.world().resource::<LuaComponentRegistry>().register_with_validator("Stamina", |value| {
+        let stamina = value.downcast_ref::<Stamina>().ok_or("not a Stamina")?;
+        if stamina.value <= 50.0 {
+            Ok(())
+        } else {
+            Err(format!("value = {} is out of bounds", stamina.value))
+        }
+    });
+    test.app.world().resource::<LuaScriptContext>().lua.globals().set("target", entity.to_bits()).unwrap();
+
+    test.execute_script(r#"
+        local frame = 0
+        register_system("BinaryValidated", function(world)
+            frame = frame + 1
+            if frame == 1 then
+                world:set_component_binary(target, "Stamina", world:encode_component("Stamina", { value = 10 }))
+            elseif frame == 2 then
+                world:set_component_binary(target, "Stamina", world:encode_component("Stamina", { value = 99 }))
+            end
+        end)
+    "#).unwrap();
+
+    let mut observed = Vec::new();
+    for _ in 0..4 {
+        test.update();
+        observed.push(test.app.world().get::<Stamina>(entity).unwrap().value);
+    }
+
+    assert!(observed.contains(&10.0), "valid binary update was dropped: {:?}", observed);
+    assert_eq!(*observed.last().unwrap(), 10.0, "rejected binary update was applied: {:?}", observed);
+}
+
+#[test]
+fn test_get_reads_pending_binary_update() {
+    let mut test = TestApp::new();
+    test.app.register_type::<Stamina>();
+    test.app
+        .world_mut()
+        .resource_mut::<SerdeComponentRegistry>()
+        .register_binary::<Stamina>("Stamina");
+    let entity = test.app.world_mut().spawn(Stamina { value: 5.0 }).id();
+    test.app.world().resource::<LuaScriptContext>().lua.globals().set("target", entity.to_bits()).unwrap();
+
+    // The update is still queued when get() runs, so it must be decoded from bincode
+    test.execute_script(r#"
+        register_system("BinaryReadThrough", function(world)
+            if _G.pending_value ~= nil then return end
+            world:set_component_binary(target, "Stamina", world:encode_component("Stamina", { value = 42 }))
+            for _, e in ipairs(world:query({"Stamina"})) do
+                _G.pending_value = e:get("Stamina").value
+            end
+        end)
+    "#).unwrap();
+
+    for _ in 0..2 {
+        test.update();
+    }
+
+    let lua = &test.app.world().resource::<LuaScriptContext>().lua;
+    assert_eq!(lua.globals().get::<f32>("pending_value").unwrap(), 42.0);
+    assert_eq!(test.app.world().get::<Stamina>(entity).unwrap().value, 42.0);
+}
+
+#[test]
+fn test_call_named_function_uses_returned_module() {
+    use mlua::{FromLuaMulti, IntoLuaMulti};
+
+    let mut test = TestApp::new();
+    let instance_id = test.execute_script(r#"
+        local enemy = { health = 30 }
+        function enemy.on_hit(damage)
+            enemy.health = enemy.health - damage
+            return enemy.health, enemy.health <= 0
+        end
+        return enemy
+    "#).unwrap();
+
+    let lua_ctx = test.app.world().resource::<LuaScriptContext>().clone();
+    let lua = lua_ctx.instance_lua(instance_id);
+    let hit = |damage: i64| {
+        let args = (damage,).into_lua_multi(&lua).unwrap();
+        let (health, dead): (i64, bool) =
+            FromLuaMulti::from_lua_multi(lua_ctx.call_named_function(instance_id, "on_hit", args).unwrap(), &lua)
+                .unwrap();
+        (health, dead)
+    };
+    assert_eq!(hit(10), (20, false));
+    assert_eq!(hit(25), (-5, true));
+
+    let missing = lua_ctx.call_named_function(instance_id, "on_death", mlua::MultiValue::new());
+    assert!(missing.unwrap_err().to_string().contains("no function 'on_death'"));
+
+    let no_module = test.execute_script("local x = 1").unwrap();
+    assert!(lua_ctx.call_named_function(no_module, "on_hit", mlua::MultiValue::new()).is_err());
+}
+
+#[test]
+fn test_subscribe_resource_fires_on_change() {
+    let mut test = TestApp::new();
+    test.execute_script(r#"
+        frame = 0
+        calls = 0
+        register_system("WatchClearColor", function(world)
+            frame = frame + 1
+            if frame == 1 then
+                handle = world:subscribe_resource("ClearColor", function(color)
+                    calls = calls + 1
+                    got_table = type(color) == "table"
+                end)
+            elseif frame == 2 then
+                world:set_resource("ClearColor", { _0 = { r = 0.5, g = 0.0, b = 0.0, a = 1.0 } })
+            elseif frame == 4 then
+                unsubscribed = world:unsubscribe_resource("ClearColor", handle)
+                world:set_resource("ClearColor", { _0 = { r = 0.25, g = 0.0, b = 0.0, a = 1.0 } })
+            end
+        end)
+    "#).unwrap();
+    for _ in 0..6 {
+        test.update();
+    }
+
+    assert!(test.lua_global_bool("got_table"));
+    assert!(test.lua_global_bool("unsubscribed"));
+    let lua_ctx = test.app.world().resource::<LuaScriptContext>().clone();
+    assert_eq!(lua_ctx.lua.globals().get::<u32>("calls").unwrap(), 1);
+    assert!(test.app.world().resource::<ResourceWatcherRegistry>().is_empty());
+}
+
+#[test]
+fn test_resource_subscribers_track_changes_separately() {
+    let mut test = TestApp::new();
+    let subscriber = test.execute_script(r#"
+        frame = 0
+        first_calls = 0
+        late_calls = 0
+        register_system("WatchClearColor", function(world)
+            frame = frame + 1
+            if frame == 1 then
+                world:subscribe_resource("ClearColor", function()
+                    first_calls = first_calls + 1
+                    callback_instance = __INSTANCE_ID__
+                end)
+            elseif frame == 3 then
+                -- Subscribed after this frame's change was made: only later changes count
+                world:subscribe_resource("ClearColor", function() late_calls = late_calls + 1 end)
+            end
+        end)
+    "#).unwrap();
+    // Runs after the subscriber's system, so its instance is current when callbacks fire
+    let other = test.execute_script(r#"
+        register_system("Other", function(world) end)
+    "#).unwrap();
+    test.update();
+    test.update();
+
+    test.app.world_mut().insert_resource(ClearColor(Color::BLACK));
+    test.update();
+
+    let lua = test.app.world().resource::<LuaScriptContext>().lua.clone();
+    assert_eq!(lua.globals().get::<u32>("first_calls").unwrap(), 1);
+    assert_eq!(lua.globals().get::<u32>("late_calls").unwrap(), 0);
+    assert_eq!(lua.globals().get::<u64>("callback_instance").unwrap(), subscriber);
+    assert_eq!(lua.globals().get::<u64>("__INSTANCE_ID__").unwrap(), other, "callbacks should restore __INSTANCE_ID__");
+}
+
+#[test]
+fn test_timer_callbacks_restore_instance_id() {
+    let mut test = TestApp::new();
+    let owner = test.execute_script(r#"
+        local started = false
+        register_system("StartTimer", function(world)
+            if started then return end
+            started = true
+            world:create_timer(0, function()
+                timer_instance = __INSTANCE_ID__
+                timer_phase = __SPAWN_PHASE__
+            end, false)
+        end)
+    "#).unwrap();
+    // Runs after the owner's system, so its instance is current when the timer fires
+    let other = test.execute_script(r#"
+        register_system("Other", function(world) end)
+    "#).unwrap();
+    test.update();
+    test.update();
+
+    let lua = test.app.world().resource::<LuaScriptContext>().lua.clone();
+    assert_eq!(lua.globals().get::<u64>("timer_instance").unwrap(), owner);
+    assert_eq!(lua.globals().get::<String>("timer_phase").unwrap(), "runtime");
+    assert_eq!(lua.globals().get::<u64>("__INSTANCE_ID__").unwrap(), other, "timers should restore __INSTANCE_ID__");
+}
+
+#[cfg(not(feature = "physics"))]
+#[test]
+fn test_physics_debug_draw_requires_physics_feature() {
+    let mut test = TestApp::new();
+    test.execute_script(r#"
+        register_system("PhysicsDebug", function(world)
+            local ok, err = pcall(function() world:physics_debug_draw(true) end)
+            feature_error = not ok and tostring(err):find("`physics` feature") ~= nil
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+
+    assert!(test.lua_global_bool("feature_error"));
+}
+
+#[derive(Component)]
+struct Health {
+    current: f32,
+}
+
+#[test]
+fn test_entity_call_method_uses_registered_component_method() {
+    let mut test = TestApp::new();
+    let enemy = test.app.world_mut().spawn((Transform::default(), Health { current: 30.0 })).id();
+    test.app.world().resource::<LuaComponentRegistry>().register_component_method::<Health, _, _, _>(
+        "Health",
+        "take_damage",
+        |health, _lua, amount: f32| {
+            health.current -= amount;
+            Ok(health.current)
+        },
+    );
+
+    test.execute_script(r#"
+        register_system("Damage", function(world)
+            for _, enemy in ipairs(world:query({"Transform"})) do
+                _G.remaining = enemy:call_method("Health", "take_damage", 10)
+                _G.unknown_errors = not pcall(function() enemy:call_method("Health", "heal", 1) end)
+            end
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+
+    assert_eq!(test.app.world().get::<Health>(enemy).unwrap().current, 20.0);
+    let remaining: f32 = test.app.world().resource::<LuaScriptContext>().lua.globals().get("remaining").unwrap();
+    assert_eq!(remaining, 20.0);
+    assert!(test.lua_global_bool("unknown_errors"));
+}
+
+#[test]
+fn test_coroutines_resume_across_frames_with_a_live_world() {
+    let mut test = TestApp::new();
+
+    test.execute_script(r#"
+        spawn({ Marker = { n = 1 } })
+    "#).unwrap();
+    test.update();
+
+    test.execute_script(r#"
+        register_system("StartCoroutines", function(world)
+            world:start_coroutine(function()
+                local _, world = coroutine.yield("next_frame")
+                _G.markers_after_next_frame = #world:query({"Marker"})
+                local _, world = coroutine.yield("frames", 2)
+                _G.markers_after_frames = #world:query({"Marker"})
+                coroutine.yield("until", function(world) return #world:query({"Marker"}) > 0 end)
+                _G.coroutine_done = true
+            end)
+            world:start_coroutine(function()
+                coroutine.yield("until", function() error("broken predicate") end)
+                _G.broken_resumed = true
+            end)
+            return true
+        end)
+    "#).unwrap();
+
+    for _ in 0..6 {
+        test.update();
+    }
+
+    assert!(test.lua_global_bool("coroutine_done"), "coroutine did not run to completion");
+    assert!(!test.lua_global_bool("broken_resumed"), "coroutine with a failing predicate resumed");
+    let globals = test.app.world().resource::<LuaScriptContext>().lua.globals();
+    assert_eq!(globals.get::<u32>("markers_after_next_frame").unwrap(), 1);
+    assert_eq!(globals.get::<u32>("markers_after_frames").unwrap(), 1);
+    assert!(test.app.world().resource::<LuaCoroutineScheduler>().is_empty());
+}
+
+#[test]
+fn test_sandboxed_lua_stdlib_file_functions_stay_in_root() {
+    let mut test = TestApp::new();
+    let sandbox_root = test.temp_dir.path().join("assets");
+    fs::write(test.temp_dir.path().join("outside.lua"), "_G.outside_ran = true").unwrap();
+    let lua_ctx = test.app.world().get_resource::<LuaScriptContext>().unwrap().clone();
+    lua_ctx.lua.set_app_data(OsUtilities::sandboxed(&sandbox_root));
+
+    test.execute_script(r#"
+        _G.open_rejected = not pcall(io.open, "../escaped.txt", "w")
+        _G.dofile_rejected = not pcall(dofile, "../outside.lua")
+        _G.loadfile_rejected = not pcall(loadfile, "../outside.lua")
+        _G.remove_rejected = not pcall(os.remove, "../outside.lua")
+        _G.execute_rejected = not pcall(os.execute, "true")
+
+        local file = io.open("notes.txt", "w")
+        file:write("inside")
+        file:close()
+        _G.inside_ok = read_file_bytes("notes.txt") == "inside"
+    "#).unwrap();
+
+    assert!(test.lua_global_bool("open_rejected"), "io.open should not escape the sandbox");
+    assert!(test.lua_global_bool("dofile_rejected"), "dofile should not escape the sandbox");
+    assert!(test.lua_global_bool("loadfile_rejected"), "loadfile should not escape the sandbox");
+    assert!(test.lua_global_bool("remove_rejected"), "os.remove should not escape the sandbox");
+    assert!(test.lua_global_bool("execute_rejected"), "os.execute is unavailable when sandboxed");
+    assert!(!test.lua_global_bool("outside_ran"));
+    assert!(!test.temp_dir.path().join("escaped.txt").exists());
+    assert!(test.temp_dir.path().join("outside.lua").exists());
+    assert!(test.lua_global_bool("inside_ok"));
+    assert!(sandbox_root.join("notes.txt").exists());
+}
+
+#[test]
+fn test_lua_defined_components_survive_persist_and_load() {
+    let mut test = TestApp::new();
+    let save_dir = test.temp_dir.path().join("saves");
+    test.app.insert_resource(SaveConfig { save_dir: save_dir.clone() });
+
+    test.execute_script(r#"
+        local frame = 0
+        register_system("PersistHealth", function(world)
+            frame = frame + 1
+            if frame == 1 then
+                world:define_component("Health", { current = "number", max = { type = "number", default = 100 } })
+                spawn({ Health = { current = 42 } })
+            elseif frame == 2 then
+                for _, e in ipairs(world:query({"Health"})) do
+                    world:persist_entity(e:id(), "hero.scn.ron")
+                    e:despawn()
+                end
+            elseif frame == 3 then
+                world:load_entity("hero.scn.ron")
+            else
+                for _, e in ipairs(world:query({"Health"})) do
+                    local health = e:get("Health")
+                    _G.health_loaded = health.current == 42 and health.max == 100
+                end
+            end
+        end)
+    "#).unwrap();
+
+    for _ in 0..6 {
+        test.update();
+    }
+
+    let saved = fs::read_to_string(save_dir.join("hero.scn.ron")).expect("entity was not saved");
+    assert!(saved.contains("LuaDefinedComponents"), "Saved scene is missing Lua components: {}", saved);
+    assert!(test.lua_global_bool("health_loaded"), "Lua-defined component was not restored by load_entity");
+}
+
+#[test]
+fn test_load_entity_replace_reuses_the_entity() {
+    let mut test = TestApp::new();
+    let save_dir = test.temp_dir.path().join("saves");
+    test.app.insert_resource(SaveConfig { save_dir });
+
+    test.execute_script(r#"
+        local frame = 0
+        local hero
+        register_system("ReplaceHero", function(world)
+            frame = frame + 1
+            if frame == 1 then
+                world:define_component("Health", { current = "number" })
+                spawn({ Health = { current = 42 }, Transform = { translation = { x = 1, y = 0, z = 0 } }, Note = { text = "kept" } })
+            elseif frame == 2 then
+                hero = world:query({"Health"})[1]:id()
+                world:persist_entity(hero, "hero.scn.ron")
+                for _, e in ipairs(world:query({"Health"})) do
+                    e:set({ Health = { current = 1 }, Transform = { translation = { x = 9, y = 0, z = 0 } } })
+                end
+            elseif frame == 3 then
+                world:load_entity("hero.scn.ron", hero)
+            elseif frame == 5 then
+                local entities = world:query({"Health", "Transform"})
+                local e = entities[1]
+                _G.replace_count = #entities
+                _G.replace_same_id = e:id() == hero
+                _G.replace_health = e:get("Health").current
+                _G.replace_x = e:get("Transform").translation.x
+                local noted = world:query({"Note"})
+                _G.replace_note_kept = #noted == 1 and noted[1]:id() == hero
+            end
+        end)
+    "#).unwrap();
+
+    for _ in 0..6 {
+        test.update();
+    }
+
+    let lua = test.app.world().resource::<LuaScriptContext>().lua.clone();
+    assert_eq!(lua.globals().get::<i64>("replace_count").unwrap(), 1, "load_entity with replace should not add an entity");
+    assert!(test.lua_global_bool("replace_same_id"), "load_entity with replace should keep the entity id");
+    assert_eq!(lua.globals().get::<f64>("replace_health").unwrap(), 42.0);
+    assert_eq!(lua.globals().get::<f64>("replace_x").unwrap(), 1.0);
+    assert!(test.lua_global_bool("replace_note_kept"), "Schemaless Lua components should survive the replace");
+}
+
+#[derive(Asset, Reflect, Default, Clone)]
+#[reflect(Asset, Default)]
+struct Palette {
+    name: String,
+    size: u32,
+}
+
+/// Same shape as a generated dispatch_create_asset arm: typed SystemState<ResMut<Assets<T>>>
+fn dispatch_create_palette(
+    lua: &mlua::Lua,
+    world: &mut World,
+    type_name: &str,
+    props: mlua::Table,
+) -> mlua::Result<mlua::Value> {
+    if type_name != "Palette" {
+        return Err(mlua::Error::RuntimeError(format!(
+            "create_asset: '{}' is not a registered asset type",
+            type_name
+        )));
+    }
+    let asset = asset_from_lua_table::<Palette>(lua, world, props)?;
+    let mut system_state = bevy::ecs::system::SystemState::<ResMut<Assets<Palette>>>::new(world);
+    let handle = system_state.get_mut(world).add(asset);
+    let asset_registry = world.resource::<AssetRegistry>().clone();
+    asset_registry
+        .handle_to_lua(lua, handle.untyped(), "Palette")
+        .map(mlua::Value::Table)
+}
+
+#[test]
+fn test_create_asset_adds_to_assets_and_returns_handle() {
+    let mut test = TestApp::new();
+    test.app.init_asset::<Palette>().register_asset_reflect::<Palette>();
+    set_asset_create_dispatcher(dispatch_create_palette);
+
+    test.execute_script(r#"
+        register_system("CreatePalette", function(world)
+            local palette = world:create_asset("Palette", { name = "warm", size = 3 })
+            _G.palette_id = palette.id
+            _G.palette_type = palette.type
+            _G.palette_read_back = world:get_asset(palette).name == "warm"
+            _G.unknown_type_errors = not pcall(function() world:create_asset("Nope", {}) end)
+            return true
+        end)
+    "#).unwrap();
+    test.update();
+
+    let assets = test.app.world().resource::<Assets<Palette>>();
+    assert_eq!(assets.len(), 1);
+    let (id, palette) = assets.iter().next().unwrap();
+    assert_eq!(palette.name, "warm");
+    assert_eq!(palette.size, 3);
+
+    let globals = test.app.world().resource::<LuaScriptContext>().lua.globals();
+    let AssetId::Index { index, .. } = id else {
+        panic!("created asset has no index: {:?}", id);
+    };
+    assert_eq!(globals.get::<i64>("palette_id").unwrap() as u64, index.to_bits());
+    assert_eq!(globals.get::<String>("palette_type").unwrap(), "Palette");
+    assert!(test.lua_global_bool("palette_read_back"));
+    assert!(test.lua_global_bool("unknown_type_errors"));
+}
+
+#[derive(Asset, Reflect, Default)]
+#[reflect(Asset)]
+struct Blob(Vec<u8>);
+
+#[derive(Default)]
+struct BlobLoader;
+
+impl bevy::asset::AssetLoader for BlobLoader {
+    type Asset = Blob;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn bevy::asset::io::Reader,
+        _settings: &(),
+        _load_context: &mut bevy::asset::LoadContext<'_>,
+    ) -> Result<Blob, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(Blob(bytes))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["blob"]
+    }
+}
+
+#[test]
+fn test_load_asset_from_bytes_loads_async_and_frees_the_buffer() {
+    let mut test = TestApp::with_asset_sources(|app| {
+        app.add_plugins(LuaBytesAssetSourcePlugin);
+    });
+    test.app
+        .init_asset::<Blob>()
+        .register_asset_reflect::<Blob>()
+        .register_asset_loader(BlobLoader);
+
+    test.execute_script(r#"
+        register_system("LoadBlob", function(world)
+            if not _G.blob then
+                _G.blob = world:load_asset_from_bytes("Blob", "abc", "blob")
+                _G.blob_path = _G.blob.path
+                _G.loading_reads_nil = world:get_asset(_G.blob) == nil
+            elseif _G.blob.id then
+                _G.blob_id = _G.blob.id
+                _G.blob_type = _G.blob.type
+                return true
+            end
+        end)
+    "#).unwrap();
+
+    let lua = test.app.world().resource::<LuaScriptContext>().lua.clone();
+    for _ in 0..200 {
+        test.update();
+        if lua.globals().get::<Option<i64>>("blob_id").unwrap().is_some() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+
+    let blob_id = lua.globals().get::<Option<i64>>("blob_id").unwrap().expect("blob never finished loading");
+    assert_eq!(lua.globals().get::<String>("blob_type").unwrap(), "Blob");
+    assert!(test.lua_global_bool("loading_reads_nil"));
+
+    // The handle table carries the real asset index, and the asset stays loaded
+    for _ in 0..10 {
+        if !test.app.world().resource::<Assets<Blob>>().is_empty() {
+            break;
+        }
+        test.update();
+    }
+    let assets = test.app.world().resource::<Assets<Blob>>();
+    let (id, blob) = assets.iter().next().expect("Blob was not added to Assets<Blob>");
+    assert_eq!(blob.0, b"abc".to_vec());
+    let AssetId::Index { index, .. } = id else {
+        panic!("loaded asset has no index: {:?}", id);
+    };
+    assert_eq!(blob_id as u64, index.to_bits());
+
+    let path: String = lua.globals().get("blob_path").unwrap();
+    assert!(!test.app.world().resource::<LuaBytesAssets>().contains(&path), "byte buffer was not freed");
+}
+
+#[test]
+fn test_sibling_field_tweens_run_together() {
+    let mut test = TestApp::new();
+    let mover = test.app.world_mut().spawn(Transform::default()).id();
+    test.app.world().resource::<LuaScriptContext>().lua.globals().set("mover", mover.to_bits()).unwrap();
+
+    // Zero-duration tweens land on their end value in the first frame they run
+    test.execute_script(r#"
+        register_system("Tweens", function(world)
+            if not _G.tweened then
+                world:tween(mover, "Transform", "translation.x", 10.0, 0.0)
+                world:tween(mover, "Transform", "translation.y", 20.0, 0.0)
+                _G.tweened = true
+            end
+            return true
+        end)
+    "#).unwrap();
+
+    for _ in 0..3 {
+        test.update();
+    }
+
+    assert!(test.lua_global_bool("tweened"), "Tween system did not run");
+    let translation = test.app.world().get::<Transform>(mover).unwrap().translation;
+    assert_eq!(translation, Vec3::new(10.0, 20.0, 0.0));
+}